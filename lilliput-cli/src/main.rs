@@ -0,0 +1,232 @@
+//! A command-line tool for inspecting and converting lilliput-encoded data.
+
+use std::{
+    fs,
+    io::{self, Read as _, Write},
+    process::ExitCode,
+};
+
+use lilliput_core::{
+    config::DecoderConfig, decoder::Decoder, error::Error, io::SliceReader, text,
+    validation::DEFAULT_MAX_DEPTH, value::Value,
+};
+use lilliput_serde::{
+    config::{SerializerConfig, UnitRepr},
+    ser::to_writer_with_config,
+};
+
+const USAGE: &str = "\
+Usage: lilliput <COMMAND> [FILE]
+
+Commands:
+  dump [--json] [FILE]  Render encoded values as lilliput text (or as JSON)
+  encode [FILE]         Encode JSON values as lilliput, writing them to stdout
+  validate [FILE]       Check that the input consists of well-formed values only
+
+Inputs are read from stdin if no FILE is given.";
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// A subcommand, along with its options.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum Command {
+    Dump { json: bool },
+    Encode,
+    Validate,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> CliResult<()> {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let (command, path) = parse_args(args)?;
+    let input = read_input(path)?;
+
+    let mut stdout = io::stdout().lock();
+
+    match command {
+        Command::Dump { json } => dump(&input, json, &mut stdout)?,
+        Command::Encode => encode(&input, &mut stdout)?,
+        Command::Validate => {
+            let count = validate(&input)?;
+            writeln!(stdout, "ok: {count} value(s), {} bytes", input.len())?;
+        }
+    }
+
+    Ok(stdout.flush()?)
+}
+
+fn parse_args(args: &[String]) -> CliResult<(Command, Option<&str>)> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(USAGE.into());
+    };
+
+    let mut json = false;
+    let mut path = None;
+
+    for arg in rest {
+        match arg.as_str() {
+            "--json" if command == "dump" => json = true,
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown option `{flag}`\n\n{USAGE}").into());
+            }
+            _ if path.is_some() => return Err(format!("unexpected argument `{arg}`").into()),
+            _ => path = Some(arg.as_str()),
+        }
+    }
+
+    let command = match command.as_str() {
+        "dump" => Command::Dump { json },
+        "encode" => Command::Encode,
+        "validate" => Command::Validate,
+        other => return Err(format!("unknown command `{other}`\n\n{USAGE}").into()),
+    };
+
+    Ok((command, path.filter(|&path| path != "-")))
+}
+
+fn read_input(path: Option<&str>) -> CliResult<Vec<u8>> {
+    match path {
+        Some(path) => fs::read(path).map_err(|err| format!("{path}: {err}").into()),
+        None => {
+            let mut input = Vec::new();
+            io::stdin().lock().read_to_end(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+/// Decodes all of the consecutive values in `input`.
+///
+/// Values nested deeper than `DEFAULT_MAX_DEPTH` get rejected,
+/// rather than overflowing the stack on malicious input.
+fn decode_all(input: &[u8]) -> Result<Vec<Value>, Error> {
+    let config = DecoderConfig::default().with_max_depth(Some(DEFAULT_MAX_DEPTH));
+    let mut decoder = Decoder::new(SliceReader::new(input), config);
+    let mut values = Vec::new();
+
+    while decoder.pos() < input.len() {
+        values.push(decoder.decode_value()?);
+    }
+
+    Ok(values)
+}
+
+/// Renders the values in `input`, one per line.
+fn dump<W>(input: &[u8], json: bool, output: &mut W) -> CliResult<()>
+where
+    W: Write,
+{
+    for value in decode_all(input)? {
+        if json {
+            serde_json::to_writer(&mut *output, &value)?;
+            writeln!(output)?;
+        } else {
+            writeln!(output, "{}", text::to_string(&value))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Encodes the (whitespace-separated) JSON values in `input`.
+fn encode<W>(input: &[u8], output: &mut W) -> CliResult<()>
+where
+    W: Write,
+{
+    // JSON has no concept of unit, so its nulls are meant as nulls:
+    let config = SerializerConfig::default().with_unit_repr(UnitRepr::Null);
+
+    let values = serde_json::Deserializer::from_slice(input).into_iter::<serde_json::Value>();
+
+    for value in values {
+        to_writer_with_config(&mut *output, &value?, config.clone())?;
+    }
+
+    Ok(())
+}
+
+/// Validates the values in `input`, returning their count.
+fn validate(input: &[u8]) -> Result<usize, Error> {
+    decode_all(input).map(|values| values.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let args_ = args(&["dump", "--json", "in.bin"]);
+        let (command, path) = parse_args(&args_).unwrap();
+        assert_eq!(command, Command::Dump { json: true });
+        assert_eq!(path, Some("in.bin"));
+
+        let args_ = args(&["validate", "-"]);
+        assert_eq!(parse_args(&args_).unwrap(), (Command::Validate, None));
+
+        assert!(parse_args(&args(&["encode", "--json"])).is_err());
+        assert!(parse_args(&args(&["frobnicate"])).is_err());
+        assert!(parse_args(&args(&[])).is_err());
+    }
+
+    #[test]
+    fn encode_and_dump() {
+        let mut encoded = Vec::new();
+        encode(br#"{"name": "Bob", "tags": [1, -2]} null"#, &mut encoded).unwrap();
+        assert_eq!(validate(&encoded).unwrap(), 2);
+
+        let mut dumped = Vec::new();
+        dump(&encoded, false, &mut dumped).unwrap();
+        assert_eq!(
+            String::from_utf8(dumped).unwrap(),
+            "{\"name\": \"Bob\", \"tags\": [1_u8, -2_i8]}\nnull\n"
+        );
+
+        let mut dumped = Vec::new();
+        dump(&encoded, true, &mut dumped).unwrap();
+        assert_eq!(
+            String::from_utf8(dumped).unwrap(),
+            "{\"name\":\"Bob\",\"tags\":[1,-2]}\nnull\n"
+        );
+    }
+
+    #[test]
+    fn validate_truncated() {
+        let mut encoded = Vec::new();
+        encode(br#""lilliput""#, &mut encoded).unwrap();
+
+        assert!(validate(&encoded[..encoded.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn validate_deeply_nested() {
+        // Deeply nested single-item seqs, terminated by an int:
+        let mut encoded = vec![0b00110001; 1024 * 1024];
+        encoded.push(0xc1);
+
+        let err = validate(&encoded).unwrap_err();
+        assert_eq!(
+            err.code(),
+            lilliput_core::error::ErrorCode::DepthLimitExceeded
+        );
+    }
+}
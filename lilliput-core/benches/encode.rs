@@ -0,0 +1,73 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use lilliput_core::{
+    config::{EncoderConfig, PackingMode},
+    encoder::Encoder,
+    io::VecWriter,
+};
+
+const CRITERION_SIGNIFICANCE_LEVEL: f64 = 0.1;
+const CRITERION_SAMPLE_SIZE: usize = 500;
+
+const SAMPLES: usize = 65_536;
+// Small integers take up at most 2 bytes on the wire:
+const CAPACITY: usize = SAMPLES * 2;
+
+fn bench_encode<T>(
+    c: &mut Criterion,
+    label: &str,
+    samples: &[T],
+    config: EncoderConfig,
+    encode: impl Fn(&mut Encoder<VecWriter<'_>>, T),
+) where
+    T: Copy,
+{
+    let mut g = c.benchmark_group(format!("{label} ({:?})", config.ints.packing));
+
+    g.significance_level(CRITERION_SIGNIFICANCE_LEVEL);
+    g.sample_size(CRITERION_SAMPLE_SIZE);
+
+    let mut encoded = Vec::with_capacity(CAPACITY);
+
+    g.bench_function("encode", |b| {
+        b.iter(|| {
+            encoded.clear();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config.clone());
+            for sample in samples {
+                encode(&mut encoder, black_box(*sample));
+            }
+        })
+    });
+
+    g.finish();
+}
+
+fn bench_small_ints(c: &mut Criterion, config: EncoderConfig) {
+    // Covers both compact values (<= 31) and values of width 1:
+    let u8_samples: Vec<u8> = (0..SAMPLES).map(|i| i as u8).collect();
+    let u16_samples: Vec<u16> = (0..SAMPLES).map(|i| (i % 256) as u16).collect();
+    let i8_samples: Vec<i8> = (0..SAMPLES).map(|i| i as u8 as i8).collect();
+
+    bench_encode(c, "u8", &u8_samples, config.clone(), |encoder, value| {
+        encoder.encode_u8(value).unwrap()
+    });
+    bench_encode(c, "u16", &u16_samples, config.clone(), |encoder, value| {
+        encoder.encode_u16(value).unwrap()
+    });
+    bench_encode(c, "i8", &i8_samples, config, |encoder, value| {
+        encoder.encode_i8(value).unwrap()
+    });
+}
+
+fn benchmark_small_ints(c: &mut Criterion) {
+    for packing in [PackingMode::Optimal, PackingMode::Native] {
+        bench_small_ints(c, EncoderConfig::default().with_packing(packing));
+    }
+}
+
+criterion_group!(small_ints, benchmark_small_ints);
+
+criterion_main!(small_ints);
@@ -209,7 +209,32 @@ fn bench_bool(c: &mut Criterion, config: EncoderConfig) {
     g.sample_size(CRITERION_SAMPLE_SIZE);
 
     let samples: Vec<Value> = samples_iter(SAMPLES).collect();
-    bench_roundtrip_with_samples(&mut g, None, &samples, config);
+    bench_roundtrip_with_samples(&mut g, None, &samples, config.clone());
+
+    // Bools get decoded from their header byte alone:
+    let encoded: Vec<u8> = {
+        let mut buf = Vec::with_capacity(SAMPLES);
+
+        let writer = VecWriter::new(&mut buf);
+        let mut encoder = Encoder::new(writer, config);
+
+        for value in sampling_values_iter::<bool>(SAMPLES) {
+            encoder.encode_bool(value).unwrap();
+        }
+
+        buf
+    };
+
+    g.bench_function("decode_bool", |b| {
+        b.iter(|| {
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+
+            for _ in 0..SAMPLES {
+                let _ = black_box(decoder.decode_bool().unwrap());
+            }
+        })
+    });
 
     g.finish();
 }
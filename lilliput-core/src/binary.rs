@@ -1,3 +1,5 @@
+use alloc::{format, string::String, vec::Vec};
+
 mod byte;
 mod byte_slice;
 
@@ -0,0 +1,241 @@
+//! Rendering values as Rust code, for embedding captured samples as test fixtures.
+//!
+//! The generated code is an expression constructing the value,
+//! which expects the contents of `lilliput_core::value` to be in scope.
+//! Like [lilliput text](crate::text) it preserves the exact type
+//! and width of every value.
+//!
+//! ```
+//! use lilliput_core::{codegen, value::*};
+//!
+//! let value = Value::Seq(SeqValue::from(vec![
+//!     Value::Int(IntValue::from(42_u8)),
+//!     Value::Null(NullValue),
+//! ]));
+//!
+//! assert_eq!(
+//!     codegen::to_rust_code(&value),
+//!     "\
+//! Value::Seq(SeqValue::from(vec![
+//!     Value::Int(IntValue::from(42_u8)),
+//!     Value::Null(NullValue),
+//! ]))"
+//! );
+//! ```
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{
+    text,
+    value::{FloatValue, Value},
+};
+
+const INDENT: &str = "    ";
+
+/// Renders `value` as a Rust expression constructing it.
+pub fn to_rust_code(value: &Value) -> String {
+    let mut code = String::new();
+    write_rust_code(value, &mut code).expect("writing to a string never fails");
+    code
+}
+
+/// Writes `value` as a Rust expression constructing it to `writer`.
+pub fn write_rust_code<W>(value: &Value, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    write_value(value, 0, writer)
+}
+
+fn write_value<W>(value: &Value, depth: usize, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    match value {
+        Value::Int(value) => {
+            writer.write_str("Value::Int(IntValue::from(")?;
+            text::write_int(value, writer)?;
+            writer.write_str("))")
+        }
+        Value::String(value) => {
+            writer.write_str("Value::String(StringValue::from(")?;
+            text::write_str(value.as_str(), writer)?;
+            writer.write_str(".to_owned()))")
+        }
+        Value::Seq(value) => {
+            writer.write_str("Value::Seq(SeqValue::from(vec![")?;
+            write_items(value.as_slice().iter(), depth, writer, |item, writer| {
+                write_value(item, depth + 1, writer)
+            })?;
+            writer.write_str("]))")
+        }
+        Value::Map(value) => {
+            writer.write_str("Value::Map(MapValue::from(Map::from_iter([")?;
+            write_items(
+                value.as_map_ref().iter(),
+                depth,
+                writer,
+                |(key, value), writer| {
+                    writer.write_char('(')?;
+                    write_value(key, depth + 1, writer)?;
+                    writer.write_str(", ")?;
+                    write_value(value, depth + 1, writer)?;
+                    writer.write_char(')')
+                },
+            )?;
+            writer.write_str("])))")
+        }
+        Value::Float(value) => {
+            writer.write_str("Value::Float(FloatValue::from(")?;
+            write_float(value, writer)?;
+            writer.write_str("))")
+        }
+        Value::Bytes(value) => {
+            writer.write_str("Value::Bytes(BytesValue::from(")?;
+            text::write_bytes(value.as_slice(), writer)?;
+            writer.write_str(".to_vec()))")
+        }
+        Value::Bool(value) => write!(writer, "Value::Bool(BoolValue::from({}))", value.0),
+        Value::Unit(_) => writer.write_str("Value::Unit(UnitValue)"),
+        Value::Null(_) => writer.write_str("Value::Null(NullValue)"),
+        Value::Ext(value) => {
+            write!(writer, "Value::Ext(ExtValue::new({}, ", value.tag)?;
+            text::write_bytes(&value.payload, writer)?;
+            writer.write_str(".to_vec()))")
+        }
+        Value::Unknown(value) => {
+            write!(
+                writer,
+                "Value::Unknown(UnknownValue::new({:#04x}, ",
+                value.header
+            )?;
+            text::write_bytes(&value.body, writer)?;
+            writer.write_str(".to_vec()))")
+        }
+    }
+}
+
+/// Writes one item per line, indented by one level deeper than `depth`,
+/// or nothing at all for empty containers.
+fn write_items<I, W, F>(items: I, depth: usize, writer: &mut W, mut write_item: F) -> fmt::Result
+where
+    I: ExactSizeIterator,
+    W: fmt::Write,
+    F: FnMut(I::Item, &mut W) -> fmt::Result,
+{
+    if items.len() == 0 {
+        return Ok(());
+    }
+
+    for item in items {
+        writer.write_char('\n')?;
+        write_indent(depth + 1, writer)?;
+        write_item(item, writer)?;
+        writer.write_char(',')?;
+    }
+
+    writer.write_char('\n')?;
+    write_indent(depth, writer)
+}
+
+fn write_indent<W>(depth: usize, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    for _ in 0..depth {
+        writer.write_str(INDENT)?;
+    }
+    Ok(())
+}
+
+fn write_float<W>(value: &FloatValue, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    // Non-finite floats have no literals, so they get rendered
+    // as their raw bits, preserving NaN payloads:
+    match *value {
+        FloatValue::F32(value) if value.is_finite() => write!(writer, "{value:?}_f32"),
+        FloatValue::F64(value) if value.is_finite() => write!(writer, "{value:?}_f64"),
+        FloatValue::F32(value) => write!(writer, "f32::from_bits({:#010x})", value.to_bits()),
+        FloatValue::F64(value) => write!(writer, "f64::from_bits({:#018x})", value.to_bits()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::*;
+
+    use super::*;
+
+    #[test]
+    fn scalars() {
+        let cases = [
+            (
+                Value::Int(IntValue::from(-128_i8)),
+                "Value::Int(IntValue::from(-128_i8))",
+            ),
+            (
+                Value::Int(IntValue::from(u64::MAX)),
+                "Value::Int(IntValue::from(18446744073709551615_u64))",
+            ),
+            (
+                Value::Float(FloatValue::from(-1e100_f64)),
+                "Value::Float(FloatValue::from(-1e100_f64))",
+            ),
+            (
+                Value::Float(FloatValue::from(f32::from_bits(0x7fc00001))),
+                "Value::Float(FloatValue::from(f32::from_bits(0x7fc00001)))",
+            ),
+            (
+                Value::String(StringValue::from("\"quoted\"\n\u{0}ñ".to_owned())),
+                r#"Value::String(StringValue::from("\"quoted\"\n\u{0}ñ".to_owned()))"#,
+            ),
+            (
+                Value::Bytes(BytesValue::from(b"lilliput\x00\xff".to_vec())),
+                r#"Value::Bytes(BytesValue::from(b"lilliput\x00\xff".to_vec()))"#,
+            ),
+            (
+                Value::Bool(BoolValue::from(true)),
+                "Value::Bool(BoolValue::from(true))",
+            ),
+            (Value::Unit(UnitValue), "Value::Unit(UnitValue)"),
+            (Value::Null(NullValue), "Value::Null(NullValue)"),
+        ];
+
+        for (value, code) in cases {
+            assert_eq!(to_rust_code(&value), code);
+        }
+    }
+
+    #[test]
+    fn nested() {
+        let value = Value::Map(MapValue::from(Map::from_iter([
+            (
+                Value::Int(IntValue::from(1_u16)),
+                Value::Seq(SeqValue::from(vec![
+                    Value::Float(FloatValue::from(1.5_f32)),
+                    Value::Map(MapValue::from(Map::from_iter([]))),
+                ])),
+            ),
+            (
+                Value::String(StringValue::from("empty".to_owned())),
+                Value::Seq(SeqValue::from(vec![])),
+            ),
+        ])));
+
+        let expected = "\
+Value::Map(MapValue::from(Map::from_iter([
+    (Value::Int(IntValue::from(1_u16)), Value::Seq(SeqValue::from(vec![
+        Value::Float(FloatValue::from(1.5_f32)),
+        Value::Map(MapValue::from(Map::from_iter([]))),
+    ]))),
+    (Value::String(StringValue::from(\"empty\".to_owned())), Value::Seq(SeqValue::from(vec![]))),
+])))";
+
+        assert_eq!(to_rust_code(&value), expected);
+    }
+}
@@ -0,0 +1,319 @@
+//! Compression of encoded values, via zstd or lz4.
+//!
+//! Compressed streams start with a small header, consisting of
+//! `COMPRESSION_MAGIC`, followed by the id of the codec used,
+//! which allows for a reader to pick the matching decompressor.
+//!
+//! ```
+//! use lilliput_core::{
+//!     compression::{Codec, CompressedReader, CompressedWriter},
+//!     decoder::Decoder,
+//!     encoder::Encoder,
+//!     io::{StdIoReader, StdIoWriter},
+//!     value::{StringValue, Value},
+//! };
+//!
+//! let value = Value::String(StringValue::from("lilliput".repeat(100)));
+//!
+//! let writer = CompressedWriter::new(Vec::new(), Codec::Zstd).unwrap();
+//! let mut encoder = Encoder::from_writer(StdIoWriter::new(writer));
+//! encoder.encode_value(&value).unwrap();
+//! let compressed = encoder.into_writer().into_writer().finish().unwrap();
+//! assert!(compressed.len() < 100);
+//!
+//! let reader = CompressedReader::new(compressed.as_slice()).unwrap();
+//! assert_eq!(reader.codec(), Codec::Zstd);
+//!
+//! let mut decoder = Decoder::from_reader(StdIoReader::new(reader));
+//! assert_eq!(decoder.decode_value().unwrap(), value);
+//! ```
+//!
+//! *This module is only available if lilliput_core is built with
+//! the `"zstd"` and/or `"lz4"` features, enabling the respective codecs.*
+
+use std::{
+    format,
+    io::{self, Read, Write},
+    string::ToString,
+};
+
+use crate::error::{Error, Result};
+
+/// The magic bytes compressed streams start with.
+pub const COMPRESSION_MAGIC: [u8; 3] = *b"LLZ";
+
+/// The length (in bytes) of a compressed stream's header.
+pub const COMPRESSION_HEADER_LEN: usize = COMPRESSION_MAGIC.len() + 1;
+
+/// A compression codec.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Codec {
+    /// Zstandard, favoring compression ratio.
+    #[cfg(feature = "zstd")]
+    Zstd,
+    /// LZ4 (frame format), favoring speed.
+    #[cfg(feature = "lz4")]
+    Lz4,
+}
+
+impl Codec {
+    const ZSTD_ID: u8 = 1;
+    const LZ4_ID: u8 = 2;
+
+    /// Returns the codec's id, as advertised by a compressed stream's header.
+    pub fn id(self) -> u8 {
+        match self {
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Self::ZSTD_ID,
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Self::LZ4_ID,
+        }
+    }
+
+    /// Returns the codec for `id`, or an error if unknown, or not enabled.
+    pub fn from_id(id: u8) -> Result<Self> {
+        match id {
+            #[cfg(feature = "zstd")]
+            Self::ZSTD_ID => Ok(Self::Zstd),
+            #[cfg(not(feature = "zstd"))]
+            Self::ZSTD_ID => Err(Error::uncategorized(
+                "zstd compression requires the `zstd` feature",
+                Some(COMPRESSION_MAGIC.len()),
+            )),
+            #[cfg(feature = "lz4")]
+            Self::LZ4_ID => Ok(Self::Lz4),
+            #[cfg(not(feature = "lz4"))]
+            Self::LZ4_ID => Err(Error::uncategorized(
+                "lz4 compression requires the `lz4` feature",
+                Some(COMPRESSION_MAGIC.len()),
+            )),
+            _ => Err(Error::invalid_value(
+                format!("compression codec {id}"),
+                "known compression codec".to_string(),
+                Some(COMPRESSION_MAGIC.len()),
+            )),
+        }
+    }
+}
+
+/// A writer compressing the bytes written to it, before passing them on to `writer`.
+///
+/// The compressed stream is only complete once `finish()` has been called.
+/// Use `StdIoWriter` for encoding values into it.
+pub struct CompressedWriter<W>
+where
+    W: Write,
+{
+    inner: CompressingWriter<W>,
+}
+
+impl<W> CompressedWriter<W>
+where
+    W: Write,
+{
+    /// Creates a writer compressing with `codec`, writing the header to `writer`.
+    pub fn new(mut writer: W, codec: Codec) -> Result<Self> {
+        writer.write_all(&COMPRESSION_MAGIC).map_err(Error::io)?;
+        writer.write_all(&[codec.id()]).map_err(Error::io)?;
+
+        let inner = match codec {
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => CompressingWriter::Zstd(
+                zstd::stream::write::Encoder::new(writer, zstd::DEFAULT_COMPRESSION_LEVEL)
+                    .map_err(Error::io)?,
+            ),
+            #[cfg(feature = "lz4")]
+            Codec::Lz4 => CompressingWriter::Lz4(lz4_flex::frame::FrameEncoder::new(writer)),
+        };
+
+        Ok(Self { inner })
+    }
+
+    /// Completes the compressed stream, returning the internal `writer`.
+    pub fn finish(self) -> Result<W> {
+        match self.inner {
+            #[cfg(feature = "zstd")]
+            CompressingWriter::Zstd(encoder) => encoder.finish().map_err(Error::io),
+            #[cfg(feature = "lz4")]
+            CompressingWriter::Lz4(encoder) => encoder
+                .finish()
+                .map_err(|err| Error::io(io::Error::from(err))),
+        }
+    }
+}
+
+impl<W> Write for CompressedWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            #[cfg(feature = "zstd")]
+            CompressingWriter::Zstd(encoder) => encoder.write(buf),
+            #[cfg(feature = "lz4")]
+            CompressingWriter::Lz4(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            #[cfg(feature = "zstd")]
+            CompressingWriter::Zstd(encoder) => encoder.flush(),
+            #[cfg(feature = "lz4")]
+            CompressingWriter::Lz4(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// A reader decompressing the bytes read from `reader`, with the codec advertised by its header.
+///
+/// Decompressors may read ahead of the compressed stream's end.
+/// Use `StdIoReader` for decoding values from it.
+pub struct CompressedReader<R>
+where
+    R: Read,
+{
+    codec: Codec,
+    inner: DecompressingReader<R>,
+}
+
+impl<R> CompressedReader<R>
+where
+    R: Read,
+{
+    /// Creates a reader decompressing `reader`, after reading its header.
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut header = [0b0; COMPRESSION_HEADER_LEN];
+        reader.read_exact(&mut header).map_err(Error::io)?;
+
+        let (magic, id) = header.split_at(COMPRESSION_MAGIC.len());
+
+        if magic != COMPRESSION_MAGIC {
+            return Err(Error::invalid_value(
+                format!("bytes {magic:02X?}"),
+                format!("compression magic {COMPRESSION_MAGIC:02X?}"),
+                Some(0),
+            ));
+        }
+
+        let codec = Codec::from_id(id[0])?;
+
+        let inner = match codec {
+            #[cfg(feature = "zstd")]
+            Codec::Zstd => DecompressingReader::Zstd(
+                zstd::stream::read::Decoder::new(reader).map_err(Error::io)?,
+            ),
+            #[cfg(feature = "lz4")]
+            Codec::Lz4 => DecompressingReader::Lz4(lz4_flex::frame::FrameDecoder::new(reader)),
+        };
+
+        Ok(Self { codec, inner })
+    }
+
+    /// Returns the codec advertised by the stream's header.
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+}
+
+impl<R> Read for CompressedReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            #[cfg(feature = "zstd")]
+            DecompressingReader::Zstd(decoder) => decoder.read(buf),
+            #[cfg(feature = "lz4")]
+            DecompressingReader::Lz4(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+// MARK: - Private
+
+enum CompressingWriter<W>
+where
+    W: Write,
+{
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+    #[cfg(feature = "lz4")]
+    Lz4(lz4_flex::frame::FrameEncoder<W>),
+}
+
+enum DecompressingReader<R>
+where
+    R: Read,
+{
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::read::Decoder<'static, io::BufReader<R>>),
+    #[cfg(feature = "lz4")]
+    Lz4(lz4_flex::frame::FrameDecoder<R>),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::vec::Vec;
+
+    use test_log::test;
+
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{StdIoReader, StdIoWriter},
+        value::{SeqValue, StringValue, Value},
+    };
+
+    use super::*;
+
+    fn roundtrip(codec: Codec) {
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::String(StringValue::from(
+                "lilliput".to_owned()
+            ));
+            64
+        ]));
+
+        let writer = CompressedWriter::new(Vec::new(), codec).unwrap();
+        let mut encoder = Encoder::from_writer(StdIoWriter::new(writer));
+        encoder.encode_value(&value).unwrap();
+        let compressed = encoder.into_writer().into_writer().finish().unwrap();
+
+        assert_eq!(compressed[..3], COMPRESSION_MAGIC);
+        assert_eq!(compressed[3], codec.id());
+        assert!(compressed.len() < 64 * 9);
+
+        let reader = CompressedReader::new(compressed.as_slice()).unwrap();
+        assert_eq!(reader.codec(), codec);
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(reader));
+        assert_eq!(decoder.decode_value().unwrap(), value);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd() {
+        roundtrip(Codec::Zstd);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn lz4() {
+        roundtrip(Codec::Lz4);
+    }
+
+    #[test]
+    fn invalid_header() {
+        let err = CompressedReader::new(&b"LLQ\x01"[..]).err().unwrap();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+        assert_eq!(err.pos(), Some(0));
+
+        let err = CompressedReader::new(&b"LLZ\xFF"[..]).err().unwrap();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+        assert_eq!(err.pos(), Some(3));
+
+        assert!(CompressedReader::new(&b"LL"[..]).is_err());
+    }
+}
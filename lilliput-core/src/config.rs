@@ -1,9 +1,14 @@
 //! Configurations for encoding/decoding.
 
-pub use float::FloatEncoderConfig;
+pub use float::{FloatEncoderConfig, PackedFloatValidation};
 pub use int::IntEncoderConfig;
 pub use length::LengthEncoderConfig;
 
+use crate::{
+    error::{Error, Result},
+    header::{MapHeader, SeqHeader, StringHeader},
+};
+
 mod float;
 mod int;
 mod length;
@@ -28,19 +33,130 @@ impl PackingMode {
     }
 }
 
+/// Version of the rules used for selecting value headers while encoding.
+///
+/// Encodings are always decodable regardless of the version used to produce them,
+/// but future releases may improve the header selection of `Latest`, changing the
+/// exact bytes produced for a given value. Pin a specific version if stable bytes
+/// are required (e.g. for cache keys or signatures derived from encodings).
+///
+/// The default is `Latest`, unless the `format_v1` feature is enabled.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum FormatVersion {
+    /// The latest rules, currently identical to `V1`.
+    Latest,
+    /// The rules of version 1, frozen byte-for-byte.
+    V1,
+}
+
+impl Default for FormatVersion {
+    fn default() -> Self {
+        if cfg!(feature = "format_v1") {
+            Self::V1
+        } else {
+            Self::Latest
+        }
+    }
+}
+
+/// Profile restricting the parts of the format a document may use.
+///
+/// Allows for documents to be exchanged with decoders supporting only a subset
+/// of the format (e.g. minimal implementations for embedded devices), with encoders
+/// restricted to that subset and decoders rejecting documents exceeding it.
+/// Profiles are ordered by strictness, from least to most strict.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[repr(u8)]
+pub enum Profile {
+    /// No restrictions, permitting all of the format.
+    #[default]
+    None = 0,
+    /// Restricts documents to the core headers of each value type (i.e. their compact
+    /// and extended variants), without streamed or columnar sequences, front-coded maps,
+    /// dictionary references, extension values (e.g. timestamps) and reserved headers.
+    ///
+    /// Encoders fall back to plain codings for map keys, sequences of maps and
+    /// dictionary strings, but reject streamed sequences and extension (or unknown)
+    /// values with a `ProfileViolation` error, as do decoders for all of them.
+    Weak = 1,
+}
+
+impl Profile {
+    /// Returns the profile for `id` (i.e. its `u8` representation), if any.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::None),
+            1 => Some(Self::Weak),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the profile permits values with header `byte`.
+    #[inline]
+    pub(crate) fn permits(self, byte: u8) -> bool {
+        self == Self::None || !SeqHeader::is_non_core(byte)
+    }
+
+    /// Returns a `ProfileViolation` error, if the profile does not permit values with header `byte`.
+    #[inline]
+    pub(crate) fn check(self, byte: u8, pos: Option<usize>) -> Result<()> {
+        if self.permits(byte) {
+            return Ok(());
+        }
+
+        let construct = match byte {
+            SeqHeader::STREAM_START | SeqHeader::STREAM_END => "streamed sequence",
+            SeqHeader::COLUMNAR => "columnar sequence",
+            SeqHeader::TABULAR => "tabular sequence",
+            SeqHeader::EXTENSION => "extension value",
+            MapHeader::FRONT_CODED => "front-coded map",
+            StringHeader::DICTIONARY_REF => "dictionary reference",
+            _ => "reserved header",
+        };
+
+        Err(Error::profile_violation(construct, self, pos))
+    }
+}
+
 /// Configuration used for encoding values.
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 #[derive(Default, Clone, Debug)]
 pub struct EncoderConfig {
+    /// Version of the header selection rules.
+    pub format: FormatVersion,
+    /// Profile restricting the parts of the format to encode.
+    #[cfg_attr(any(test, feature = "testing"), proptest(value = "Profile::None"))]
+    pub profile: Profile,
     /// Configuration used for encoding value lengths (in header extensions).
     pub lengths: LengthEncoderConfig,
     /// Configuration used for encoding integer values.
     pub ints: IntEncoderConfig,
     /// Configuration used for encoding floating-point values.
     pub floats: FloatEncoderConfig,
+    /// Coding used for the keys of map values.
+    pub map_keys: MapKeyCoding,
+    /// Coding used for sequences of map values.
+    pub seqs: SeqCoding,
+    /// Whether documents get preceded by a preamble,
+    /// by APIs encoding whole documents (see `preamble`).
+    pub preamble: bool,
 }
 
 impl EncoderConfig {
+    /// Sets the format version to `format`, returning `self`.
+    pub fn with_format(mut self, format: FormatVersion) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets profile to `profile`, returning `self`.
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
     /// Sets packing-modes to `packing`, returning `self`.
     pub fn with_packing(mut self, packing: PackingMode) -> Self {
         self.lengths = self.lengths.with_packing(packing);
@@ -48,9 +164,194 @@ impl EncoderConfig {
         self.floats = self.floats.with_packing(packing);
         self
     }
+
+    /// Sets map-keys to `map_keys`, returning `self`.
+    pub fn with_map_keys(mut self, map_keys: MapKeyCoding) -> Self {
+        self.map_keys = map_keys;
+        self
+    }
+
+    /// Sets seqs to `seqs`, returning `self`.
+    pub fn with_seqs(mut self, seqs: SeqCoding) -> Self {
+        self.seqs = seqs;
+        self
+    }
+
+    /// Sets preamble to `preamble`, returning `self`.
+    pub fn with_preamble(mut self, preamble: bool) -> Self {
+        self.preamble = preamble;
+        self
+    }
+}
+
+/// Coding used for the keys of map values.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MapKeyCoding {
+    /// Encodes each key as a standalone value.
+    #[default]
+    Plain,
+    /// Encodes maps with two or more keys, all of which are strings, with front coding:
+    /// each key only stores the suffix following the prefix it shares with the key before it.
+    ///
+    /// Most effective for sorted keys with long common prefixes (e.g. metric names),
+    /// as is the case for maps backed by a `BTreeMap`. Decoders older than this
+    /// coding see front-coded maps as values with a reserved header.
+    FrontCoded,
+}
+
+/// Coding used for sequences of map values.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SeqCoding {
+    /// Encodes each item as a standalone value.
+    #[default]
+    Plain,
+    /// Encodes sequences of two or more maps, all of which have the same keys
+    /// (in the same order), column by column: the keys get written once,
+    /// followed by a sequence of each key's values, for all of the maps.
+    ///
+    /// Most effective for sequences of structs (e.g. time-series samples), whose
+    /// columns tend to be similar, if not homogeneous. Decoders older than this
+    /// coding see columnar sequences as values with a reserved header.
+    Columnar,
+    /// Encodes sequences of two or more maps, all of which have the same keys
+    /// (in the same order), row by row: the keys get written once, followed by
+    /// each map's values, in the order of the keys.
+    ///
+    /// Unlike columnar sequences, tabular ones can be decoded one map at a time,
+    /// e.g. for deserializing a `Vec<Struct>` without buffering it as a whole.
+    /// Decoders older than this coding see tabular sequences as values with a reserved header.
+    Tabular,
 }
 
 /// Configuration used for decoding values.
 #[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
-pub struct DecoderConfig {}
+pub struct DecoderConfig {
+    /// Whether to copy decoded strings and byte arrays, rather than
+    /// borrowing them from the input, even if the reader supports it.
+    ///
+    /// Guarantees that no decoded reference outlives a reuse of the
+    /// input's buffer (e.g. of a ring buffer), at the cost of copying.
+    pub force_owned: bool,
+    /// How to handle values with reserved header bytes,
+    /// as used by newer versions of the format.
+    pub unknown_headers: UnknownHeaderPolicy,
+    /// How to handle strings containing invalid UTF-8.
+    pub utf8: Utf8Policy,
+    /// How to handle maps containing duplicate keys, when decoding `Value`s
+    /// (or deserializing maps, via lilliput-serde).
+    pub duplicate_keys: DuplicateKeyPolicy,
+    /// Whether to reject integers and floats encoded wider than necessary,
+    /// i.e. not as produced by `PackingMode::Optimal` (with lossless float packing).
+    ///
+    /// Guarantees each value to have a single accepted encoding,
+    /// as required by signatures or hashes derived from encodings.
+    pub canonical: bool,
+    /// The nesting depth of sequences and maps, upon reaching which decoding
+    /// (and skipping) of values fails, or `None` for no limit.
+    ///
+    /// Bounds the decoder's recursion, for inputs from untrusted sources.
+    pub max_depth: Option<u8>,
+    /// Profile restricting the parts of the format to accept.
+    pub profile: Profile,
+    /// Whether documents are expected to be preceded by a preamble,
+    /// by APIs decoding whole documents (see `preamble`).
+    pub preamble: bool,
+}
+
+impl DecoderConfig {
+    /// Sets force-owned to `force_owned`, returning `self`.
+    pub fn with_force_owned(mut self, force_owned: bool) -> Self {
+        self.force_owned = force_owned;
+        self
+    }
+
+    /// Sets unknown-headers to `unknown_headers`, returning `self`.
+    pub fn with_unknown_headers(mut self, unknown_headers: UnknownHeaderPolicy) -> Self {
+        self.unknown_headers = unknown_headers;
+        self
+    }
+
+    /// Sets utf8 to `utf8`, returning `self`.
+    pub fn with_utf8(mut self, utf8: Utf8Policy) -> Self {
+        self.utf8 = utf8;
+        self
+    }
+
+    /// Sets duplicate-keys to `duplicate_keys`, returning `self`.
+    pub fn with_duplicate_keys(mut self, duplicate_keys: DuplicateKeyPolicy) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+
+    /// Sets canonical to `canonical`, returning `self`.
+    pub fn with_canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+
+    /// Sets max-depth to `max_depth`, returning `self`.
+    pub fn with_max_depth(mut self, max_depth: Option<u8>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets preamble to `preamble`, returning `self`.
+    pub fn with_preamble(mut self, preamble: bool) -> Self {
+        self.preamble = preamble;
+        self
+    }
+
+    /// Sets profile to `profile`, returning `self`.
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+/// Policy for handling strings containing invalid UTF-8.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Utf8Policy {
+    /// Reject invalid strings with an error.
+    #[default]
+    Reject,
+    /// Replace invalid sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// Strings get copied, rather than borrowed from the input.
+    Replace,
+}
+
+/// Policy for handling maps containing duplicate keys.
+///
+/// Keys are compared as decoded values, rather than by their encodings.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DuplicateKeyPolicy {
+    /// Keep the last of the duplicate keys' values.
+    #[default]
+    LastWins,
+    /// Keep the first of the duplicate keys' values, skipping the others.
+    FirstWins,
+    /// Reject duplicate keys with an error.
+    Reject,
+}
+
+/// Policy for handling values with reserved header bytes.
+///
+/// Reserved headers are followed by the length of their body (as an
+/// unsigned integer value), allowing decoders to skip over values of
+/// types introduced by newer versions of the format.
+#[cfg_attr(any(test, feature = "testing"), derive(proptest_derive::Arbitrary))]
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum UnknownHeaderPolicy {
+    /// Reject reserved headers with an error.
+    #[default]
+    Reject,
+    /// Decode values with reserved headers, as well as references to
+    /// unknown dictionary ids, as opaque `Value::Unknown`s
+    /// (and skip them when skipping values).
+    Collect,
+}
@@ -1,34 +1,145 @@
 //! Decoders for decoding lilliput-encoded values.
 
+use alloc::{borrow::ToOwned, format, string::ToString, vec::Vec};
+use core::ops::Range;
+
 use crate::{
-    error::{Error, Result},
-    header::Header,
-    io::{Read, Reference},
+    config::{DecoderConfig, Profile, UnknownHeaderPolicy, Utf8Policy},
+    dictionary::Dictionary,
+    error::{Error, ErrorCode, Result},
+    header::{Header, MapHeader, SeqHeader, StringHeader},
+    io::{PeekingReader, Read, Reference, SliceReader, VecWriter, Write},
     marker::Marker,
-    value::Value,
+    preamble::{Preamble, FORMAT_VERSION, PREAMBLE_MAGIC},
+    spec::{BodySpec, HeaderSpec},
+    validation::DEFAULT_MAX_DEPTH,
+    value::{Value, ValueKind},
+    visit::{ValueVisitor, Walk},
+};
+
+pub use self::{
+    map::FrontCodedKeys,
+    seq::{ColumnarSeq, TabularSeq},
+    stats::DecoderStats,
 };
 
+#[cfg(feature = "async")]
+pub use self::asynchronous::AsyncDecoder;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "bigint")]
+mod big_int;
 mod bool;
 mod bytes;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod ext;
 mod float;
 mod int;
 mod map;
 mod null;
+#[cfg(test)]
+mod regressions;
 mod seq;
+mod stats;
 mod string;
+mod timestamp;
 mod unit;
+mod unknown;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod value_ref;
 
 /// A decoder for decoding lilliput-encoded values.
 #[derive(Debug)]
 pub struct Decoder<R> {
     reader: R,
     pos: usize,
+    config: DecoderConfig,
+    stats: Option<DecoderStats>,
+    dictionary: Option<Dictionary>,
+    /// The nesting depth of the value being decoded.
+    depth: usize,
+    /// Whether decoded strings get registered with the dictionary.
+    interning: bool,
 }
 
 impl<R> Decoder<R> {
     /// Creates a decoder from a `reader`.
     pub fn from_reader(reader: R) -> Self {
-        Decoder { reader, pos: 0 }
+        Self::new(reader, DecoderConfig::default())
+    }
+
+    /// Creates a decoder from a `reader`, configured by `config`.
+    pub fn new(reader: R, config: DecoderConfig) -> Self {
+        Decoder {
+            reader,
+            pos: 0,
+            config,
+            stats: None,
+            dictionary: None,
+            depth: 0,
+            interning: false,
+        }
+    }
+
+    /// Enables the collection of allocation statistics, returning `self`.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(DecoderStats::default());
+        self
+    }
+
+    /// Returns the statistics collected so far, if enabled.
+    pub fn stats(&self) -> Option<&DecoderStats> {
+        self.stats.as_ref()
+    }
+
+    /// Returns the statistics collected so far, if enabled, resetting them.
+    pub fn take_stats(&mut self) -> Option<DecoderStats> {
+        self.stats.as_mut().map(core::mem::take)
+    }
+
+    /// Attaches `dictionary`, returning `self`.
+    ///
+    /// References to dictionary strings get resolved against it, and must
+    /// use the ids of the dictionary attached to the encoder that produced them.
+    /// See the [`dictionary`](crate::dictionary) module for details.
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Returns the attached dictionary, if any.
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.dictionary.as_ref()
+    }
+
+    /// Returns the attached dictionary, if any, for registering further strings.
+    pub fn dictionary_mut(&mut self) -> Option<&mut Dictionary> {
+        self.dictionary.as_mut()
+    }
+
+    /// Replaces the attached dictionary with `dictionary`, returning the previous one.
+    pub fn set_dictionary(&mut self, dictionary: Option<Dictionary>) -> Option<Dictionary> {
+        core::mem::replace(&mut self.dictionary, dictionary)
+    }
+
+    /// Enables string interning, returning `self`.
+    ///
+    /// Strings of at least `MIN_INTERNED_LEN` bytes get registered with the
+    /// attached dictionary (an empty one, if none) once decoded (or skipped),
+    /// for resolving references from an encoder with interning enabled.
+    /// See the [`dictionary`](crate::dictionary) module for details.
+    pub fn with_interning(mut self) -> Self {
+        self.dictionary.get_or_insert_with(Dictionary::default);
+        self.interning = true;
+        self
+    }
+
+    /// Returns the decoder's configuration.
+    pub fn config(&self) -> &DecoderConfig {
+        &self.config
     }
 
     /// Returns the decoder's internal `reader`, consuming `self`.
@@ -50,21 +161,203 @@ where
 
     /// Decodes a `Value`.
     pub fn decode_value(&mut self) -> Result<Value> {
-        let header = self.decode_header()?;
-        self.decode_value_of(header)
+        self.nested(Self::decode_nested_value)
+    }
+
+    /// Decodes the next value, whatever its kind.
+    ///
+    /// Pairs with `peek_value_kind()` for branching on the kind of the
+    /// upcoming value, falling back to decoding it as a `Value`.
+    ///
+    /// ```
+    /// use lilliput_core::{decoder::Decoder, io::SliceReader, value::ValueKind};
+    ///
+    /// // The string `"lilliput"`:
+    /// let encoded = [0x68, b'l', b'i', b'l', b'l', b'i', b'p', b'u', b't'];
+    ///
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    ///
+    /// let string = match decoder.peek_value_kind().unwrap() {
+    ///     ValueKind::String => decoder.decode_string().unwrap(),
+    ///     _ => format!("{:?}", decoder.decode_any().unwrap()),
+    /// };
+    /// assert_eq!(string, "lilliput");
+    /// ```
+    pub fn decode_any(&mut self) -> Result<Value> {
+        self.decode_value()
+    }
+
+    /// Decodes a `Value`, substituting any invalid parts, rather than failing.
+    ///
+    /// Strings containing invalid UTF-8 get their invalid sequences replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER`, while values with reserved headers (or references
+    /// to unknown dictionary ids) get captured as opaque `Value::Unknown`s, regardless
+    /// of `DecoderConfig::unknown_headers`. Meant for inspecting corrupted data,
+    /// which still fails to decode if truncated, or structurally invalid.
+    ///
+    /// ```
+    /// use lilliput_core::{
+    ///     decoder::Decoder,
+    ///     io::SliceReader,
+    ///     value::{StringValue, Value},
+    /// };
+    ///
+    /// // The string `"a"`, followed by an invalid byte:
+    /// let encoded = [0x62, b'a', 0xFF];
+    ///
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    /// let value = decoder.decode_value_lossy().unwrap();
+    /// assert_eq!(value, Value::String(StringValue::from("a\u{FFFD}".to_owned())));
+    /// ```
+    pub fn decode_value_lossy(&mut self) -> Result<Value> {
+        let config = self.config;
+        self.config.unknown_headers = UnknownHeaderPolicy::Collect;
+        self.config.utf8 = Utf8Policy::Replace;
+
+        let result = self.decode_value();
+
+        self.config = config;
+
+        result
+    }
+
+    /// Decodes a `Value`, along with the range of bytes it occupied in the input.
+    ///
+    /// ```
+    /// use lilliput_core::{decoder::Decoder, io::SliceReader};
+    ///
+    /// // The integer `1`, followed by the string `"a"`:
+    /// let encoded = [0xC1, 0x61, b'a'];
+    ///
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    /// decoder.skip_value().unwrap();
+    ///
+    /// let (_, span) = decoder.decode_value_spanned().unwrap();
+    /// assert_eq!(span, 1..3);
+    /// ```
+    pub fn decode_value_spanned(&mut self) -> Result<(Value, Range<usize>)> {
+        let start = self.pos;
+        let value = self.decode_value()?;
+
+        Ok((value, start..self.pos))
     }
 
     // MARK: - Marker
 
     /// Decodes a value's type `Marker`.
     pub fn peek_marker(&mut self) -> Result<Marker> {
-        self.peek_byte().map(Marker::detect)
+        match self.peek_byte()? {
+            MapHeader::FRONT_CODED => Ok(Marker::Map),
+            StringHeader::DICTIONARY_REF => Ok(Marker::String),
+            SeqHeader::COLUMNAR | SeqHeader::TABULAR => Ok(Marker::Seq),
+            byte => Ok(Marker::detect(byte)),
+        }
+    }
+
+    /// Returns the kind of the next to-be-decoded value, without advancing the position.
+    ///
+    /// Values with reserved headers are of kind `ValueKind::Unknown`,
+    /// regardless of whether the decoder is configured to collect them.
+    pub fn peek_value_kind(&mut self) -> Result<ValueKind> {
+        let byte = self.peek_byte()?;
+
+        if byte == SeqHeader::EXTENSION {
+            return Ok(ValueKind::Ext);
+        }
+
+        if SeqHeader::is_reserved(byte) {
+            return Ok(ValueKind::Unknown);
+        }
+
+        self.peek_marker().map(From::from)
+    }
+
+    /// Checks that the input has been decoded completely,
+    /// returning a `TrailingBytes` error, if not.
+    ///
+    /// ```
+    /// use lilliput_core::{decoder::Decoder, error::ErrorCode, io::SliceReader};
+    ///
+    /// let encoded = [0xC1, 0xC2];
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    /// decoder.decode_value().unwrap();
+    ///
+    /// let err = decoder.end().unwrap_err();
+    /// assert_eq!(err.code(), ErrorCode::TrailingBytes);
+    /// assert_eq!(err.pos(), Some(1));
+    /// ```
+    pub fn end(&mut self) -> Result<()> {
+        match self.reader.peek_one() {
+            Ok(_) => {
+                let len = self.reader.remaining_len_hint().unwrap_or(1);
+                Err(Error::trailing_bytes(len, Some(self.pos)))
+            }
+            Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Decodes a preamble, returning the format version and profile it announces.
+    ///
+    /// Returns an error if the input doesn't start with a preamble, if its
+    /// format version is not supported by this decoder, or if its profile is
+    /// less strict than `DecoderConfig::profile`.
+    /// See the [`preamble`](crate::preamble) module for details.
+    pub fn expect_preamble(&mut self) -> Result<Preamble> {
+        let pos = self.pos;
+
+        let mut magic = [0b0; PREAMBLE_MAGIC.len()];
+        self.pull_bytes_into(&mut magic)?;
+
+        if magic != PREAMBLE_MAGIC {
+            return Err(Error::invalid_value(
+                format!("bytes {magic:02X?}"),
+                format!("preamble magic {PREAMBLE_MAGIC:02X?}"),
+                Some(pos),
+            ));
+        }
+
+        let pos = self.pos;
+        let version = self.pull_byte()?;
+
+        if !(1..=FORMAT_VERSION).contains(&version) {
+            return Err(Error::invalid_value(
+                format!("format version {version}"),
+                format!("format version of at most {FORMAT_VERSION}"),
+                Some(pos),
+            ));
+        }
+
+        let pos = self.pos;
+        let id = self.pull_byte()?;
+
+        let Some(profile) = Profile::from_id(id) else {
+            return Err(Error::invalid_value(
+                format!("profile {id}"),
+                "known profile".to_owned(),
+                Some(pos),
+            ));
+        };
+
+        if profile < self.config.profile {
+            return Err(Error::invalid_value(
+                format!("{profile:?} profile"),
+                format!("{:?} profile", self.config.profile),
+                Some(pos),
+            ));
+        }
+
+        Ok(Preamble { version, profile })
     }
 
     // MARK: - Header
 
     /// Decodes a value's `Header`.
     pub fn decode_header(&mut self) -> Result<Header> {
+        if self.peek_extension()? {
+            return self.decode_ext_header().map(From::from);
+        }
+
         match self.peek_marker()? {
             Marker::Int => self.decode_int_header().map(From::from),
             Marker::String => self.decode_string_header().map(From::from),
@@ -78,18 +371,47 @@ where
         }
     }
 
+    /// Decodes the next value's `Header`, without advancing the position.
+    ///
+    /// Requires a reader supporting `Read::peek()` for headers spanning
+    /// multiple bytes, such as `SliceReader` or `BufferedReader`.
+    ///
+    /// ```
+    /// use lilliput_core::{decoder::Decoder, header::Header, io::SliceReader};
+    ///
+    /// // A map of `"name"` to `"lilliput"`:
+    /// let encoded = [0x19, 0x64, b'n', b'a', b'm', b'e', 0x68, b'l', b'i', b'l', b'l', b'i', b'p', b'u', b't'];
+    ///
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    ///
+    /// let Header::Map(header) = decoder.peek_header().unwrap() else {
+    ///     panic!("expected map header");
+    /// };
+    /// assert_eq!(header.len(), 1);
+    /// assert_eq!(decoder.pos(), 0);
+    /// ```
+    pub fn peek_header(&mut self) -> Result<Header> {
+        let mut decoder = Decoder::new(PeekingReader::new(&mut self.reader), self.config);
+        decoder.pos = self.pos;
+
+        decoder.decode_header()
+    }
+
     // MARK: - Skip
 
     /// Skips the next to-be-decoded value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn skip_value(&mut self) -> Result<()> {
-        let header = self.decode_header()?;
-        self.skip_value_of(header)
+        self.nested(Self::skip_nested_value)
     }
 
     /// Skips the value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn skip_value_of(&mut self, header: Header) -> Result<()> {
+        if self.interning {
+            return self.decode_value_of(header).map(drop);
+        }
+
         match header {
             Header::Int(header) => self.skip_int_value_of(header),
             Header::String(header) => self.skip_string_value_of(header),
@@ -100,7 +422,55 @@ where
             Header::Bool(header) => self.skip_bool_value_of(header),
             Header::Unit(header) => self.skip_unit_value_of(header),
             Header::Null(header) => self.skip_null_value_of(header),
+            Header::Ext(header) => self.skip_ext_value_of(header),
+        }
+    }
+
+    // MARK: - Pass-through
+
+    /// Copies the next to-be-decoded value verbatim (i.e. header and body)
+    /// to `writer`, without decoding it, returning the number of bytes copied.
+    ///
+    /// Useful for splicing encoded sub-documents from one stream into another.
+    /// Not supported with string interning enabled, as references to interned
+    /// strings would not resolve within the other stream.
+    ///
+    /// The copied value's nesting depth is limited by `DecoderConfig::max_depth`,
+    /// or `validation::DEFAULT_MAX_DEPTH`, if unlimited.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_into_writer<W>(&mut self, writer: &mut W) -> Result<usize>
+    where
+        W: Write,
+    {
+        if self.interning {
+            return Err(Error::uncategorized(
+                "verbatim copies are not supported while interning strings",
+                Some(self.pos),
+            ));
         }
+
+        let start = self.pos;
+        let mut scratch = Vec::new();
+
+        self.copy_value_into(writer, &mut scratch)?;
+
+        Ok(self.pos - start)
+    }
+
+    // MARK: - Visit
+
+    /// Walks the next to-be-decoded value, calling `visitor` for its contents,
+    /// without decoding it into a `Value`.
+    ///
+    /// See the [`visit`](crate::visit) module for details.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn walk_value<V>(&mut self, visitor: &mut V) -> Result<()>
+    where
+        V: ?Sized + ValueVisitor,
+    {
+        let mut scratch = Vec::new();
+
+        self.walk_value_with(visitor, &mut scratch)
     }
 
     // MARK: - Body
@@ -117,6 +487,7 @@ where
             Header::Bool(header) => self.decode_bool_value_of(header).map(From::from),
             Header::Unit(header) => self.decode_unit_value_of(header).map(From::from),
             Header::Null(header) => self.decode_null_value_of(header).map(From::from),
+            Header::Ext(header) => self.decode_ext_value_of(header).map(From::from),
         }
     }
 }
@@ -127,9 +498,108 @@ impl<'de, R> Decoder<R>
 where
     R: Read<'de>,
 {
+    /// Runs `f` for the next value, nested one level deeper than the current one,
+    /// returning a `DepthLimitExceeded` error if it is a sequence or map
+    /// at (or beyond) `DecoderConfig::max_depth`.
+    #[inline]
+    fn nested<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        self.nested_within(self.config.max_depth, f)
+    }
+
+    /// Runs `f` for the next value, just like `nested()`, but limited to `max_depth`.
+    #[inline]
+    fn nested_within<T, F>(&mut self, max_depth: Option<u8>, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        if let Some(max_depth) = max_depth {
+            if self.depth >= usize::from(max_depth)
+                && matches!(self.peek_value_kind()?, ValueKind::Seq | ValueKind::Map)
+            {
+                return Err(Error::depth_limit_exceeded(Some(self.pos)));
+            }
+        }
+
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    fn decode_nested_value(&mut self) -> Result<Value> {
+        let byte = self.peek_byte()?;
+
+        if byte == SeqHeader::STREAM_START
+            || byte == SeqHeader::COLUMNAR
+            || byte == SeqHeader::TABULAR
+        {
+            return self.decode_seq_value().map(From::from);
+        }
+
+        if byte == MapHeader::FRONT_CODED {
+            return self.decode_map_value().map(From::from);
+        }
+
+        if byte == StringHeader::DICTIONARY_REF {
+            return self.decode_dictionary_ref_value();
+        }
+
+        if self.collects_unknown(byte) {
+            return self.decode_unknown_value().map(From::from);
+        }
+
+        let header = self.decode_header()?;
+        self.decode_value_of(header)
+    }
+
+    fn skip_nested_value(&mut self) -> Result<()> {
+        // Skipped strings need to be interned all the same:
+        if self.interning {
+            return self.decode_nested_value().map(drop);
+        }
+
+        let byte = self.peek_byte()?;
+
+        if byte == SeqHeader::STREAM_START {
+            return self.skip_seq_stream();
+        }
+
+        if byte == SeqHeader::COLUMNAR || byte == SeqHeader::TABULAR {
+            return self.skip_keyed_seq();
+        }
+
+        if byte == MapHeader::FRONT_CODED {
+            return self.skip_front_coded_map();
+        }
+
+        if byte == StringHeader::DICTIONARY_REF {
+            return self.skip_unknown_value();
+        }
+
+        if self.collects_unknown(byte) {
+            return self.skip_unknown_value();
+        }
+
+        let header = self.decode_header()?;
+        self.skip_value_of(header)
+    }
+
+    #[inline]
+    fn collects_unknown(&self, byte: u8) -> bool {
+        self.config.unknown_headers == UnknownHeaderPolicy::Collect && SeqHeader::is_reserved(byte)
+    }
+
     #[inline]
-    fn peek_byte(&mut self) -> Result<u8> {
-        self.reader.peek_one()
+    pub(crate) fn peek_byte(&mut self) -> Result<u8> {
+        let byte = self.reader.peek_one()?;
+
+        self.config.profile.check(byte, Some(self.pos))?;
+
+        Ok(byte)
     }
 
     #[inline]
@@ -179,6 +649,22 @@ where
         len: usize,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, [u8]>> {
+        if self.config.force_owned {
+            let borrowed = match self.reader.read(len, scratch)? {
+                Reference::Borrowed(bytes) => Some(bytes),
+                Reference::Copied(_) => None,
+            };
+
+            // Copied just like readers without support for borrowing would:
+            if let Some(bytes) = borrowed {
+                scratch.extend_from_slice(bytes);
+            }
+
+            self.pos += len;
+
+            return Ok(Reference::Copied(scratch));
+        }
+
         let bytes = self.reader.read(len, scratch)?;
 
         debug_assert_eq!(bytes.len(), len);
@@ -188,17 +674,374 @@ where
         Ok(bytes)
     }
 
+    #[inline]
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        self.reader.skip(len)?;
+
+        self.pos += len;
+
+        Ok(())
+    }
+
+    /// Pulls exactly `len` bytes, in chunks, writing them to `writer`,
+    /// after passing each chunk to `inspect`.
+    #[cfg(feature = "std")]
+    fn pull_bytes_to_writer<W, F>(
+        &mut self,
+        len: usize,
+        writer: &mut W,
+        mut inspect: F,
+    ) -> Result<()>
+    where
+        W: std::io::Write,
+        F: FnMut(&[u8]) -> Result<()>,
+    {
+        const CHUNK_LEN: usize = 8 * 1024;
+
+        let mut scratch = Vec::new();
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let chunk_len = remaining.min(CHUNK_LEN);
+
+            scratch.clear();
+            let chunk = self.pull_bytes(chunk_len, &mut scratch)?;
+
+            inspect(&chunk)?;
+            writer.write_all(&chunk).map_err(Error::io)?;
+
+            remaining -= chunk_len;
+        }
+
+        Ok(())
+    }
+
+    fn pull_bytes_buf(&mut self, len: usize) -> Result<Vec<u8>> {
+        let buf = match self.reader.read_owned(len)? {
+            Some(buf) => buf,
+            None => {
+                let mut buf = Vec::new();
+                match self.reader.read(len, &mut buf)? {
+                    Reference::Borrowed(slice) => {
+                        debug_assert_eq!(buf.len(), 0);
+                        buf.extend_from_slice(slice);
+                    }
+                    Reference::Copied(slice) => {
+                        debug_assert_eq!(slice.len(), buf.len());
+                    }
+                }
+                buf
+            }
+        };
+
+        debug_assert_eq!(buf.len(), len);
+
+        self.record_growth(0, &buf);
+
+        self.pos += len;
+
+        Ok(buf)
+    }
+
+    fn copy_value_into<W>(&mut self, writer: &mut W, scratch: &mut Vec<u8>) -> Result<()>
+    where
+        W: Write,
+    {
+        // Copying recurses into nested values, so needs bounding even if decoding doesn't:
+        let max_depth = self.config.max_depth.unwrap_or(DEFAULT_MAX_DEPTH);
+
+        self.nested_within(Some(max_depth), |decoder| {
+            decoder.copy_nested_value_into(writer, scratch)
+        })
+    }
+
+    fn copy_nested_value_into<W>(&mut self, writer: &mut W, scratch: &mut Vec<u8>) -> Result<()>
+    where
+        W: Write,
+    {
+        let pos = self.pos;
+        let byte = self.pull_byte()?;
+        writer.write(&[byte])?;
+
+        if byte == SeqHeader::STREAM_START {
+            loop {
+                if self.decode_seq_stream_end()? {
+                    writer.write(&[SeqHeader::STREAM_END])?;
+                    return Ok(());
+                }
+
+                self.copy_value_into(writer, scratch)?;
+            }
+        }
+
+        if byte == MapHeader::FRONT_CODED
+            || byte == SeqHeader::COLUMNAR
+            || byte == SeqHeader::TABULAR
+            || byte == SeqHeader::EXTENSION
+            || SeqHeader::is_collectable(byte)
+        {
+            // Front-coded maps, columnar and tabular sequences, extension values, dictionary references
+            // and values with reserved headers are prefixed with the length of their body:
+            let mut len_bytes = Vec::new();
+            self.copy_value_into(&mut VecWriter::new(&mut len_bytes), scratch)?;
+            writer.write(&len_bytes)?;
+
+            let len = Decoder::from_reader(SliceReader::new(&len_bytes))
+                .decode_u64()
+                .map_err(|err| err.offset_pos(pos + 1))?;
+            let len: usize = len
+                .try_into()
+                .map_err(|_| Error::length_exceeds_platform(len, Some(pos + 1)))?;
+
+            return self.copy_bytes_into(len, writer, scratch);
+        }
+
+        let spec = HeaderSpec::for_byte(byte);
+
+        let compact = spec
+            .compact
+            .filter(|compact| (byte & compact.variant_bit) != 0b0);
+
+        let content: u128 = match (compact, spec.extended) {
+            (Some(compact), _) => (byte & compact.content_bits).into(),
+            (None, Some(extended)) => {
+                let width = extended
+                    .width_rule
+                    .width_for_bits(byte & extended.width_bits);
+
+                const MAX_WIDTH: usize = 16;
+                let mut padded_be_bytes: [u8; MAX_WIDTH] = [0b0; MAX_WIDTH];
+                let be_bytes = &mut padded_be_bytes[(MAX_WIDTH - width)..];
+                self.pull_bytes_into(be_bytes)?;
+                writer.write(be_bytes)?;
+
+                u128::from_be_bytes(padded_be_bytes)
+            }
+            (None, None) => 0,
+        };
+
+        if spec.body == BodySpec::None {
+            return Ok(());
+        }
+
+        // Lengths are at most 8 bytes wide, so `content` fits into a `u64`:
+        let len: usize = content
+            .try_into()
+            .map_err(|_| Error::length_exceeds_platform(content as u64, Some(pos)))?;
+
+        match spec.body {
+            BodySpec::None => {}
+            BodySpec::Bytes => self.copy_bytes_into(len, writer, scratch)?,
+            BodySpec::Values => {
+                for _ in 0..len {
+                    self.copy_value_into(writer, scratch)?;
+                }
+            }
+            BodySpec::Entries => {
+                for _ in 0..len {
+                    self.copy_value_into(writer, scratch)?; // key
+                    self.copy_value_into(writer, scratch)?; // value
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies `len` bytes verbatim to `writer`, in chunks,
+    /// to avoid buffering large bodies in their entirety.
+    fn copy_bytes_into<W>(
+        &mut self,
+        len: usize,
+        writer: &mut W,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()>
+    where
+        W: Write,
+    {
+        const MAX_CHUNK_LENGTH: usize = 8192;
+
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk_len = remaining.min(MAX_CHUNK_LENGTH);
+            scratch.clear();
+            let bytes = self.pull_bytes(chunk_len, scratch)?;
+            writer.write(&bytes)?;
+            remaining -= chunk_len;
+        }
+
+        Ok(())
+    }
+
+    fn walk_value_with<V>(&mut self, visitor: &mut V, scratch: &mut Vec<u8>) -> Result<()>
+    where
+        V: ?Sized + ValueVisitor,
+    {
+        self.nested(|decoder| decoder.walk_nested_value_with(visitor, scratch))
+    }
+
+    fn walk_nested_value_with<V>(&mut self, visitor: &mut V, scratch: &mut Vec<u8>) -> Result<()>
+    where
+        V: ?Sized + ValueVisitor,
+    {
+        match self.peek_marker()? {
+            Marker::Int => visitor.visit_int(self.decode_int_value()?),
+            Marker::String => {
+                scratch.clear();
+                let value = self.decode_str(scratch)?;
+                visitor.visit_str(&value)
+            }
+            Marker::Seq if self.peek_columnar_seq()? || self.peek_tabular_seq()? => {
+                self.walk_keyed_seq_with(visitor, scratch)
+            }
+            Marker::Seq => {
+                let len = self.decode_seq_start()?;
+                let walk = visitor.visit_seq_start(len)?;
+
+                match len {
+                    Some(len) => {
+                        for _ in 0..len {
+                            match walk {
+                                Walk::Enter => self.walk_value_with(visitor, scratch)?,
+                                Walk::Skip => self.skip_value()?,
+                            }
+                        }
+                    }
+                    None => {
+                        while !self.decode_seq_stream_end()? {
+                            match walk {
+                                Walk::Enter => self.walk_value_with(visitor, scratch)?,
+                                Walk::Skip => self.skip_value()?,
+                            }
+                        }
+                    }
+                }
+
+                visitor.visit_seq_end()
+            }
+            Marker::Map if self.peek_front_coded_map()? => {
+                self.walk_front_coded_map_with(visitor, scratch)
+            }
+            Marker::Map => {
+                let len = self.decode_map_header()?.len();
+                let walk = visitor.visit_map_start(len)?;
+
+                for _ in 0..len {
+                    match walk {
+                        Walk::Enter => {
+                            self.walk_value_with(visitor, scratch)?; // key
+                            self.walk_value_with(visitor, scratch)?; // value
+                        }
+                        Walk::Skip => {
+                            self.skip_value()?; // key
+                            self.skip_value()?; // value
+                        }
+                    }
+                }
+
+                visitor.visit_map_end()
+            }
+            Marker::Float => visitor.visit_float(self.decode_float_value()?),
+            Marker::Bytes => {
+                scratch.clear();
+                let value = self.decode_bytes(scratch)?;
+                visitor.visit_bytes(&value)
+            }
+            Marker::Bool => visitor.visit_bool(self.decode_bool()?),
+            Marker::Unit => {
+                self.decode_unit()?;
+                visitor.visit_unit()
+            }
+            Marker::Null => {
+                self.decode_null()?;
+                visitor.visit_null()
+            }
+        }
+    }
+
     #[inline]
     fn pull_len_bytes(&mut self, width: u8) -> Result<usize> {
         let pos = self.pos;
 
+        let len = self.pull_len_bytes_u64(width)?;
+
+        len.try_into()
+            .map_err(|_| Error::length_exceeds_platform(len, Some(pos)))
+    }
+
+    #[inline]
+    fn pull_len_bytes_u64(&mut self, width: u8) -> Result<u64> {
         const MAX_WIDTH: usize = 8;
         let mut padded_be_bytes: [u8; MAX_WIDTH] = [0b0; MAX_WIDTH];
         self.pull_bytes_into(&mut padded_be_bytes[(MAX_WIDTH - (width as usize))..])?;
 
-        u64::from_be_bytes(padded_be_bytes)
-            .try_into()
-            .map_err(|_| Error::number_out_of_range(Some(pos)))
+        Ok(u64::from_be_bytes(padded_be_bytes))
+    }
+
+    /// Records an allocation, if `vec` has grown beyond `capacity`.
+    #[inline]
+    fn record_growth<T>(&mut self, capacity: usize, vec: &Vec<T>) {
+        if let Some(stats) = self.stats.as_mut() {
+            if vec.capacity() > capacity {
+                stats.record_allocation(vec.capacity() * core::mem::size_of::<T>());
+            }
+        }
+    }
+
+    /// Pulls the header byte and body length of a value prefixed with the
+    /// length of its body, returning the position at which its body ends.
+    fn decode_body_start(&mut self) -> Result<usize> {
+        self.pull_byte()?;
+
+        let pos = self.pos;
+        let body_len = self.decode_usize()?;
+
+        self.pos
+            .checked_add(body_len)
+            .ok_or_else(|| Error::length_exceeds_platform(body_len as u64, Some(pos)))
+    }
+
+    /// Checks that a body ended at `body_end`, as declared by its length.
+    fn check_body_end(&mut self, body_end: usize) -> Result<()> {
+        if self.pos != body_end {
+            return Err(Error::invalid_length(
+                format!("body ending at {}", self.pos),
+                format!("body ending at {body_end}"),
+                Some(self.pos),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Decodes an unsigned integer value, as a length.
+    fn decode_usize(&mut self) -> Result<usize> {
+        let pos = self.pos;
+        let len = self.decode_u64()?;
+
+        len.try_into()
+            .map_err(|_| Error::length_exceeds_platform(len, Some(pos)))
+    }
+
+    /// Returns the capacity to pre-allocate for a container of `len` items,
+    /// each of which takes up at least `min_item_width` bytes in the input.
+    ///
+    /// A malicious header might declare a length far exceeding the actual input,
+    /// so rather than trusting `len` the capacity gets capped by the number of bytes
+    /// left to read (or by a fixed limit, if unknown), with any remaining
+    /// items growing the container adaptively as they get decoded.
+    #[inline]
+    fn capacity_for(&self, len: usize, min_item_width: usize) -> usize {
+        const MAX_CAPACITY_IF_UNKNOWN: usize = 1024;
+
+        let max_capacity = self
+            .reader
+            .remaining_len_hint()
+            .map_or(MAX_CAPACITY_IF_UNKNOWN, |remaining| {
+                remaining / min_item_width
+            });
+
+        len.min(max_capacity)
     }
 }
 
@@ -206,7 +1049,19 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::{error::ErrorCode, io::SliceReader};
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::{DuplicateKeyPolicy, EncoderConfig, MapKeyCoding, PackingMode, SeqCoding},
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{BufferedReader, SliceReader, StdIoReader, VecWriter},
+        value::{
+            BytesValue, ExtValue, FloatValue, IntValue, Map, MapValue, NullValue, SeqValue,
+            StringValue, UnknownValue,
+        },
+    };
 
     use super::*;
 
@@ -247,7 +1102,7 @@ mod test {
 
         let mut buf = vec![];
         decoder.pull_bytes_into(&mut buf).unwrap();
-        assert_eq!(buf, &[]);
+        assert!(buf.is_empty());
         assert_eq!(decoder.pos, 0);
 
         let mut buf = vec![0];
@@ -274,7 +1129,7 @@ mod test {
         assert_eq!(decoder.pos, 0);
 
         let reference = decoder.pull_bytes(0, &mut scratch).unwrap();
-        assert_eq!(reference.as_ref(), &[]);
+        assert!(reference.as_ref().is_empty());
         assert_eq!(decoder.pos, 0);
 
         scratch.clear();
@@ -295,4 +1150,1289 @@ mod test {
         assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
         assert_eq!(decoder.pos, 3);
     }
+
+    #[test]
+    fn pull_bytes_force_owned() {
+        let bytes = SliceReader::new(&[1, 2, 3]);
+        let config = DecoderConfig::default().with_force_owned(true);
+        let mut decoder = Decoder::new(bytes, config);
+        let mut scratch = vec![];
+
+        let reference = decoder.pull_bytes(2, &mut scratch).unwrap();
+        assert!(matches!(reference, Reference::Copied(&[1, 2])));
+        assert_eq!(decoder.pos, 2);
+        assert_eq!(scratch, [1, 2]);
+    }
+
+    #[test]
+    fn pull_bytes_buf() {
+        let bytes: &[u8] = &[1, 2, 3];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(bytes));
+        assert_eq!(decoder.pull_bytes_buf(0).unwrap(), Vec::<u8>::new());
+        assert_eq!(decoder.pull_bytes_buf(2).unwrap(), vec![1, 2]);
+        assert_eq!(decoder.pos, 2);
+        let error_code = decoder.pull_bytes_buf(2).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(bytes));
+        assert_eq!(decoder.pull_bytes_buf(3).unwrap(), vec![1, 2, 3]);
+        assert_eq!(decoder.pos, 3);
+    }
+
+    #[test]
+    fn peek_value_kind() {
+        let values = [
+            Value::from(IntValue::from(-3_i8)),
+            Value::from(StringValue::from("a".to_owned())),
+            Value::from(SeqValue::from(vec![])),
+            Value::from(MapValue::from(Map::new())),
+            Value::from(FloatValue::from(1.5_f32)),
+            Value::from(BytesValue::from(vec![1])),
+            Value::from(NullValue),
+            Value::from(ExtValue::new(64, vec![])),
+        ];
+
+        for value in values {
+            let mut encoded = Vec::new();
+            Encoder::from_writer(VecWriter::new(&mut encoded))
+                .encode_value(&value)
+                .unwrap();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            assert_eq!(decoder.peek_value_kind().unwrap(), value.kind());
+            assert_eq!(decoder.decode_any().unwrap(), value);
+        }
+
+        // Reserved headers, regardless of the unknown header policy:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&[0x2F, 0xC0]));
+        assert_eq!(decoder.peek_value_kind().unwrap(), ValueKind::Unknown);
+        assert!(decoder.decode_any().is_err());
+    }
+
+    #[test]
+    fn peek_header() {
+        let values = [
+            Value::from(StringValue::from("x".repeat(300))),
+            Value::from(IntValue::from(u64::MAX)),
+            Value::from(SeqValue::from(vec![Value::from(NullValue); 40])),
+            Value::from(BytesValue::from(vec![0; 70_000])),
+        ];
+
+        for value in values {
+            let mut encoded = Vec::new();
+            Encoder::from_writer(VecWriter::new(&mut encoded))
+                .encode_value(&value)
+                .unwrap();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let header = decoder.peek_header().unwrap();
+            assert_eq!(decoder.pos(), 0);
+            assert_eq!(decoder.decode_header().unwrap(), header);
+
+            let reader = BufferedReader::with_capacity(2, encoded.as_slice());
+            let mut decoder = Decoder::from_reader(reader);
+            assert_eq!(decoder.peek_header().unwrap(), header);
+            assert_eq!(decoder.decode_value().unwrap(), value);
+
+            let mut decoder = Decoder::from_reader(StdIoReader::new(encoded.as_slice()));
+            assert_eq!(decoder.peek_header().unwrap(), header);
+            assert_eq!(decoder.decode_value().unwrap(), value);
+        }
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&[0x41]));
+        let err = decoder.peek_header().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn decode_into_writer() {
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::String(StringValue::from("lilliput".repeat(2000))),
+            Value::Bytes(BytesValue::from(vec![42; 300])),
+            Value::Float(FloatValue::F64(1.5)),
+            Value::Int(IntValue::from(-1_000_000_i64)),
+        ]));
+
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_value(&value)
+            .unwrap();
+        // Trailing data, which is expected to be left untouched:
+        encoded.push(0b0000_0000);
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(&encoded[..]));
+        let mut copied: Vec<u8> = Vec::new();
+        let len = decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+
+        assert_eq!(len, encoded.len() - 1);
+        assert_eq!(copied, &encoded[..len]);
+        assert_eq!(decoder.pos, len);
+        decoder.decode_null().unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[..(len - 1)]));
+        let mut copied: Vec<u8> = Vec::new();
+        let error_code = decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap_err()
+            .code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn decode_into_writer_max_depth() {
+        // A million nested sequences, each holding just the next one:
+        let mut encoded = vec![0b0011_0001; 1_000_000];
+        encoded.push(0b1100_0001);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut copied: Vec<u8> = Vec::new();
+        let err = decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+        assert_eq!(err.pos(), Some(usize::from(DEFAULT_MAX_DEPTH)));
+
+        let config = DecoderConfig::default().with_max_depth(Some(2));
+        let mut decoder = Decoder::new(SliceReader::new(&encoded[999_998..]), config);
+        let mut copied: Vec<u8> = Vec::new();
+        let len = decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+        assert_eq!(len, 3);
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded[999_997..]), config);
+        let err = decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+    }
+
+    #[test]
+    fn unknown_headers() {
+        let unknown = Value::Unknown(UnknownValue::new(0b0010_1111, vec![1, 2, 3]));
+        let value = Value::Seq(SeqValue::from(vec![
+            unknown.clone(),
+            Value::Int(IntValue::from(42_u8)),
+        ]));
+
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_value(&value)
+            .unwrap();
+        assert_eq!(
+            encoded[..6],
+            [0b0011_0010, 0b0010_1111, 0b1100_0011, 1, 2, 3]
+        );
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::ReservedType);
+
+        let config = DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Collect);
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        decoder.decode_seq_header().unwrap();
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.decode_u8().unwrap(), 42);
+
+        // Only reserved headers can be encoded as unknown values:
+        let invalid = Value::Unknown(UnknownValue::new(SeqHeader::STREAM_END, vec![]));
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        assert!(encoder.encode_value(&invalid).is_err());
+    }
+
+    #[test]
+    fn decode_value_lossy() {
+        let unknown = Value::Unknown(UnknownValue::new(0b0010_1111, vec![1, 2, 3]));
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::String(StringValue::from("ab".to_owned())),
+            unknown.clone(),
+        ]));
+
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_value(&value)
+            .unwrap();
+        // Corrupt the string's second byte:
+        assert_eq!(encoded[1..4], [0x62, b'a', b'b']);
+        encoded[3] = 0xFF;
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_value().unwrap_err().code(), ErrorCode::Utf8);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(
+            decoder.decode_value_lossy().unwrap(),
+            Value::Seq(SeqValue::from(vec![
+                Value::String(StringValue::from("a\u{FFFD}".to_owned())),
+                unknown,
+            ]))
+        );
+        assert_eq!(decoder.pos, encoded.len());
+
+        // The decoder's config remains unaffected:
+        assert_eq!(
+            decoder.config().unknown_headers,
+            UnknownHeaderPolicy::Reject
+        );
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[..3]));
+        assert_eq!(
+            decoder.decode_value_lossy().unwrap_err().code(),
+            ErrorCode::UnexpectedEndOfFile
+        );
+    }
+
+    #[test]
+    fn expect_preamble() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_preamble().unwrap();
+        encoder.encode_u8(1).unwrap();
+        assert_eq!(encoded, [b'L', b'L', b'P', b'T', 1, 0, 0xC1]);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let preamble = decoder.expect_preamble().unwrap();
+        assert_eq!(preamble.version, 1);
+        assert_eq!(preamble.profile, Profile::None);
+        assert_eq!(decoder.decode_u8().unwrap(), 1);
+
+        // Documents without a preamble get rejected:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[6..]));
+        assert_eq!(
+            decoder.expect_preamble().unwrap_err().code(),
+            ErrorCode::UnexpectedEndOfFile
+        );
+        let mut decoder = Decoder::from_reader(SliceReader::new(&[0xC1; 8]));
+        let error = decoder.expect_preamble().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+        assert_eq!(error.pos(), Some(0));
+
+        // … as do ones of unsupported format versions:
+        encoded[4] = 2;
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.expect_preamble().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+        assert_eq!(error.pos(), Some(4));
+
+        // … or of profiles less strict than the decoder's:
+        encoded[4] = 1;
+        let config = DecoderConfig::default().with_profile(Profile::Weak);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let error = decoder.expect_preamble().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+        assert_eq!(error.pos(), Some(5));
+    }
+
+    #[test]
+    fn front_coded_maps() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+        let encode = |value: &Value, map_keys: MapKeyCoding| {
+            let config = EncoderConfig::default().with_map_keys(map_keys);
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config)
+                .encode_value(value)
+                .unwrap();
+            encoded
+        };
+
+        let map: Map = [
+            "cpu.load.1m",
+            "cpu.load.5m",
+            "cpu.user",
+            "h\u{e8}llo",
+            "h\u{e9}llo",
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, key)| (string(key), Value::Int(IntValue::from(index as u8))))
+        .collect();
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::Map(MapValue::from(map)),
+            Value::Null(NullValue),
+        ]));
+
+        let plain = encode(&value, MapKeyCoding::Plain);
+        let encoded = encode(&value, MapKeyCoding::FrontCoded);
+        assert_eq!(encoded[1], MapHeader::FRONT_CODED);
+        assert!(encoded.len() < plain.len());
+
+        // Shared prefixes never split characters (e.g. `é` and `è` share their first byte):
+        let tail = [0xC1, 0x65, 0xC3, 0xA9, b'l', b'l', b'o', 0xC4];
+        assert!(encoded[..encoded.len() - 1].ends_with(&tail));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.decode_seq_header().unwrap();
+        decoder.skip_value().unwrap();
+        decoder.decode_null().unwrap();
+
+        // Entries can be decoded one by one:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.decode_seq_header().unwrap();
+        let mut keys = decoder.decode_front_coded_map_start().unwrap();
+        assert_eq!(keys.len(), 5);
+        let mut entries = Vec::new();
+        for _ in 0..keys.len() {
+            let key = decoder
+                .decode_front_coded_map_key(&mut keys)
+                .unwrap()
+                .to_owned();
+            entries.push((key, decoder.decode_u8().unwrap()));
+        }
+        decoder.decode_front_coded_map_end(keys).unwrap();
+        decoder.decode_null().unwrap();
+        assert_eq!(entries[2], ("cpu.user".to_owned(), 2));
+        assert_eq!(entries[4], ("h\u{e9}llo".to_owned(), 4));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut copied: Vec<u8> = Vec::new();
+        decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+        assert_eq!(copied, encoded);
+
+        // Maps with fewer than two keys, or non-string keys are not front-coded:
+        let map = Map::from_iter([(string("a"), Value::Null(NullValue))]);
+        let value = Value::Map(MapValue::from(map.clone()));
+        assert_eq!(
+            encode(&value, MapKeyCoding::FrontCoded),
+            encode(&value, MapKeyCoding::Plain)
+        );
+
+        let mut map = map;
+        map.insert(Value::Int(IntValue::from(1_u8)), Value::Null(NullValue));
+        let value = Value::Map(MapValue::from(map));
+        assert_eq!(
+            encode(&value, MapKeyCoding::FrontCoded),
+            encode(&value, MapKeyCoding::Plain)
+        );
+    }
+
+    #[test]
+    fn invalid_front_coded_maps() {
+        let decode = |bytes: &[u8]| {
+            let mut decoder = Decoder::from_reader(SliceReader::new(bytes));
+            decoder.decode_value().unwrap_err().code()
+        };
+
+        // {"ab": null, "ac": null}, with the second key's prefix length exceeding the first key:
+        let bytes = [
+            0x2A, 0xCA, 0xC2, 0xC0, 0x62, b'a', b'b', 0x00, 0xC3, 0x61, b'c', 0x00,
+        ];
+        assert_eq!(decode(&bytes), ErrorCode::InvalidLength);
+
+        // ... with a body length one byte longer than the body:
+        let bytes = [
+            0x2A, 0xCB, 0xC2, 0xC0, 0x62, b'a', b'b', 0x00, 0xC1, 0x61, b'c', 0x00, 0x00,
+        ];
+        assert_eq!(decode(&bytes), ErrorCode::InvalidLength);
+
+        // ... with a truncated body:
+        assert_eq!(decode(&bytes[..8]), ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn columnar_seqs() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+        let encode = |value: &Value, seqs: SeqCoding| {
+            let config = EncoderConfig::default().with_seqs(seqs);
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config)
+                .encode_value(value)
+                .unwrap();
+            encoded
+        };
+        let sample = |time: u16, value: f32| {
+            let map = Map::from_iter([
+                (string("time"), Value::Int(IntValue::from(time))),
+                (string("value"), Value::Float(FloatValue::from(value))),
+            ]);
+            Value::Map(MapValue::from(map))
+        };
+
+        let samples = Value::Seq(SeqValue::from(vec![
+            sample(1000, 0.5),
+            sample(1001, 0.25),
+            sample(1002, 0.125),
+        ]));
+        let value = Value::Seq(SeqValue::from(vec![samples, Value::Null(NullValue)]));
+
+        let plain = encode(&value, SeqCoding::Plain);
+        let encoded = encode(&value, SeqCoding::Columnar);
+        assert_eq!(encoded[1], SeqHeader::COLUMNAR);
+        assert!(encoded.len() < plain.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.decode_seq_header().unwrap();
+        assert_eq!(decoder.peek_marker().unwrap(), Marker::Seq);
+        decoder.skip_value().unwrap();
+        decoder.decode_null().unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut copied: Vec<u8> = Vec::new();
+        decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+        assert_eq!(copied, encoded);
+
+        #[derive(Default)]
+        struct Ints(Vec<IntValue>);
+
+        impl ValueVisitor for Ints {
+            fn visit_int(&mut self, value: IntValue) -> Result<()> {
+                self.0.push(value);
+                Ok(())
+            }
+        }
+
+        let mut ints = Ints::default();
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.walk_value(&mut ints).unwrap();
+        assert_eq!(ints.0, [1000_u16, 1001, 1002].map(IntValue::from).to_vec());
+
+        // Sequences with fewer than two items, non-map items, or differing keys are not columnar:
+        for items in [
+            vec![sample(1000, 0.5)],
+            vec![sample(1000, 0.5), Value::Null(NullValue)],
+            vec![sample(1000, 0.5), Value::Map(MapValue::default())],
+            vec![
+                Value::Map(MapValue::default()),
+                Value::Map(MapValue::default()),
+            ],
+        ] {
+            let value = Value::Seq(SeqValue::from(items));
+            assert_eq!(
+                encode(&value, SeqCoding::Columnar),
+                encode(&value, SeqCoding::Plain)
+            );
+        }
+
+        // Duplicate keys get rejected:
+        let bytes = [
+            0x2C, 0xCA, 0xC1, 0xC2, 0x61, b'a', 0x61, b'a', 0x31, 0x00, 0x31, 0x00,
+        ];
+        let mut decoder = Decoder::from_reader(SliceReader::new(&bytes));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidValue);
+
+        // Columns need to hold one value per map:
+        let bytes = [0x2C, 0xC7, 0xC1, 0xC1, 0x61, b'a', 0x32, 0x00, 0x00];
+        let mut decoder = Decoder::from_reader(SliceReader::new(&bytes));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidLength);
+    }
+
+    #[test]
+    fn columnar_seq_columns() {
+        let string = |value: String| Value::String(StringValue::from(value));
+        let rows: Vec<Value> = (0..16_u8)
+            .map(|index| {
+                let map = Map::from_iter([
+                    (
+                        string("index".to_owned()),
+                        Value::Int(IntValue::from(index)),
+                    ),
+                    (string("label".to_owned()), string(format!("#{index}"))),
+                ]);
+                Value::Map(MapValue::from(map))
+            })
+            .collect();
+
+        let config = EncoderConfig::default().with_seqs(SeqCoding::Columnar);
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::new(VecWriter::new(&mut encoded), config)
+            .encode_seq(&rows)
+            .unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let columnar = decoder.decode_columnar_seq_start().unwrap();
+        assert_eq!((columnar.len(), columnar.keys_len()), (16, 2));
+
+        let keys = [
+            decoder.decode_value().unwrap(),
+            decoder.decode_value().unwrap(),
+        ];
+        columnar.check_keys(&keys).unwrap();
+        assert_eq!(keys, ["index", "label"].map(|key| string(key.to_owned())));
+
+        // Columns are sequences of their own, with integer columns decodable in bulk:
+        decoder.decode_columnar_seq_column(&columnar).unwrap();
+        let indices: Vec<u8> = (0..16).map(|_| decoder.decode_u8().unwrap()).collect();
+        assert_eq!(indices, (0..16).collect::<Vec<u8>>());
+
+        decoder.skip_value().unwrap();
+        decoder.decode_columnar_seq_end(columnar).unwrap();
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let columnar = decoder.decode_columnar_seq_start().unwrap();
+        decoder.skip_value().unwrap();
+        decoder.skip_value().unwrap();
+        let indices: Vec<u8> = decoder.decode_compact_int_seq().unwrap();
+        assert_eq!(indices, (0..16).collect::<Vec<u8>>());
+        decoder.skip_value().unwrap();
+        decoder.decode_columnar_seq_end(columnar).unwrap();
+    }
+
+    #[test]
+    fn tabular_seqs() {
+        let string = |value: String| Value::String(StringValue::from(value));
+        let rows: Vec<Value> = (0..16_u8)
+            .map(|index| {
+                let map = Map::from_iter([
+                    (
+                        string("index".to_owned()),
+                        Value::Int(IntValue::from(index)),
+                    ),
+                    (string("label".to_owned()), string(format!("#{index}"))),
+                ]);
+                Value::Map(MapValue::from(map))
+            })
+            .collect();
+        let value = Value::Seq(SeqValue::from(rows));
+
+        let encode = |seqs: SeqCoding| {
+            let config = EncoderConfig::default().with_seqs(seqs);
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config)
+                .encode_value(&value)
+                .unwrap();
+            encoded
+        };
+
+        let encoded = encode(SeqCoding::Tabular);
+        assert_eq!(encoded[0], SeqHeader::TABULAR);
+        assert!(encoded.len() < encode(SeqCoding::Plain).len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.peek_marker().unwrap(), Marker::Seq);
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut copied: Vec<u8> = Vec::new();
+        decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+        assert_eq!(copied, encoded);
+
+        // Rows get decoded one by one, following the keys:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let tabular = decoder.decode_tabular_seq_start().unwrap();
+        assert_eq!((tabular.len(), tabular.keys_len()), (16, 2));
+
+        let keys = [
+            decoder.decode_value().unwrap(),
+            decoder.decode_value().unwrap(),
+        ];
+        tabular.check_keys(&keys).unwrap();
+        assert_eq!(keys, ["index", "label"].map(|key| string(key.to_owned())));
+
+        for index in 0..16 {
+            assert_eq!(decoder.decode_u8().unwrap(), index);
+            assert_eq!(decoder.decode_string().unwrap(), format!("#{index}"));
+        }
+        decoder.decode_tabular_seq_end(tabular).unwrap();
+        assert_eq!(decoder.pos, encoded.len());
+
+        // Duplicate keys get rejected:
+        let bytes = [0x2E, 0xC8, 0xC1, 0xC2, 0x61, b'a', 0x61, b'a', 0xC1, 0xC2];
+        let mut decoder = Decoder::from_reader(SliceReader::new(&bytes));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidValue);
+
+        // Rows need to hold one value per key, within the body:
+        let bytes = [0x2E, 0xC5, 0xC2, 0xC1, 0x61, b'a', 0xC1, 0xC2];
+        let mut decoder = Decoder::from_reader(SliceReader::new(&bytes));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidLength);
+    }
+
+    #[test]
+    fn value_refs() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+
+        let map: Map = [
+            (string("name"), string("lilliput")),
+            (
+                string("blob"),
+                Value::Bytes(BytesValue::from(vec![1, 2, 3])),
+            ),
+            (
+                string("items"),
+                Value::Seq(SeqValue::from(vec![string("a"), Value::Null(NullValue)])),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let value = Value::Map(MapValue::from(map));
+
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_value(&value)
+            .unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let value_ref = decoder.decode_value_ref().unwrap();
+        assert_eq!(decoder.pos, encoded.len());
+        assert!(value_ref.is_borrowed());
+        assert_eq!(value_ref.get("name").unwrap().as_str(), Some("lilliput"));
+        assert_eq!(
+            value_ref.get("blob").unwrap().as_bytes(),
+            Some(&[1, 2, 3][..])
+        );
+        assert_eq!(value_ref.get("items").unwrap().as_seq().unwrap().len(), 2);
+        assert_eq!(value_ref.to_value(), value);
+
+        // Readers which can't lend out their bytes produce copies:
+        let mut decoder = Decoder::from_reader(StdIoReader::new(&encoded[..]));
+        let value_ref = decoder.decode_value_ref().unwrap();
+        assert!(!value_ref.is_borrowed());
+        assert_eq!(value_ref.to_value(), value);
+
+        // Front-coded keys need decoding, so get copied:
+        let config = EncoderConfig::default().with_map_keys(MapKeyCoding::FrontCoded);
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::new(VecWriter::new(&mut encoded), config)
+            .encode_value(&value)
+            .unwrap();
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let value_ref = decoder.decode_value_ref().unwrap();
+        assert!(!value_ref.is_borrowed());
+        assert_eq!(value_ref.to_value(), value);
+    }
+
+    #[cfg(feature = "preserve_order")]
+    #[test]
+    fn preserve_order() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+
+        let map: Map = ["c", "a", "b"]
+            .into_iter()
+            .map(|key| (string(key), Value::Null(NullValue)))
+            .collect();
+        let value = Value::Map(MapValue::from(map));
+
+        for map_keys in [MapKeyCoding::Plain, MapKeyCoding::FrontCoded] {
+            let config = EncoderConfig::default().with_map_keys(map_keys);
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config.clone())
+                .encode_value(&value)
+                .unwrap();
+
+            let decoded = Decoder::from_reader(SliceReader::new(&encoded))
+                .decode_value()
+                .unwrap();
+            let keys: Vec<_> = decoded.as_map().unwrap().as_map_ref().keys().collect();
+            assert_eq!(keys, [&string("c"), &string("a"), &string("b")]);
+
+            let mut reencoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut reencoded), config)
+                .encode_value(&decoded)
+                .unwrap();
+            assert_eq!(reencoded, encoded);
+        }
+    }
+
+    #[test]
+    fn dictionary_refs() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+        let dictionary: Dictionary = ["temperature", "humidity"].into_iter().collect();
+
+        let value = Value::Seq(SeqValue::from(vec![
+            string("humidity"),
+            string("pressure"),
+            string("humidity"),
+        ]));
+
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .with_dictionary(dictionary.clone())
+            .encode_value(&value)
+            .unwrap();
+        assert_eq!(encoded[1..4], [StringHeader::DICTIONARY_REF, 0xC1, 0xC1]);
+
+        let mut decoder =
+            Decoder::from_reader(SliceReader::new(&encoded)).with_dictionary(dictionary.clone());
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.pos, encoded.len());
+
+        let mut decoder =
+            Decoder::from_reader(SliceReader::new(&encoded)).with_dictionary(dictionary);
+        decoder.decode_seq_header().unwrap();
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.peek_marker().unwrap(), Marker::String);
+        assert!(!decoder.peek_dictionary_ref().unwrap());
+        assert_eq!(decoder.decode_string().unwrap(), "pressure");
+        let mut scratch = Vec::new();
+        assert_eq!(&*decoder.decode_str(&mut scratch).unwrap(), "humidity");
+
+        // Without a dictionary ids are unknown:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidValue);
+
+        // ... unless collected as unknown values, which encode to the same bytes:
+        let config = DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Collect);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let collected = decoder.decode_value().unwrap();
+        assert_eq!(
+            collected[0],
+            Value::Unknown(UnknownValue::new(StringHeader::DICTIONARY_REF, vec![0xC1]))
+        );
+
+        let mut reencoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut reencoded))
+            .encode_value(&collected)
+            .unwrap();
+        assert_eq!(reencoded, encoded);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut copied: Vec<u8> = Vec::new();
+        decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+        assert_eq!(copied, encoded);
+    }
+
+    #[test]
+    fn decode_seq_len() {
+        // compact:
+        let bytes = SliceReader::new(&[0b0011_0011]);
+        let mut decoder = Decoder::from_reader(bytes);
+        assert_eq!(decoder.decode_seq_len().unwrap(), 3);
+
+        // extended, with a length exceeding `u32::MAX`:
+        let bytes = SliceReader::new(&[0b0010_0100, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        let mut decoder = Decoder::from_reader(bytes);
+        assert_eq!(decoder.decode_seq_len().unwrap(), 1 << 32);
+        assert_eq!(decoder.pos, 6);
+    }
+
+    #[test]
+    fn decode_map_len() {
+        // compact:
+        let bytes = SliceReader::new(&[0b0001_1010]);
+        let mut decoder = Decoder::from_reader(bytes);
+        assert_eq!(decoder.decode_map_len().unwrap(), 2);
+
+        // extended, with a length exceeding `u32::MAX`:
+        let bytes = SliceReader::new(&[0b0001_0100, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        let mut decoder = Decoder::from_reader(bytes);
+        assert_eq!(decoder.decode_map_len().unwrap(), 1 << 32);
+        assert_eq!(decoder.pos, 6);
+    }
+
+    #[test]
+    fn stats() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_seq_header(&SeqHeader::for_len(2, PackingMode::Optimal))
+            .unwrap();
+        encoder.encode_str("hello").unwrap();
+        encoder.encode_u8(42).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded)).with_stats();
+        decoder.decode_value().unwrap();
+
+        let stats = decoder.take_stats().unwrap();
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(
+            stats.allocated_bytes,
+            2 * core::mem::size_of::<Value>() + "hello".len()
+        );
+        assert_eq!(decoder.stats(), Some(&DecoderStats::default()));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.decode_value().unwrap();
+        assert_eq!(decoder.stats(), None);
+    }
+
+    #[test]
+    fn stats_streamed_growth() {
+        let encoded = [
+            SeqHeader::STREAM_START,
+            0xc1,
+            0xc2,
+            0xc3,
+            0xc4,
+            0xc5,
+            SeqHeader::STREAM_END,
+        ];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded)).with_stats();
+        let value = decoder.decode_value().unwrap();
+        let Value::Seq(seq) = value else {
+            panic!("expected seq");
+        };
+
+        let stats = decoder.stats().unwrap();
+        assert!(stats.allocations > 1);
+        assert!(stats.allocated_bytes >= seq.len() * core::mem::size_of::<Value>());
+    }
+
+    #[test]
+    fn capacity_for() {
+        let bytes = SliceReader::new(&[1, 2, 3, 4]);
+        let decoder = Decoder::from_reader(bytes);
+        assert_eq!(decoder.capacity_for(2, 1), 2);
+        assert_eq!(decoder.capacity_for(usize::MAX, 1), 4);
+        assert_eq!(decoder.capacity_for(usize::MAX, 2), 2);
+
+        let bytes: &[u8] = &[1, 2, 3, 4];
+        let decoder = Decoder::from_reader(StdIoReader::new(bytes));
+        assert_eq!(decoder.capacity_for(2, 1), 2);
+        assert_eq!(decoder.capacity_for(usize::MAX, 1), 1024);
+    }
+
+    #[test]
+    fn oversized_container_lengths() {
+        // extended, declaring `u32::MAX` items, while providing only one:
+        let encoded = [0b0010_0011, 0xff, 0xff, 0xff, 0xff, 0b0000_0000];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error_code = decoder.decode_seq().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(&encoded[..]));
+        let error_code = decoder.decode_seq().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+
+        // extended, declaring `u32::MAX` entries, while providing only one:
+        let encoded = [
+            0b0001_0011,
+            0xff,
+            0xff,
+            0xff,
+            0xff,
+            0b0000_0000,
+            0b0000_0000,
+        ];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error_code = decoder.decode_map().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn weak_profile() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+        let rows = (0..3_u8).map(|index| {
+            let map = Map::from_iter([
+                (string("sensor.id"), Value::Int(IntValue::from(index))),
+                (string("sensor.name"), Value::Null(NullValue)),
+            ]);
+            Value::Map(MapValue::from(map))
+        });
+        let value = Value::Seq(SeqValue::from(rows.collect::<Vec<_>>()));
+
+        let encode = |profile: Profile, seqs: SeqCoding| {
+            let config = EncoderConfig::default()
+                .with_profile(profile)
+                .with_seqs(seqs)
+                .with_map_keys(MapKeyCoding::FrontCoded);
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config)
+                .encode_value(&value)
+                .unwrap();
+            encoded
+        };
+        let weak_config = DecoderConfig::default().with_profile(Profile::Weak);
+
+        // Encoders fall back to plain codings:
+        for seqs in [SeqCoding::Columnar, SeqCoding::Tabular] {
+            let encoded = encode(Profile::Weak, seqs);
+            assert!(!encoded.contains(&SeqHeader::COLUMNAR));
+            assert!(!encoded.contains(&MapHeader::FRONT_CODED));
+            let mut decoder = Decoder::new(SliceReader::new(&encoded), weak_config);
+            assert!(!decoder.peek_tabular_seq().unwrap());
+            assert_eq!(decoder.decode_value().unwrap(), value);
+        }
+
+        // … while decoders reject documents exceeding their profile:
+        let encoded = encode(Profile::None, SeqCoding::Columnar);
+        assert_eq!(encoded[0], SeqHeader::COLUMNAR);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), weak_config);
+        let error = decoder.decode_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::ProfileViolation);
+        assert_eq!(error.pos(), Some(0));
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), weak_config);
+        assert_eq!(
+            decoder.skip_value().unwrap_err().code(),
+            ErrorCode::ProfileViolation
+        );
+
+        // Parts of the format without plain fallbacks get rejected by encoders:
+        let config = EncoderConfig::default().with_profile(Profile::Weak);
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config);
+        assert_eq!(
+            encoder.encode_seq_stream_start().unwrap_err().code(),
+            ErrorCode::ProfileViolation
+        );
+        assert_eq!(
+            encoder.encode_ext(1, b"ext").unwrap_err().code(),
+            ErrorCode::ProfileViolation
+        );
+        assert!(encoded.is_empty());
+    }
+
+    #[test]
+    fn utf8_policy() {
+        // The string `"a"`, followed by an invalid byte:
+        let encoded = [0x62, b'a', 0xFF];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_string().unwrap_err().code(), ErrorCode::Utf8);
+
+        let config = DecoderConfig::default().with_utf8(Utf8Policy::Replace);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(decoder.decode_string().unwrap(), "a\u{FFFD}");
+
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let mut scratch = Vec::new();
+        let string = decoder.decode_str(&mut scratch).unwrap();
+        assert_eq!(&*string, "a\u{FFFD}");
+    }
+
+    #[test]
+    fn duplicate_key_policy() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(2))
+            .unwrap();
+        encoder.encode_str("key").unwrap();
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_str("key").unwrap();
+        encoder.encode_u8(2).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let map = decoder.decode_map().unwrap();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.values().next(), Some(&Value::Int(IntValue::from(2_u8))));
+
+        let config = DecoderConfig::default().with_duplicate_keys(DuplicateKeyPolicy::FirstWins);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let map = decoder.decode_map().unwrap();
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.values().next(), Some(&Value::Int(IntValue::from(1_u8))));
+
+        let config = DecoderConfig::default().with_duplicate_keys(DuplicateKeyPolicy::Reject);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let error = decoder.decode_map().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+        assert_eq!(error.pos(), Some(6));
+    }
+
+    #[test]
+    fn canonical() {
+        let encode = |value: &Value, packing: PackingMode| {
+            let config = EncoderConfig::default().with_packing(packing);
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config)
+                .encode_value(value)
+                .unwrap();
+            encoded
+        };
+        let config = DecoderConfig::default().with_canonical(true);
+
+        // Values, and whether their unpacked encoding is canonical:
+        let values = [
+            (Value::Int(IntValue::from(1_u8)), false),
+            (Value::Int(IntValue::from(300_u32)), false),
+            (Value::Int(IntValue::from(-1000_i64)), false),
+            (Value::Float(FloatValue::from(1.5_f64)), false),
+            (Value::Float(FloatValue::from(0.1_f32)), true),
+        ];
+
+        for (value, is_canonical) in values {
+            let encoded = encode(&value, PackingMode::Optimal);
+            let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+            assert!(decoder.decode_value().is_ok());
+
+            let encoded = encode(&value, PackingMode::None);
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            assert!(decoder.decode_value().is_ok());
+            let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+            match decoder.decode_value() {
+                Ok(_) => assert!(is_canonical),
+                Err(error) => {
+                    assert!(!is_canonical);
+                    assert_eq!(error.code(), ErrorCode::InvalidValue);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth: usize| {
+            (0..depth).fold(Value::Null(NullValue), |value, _| {
+                Value::Seq(SeqValue::from(vec![value]))
+            })
+        };
+        let encode = |value: &Value| {
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::from_writer(VecWriter::new(&mut encoded))
+                .encode_value(value)
+                .unwrap();
+            encoded
+        };
+        let config = DecoderConfig::default().with_max_depth(Some(2));
+
+        let value = nested(2);
+        let encoded = encode(&value);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(decoder.decode_value().unwrap(), value);
+
+        let encoded = encode(&nested(3));
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        let error = decoder.decode_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DepthLimitExceeded);
+        assert_eq!(error.pos(), Some(2));
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(
+            decoder.skip_value().unwrap_err().code(),
+            ErrorCode::DepthLimitExceeded
+        );
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert!(decoder.decode_value().is_ok());
+    }
+
+    #[test]
+    fn streamed_seq() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_seq_stream_start().unwrap();
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_seq_stream_start().unwrap();
+        encoder.encode_seq_stream_end().unwrap();
+        encoder.encode_str("two").unwrap();
+        encoder.encode_seq_stream_end().unwrap();
+        encoder.encode_null().unwrap();
+
+        let expected = Value::from(SeqValue::from(vec![
+            Value::from(IntValue::from(1_u8)),
+            Value::from(SeqValue::from(vec![])),
+            Value::from(StringValue::from("two".to_owned())),
+        ]));
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_value().unwrap(), expected);
+        assert_eq!(decoder.pos(), encoded.len() - 1);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.skip_value().unwrap();
+        decoder.decode_null().unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut copied: Vec<u8> = Vec::new();
+        let len = decoder
+            .decode_into_writer(&mut VecWriter::new(&mut copied))
+            .unwrap();
+        assert_eq!(len, encoded.len() - 1);
+        assert_eq!(copied, &encoded[..len]);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_seq_start().unwrap(), None);
+        assert!(!decoder.decode_seq_stream_end().unwrap());
+        assert_eq!(decoder.decode_u8().unwrap(), 1);
+
+        // Methods requiring a known length:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error_code = decoder.decode_seq_header().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnknownLength);
+
+        // Stream end without a stream:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded[(len - 1)..]));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::ReservedType);
+    }
+
+    #[test]
+    fn decode_to_writer() {
+        // Long enough for chars to get split across chunks:
+        let string = "€".repeat(10_000);
+        let bytes = vec![0xff; 20_000];
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_str(&string).unwrap();
+        encoder.encode_bytes(&bytes).unwrap();
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(encoded.as_slice()));
+
+        let mut written: Vec<u8> = Vec::new();
+        let len = decoder.decode_str_to_writer(&mut written).unwrap();
+        assert_eq!(len, string.len());
+        assert_eq!(written, string.as_bytes());
+
+        let mut written: Vec<u8> = Vec::new();
+        let len = decoder.decode_bytes_to_writer(&mut written).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(written, bytes);
+
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn decode_str_to_writer_invalid_utf8() {
+        // "li\xffput":
+        let encoded = [0b0110_0110, b'l', b'i', 0xff, b'p', b'u', b't'];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let error = decoder.decode_str_to_writer(&mut Vec::new()).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Utf8);
+        assert_eq!(error.pos(), Some(3));
+    }
+
+    #[test]
+    fn skip_excessive_len() {
+        // A string claiming a length of `u64::MAX` bytes:
+        let encoded = [0b0100_0111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert!(decoder.skip_value().is_err());
+    }
+
+    #[test]
+    fn decode_compact_int_seq() {
+        // [1, 31, -1, 1000], with the last item being extended:
+        let encoded = [
+            0b0011_0100,
+            0b1100_0001,
+            0b1101_1111,
+            0b1110_0001,
+            0b1000_0001,
+            0x03,
+            0xe8,
+        ];
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let items: Vec<i32> = decoder.decode_compact_int_seq().unwrap();
+        assert_eq!(items, vec![1, 31, -1, 1000]);
+        assert_eq!(decoder.pos(), encoded.len());
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(&encoded[..]));
+        let items: Vec<i64> = decoder.decode_compact_int_seq().unwrap();
+        assert_eq!(items, vec![1, 31, -1, 1000]);
+        assert_eq!(decoder.pos(), encoded.len());
+
+        // Negative compact item, out of range for `u8`:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.decode_compact_int_seq::<u8>().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NumberOutOfRange);
+        assert_eq!(err.pos(), Some(3));
+
+        // Extended item, out of range for `i8`:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.decode_compact_int_seq::<i8>().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NumberOutOfRange);
+        assert_eq!(err.pos(), Some(4));
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn length_exceeds_platform() {
+        let bytes = SliceReader::new(&[0b0010_0100, 0x01, 0x00, 0x00, 0x00, 0x00]);
+        let mut decoder = Decoder::from_reader(bytes);
+        let error_code = decoder.decode_seq_header().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::LengthExceedsPlatform);
+    }
+
+    proptest! {
+        #[test]
+        fn skip_value_skips_exactly_one_value(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config);
+            encoder.encode_value(&value).unwrap();
+            encoder.encode_null().unwrap();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            decoder.skip_value().unwrap();
+            prop_assert_eq!(decoder.pos(), encoded.len() - 1);
+
+            let mut decoder = Decoder::from_reader(StdIoReader::new(encoded.as_slice()));
+            decoder.skip_value().unwrap();
+            decoder.decode_null().unwrap();
+        }
+
+        #[test]
+        fn decode_into_writer_copies_verbatim(value in Value::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::from_writer(VecWriter::new(&mut encoded))
+                .encode_value(&value)
+                .unwrap();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let mut copied: Vec<u8> = Vec::new();
+            let len = decoder
+                .decode_into_writer(&mut VecWriter::new(&mut copied))
+                .unwrap();
+
+            prop_assert_eq!(len, encoded.len());
+            prop_assert_eq!(copied, encoded);
+        }
+
+        #[test]
+        fn decode_compact_int_seq_roundtrip(
+            items in proptest::collection::vec(prop_oneof![-40_i64..40, any::<i64>()], 0..64),
+            packing_mode in PackingMode::arbitrary(),
+        ) {
+            let config = EncoderConfig::default().with_packing(packing_mode);
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config);
+            encoder
+                .encode_seq_header(&SeqHeader::for_len(items.len(), packing_mode))
+                .unwrap();
+            for &item in &items {
+                encoder.encode_i64(item).unwrap();
+            }
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let decoded: Vec<i64> = decoder.decode_compact_int_seq().unwrap();
+
+            prop_assert_eq!(decoded, items);
+            prop_assert_eq!(decoder.pos(), encoded.len());
+        }
+
+        #[test]
+        fn decode_value_ref_matches_decode_value(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config)
+                .encode_value(&value)
+                .unwrap();
+
+            let expected = Decoder::from_reader(SliceReader::new(&encoded)).decode_value().unwrap();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            let value_ref = decoder.decode_value_ref().unwrap();
+
+            prop_assert_eq!(value_ref.to_value(), expected);
+            prop_assert_eq!(decoder.pos(), encoded.len());
+        }
+    }
 }
@@ -0,0 +1,215 @@
+use tokio::io::{AsyncRead, AsyncReadExt as _};
+
+use crate::{
+    error::{Error, ErrorCode, Result},
+    io::SliceReader,
+    value::Value,
+};
+
+use super::Decoder;
+
+/// The minimum number of bytes to read from the underlying reader at once.
+const MIN_READ_LEN: usize = 4 * 1024;
+
+/// A decoder for decoding lilliput values from an async reader.
+///
+/// Only the bytes of the value currently being decoded (plus any bytes
+/// read ahead of it) get buffered, which allows for decoding a stream
+/// of values as they arrive, without buffering the stream as a whole.
+///
+/// Only available with the `async` feature enabled.
+#[derive(Debug)]
+pub struct AsyncDecoder<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    pos: usize,
+    is_eof: bool,
+}
+
+impl<R> AsyncDecoder<R> {
+    /// Creates a decoder from `reader`.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            pos: 0,
+            is_eof: false,
+        }
+    }
+
+    /// Returns the bytes read from the reader, but not decoded yet.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Returns the decoder's internal `reader`, consuming `self`.
+    ///
+    /// Any bytes read ahead get discarded, see `buffered()`.
+    pub fn into_reader(self) -> R {
+        self.reader
+    }
+
+    /// Returns the decoder's current read position,
+    /// i.e. the number of bytes decoded so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<R> AsyncDecoder<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Decodes the next `Value`.
+    ///
+    /// Returns an error if the reader ends before a complete value.
+    pub async fn decode_value(&mut self) -> Result<Value> {
+        self.next_value().await?.ok_or_else(Error::end_of_file)
+    }
+
+    /// Decodes the next `Value`, if any.
+    ///
+    /// Returns `None` if the reader ends right where the previous value ended,
+    /// and an error if it ends within a value.
+    pub async fn next_value(&mut self) -> Result<Option<Value>> {
+        let Some(len) = self.fill_value().await? else {
+            return Ok(None);
+        };
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&self.buffer[..len]));
+        let value = (decoder.decode_value()).map_err(|err| err.offset_pos(self.pos))?;
+
+        self.buffer.drain(..len);
+        self.pos += len;
+
+        Ok(Some(value))
+    }
+
+    /// Reads until the buffer contains a complete value, returning its length,
+    /// or `None` if the reader ended with an empty buffer.
+    async fn fill_value(&mut self) -> Result<Option<usize>> {
+        loop {
+            if !self.buffer.is_empty() {
+                let mut decoder = Decoder::from_reader(SliceReader::new(&self.buffer));
+
+                match decoder.skip_value() {
+                    Ok(()) => return Ok(Some(decoder.pos())),
+                    Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile && !self.is_eof => {}
+                    Err(err) => return Err(err.offset_pos(self.pos)),
+                }
+            } else if self.is_eof {
+                return Ok(None);
+            }
+
+            self.read_more().await?;
+        }
+    }
+
+    async fn read_more(&mut self) -> Result<()> {
+        // Growing reads with the buffer keeps the number of
+        // times a partial value gets re-scanned logarithmic:
+        let read_len = self.buffer.len().max(MIN_READ_LEN);
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + read_len, 0b0);
+
+        let result = self.reader.read(&mut self.buffer[len..]).await;
+        let read = *result.as_ref().unwrap_or(&0);
+        self.buffer.truncate(len + read);
+
+        self.is_eof = result.map_err(Error::io)? == 0;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        encoder::AsyncEncoder,
+        value::{SeqValue, StringValue},
+    };
+
+    use super::*;
+
+    /// Runs `future` to completion, which must never be pending,
+    /// as is the case for in-memory readers and writers.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(core::ptr::null(), &VTABLE),
+            |_| {},
+            |_| {},
+            |_| {},
+        );
+
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut context = Context::from_waker(&waker);
+
+        match pin!(future).poll(&mut context) {
+            Poll::Ready(output) => output,
+            Poll::Pending => panic!("in-memory future should not be pending"),
+        }
+    }
+
+    fn encode(values: &[Value]) -> Vec<u8> {
+        let mut encoder = AsyncEncoder::from_writer(Vec::new());
+        for value in values {
+            block_on(encoder.encode_value(value)).unwrap();
+        }
+        encoder.into_writer()
+    }
+
+    #[test]
+    fn stream_of_values() {
+        let values = vec![
+            Value::String(StringValue::from("lilliput".repeat(1000))),
+            Value::Seq(SeqValue::from(vec![Value::default(); 3])),
+        ];
+        let encoded = encode(&values);
+
+        let mut decoder = AsyncDecoder::from_reader(encoded.as_slice());
+        assert_eq!(block_on(decoder.decode_value()).unwrap(), values[0]);
+        assert_eq!(
+            block_on(decoder.next_value()).unwrap(),
+            Some(values[1].clone())
+        );
+        assert_eq!(block_on(decoder.next_value()).unwrap(), None);
+        assert_eq!(decoder.pos(), encoded.len());
+
+        let error_code = block_on(decoder.decode_value()).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn truncated() {
+        let encoded = encode(&[Value::String(StringValue::from("lilliput".to_owned()))]);
+
+        let mut decoder = AsyncDecoder::from_reader(&encoded[..encoded.len() - 1]);
+        let error_code = block_on(decoder.next_value()).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(values in proptest::collection::vec(Value::arbitrary(), 0..5)) {
+            let encoded = encode(&values);
+
+            let mut decoder = AsyncDecoder::from_reader(encoded.as_slice());
+            let mut decoded = Vec::new();
+            while let Some(value) = block_on(decoder.next_value()).unwrap() {
+                decoded.push(value);
+            }
+
+            prop_assert_eq!(decoded, values);
+        }
+    }
+}
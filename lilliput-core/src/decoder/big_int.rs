@@ -0,0 +1,39 @@
+use num_bigint::BigInt;
+
+use crate::{
+    error::Result,
+    marker::Marker,
+    value::{IntValue, BIG_INT_TAG},
+};
+
+use super::{Decoder, Read};
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a big integer, as encoded by `Encoder::encode_big_int()`,
+    /// or from a plain integer value.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_big_int(&mut self) -> Result<BigInt> {
+        if self.peek_marker()? == Marker::Int {
+            let int = self.decode_int_value()?;
+
+            return Ok(match int {
+                IntValue::Signed(_) => i128::try_from(int).map(BigInt::from),
+                IntValue::Unsigned(_) => u128::try_from(int).map(BigInt::from),
+            }
+            .expect("integer should fit into 128 bits"));
+        }
+
+        let body_end = self.decode_extension_start(BIG_INT_TAG, "big integer")?;
+
+        let bytes = self.decode_bytes_buf()?;
+
+        self.check_body_end(body_end)?;
+
+        Ok(BigInt::from_signed_bytes_be(&bytes))
+    }
+}
@@ -9,14 +9,18 @@ where
     // MARK: - Value
 
     /// Decodes a boolean value.
+    ///
+    /// Booleans are encoded entirely within their header byte,
+    /// so this reads a single byte, without constructing a header.
+    #[inline]
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_bool(&mut self) -> Result<bool>
     where
         R: Read<'de>,
     {
-        let header: BoolHeader = self.decode_bool_header()?;
+        let byte = self.pull_byte_expecting(Marker::Bool)?;
 
-        self.decode_bool_of(header)
+        Ok((byte & BoolHeader::VALUE_BIT) != 0b0)
     }
 
     /// Decodes a boolean value, as a `BoolValue`.
@@ -49,7 +53,8 @@ where
         R: Read<'de>,
     {
         let _ = header;
-        self.reader.skip_one()
+
+        Ok(())
     }
 
     // MARK: - Body
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 use crate::{
     error::Result,
     header::BytesHeader,
@@ -33,6 +35,23 @@ where
         self.decode_bytes_buf_of(header)
     }
 
+    /// Decodes a byte array value, writing its bytes to `writer` in chunks,
+    /// returning the number of bytes written.
+    ///
+    /// Allows for extracting large values with bounded memory usage.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_bytes_to_writer<W>(&mut self, writer: &mut W) -> Result<usize>
+    where
+        W: std::io::Write,
+    {
+        let len = self.decode_bytes_header()?.len();
+
+        self.pull_bytes_to_writer(len, writer, |_| Ok(()))?;
+
+        Ok(len)
+    }
+
     /// Decodes a byte array value, as a `BytesValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_bytes_value(&mut self) -> Result<BytesValue> {
@@ -65,7 +84,7 @@ where
     where
         R: Read<'de>,
     {
-        self.reader.skip(header.len())
+        self.skip_bytes(header.len())
     }
 
     // MARK: - Body
@@ -76,11 +95,9 @@ where
         self.decode_bytes_buf_of(header).map(From::from)
     }
 
-    // MARK: - Private
-
     /// Decodes byte array value for a given `header`, using a scratch buffer.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    fn decode_bytes_of<'s>(
+    pub fn decode_bytes_of<'s>(
         &'s mut self,
         header: BytesHeader,
         scratch: &'s mut Vec<u8>,
@@ -90,19 +107,7 @@ where
 
     /// Decodes byte array value for a given `header`, returning an owned buffer.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    fn decode_bytes_buf_of(&mut self, header: BytesHeader) -> Result<Vec<u8>> {
-        let mut buf = Vec::new();
-
-        match self.decode_bytes_of(header, &mut buf)? {
-            Reference::Borrowed(slice) => {
-                debug_assert_eq!(buf.len(), 0);
-                buf.extend_from_slice(slice);
-            }
-            Reference::Copied(slice) => {
-                debug_assert_eq!(slice.len(), buf.len());
-            }
-        }
-
-        Ok(buf)
+    pub fn decode_bytes_buf_of(&mut self, header: BytesHeader) -> Result<Vec<u8>> {
+        self.pull_bytes_buf(header.len())
     }
 }
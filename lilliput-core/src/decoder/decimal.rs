@@ -0,0 +1,57 @@
+use rust_decimal::Decimal;
+
+use crate::{
+    error::{Error, Result},
+    marker::Marker,
+    value::DECIMAL_TAG,
+};
+
+use super::{Decoder, Read};
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a decimal, as encoded by `Encoder::encode_decimal()`,
+    /// or from a plain integer value.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_decimal(&mut self) -> Result<Decimal> {
+        let pos = self.pos;
+
+        let out_of_range = || Error::number_out_of_range(Some(pos));
+
+        if self.peek_marker()? == Marker::Int {
+            let int = self.decode_i128()?;
+
+            return Decimal::try_from_i128_with_scale(int, 0).map_err(|_| out_of_range());
+        }
+
+        let body_end = self.decode_extension_start(DECIMAL_TAG, "decimal")?;
+
+        let scale = self.decode_u32()?;
+        let bytes = self.decode_bytes_buf()?;
+
+        self.check_body_end(body_end)?;
+
+        let mantissa = mantissa_from_be_bytes(&bytes).ok_or_else(out_of_range)?;
+
+        Decimal::try_from_i128_with_scale(mantissa, scale).map_err(|_| out_of_range())
+    }
+}
+
+/// Sign-extends the big-endian two's complement `bytes` into an `i128`,
+/// returning `None` if they don't fit into one.
+fn mantissa_from_be_bytes(bytes: &[u8]) -> Option<i128> {
+    let fill = match bytes.first() {
+        Some(byte) if byte & 0x80 != 0 => 0xFF,
+        _ => 0x00,
+    };
+
+    let mut buf = [fill; 16];
+    let start = buf.len().checked_sub(bytes.len())?;
+    buf[start..].copy_from_slice(bytes);
+
+    Some(i128::from_be_bytes(buf))
+}
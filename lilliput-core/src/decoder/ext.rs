@@ -0,0 +1,114 @@
+use alloc::{borrow::ToOwned, format, string::ToString};
+
+use crate::{
+    error::{Error, Result},
+    header::{ExtHeader, SeqHeader},
+    value::ExtValue,
+};
+
+use super::{Decoder, Read};
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes an extension value, as an `ExtValue`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_ext_value(&mut self) -> Result<ExtValue> {
+        let header = self.decode_ext_header()?;
+        self.decode_ext_value_of(header)
+    }
+
+    /// Returns `true` if the next to-be-decoded value is an extension value
+    /// (e.g. a timestamp or duration).
+    pub fn peek_extension(&mut self) -> Result<bool> {
+        Ok(self.peek_byte()? == SeqHeader::EXTENSION)
+    }
+
+    // MARK: - Header
+
+    /// Decodes an extension value's header, i.e. its header byte,
+    /// the length of its body, and its tag.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_ext_header(&mut self) -> Result<ExtHeader> {
+        let pos = self.pos;
+
+        if !self.peek_extension()? {
+            return Err(Error::invalid_type(
+                self.peek_marker()?.to_string(),
+                "extension".to_owned(),
+                Some(pos),
+            ));
+        }
+
+        let body_end = self.decode_body_start()?;
+
+        let tag_pos = self.pos;
+        let tag = self.decode_u64()?;
+
+        let len = body_end.checked_sub(self.pos).ok_or_else(|| {
+            Error::invalid_length(
+                format!("tag ending at {}", self.pos),
+                format!("body ending at {body_end}"),
+                Some(tag_pos),
+            )
+        })?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(tag = tag, len = len);
+
+        Ok(ExtHeader::new(tag, len))
+    }
+
+    // MARK: - Skip
+
+    /// Skips the extension value for a given `header`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn skip_ext_value_of(&mut self, header: ExtHeader) -> Result<()> {
+        self.skip_bytes(header.len())
+    }
+
+    // MARK: - Body
+
+    /// Decodes the extension value for a given `header`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_ext_value_of(&mut self, header: ExtHeader) -> Result<ExtValue> {
+        let payload = self.pull_bytes_buf(header.len())?;
+
+        Ok(ExtValue::new(header.tag(), payload))
+    }
+
+    // MARK: - Private
+
+    /// Decodes the header of an extension value with tag `expected_tag`,
+    /// returning the position at which its body ends.
+    pub(super) fn decode_extension_start(
+        &mut self,
+        expected_tag: u64,
+        expected: &str,
+    ) -> Result<usize> {
+        let pos = self.pos;
+
+        if !self.peek_extension()? {
+            return Err(Error::invalid_type(
+                self.peek_marker()?.to_string(),
+                expected.to_owned(),
+                Some(pos),
+            ));
+        }
+
+        let header = self.decode_ext_header()?;
+
+        if header.tag() != expected_tag {
+            return Err(Error::invalid_type(
+                format!("extension with tag {}", header.tag()),
+                expected.to_owned(),
+                Some(pos),
+            ));
+        }
+
+        Ok(self.pos + header.len())
+    }
+}
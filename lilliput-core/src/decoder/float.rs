@@ -1,6 +1,15 @@
 use lilliput_float::{FpExtend as _, FpFromBeBytes as _, F16, F24, F32, F40, F48, F56, F64, F8};
 
-use crate::{error::Result, header::FloatHeader, marker::Marker, value::FloatValue};
+use alloc::{borrow::ToOwned, format};
+
+use crate::{
+    config::PackedFloatValidation,
+    error::{Error, Result},
+    header::FloatHeader,
+    marker::Marker,
+    num::WithValidatedPackedBeBytes as _,
+    value::FloatValue,
+};
 
 use super::{Decoder, Read};
 
@@ -52,7 +61,7 @@ where
     /// Skips the floating-point value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn skip_float_value_of(&mut self, header: FloatHeader) -> Result<()> {
-        self.reader.skip(header.width().into())
+        self.skip_bytes(header.width().into())
     }
 
     // MARK: - Body
@@ -60,6 +69,24 @@ where
     /// Decodes floating-point value for a given `header`, as a `FloatValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_float_value_of(&mut self, header: FloatHeader) -> Result<FloatValue> {
+        let pos = self.pos;
+        let value = self.unpack_float_value_of(header)?;
+
+        if self.config.canonical && canonical_width(&value) != header.width() {
+            return Err(Error::invalid_value(
+                format!("over-wide {}-byte float {value:?}", header.width()),
+                "canonical float".to_owned(),
+                Some(pos),
+            ));
+        }
+
+        Ok(value)
+    }
+
+    // MARK: - Private
+
+    /// Decodes floating-point value for a given `header`, unpacking it.
+    fn unpack_float_value_of(&mut self, header: FloatHeader) -> Result<FloatValue> {
         match header.width() {
             1 => {
                 let mut bytes: [u8; 1] = [0b0; 1];
@@ -115,7 +142,27 @@ where
                 let value = F64::from_be_bytes(bytes);
                 Ok(FloatValue::F64(value.into()))
             }
-            _ => unreachable!(),
+            // Unreachable for decoded headers, whose width is at most 8:
+            width => Err(super::int::invalid_width(
+                width.into(),
+                FloatHeader::MAX_VALUE_WIDTH,
+                self.pos,
+            )),
+        }
+    }
+}
+
+/// Returns the width of `value`'s canonical encoding,
+/// i.e. as packed losslessly by `PackingMode::Optimal`.
+fn canonical_width(value: &FloatValue) -> u8 {
+    let validation = PackedFloatValidation::default();
+
+    match value {
+        FloatValue::F32(value) => {
+            value.with_validated_optimal_packed_be_bytes(&validation.f32, |bytes| bytes.len() as u8)
+        }
+        FloatValue::F64(value) => {
+            value.with_validated_optimal_packed_be_bytes(&validation.f64, |bytes| bytes.len() as u8)
         }
     }
 }
@@ -1,3 +1,4 @@
+use alloc::{borrow::ToOwned, format};
 use core::num::TryFromIntError;
 
 use num_traits::{Signed, Unsigned};
@@ -21,49 +22,61 @@ where
     /// Decodes a 8-bit signed integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_i8(&mut self) -> Result<i8> {
-        self.decode_signed_int()
+        self.decode_compact_int_or_else(Self::decode_signed_int)
     }
 
     /// Decodes a 16-bit signed integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_i16(&mut self) -> Result<i16> {
-        self.decode_signed_int()
+        self.decode_compact_int_or_else(Self::decode_signed_int)
     }
 
     /// Decodes a 32-bit signed integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_i32(&mut self) -> Result<i32> {
-        self.decode_signed_int()
+        self.decode_compact_int_or_else(Self::decode_signed_int)
     }
 
     /// Decodes a 64-bit signed integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_i64(&mut self) -> Result<i64> {
-        self.decode_signed_int()
+        self.decode_compact_int_or_else(Self::decode_signed_int)
+    }
+
+    /// Decodes a 128-bit signed integer value.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_i128(&mut self) -> Result<i128> {
+        self.decode_compact_int_or_else(Self::decode_signed_int)
     }
 
     /// Decodes a 8-bit unsigned integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_u8(&mut self) -> Result<u8> {
-        self.decode_unsigned_int()
+        self.decode_compact_int_or_else(Self::decode_unsigned_int)
     }
 
     /// Decodes a 16-bit unsigned integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_u16(&mut self) -> Result<u16> {
-        self.decode_unsigned_int()
+        self.decode_compact_int_or_else(Self::decode_unsigned_int)
     }
 
     /// Decodes a 32-bit unsigned integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_u32(&mut self) -> Result<u32> {
-        self.decode_unsigned_int()
+        self.decode_compact_int_or_else(Self::decode_unsigned_int)
     }
 
     /// Decodes a 64-bit unsigned integer value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_u64(&mut self) -> Result<u64> {
-        self.decode_unsigned_int()
+        self.decode_compact_int_or_else(Self::decode_unsigned_int)
+    }
+
+    /// Decodes a 128-bit unsigned integer value.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_u128(&mut self) -> Result<u128> {
+        self.decode_compact_int_or_else(Self::decode_unsigned_int)
     }
 
     /// Decodes a signed integer value.
@@ -165,7 +178,7 @@ where
             IntHeader::Extended(header) => header,
         };
 
-        self.reader.skip(header.width().into())
+        self.skip_bytes(header.width().into())
     }
 
     // MARK: - Body
@@ -192,6 +205,10 @@ where
                 }
             }
             IntHeader::Extended(ExtendedIntHeader { is_signed, width }) => {
+                if self.config.canonical {
+                    self.check_canonical_int(width)?;
+                }
+
                 (is_signed, width as usize)
             }
         };
@@ -293,7 +310,126 @@ where
                     Ok(IntValue::Unsigned(UnsignedIntValue::U64(value)))
                 }
             }
-            _ => unreachable!(),
+            9..=16 => {
+                const MAX_WIDTH: usize = 16;
+                let mut padded_be_bytes: [u8; MAX_WIDTH] = [0b0; MAX_WIDTH];
+                self.pull_bytes_into(&mut padded_be_bytes[(MAX_WIDTH - width)..])?;
+
+                #[cfg(feature = "tracing")]
+                let bytes = crate::binary::fmt_bytes(&padded_be_bytes[(MAX_WIDTH - width)..]);
+
+                let value = u128::from_be_bytes(padded_be_bytes);
+
+                if is_signed {
+                    let value = i128::from_zig_zag(value);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(bytes = bytes, value = value);
+
+                    Ok(IntValue::Signed(SignedIntValue::I128(value)))
+                } else {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(bytes = bytes, value = value);
+
+                    Ok(IntValue::Unsigned(UnsignedIntValue::U128(value)))
+                }
+            }
+            // Unreachable for decoded headers, whose width is at most 16:
+            width => Err(invalid_width(
+                width,
+                IntHeader::MAX_EXTENDED_WIDTH,
+                self.pos,
+            )),
         }
     }
+
+    // MARK: - Private
+
+    /// Decodes a compact integer value straight from its header byte,
+    /// without going through `IntHeader` and `IntValue`, if the next value
+    /// is one, otherwise falling back to `decode`.
+    #[inline(always)]
+    fn decode_compact_int_or_else<T>(
+        &mut self,
+        decode: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T>
+    where
+        T: TryFrom<u8> + TryFrom<i8>,
+    {
+        let pos = self.pos;
+        let byte = self.peek_byte()?;
+
+        if !is_compact_int(byte) {
+            return decode(self);
+        }
+
+        self.pull_byte()?;
+
+        compact_int_to(byte).ok_or_else(|| Error::number_out_of_range(Some(pos)))
+    }
+
+    /// Rejects the upcoming `width` bytes of an extended integer value,
+    /// if wider than necessary (i.e. with a leading zero byte, or fitting
+    /// into a compact header), as per `DecoderConfig::canonical`.
+    fn check_canonical_int(&mut self, width: u8) -> Result<()> {
+        let pos = self.pos;
+        let bytes = self.reader.peek(width.into())?;
+
+        let is_canonical = match bytes {
+            [byte] => *byte > IntHeader::MAX_COMPACT_VALUE,
+            [byte, ..] => *byte != 0b0,
+            [] => true,
+        };
+
+        if is_canonical {
+            return Ok(());
+        }
+
+        Err(Error::invalid_value(
+            format!("over-wide integer bytes {bytes:02X?}"),
+            "canonical integer".to_owned(),
+            Some(pos),
+        ))
+    }
+}
+
+// MARK: - Compact Fast Path
+
+/// Returns `true`, if `byte` is the header of a compact integer value, otherwise `false`.
+#[inline(always)]
+pub(super) fn is_compact_int(byte: u8) -> bool {
+    const MASK: u8 = IntHeader::TYPE_BITS | IntHeader::COMPACT_VARIANT_BIT;
+
+    (byte & MASK) == MASK
+}
+
+/// Converts the value of a compact integer header `byte` to `T`, if in range.
+#[inline(always)]
+pub(super) fn compact_int_to<T>(byte: u8) -> Option<T>
+where
+    T: TryFrom<u8> + TryFrom<i8>,
+{
+    let bits = byte & IntHeader::COMPACT_VALUE_BITS;
+
+    if (byte & IntHeader::SIGNEDNESS_BIT) != 0b0 {
+        T::try_from(i8::from_zig_zag(bits)).ok()
+    } else {
+        T::try_from(bits).ok()
+    }
+}
+
+/// Returns the error for an extended header's body `width`,
+/// which is only ever reached for headers not decoded from input.
+#[cold]
+pub(super) fn invalid_width(width: usize, max_width: u8, pos: usize) -> Error {
+    if width > usize::from(max_width) {
+        let bits = u32::try_from(width.saturating_mul(8)).unwrap_or(u32::MAX);
+        Error::unsupported_numeric_width(bits, Some(pos))
+    } else {
+        Error::invalid_length(
+            format!("{width} bytes"),
+            format!("1 to {max_width} bytes"),
+            Some(pos),
+        )
+    }
 }
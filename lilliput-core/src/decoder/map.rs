@@ -1,12 +1,38 @@
+use alloc::{format, string::String, string::ToString, vec::Vec};
+
 use crate::{
-    error::Result,
+    config::DuplicateKeyPolicy,
+    error::{Error, Result},
     header::MapHeader,
     marker::Marker,
-    value::{Map, MapValue},
+    value::{Map, MapValue, StringValue, Value},
+    visit::{ValueVisitor, Walk},
 };
 
 use super::{Decoder, Read};
 
+/// The keys of a map value with front-coded keys, as decoded one by one.
+///
+/// See `Decoder::decode_front_coded_map_start()`.
+#[derive(Clone, Debug)]
+pub struct FrontCodedKeys {
+    len: usize,
+    body_end: usize,
+    key: String,
+}
+
+impl FrontCodedKeys {
+    /// Returns the map's number of entries.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
 impl<'de, R> Decoder<R>
 where
     R: Read<'de>,
@@ -14,8 +40,14 @@ where
     // MARK: - Value
 
     /// Decodes a map value.
+    ///
+    /// Unlike `decode_map_header()` this also supports maps with front-coded keys.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_map(&mut self) -> Result<Map> {
+        if self.peek_front_coded_map()? {
+            return self.decode_front_coded_map();
+        }
+
         let header = self.decode_map_header()?;
         self.decode_map_of(header)
     }
@@ -26,6 +58,47 @@ where
         self.decode_map().map(From::from)
     }
 
+    /// Returns `true` if the next to-be-decoded value is a map with front-coded keys.
+    ///
+    /// Front-coded maps have no `MapHeader` and need to be decoded either
+    /// as a whole, via `decode_map()` or `decode_value()`, or entry by entry,
+    /// via `decode_front_coded_map_start()`.
+    pub fn peek_front_coded_map(&mut self) -> Result<bool> {
+        Ok(self.peek_byte()? == MapHeader::FRONT_CODED)
+    }
+
+    /// Decodes the start of a map value with front-coded keys,
+    /// for decoding its entries one by one.
+    ///
+    /// Each entry's key gets decoded via `decode_front_coded_map_key()`,
+    /// followed by its value, with `decode_front_coded_map_end()`
+    /// checking the map's end, once all of its entries have been decoded.
+    pub fn decode_front_coded_map_start(&mut self) -> Result<FrontCodedKeys> {
+        let body_end = self.decode_body_start()?;
+        let len = self.decode_usize()?;
+
+        Ok(FrontCodedKeys {
+            len,
+            body_end,
+            key: String::new(),
+        })
+    }
+
+    /// Decodes the key of a front-coded map's next entry.
+    pub fn decode_front_coded_map_key<'k>(
+        &mut self,
+        keys: &'k mut FrontCodedKeys,
+    ) -> Result<&'k str> {
+        self.decode_front_coded_key(&mut keys.key)?;
+
+        Ok(&keys.key)
+    }
+
+    /// Checks that a front-coded map ended after its last entry.
+    pub fn decode_front_coded_map_end(&mut self, keys: FrontCodedKeys) -> Result<()> {
+        self.check_body_end(keys.body_end)
+    }
+
     // MARK: - Header
 
     /// Decodes a map value's header.
@@ -61,6 +134,25 @@ where
         }
     }
 
+    /// Decodes a map value's header, returning its 64-bit length.
+    ///
+    /// Unlike `decode_map_header()` this does not require the length
+    /// to fit into a `usize`, allowing for the value's entries to be
+    /// streamed one by one, even on 32-bit targets.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_map_len(&mut self) -> Result<u64> {
+        let byte = self.pull_byte_expecting(Marker::Map)?;
+
+        let is_compact = (byte & MapHeader::COMPACT_VARIANT_BIT) != 0b0;
+
+        if is_compact {
+            Ok((byte & MapHeader::COMPACT_LEN_BITS).into())
+        } else {
+            let len_width = 1 + (byte & MapHeader::EXTENDED_LEN_WIDTH_BITS);
+            self.pull_len_bytes_u64(len_width)
+        }
+    }
+
     // MARK: - Skip
 
     /// Skips the map value for a given `header`.
@@ -79,6 +171,13 @@ where
         Ok(())
     }
 
+    /// Skips a map value with front-coded keys.
+    pub(super) fn skip_front_coded_map(&mut self) -> Result<()> {
+        let body_end = self.decode_body_start()?;
+
+        self.skip_bytes(body_end - self.pos)
+    }
+
     // MARK: - Body
 
     /// Decodes map value for a given `header`, as a `MapValue`.
@@ -92,14 +191,158 @@ where
     /// Decodes map value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn decode_map_of(&mut self, header: MapHeader) -> Result<Map> {
+        let len = header.len();
+
+        // Each entry takes up at least two bytes, one for its key and one for its value:
+        #[cfg(feature = "preserve_order")]
+        let mut map = Map::with_capacity(self.capacity_for(len, 2));
+        #[cfg(not(feature = "preserve_order"))]
         let mut map = Map::default();
 
-        for _ in 0..header.len() {
+        for _ in 0..len {
+            let pos = self.pos;
             let key = self.decode_value()?;
             let value = self.decode_value()?;
-            map.insert(key, value);
+            self.insert_entry(&mut map, key, value, pos)?;
+        }
+
+        if let Some(stats) = self.stats.as_mut().filter(|_| len > 0) {
+            stats.record_allocation(len * core::mem::size_of::<(Value, Value)>());
         }
 
         Ok(map)
     }
+
+    /// Decodes a map value with front-coded keys, where each key is encoded as
+    /// the length of the prefix it shares with its preceding key, followed by the rest.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_front_coded_map(&mut self) -> Result<Map> {
+        let body_end = self.decode_body_start()?;
+        let len = self.decode_usize()?;
+
+        // Each entry takes up at least three bytes: its prefix length, suffix and value:
+        #[cfg(feature = "preserve_order")]
+        let mut map = Map::with_capacity(self.capacity_for(len, 3));
+        #[cfg(not(feature = "preserve_order"))]
+        let mut map = Map::default();
+
+        let mut key = String::new();
+
+        for _ in 0..len {
+            let pos = self.pos;
+            self.decode_front_coded_key(&mut key)?;
+            let value = self.decode_value()?;
+            self.insert_entry(
+                &mut map,
+                Value::String(StringValue(key.clone())),
+                value,
+                pos,
+            )?;
+        }
+
+        self.check_body_end(body_end)?;
+
+        if let Some(stats) = self.stats.as_mut().filter(|_| len > 0) {
+            stats.record_allocation(len * core::mem::size_of::<(Value, Value)>());
+        }
+
+        Ok(map)
+    }
+
+    /// Walks a map value with front-coded keys, passing its keys to `visitor` as strings.
+    pub(super) fn walk_front_coded_map_with<V>(
+        &mut self,
+        visitor: &mut V,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()>
+    where
+        V: ?Sized + ValueVisitor,
+    {
+        let body_end = self.decode_body_start()?;
+        let len = self.decode_usize()?;
+
+        match visitor.visit_map_start(len)? {
+            Walk::Enter => {
+                let mut key = String::new();
+
+                for _ in 0..len {
+                    self.decode_front_coded_key(&mut key)?;
+                    visitor.visit_str(&key)?;
+                    self.walk_value_with(visitor, scratch)?;
+                }
+
+                self.check_body_end(body_end)?;
+            }
+            Walk::Skip if self.interning => {
+                let mut key = String::new();
+
+                for _ in 0..len {
+                    self.decode_front_coded_key(&mut key)?;
+                    self.skip_value()?;
+                }
+
+                self.check_body_end(body_end)?;
+            }
+            Walk::Skip => self.skip_bytes(body_end.saturating_sub(self.pos))?,
+        }
+
+        visitor.visit_map_end()
+    }
+
+    /// Seeks the entry for `key` within a map value with front-coded keys,
+    /// returning `true` (with the decoder positioned at its value) if found.
+    pub(crate) fn seek_front_coded_map_key(&mut self, key: &str) -> Result<bool> {
+        let body_end = self.decode_body_start()?;
+        let len = self.decode_usize()?;
+
+        let mut current = String::new();
+
+        for _ in 0..len {
+            self.decode_front_coded_key(&mut current)?;
+
+            if current == key {
+                return Ok(true);
+            }
+
+            self.skip_value()?;
+        }
+
+        self.check_body_end(body_end)?;
+
+        Ok(false)
+    }
+
+    /// Inserts the entry of `key` (decoded at `pos`) and `value` into `map`,
+    /// handling duplicate keys according to `DecoderConfig::duplicate_keys`.
+    fn insert_entry(&self, map: &mut Map, key: Value, value: Value, pos: usize) -> Result<()> {
+        match self.config.duplicate_keys {
+            DuplicateKeyPolicy::LastWins => {}
+            _ if !map.contains_key(&key) => {}
+            DuplicateKeyPolicy::FirstWins => return Ok(()),
+            DuplicateKeyPolicy::Reject => return Err(Error::duplicate_key(&key, Some(pos))),
+        }
+
+        map.insert(key, value);
+
+        Ok(())
+    }
+
+    /// Decodes a front-coded key, replacing `key` (i.e. the preceding key) with it.
+    fn decode_front_coded_key(&mut self, key: &mut String) -> Result<()> {
+        let pos = self.pos;
+        let prefix_len = self.decode_usize()?;
+
+        if !key.is_char_boundary(prefix_len) {
+            return Err(Error::invalid_length(
+                prefix_len.to_string(),
+                format!("key prefix length of at most {}", key.len()),
+                Some(pos),
+            ));
+        }
+
+        key.truncate(prefix_len);
+        key.push_str(&self.decode_string()?);
+
+        Ok(())
+    }
 }
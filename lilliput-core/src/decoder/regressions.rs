@@ -0,0 +1,171 @@
+//! Malformed inputs, each asserting the specific error it gets rejected with.
+//!
+//! Inputs that used to (or could conceivably) panic, overflow or over-allocate
+//! belong here, along with the error expected for them, rather than just
+//! being checked for not panicking.
+
+use proptest::prelude::*;
+use test_log::test;
+
+use crate::{
+    error::ErrorCode,
+    header::{ExtendedIntHeader, FloatHeader, IntHeader},
+    io::{SliceReader, StdIoReader, VecWriter},
+    value::Value,
+    visit::ValueVisitor,
+};
+
+use super::*;
+
+/// The error expected for a length of `u64::MAX`,
+/// which only fits into a `usize` on 64-bit targets.
+const U64_MAX_LEN: ErrorCode = if cfg!(target_pointer_width = "64") {
+    ErrorCode::UnexpectedEndOfFile
+} else {
+    ErrorCode::LengthExceedsPlatform
+};
+
+const CORPUS: &[(&str, &[u8], ErrorCode)] = &[
+    ("empty", &[], ErrorCode::UnexpectedEndOfFile),
+    // Truncated bodies and extended headers:
+    (
+        "int_truncated_width_4",
+        &[0b1000_0011, 0x01],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "int_truncated_width_16",
+        &[0b1000_1111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "float_truncated_width_8",
+        &[0b0000_1111, 0x00, 0x00, 0x00],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "string_truncated_len",
+        &[0b0100_0011, 0x01],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "bytes_truncated_len",
+        &[0b0000_0110, 0x00, 0x01],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "seq_truncated_len",
+        &[0b0010_0001, 0x01],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "map_truncated_len",
+        &[0b0001_0111, 0xff],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "map_missing_value",
+        &[0b0001_1001, 0b0000_0000],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    (
+        "seq_stream_unterminated",
+        &[0b0010_1000, 0b0000_0000],
+        ErrorCode::UnexpectedEndOfFile,
+    ),
+    // Lengths of `u64::MAX`:
+    (
+        "string_len_u64_max",
+        &[0b0100_0111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        U64_MAX_LEN,
+    ),
+    (
+        "bytes_len_u64_max",
+        &[0b0000_0111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        U64_MAX_LEN,
+    ),
+    (
+        "seq_len_u64_max",
+        &[0b0010_0111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        U64_MAX_LEN,
+    ),
+    (
+        "map_len_u64_max",
+        &[0b0001_0111, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+        U64_MAX_LEN,
+    ),
+    // Invalid contents:
+    ("string_invalid_utf8", &[0b0110_0001, 0xff], ErrorCode::Utf8),
+    // Reserved headers:
+    ("seq_reserved", &[0b0010_1111], ErrorCode::ReservedType),
+    (
+        "seq_stream_end_stray",
+        &[0b0010_1001],
+        ErrorCode::ReservedType,
+    ),
+];
+
+#[test]
+fn corpus() {
+    for &(name, encoded, expected) in CORPUS {
+        let mut decoder = Decoder::from_reader(SliceReader::new(encoded));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, expected, "{name} (slice reader)");
+
+        let mut decoder = Decoder::from_reader(StdIoReader::new(encoded));
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, expected, "{name} (std io reader)");
+    }
+}
+
+#[test]
+fn invalid_int_widths() {
+    for (width, expected) in [
+        (0, ErrorCode::InvalidLength),
+        (17, ErrorCode::UnsupportedNumericWidth),
+        (u8::MAX, ErrorCode::UnsupportedNumericWidth),
+    ] {
+        let header = IntHeader::Extended(ExtendedIntHeader {
+            is_signed: false,
+            width,
+        });
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&[0; 32]));
+        let error_code = decoder.decode_int_value_of(header).unwrap_err().code();
+        assert_eq!(error_code, expected, "width {width}");
+        assert_eq!(decoder.pos(), 0);
+    }
+}
+
+#[test]
+fn invalid_float_widths() {
+    for (width, expected) in [
+        (0, ErrorCode::InvalidLength),
+        (9, ErrorCode::UnsupportedNumericWidth),
+    ] {
+        let header = FloatHeader { width };
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&[0; 32]));
+        let error_code = decoder.decode_float_value_of(header).unwrap_err().code();
+        assert_eq!(error_code, expected, "width {width}");
+        assert_eq!(decoder.pos(), 0);
+    }
+}
+
+struct NoopVisitor;
+
+impl ValueVisitor for NoopVisitor {}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_never_panic(encoded in prop::collection::vec(any::<u8>(), 0..64)) {
+        let _: Result<Value> = Decoder::from_reader(SliceReader::new(&encoded)).decode_value();
+        let _: Result<Value> = Decoder::from_reader(StdIoReader::new(&encoded[..])).decode_value();
+        let _ = Decoder::from_reader(SliceReader::new(&encoded)).skip_value();
+        let _ = Decoder::from_reader(SliceReader::new(&encoded)).walk_value(&mut NoopVisitor);
+
+        let mut copied = Vec::new();
+        let _ = Decoder::from_reader(SliceReader::new(&encoded))
+            .decode_into_writer(&mut VecWriter::new(&mut copied));
+    }
+}
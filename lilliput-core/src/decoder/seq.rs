@@ -1,12 +1,85 @@
+use alloc::{borrow::ToOwned, format, vec::Vec};
+
 use crate::{
-    error::Result,
+    encoder::Encoder,
+    error::{Error, Result},
     header::SeqHeader,
-    io::Read,
+    io::{Read, SliceReader, VecWriter},
     marker::Marker,
-    value::{Seq, SeqValue},
+    value::{IntValue, Map, MapValue, Seq, SeqValue, Value},
+    visit::ValueVisitor,
+};
+
+use super::{
+    int::{compact_int_to, is_compact_int},
+    Decoder,
 };
 
-use super::Decoder;
+/// A columnar sequence of maps, as decoded column by column.
+///
+/// See `Decoder::decode_columnar_seq_start()`.
+#[derive(Clone, Debug)]
+pub struct ColumnarSeq {
+    len: usize,
+    keys_len: usize,
+    keys_pos: usize,
+    body_end: usize,
+}
+
+impl ColumnarSeq {
+    /// Returns the sequence's number of maps (i.e. rows).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the sequence has no maps.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of keys (i.e. columns) shared by the sequence's maps.
+    pub fn keys_len(&self) -> usize {
+        self.keys_len
+    }
+
+    /// Checks the sequence's decoded `keys` for duplicates.
+    pub fn check_keys(&self, keys: &[Value]) -> Result<()> {
+        check_distinct_keys(keys, self.keys_pos)
+    }
+}
+
+/// A tabular sequence of maps, as decoded row by row.
+///
+/// See `Decoder::decode_tabular_seq_start()`.
+#[derive(Clone, Debug)]
+pub struct TabularSeq {
+    len: usize,
+    keys_len: usize,
+    keys_pos: usize,
+    body_end: usize,
+}
+
+impl TabularSeq {
+    /// Returns the sequence's number of maps (i.e. rows).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the sequence has no maps.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of keys (i.e. values per row) shared by the sequence's maps.
+    pub fn keys_len(&self) -> usize {
+        self.keys_len
+    }
+
+    /// Checks the sequence's decoded `keys` for duplicates.
+    pub fn check_keys(&self, keys: &[Value]) -> Result<()> {
+        check_distinct_keys(keys, self.keys_pos)
+    }
+}
 
 impl<'de, R> Decoder<R>
 where
@@ -17,6 +90,13 @@ where
     /// Decodes a sequence value.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_seq(&mut self) -> Result<Seq> {
+        match self.peek_byte()? {
+            SeqHeader::STREAM_START => return self.decode_seq_stream(),
+            SeqHeader::COLUMNAR => return self.decode_columnar_seq(),
+            SeqHeader::TABULAR => return self.decode_tabular_seq(),
+            _ => {}
+        }
+
         let header = self.decode_seq_header()?;
 
         self.decode_seq_of(header)
@@ -25,9 +105,101 @@ where
     /// Decodes a sequence value, as a `SeqValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_seq_value(&mut self) -> Result<SeqValue> {
+        self.decode_seq().map(From::from)
+    }
+
+    /// Decodes a sequence of integer values, as a `Vec<T>`.
+    ///
+    /// Optimized for sequences of small numbers: runs of items encoded
+    /// as compact integers get decoded straight from the reader's buffer,
+    /// with any other items falling back to regular integer decoding.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_compact_int_seq<T>(&mut self) -> Result<Vec<T>>
+    where
+        T: TryFrom<u8> + TryFrom<i8> + TryFrom<i128> + TryFrom<u128>,
+    {
         let header = self.decode_seq_header()?;
 
-        self.decode_seq_value_of(header)
+        self.decode_compact_int_seq_of(header)
+    }
+
+    /// Returns `true` if the next to-be-decoded value is a columnar sequence of maps.
+    ///
+    /// Columnar sequences have no `SeqHeader`, so are rejected by header-level
+    /// methods (e.g. `decode_seq_header()`), but get decoded by `decode_seq()`.
+    pub fn peek_columnar_seq(&mut self) -> Result<bool> {
+        Ok(self.peek_byte()? == SeqHeader::COLUMNAR)
+    }
+
+    /// Decodes the start of a columnar sequence of maps,
+    /// for decoding its keys and columns one by one.
+    ///
+    /// The sequence's `keys_len()` keys get decoded next (e.g. via `decode_value()`),
+    /// followed by each key's column: its header, decoded via `decode_columnar_seq_column()`,
+    /// and the column's `len()` values. Once all columns have been decoded,
+    /// `decode_columnar_seq_end()` checks the sequence's end.
+    pub fn decode_columnar_seq_start(&mut self) -> Result<ColumnarSeq> {
+        let (len, keys_len, keys_pos, body_end) = self.decode_keyed_seq_start()?;
+
+        Ok(ColumnarSeq {
+            len,
+            keys_len,
+            keys_pos,
+            body_end,
+        })
+    }
+
+    /// Decodes the header of a columnar sequence's next column,
+    /// checking that it holds one value per map.
+    pub fn decode_columnar_seq_column(&mut self, columnar: &ColumnarSeq) -> Result<()> {
+        let pos = self.pos;
+        let len = self.decode_seq_header()?.len();
+
+        if len != columnar.len {
+            return Err(Error::invalid_length(
+                format!("{len} values"),
+                format!("{} values, one per map", columnar.len),
+                Some(pos),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a columnar sequence ended after its last column.
+    pub fn decode_columnar_seq_end(&mut self, columnar: ColumnarSeq) -> Result<()> {
+        self.check_body_end(columnar.body_end)
+    }
+
+    /// Returns `true` if the next to-be-decoded value is a tabular sequence of maps.
+    ///
+    /// Tabular sequences have no `SeqHeader`, so are rejected by header-level
+    /// methods (e.g. `decode_seq_header()`), but get decoded by `decode_seq()`.
+    pub fn peek_tabular_seq(&mut self) -> Result<bool> {
+        Ok(self.peek_byte()? == SeqHeader::TABULAR)
+    }
+
+    /// Decodes the start of a tabular sequence of maps,
+    /// for decoding its keys and rows one by one.
+    ///
+    /// The sequence's `keys_len()` keys get decoded next (e.g. via `decode_value()`),
+    /// followed by the values of each of its `len()` rows, one per key, in the order
+    /// of the keys. Once all rows have been decoded, `decode_tabular_seq_end()`
+    /// checks the sequence's end.
+    pub fn decode_tabular_seq_start(&mut self) -> Result<TabularSeq> {
+        let (len, keys_len, keys_pos, body_end) = self.decode_keyed_seq_start()?;
+
+        Ok(TabularSeq {
+            len,
+            keys_len,
+            keys_pos,
+            body_end,
+        })
+    }
+
+    /// Checks that a tabular sequence ended after its last row.
+    pub fn decode_tabular_seq_end(&mut self, tabular: TabularSeq) -> Result<()> {
+        self.check_body_end(tabular.body_end)
     }
 
     // MARK: - Header
@@ -39,6 +211,10 @@ where
 
         let is_compact = (byte & SeqHeader::COMPACT_VARIANT_BIT) != 0b0;
 
+        if !is_compact {
+            check_not_streamed(byte)?;
+        }
+
         if is_compact {
             let len = byte & SeqHeader::COMPACT_LEN_BITS;
 
@@ -65,6 +241,57 @@ where
         }
     }
 
+    /// Decodes a sequence value's header, returning its 64-bit length.
+    ///
+    /// Unlike `decode_seq_header()` this does not require the length
+    /// to fit into a `usize`, allowing for the value's elements to be
+    /// streamed one by one, even on 32-bit targets.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_seq_len(&mut self) -> Result<u64> {
+        let byte = self.pull_byte_expecting(Marker::Seq)?;
+
+        let is_compact = (byte & SeqHeader::COMPACT_VARIANT_BIT) != 0b0;
+
+        if is_compact {
+            Ok((byte & SeqHeader::COMPACT_LEN_BITS).into())
+        } else {
+            check_not_streamed(byte)?;
+
+            let len_width = 1 + (byte & SeqHeader::EXTENDED_LEN_WIDTH_BITS);
+            self.pull_len_bytes_u64(len_width)
+        }
+    }
+
+    // MARK: - Stream
+
+    /// Decodes a sequence value's header, returning its length,
+    /// or `None` for a streamed sequence.
+    ///
+    /// The items of a streamed sequence are to be decoded until
+    /// `decode_seq_stream_end()` returns `true`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_seq_start(&mut self) -> Result<Option<usize>> {
+        if self.peek_byte()? == SeqHeader::STREAM_START {
+            self.pull_byte()?;
+            return Ok(None);
+        }
+
+        self.decode_seq_header().map(|header| Some(header.len()))
+    }
+
+    /// Decodes the end of a streamed sequence value, if next,
+    /// returning `true`, otherwise `false` (without advancing).
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_seq_stream_end(&mut self) -> Result<bool> {
+        if self.peek_byte()? != SeqHeader::STREAM_END {
+            return Ok(false);
+        }
+
+        self.pull_byte()?;
+
+        Ok(true)
+    }
+
     // MARK: - Skip
 
     /// Skips the sequence value for a given `header`.
@@ -90,18 +317,263 @@ where
         self.decode_seq_of(header).map(From::from)
     }
 
+    /// Decodes sequence of integer values for a given `header`, as a `Vec<T>`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_compact_int_seq_of<T>(&mut self, header: SeqHeader) -> Result<Vec<T>>
+    where
+        T: TryFrom<u8> + TryFrom<i8> + TryFrom<i128> + TryFrom<u128>,
+    {
+        let len = header.len();
+
+        let mut items: Vec<T> = Vec::with_capacity(self.capacity_for(len, 1));
+        self.record_growth(0, &items);
+
+        while items.len() < len {
+            let remaining = len - items.len();
+
+            let buffered = self.reader.buffered();
+            let run = buffered
+                .iter()
+                .take(remaining)
+                .take_while(|&&byte| is_compact_int(byte))
+                .count();
+
+            let capacity = items.capacity();
+            for (offset, &byte) in buffered[..run].iter().enumerate() {
+                let item = compact_int_to::<T>(byte)
+                    .ok_or_else(|| Error::number_out_of_range(Some(self.pos + offset)))?;
+                items.push(item);
+            }
+            self.record_growth(capacity, &items);
+
+            if run > 0 {
+                self.reader.skip(run)?;
+                self.pos += run;
+                continue;
+            }
+
+            let pos = self.pos;
+            let byte = self.peek_byte()?;
+
+            let item = if is_compact_int(byte) {
+                self.pull_byte()?;
+                compact_int_to::<T>(byte)
+            } else {
+                match self.decode_int_value()? {
+                    IntValue::Signed(value) => T::try_from(value.canonicalized()).ok(),
+                    IntValue::Unsigned(value) => T::try_from(value.canonicalized()).ok(),
+                }
+            };
+
+            let capacity = items.capacity();
+            items.push(item.ok_or_else(|| Error::number_out_of_range(Some(pos)))?);
+            self.record_growth(capacity, &items);
+        }
+
+        Ok(items)
+    }
+
     // MARK: - Private
 
+    /// Decodes a streamed sequence value, including its start and end.
+    fn decode_seq_stream(&mut self) -> Result<Seq> {
+        self.pull_byte_expecting(Marker::Seq)?;
+
+        let mut seq = Seq::new();
+
+        while !self.decode_seq_stream_end()? {
+            let value = self.decode_value()?;
+            let capacity = seq.capacity();
+            seq.push(value);
+            self.record_growth(capacity, &seq);
+        }
+
+        Ok(seq)
+    }
+
+    /// Skips a streamed sequence value, including its start and end.
+    pub(super) fn skip_seq_stream(&mut self) -> Result<()> {
+        self.pull_byte_expecting(Marker::Seq)?;
+
+        while !self.decode_seq_stream_end()? {
+            self.skip_value()?;
+        }
+
+        Ok(())
+    }
+
+    /// Skips a columnar (or tabular) sequence of maps.
+    pub(super) fn skip_keyed_seq(&mut self) -> Result<()> {
+        let body_end = self.decode_body_start()?;
+
+        self.skip_bytes(body_end - self.pos)
+    }
+
+    /// Decodes a columnar sequence of maps, where the maps' keys are encoded once,
+    /// followed by each key's values, for all of the maps.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_columnar_seq(&mut self) -> Result<Seq> {
+        let columnar = self.decode_columnar_seq_start()?;
+        let (len, keys_len) = (columnar.len, columnar.keys_len);
+
+        let mut keys = Vec::with_capacity(self.capacity_for(keys_len, 1));
+        for _ in 0..keys_len {
+            keys.push(self.decode_value()?);
+        }
+
+        columnar.check_keys(&keys)?;
+
+        // The maps get created while decoding the first column,
+        // each of whose values takes up at least one byte:
+        let mut maps: Vec<Map> = Vec::with_capacity(self.capacity_for(len, 1));
+        self.record_growth(0, &maps);
+
+        for (column, key) in keys.into_iter().enumerate() {
+            self.decode_columnar_seq_column(&columnar)?;
+
+            for row in 0..len {
+                let value = self.decode_value()?;
+
+                if column == 0 {
+                    let capacity = maps.capacity();
+                    maps.push(Map::default());
+                    self.record_growth(capacity, &maps);
+                }
+
+                maps[row].insert(key.clone(), value);
+            }
+        }
+
+        self.decode_columnar_seq_end(columnar)?;
+
+        if let Some(stats) = self.stats.as_mut().filter(|_| len > 0) {
+            stats.record_allocation(len * keys_len * core::mem::size_of::<(Value, Value)>());
+        }
+
+        Ok(maps
+            .into_iter()
+            .map(|map| Value::Map(MapValue::from(map)))
+            .collect())
+    }
+
+    /// Decodes a tabular sequence of maps, where the maps' keys are encoded once,
+    /// followed by each of the maps' values.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_tabular_seq(&mut self) -> Result<Seq> {
+        let tabular = self.decode_tabular_seq_start()?;
+        let (len, keys_len) = (tabular.len, tabular.keys_len);
+
+        let mut keys = Vec::with_capacity(self.capacity_for(keys_len, 1));
+        for _ in 0..keys_len {
+            keys.push(self.decode_value()?);
+        }
+
+        tabular.check_keys(&keys)?;
+
+        let mut seq = Seq::with_capacity(self.capacity_for(len, keys_len));
+        self.record_growth(0, &seq);
+
+        for _ in 0..len {
+            let mut map = Map::default();
+            for key in &keys {
+                let value = self.decode_value()?;
+                map.insert(key.clone(), value);
+            }
+
+            let capacity = seq.capacity();
+            seq.push(Value::Map(MapValue::from(map)));
+            self.record_growth(capacity, &seq);
+        }
+
+        self.decode_tabular_seq_end(tabular)?;
+
+        if let Some(stats) = self.stats.as_mut().filter(|_| len > 0) {
+            stats.record_allocation(len * keys_len * core::mem::size_of::<(Value, Value)>());
+        }
+
+        Ok(seq)
+    }
+
+    /// Decodes the start of a columnar (or tabular) sequence of maps,
+    /// returning its length, number of keys, the keys' position and the body's end.
+    fn decode_keyed_seq_start(&mut self) -> Result<(usize, usize, usize, usize)> {
+        let body_end = self.decode_body_start()?;
+        let len = self.decode_usize()?;
+
+        let keys_pos = self.pos;
+        let keys_len = self.decode_usize()?;
+
+        if keys_len == 0 {
+            return Err(Error::invalid_length(
+                "0 keys".to_owned(),
+                "at least one key".to_owned(),
+                Some(keys_pos),
+            ));
+        }
+
+        Ok((len, keys_len, keys_pos, body_end))
+    }
+
+    /// Walks a columnar (or tabular) sequence of maps, passing it to `visitor` row by row.
+    pub(super) fn walk_keyed_seq_with<V>(
+        &mut self,
+        visitor: &mut V,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()>
+    where
+        V: ?Sized + ValueVisitor,
+    {
+        // The maps' values are stored without their keys, so the sequence
+        // gets decoded as a whole, with its plain encoding being walked:
+        let seq = self.decode_seq()?;
+
+        let mut plain = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut plain)).encode_seq(&seq)?;
+
+        Decoder::from_reader(SliceReader::new(&plain)).walk_value_with(visitor, scratch)
+    }
+
     /// Decodes sequence value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn decode_seq_of(&mut self, header: SeqHeader) -> Result<Seq> {
-        let mut seq = Seq::default();
+        let len = header.len();
 
-        for _ in 0..header.len() {
+        let mut seq = Seq::with_capacity(self.capacity_for(len, 1));
+        self.record_growth(0, &seq);
+
+        for _ in 0..len {
             let value = self.decode_value()?;
+            let capacity = seq.capacity();
             seq.push(value);
+            self.record_growth(capacity, &seq);
         }
 
         Ok(seq)
     }
 }
+
+/// Checks the decoded `keys` (at `pos`) of a columnar or tabular sequence for duplicates.
+fn check_distinct_keys(keys: &[Value], pos: usize) -> Result<()> {
+    let mut sorted_keys: Vec<&Value> = keys.iter().collect();
+    sorted_keys.sort();
+
+    match sorted_keys.windows(2).find(|keys| keys[0] == keys[1]) {
+        Some(keys) => Err(Error::duplicate_key(keys[0], Some(pos))),
+        None => Ok(()),
+    }
+}
+
+/// Rejects the header `byte` of a streamed sequence,
+/// for methods expecting a sequence of known length.
+#[inline]
+fn check_not_streamed(byte: u8) -> Result<()> {
+    if (byte & SeqHeader::STREAMED_VARIANT_BIT) == 0b0 {
+        return Ok(());
+    }
+
+    if byte == SeqHeader::STREAM_START {
+        Err(Error::unknown_length())
+    } else {
+        Err(Error::reserved_type())
+    }
+}
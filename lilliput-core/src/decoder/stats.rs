@@ -0,0 +1,21 @@
+/// Statistics collected by a decoder, on the memory it allocated.
+///
+/// Allocations get counted for the buffers backing decoded strings,
+/// byte arrays and sequences (including reallocations due to growth),
+/// while maps are accounted as a single allocation of their entries' size.
+/// Caller-provided scratch buffers are not accounted for.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub struct DecoderStats {
+    /// Number of allocations.
+    pub allocations: usize,
+    /// Number of allocated bytes, including any capacity
+    /// beyond the length of the decoded values.
+    pub allocated_bytes: usize,
+}
+
+impl DecoderStats {
+    pub(crate) fn record_allocation(&mut self, bytes: usize) {
+        self.allocations += 1;
+        self.allocated_bytes += bytes;
+    }
+}
@@ -1,11 +1,14 @@
-use std::ops::Range;
+use alloc::{borrow::ToOwned, format, string::String, string::ToString, vec::Vec};
+use core::ops::Range;
 
 use crate::{
+    config::{UnknownHeaderPolicy, Utf8Policy},
+    dictionary::MIN_INTERNED_LEN,
     error::{Error, Result},
     header::StringHeader,
-    io::{Read, Reference},
+    io::{Read, Reference, SliceReader},
     marker::Marker,
-    value::StringValue,
+    value::{StringValue, Value},
 };
 
 use super::Decoder;
@@ -22,6 +25,10 @@ where
         &'s mut self,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, str>> {
+        if self.peek_dictionary_ref()? {
+            return self.decode_dictionary_ref().map(Reference::Copied);
+        }
+
         let header = self.decode_string_header()?;
         self.decode_str_of(header, scratch)
     }
@@ -32,6 +39,12 @@ where
         &'s mut self,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, [u8]>> {
+        if self.peek_dictionary_ref()? {
+            return self
+                .decode_dictionary_ref()
+                .map(|string| Reference::Copied(string.as_bytes()));
+        }
+
         let header = self.decode_string_header()?;
         self.decode_str_bytes_of(header, scratch)
     }
@@ -39,6 +52,10 @@ where
     /// Decodes a string value, as an owned string.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_string(&mut self) -> Result<String> {
+        if self.peek_dictionary_ref()? {
+            return self.decode_dictionary_ref().map(ToOwned::to_owned);
+        }
+
         let header = self.decode_string_header()?;
         self.decode_string_of(header)
     }
@@ -46,17 +63,70 @@ where
     /// Decodes a string value's raw-bytes, as an owned buffer.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_string_bytes_buf(&mut self) -> Result<Vec<u8>> {
+        if self.peek_dictionary_ref()? {
+            return self
+                .decode_dictionary_ref()
+                .map(|string| string.as_bytes().to_vec());
+        }
+
         let header = self.decode_string_header()?;
         self.decode_string_bytes_buf_of(header)
     }
 
+    /// Decodes a string value, writing its bytes to `writer` in chunks,
+    /// returning the number of bytes written.
+    ///
+    /// Allows for extracting large values with bounded memory usage.
+    /// The bytes get validated as UTF-8 before being written, so an error
+    /// for invalid UTF-8 leaves the bytes preceding it written.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_str_to_writer<W>(&mut self, writer: &mut W) -> Result<usize>
+    where
+        W: std::io::Write,
+    {
+        if self.peek_dictionary_ref()? {
+            let string = self.decode_dictionary_ref()?;
+            writer.write_all(string.as_bytes()).map_err(Error::io)?;
+            return Ok(string.len());
+        }
+
+        // Strings to be interned (or repaired) need to be decoded in full:
+        if self.interning || self.config.utf8 == Utf8Policy::Replace {
+            let string = self.decode_string()?;
+            writer.write_all(string.as_bytes()).map_err(Error::io)?;
+            return Ok(string.len());
+        }
+
+        let len = self.decode_string_header()?.len();
+
+        let mut validator = crate::utf8::Utf8Validator::new(self.pos);
+        self.pull_bytes_to_writer(len, writer, |chunk| validator.validate(chunk))?;
+        validator.finish()?;
+
+        Ok(len)
+    }
+
     /// Decodes a string value, as a `StringValue`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn decode_string_value(&mut self) -> Result<StringValue> {
+        if self.peek_dictionary_ref()? {
+            return self.decode_string().map(From::from);
+        }
+
         let header = self.decode_string_header()?;
         self.decode_string_value_of(header)
     }
 
+    /// Returns `true` if the next to-be-decoded value is a reference to a dictionary string.
+    ///
+    /// References have no `StringHeader`, so are rejected by `decode_string_header()`,
+    /// but get resolved by all other methods decoding strings. Returns an error
+    /// if the attached dictionary (if any) has no string registered under their id.
+    pub fn peek_dictionary_ref(&mut self) -> Result<bool> {
+        Ok(self.peek_byte()? == StringHeader::DICTIONARY_REF)
+    }
+
     // MARK: - Header
 
     /// Decodes a string value's header.
@@ -97,12 +167,16 @@ where
     /// Skips the map value for a given `header`.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn skip_string_value_of(&mut self, header: StringHeader) -> Result<()> {
+        if self.interning {
+            return self.decode_string_bytes_buf_of(header).map(drop);
+        }
+
         let len: usize = match header {
             StringHeader::Compact(header) => header.len().into(),
             StringHeader::Extended(header) => header.len(),
         };
 
-        self.reader.skip(len)
+        self.skip_bytes(len)
     }
 
     // MARK: - Body
@@ -113,19 +187,26 @@ where
         self.decode_string_of(header).map(From::from)
     }
 
-    // MARK: - Private
-
+    /// Decodes a string value for a given `header`, as a reference.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    fn decode_str_of<'s>(
+    pub fn decode_str_of<'s>(
         &'s mut self,
         header: StringHeader,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'de, 's, str>> {
+        // Repaired strings can't be borrowed from the input:
+        if self.config.utf8 == Utf8Policy::Replace {
+            *scratch = self.decode_string_of(header)?.into_bytes();
+            let string = core::str::from_utf8(scratch).map_err(|err| Error::utf8(err, None))?;
+
+            return Ok(Reference::Copied(string));
+        }
+
         let (bytes, range) = self.decode_str_bytes_and_range_of(header, scratch)?;
 
         let str_ref = match bytes {
-            Reference::Borrowed(bytes) => std::str::from_utf8(bytes).map(Reference::Borrowed),
-            Reference::Copied(bytes) => std::str::from_utf8(bytes).map(Reference::Copied),
+            Reference::Borrowed(bytes) => core::str::from_utf8(bytes).map(Reference::Borrowed),
+            Reference::Copied(bytes) => core::str::from_utf8(bytes).map(Reference::Copied),
         }
         .map_err(|err| {
             let pos = range.start + err.valid_up_to() + 1;
@@ -135,19 +216,18 @@ where
         Ok(str_ref)
     }
 
+    /// Decodes a string value for a given `header`, as an owned string.
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    fn decode_str_bytes_of<'s>(
-        &'s mut self,
-        header: StringHeader,
-        scratch: &'s mut Vec<u8>,
-    ) -> Result<Reference<'de, 's, [u8]>> {
-        Ok(self.decode_str_bytes_and_range_of(header, scratch)?.0)
-    }
-
-    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-    fn decode_string_of(&mut self, header: StringHeader) -> Result<String> {
+    pub fn decode_string_of(&mut self, header: StringHeader) -> Result<String> {
         let (bytes_buf, range) = self.decode_string_bytes_buf_and_range_of(header)?;
 
+        if self.config.utf8 == Utf8Policy::Replace {
+            let string = String::from_utf8(bytes_buf)
+                .unwrap_or_else(|err| String::from_utf8_lossy(err.as_bytes()).into_owned());
+
+            return Ok(string);
+        }
+
         let string = String::from_utf8(bytes_buf).map_err(|err| {
             let err = err.utf8_error();
             let pos = range.start + err.valid_up_to() + 1;
@@ -157,6 +237,73 @@ where
         Ok(string)
     }
 
+    // MARK: - Dictionary
+
+    /// Decodes a reference to a dictionary string as a `Value`, falling back
+    /// to an opaque `Value::Unknown` for unknown ids if enabled via
+    /// `DecoderConfig::unknown_headers`, so that it can be encoded again.
+    pub(super) fn decode_dictionary_ref_value(&mut self) -> Result<Value> {
+        if self.config.unknown_headers != UnknownHeaderPolicy::Collect {
+            return self.decode_string_value().map(From::from);
+        }
+
+        let unknown = self.decode_unknown_value()?;
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&unknown.body));
+        let string = decoder
+            .decode_u64()
+            .ok()
+            .filter(|_| decoder.pos == unknown.body.len())
+            .and_then(|id| self.dictionary.as_ref()?.get(id));
+
+        match string {
+            Some(string) => Ok(Value::String(StringValue::from(string.to_owned()))),
+            None => Ok(Value::Unknown(unknown)),
+        }
+    }
+
+    fn decode_dictionary_ref(&mut self) -> Result<&str> {
+        let pos = self.pos;
+        self.pull_byte()?;
+
+        let len = self.decode_u64()?;
+        let start = self.pos;
+        let id = self.decode_u64()?;
+
+        if (self.pos - start) as u64 != len {
+            return Err(Error::invalid_length(
+                (self.pos - start).to_string(),
+                len.to_string(),
+                Some(start),
+            ));
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(id = id);
+
+        self.dictionary
+            .as_ref()
+            .and_then(|dictionary| dictionary.get(id))
+            .ok_or_else(|| {
+                Error::invalid_value(
+                    format!("dictionary id {id}"),
+                    "registered dictionary id".to_owned(),
+                    Some(pos),
+                )
+            })
+    }
+
+    // MARK: - Private
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn decode_str_bytes_of<'s>(
+        &'s mut self,
+        header: StringHeader,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'de, 's, [u8]>> {
+        Ok(self.decode_str_bytes_and_range_of(header, scratch)?.0)
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     fn decode_string_bytes_buf_of(&mut self, header: StringHeader) -> Result<Vec<u8>> {
         Ok(self.decode_string_bytes_buf_and_range_of(header)?.0)
@@ -167,19 +314,11 @@ where
         &mut self,
         header: StringHeader,
     ) -> Result<(Vec<u8>, Range<usize>)> {
-        let mut buf = Vec::new();
+        let start = self.pos;
+        let buf = self.pull_bytes_buf(header.len())?;
+        let range = start..(start + buf.len());
 
-        let (bytes, range) = self.decode_str_bytes_and_range_of(header, &mut buf)?;
-
-        match bytes {
-            Reference::Borrowed(slice) => {
-                debug_assert_eq!(buf.len(), 0);
-                buf.extend_from_slice(slice);
-            }
-            Reference::Copied(slice) => {
-                debug_assert_eq!(slice.len(), buf.len());
-            }
-        }
+        self.intern(&buf);
 
         Ok((buf, range))
     }
@@ -193,9 +332,32 @@ where
         scratch.clear();
 
         let start = self.pos;
+
+        if self.interning {
+            let (buf, range) = self.decode_string_bytes_buf_and_range_of(header)?;
+            *scratch = buf;
+
+            return Ok((Reference::Copied(scratch), range));
+        }
+
         let bytes = self.pull_bytes(header.len(), scratch)?;
         let range = start..(start + bytes.len());
 
         Ok((bytes, range))
     }
+
+    /// Registers the decoded string `bytes` with the dictionary, if interning.
+    fn intern(&mut self, bytes: &[u8]) {
+        if !self.interning || bytes.len() < MIN_INTERNED_LEN {
+            return;
+        }
+
+        let (Some(dictionary), Ok(string)) =
+            (self.dictionary.as_mut(), core::str::from_utf8(bytes))
+        else {
+            return;
+        };
+
+        dictionary.register(string);
+    }
 }
@@ -0,0 +1,59 @@
+use alloc::{borrow::ToOwned, string::ToString};
+use core::time::Duration;
+
+use crate::{
+    error::{Error, Result},
+    timestamp::{Timestamp, DURATION_TAG, NANOS_PER_SEC, TIMESTAMP_TAG},
+};
+
+use super::{Decoder, Read};
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a timestamp, as encoded by `Encoder::encode_timestamp()`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_timestamp(&mut self) -> Result<Timestamp> {
+        let body_end = self.decode_extension_start(TIMESTAMP_TAG, "timestamp")?;
+
+        let secs = self.decode_i64()?;
+        let nanos = self.decode_nanos()?;
+
+        self.check_body_end(body_end)?;
+
+        Ok(Timestamp::new(secs, nanos).expect("nanos should be valid"))
+    }
+
+    /// Decodes a duration, as encoded by `Encoder::encode_duration()`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_duration(&mut self) -> Result<Duration> {
+        let body_end = self.decode_extension_start(DURATION_TAG, "duration")?;
+
+        let secs = self.decode_u64()?;
+        let nanos = self.decode_nanos()?;
+
+        self.check_body_end(body_end)?;
+
+        Ok(Duration::new(secs, nanos))
+    }
+
+    // MARK: - Private
+
+    fn decode_nanos(&mut self) -> Result<u32> {
+        let pos = self.pos;
+        let nanos = self.decode_u32()?;
+
+        if nanos >= NANOS_PER_SEC {
+            return Err(Error::invalid_value(
+                nanos.to_string(),
+                "nanoseconds within a second".to_owned(),
+                Some(pos),
+            ));
+        }
+
+        Ok(nanos)
+    }
+}
@@ -0,0 +1,67 @@
+use alloc::borrow::ToOwned;
+
+use crate::{
+    error::{Error, Result},
+    header::SeqHeader,
+    value::UnknownValue,
+};
+
+use super::{Decoder, Read};
+
+impl<'r, R> Decoder<R>
+where
+    R: Read<'r>,
+{
+    // MARK: - Value
+
+    /// Decodes a value with a reserved header, as an opaque `UnknownValue`.
+    ///
+    /// Unlike `decode_value()` this does not depend on `DecoderConfig::unknown_headers`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_unknown_value(&mut self) -> Result<UnknownValue> {
+        let header = self.pull_reserved_byte()?;
+        let len = self.decode_unknown_len()?;
+        let body = self.pull_bytes_buf(len)?;
+
+        Ok(UnknownValue::new(header, body))
+    }
+
+    // MARK: - Skip
+
+    /// Skips a value with a reserved header.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn skip_unknown_value(&mut self) -> Result<()> {
+        self.pull_reserved_byte()?;
+        let len = self.decode_unknown_len()?;
+
+        self.skip_bytes(len)
+    }
+
+    // MARK: - Private
+
+    fn pull_reserved_byte(&mut self) -> Result<u8> {
+        let pos = self.pos;
+        let byte = self.pull_byte()?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(byte = crate::binary::fmt_byte(byte));
+
+        if !SeqHeader::is_collectable(byte) {
+            return Err(Error::invalid_type(
+                crate::binary::fmt_byte(byte),
+                "reserved header".to_owned(),
+                Some(pos),
+            ));
+        }
+
+        Ok(byte)
+    }
+
+    fn decode_unknown_len(&mut self) -> Result<usize> {
+        let pos = self.pos;
+        let len = self.decode_u64()?;
+
+        len.try_into()
+            .map_err(|_| Error::length_exceeds_platform(len, Some(pos)))
+    }
+}
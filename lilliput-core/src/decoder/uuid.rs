@@ -0,0 +1,31 @@
+use alloc::{borrow::ToOwned, string::ToString};
+
+use crate::{
+    error::{Error, Result},
+    value::UUID_TAG,
+};
+
+use super::{Decoder, Read};
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a UUID, as encoded by `Encoder::encode_uuid()`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_uuid(&mut self) -> Result<::uuid::Uuid> {
+        let body_end = self.decode_extension_start(UUID_TAG, "UUID")?;
+
+        let pos = self.pos;
+        let bytes = self.decode_bytes_buf()?;
+        let uuid = ::uuid::Uuid::from_slice(&bytes).map_err(|_| {
+            Error::invalid_length(bytes.len().to_string(), "16 bytes".to_owned(), Some(pos))
+        })?;
+
+        self.check_body_end(body_end)?;
+
+        Ok(uuid)
+    }
+}
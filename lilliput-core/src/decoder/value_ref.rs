@@ -0,0 +1,121 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    vec::Vec,
+};
+
+use crate::{
+    error::Result,
+    header::{Header, MapHeader, SeqHeader, StringHeader},
+    io::{Read, Reference},
+    marker::Marker,
+    value::ValueRef,
+};
+
+use super::Decoder;
+
+impl<'de, R> Decoder<R>
+where
+    R: Read<'de>,
+{
+    // MARK: - Value
+
+    /// Decodes a value, borrowing its strings and byte arrays from the input.
+    ///
+    /// Strings and byte arrays only get copied if the reader does not support
+    /// borrowing (e.g. for `StdIoReader`), or if their representation requires
+    /// decoding (e.g. front-coded map keys, or dictionary references).
+    ///
+    /// ```
+    /// use lilliput_core::{decoder::Decoder, io::SliceReader};
+    ///
+    /// // A map of `"name"` to `"lilliput"`:
+    /// let encoded = [0x19, 0x64, b'n', b'a', b'm', b'e', 0x68, b'l', b'i', b'l', b'l', b'i', b'p', b'u', b't'];
+    ///
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    /// let value = decoder.decode_value_ref().unwrap();
+    ///
+    /// assert_eq!(value.get("name").and_then(|name| name.as_str()), Some("lilliput"));
+    /// assert!(value.is_borrowed());
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn decode_value_ref(&mut self) -> Result<ValueRef<'de>> {
+        self.nested(Self::decode_nested_value_ref)
+    }
+
+    // MARK: - Private
+
+    fn decode_nested_value_ref(&mut self) -> Result<ValueRef<'de>> {
+        let byte = self.peek_byte()?;
+
+        if byte == SeqHeader::STREAM_START {
+            return self.decode_seq_stream_ref();
+        }
+
+        // Representations which can't be borrowed from the input as they are:
+        if byte == SeqHeader::COLUMNAR
+            || byte == SeqHeader::TABULAR
+            || byte == MapHeader::FRONT_CODED
+            || byte == StringHeader::DICTIONARY_REF
+            || self.collects_unknown(byte)
+        {
+            return self.decode_nested_value().map(From::from);
+        }
+
+        let mut scratch = Vec::new();
+
+        match self.decode_header()? {
+            Header::String(header) => {
+                let string = match self.decode_str_of(header, &mut scratch)? {
+                    Reference::Borrowed(string) => Cow::Borrowed(string),
+                    Reference::Copied(string) => Cow::Owned(string.to_owned()),
+                };
+
+                Ok(ValueRef::String(string))
+            }
+            Header::Bytes(header) => {
+                let bytes = match self.decode_bytes_of(header, &mut scratch)? {
+                    Reference::Borrowed(bytes) => Cow::Borrowed(bytes),
+                    Reference::Copied(bytes) => Cow::Owned(bytes.to_owned()),
+                };
+
+                Ok(ValueRef::Bytes(bytes))
+            }
+            Header::Seq(header) => {
+                let len = header.len();
+                let mut seq = Vec::with_capacity(self.capacity_for(len, 1));
+
+                for _ in 0..len {
+                    seq.push(self.decode_value_ref()?);
+                }
+
+                Ok(ValueRef::Seq(seq))
+            }
+            Header::Map(header) => {
+                let len = header.len();
+                let mut map = Vec::with_capacity(self.capacity_for(len, 2));
+
+                for _ in 0..len {
+                    let key = self.decode_value_ref()?;
+                    let value = self.decode_value_ref()?;
+                    map.push((key, value));
+                }
+
+                Ok(ValueRef::Map(map))
+            }
+            header => self.decode_value_of(header).map(From::from),
+        }
+    }
+
+    /// Decodes a streamed sequence value, including its start and end.
+    fn decode_seq_stream_ref(&mut self) -> Result<ValueRef<'de>> {
+        self.pull_byte_expecting(Marker::Seq)?;
+
+        let mut seq = Vec::new();
+
+        while !self.decode_seq_stream_end()? {
+            seq.push(self.decode_value_ref()?);
+        }
+
+        Ok(ValueRef::Seq(seq))
+    }
+}
@@ -0,0 +1,241 @@
+//! Session-scoped dictionaries of strings.
+//!
+//! For long-lived connections a dictionary of frequently used strings
+//! (e.g. schema and field names) can be negotiated once, and then attached
+//! to both the `Encoder` and the `Decoder` of every subsequent message.
+//! Strings registered with the encoder's dictionary get encoded as
+//! references to their ids, which the decoder resolves with its own.
+//!
+//! ```
+//! use lilliput_core::{
+//!     decoder::Decoder,
+//!     dictionary::Dictionary,
+//!     encoder::Encoder,
+//!     io::{SliceReader, VecWriter},
+//! };
+//!
+//! let dictionary: Dictionary = ["temperature", "humidity"].into_iter().collect();
+//!
+//! // The dictionary gets negotiated once, e.g. by sending its strings:
+//! let mut handshake = Vec::new();
+//! let mut encoder = Encoder::from_writer(VecWriter::new(&mut handshake));
+//! encoder.encode_seq_header(&encoder.header_for_seq_len(dictionary.len())).unwrap();
+//! for string in dictionary.strings() {
+//!     encoder.encode_str(string).unwrap();
+//! }
+//!
+//! let mut decoder = Decoder::from_reader(SliceReader::new(&handshake));
+//! let len = decoder.decode_seq_header().unwrap().len();
+//! let negotiated: Dictionary = (0..len).map(|_| decoder.decode_string().unwrap()).collect();
+//! assert_eq!(negotiated, dictionary);
+//!
+//! // … and then used by all subsequent messages:
+//! let mut encoded = Vec::new();
+//! Encoder::from_writer(VecWriter::new(&mut encoded))
+//!     .with_dictionary(dictionary)
+//!     .encode_str("temperature")
+//!     .unwrap();
+//! assert_eq!(encoded.len(), 3);
+//!
+//! let mut decoder = Decoder::from_reader(SliceReader::new(&encoded)).with_dictionary(negotiated);
+//! assert_eq!(decoder.decode_string().unwrap(), "temperature");
+//! ```
+//!
+//! Alternatively dictionaries can be built up on the fly, by enabling string
+//! interning on both sides via `with_interning()`: the first occurrence of each
+//! string (of at least `MIN_INTERNED_LEN` bytes) gets encoded as is and registered,
+//! with any repetitions encoded as references to it. As both sides register strings
+//! in the order they occur, the decoder has to decode (or skip) every value,
+//! in order, with the same initial dictionary as the encoder:
+//!
+//! ```
+//! use lilliput_core::{
+//!     decoder::Decoder,
+//!     encoder::Encoder,
+//!     io::{SliceReader, VecWriter},
+//! };
+//!
+//! let mut encoded = Vec::new();
+//! let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded)).with_interning();
+//! encoder.encode_str("temperature").unwrap();
+//! encoder.encode_str("temperature").unwrap();
+//! assert_eq!(encoded.len(), 12 + 3);
+//!
+//! let mut decoder = Decoder::from_reader(SliceReader::new(&encoded)).with_interning();
+//! assert_eq!(decoder.decode_string().unwrap(), "temperature");
+//! assert_eq!(decoder.decode_string().unwrap(), "temperature");
+//! ```
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+/// The minimum length (in bytes) of strings registered by string interning.
+///
+/// Shorter strings would take up about as many bytes as references to them.
+pub const MIN_INTERNED_LEN: usize = 4;
+
+/// A dictionary of strings, each registered under a numeric id.
+///
+/// Ids are assigned in order of registration, starting at `0`,
+/// so two dictionaries with the same strings registered in the
+/// same order assign the same ids.
+#[derive(Default, Clone, Eq, PartialEq, Debug)]
+pub struct Dictionary {
+    strings: Vec<String>,
+    ids: BTreeMap<String, u64>,
+}
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `string`, returning its id.
+    ///
+    /// Returns the existing id if `string` is already registered.
+    pub fn register(&mut self, string: impl Into<String>) -> u64 {
+        let string = string.into();
+
+        if let Some(&id) = self.ids.get(&string) {
+            return id;
+        }
+
+        let id = self.strings.len() as u64;
+        self.strings.push(string.clone());
+        self.ids.insert(string, id);
+
+        id
+    }
+
+    /// Returns the string registered under `id`, if any.
+    pub fn get(&self, id: u64) -> Option<&str> {
+        let index: usize = id.try_into().ok()?;
+
+        self.strings.get(index).map(String::as_str)
+    }
+
+    /// Returns the id of `string`, if registered.
+    pub fn id_of(&self, string: &str) -> Option<u64> {
+        self.ids.get(string).copied()
+    }
+
+    /// Returns the registered strings, ordered by their ids.
+    pub fn strings(&self) -> &[String] {
+        &self.strings
+    }
+
+    /// Returns the number of registered strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns `true` if no strings are registered.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Removes all registered strings.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+        self.ids.clear();
+    }
+}
+
+impl<S> FromIterator<S> for Dictionary
+where
+    S: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut dictionary = Self::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+impl<S> Extend<S> for Dictionary
+where
+    S: Into<String>,
+{
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for string in iter {
+            self.register(string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+        value::{Map, MapValue, SeqValue, StringValue, Value},
+    };
+
+    use super::*;
+
+    #[test]
+    fn register() {
+        let mut dictionary = Dictionary::new();
+
+        assert_eq!(dictionary.register("a"), 0);
+        assert_eq!(dictionary.register("b"), 1);
+        assert_eq!(dictionary.register("a"), 0);
+
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(dictionary.get(1), Some("b"));
+        assert_eq!(dictionary.get(2), None);
+        assert_eq!(dictionary.id_of("a"), Some(0));
+        assert_eq!(dictionary.id_of("c"), None);
+        assert_eq!(dictionary.strings(), ["a", "b"]);
+
+        dictionary.clear();
+        assert!(dictionary.is_empty());
+        assert_eq!(dictionary.id_of("a"), None);
+    }
+
+    #[test]
+    fn interning() {
+        let entry = |name: &str| {
+            let mut map = Map::default();
+            map.insert(
+                Value::String(StringValue::from("name".to_owned())),
+                Value::String(StringValue::from(name.to_owned())),
+            );
+            map.insert(
+                Value::String(StringValue::from("kind".to_owned())),
+                Value::String(StringValue::from("sensor".to_owned())),
+            );
+            Value::Map(MapValue::from(map))
+        };
+        let value = Value::Seq(SeqValue::from(vec![entry("a"), entry("b"), entry("c")]));
+
+        let mut plain = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut plain))
+            .encode_value(&value)
+            .unwrap();
+
+        let mut interned = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut interned)).with_interning();
+        encoder.encode_value(&value).unwrap();
+        encoder.encode_str("sensor").unwrap();
+        assert!(interned.len() < plain.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&interned)).with_interning();
+        assert_eq!(decoder.decode_value().unwrap(), value);
+        assert_eq!(decoder.decode_string().unwrap(), "sensor");
+        assert_eq!(decoder.dictionary().unwrap().len(), 3);
+        assert!(decoder.dictionary().unwrap().id_of("sensor").is_some());
+
+        // Skipped strings get interned all the same:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&interned)).with_interning();
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.decode_string().unwrap(), "sensor");
+
+        // Without interning references can't be resolved:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&interned));
+        assert!(decoder.decode_value().is_err());
+    }
+}
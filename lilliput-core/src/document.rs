@@ -0,0 +1,364 @@
+//! Lazily navigated documents.
+//!
+//! A `Document` wraps an encoded value, handing out `Cursor`s which decode
+//! only the headers (and keys) needed for reaching a nested value, skipping
+//! over all of its siblings without decoding them.
+//!
+//! ```
+//! use lilliput_core::{
+//!     document::Document,
+//!     encoder::Encoder,
+//!     io::VecWriter,
+//!     value::{Path, Value},
+//! };
+//!
+//! let value: Value = lilliput_core::text::from_str(r#"{"users": [{"name": "ada"}, {"name": "bob"}]}"#).unwrap();
+//! let mut encoded = Vec::new();
+//! Encoder::from_writer(VecWriter::new(&mut encoded)).encode_value(&value).unwrap();
+//!
+//! let document = Document::new(&encoded);
+//!
+//! let name = document.root().get("users").unwrap().unwrap().index(1).unwrap().unwrap();
+//! assert_eq!(name.get("name").unwrap().unwrap().decode_value_ref().unwrap().as_str(), Some("bob"));
+//!
+//! let path: Path = ".users[0].name".parse().unwrap();
+//! let name = document.at(&path).unwrap().unwrap();
+//! assert_eq!(name.decode_value().unwrap().as_str(), Some("ada"));
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    config::DecoderConfig,
+    decoder::Decoder,
+    error::{Error, Result},
+    io::SliceReader,
+    marker::Marker,
+    value::{Path, PathSegment, Value, ValueRef},
+};
+
+/// An encoded value, navigated lazily via `Cursor`s.
+#[derive(Copy, Clone, Debug)]
+pub struct Document<'a> {
+    bytes: &'a [u8],
+    config: DecoderConfig,
+}
+
+impl<'a> Document<'a> {
+    /// Creates a document from the encoded value in `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            config: DecoderConfig::default(),
+        }
+    }
+
+    /// Sets the config used for decoding to `config`, returning `self`.
+    pub fn with_config(mut self, config: DecoderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the document's encoded bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns a cursor pointing at the document's root value.
+    pub fn root(&self) -> Cursor<'a> {
+        Cursor {
+            bytes: self.bytes,
+            pos: 0,
+            config: self.config,
+        }
+    }
+
+    /// Returns a cursor pointing at the value at `path`, if any.
+    ///
+    /// Shorthand for `self.root().at(path)`.
+    pub fn at(&self, path: &Path) -> Result<Option<Cursor<'a>>> {
+        self.root().at(path)
+    }
+}
+
+/// A position of a value within a `Document`.
+///
+/// Errors report positions relative to the start of the document.
+#[derive(Copy, Clone, Debug)]
+pub struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    config: DecoderConfig,
+}
+
+impl<'a> Cursor<'a> {
+    /// Returns the position of the value's header within the document.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the value's type `Marker`.
+    pub fn marker(&self) -> Result<Marker> {
+        self.with_decoder(|decoder| decoder.peek_marker())
+    }
+
+    /// Returns a cursor pointing at the value for the string `key`,
+    /// if the value is a map containing it.
+    pub fn get(&self, key: &str) -> Result<Option<Self>> {
+        self.find(|decoder| find_key(decoder, Key::Str(key)))
+    }
+
+    /// Returns a cursor pointing at the value for `key`,
+    /// if the value is a map containing it.
+    pub fn get_key(&self, key: &Value) -> Result<Option<Self>> {
+        let key = key.as_str().map_or(Key::Value(key), Key::Str);
+
+        self.find(|decoder| find_key(decoder, key))
+    }
+
+    /// Returns a cursor pointing at the item at `index`,
+    /// if the value is a sequence of more than `index` items.
+    ///
+    /// The items of columnar (and tabular) sequences are not stored as values
+    /// of their own, so they can't be pointed at and produce an error.
+    pub fn index(&self, index: usize) -> Result<Option<Self>> {
+        self.find(|decoder| find_index(decoder, index))
+    }
+
+    /// Returns a cursor pointing at the value at `path`, relative to `self`, if any.
+    pub fn at(&self, path: &Path) -> Result<Option<Self>> {
+        let mut cursor = *self;
+
+        for segment in path.segments() {
+            let next = match segment {
+                PathSegment::Key(key) => cursor.get_key(key)?,
+                PathSegment::Index(index) => cursor.index(*index)?,
+            };
+
+            match next {
+                Some(next) => cursor = next,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(cursor))
+    }
+
+    /// Returns the value's encoded bytes.
+    pub fn as_bytes(&self) -> Result<&'a [u8]> {
+        let len = self.with_decoder(|decoder| {
+            decoder.skip_value()?;
+            Ok(decoder.pos())
+        })?;
+
+        Ok(&self.bytes[self.pos..][..len])
+    }
+
+    /// Decodes the value.
+    pub fn decode_value(&self) -> Result<Value> {
+        self.with_decoder(|decoder| decoder.decode_value())
+    }
+
+    /// Decodes the value, borrowing its strings and byte arrays from the document.
+    pub fn decode_value_ref(&self) -> Result<ValueRef<'a>> {
+        self.with_decoder(|decoder| decoder.decode_value_ref())
+    }
+
+    /// Returns a decoder, positioned at the value.
+    ///
+    /// The decoder's positions are relative to the cursor's position.
+    pub fn decoder(&self) -> Decoder<SliceReader<'a>> {
+        let bytes = self.bytes.get(self.pos..).unwrap_or_default();
+
+        Decoder::new(SliceReader::new(bytes), self.config)
+    }
+
+    // MARK: - Private
+
+    fn with_decoder<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Decoder<SliceReader<'a>>) -> Result<T>,
+    {
+        f(&mut self.decoder()).map_err(|err| err.offset_pos(self.pos))
+    }
+
+    /// Runs `find`, turning the relative position it returns (if any) into a cursor.
+    fn find<F>(&self, find: F) -> Result<Option<Self>>
+    where
+        F: FnOnce(&mut Decoder<SliceReader<'a>>) -> Result<Option<usize>>,
+    {
+        let pos = self.with_decoder(find)?;
+
+        Ok(pos.map(|pos| Self {
+            pos: self.pos + pos,
+            ..*self
+        }))
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Key<'k> {
+    Str(&'k str),
+    Value(&'k Value),
+}
+
+/// Finds the entry for `key` in the map the decoder is positioned at,
+/// returning the position of its value, if found.
+fn find_key(decoder: &mut Decoder<SliceReader<'_>>, key: Key<'_>) -> Result<Option<usize>> {
+    if decoder.peek_front_coded_map()? {
+        // Front-coded maps only ever have string keys:
+        let found = match key {
+            Key::Str(key) => decoder.seek_front_coded_map_key(key)?,
+            Key::Value(_) => false,
+        };
+
+        return Ok(found.then(|| decoder.pos()));
+    }
+
+    let len = decoder.decode_map_len()?;
+    let mut scratch = Vec::new();
+
+    for _ in 0..len {
+        let is_match = match (key, decoder.peek_marker()?) {
+            (Key::Str(key), Marker::String) => &*decoder.decode_str(&mut scratch)? == key,
+            (Key::Str(_), _) | (Key::Value(_), Marker::String) => {
+                decoder.skip_value()?;
+                false
+            }
+            (Key::Value(key), _) => decoder.decode_value()? == *key,
+        };
+
+        if is_match {
+            return Ok(Some(decoder.pos()));
+        }
+
+        decoder.skip_value()?;
+    }
+
+    Ok(None)
+}
+
+/// Finds the item at `index` in the sequence the decoder is positioned at,
+/// returning its position, if found.
+fn find_index(decoder: &mut Decoder<SliceReader<'_>>, index: usize) -> Result<Option<usize>> {
+    if decoder.peek_columnar_seq()? {
+        return Err(Error::uncategorized(
+            "can't point at items of columnar sequence",
+            Some(decoder.pos()),
+        ));
+    }
+
+    if decoder.peek_tabular_seq()? {
+        return Err(Error::uncategorized(
+            "can't point at items of tabular sequence",
+            Some(decoder.pos()),
+        ));
+    }
+
+    match decoder.decode_seq_start()? {
+        Some(len) if index >= len => Ok(None),
+        Some(_) => {
+            for _ in 0..index {
+                decoder.skip_value()?;
+            }
+
+            Ok(Some(decoder.pos()))
+        }
+        None => {
+            for _ in 0..index {
+                if decoder.decode_seq_stream_end()? {
+                    return Ok(None);
+                }
+
+                decoder.skip_value()?;
+            }
+
+            Ok((!decoder.decode_seq_stream_end()?).then(|| decoder.pos()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::{EncoderConfig, MapKeyCoding, SeqCoding},
+        encoder::Encoder,
+        error::ErrorCode,
+        io::VecWriter,
+    };
+
+    use super::*;
+
+    fn encode(value: &Value, config: EncoderConfig) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        Encoder::new(VecWriter::new(&mut encoded), config)
+            .encode_value(value)
+            .unwrap();
+        encoded
+    }
+
+    #[test]
+    fn navigate() {
+        let value: Value = crate::text::from_str(
+            r#"{"id": 7_u8, 1_u8: "one", "tags": ["a", "b"], "nested": {"deep": [null, true]}}"#,
+        )
+        .unwrap();
+
+        for config in [
+            EncoderConfig::default(),
+            EncoderConfig::default().with_map_keys(MapKeyCoding::FrontCoded),
+        ] {
+            let encoded = encode(&value, config);
+            let document = Document::new(&encoded);
+            let root = document.root();
+
+            assert_eq!(root.marker().unwrap(), Marker::Map);
+            assert_eq!(root.as_bytes().unwrap(), &encoded[..]);
+            assert!(root.get("missing").unwrap().is_none());
+
+            let tags = root.get("tags").unwrap().unwrap();
+            let tag = tags.index(1).unwrap().unwrap();
+            assert_eq!(tag.decode_value_ref().unwrap().as_str(), Some("b"));
+            assert!(tags.index(2).unwrap().is_none());
+
+            let path: Path = ".nested.deep[1]".parse().unwrap();
+            let deep = document.at(&path).unwrap().unwrap();
+            assert_eq!(deep.decode_value().unwrap().as_bool(), Some(true));
+
+            let err = tags.get("a").unwrap_err();
+            assert_eq!(err.code(), ErrorCode::InvalidType);
+            assert_eq!(err.pos(), Some(tags.pos()));
+        }
+
+        // Non-string keys:
+        let encoded = encode(&value, EncoderConfig::default());
+        let path: Path = "[1_u8]".parse().unwrap();
+        let one = Document::new(&encoded).at(&path).unwrap().unwrap();
+        assert_eq!(one.decode_value().unwrap().as_str(), Some("one"));
+    }
+
+    #[test]
+    fn streamed_columnar_and_tabular_seqs() {
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_seq_stream_start().unwrap();
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_u8(2).unwrap();
+        encoder.encode_seq_stream_end().unwrap();
+
+        let root = Document::new(&encoded).root();
+        let item = root.index(1).unwrap().unwrap();
+        assert_eq!(item.decode_value().unwrap().as_u64(), Some(2));
+        assert!(root.index(2).unwrap().is_none());
+
+        let value: Value = crate::text::from_str(r#"[{"a": 1_u8}, {"a": 2_u8}]"#).unwrap();
+        for seqs in [SeqCoding::Columnar, SeqCoding::Tabular] {
+            let encoded = encode(&value, EncoderConfig::default().with_seqs(seqs));
+            let root = Document::new(&encoded).root();
+            assert!(root.index(0).is_err());
+            assert_eq!(root.decode_value().unwrap(), value);
+        }
+    }
+}
@@ -1,16 +1,55 @@
 //! Encoders for encoding lilliput values.
 
-use crate::{config::EncoderConfig, error::Result, header::Header, io::Write, value::Value};
+use alloc::{format, vec::Vec};
 
+use crate::{
+    config::EncoderConfig,
+    decoder::Decoder,
+    dictionary::Dictionary,
+    error::{Error, Result},
+    header::Header,
+    io::{SliceReader, VecWriter, Write},
+    marker::Marker,
+    preamble::{FORMAT_VERSION, PREAMBLE_MAGIC},
+    value::Value,
+};
+
+pub use self::{
+    constant::ConstEncoder,
+    stats::{EncoderStats, HeaderPacking, MarkerStats},
+};
+
+#[cfg(feature = "async")]
+pub use self::asynchronous::AsyncEncoder;
+#[cfg(any(test, feature = "validating"))]
+pub use self::validating::ValidatingEncoder;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "bigint")]
+mod big_int;
 mod bool;
 mod bytes;
+mod constant;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod ext;
 mod float;
+#[cfg(test)]
+mod golden;
 mod int;
 mod map;
 mod null;
 mod seq;
+mod stats;
 mod string;
+mod timestamp;
 mod unit;
+mod unknown;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(any(test, feature = "validating"))]
+mod validating;
 
 /// An encoder for encoding lilliput values.
 #[derive(Debug)]
@@ -18,6 +57,10 @@ pub struct Encoder<W> {
     writer: W,
     pos: usize,
     config: EncoderConfig,
+    stats: Option<EncoderStats>,
+    dictionary: Option<Dictionary>,
+    /// Whether encoded strings get registered with the dictionary.
+    interning: bool,
 }
 
 impl<W> Encoder<W> {
@@ -32,18 +75,100 @@ impl<W> Encoder<W> {
             writer,
             pos: 0,
             config,
+            stats: None,
+            dictionary: None,
+            interning: false,
         }
     }
 
+    /// Enables the collection of encoding statistics, returning `self`.
+    pub fn with_stats(mut self) -> Self {
+        self.stats = Some(EncoderStats::default());
+        self
+    }
+
+    /// Returns the statistics collected so far, if enabled.
+    pub fn stats(&self) -> Option<&EncoderStats> {
+        self.stats.as_ref()
+    }
+
+    /// Returns the statistics collected so far, if enabled, resetting them.
+    pub fn take_stats(&mut self) -> Option<EncoderStats> {
+        self.stats.as_mut().map(core::mem::take)
+    }
+
+    /// Attaches `dictionary`, returning `self`.
+    ///
+    /// Strings registered with the dictionary get encoded as references to their ids.
+    /// See the [`dictionary`](crate::dictionary) module for details.
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Returns the attached dictionary, if any.
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.dictionary.as_ref()
+    }
+
+    /// Returns the attached dictionary, if any, for registering further strings.
+    pub fn dictionary_mut(&mut self) -> Option<&mut Dictionary> {
+        self.dictionary.as_mut()
+    }
+
+    /// Replaces the attached dictionary with `dictionary`, returning the previous one.
+    pub fn set_dictionary(&mut self, dictionary: Option<Dictionary>) -> Option<Dictionary> {
+        core::mem::replace(&mut self.dictionary, dictionary)
+    }
+
+    /// Enables string interning, returning `self`.
+    ///
+    /// Strings of at least `MIN_INTERNED_LEN` bytes get registered with the
+    /// attached dictionary (an empty one, if none) once encoded, with any repetitions
+    /// encoded as references to them. Requires a decoder with interning enabled.
+    /// See the [`dictionary`](crate::dictionary) module for details.
+    pub fn with_interning(mut self) -> Self {
+        self.dictionary.get_or_insert_with(Dictionary::default);
+        self.interning = true;
+        self
+    }
+
     /// Returns the encoder's internal `writer`, consuming `self`.
     pub fn into_writer(self) -> W {
         self.writer
     }
 
+    /// Returns the encoder's configuration.
+    pub fn config(&self) -> &EncoderConfig {
+        &self.config
+    }
+
     /// Returns the encoder's current write position.
     pub fn pos(&self) -> usize {
         self.pos
     }
+
+    /// Creates an encoder for encoding values into `writer` ahead of `self`,
+    /// e.g. for buffering a container's elements until their count is known.
+    ///
+    /// The fork takes over the statistics and dictionary of `self`,
+    /// until handing them back via `join()`.
+    pub fn fork<V>(&mut self, writer: V) -> Encoder<V> {
+        let mut encoder = Encoder::new(writer, self.config.clone());
+        encoder.stats = self.stats.take();
+        encoder.dictionary = self.dictionary.take();
+        encoder.interning = self.interning;
+        encoder
+    }
+
+    /// Takes back the statistics and dictionary from `fork`,
+    /// returning its internal writer, for passing its bytes
+    /// to `encode_forked_bytes()`.
+    pub fn join<V>(&mut self, mut fork: Encoder<V>) -> V {
+        self.stats = fork.stats.take();
+        self.dictionary = fork.dictionary.take();
+        fork.writer
+    }
 }
 
 impl<W> Encoder<W>
@@ -62,7 +187,57 @@ where
             Header::Bool(value) => self.encode_bool_header(value),
             Header::Unit(value) => self.encode_unit_header(value),
             Header::Null(value) => self.encode_null_header(value),
+            Header::Ext(value) => self.encode_ext_header(value),
+        }
+    }
+
+    /// Encodes a preamble, identifying the document that follows as lilliput.
+    ///
+    /// See the [`preamble`](crate::preamble) module for details.
+    pub fn encode_preamble(&mut self) -> Result<()> {
+        self.push_bytes(&PREAMBLE_MAGIC)?;
+        self.push_byte(FORMAT_VERSION)?;
+        self.push_byte(self.config.profile as u8)
+    }
+
+    /// Writes already-encoded `bytes` verbatim.
+    ///
+    /// The caller is responsible for `bytes` containing
+    /// a valid encoding, as they are written without validation.
+    pub fn encode_raw_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.push_bytes(bytes)
+    }
+
+    /// Writes the `bytes` encoded by a fork (see `fork()`) verbatim.
+    ///
+    /// Unlike `encode_raw_bytes()` this doesn't record `bytes` with
+    /// the statistics, as the fork has done so already.
+    pub fn encode_forked_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer.write(bytes)?;
+        self.pos += bytes.len();
+
+        Ok(())
+    }
+
+    /// Writes an already-encoded value's `bytes` verbatim,
+    /// allowing for documents to be composed from cached fragments.
+    ///
+    /// Unlike `encode_raw_bytes()` this validates that `bytes` contain
+    /// exactly one well-formed encoded value before writing them,
+    /// returning an error (with nothing written) otherwise.
+    pub fn encode_raw_value(&mut self, bytes: &[u8]) -> Result<()> {
+        let mut decoder = Decoder::from_reader(SliceReader::new(bytes));
+        decoder.decode_value()?;
+
+        if decoder.pos() != bytes.len() {
+            return Err(Error::invalid_length(
+                format!("{} bytes", bytes.len()),
+                format!("{} bytes of a single encoded value", decoder.pos()),
+                Some(decoder.pos()),
+            ));
         }
+
+        self.push_bytes(bytes)
     }
 
     /// Encodes a `Value`.
@@ -77,6 +252,8 @@ where
             Value::Bool(value) => self.encode_bool_value(value),
             Value::Unit(value) => self.encode_unit_value(value),
             Value::Null(value) => self.encode_null_value(value),
+            Value::Ext(value) => self.encode_ext_value(value),
+            Value::Unknown(value) => self.encode_unknown_value(value),
         }
     }
 }
@@ -87,6 +264,39 @@ impl<W> Encoder<W>
 where
     W: Write,
 {
+    /// Encodes a value with the header `byte`, followed by the length of its body
+    /// and the body itself, as encoded by `encode_body` into a buffer.
+    ///
+    /// The body gets encoded with `self`'s statistics and dictionary.
+    fn encode_length_prefixed<F>(&mut self, byte: u8, marker: Marker, encode_body: F) -> Result<()>
+    where
+        F: FnOnce(&mut Encoder<VecWriter<'_>>) -> Result<()>,
+    {
+        self.config.profile.check(byte, Some(self.pos))?;
+
+        let mut body = Vec::new();
+        let mut encoder = Encoder::new(VecWriter::new(&mut body), self.config.clone());
+        encoder.stats = self.stats.take();
+        encoder.dictionary = self.dictionary.take();
+        encoder.interning = self.interning;
+
+        let result = encode_body(&mut encoder);
+        self.stats = encoder.stats.take();
+        self.dictionary = encoder.dictionary.take();
+        result?;
+
+        self.record_header(marker, HeaderPacking::Compact);
+
+        self.push_byte(byte)?;
+        self.encode_u64(body.len() as u64)?;
+
+        // The body's bytes have already been recorded, while encoding it:
+        self.writer.write(&body)?;
+        self.pos += body.len();
+
+        Ok(())
+    }
+
     #[inline]
     fn push_byte(&mut self, byte: u8) -> Result<()> {
         self.push_bytes(&[byte])
@@ -96,15 +306,70 @@ where
         self.writer.write(bytes)?;
         self.pos += bytes.len();
 
+        if let Some(stats) = self.stats.as_mut() {
+            stats.record_bytes(bytes.len());
+        }
+
+        Ok(())
+    }
+
+    /// Pushes exactly `len` bytes read from `reader`, in chunks,
+    /// passing each chunk to `inspect` before pushing it.
+    #[cfg(feature = "std")]
+    fn push_bytes_from_reader<R, F>(
+        &mut self,
+        len: usize,
+        reader: &mut R,
+        mut inspect: F,
+    ) -> Result<()>
+    where
+        R: std::io::Read,
+        F: FnMut(&[u8]) -> Result<()>,
+    {
+        const CHUNK_LEN: usize = 8 * 1024;
+
+        let mut buf = [0b0; CHUNK_LEN];
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let chunk = &mut buf[..remaining.min(CHUNK_LEN)];
+
+            let read = match reader.read(chunk) {
+                Ok(0) => return Err(Error::end_of_file()),
+                Ok(read) => read,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::io(err)),
+            };
+
+            inspect(&chunk[..read])?;
+            self.push_bytes(&chunk[..read])?;
+
+            remaining -= read;
+        }
+
         Ok(())
     }
+
+    #[inline]
+    fn record_header(&mut self, marker: Marker, packing: HeaderPacking) {
+        if let Some(stats) = self.stats.as_mut() {
+            stats.record_header(marker, packing);
+        }
+    }
 }
 
 // MARK: - Tests
 
 #[cfg(test)]
 mod test {
-    use crate::io::{StdIoWriter, VecWriter};
+    use proptest::prelude::*;
+
+    use crate::{
+        config::PackingMode,
+        error::ErrorCode,
+        header::SeqHeader,
+        io::{StdIoWriter, VecWriter},
+    };
 
     use super::*;
 
@@ -130,4 +395,168 @@ mod test {
 
         assert_eq!(vec, vec![1, 2, 3]);
     }
+
+    #[test]
+    fn stats() {
+        let mut vec: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut vec);
+        let mut encoder = Encoder::from_writer(writer).with_stats();
+
+        encoder
+            .encode_seq_header(&SeqHeader::for_len(3, PackingMode::Optimal))
+            .unwrap();
+        encoder.encode_u8(42).unwrap();
+        encoder.encode_u16(1000).unwrap();
+        encoder.encode_str("hello").unwrap();
+
+        let stats = encoder.take_stats().unwrap();
+
+        let seq = stats.for_marker(Marker::Seq);
+        assert_eq!(seq.values, 1);
+        assert_eq!(seq.compact, 1);
+        assert_eq!(seq.bytes, 1);
+
+        let int = stats.for_marker(Marker::Int);
+        assert_eq!(int.values, 2);
+        assert_eq!(int.extended_with_width(1), 1);
+        assert_eq!(int.extended_with_width(2), 1);
+        assert_eq!(int.bytes, 2 + 3);
+
+        let string = stats.for_marker(Marker::String);
+        assert_eq!(string.values, 1);
+        assert_eq!(string.bytes, 1 + 5);
+
+        assert_eq!(stats.total_values(), 4);
+        assert_eq!(stats.total_bytes(), encoder.pos());
+
+        assert_eq!(encoder.stats(), Some(&EncoderStats::default()));
+
+        encoder.encode_u128(u128::MAX).unwrap();
+
+        let stats = encoder.take_stats().unwrap();
+        let int = stats.for_marker(Marker::Int);
+        assert_eq!(int.extended_with_width(16), 1);
+    }
+
+    #[test]
+    fn encode_raw_value() {
+        let mut fragment: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut fragment));
+        encoder.encode_str("lilliput").unwrap();
+
+        let mut vec: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut vec);
+        let mut encoder = Encoder::from_writer(writer);
+
+        encoder
+            .encode_seq_header(&SeqHeader::for_len(2, PackingMode::Optimal))
+            .unwrap();
+        encoder.encode_raw_value(&fragment).unwrap();
+        encoder.encode_raw_value(&fragment).unwrap();
+
+        // Truncated:
+        let error_code = (encoder.encode_raw_value(&fragment[..3]))
+            .unwrap_err()
+            .code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+
+        // Trailing bytes:
+        let mut trailing = fragment.clone();
+        trailing.push(0b0000_0000);
+        let error_code = encoder.encode_raw_value(&trailing).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidLength);
+
+        assert_eq!(encoder.pos(), 1 + 2 * fragment.len());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&vec));
+        let decoded = decoder.decode_seq().unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoder.pos(), vec.len());
+    }
+
+    #[test]
+    fn stats_disabled() {
+        let mut vec: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut vec);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_bool(true).unwrap();
+
+        assert_eq!(encoder.stats(), None);
+    }
+
+    /// A reader providing at most `chunk_len` bytes per read.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        chunk_len: usize,
+    }
+
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = buf.len().min(self.chunk_len).min(self.bytes.len());
+            let (chunk, rest) = self.bytes.split_at(len);
+            buf[..len].copy_from_slice(chunk);
+            self.bytes = rest;
+            Ok(len)
+        }
+    }
+
+    fn encode_with<F>(encode: F) -> Result<Vec<u8>>
+    where
+        F: FnOnce(&mut Encoder<VecWriter<'_>>) -> Result<()>,
+    {
+        let mut vec: Vec<u8> = Vec::new();
+        encode(&mut Encoder::from_writer(VecWriter::new(&mut vec)))?;
+        Ok(vec)
+    }
+
+    #[test]
+    fn encode_from_reader_errors() {
+        // Too few bytes:
+        let mut reader = Trickle {
+            bytes: b"lilli",
+            chunk_len: 2,
+        };
+        let error_code = encode_with(|encoder| encoder.encode_bytes_from_reader(8, &mut reader))
+            .unwrap_err()
+            .code();
+        assert_eq!(error_code, ErrorCode::UnexpectedEndOfFile);
+
+        // Invalid UTF-8:
+        let mut reader = Trickle {
+            bytes: b"lil\xffput",
+            chunk_len: 2,
+        };
+        let error =
+            encode_with(|encoder| encoder.encode_str_from_reader(7, &mut reader)).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::Utf8);
+        assert_eq!(error.pos(), Some(1 + 3));
+
+        // Char split by the end of the string:
+        let mut reader = Trickle {
+            bytes: "ñ".as_bytes(),
+            chunk_len: 2,
+        };
+        let error_code = encode_with(|encoder| encoder.encode_str_from_reader(1, &mut reader))
+            .unwrap_err()
+            .code();
+        assert_eq!(error_code, ErrorCode::Utf8);
+    }
+
+    proptest! {
+        #[test]
+        fn encode_str_from_reader(value in any::<String>(), chunk_len in 1_usize..8) {
+            let mut reader = Trickle { bytes: value.as_bytes(), chunk_len };
+            let encoded = encode_with(|encoder| encoder.encode_str_from_reader(value.len(), &mut reader)).unwrap();
+
+            prop_assert_eq!(encoded, encode_with(|encoder| encoder.encode_str(&value)).unwrap());
+        }
+
+        #[test]
+        fn encode_bytes_from_reader(value in any::<Vec<u8>>(), chunk_len in 1_usize..8) {
+            let mut reader = Trickle { bytes: &value, chunk_len };
+            let encoded = encode_with(|encoder| encoder.encode_bytes_from_reader(value.len(), &mut reader)).unwrap();
+
+            prop_assert_eq!(encoded, encode_with(|encoder| encoder.encode_bytes(&value)).unwrap());
+        }
+    }
 }
@@ -0,0 +1,89 @@
+use tokio::io::{AsyncWrite, AsyncWriteExt as _};
+
+use crate::{
+    config::EncoderConfig,
+    error::{Error, Result},
+    io::VecWriter,
+    value::Value,
+};
+
+use super::Encoder;
+
+/// An encoder for encoding lilliput values into an async writer.
+///
+/// Each value gets encoded into an internal buffer first,
+/// which then gets written to the writer as a whole.
+///
+/// Only available with the `async` feature enabled.
+#[derive(Debug)]
+pub struct AsyncEncoder<W> {
+    writer: W,
+    config: EncoderConfig,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+impl<W> AsyncEncoder<W> {
+    /// Creates an encoder from `writer`.
+    pub fn from_writer(writer: W) -> Self {
+        Self::new(writer, EncoderConfig::default())
+    }
+
+    /// Creates an encoder from `writer`, configured by `config`.
+    pub fn new(writer: W, config: EncoderConfig) -> Self {
+        Self {
+            writer,
+            config,
+            buffer: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the encoder's internal `writer`, consuming `self`.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+
+    /// Returns the encoder's current write position.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<W> AsyncEncoder<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// Encodes a `Value`.
+    pub async fn encode_value(&mut self, value: &Value) -> Result<()> {
+        self.encode_with(|encoder| encoder.encode_value(value))
+            .await
+    }
+
+    /// Encodes whatever `encode` encodes into the provided encoder,
+    /// allowing for use of all of `Encoder`'s methods.
+    ///
+    /// Nothing gets written, if `encode` returns an error.
+    pub async fn encode_with<F>(&mut self, encode: F) -> Result<()>
+    where
+        F: FnOnce(&mut Encoder<VecWriter<'_>>) -> Result<()>,
+    {
+        self.buffer.clear();
+
+        let mut encoder = Encoder::new(VecWriter::new(&mut self.buffer), self.config.clone());
+        encode(&mut encoder)?;
+
+        self.writer
+            .write_all(&self.buffer)
+            .await
+            .map_err(Error::io)?;
+        self.pos += self.buffer.len();
+
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.writer.flush().await.map_err(Error::io)
+    }
+}
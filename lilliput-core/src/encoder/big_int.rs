@@ -0,0 +1,21 @@
+use num_bigint::BigInt;
+
+use crate::{error::Result, header::SeqHeader, io::Write, marker::Marker, value::BIG_INT_TAG};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a big integer, as an extension value wrapping
+    /// its big-endian two's complement bytes.
+    pub fn encode_big_int(&mut self, value: &BigInt) -> Result<()> {
+        self.encode_length_prefixed(SeqHeader::EXTENSION, Marker::Seq, |encoder| {
+            encoder.encode_u64(BIG_INT_TAG)?;
+            encoder.encode_bytes(&value.to_signed_bytes_be())
+        })
+    }
+}
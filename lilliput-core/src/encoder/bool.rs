@@ -1,6 +1,8 @@
-use crate::{binary, error::Result, header::BoolHeader, io::Write, value::BoolValue};
+use crate::{
+    binary, error::Result, header::BoolHeader, io::Write, marker::Marker, value::BoolValue,
+};
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -37,6 +39,8 @@ where
             value = header.value()
         );
 
+        self.record_header(Marker::Bool, HeaderPacking::Compact);
+
         self.push_byte(byte)
     }
 
@@ -1,9 +1,9 @@
 use crate::{
-    config::PackingMode, error::Result, header::BytesHeader, io::Write,
+    config::PackingMode, error::Result, header::BytesHeader, io::Write, marker::Marker,
     num::WithPackedBeBytes as _, value::BytesValue,
 };
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -21,6 +21,22 @@ where
         Ok(())
     }
 
+    /// Encodes a byte array value of `len` bytes, streaming them from `reader`
+    /// in chunks, so that they never need to be contiguous in memory.
+    ///
+    /// Returns an error if `reader` ends before providing `len` bytes,
+    /// in which case the value is left partially encoded.
+    #[cfg(feature = "std")]
+    pub fn encode_bytes_from_reader<R>(&mut self, len: usize, reader: &mut R) -> Result<()>
+    where
+        R: std::io::Read,
+    {
+        self.encode_bytes_header(&BytesHeader::for_len(len))?;
+
+        // Push the value's actual bytes:
+        self.push_bytes_from_reader(len, reader, |_| Ok(()))
+    }
+
     /// Encodes a byte array value, from a `BytesValue`.
     pub fn encode_bytes_value(&mut self, value: &BytesValue) -> Result<()> {
         self.encode_bytes(&value.0)
@@ -50,10 +66,15 @@ where
             #[cfg(feature = "tracing")]
             tracing::debug!(
                 byte = crate::binary::fmt_byte(byte),
-                bytes = format!("{:b}", crate::binary::BytesSlice(bytes)),
+                bytes = alloc::format!("{:b}", crate::binary::BytesSlice(bytes)),
                 len = len
             );
 
+            self.record_header(
+                Marker::Bytes,
+                HeaderPacking::Extended { width: width as u8 },
+            );
+
             // Push the value's header:
             self.push_byte(byte)?;
 
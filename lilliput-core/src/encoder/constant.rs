@@ -0,0 +1,361 @@
+use crate::header::{
+    BoolHeader, BytesHeader, IntHeader, MapHeader, NullHeader, SeqHeader, StringHeader, UnitHeader,
+};
+
+/// An encoder for encoding lilliput values at compile time.
+///
+/// The produced encodings are identical to those of an `Encoder`
+/// using the default (i.e. optimal) packing modes.
+///
+/// Floating-point values are not supported, as their bit-representations
+/// are not accessible from within const contexts on the crate's minimum
+/// supported Rust version.
+///
+/// # Example
+///
+/// ```
+/// use lilliput_core::encoder::ConstEncoder;
+///
+/// static DEFAULTS: &[u8] = lilliput_core::encode_const!(
+///     32,
+///     ConstEncoder::new()
+///         .encode_map_header(2)
+///         .encode_str("retries")
+///         .encode_u64(3)
+///         .encode_str("verbose")
+///         .encode_bool(false)
+/// );
+///
+/// assert_eq!(DEFAULTS.len(), 19);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct ConstEncoder<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for ConstEncoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ConstEncoder<N> {
+    /// Creates an encoder with a capacity of `N` bytes.
+    pub const fn new() -> Self {
+        Self {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the number of encoded bytes.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes have been encoded yet, otherwise `false`.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the encoded bytes.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.buf.split_at(self.len).0
+    }
+
+    /// Returns the encoded bytes as an array of exactly `M` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `M` does not match the number of encoded bytes.
+    pub const fn into_array<const M: usize>(self) -> [u8; M] {
+        assert!(M == self.len, "array length must match encoded length");
+
+        let mut array = [0; M];
+        let mut index = 0;
+        while index < M {
+            array[index] = self.buf[index];
+            index += 1;
+        }
+        array
+    }
+
+    // MARK: - Values
+
+    /// Encodes a signed integer value.
+    pub const fn encode_i64(self, value: i64) -> Self {
+        let zig_zag = ((value >> (i64::BITS - 1)) as u64) ^ ((value << 1) as u64);
+        self.encode_int(true, zig_zag)
+    }
+
+    /// Encodes an unsigned integer value.
+    pub const fn encode_u64(self, value: u64) -> Self {
+        self.encode_int(false, value)
+    }
+
+    /// Encodes a string value.
+    pub const fn encode_str(self, value: &str) -> Self {
+        self.encode_string_header(value.len())
+            .push_bytes(value.as_bytes())
+    }
+
+    /// Encodes a byte array value.
+    pub const fn encode_bytes(self, value: &[u8]) -> Self {
+        self.encode_bytes_header(value.len()).push_bytes(value)
+    }
+
+    /// Encodes a boolean value.
+    pub const fn encode_bool(self, value: bool) -> Self {
+        let mut byte = BoolHeader::TYPE_BITS;
+        if value {
+            byte |= BoolHeader::VALUE_BIT;
+        }
+        self.push_byte(byte)
+    }
+
+    /// Encodes a unit value.
+    pub const fn encode_unit(self) -> Self {
+        self.push_byte(UnitHeader::TYPE_BITS)
+    }
+
+    /// Encodes a null value.
+    pub const fn encode_null(self) -> Self {
+        self.push_byte(NullHeader::TYPE_BITS)
+    }
+
+    // MARK: - Headers
+
+    /// Encodes a string value's header, from its length.
+    pub const fn encode_string_header(self, len: usize) -> Self {
+        if len <= StringHeader::COMPACT_MAX_LEN as usize {
+            let byte = StringHeader::TYPE_BITS | StringHeader::COMPACT_VARIANT_BIT | len as u8;
+            self.push_byte(byte)
+        } else {
+            self.encode_extended_len(
+                StringHeader::TYPE_BITS,
+                StringHeader::EXTENDED_LEN_WIDTH_BITS,
+                len,
+            )
+        }
+    }
+
+    /// Encodes a sequence value's header, from its length.
+    ///
+    /// The sequence's elements are expected to be encoded next.
+    pub const fn encode_seq_header(self, len: usize) -> Self {
+        if len <= SeqHeader::COMPACT_MAX_LEN as usize {
+            let byte = SeqHeader::TYPE_BITS | SeqHeader::COMPACT_VARIANT_BIT | len as u8;
+            self.push_byte(byte)
+        } else {
+            self.encode_extended_len(
+                SeqHeader::TYPE_BITS,
+                SeqHeader::EXTENDED_LEN_WIDTH_BITS,
+                len,
+            )
+        }
+    }
+
+    /// Encodes a map value's header, from its length.
+    ///
+    /// The map's keys and values are expected to be encoded next, alternatingly.
+    pub const fn encode_map_header(self, len: usize) -> Self {
+        if len <= MapHeader::COMPACT_MAX_LEN as usize {
+            let byte = MapHeader::TYPE_BITS | MapHeader::COMPACT_VARIANT_BIT | len as u8;
+            self.push_byte(byte)
+        } else {
+            self.encode_extended_len(
+                MapHeader::TYPE_BITS,
+                MapHeader::EXTENDED_LEN_WIDTH_BITS,
+                len,
+            )
+        }
+    }
+
+    /// Encodes a byte array value's header, from its length.
+    pub const fn encode_bytes_header(self, len: usize) -> Self {
+        // The bytes header only supports native packing:
+        let (exponent, width) = match len as u64 {
+            0..=0xFF => (0, 1),
+            0x100..=0xFFFF => (1, 2),
+            0x10000..=0xFFFFFFFF => (2, 4),
+            _ => (3, 8),
+        };
+
+        self.push_byte(BytesHeader::TYPE_BITS | exponent)
+            .push_be_bytes(len as u64, width)
+    }
+
+    // MARK: - Auxiliary Methods
+
+    const fn encode_int(self, is_signed: bool, bits: u64) -> Self {
+        let mut byte = IntHeader::TYPE_BITS;
+        if is_signed {
+            byte |= IntHeader::SIGNEDNESS_BIT;
+        }
+
+        if bits <= IntHeader::COMPACT_VALUE_BITS as u64 {
+            byte |= IntHeader::COMPACT_VARIANT_BIT | bits as u8;
+            return self.push_byte(byte);
+        }
+
+        let width = Self::optimal_width(bits);
+        byte |= (width as u8 - 1) & IntHeader::EXTENDED_WIDTH_BITS;
+
+        self.push_byte(byte).push_be_bytes(bits, width)
+    }
+
+    const fn encode_extended_len(self, type_bits: u8, width_bits: u8, len: usize) -> Self {
+        let width = Self::optimal_width(len as u64);
+        let byte = type_bits | ((width as u8 - 1) & width_bits);
+
+        self.push_byte(byte).push_be_bytes(len as u64, width)
+    }
+
+    const fn optimal_width(bits: u64) -> usize {
+        let width = (u64::BITS - bits.leading_zeros() + u8::BITS - 1) / u8::BITS;
+        if width == 0 {
+            1
+        } else {
+            width as usize
+        }
+    }
+
+    const fn push_be_bytes(self, bits: u64, width: usize) -> Self {
+        let be_bytes = bits.to_be_bytes();
+        self.push_bytes(be_bytes.split_at(be_bytes.len() - width).1)
+    }
+
+    const fn push_bytes(mut self, bytes: &[u8]) -> Self {
+        assert!(
+            bytes.len() <= N - self.len,
+            "encoding exceeds encoder capacity"
+        );
+
+        let mut index = 0;
+        while index < bytes.len() {
+            self.buf[self.len] = bytes[index];
+            self.len += 1;
+            index += 1;
+        }
+        self
+    }
+
+    const fn push_byte(self, byte: u8) -> Self {
+        self.push_bytes(&[byte])
+    }
+}
+
+/// Encodes a lilliput value at compile time, producing a `&'static [u8]`.
+///
+/// Expects the encoder's capacity, followed by a `ConstEncoder` expression.
+///
+/// See [`ConstEncoder`](crate::encoder::ConstEncoder) for an example.
+#[macro_export]
+macro_rules! encode_const {
+    ($capacity:expr, $encoder:expr $(,)?) => {{
+        const ENCODER: $crate::encoder::ConstEncoder<{ $capacity }> = $encoder;
+        const BYTES: [u8; ENCODER.len()] = ENCODER.into_array();
+        &BYTES
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{encoder::Encoder, io::VecWriter};
+
+    use super::*;
+
+    fn encode(f: impl FnOnce(&mut Encoder<VecWriter<'_>>)) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        f(&mut encoder);
+        encoded
+    }
+
+    #[test]
+    fn encode_const() {
+        static ENCODED: &[u8] = crate::encode_const!(
+            64,
+            ConstEncoder::new()
+                .encode_map_header(2)
+                .encode_str("name")
+                .encode_str("lilliput")
+                .encode_str("tags")
+                .encode_seq_header(3)
+                .encode_unit()
+                .encode_null()
+                .encode_bool(true)
+        );
+
+        let expected = encode(|encoder| {
+            encoder
+                .encode_map_header(&encoder.header_for_map_len(2))
+                .unwrap();
+            encoder.encode_str("name").unwrap();
+            encoder.encode_str("lilliput").unwrap();
+            encoder.encode_str("tags").unwrap();
+            encoder
+                .encode_seq_header(&encoder.header_for_seq_len(3))
+                .unwrap();
+            encoder.encode_unit().unwrap();
+            encoder.encode_null().unwrap();
+            encoder.encode_bool(true).unwrap();
+        });
+        assert_eq!(ENCODED, expected.as_slice());
+    }
+
+    proptest! {
+        #[test]
+        fn i64_matches_encoder(value in i64::arbitrary()) {
+            let encoded = ConstEncoder::<9>::new().encode_i64(value);
+            let expected = encode(|encoder| encoder.encode_i64(value).unwrap());
+            prop_assert_eq!(encoded.as_bytes(), expected.as_slice());
+        }
+
+        #[test]
+        fn u64_matches_encoder(value in u64::arbitrary()) {
+            let encoded = ConstEncoder::<9>::new().encode_u64(value);
+            let expected = encode(|encoder| encoder.encode_u64(value).unwrap());
+            prop_assert_eq!(encoded.as_bytes(), expected.as_slice());
+        }
+
+        #[test]
+        fn str_matches_encoder(value in ".{0,300}") {
+            let encoded = ConstEncoder::<1024>::new().encode_str(&value);
+            let expected = encode(|encoder| encoder.encode_str(&value).unwrap());
+            prop_assert_eq!(encoded.as_bytes(), expected.as_slice());
+        }
+
+        #[test]
+        fn bytes_matches_encoder(value in proptest::collection::vec(u8::arbitrary(), 0..300)) {
+            let encoded = ConstEncoder::<512>::new().encode_bytes(&value);
+            let expected = encode(|encoder| encoder.encode_bytes(&value).unwrap());
+            prop_assert_eq!(encoded.as_bytes(), expected.as_slice());
+        }
+
+        #[test]
+        fn seq_header_matches_encoder(len in 0_usize..100_000) {
+            let encoded = ConstEncoder::<9>::new().encode_seq_header(len);
+            let expected = encode(|encoder| {
+                let header = encoder.header_for_seq_len(len);
+                encoder.encode_seq_header(&header).unwrap()
+            });
+            prop_assert_eq!(encoded.as_bytes(), expected.as_slice());
+        }
+
+        #[test]
+        fn map_header_matches_encoder(len in 0_usize..100_000) {
+            let encoded = ConstEncoder::<9>::new().encode_map_header(len);
+            let expected = encode(|encoder| {
+                let header = encoder.header_for_map_len(len);
+                encoder.encode_map_header(&header).unwrap()
+            });
+            prop_assert_eq!(encoded.as_bytes(), expected.as_slice());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use rust_decimal::Decimal;
+
+use crate::{error::Result, header::SeqHeader, io::Write, marker::Marker, value::DECIMAL_TAG};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a decimal, as an extension value wrapping its scale,
+    /// followed by the big-endian two's complement bytes of its mantissa.
+    pub fn encode_decimal(&mut self, value: &Decimal) -> Result<()> {
+        let bytes = value.mantissa().to_be_bytes();
+        let mantissa = &bytes[redundant_sign_bytes(&bytes)..];
+
+        self.encode_length_prefixed(SeqHeader::EXTENSION, Marker::Seq, |encoder| {
+            encoder.encode_u64(DECIMAL_TAG)?;
+            encoder.encode_u32(value.scale())?;
+            encoder.encode_bytes(mantissa)
+        })
+    }
+}
+
+/// Returns the number of leading bytes of the two's complement `bytes`
+/// that can be dropped without changing the value they represent.
+fn redundant_sign_bytes(bytes: &[u8]) -> usize {
+    bytes
+        .windows(2)
+        .take_while(|pair| match pair {
+            [0x00, next] => next & 0x80 == 0,
+            [0xFF, next] => next & 0x80 != 0,
+            _ => false,
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    #[test]
+    fn redundant_sign_bytes() {
+        let cases: [(i128, &[u8]); 6] = [
+            (0, &[0x00]),
+            (127, &[0x7F]),
+            (128, &[0x00, 0x80]),
+            (-1, &[0xFF]),
+            (-128, &[0x80]),
+            (-129, &[0xFF, 0x7F]),
+        ];
+
+        for (value, expected) in cases {
+            let bytes = value.to_be_bytes();
+            assert_eq!(&bytes[super::redundant_sign_bytes(&bytes)..], expected);
+        }
+    }
+}
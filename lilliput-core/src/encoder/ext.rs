@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+
+use crate::{
+    error::{Error, Result},
+    header::{ExtHeader, SeqHeader},
+    io::{VecWriter, Write},
+    marker::Marker,
+    value::ExtValue,
+};
+
+use super::{Encoder, HeaderPacking};
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes an extension value, from its `tag` and encoded `payload`.
+    pub fn encode_ext(&mut self, tag: u64, payload: &[u8]) -> Result<()> {
+        self.encode_ext_header(&ExtHeader::new(tag, payload.len()))?;
+
+        // Push the value's actual payload:
+        self.push_bytes(payload)
+    }
+
+    /// Encodes an extension value, from an `ExtValue`.
+    pub fn encode_ext_value(&mut self, value: &ExtValue) -> Result<()> {
+        self.encode_ext(value.tag, &value.payload)
+    }
+
+    // MARK: - Header
+
+    /// Encodes an extension value's header, i.e. its header byte,
+    /// the length of its body, and its tag.
+    pub fn encode_ext_header(&mut self, header: &ExtHeader) -> Result<()> {
+        self.config
+            .profile
+            .check(SeqHeader::EXTENSION, Some(self.pos))?;
+
+        // The body's length includes the tag, whose width depends on the config:
+        let mut tag = Vec::new();
+        Encoder::new(VecWriter::new(&mut tag), self.config.clone()).encode_u64(header.tag())?;
+
+        let body_len = (header.len() as u64)
+            .checked_add(tag.len() as u64)
+            .ok_or_else(|| Error::number_out_of_range(Some(self.pos)))?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            byte = crate::binary::fmt_byte(SeqHeader::EXTENSION),
+            tag = header.tag(),
+            len = header.len()
+        );
+
+        self.record_header(Marker::Seq, HeaderPacking::Compact);
+
+        self.push_byte(SeqHeader::EXTENSION)?;
+        self.encode_u64(body_len)?;
+        self.encode_u64(header.tag())
+    }
+}
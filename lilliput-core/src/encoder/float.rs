@@ -1,9 +1,9 @@
 use crate::{
-    error::Result, header::FloatHeader, io::Write, num::WithValidatedPackedBeBytes as _,
-    value::FloatValue,
+    error::Result, header::FloatHeader, io::Write, marker::Marker,
+    num::WithValidatedPackedBeBytes as _, value::FloatValue,
 };
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -56,6 +56,8 @@ where
         #[cfg(feature = "tracing")]
         tracing::debug!(byte = crate::binary::fmt_byte(byte), width = width);
 
+        self.record_header(Marker::Float, HeaderPacking::Extended { width });
+
         // Push the value's header:
         self.push_byte(byte)
     }
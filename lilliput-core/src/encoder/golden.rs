@@ -0,0 +1,757 @@
+//! Golden-byte tests, pinning the encodings produced for `FormatVersion::V1`.
+//!
+//! The expected bytes in here must never change: a failure means that
+//! the encoder's header selection for `V1` has changed, which would
+//! silently break anything derived from previously produced encodings.
+
+use crate::{
+    config::{EncoderConfig, FormatVersion, PackingMode},
+    encoder::Encoder,
+    error::Result,
+    io::VecWriter,
+    value::{
+        BoolValue, FloatValue, IntValue, Map, MapValue, NullValue, SeqValue, StringValue, Value,
+    },
+};
+
+fn assert_golden(
+    packing: PackingMode,
+    encode: impl FnOnce(&mut Encoder<VecWriter<'_>>) -> Result<()>,
+    expected: &[u8],
+) {
+    let config = EncoderConfig::default()
+        .with_format(FormatVersion::V1)
+        .with_packing(packing);
+
+    let mut encoded: Vec<u8> = Vec::new();
+    let writer = VecWriter::new(&mut encoded);
+    let mut encoder = Encoder::new(writer, config);
+    encode(&mut encoder).unwrap();
+
+    assert_eq!(encoded, expected, "packing: {packing:?}");
+}
+
+fn string(value: &str) -> Value {
+    Value::String(StringValue::from(value.to_owned()))
+}
+
+#[test]
+fn ints_optimal() {
+    assert_golden(PackingMode::Optimal, |e| e.encode_u64(0), &[0xC0]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_u64(31), &[0xDF]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_u64(32), &[0x80, 0x20]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_u64(255), &[0x80, 0xFF]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_u64(256),
+        &[0x81, 0x01, 0x00],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_u64(65535),
+        &[0x81, 0xFF, 0xFF],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_u64(1099511627776),
+        &[0x85, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_u64(u64::MAX),
+        &[0x87, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_golden(PackingMode::Optimal, |e| e.encode_u8(0), &[0xC0]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_u8(200), &[0x80, 0xC8]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_i64(-1), &[0xE1]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_i64(-16), &[0xFF]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_i64(15), &[0xFE]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_i64(-17), &[0xA0, 0x21]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_i64(1000),
+        &[0xA1, 0x07, 0xD0],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_i64(i64::MIN),
+        &[0xA7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_i64(i64::MAX),
+        &[0xA7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE],
+    );
+    assert_golden(PackingMode::Optimal, |e| e.encode_i16(-1), &[0xE1]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_i16(300),
+        &[0xA1, 0x02, 0x58],
+    );
+}
+
+#[test]
+fn ints_native() {
+    assert_golden(PackingMode::Native, |e| e.encode_u64(0), &[0x80, 0x00]);
+    assert_golden(PackingMode::Native, |e| e.encode_u64(31), &[0x80, 0x1F]);
+    assert_golden(PackingMode::Native, |e| e.encode_u64(32), &[0x80, 0x20]);
+    assert_golden(PackingMode::Native, |e| e.encode_u64(255), &[0x80, 0xFF]);
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_u64(256),
+        &[0x81, 0x01, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_u64(65535),
+        &[0x81, 0xFF, 0xFF],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_u64(1099511627776),
+        &[0x87, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_u64(u64::MAX),
+        &[0x87, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_golden(PackingMode::Native, |e| e.encode_u8(0), &[0x80, 0x00]);
+    assert_golden(PackingMode::Native, |e| e.encode_u8(200), &[0x80, 0xC8]);
+    assert_golden(PackingMode::Native, |e| e.encode_i64(-1), &[0xA0, 0x01]);
+    assert_golden(PackingMode::Native, |e| e.encode_i64(-16), &[0xA0, 0x1F]);
+    assert_golden(PackingMode::Native, |e| e.encode_i64(15), &[0xA0, 0x1E]);
+    assert_golden(PackingMode::Native, |e| e.encode_i64(-17), &[0xA0, 0x21]);
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_i64(1000),
+        &[0xA1, 0x07, 0xD0],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_i64(i64::MIN),
+        &[0xA7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_i64(i64::MAX),
+        &[0xA7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE],
+    );
+    assert_golden(PackingMode::Native, |e| e.encode_i16(-1), &[0xA0, 0x01]);
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_i16(300),
+        &[0xA1, 0x02, 0x58],
+    );
+}
+
+#[test]
+fn ints_none() {
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(0),
+        &[0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(31),
+        &[0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(32),
+        &[0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(255),
+        &[0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(256),
+        &[0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(65535),
+        &[0x87, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(1099511627776),
+        &[0x87, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_u64(u64::MAX),
+        &[0x87, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_golden(PackingMode::None, |e| e.encode_u8(0), &[0x80, 0x00]);
+    assert_golden(PackingMode::None, |e| e.encode_u8(200), &[0x80, 0xC8]);
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(-1),
+        &[0xA7, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(-16),
+        &[0xA7, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(15),
+        &[0xA7, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1E],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(-17),
+        &[0xA7, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x21],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(1000),
+        &[0xA7, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07, 0xD0],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(i64::MIN),
+        &[0xA7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i64(i64::MAX),
+        &[0xA7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE],
+    );
+    assert_golden(PackingMode::None, |e| e.encode_i16(-1), &[0xA1, 0x00, 0x01]);
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i16(300),
+        &[0xA1, 0x02, 0x58],
+    );
+}
+
+#[test]
+fn ints_128_bit() {
+    assert_golden(PackingMode::Optimal, |e| e.encode_u128(42), &[0x80, 0x2A]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_u128(u128::from(u64::MAX) + 1),
+        &[0x88, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_u128(u128::MAX),
+        &[
+            0x8F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF,
+        ],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_i128(i128::MIN),
+        &[
+            0xAF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF,
+        ],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_u128(u128::from(u64::MAX) + 1),
+        &[
+            0x8F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00,
+        ],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_i128(-1),
+        &[
+            0xAF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x01,
+        ],
+    );
+}
+
+#[test]
+fn floats_optimal() {
+    assert_golden(PackingMode::Optimal, |e| e.encode_f32(0.0), &[0x08, 0x00]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_f32(1.5), &[0x08, 0x3C]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_f32(-0.1),
+        &[0x0B, 0xBD, 0xCC, 0xCC, 0xCD],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_f32(f32::INFINITY),
+        &[0x08, 0x78],
+    );
+    assert_golden(PackingMode::Optimal, |e| e.encode_f64(0.0), &[0x08, 0x00]);
+    assert_golden(PackingMode::Optimal, |e| e.encode_f64(1.5), &[0x08, 0x3C]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_f64(0.1),
+        &[0x0F, 0x3F, 0xB9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_f64(f64::NEG_INFINITY),
+        &[0x08, 0xF8],
+    );
+}
+
+#[test]
+fn floats_native() {
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f32(0.0),
+        &[0x0B, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f32(1.5),
+        &[0x0B, 0x3F, 0xC0, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f32(-0.1),
+        &[0x0B, 0xBD, 0xCC, 0xCC, 0xCD],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f32(f32::INFINITY),
+        &[0x0B, 0x7F, 0x80, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f64(0.0),
+        &[0x0B, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f64(1.5),
+        &[0x0B, 0x3F, 0xC0, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f64(0.1),
+        &[0x0F, 0x3F, 0xB9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_f64(f64::NEG_INFINITY),
+        &[0x0B, 0xFF, 0x80, 0x00, 0x00],
+    );
+}
+
+#[test]
+fn floats_none() {
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f32(0.0),
+        &[0x0B, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f32(1.5),
+        &[0x0B, 0x3F, 0xC0, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f32(-0.1),
+        &[0x0B, 0xBD, 0xCC, 0xCC, 0xCD],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f32(f32::INFINITY),
+        &[0x0B, 0x7F, 0x80, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f64(0.0),
+        &[0x0F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f64(1.5),
+        &[0x0F, 0x3F, 0xF8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f64(0.1),
+        &[0x0F, 0x3F, 0xB9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9A],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_f64(f64::NEG_INFINITY),
+        &[0x0F, 0xFF, 0xF0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+}
+
+#[test]
+fn strings_optimal() {
+    assert_golden(PackingMode::Optimal, |e| e.encode_str(""), &[0x60]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_str("lilliput"),
+        &[0x68, 0x6C, 0x69, 0x6C, 0x6C, 0x69, 0x70, 0x75, 0x74],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_str("0123456789abcdef0123456789abcdef"),
+        &[
+            0x40, 0x20, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x61, 0x62,
+            0x63, 0x64, 0x65, 0x66, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39,
+            0x61, 0x62, 0x63, 0x64, 0x65, 0x66,
+        ],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_string_header(&e.header_for_str_len(0)),
+        &[0x60],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_string_header(&e.header_for_str_len(300)),
+        &[0x41, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_string_header(&e.header_for_str_len(70000)),
+        &[0x42, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn strings_native() {
+    assert_golden(PackingMode::Native, |e| e.encode_str(""), &[0x40, 0x00]);
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_str("lilliput"),
+        &[0x40, 0x08, 0x6C, 0x69, 0x6C, 0x6C, 0x69, 0x70, 0x75, 0x74],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_str("0123456789abcdef0123456789abcdef"),
+        &[
+            0x40, 0x20, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x61, 0x62,
+            0x63, 0x64, 0x65, 0x66, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39,
+            0x61, 0x62, 0x63, 0x64, 0x65, 0x66,
+        ],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_string_header(&e.header_for_str_len(0)),
+        &[0x40, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_string_header(&e.header_for_str_len(300)),
+        &[0x41, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_string_header(&e.header_for_str_len(70000)),
+        &[0x43, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn strings_none() {
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_str(""),
+        &[0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_str("lilliput"),
+        &[
+            0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0x6C, 0x69, 0x6C, 0x6C, 0x69,
+            0x70, 0x75, 0x74,
+        ],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_str("0123456789abcdef0123456789abcdef"),
+        &[
+            0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20, 0x30, 0x31, 0x32, 0x33, 0x34,
+            0x35, 0x36, 0x37, 0x38, 0x39, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x30, 0x31, 0x32,
+            0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66,
+        ],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_string_header(&e.header_for_str_len(0)),
+        &[0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_string_header(&e.header_for_str_len(300)),
+        &[0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_string_header(&e.header_for_str_len(70000)),
+        &[0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn bytes_optimal() {
+    assert_golden(PackingMode::Optimal, |e| e.encode_bytes(&[]), &[0x04, 0x00]);
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_bytes(&[1, 2, 3]),
+        &[0x04, 0x03, 0x01, 0x02, 0x03],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_bytes_header(&e.header_for_bytes_len(300)),
+        &[0x05, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_bytes_header(&e.header_for_bytes_len(70000)),
+        &[0x06, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn bytes_native() {
+    assert_golden(PackingMode::Native, |e| e.encode_bytes(&[]), &[0x04, 0x00]);
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_bytes(&[1, 2, 3]),
+        &[0x04, 0x03, 0x01, 0x02, 0x03],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_bytes_header(&e.header_for_bytes_len(300)),
+        &[0x05, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_bytes_header(&e.header_for_bytes_len(70000)),
+        &[0x06, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn bytes_none() {
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_bytes(&[]),
+        &[0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_bytes(&[1, 2, 3]),
+        &[
+            0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x01, 0x02, 0x03,
+        ],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_bytes_header(&e.header_for_bytes_len(300)),
+        &[0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_bytes_header(&e.header_for_bytes_len(70000)),
+        &[0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn seqs_and_maps_optimal() {
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_seq_header(&e.header_for_seq_len(0)),
+        &[0x30],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_seq_header(&e.header_for_seq_len(7)),
+        &[0x37],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_seq_header(&e.header_for_seq_len(8)),
+        &[0x20, 0x08],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_seq_header(&e.header_for_seq_len(300)),
+        &[0x21, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_map_header(&e.header_for_map_len(0)),
+        &[0x18],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_map_header(&e.header_for_map_len(7)),
+        &[0x1F],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_map_header(&e.header_for_map_len(8)),
+        &[0x10, 0x08],
+    );
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_map_header(&e.header_for_map_len(70000)),
+        &[0x12, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn seqs_and_maps_native() {
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_seq_header(&e.header_for_seq_len(0)),
+        &[0x20, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_seq_header(&e.header_for_seq_len(7)),
+        &[0x20, 0x07],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_seq_header(&e.header_for_seq_len(8)),
+        &[0x20, 0x08],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_seq_header(&e.header_for_seq_len(300)),
+        &[0x21, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_map_header(&e.header_for_map_len(0)),
+        &[0x10, 0x00],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_map_header(&e.header_for_map_len(7)),
+        &[0x10, 0x07],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_map_header(&e.header_for_map_len(8)),
+        &[0x10, 0x08],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_map_header(&e.header_for_map_len(70000)),
+        &[0x13, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn seqs_and_maps_none() {
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_seq_header(&e.header_for_seq_len(0)),
+        &[0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_seq_header(&e.header_for_seq_len(7)),
+        &[0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_seq_header(&e.header_for_seq_len(8)),
+        &[0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_seq_header(&e.header_for_seq_len(300)),
+        &[0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x2C],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_map_header(&e.header_for_map_len(0)),
+        &[0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_map_header(&e.header_for_map_len(7)),
+        &[0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x07],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_map_header(&e.header_for_map_len(8)),
+        &[0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_map_header(&e.header_for_map_len(70000)),
+        &[0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x11, 0x70],
+    );
+}
+
+#[test]
+fn bool_unit_null() {
+    for packing in [PackingMode::Optimal, PackingMode::Native, PackingMode::None] {
+        assert_golden(packing, |e| e.encode_bool(false), &[0x02]);
+        assert_golden(packing, |e| e.encode_bool(true), &[0x03]);
+        assert_golden(packing, |e| e.encode_unit(), &[0x01]);
+        assert_golden(packing, |e| e.encode_null(), &[0x00]);
+    }
+}
+
+#[test]
+fn document() {
+    let mut map = Map::default();
+    map.insert(string("id"), Value::Int(IntValue::from(42_u32)));
+    map.insert(string("none"), Value::Null(NullValue));
+    map.insert(string("ok"), Value::Bool(BoolValue::from(true)));
+    map.insert(string("ratio"), Value::Float(FloatValue::from(0.5_f64)));
+    map.insert(
+        string("tags"),
+        Value::Seq(SeqValue::from(vec![string("a"), string("b")])),
+    );
+    let value = Value::Map(MapValue::from(map));
+
+    assert_golden(
+        PackingMode::Optimal,
+        |e| e.encode_value(&value),
+        &[
+            0x1D, 0x62, 0x69, 0x64, 0x80, 0x2A, 0x64, 0x6E, 0x6F, 0x6E, 0x65, 0x00, 0x62, 0x6F,
+            0x6B, 0x03, 0x65, 0x72, 0x61, 0x74, 0x69, 0x6F, 0x08, 0x30, 0x64, 0x74, 0x61, 0x67,
+            0x73, 0x32, 0x61, 0x61, 0x61, 0x62,
+        ],
+    );
+    assert_golden(
+        PackingMode::Native,
+        |e| e.encode_value(&value),
+        &[
+            0x10, 0x05, 0x40, 0x02, 0x69, 0x64, 0x80, 0x2A, 0x40, 0x04, 0x6E, 0x6F, 0x6E, 0x65,
+            0x00, 0x40, 0x02, 0x6F, 0x6B, 0x03, 0x40, 0x05, 0x72, 0x61, 0x74, 0x69, 0x6F, 0x0B,
+            0x3F, 0x00, 0x00, 0x00, 0x40, 0x04, 0x74, 0x61, 0x67, 0x73, 0x20, 0x02, 0x40, 0x01,
+            0x61, 0x40, 0x01, 0x62,
+        ],
+    );
+    assert_golden(
+        PackingMode::None,
+        |e| e.encode_value(&value),
+        &[
+            0x17, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0x47, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x02, 0x69, 0x64, 0x83, 0x00, 0x00, 0x00, 0x2A, 0x47, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x6E, 0x6F, 0x6E, 0x65, 0x00, 0x47, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x6F, 0x6B, 0x03, 0x47, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x05, 0x72, 0x61, 0x74, 0x69, 0x6F, 0x0F, 0x3F, 0xE0, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x47, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x74,
+            0x61, 0x67, 0x73, 0x27, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x47, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x61, 0x47, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x01, 0x62,
+        ],
+    );
+}
@@ -2,14 +2,16 @@ use num_traits::{Signed, Unsigned};
 
 use crate::{
     binary::bits_if,
+    config::PackingMode,
     error::Result,
     header::{CompactIntHeader, ExtendedIntHeader, IntHeader},
     io::Write,
-    num::WithPackedBeBytes,
+    marker::Marker,
+    num::{ToZigZag, WithPackedBeBytes},
     value::{IntValue, SignedIntValue, UnsignedIntValue},
 };
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -37,6 +39,11 @@ where
         self.encode_signed_int(value)
     }
 
+    /// Encodes a 128-bit signed integer value.
+    pub fn encode_i128(&mut self, value: i128) -> Result<()> {
+        self.encode_signed_int(value)
+    }
+
     /// Encodes a 8-bit unsigned integer value.
     pub fn encode_u8(&mut self, value: u8) -> Result<()> {
         self.encode_unsigned_int(value)
@@ -57,6 +64,11 @@ where
         self.encode_unsigned_int(value)
     }
 
+    /// Encodes a 128-bit unsigned integer value.
+    pub fn encode_u128(&mut self, value: u128) -> Result<()> {
+        self.encode_unsigned_int(value)
+    }
+
     /// Encodes a signed integer value, from a `SignedIntValue`.
     pub fn encode_signed_int_value(&mut self, value: &SignedIntValue) -> Result<()> {
         match value {
@@ -64,6 +76,7 @@ where
             SignedIntValue::I16(value) => self.encode_signed_int(*value),
             SignedIntValue::I32(value) => self.encode_signed_int(*value),
             SignedIntValue::I64(value) => self.encode_signed_int(*value),
+            SignedIntValue::I128(value) => self.encode_signed_int(*value),
         }
     }
 
@@ -74,6 +87,7 @@ where
             UnsignedIntValue::U16(value) => self.encode_unsigned_int(*value),
             UnsignedIntValue::U32(value) => self.encode_unsigned_int(*value),
             UnsignedIntValue::U64(value) => self.encode_unsigned_int(*value),
+            UnsignedIntValue::U128(value) => self.encode_unsigned_int(*value),
         }
     }
 
@@ -119,6 +133,14 @@ where
             }
         }
 
+        let packing = match header {
+            IntHeader::Compact(_) => HeaderPacking::Compact,
+            IntHeader::Extended(ExtendedIntHeader { width, .. }) => {
+                HeaderPacking::Extended { width: *width }
+            }
+        };
+        self.record_header(Marker::Int, packing);
+
         // Push the header byte:
         self.push_byte(byte)
     }
@@ -142,8 +164,13 @@ where
     #[inline]
     fn encode_signed_int<S>(&mut self, value: S) -> Result<()>
     where
-        S: Signed + WithPackedBeBytes,
+        S: Copy + Signed + WithPackedBeBytes + ToZigZag,
+        S::ZigZag: Into<u128>,
     {
+        if self.encode_small_int(true, value.to_zig_zag().into())? {
+            return Ok(());
+        }
+
         let packing_mode = self.config.ints.packing;
         value.with_packed_be_bytes(packing_mode, |bytes| {
             let header = IntHeader::for_int_be_bytes(true, bytes, packing_mode);
@@ -164,8 +191,12 @@ where
     #[inline]
     fn encode_unsigned_int<U>(&mut self, value: U) -> Result<()>
     where
-        U: Unsigned + WithPackedBeBytes,
+        U: Copy + Unsigned + WithPackedBeBytes + Into<u128>,
     {
+        if self.encode_small_int(false, value.into())? {
+            return Ok(());
+        }
+
         let packing_mode = self.config.ints.packing;
         value.with_packed_be_bytes(packing_mode, |bytes| {
             let header = IntHeader::for_int_be_bytes(false, bytes, packing_mode);
@@ -182,4 +213,33 @@ where
             Ok(())
         })
     }
+
+    /// Fast path for integers whose (zig-zag encoded, if signed) `bits`
+    /// fit into a compact header, or an extended header of width 1,
+    /// returning `false` (without encoding anything) otherwise.
+    ///
+    /// Produces the same encoding as the general path, while skipping
+    /// its big-endian byte packing and header construction.
+    #[inline(always)]
+    fn encode_small_int(&mut self, is_signed: bool, bits: u128) -> Result<bool> {
+        let packing_mode = self.config.ints.packing;
+
+        // Without packing the width depends on the value's type, rather than its bits:
+        if packing_mode == PackingMode::None || bits > u128::from(u8::MAX) {
+            return Ok(false);
+        }
+
+        let bits = bits as u8;
+        let byte = IntHeader::TYPE_BITS | bits_if(IntHeader::SIGNEDNESS_BIT, is_signed);
+
+        if packing_mode == PackingMode::Optimal && bits <= IntHeader::COMPACT_VALUE_BITS {
+            self.record_header(Marker::Int, HeaderPacking::Compact);
+            self.push_byte(byte | IntHeader::COMPACT_VARIANT_BIT | bits)?;
+        } else {
+            self.record_header(Marker::Int, HeaderPacking::Extended { width: 1 });
+            self.push_bytes(&[byte, bits])?;
+        }
+
+        Ok(true)
+    }
 }
@@ -1,12 +1,14 @@
 use crate::{
+    config::MapKeyCoding,
     error::Result,
     header::{CompactMapHeader, ExtendedMapHeader, MapHeader},
     io::Write,
+    marker::Marker,
     num::WithPackedBeBytes as _,
-    value::{Map, MapValue},
+    value::{Map, MapValue, Value},
 };
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -15,7 +17,17 @@ where
     // MARK: - Value
 
     /// Encodes a map value.
+    ///
+    /// Maps are encoded with front-coded keys, if enabled
+    /// via `EncoderConfig::map_keys` and applicable.
     pub fn encode_map(&mut self, value: &Map) -> Result<()> {
+        if self.config.map_keys == MapKeyCoding::FrontCoded
+            && self.config.profile.permits(MapHeader::FRONT_CODED)
+            && is_front_codable(value)
+        {
+            return self.encode_front_coded_map(value);
+        }
+
         self.encode_map_header(&self.header_for_map_len(value.len()))?;
 
         for (key, value) in value {
@@ -42,6 +54,8 @@ where
                 byte |= MapHeader::COMPACT_VARIANT_BIT;
                 byte |= len & MapHeader::COMPACT_LEN_BITS;
 
+                self.record_header(Marker::Map, HeaderPacking::Compact);
+
                 // Push the value's header:
                 self.push_byte(byte)
             }
@@ -54,10 +68,12 @@ where
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
                         byte = crate::binary::fmt_byte(byte),
-                        bytes = format!("{:b}", crate::binary::BytesSlice(bytes)),
+                        bytes = alloc::format!("{:b}", crate::binary::BytesSlice(bytes)),
                         len = len
                     );
 
+                    self.record_header(Marker::Map, HeaderPacking::Extended { width });
+
                     // Push the value's header:
                     self.push_byte(byte)?;
 
@@ -72,4 +88,53 @@ where
     pub fn header_for_map_len(&self, len: usize) -> MapHeader {
         MapHeader::for_len(len, self.config.lengths.packing)
     }
+
+    // MARK: - Private
+
+    fn encode_front_coded_map(&mut self, value: &Map) -> Result<()> {
+        self.encode_length_prefixed(MapHeader::FRONT_CODED, Marker::Map, |encoder| {
+            encoder.encode_front_coded_map_body(value)
+        })
+    }
+
+    fn encode_front_coded_map_body(&mut self, value: &Map) -> Result<()> {
+        self.encode_u64(value.len() as u64)?;
+
+        let mut previous_key = "";
+
+        for (key, value) in value {
+            let Value::String(key) = key else {
+                unreachable!("front-coded maps only have string keys");
+            };
+            let key = key.as_str();
+
+            let prefix_len = shared_prefix_len(previous_key, key);
+            self.encode_u64(prefix_len as u64)?;
+            self.encode_str(&key[prefix_len..])?;
+            self.encode_value(value)?;
+
+            previous_key = key;
+        }
+
+        Ok(())
+    }
+}
+
+fn is_front_codable(map: &Map) -> bool {
+    map.len() > 1 && map.keys().all(|key| matches!(key, Value::String(_)))
+}
+
+/// Returns the length of the prefix `lhs` and `rhs` have in common,
+/// in bytes, without splitting any of their characters.
+fn shared_prefix_len(lhs: &str, rhs: &str) -> usize {
+    let len = lhs
+        .bytes()
+        .zip(rhs.bytes())
+        .take_while(|(lhs, rhs)| lhs == rhs)
+        .count();
+
+    (0..=len)
+        .rev()
+        .find(|&len| rhs.is_char_boundary(len))
+        .unwrap_or(0)
 }
@@ -1,12 +1,16 @@
+use alloc::vec::Vec;
+
 use crate::{
+    config::SeqCoding,
     error::Result,
     header::{CompactSeqHeader, ExtendedSeqHeader, SeqHeader},
     io::Write,
+    marker::Marker,
     num::WithPackedBeBytes as _,
-    value::{SeqValue, Value},
+    value::{Map, SeqValue, Value},
 };
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -15,7 +19,24 @@ where
     // MARK: - Value
 
     /// Encodes a sequence value.
+    ///
+    /// Sequences of maps are encoded column by column (or row by row),
+    /// if enabled via `EncoderConfig::seqs` and applicable.
     pub fn encode_seq(&mut self, value: &[Value]) -> Result<()> {
+        match self.config.seqs {
+            SeqCoding::Columnar if self.config.profile.permits(SeqHeader::COLUMNAR) => {
+                if let Some(rows) = homogeneous_rows(value) {
+                    return self.encode_columnar_seq(&rows);
+                }
+            }
+            SeqCoding::Tabular if self.config.profile.permits(SeqHeader::TABULAR) => {
+                if let Some(rows) = homogeneous_rows(value) {
+                    return self.encode_tabular_seq(&rows);
+                }
+            }
+            _ => {}
+        }
+
         self.encode_seq_header(&self.header_for_seq_len(value.len()))?;
 
         for value in value {
@@ -41,6 +62,8 @@ where
                 byte |= SeqHeader::COMPACT_VARIANT_BIT;
                 byte |= len & SeqHeader::COMPACT_LEN_BITS;
 
+                self.record_header(Marker::Seq, HeaderPacking::Compact);
+
                 // Push the value's header:
                 self.push_byte(byte)
             }
@@ -53,10 +76,12 @@ where
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
                         byte = crate::binary::fmt_byte(byte),
-                        bytes = format!("{:b}", crate::binary::BytesSlice(bytes)),
+                        bytes = alloc::format!("{:b}", crate::binary::BytesSlice(bytes)),
                         len = len
                     );
 
+                    self.record_header(Marker::Seq, HeaderPacking::Extended { width });
+
                     // Push the value's header:
                     self.push_byte(byte)?;
 
@@ -71,4 +96,111 @@ where
     pub fn header_for_seq_len(&self, len: usize) -> SeqHeader {
         SeqHeader::for_len(len, self.config.lengths.packing)
     }
+
+    // MARK: - Stream
+
+    /// Encodes the start of a streamed sequence value,
+    /// for sequences whose length is not known up front.
+    ///
+    /// The sequence's items are to be encoded as usual,
+    /// followed by a call to `encode_seq_stream_end()`.
+    pub fn encode_seq_stream_start(&mut self) -> Result<()> {
+        self.config
+            .profile
+            .check(SeqHeader::STREAM_START, Some(self.pos))?;
+
+        self.record_header(Marker::Seq, HeaderPacking::Compact);
+
+        self.push_byte(SeqHeader::STREAM_START)
+    }
+
+    /// Encodes the end of a streamed sequence value.
+    pub fn encode_seq_stream_end(&mut self) -> Result<()> {
+        self.push_byte(SeqHeader::STREAM_END)
+    }
+
+    // MARK: - Private
+
+    fn encode_columnar_seq(&mut self, rows: &[&Map]) -> Result<()> {
+        self.encode_length_prefixed(SeqHeader::COLUMNAR, Marker::Seq, |encoder| {
+            encoder.encode_columnar_seq_body(rows)
+        })
+    }
+
+    fn encode_columnar_seq_body(&mut self, rows: &[&Map]) -> Result<()> {
+        let keys = rows[0].keys();
+
+        self.encode_u64(rows.len() as u64)?;
+        self.encode_u64(keys.len() as u64)?;
+
+        for key in keys.clone() {
+            self.encode_value(key)?;
+        }
+
+        // Rows have identical keys, so their values line up by column:
+        let mut columns: Vec<_> = rows.iter().map(|row| row.values()).collect();
+
+        // Each column is a sequence of its own, keeping columns
+        // of integers decodable via `decode_compact_int_seq()`:
+        for _ in 0..keys.len() {
+            self.encode_seq_header(&self.header_for_seq_len(rows.len()))?;
+
+            for values in &mut columns {
+                let value = values.next().expect("rows should have identical keys");
+                self.encode_value(value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_tabular_seq(&mut self, rows: &[&Map]) -> Result<()> {
+        self.encode_length_prefixed(SeqHeader::TABULAR, Marker::Seq, |encoder| {
+            encoder.encode_tabular_seq_body(rows)
+        })
+    }
+
+    fn encode_tabular_seq_body(&mut self, rows: &[&Map]) -> Result<()> {
+        let keys = rows[0].keys();
+
+        self.encode_u64(rows.len() as u64)?;
+        self.encode_u64(keys.len() as u64)?;
+
+        for key in keys {
+            self.encode_value(key)?;
+        }
+
+        // Rows have identical keys, so only their values get encoded:
+        for row in rows {
+            for value in row.values() {
+                self.encode_value(value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the maps of `seq`, if it consists of two or more maps,
+/// all of which have the same (non-empty) keys, in the same order.
+fn homogeneous_rows(seq: &[Value]) -> Option<Vec<&Map>> {
+    if seq.len() < 2 {
+        return None;
+    }
+
+    let rows: Vec<&Map> = seq
+        .iter()
+        .map(|item| match item {
+            Value::Map(map) => Some(&map.0),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    let first = rows[0];
+    let is_columnar = !first.is_empty()
+        && rows[1..]
+            .iter()
+            .all(|row| row.len() == first.len() && row.keys().eq(first.keys()));
+
+    is_columnar.then_some(rows)
 }
@@ -0,0 +1,106 @@
+use crate::marker::Marker;
+
+/// The packing decision made for a value's header.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum HeaderPacking {
+    /// The header was packed into its compact variant.
+    Compact,
+    /// The header was written in its extended variant,
+    /// with a value (or length) of `width` bytes.
+    Extended {
+        /// The extension's width in bytes.
+        width: u8,
+    },
+}
+
+/// Statistics collected for values of a single type.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MarkerStats {
+    /// Number of encoded values.
+    pub values: usize,
+    /// Number of written bytes (headers, extensions and payloads),
+    /// excluding those of any nested values.
+    pub bytes: usize,
+    /// Number of values encoded with a compact header.
+    pub compact: usize,
+    /// Number of values encoded with an extended header, indexed by `width - 1`.
+    pub extended: [usize; 16],
+}
+
+impl MarkerStats {
+    /// Returns the number of values encoded with an extended header of `width` bytes.
+    pub fn extended_with_width(&self, width: u8) -> usize {
+        match width {
+            1..=16 => self.extended[(width - 1) as usize],
+            _ => 0,
+        }
+    }
+}
+
+/// Statistics collected by an encoder, per value type and packing decision.
+#[derive(Default, Clone, Eq, PartialEq, Debug)]
+pub struct EncoderStats {
+    markers: [MarkerStats; 9],
+    current: Option<Marker>,
+}
+
+impl EncoderStats {
+    const MARKERS: [Marker; 9] = [
+        Marker::Null,
+        Marker::Unit,
+        Marker::Bool,
+        Marker::Bytes,
+        Marker::Float,
+        Marker::Map,
+        Marker::Seq,
+        Marker::String,
+        Marker::Int,
+    ];
+
+    /// Returns the statistics collected for values of type `marker`.
+    pub fn for_marker(&self, marker: Marker) -> &MarkerStats {
+        &self.markers[Self::index_of(marker)]
+    }
+
+    /// Returns an iterator over the statistics collected for each value type.
+    pub fn iter(&self) -> impl Iterator<Item = (Marker, &MarkerStats)> {
+        Self::MARKERS.into_iter().zip(self.markers.iter())
+    }
+
+    /// Returns the total number of encoded values.
+    pub fn total_values(&self) -> usize {
+        self.markers.iter().map(|stats| stats.values).sum()
+    }
+
+    /// Returns the total number of written bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.markers.iter().map(|stats| stats.bytes).sum()
+    }
+
+    pub(crate) fn record_header(&mut self, marker: Marker, packing: HeaderPacking) {
+        let stats = &mut self.markers[Self::index_of(marker)];
+
+        stats.values += 1;
+
+        match packing {
+            HeaderPacking::Compact => stats.compact += 1,
+            HeaderPacking::Extended { width } => stats.extended[(width - 1) as usize] += 1,
+        }
+
+        self.current = Some(marker);
+    }
+
+    pub(crate) fn record_bytes(&mut self, len: usize) {
+        // Bytes are attributed to the most recently encoded header:
+        if let Some(marker) = self.current {
+            self.markers[Self::index_of(marker)].bytes += len;
+        }
+    }
+
+    #[inline]
+    fn index_of(marker: Marker) -> usize {
+        // Markers are represented by their leading bit,
+        // so the bit's position makes for a dense index:
+        (u8::BITS - (marker as u8).leading_zeros()) as usize
+    }
+}
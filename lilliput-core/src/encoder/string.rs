@@ -1,12 +1,14 @@
 use crate::{
+    dictionary::MIN_INTERNED_LEN,
     error::Result,
     header::{CompactStringHeader, ExtendedStringHeader, StringHeader},
     io::Write,
+    marker::Marker,
     num::WithPackedBeBytes as _,
     value::StringValue,
 };
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -15,12 +17,60 @@ where
     // MARK: - Value
 
     /// Encodes a string value, from a reference.
+    ///
+    /// Strings registered with the attached dictionary (if any)
+    /// get encoded as references to their ids.
     pub fn encode_str(&mut self, value: &str) -> Result<()> {
+        let dictionary = self
+            .dictionary
+            .as_ref()
+            .filter(|_| self.config.profile.permits(StringHeader::DICTIONARY_REF));
+
+        if let Some(id) = dictionary.and_then(|d| d.id_of(value)) {
+            return self.encode_dictionary_ref(id);
+        }
+
         self.encode_string_header(&self.header_for_str_len(value.len()))?;
 
         // Push the value's actual bytes:
         self.push_bytes(value.as_bytes())?;
 
+        self.intern(value);
+
+        Ok(())
+    }
+
+    /// Encodes a string value of `len` bytes, streaming them from `reader`
+    /// in chunks, so that they never need to be contiguous in memory.
+    ///
+    /// Returns an error if `reader` ends before providing `len` bytes,
+    /// or provides invalid UTF-8, in which case the value is left partially encoded.
+    #[cfg(feature = "std")]
+    pub fn encode_str_from_reader<R>(&mut self, len: usize, reader: &mut R) -> Result<()>
+    where
+        R: std::io::Read,
+    {
+        self.encode_string_header(&self.header_for_str_len(len))?;
+
+        let mut validator = crate::utf8::Utf8Validator::new(self.pos);
+
+        // Strings to be interned need to be collected, as they get streamed:
+        let mut interned = (self.interning && len >= MIN_INTERNED_LEN).then(Vec::new);
+
+        // Push the value's actual bytes:
+        self.push_bytes_from_reader(len, reader, |chunk| {
+            if let Some(interned) = interned.as_mut() {
+                interned.extend_from_slice(chunk);
+            }
+            validator.validate(chunk)
+        })?;
+
+        validator.finish()?;
+
+        if let Some(interned) = interned {
+            self.intern(core::str::from_utf8(&interned).expect("should be valid UTF-8"));
+        }
+
         Ok(())
     }
 
@@ -42,6 +92,8 @@ where
                 byte |= StringHeader::COMPACT_VARIANT_BIT;
                 byte |= len & StringHeader::COMPACT_LEN_BITS;
 
+                self.record_header(Marker::String, HeaderPacking::Compact);
+
                 // Push the value's header:
                 self.push_byte(byte)
             }
@@ -54,10 +106,12 @@ where
                     #[cfg(feature = "tracing")]
                     tracing::debug!(
                         byte = crate::binary::fmt_byte(byte),
-                        bytes = format!("{:b}", crate::binary::BytesSlice(bytes)),
+                        bytes = alloc::format!("{:b}", crate::binary::BytesSlice(bytes)),
                         len = len
                     );
 
+                    self.record_header(Marker::String, HeaderPacking::Extended { width });
+
                     // Push the value's header:
                     self.push_byte(byte)?;
 
@@ -72,4 +126,26 @@ where
     pub fn header_for_str_len(&self, len: usize) -> StringHeader {
         StringHeader::for_len(len, self.config.lengths.packing)
     }
+
+    // MARK: - Private
+
+    /// Registers the encoded string `value` with the dictionary, if interning.
+    fn intern(&mut self, value: &str) {
+        if !self.interning || value.len() < MIN_INTERNED_LEN {
+            return;
+        }
+
+        if let Some(dictionary) = self.dictionary.as_mut() {
+            dictionary.register(value);
+        }
+    }
+
+    fn encode_dictionary_ref(&mut self, id: u64) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(id = id);
+
+        self.encode_length_prefixed(StringHeader::DICTIONARY_REF, Marker::String, |encoder| {
+            encoder.encode_u64(id)
+        })
+    }
 }
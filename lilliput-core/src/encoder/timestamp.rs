@@ -0,0 +1,40 @@
+use core::time::Duration;
+
+use crate::{
+    error::Result,
+    header::SeqHeader,
+    io::Write,
+    marker::Marker,
+    timestamp::{Timestamp, DURATION_TAG, TIMESTAMP_TAG},
+};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a timestamp, as an extension value.
+    ///
+    /// See the [`timestamp`](crate::timestamp) module for details.
+    pub fn encode_timestamp(&mut self, value: Timestamp) -> Result<()> {
+        self.encode_length_prefixed(SeqHeader::EXTENSION, Marker::Seq, |encoder| {
+            encoder.encode_u64(TIMESTAMP_TAG)?;
+            encoder.encode_i64(value.secs())?;
+            encoder.encode_u32(value.nanos())
+        })
+    }
+
+    /// Encodes a duration, as an extension value.
+    ///
+    /// See the [`timestamp`](crate::timestamp) module for details.
+    pub fn encode_duration(&mut self, value: Duration) -> Result<()> {
+        self.encode_length_prefixed(SeqHeader::EXTENSION, Marker::Seq, |encoder| {
+            encoder.encode_u64(DURATION_TAG)?;
+            encoder.encode_u64(value.as_secs())?;
+            encoder.encode_u32(value.subsec_nanos())
+        })
+    }
+}
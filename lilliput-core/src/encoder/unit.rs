@@ -1,6 +1,6 @@
-use crate::{error::Result, header::UnitHeader, io::Write, value::UnitValue};
+use crate::{error::Result, header::UnitHeader, io::Write, marker::Marker, value::UnitValue};
 
-use super::Encoder;
+use super::{Encoder, HeaderPacking};
 
 impl<W> Encoder<W>
 where
@@ -34,6 +34,8 @@ where
         #[cfg(feature = "tracing")]
         tracing::debug!(byte = crate::binary::fmt_byte(byte));
 
+        self.record_header(Marker::Unit, HeaderPacking::Compact);
+
         self.push_byte(byte)
     }
 
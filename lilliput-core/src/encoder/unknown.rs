@@ -0,0 +1,38 @@
+use alloc::borrow::ToOwned;
+
+use crate::{
+    error::{Error, Result},
+    header::SeqHeader,
+    io::Write,
+    value::UnknownValue,
+};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes an unknown value, from an `UnknownValue`,
+    /// reproducing its original header and body.
+    ///
+    /// Returns an error if the value's header is neither one of the reserved header bytes,
+    /// nor that of dictionary references (as collected for unknown ids).
+    pub fn encode_unknown_value(&mut self, value: &UnknownValue) -> Result<()> {
+        if !SeqHeader::is_collectable(value.header) {
+            return Err(Error::invalid_value(
+                crate::binary::fmt_byte(value.header),
+                "reserved header".to_owned(),
+                Some(self.pos),
+            ));
+        }
+
+        self.config.profile.check(value.header, Some(self.pos))?;
+
+        self.push_byte(value.header)?;
+        self.encode_u64(value.body.len() as u64)?;
+        self.push_bytes(&value.body)
+    }
+}
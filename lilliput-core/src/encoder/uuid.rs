@@ -0,0 +1,18 @@
+use crate::{error::Result, header::SeqHeader, io::Write, marker::Marker, value::UUID_TAG};
+
+use super::Encoder;
+
+impl<W> Encoder<W>
+where
+    W: Write,
+{
+    // MARK: - Value
+
+    /// Encodes a UUID, as an extension value wrapping its 16 bytes.
+    pub fn encode_uuid(&mut self, value: &::uuid::Uuid) -> Result<()> {
+        self.encode_length_prefixed(SeqHeader::EXTENSION, Marker::Seq, |encoder| {
+            encoder.encode_u64(UUID_TAG)?;
+            encoder.encode_bytes(value.as_bytes())
+        })
+    }
+}
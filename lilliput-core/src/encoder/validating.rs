@@ -0,0 +1,362 @@
+use alloc::vec::Vec;
+
+use crate::{
+    config::{EncoderConfig, PackedFloatValidation},
+    decoder::Decoder,
+    error::Result,
+    header::Header,
+    io::{SliceReader, Write},
+    value::{FloatValue, Value},
+};
+
+use super::Encoder;
+
+/// An encoder that re-decodes everything it encodes, asserting that
+/// the decoded values match the ones that were passed for encoding.
+///
+/// Validation happens on the fly, after each call to one of its `encode_*` methods,
+/// and failures result in a panic, regardless of whether debug assertions are enabled.
+/// This makes the validating encoder suitable for catching encoder bugs in integration
+/// environments, while its overhead makes it unsuitable for production use.
+///
+/// Floating-point values are matched using the encoder's configured float validation,
+/// allowing for lossy float-packing.
+///
+/// Only available with the `validating` feature enabled.
+#[derive(Debug)]
+pub struct ValidatingEncoder<W> {
+    encoder: Encoder<CapturingWriter<W>>,
+}
+
+impl<W> ValidatingEncoder<W> {
+    /// Creates a validating encoder from `writer`.
+    pub fn from_writer(writer: W) -> Self {
+        Self::new(writer, EncoderConfig::default())
+    }
+
+    /// Creates a validating encoder from `writer`, configured by `config`.
+    pub fn new(writer: W, config: EncoderConfig) -> Self {
+        let writer = CapturingWriter {
+            writer,
+            captured: Vec::new(),
+        };
+
+        Self {
+            encoder: Encoder::new(writer, config),
+        }
+    }
+
+    /// Returns the encoder's internal `writer`, consuming `self`.
+    pub fn into_writer(self) -> W {
+        self.encoder.into_writer().writer
+    }
+
+    /// Returns the encoder's current write position.
+    pub fn pos(&self) -> usize {
+        self.encoder.pos()
+    }
+}
+
+impl<W> ValidatingEncoder<W>
+where
+    W: Write,
+{
+    /// Encodes a value's `Header`, validating its encoding.
+    pub fn encode_header(&mut self, header: &Header) -> Result<()> {
+        self.encoder.encode_header(header)?;
+
+        self.validate(
+            |decoder| decoder.decode_header(),
+            |decoded| decoded == header,
+            header,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a `Value`, validating its encoding.
+    pub fn encode_value(&mut self, value: &Value) -> Result<()> {
+        self.encoder.encode_value(value)?;
+
+        let validation = self.encoder.config.floats.validation.clone();
+        self.validate(
+            |decoder| decoder.decode_value(),
+            |decoded| value_matches(value, decoded, &validation),
+            value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a signed integer value, validating its encoding.
+    pub fn encode_i64(&mut self, value: i64) -> Result<()> {
+        self.encoder.encode_i64(value)?;
+
+        self.validate(
+            |decoder| decoder.decode_i64(),
+            |decoded| *decoded == value,
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes an unsigned integer value, validating its encoding.
+    pub fn encode_u64(&mut self, value: u64) -> Result<()> {
+        self.encoder.encode_u64(value)?;
+
+        self.validate(
+            |decoder| decoder.decode_u64(),
+            |decoded| *decoded == value,
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a 32-bit floating-point value, validating its encoding.
+    pub fn encode_f32(&mut self, value: f32) -> Result<()> {
+        self.encoder.encode_f32(value)?;
+
+        let validator = self.encoder.config.floats.validation.f32.clone();
+        self.validate(
+            |decoder| decoder.decode_f32(),
+            |decoded| validator.validate(value, *decoded),
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a 64-bit floating-point value, validating its encoding.
+    pub fn encode_f64(&mut self, value: f64) -> Result<()> {
+        self.encoder.encode_f64(value)?;
+
+        let validator = self.encoder.config.floats.validation.f64.clone();
+        self.validate(
+            |decoder| decoder.decode_f64(),
+            |decoded| validator.validate(value, *decoded),
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a string value, validating its encoding.
+    pub fn encode_str(&mut self, value: &str) -> Result<()> {
+        self.encoder.encode_str(value)?;
+
+        self.validate(
+            |decoder| decoder.decode_string(),
+            |decoded| decoded == value,
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a byte array value, validating its encoding.
+    pub fn encode_bytes(&mut self, value: &[u8]) -> Result<()> {
+        self.encoder.encode_bytes(value)?;
+
+        self.validate(
+            |decoder| decoder.decode_bytes_buf(),
+            |decoded| decoded == value,
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a boolean value, validating its encoding.
+    pub fn encode_bool(&mut self, value: bool) -> Result<()> {
+        self.encoder.encode_bool(value)?;
+
+        self.validate(
+            |decoder| decoder.decode_bool(),
+            |decoded| *decoded == value,
+            &value,
+        );
+
+        Ok(())
+    }
+
+    /// Encodes a unit value, validating its encoding.
+    pub fn encode_unit(&mut self) -> Result<()> {
+        self.encoder.encode_unit()?;
+
+        self.validate(|decoder| decoder.decode_unit(), |_| true, &());
+
+        Ok(())
+    }
+
+    /// Encodes a null value, validating its encoding.
+    pub fn encode_null(&mut self) -> Result<()> {
+        self.encoder.encode_null()?;
+
+        self.validate(|decoder| decoder.decode_null(), |_| true, &());
+
+        Ok(())
+    }
+
+    fn validate<T, E>(
+        &mut self,
+        decode: impl for<'a> FnOnce(&mut Decoder<SliceReader<'a>>) -> Result<T>,
+        matches: impl FnOnce(&T) -> bool,
+        expected: &E,
+    ) where
+        T: core::fmt::Debug,
+        E: ?Sized + core::fmt::Debug,
+    {
+        let captured = core::mem::take(&mut self.encoder.writer.captured);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&captured));
+
+        let decoded = match decode(&mut decoder) {
+            Ok(decoded) => decoded,
+            Err(err) => panic!(
+                "validating encoder: failed to decode encoding of {expected:?}: {err} (bytes: {captured:02x?})"
+            ),
+        };
+
+        assert!(
+            matches(&decoded),
+            "validating encoder: decoded {decoded:?} does not match encoded {expected:?} (bytes: {captured:02x?})"
+        );
+
+        assert_eq!(
+            decoder.pos(),
+            captured.len(),
+            "validating encoder: decoding of {expected:?} left trailing bytes (bytes: {captured:02x?})"
+        );
+
+        // Re-use the buffer's allocation:
+        self.encoder.writer.captured = captured;
+        self.encoder.writer.captured.clear();
+    }
+}
+
+#[derive(Debug)]
+struct CapturingWriter<W> {
+    writer: W,
+    captured: Vec<u8>,
+}
+
+impl<W> Write for CapturingWriter<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let len = self.writer.write(buf)?;
+        self.captured.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
+
+fn value_matches(expected: &Value, decoded: &Value, validation: &PackedFloatValidation) -> bool {
+    match (expected, decoded) {
+        (Value::Float(expected), Value::Float(decoded)) => {
+            float_matches(expected, decoded, validation)
+        }
+        (Value::Seq(expected), Value::Seq(decoded)) => {
+            expected.0.len() == decoded.0.len()
+                && (expected.0.iter())
+                    .zip(decoded.0.iter())
+                    .all(|(expected, decoded)| value_matches(expected, decoded, validation))
+        }
+        (Value::Map(expected), Value::Map(decoded)) => {
+            expected.0.len() == decoded.0.len()
+                && (expected.0.iter()).zip(decoded.0.iter()).all(
+                    |((expected_key, expected_value), (decoded_key, decoded_value))| {
+                        value_matches(expected_key, decoded_key, validation)
+                            && value_matches(expected_value, decoded_value, validation)
+                    },
+                )
+        }
+        (expected, decoded) => expected == decoded,
+    }
+}
+
+fn float_matches(
+    expected: &FloatValue,
+    decoded: &FloatValue,
+    validation: &PackedFloatValidation,
+) -> bool {
+    match *expected {
+        FloatValue::F32(expected) => validation.f32.validate(expected, f32::from(*decoded)),
+        FloatValue::F64(expected) => validation.f64.validate(expected, f64::from(*decoded)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{config::PackingMode, io::VecWriter};
+
+    use super::*;
+
+    #[test]
+    fn writes_through() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = ValidatingEncoder::from_writer(writer);
+
+        encoder.encode_str("lilliput").unwrap();
+        encoder.encode_i64(-42).unwrap();
+        encoder.encode_bool(true).unwrap();
+        encoder.encode_null().unwrap();
+        assert_eq!(encoder.pos(), 13);
+        drop(encoder);
+
+        let mut expected: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut expected);
+        let mut encoder = Encoder::from_writer(writer);
+
+        encoder.encode_str("lilliput").unwrap();
+        encoder.encode_i64(-42).unwrap();
+        encoder.encode_bool(true).unwrap();
+        encoder.encode_null().unwrap();
+
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "validating encoder")]
+    fn panics_on_mismatch() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = ValidatingEncoder::from_writer(writer);
+
+        // Smuggle an extra byte into the captured encoding:
+        encoder.encoder.writer.captured.push(0b0);
+        encoder.encode_null().unwrap();
+    }
+
+    proptest! {
+        #[test]
+        fn encode_value(value in Value::arbitrary(), packing_mode in PackingMode::arbitrary()) {
+            let config = EncoderConfig::default().with_packing(packing_mode);
+
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = ValidatingEncoder::new(writer, config);
+
+            encoder.encode_value(&value).unwrap();
+        }
+
+        #[test]
+        fn encode_header(header in Header::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = ValidatingEncoder::from_writer(writer);
+
+            encoder.encode_header(&header).unwrap();
+        }
+    }
+}
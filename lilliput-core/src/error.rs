@@ -1,10 +1,14 @@
 //! When encoding or decoding Lilliput goes wrong.
 
 use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
 use alloc::string::ToString;
 use core::fmt::{self, Debug, Display};
 use core::result;
 
+use crate::config::Profile;
+
 /// Alias for a `Result` with the error type `Error`.
 pub type Result<T> = result::Result<T, Error>;
 
@@ -21,12 +25,17 @@ pub struct Expectation<U, E = U> {
 pub struct Error {
     kind: Box<ErrorKind>,
     pos: Option<usize>,
+    path: Option<Box<str>>,
 }
 
 impl Error {
     #[cold]
     pub(crate) fn new(kind: Box<ErrorKind>, pos: Option<usize>) -> Self {
-        Self { kind, pos }
+        Self {
+            kind,
+            pos,
+            path: None,
+        }
     }
 
     /// EOF while parsing.
@@ -84,6 +93,16 @@ impl Error {
         Self::new(Box::new(ErrorKind::depth_limit_exceeded()), pos)
     }
 
+    /// A map (or the maps of a columnar or tabular sequence) contained `key` more than once.
+    #[cold]
+    pub fn duplicate_key(key: impl Debug, pos: Option<usize>) -> Self {
+        Self::invalid_value(
+            format!("duplicate key {key:?}"),
+            "distinct keys".to_string(),
+            pos,
+        )
+    }
+
     /// An encoded string could not be parsed as UTF-8.
     #[cold]
     pub fn utf8(err: core::str::Utf8Error, pos: Option<usize>) -> Self {
@@ -96,12 +115,110 @@ impl Error {
         Self::new(Box::new(ErrorKind::reserved_type()), None)
     }
 
+    /// A decoded length exceeded the platform's `usize::MAX`.
+    #[cold]
+    pub fn length_exceeds_platform(len: u64, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::length_exceeds_platform(len)), pos)
+    }
+
+    /// An integer exceeded the supported width of 128 bits.
+    #[cold]
+    pub fn unsupported_numeric_width(bits: u32, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::unsupported_numeric_width(bits)), pos)
+    }
+
+    /// A decoded length (or count) exceeded a configured limit.
+    #[cold]
+    pub fn limit_exceeded(len: usize, limit: usize, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::limit_exceeded(len, limit)), pos)
+    }
+
+    /// A string's length exceeded a configured limit.
+    #[cold]
+    pub fn string_too_long(len: usize, limit: usize, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::string_too_long(len, limit)), pos)
+    }
+
+    /// A byte array's (or extension value's) length exceeded a configured limit.
+    #[cold]
+    pub fn bytes_too_long(len: usize, limit: usize, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::bytes_too_long(len, limit)), pos)
+    }
+
+    /// A sequence's number of items exceeded a configured limit.
+    #[cold]
+    pub fn seq_too_long(len: usize, limit: usize, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::seq_too_long(len, limit)), pos)
+    }
+
+    /// A map's number of entries exceeded a configured limit.
+    #[cold]
+    pub fn map_too_long(len: usize, limit: usize, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::map_too_long(len, limit)), pos)
+    }
+
+    /// A value's header `byte` is reserved for future versions of the format.
+    #[cold]
+    pub fn reserved_marker(byte: u8, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::reserved_marker(byte)), pos)
+    }
+
+    /// A value was followed by `len` unexpected bytes.
+    #[cold]
+    pub fn trailing_bytes(len: usize, pos: Option<usize>) -> Self {
+        Self::new(Box::new(ErrorKind::trailing_bytes(len)), pos)
+    }
+
+    /// A part of the format (e.g. a `"streamed sequence"`) is not permitted by `profile`.
+    #[cold]
+    pub fn profile_violation(
+        construct: &'static str,
+        profile: Profile,
+        pos: Option<usize>,
+    ) -> Self {
+        Self::new(
+            Box::new(ErrorKind::profile_violation(construct, profile)),
+            pos,
+        )
+    }
+
+    /// A struct contained the `field`, which is not among its `expected` fields.
+    #[cold]
+    pub fn unknown_field(
+        field: &str,
+        expected: &'static [&'static str],
+        pos: Option<usize>,
+    ) -> Self {
+        Self::new(Box::new(ErrorKind::unknown_field(field, expected)), pos)
+    }
+
+    /// A checksum of `actual` did not match the `expected` one.
+    #[cold]
+    pub fn checksum_mismatch(actual: u32, expected: u32, pos: Option<usize>) -> Self {
+        Self::new(
+            Box::new(ErrorKind::checksum_mismatch(actual, expected)),
+            pos,
+        )
+    }
+
+    /// A fixed-size buffer ran out of space, after `written` bytes.
+    #[cold]
+    pub fn buffer_full(written: usize) -> Self {
+        Self::new(Box::new(ErrorKind::buffer_full(written)), None)
+    }
+
     /// A `std::io::Error`.
     #[cfg(feature = "std")]
     pub fn io(err: std::io::Error) -> Self {
         Self::new(Box::new(ErrorKind::io(err)), None)
     }
 
+    /// An `embedded_io::Error`.
+    #[cfg(feature = "embedded-io")]
+    pub fn embedded_io(err: impl embedded_io::Error) -> Self {
+        Self::new(Box::new(ErrorKind::EmbeddedIo(err.kind())), None)
+    }
+
     /// Returns the error's kind.
     pub fn kind(&self) -> &ErrorKind {
         &self.kind
@@ -116,30 +233,64 @@ impl Error {
     pub fn code(&self) -> ErrorCode {
         self.kind.as_code()
     }
+
+    /// Returns the path to the value the error occurred at (e.g. `.users[3].name`), if tracked.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Sets the path to the value the error occurred at, returning `self`.
+    pub fn with_path(mut self, path: String) -> Self {
+        self.path = Some(path.into_boxed_str());
+        self
+    }
+
+    /// Sets the error's position to `pos`, unless already known, returning `self`,
+    /// e.g. for errors raised by serde visitors, which have no access to positions.
+    pub fn or_pos(mut self, pos: usize) -> Self {
+        self.pos = self.pos.or(Some(pos));
+        self
+    }
+
+    /// Shifts the error's position (if any) by `offset`,
+    /// e.g. for values decoded from within a larger input.
+    pub fn offset_pos(mut self, offset: usize) -> Self {
+        self.pos = self.pos.map(|pos| pos + offset);
+        self
+    }
 }
 
 impl Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Humans often end up seeing this representation because it is what `.unwrap()` shows.
+        write!(f, "Error({:?}", self.kind.to_string())?;
+        if let Some(path) = &self.path {
+            write!(f, ", path: {path}")?;
+        }
         if let Some(pos) = self.pos {
-            write!(f, "Error({:?}, position: {pos:?})", self.kind.to_string())
-        } else {
-            write!(f, "Error({:?})", self.kind.to_string())
+            write!(f, ", position: {pos:?}")?;
         }
+        write!(f, ")")
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // Humans often end up seeing this representation because it is what `.unwrap()` shows.
-        if let Some(pos) = self.pos {
-            write!(f, "{:?}, at position: {pos:?}", self.kind.to_string())
-        } else {
-            write!(f, "{:?}", self.kind.to_string(),)
+        match (&self.path, self.pos) {
+            (Some(path), Some(pos)) => write!(
+                f,
+                "{:?}, at {path} (position: {pos:?})",
+                self.kind.to_string()
+            ),
+            (Some(path), None) => write!(f, "{:?}, at {path}", self.kind.to_string()),
+            (None, Some(pos)) => write!(f, "{:?}, at position: {pos:?}", self.kind.to_string()),
+            (None, None) => write!(f, "{:?}", self.kind.to_string()),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match &*self.kind {
@@ -153,8 +304,23 @@ impl std::error::Error for Error {
             ErrorKind::DepthLimitExceeded => None,
             ErrorKind::Utf8(err) => Some(err),
             ErrorKind::ReservedType => None,
+            ErrorKind::LengthExceedsPlatform(_) => None,
+            ErrorKind::UnsupportedNumericWidth(_) => None,
+            ErrorKind::LimitExceeded(_) => None,
+            ErrorKind::StringTooLong(_) => None,
+            ErrorKind::BytesTooLong(_) => None,
+            ErrorKind::SeqTooLong(_) => None,
+            ErrorKind::MapTooLong(_) => None,
+            ErrorKind::ReservedMarker(_) => None,
+            ErrorKind::TrailingBytes(_) => None,
+            ErrorKind::ChecksumMismatch(_) => None,
+            ErrorKind::ProfileViolation(_) => None,
+            ErrorKind::UnknownField(_) => None,
+            ErrorKind::BufferFull(_) => None,
             #[cfg(feature = "std")]
             ErrorKind::StdIo(err) => Some(err),
+            #[cfg(feature = "embedded-io")]
+            ErrorKind::EmbeddedIo(_) => None,
         }
     }
 }
@@ -183,6 +349,11 @@ impl serde::de::Error for Error {
     fn invalid_length(len: usize, exp: &dyn serde::de::Expected) -> Self {
         Error::invalid_length(len.to_string(), exp.to_string(), None)
     }
+
+    #[cold]
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Error::unknown_field(field, expected, None)
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -221,9 +392,38 @@ pub enum ErrorCode {
     Utf8 = 81,
     /// Reserved type
     ReservedType = 91,
+    /// A decoded length exceeded the platform's `usize::MAX`.
+    LengthExceedsPlatform = 101,
+    /// An integer exceeded the supported width of 128 bits.
+    UnsupportedNumericWidth = 111,
+    /// A decoded length (or count) exceeded a configured limit.
+    LimitExceeded = 121,
+    /// A fixed-size buffer ran out of space.
+    BufferFull = 131,
+    /// A string's length exceeded a configured limit.
+    StringTooLong = 141,
+    /// A byte array's (or extension value's) length exceeded a configured limit.
+    BytesTooLong = 151,
+    /// A sequence's number of items exceeded a configured limit.
+    SeqTooLong = 161,
+    /// A map's number of entries exceeded a configured limit.
+    MapTooLong = 171,
+    /// A value's header byte is reserved for future versions of the format.
+    ReservedMarker = 181,
+    /// A value was followed by unexpected bytes.
+    TrailingBytes = 191,
+    /// A checksum did not match the checksummed bytes.
+    ChecksumMismatch = 201,
+    /// A part of the format is not permitted by the configured profile.
+    ProfileViolation = 211,
+    /// A struct contained an unknown field.
+    UnknownField = 221,
     /// `std::io::Error`.
     #[cfg(feature = "std")]
     StdIo = 255,
+    /// `embedded_io::Error`.
+    #[cfg(feature = "embedded-io")]
+    EmbeddedIo = 254,
 }
 
 /// This type represents all possible errors that can occur when serializing or
@@ -251,9 +451,45 @@ pub enum ErrorKind {
     Utf8(core::str::Utf8Error),
     /// ReservedType.
     ReservedType,
+    /// A decoded length exceeded the platform's `usize::MAX`.
+    LengthExceedsPlatform(u64),
+    /// An integer exceeded the supported width of 128 bits,
+    /// with the number of bits required for representing it.
+    UnsupportedNumericWidth(u32),
+    /// A decoded length (or count) exceeded a configured limit.
+    LimitExceeded(Expectation<usize>),
+    /// A string's length exceeded a configured limit.
+    StringTooLong(Expectation<usize>),
+    /// A byte array's (or extension value's) length exceeded a configured limit.
+    BytesTooLong(Expectation<usize>),
+    /// A sequence's number of items exceeded a configured limit.
+    SeqTooLong(Expectation<usize>),
+    /// A map's number of entries exceeded a configured limit.
+    MapTooLong(Expectation<usize>),
+    /// A value's header byte is reserved for future versions of the format,
+    /// with the enclosed byte.
+    ReservedMarker(u8),
+    /// A value was followed by unexpected bytes, with the number
+    /// of bytes following it (or `1`, if the reader can't tell).
+    TrailingBytes(usize),
+    /// A checksum did not match the checksummed bytes,
+    /// with the actual and the expected checksum.
+    ChecksumMismatch(Expectation<u32>),
+    /// A part of the format is not permitted by the configured profile,
+    /// with the part and the profile.
+    ProfileViolation(Expectation<&'static str, Profile>),
+    /// A struct contained an unknown field,
+    /// with the field's name and the struct's fields.
+    UnknownField(Expectation<String, &'static [&'static str]>),
+    /// A fixed-size buffer ran out of space,
+    /// with the number of bytes written to it before.
+    BufferFull(usize),
     /// `std::io::Error`.
     #[cfg(feature = "std")]
     StdIo(std::io::Error),
+    /// `embedded_io::Error`, of the enclosed kind.
+    #[cfg(feature = "embedded-io")]
+    EmbeddedIo(embedded_io::ErrorKind),
 }
 
 impl ErrorKind {
@@ -318,6 +554,95 @@ impl ErrorKind {
         Self::ReservedType
     }
 
+    /// A decoded length exceeded the platform's `usize::MAX`.
+    fn length_exceeds_platform(len: u64) -> Self {
+        Self::LengthExceedsPlatform(len)
+    }
+
+    /// An integer exceeded the supported width of 128 bits.
+    fn unsupported_numeric_width(bits: u32) -> Self {
+        Self::UnsupportedNumericWidth(bits)
+    }
+
+    /// A decoded length (or count) exceeded a configured limit.
+    fn limit_exceeded(len: usize, limit: usize) -> Self {
+        Self::LimitExceeded(Expectation {
+            unexpected: len,
+            expected: limit,
+        })
+    }
+
+    /// A string's length exceeded a configured limit.
+    fn string_too_long(len: usize, limit: usize) -> Self {
+        Self::StringTooLong(Expectation {
+            unexpected: len,
+            expected: limit,
+        })
+    }
+
+    /// A byte array's (or extension value's) length exceeded a configured limit.
+    fn bytes_too_long(len: usize, limit: usize) -> Self {
+        Self::BytesTooLong(Expectation {
+            unexpected: len,
+            expected: limit,
+        })
+    }
+
+    /// A sequence's number of items exceeded a configured limit.
+    fn seq_too_long(len: usize, limit: usize) -> Self {
+        Self::SeqTooLong(Expectation {
+            unexpected: len,
+            expected: limit,
+        })
+    }
+
+    /// A map's number of entries exceeded a configured limit.
+    fn map_too_long(len: usize, limit: usize) -> Self {
+        Self::MapTooLong(Expectation {
+            unexpected: len,
+            expected: limit,
+        })
+    }
+
+    /// A value's header byte is reserved for future versions of the format.
+    fn reserved_marker(byte: u8) -> Self {
+        Self::ReservedMarker(byte)
+    }
+
+    /// A value was followed by unexpected bytes.
+    fn trailing_bytes(len: usize) -> Self {
+        Self::TrailingBytes(len)
+    }
+
+    /// A part of the format is not permitted by the configured profile.
+    fn profile_violation(construct: &'static str, profile: Profile) -> Self {
+        Self::ProfileViolation(Expectation {
+            unexpected: construct,
+            expected: profile,
+        })
+    }
+
+    /// A struct contained an unknown field.
+    fn unknown_field(field: &str, expected: &'static [&'static str]) -> Self {
+        Self::UnknownField(Expectation {
+            unexpected: field.to_string(),
+            expected,
+        })
+    }
+
+    /// A checksum did not match the checksummed bytes.
+    fn checksum_mismatch(actual: u32, expected: u32) -> Self {
+        Self::ChecksumMismatch(Expectation {
+            unexpected: actual,
+            expected,
+        })
+    }
+
+    /// A fixed-size buffer ran out of space.
+    fn buffer_full(written: usize) -> Self {
+        Self::BufferFull(written)
+    }
+
     #[cfg(feature = "std")]
     fn io(err: std::io::Error) -> Self {
         if err.kind() == std::io::ErrorKind::UnexpectedEof {
@@ -340,7 +665,23 @@ impl ErrorKind {
             ErrorKind::DepthLimitExceeded => ErrorCode::DepthLimitExceeded,
             ErrorKind::Utf8(_) => ErrorCode::Utf8,
             ErrorKind::ReservedType => ErrorCode::ReservedType,
+            ErrorKind::LengthExceedsPlatform(_) => ErrorCode::LengthExceedsPlatform,
+            ErrorKind::UnsupportedNumericWidth(_) => ErrorCode::UnsupportedNumericWidth,
+            ErrorKind::LimitExceeded(_) => ErrorCode::LimitExceeded,
+            ErrorKind::StringTooLong(_) => ErrorCode::StringTooLong,
+            ErrorKind::BytesTooLong(_) => ErrorCode::BytesTooLong,
+            ErrorKind::SeqTooLong(_) => ErrorCode::SeqTooLong,
+            ErrorKind::MapTooLong(_) => ErrorCode::MapTooLong,
+            ErrorKind::ReservedMarker(_) => ErrorCode::ReservedMarker,
+            ErrorKind::TrailingBytes(_) => ErrorCode::TrailingBytes,
+            ErrorKind::ChecksumMismatch(_) => ErrorCode::ChecksumMismatch,
+            ErrorKind::ProfileViolation(_) => ErrorCode::ProfileViolation,
+            ErrorKind::UnknownField(_) => ErrorCode::UnknownField,
+            ErrorKind::BufferFull(_) => ErrorCode::BufferFull,
+            #[cfg(feature = "std")]
             ErrorKind::StdIo(_) => ErrorCode::StdIo,
+            #[cfg(feature = "embedded-io")]
+            ErrorKind::EmbeddedIo(_) => ErrorCode::EmbeddedIo,
         }
     }
 }
@@ -378,8 +719,83 @@ impl Display for ErrorKind {
             }
             Self::Utf8(err) => Display::fmt(err, f),
             Self::ReservedType => f.write_str("reserved type"),
+            Self::LengthExceedsPlatform(len) => {
+                write!(f, "length {len} exceeds platform maximum of {}", usize::MAX)
+            }
+            Self::UnsupportedNumericWidth(bits) => {
+                write!(f, "{bits}-bit integer exceeds supported width of 128 bits")
+            }
+            Self::LimitExceeded(unexpected) => {
+                write!(
+                    f,
+                    "length {} exceeds limit of {}",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::StringTooLong(unexpected) => {
+                write!(
+                    f,
+                    "string length {} exceeds limit of {}",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::BytesTooLong(unexpected) => {
+                write!(
+                    f,
+                    "byte array length {} exceeds limit of {}",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::SeqTooLong(unexpected) => {
+                write!(
+                    f,
+                    "sequence length {} exceeds limit of {}",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::MapTooLong(unexpected) => {
+                write!(
+                    f,
+                    "map length {} exceeds limit of {}",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::ReservedMarker(byte) => write!(f, "reserved header byte {byte:#010b}"),
+            Self::TrailingBytes(len) => write!(f, "{len} trailing bytes after value"),
+            Self::ProfileViolation(unexpected) => {
+                write!(
+                    f,
+                    "{} not permitted by {:?} profile",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::UnknownField(unexpected) => {
+                write!(f, "unknown field `{}`, ", unexpected.unexpected)?;
+
+                match unexpected.expected {
+                    [] => write!(f, "there are no fields"),
+                    fields => {
+                        write!(f, "expected one of ")?;
+                        for (index, field) in fields.iter().enumerate() {
+                            let separator = if index == 0 { "" } else { ", " };
+                            write!(f, "{separator}`{field}`")?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            Self::ChecksumMismatch(unexpected) => {
+                write!(
+                    f,
+                    "checksum {:#010x} does not match expected {:#010x}",
+                    unexpected.unexpected, unexpected.expected
+                )
+            }
+            Self::BufferFull(written) => write!(f, "buffer full after {written} bytes"),
             #[cfg(feature = "std")]
             Self::StdIo(err) => Display::fmt(err, f),
+            #[cfg(feature = "embedded-io")]
+            Self::EmbeddedIo(kind) => write!(f, "I/O error: {kind:?}"),
         }
     }
 }
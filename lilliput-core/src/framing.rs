@@ -0,0 +1,591 @@
+//! Framing of encoded values, for exchanging them over byte streams.
+//!
+//! Each frame consists of a 4-byte big-endian length prefix,
+//! followed by exactly one encoded value of that length,
+//! which allows for a receiver to tell complete values apart
+//! from partially received ones, without having to decode them.
+//!
+//! Optionally each frame's body is followed by a 4-byte big-endian
+//! CRC-32C checksum of it (see `FrameEncoder::with_checksum()`),
+//! for detecting corrupted frames, e.g. when stored on disk.
+//!
+//! ```
+//! use lilliput_core::{
+//!     framing::{FrameDecoder, FrameEncoder},
+//!     io::VecWriter,
+//!     value::{IntValue, Value},
+//! };
+//!
+//! let value = Value::Int(IntValue::from(42_u8));
+//!
+//! let mut encoded = Vec::new();
+//! let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut encoded));
+//! encoder.encode_value(&value).unwrap();
+//!
+//! let mut decoder = FrameDecoder::default();
+//!
+//! // Frames can be received in arbitrary chunks:
+//! decoder.extend_from_slice(&encoded[..3]);
+//! assert_eq!(decoder.decode_value().unwrap(), None);
+//!
+//! decoder.extend_from_slice(&encoded[3..]);
+//! assert_eq!(decoder.decode_value().unwrap(), Some(value));
+//! ```
+
+use alloc::{format, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    config::{DecoderConfig, EncoderConfig},
+    crc32c::crc32c,
+    decoder::Decoder,
+    encoder::Encoder,
+    error::{Error, Result},
+    io::{SliceReader, VecWriter, Write},
+    validation::DEFAULT_MAX_DEPTH,
+    value::Value,
+};
+
+/// The length (in bytes) of a frame's length prefix.
+pub const FRAME_PREFIX_LEN: usize = 4;
+
+/// The length (in bytes) of a frame's checksum, if enabled.
+pub const FRAME_CHECKSUM_LEN: usize = 4;
+
+/// The default maximum length (in bytes) of a frame's body.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// An encoder for encoding lilliput values as length-prefixed frames.
+#[derive(Debug)]
+pub struct FrameEncoder<W> {
+    encoder: Encoder<W>,
+    buffer: Vec<u8>,
+    max_frame_len: usize,
+    checksum: bool,
+}
+
+impl<W> FrameEncoder<W> {
+    /// Creates an encoder from `writer`.
+    pub fn from_writer(writer: W) -> Self {
+        Self::new(writer, EncoderConfig::default())
+    }
+
+    /// Creates an encoder from `writer`, configured by `config`.
+    pub fn new(writer: W, config: EncoderConfig) -> Self {
+        Self {
+            encoder: Encoder::new(writer, config),
+            buffer: Vec::new(),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            checksum: false,
+        }
+    }
+
+    /// Limits the length of frame bodies to `max_frame_len` bytes, returning `self`.
+    ///
+    /// Lengths beyond `u32::MAX` can't be represented by a frame's prefix.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Enables the appending of a CRC-32C checksum to each frame's body, returning `self`.
+    ///
+    /// The checksum is not included in the frame's length prefix,
+    /// so the receiving decoder needs to have checksums enabled, too.
+    ///
+    /// ```
+    /// use lilliput_core::{
+    ///     error::ErrorCode,
+    ///     framing::{FrameDecoder, FrameEncoder},
+    ///     io::VecWriter,
+    ///     value::{IntValue, Value},
+    /// };
+    ///
+    /// let value = Value::Int(IntValue::from(42_u8));
+    ///
+    /// let mut encoded = Vec::new();
+    /// let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut encoded)).with_checksum();
+    /// encoder.encode_value(&value).unwrap();
+    ///
+    /// let mut decoder = FrameDecoder::default().with_checksum();
+    /// decoder.extend_from_slice(&encoded);
+    /// assert_eq!(decoder.decode_value().unwrap(), Some(value));
+    ///
+    /// // Corrupting the frame's body:
+    /// encoded[4] ^= 0b1;
+    ///
+    /// let mut decoder = FrameDecoder::default().with_checksum();
+    /// decoder.extend_from_slice(&encoded);
+    /// let err = decoder.decode_value().unwrap_err();
+    /// assert_eq!(err.code(), ErrorCode::ChecksumMismatch);
+    /// ```
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
+
+    /// Returns the encoder's internal `writer`, consuming `self`.
+    pub fn into_writer(self) -> W {
+        self.encoder.into_writer()
+    }
+
+    /// Returns the encoder's current write position.
+    pub fn pos(&self) -> usize {
+        self.encoder.pos()
+    }
+}
+
+impl<W> FrameEncoder<W>
+where
+    W: Write,
+{
+    /// Encodes a `Value` as a frame.
+    pub fn encode_value(&mut self, value: &Value) -> Result<()> {
+        self.encode_with(|encoder| encoder.encode_value(value))
+    }
+
+    /// Encodes whatever `encode` encodes into the provided encoder as a frame,
+    /// allowing for use of all of `Encoder`'s methods.
+    ///
+    /// The caller is responsible for encoding exactly one value.
+    /// Nothing gets written, if `encode` returns an error.
+    pub fn encode_with<F>(&mut self, encode: F) -> Result<()>
+    where
+        F: FnOnce(&mut Encoder<VecWriter<'_>>) -> Result<()>,
+    {
+        self.buffer.clear();
+
+        let config = self.encoder.config().clone();
+        encode(&mut Encoder::new(VecWriter::new(&mut self.buffer), config))?;
+
+        let len = self.buffer.len();
+        let prefix = u32::try_from(len)
+            .ok()
+            .filter(|_| len <= self.max_frame_len)
+            .ok_or_else(|| frame_too_long(len, self.max_frame_len, Some(self.pos())))?;
+
+        self.encoder.encode_raw_bytes(&prefix.to_be_bytes())?;
+        self.encoder.encode_raw_bytes(&self.buffer)?;
+
+        if self.checksum {
+            self.encoder
+                .encode_raw_bytes(&crc32c(&self.buffer).to_be_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A decoder for decoding length-prefixed frames of lilliput values,
+/// from bytes received in arbitrary chunks.
+///
+/// Bytes get provided via `extend_from_slice()` (or `read_from()`),
+/// after which any frames completed by them can be decoded.
+///
+/// As frames are commonly received from untrusted sources, a default decoder
+/// limits the nesting depth of decoded values to `validation::DEFAULT_MAX_DEPTH`.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    start: usize,
+    pos: usize,
+    max_frame_len: usize,
+    checksum: bool,
+    config: DecoderConfig,
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new(DecoderConfig::default().with_max_depth(Some(DEFAULT_MAX_DEPTH)))
+    }
+}
+
+impl FrameDecoder {
+    /// Creates a decoder, decoding the values of frames as configured by `config`.
+    pub fn new(config: DecoderConfig) -> Self {
+        Self {
+            buffer: Vec::new(),
+            start: 0,
+            pos: 0,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            checksum: false,
+            config,
+        }
+    }
+
+    /// Returns the configuration used for decoding the values of frames.
+    pub fn config(&self) -> &DecoderConfig {
+        &self.config
+    }
+
+    /// Limits the length of frame bodies to `max_frame_len` bytes, returning `self`.
+    ///
+    /// Frames announcing a longer body get rejected as soon as
+    /// their prefix is received, before buffering their body.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Enables the verification of each frame's CRC-32C checksum, returning `self`.
+    ///
+    /// Frames whose body does not match their checksum get rejected with
+    /// a `ChecksumMismatch` error, in which case they get skipped, nevertheless.
+    /// See `FrameEncoder::with_checksum()` for details.
+    pub fn with_checksum(mut self) -> Self {
+        self.checksum = true;
+        self
+    }
+
+    /// Appends received `bytes` to the decoder's buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.compact();
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Reads a chunk of bytes from `reader` into the decoder's buffer,
+    /// returning the number of bytes read, with `0` indicating its end.
+    #[cfg(feature = "std")]
+    pub fn read_from<R>(&mut self, reader: &mut R) -> Result<usize>
+    where
+        R: std::io::Read,
+    {
+        const CHUNK_LEN: usize = 8 * 1024;
+
+        self.compact();
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_LEN, 0b0);
+
+        let result = reader.read(&mut self.buffer[len..]);
+        let read = *result.as_ref().unwrap_or(&0);
+        self.buffer.truncate(len + read);
+
+        result.map_err(Error::io)
+    }
+
+    /// Returns the bytes received, but not decoded yet.
+    ///
+    /// Non-empty buffered bytes at the end of a stream indicate a truncated frame.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Returns the decoder's current read position,
+    /// i.e. the number of bytes decoded so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Decodes the body of the next frame, if it has been received completely,
+    /// without decoding the value contained in it.
+    pub fn decode_frame(&mut self) -> Result<Option<&[u8]>> {
+        let Some(range) = self.next_frame()? else {
+            return Ok(None);
+        };
+
+        self.consume_frame(&range)?;
+
+        Ok(Some(&self.buffer[range]))
+    }
+
+    /// Decodes the `Value` of the next frame, if it has been received completely.
+    ///
+    /// Returns an error if the frame's body does not contain exactly one value,
+    /// in which case the frame gets skipped, nevertheless.
+    pub fn decode_value(&mut self) -> Result<Option<Value>> {
+        let Some(range) = self.next_frame()? else {
+            return Ok(None);
+        };
+
+        let body_pos = self.pos + FRAME_PREFIX_LEN;
+        self.consume_frame(&range)?;
+
+        let body = &self.buffer[range];
+
+        let mut decoder = Decoder::new(SliceReader::new(body), self.config);
+        let value = decoder
+            .decode_value()
+            .map_err(|err| err.offset_pos(body_pos))?;
+
+        if decoder.pos() != body.len() {
+            return Err(Error::invalid_length(
+                format!("{} bytes", body.len()),
+                format!("{} bytes of a single encoded value", decoder.pos()),
+                Some(body_pos + decoder.pos()),
+            ));
+        }
+
+        Ok(Some(value))
+    }
+
+    /// Returns the range of the next frame's body within the buffer,
+    /// if it has been received completely.
+    fn next_frame(&self) -> Result<Option<Range<usize>>> {
+        let buffered = self.buffered();
+
+        let Some(prefix) = buffered.get(..FRAME_PREFIX_LEN) else {
+            return Ok(None);
+        };
+
+        let prefix: [u8; FRAME_PREFIX_LEN] = prefix.try_into().expect("prefix should have length");
+        let len = u32::from_be_bytes(prefix) as usize;
+
+        if len > self.max_frame_len {
+            return Err(frame_too_long(len, self.max_frame_len, Some(self.pos)));
+        }
+
+        if buffered.len() < FRAME_PREFIX_LEN + len + self.checksum_len() {
+            return Ok(None);
+        }
+
+        let start = self.start + FRAME_PREFIX_LEN;
+
+        Ok(Some(start..(start + len)))
+    }
+
+    /// Consumes the frame of the body at `range` within the buffer,
+    /// verifying its checksum, if enabled.
+    fn consume_frame(&mut self, range: &Range<usize>) -> Result<()> {
+        let checksum_pos = self.pos + FRAME_PREFIX_LEN + range.len();
+        let end = range.end + self.checksum_len();
+
+        self.consume(end);
+
+        if !self.checksum {
+            return Ok(());
+        }
+
+        let expected: [u8; FRAME_CHECKSUM_LEN] = self.buffer[range.end..end]
+            .try_into()
+            .expect("checksum should have length");
+        let expected = u32::from_be_bytes(expected);
+        let actual = crc32c(&self.buffer[range.clone()]);
+
+        if actual != expected {
+            return Err(Error::checksum_mismatch(
+                actual,
+                expected,
+                Some(checksum_pos),
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn consume(&mut self, end: usize) {
+        self.pos += end - self.start;
+        self.start = end;
+    }
+
+    fn checksum_len(&self) -> usize {
+        if self.checksum {
+            FRAME_CHECKSUM_LEN
+        } else {
+            0
+        }
+    }
+
+    /// Drops already decoded bytes from the buffer.
+    fn compact(&mut self) {
+        self.buffer.drain(..self.start);
+        self.start = 0;
+    }
+}
+
+fn frame_too_long(len: usize, max_len: usize, pos: Option<usize>) -> Error {
+    Error::invalid_length(
+        format!("frame of {len} bytes"),
+        format!("frame of at most {max_len} bytes"),
+        pos,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        error::ErrorCode,
+        value::{IntValue, SeqValue, StringValue},
+    };
+
+    use super::*;
+
+    fn encode(values: &[Value]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut encoded));
+        for value in values {
+            encoder.encode_value(value).unwrap();
+        }
+        encoded
+    }
+
+    #[test]
+    fn frame_layout() {
+        let encoded = encode(&[Value::Int(IntValue::from(1_u8))]);
+        assert_eq!(encoded, [0x00, 0x00, 0x00, 0x01, 0xc1]);
+
+        let mut decoder = FrameDecoder::default();
+        decoder.extend_from_slice(&encoded);
+        assert_eq!(decoder.decode_frame().unwrap(), Some(&[0xc1][..]));
+        assert_eq!(decoder.decode_frame().unwrap(), None);
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn byte_by_byte() {
+        let values = vec![
+            Value::String(StringValue::from("lilliput".to_owned())),
+            Value::Seq(SeqValue::from(vec![Value::default(); 3])),
+        ];
+        let encoded = encode(&values);
+
+        let mut decoder = FrameDecoder::default();
+        let mut decoded = Vec::new();
+        for byte in encoded {
+            decoder.extend_from_slice(&[byte]);
+            decoded.extend(decoder.decode_value().unwrap());
+        }
+
+        assert_eq!(decoded, values);
+        assert!(decoder.buffered().is_empty());
+    }
+
+    #[test]
+    fn read_from() {
+        let values = vec![Value::default(), Value::Int(IntValue::from(42_u8))];
+        let encoded = encode(&values);
+
+        let mut reader = encoded.as_slice();
+        let mut decoder = FrameDecoder::default();
+        while decoder.read_from(&mut reader).unwrap() > 0 {}
+
+        assert_eq!(decoder.decode_value().unwrap(), Some(values[0].clone()));
+        assert_eq!(decoder.decode_value().unwrap(), Some(values[1].clone()));
+        assert_eq!(decoder.decode_value().unwrap(), None);
+    }
+
+    #[test]
+    fn frame_too_long() {
+        let value = Value::String(StringValue::from("lilliput".to_owned()));
+
+        let mut sink = Vec::new();
+        let mut encoder =
+            FrameEncoder::from_writer(VecWriter::new(&mut sink)).with_max_frame_len(4);
+        let error_code = encoder.encode_value(&value).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidLength);
+
+        // Rejected upon receiving the prefix, without waiting for the body:
+        let mut decoder = FrameDecoder::default().with_max_frame_len(4);
+        decoder.extend_from_slice(&encode(&[value])[..FRAME_PREFIX_LEN]);
+        let error_code = decoder.decode_value().unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidLength);
+    }
+
+    #[test]
+    fn trailing_bytes_in_frame() {
+        let encoded = [
+            0x00, 0x00, 0x00, 0x02, 0xc1, 0xc2, 0x00, 0x00, 0x00, 0x01, 0xc1,
+        ];
+
+        let mut decoder = FrameDecoder::default();
+        decoder.extend_from_slice(&encoded);
+        let error = decoder.decode_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidLength);
+        assert_eq!(error.pos(), Some(5));
+
+        // The malformed frame gets skipped:
+        assert_eq!(
+            decoder.decode_value().unwrap(),
+            Some(Value::Int(IntValue::from(1_u8)))
+        );
+    }
+
+    #[test]
+    fn checksum() {
+        let values = vec![
+            Value::Int(IntValue::from(1_u8)),
+            Value::Int(IntValue::from(2_u8)),
+        ];
+
+        let mut encoded = Vec::new();
+        let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut encoded)).with_checksum();
+        for value in &values {
+            encoder.encode_value(value).unwrap();
+        }
+        assert_eq!(
+            encoded[..9],
+            [0x00, 0x00, 0x00, 0x01, 0xc1, 0x63, 0x9B, 0xF6, 0x96]
+        );
+
+        // Corrupting the first frame's body:
+        encoded[4] = 0xc3;
+
+        // Frames are only complete once their checksum has been received:
+        let mut decoder = FrameDecoder::default().with_checksum();
+        decoder.extend_from_slice(&encoded[..8]);
+        assert_eq!(decoder.decode_value().unwrap(), None);
+
+        // Corrupted frames get skipped:
+        decoder.extend_from_slice(&encoded[8..]);
+        let error = decoder.decode_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::ChecksumMismatch);
+        assert_eq!(error.pos(), Some(5));
+        assert_eq!(decoder.decode_value().unwrap(), Some(values[1].clone()));
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+
+    #[test]
+    fn max_depth() {
+        // A frame of deeply nested single-item seqs:
+        let depth = 1024 * 1024;
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&(depth as u32 + 1).to_be_bytes());
+        encoded.resize(FRAME_PREFIX_LEN + depth, 0b00110001);
+        encoded.push(0xc1);
+
+        let mut decoder = FrameDecoder::default();
+        decoder.extend_from_slice(&encoded);
+        let error = decoder.decode_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DepthLimitExceeded);
+        assert_eq!(
+            error.pos(),
+            Some(FRAME_PREFIX_LEN + usize::from(DEFAULT_MAX_DEPTH))
+        );
+
+        let config = DecoderConfig::default().with_max_depth(Some(2));
+        let mut decoder = FrameDecoder::new(config);
+        decoder.extend_from_slice(&encode(&[Value::Seq(SeqValue::from(vec![Value::Seq(
+            SeqValue::default(),
+        )]))]));
+        assert!(decoder.decode_value().unwrap().is_some());
+        decoder.extend_from_slice(&encode(&[Value::Seq(SeqValue::from(vec![Value::Seq(
+            SeqValue::from(vec![Value::Seq(SeqValue::default())]),
+        )]))]));
+        let error = decoder.decode_value().unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DepthLimitExceeded);
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(
+            values in proptest::collection::vec(Value::arbitrary(), 0..5),
+            chunk_len in 1_usize..64,
+        ) {
+            let encoded = encode(&values);
+
+            let mut decoder = FrameDecoder::default();
+            let mut decoded = Vec::new();
+            for chunk in encoded.chunks(chunk_len) {
+                decoder.extend_from_slice(chunk);
+                while let Some(value) = decoder.decode_value().unwrap() {
+                    decoded.push(value);
+                }
+            }
+
+            prop_assert_eq!(decoded, values);
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 mod bool;
 mod bytes;
+mod ext;
 mod float;
 mod int;
 mod map;
@@ -20,6 +21,7 @@ use crate::marker::Marker;
 pub use self::{
     bool::BoolHeader,
     bytes::BytesHeader,
+    ext::ExtHeader,
     float::FloatHeader,
     int::{CompactIntHeader, ExtendedIntHeader, IntHeader},
     map::{CompactMapHeader, ExtendedMapHeader, MapHeader},
@@ -55,8 +57,8 @@ pub enum Header {
     /// Represents a map of key-value pairs.
     ///
     /// By default the map is backed by a `BTreeMap`. Enable the `preserve_order`
-    /// feature of serde_lilliput to use `OrderMap` instead, which preserves
-    /// entries in the order they are inserted into the map.
+    /// feature (of either lilliput-core or lilliput-serde) to use `OrderMap` instead,
+    /// which preserves entries in the order they are inserted into the map.
     Map(MapHeader),
 
     /// Represents a floating-point number.
@@ -73,6 +75,9 @@ pub enum Header {
 
     /// Represents a null value.
     Null(NullHeader),
+
+    /// Represents an extension value.
+    Ext(ExtHeader),
 }
 
 impl Default for Header {
@@ -145,6 +150,13 @@ impl From<NullHeader> for Header {
     }
 }
 
+impl From<ExtHeader> for Header {
+    #[inline]
+    fn from(value: ExtHeader) -> Self {
+        Self::Ext(value)
+    }
+}
+
 impl Header {
     /// Returns the header's type marker.
     pub fn marker(&self) -> Marker {
@@ -158,6 +170,8 @@ impl Header {
             Header::Bool(_) => Marker::Bool,
             Header::Unit(_) => Marker::Unit,
             Header::Null(_) => Marker::Null,
+            // Extension values use one of the sequence type's reserved headers:
+            Header::Ext(_) => Marker::Seq,
         }
     }
 }
@@ -0,0 +1,78 @@
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+#[cfg(any(test, feature = "testing"))]
+use proptest_derive::Arbitrary;
+
+/// Header representing an extension value.
+///
+/// Extension values are encoded with the sequence type's extension header,
+/// followed by the length of their body, and their tag, with the body's
+/// remaining bytes being the extension's (tag-specific) payload.
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ExtHeader {
+    tag: u64,
+    #[cfg_attr(
+        any(test, feature = "testing"),
+        proptest(strategy = "0..=(u32::MAX as usize)")
+    )]
+    len: usize,
+}
+
+impl ExtHeader {
+    /// Creates a header from an extension value's tag and its payload's length.
+    #[inline]
+    pub fn new(tag: u64, len: usize) -> Self {
+        Self { tag, len }
+    }
+
+    /// Returns the associated value's tag.
+    #[inline]
+    pub fn tag(&self) -> u64 {
+        self.tag
+    }
+
+    /// Returns `true` if the associated value's payload has a length of zero bytes, otherwise `false`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the length of the associated value's payload (i.e. excluding its tag).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+    };
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn encode_decode_roundtrip(header in ExtHeader::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_ext_header(&header).unwrap();
+
+            prop_assert!(encoded.len() <= 1 + 9 + 9);
+
+            let reader = SliceReader::new(&encoded);
+            let mut decoder = Decoder::from_reader(reader);
+            let decoded = decoder.decode_ext_header().unwrap();
+            prop_assert_eq!(&decoded, &header);
+        }
+    }
+}
@@ -11,7 +11,7 @@ pub struct FloatHeader {
         any(test, feature = "testing"),
         proptest(strategy = "(1..=FloatHeader::MAX_VALUE_WIDTH)")
     )]
-    width: u8,
+    pub(crate) width: u8,
 }
 
 impl FloatHeader {
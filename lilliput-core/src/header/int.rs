@@ -149,7 +149,7 @@ impl IntHeader {
     pub(crate) const COMPACT_VARIANT_BIT: u8 = 0b01000000;
     pub(crate) const COMPACT_VALUE_BITS: u8 = 0b00011111;
 
-    pub(crate) const EXTENDED_WIDTH_BITS: u8 = 0b00000111;
+    pub(crate) const EXTENDED_WIDTH_BITS: u8 = 0b00001111;
 }
 
 #[cfg(test)]
@@ -244,6 +244,25 @@ mod tests {
             }
         }
 
+        #[test]
+        fn for_u128(unsigned in u128::arbitrary(), packing_mode in PackingMode::arbitrary()) {
+            let header = IntHeader::for_unsigned(unsigned, packing_mode);
+
+            let extended_width = header.extended_width().unwrap_or(0);
+
+            match packing_mode {
+                PackingMode::None => prop_assert!(extended_width == 16),
+                PackingMode::Native => prop_assert!([1, 2, 4, 8, 16].contains(&extended_width)),
+                PackingMode::Optimal => {
+                    if unsigned <= IntHeader::COMPACT_VALUE_BITS as u128 {
+                        prop_assert!(extended_width == 0)
+                    } else {
+                        prop_assert!(extended_width <= 16)
+                    }
+                },
+            }
+        }
+
         #[test]
         fn for_i8(signed in i8::arbitrary(), packing_mode in PackingMode::arbitrary()) {
             let unsigned = signed.to_zig_zag();
@@ -324,6 +343,26 @@ mod tests {
             }
         }
 
+        #[test]
+        fn for_i128(signed in i128::arbitrary(), packing_mode in PackingMode::arbitrary()) {
+            let unsigned = signed.to_zig_zag();
+            let header = IntHeader::for_unsigned(unsigned, packing_mode);
+
+            let extended_width = header.extended_width().unwrap_or(0);
+
+            match packing_mode {
+                PackingMode::None => prop_assert!(extended_width == 16),
+                PackingMode::Native => prop_assert!([1, 2, 4, 8, 16].contains(&extended_width)),
+                PackingMode::Optimal => {
+                    if unsigned <= IntHeader::COMPACT_VALUE_BITS as u128 {
+                        prop_assert!(extended_width == 0)
+                    } else {
+                        prop_assert!(extended_width <= 16)
+                    }
+                },
+            }
+        }
+
         #[test]
         fn encode_decode_roundtrip(header in IntHeader::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
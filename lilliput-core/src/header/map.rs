@@ -127,6 +127,10 @@ impl MapHeader {
     pub(crate) const EXTENDED_LEN_WIDTH_BITS: u8 = 0b00000111;
 
     pub(crate) const COMPACT_MAX_LEN: u8 = Self::COMPACT_LEN_BITS;
+
+    /// Header byte of front-coded maps, taken from the
+    /// reserved headers of the sequence type's value space.
+    pub(crate) const FRONT_CODED: u8 = 0b00101010;
 }
 
 #[cfg(test)]
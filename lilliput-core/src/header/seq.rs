@@ -125,7 +125,58 @@ impl SeqHeader {
     pub(crate) const COMPACT_LEN_BITS: u8 = 0b00000111;
     pub(crate) const EXTENDED_LEN_WIDTH_BITS: u8 = 0b00000111;
 
+    pub(crate) const STREAMED_VARIANT_BIT: u8 = 0b00001000;
+    pub(crate) const STREAM_START: u8 = Self::TYPE_BITS | Self::STREAMED_VARIANT_BIT;
+    pub(crate) const STREAM_END: u8 = Self::STREAM_START | 0b00000001;
+
     pub(crate) const COMPACT_MAX_LEN: u8 = Self::COMPACT_LEN_BITS;
+
+    /// Header byte of columnar sequences of maps,
+    /// taken from the sequence type's reserved headers.
+    pub(crate) const COLUMNAR: u8 = 0b00101100;
+
+    /// Header byte of extension values (e.g. timestamps), whose body starts
+    /// with the extension's tag, taken from the sequence type's reserved headers.
+    pub(crate) const EXTENSION: u8 = 0b00101101;
+
+    /// Header byte of tabular sequences of maps,
+    /// taken from the sequence type's reserved headers.
+    pub(crate) const TABULAR: u8 = 0b00101110;
+
+    /// Returns `true` if `byte` is one of the header bytes reserved
+    /// for types introduced by future versions of the format.
+    #[inline]
+    pub(crate) fn is_reserved(byte: u8) -> bool {
+        const MASK: u8 = SeqHeader::MASK & !SeqHeader::EXTENDED_LEN_WIDTH_BITS;
+
+        (byte & !Self::MASK) == 0b0
+            && (byte & MASK) == Self::STREAM_START
+            && byte != Self::STREAM_START
+            && byte != Self::STREAM_END
+            && byte != super::MapHeader::FRONT_CODED
+            && byte != super::StringHeader::DICTIONARY_REF
+            && byte != Self::COLUMNAR
+            && byte != Self::EXTENSION
+            && byte != Self::TABULAR
+    }
+
+    /// Returns `true` if `byte` is one of the header bytes beyond the core headers,
+    /// i.e. those of streamed, columnar and tabular sequences, front-coded maps,
+    /// dictionary references and extension values, as well as reserved ones.
+    #[inline]
+    pub(crate) fn is_non_core(byte: u8) -> bool {
+        const MASK: u8 = SeqHeader::MASK & !SeqHeader::EXTENDED_LEN_WIDTH_BITS;
+
+        (byte & !Self::MASK) == 0b0 && (byte & MASK) == Self::STREAM_START
+    }
+
+    /// Returns `true` if values with header `byte` may be decoded as opaque
+    /// `UnknownValue`s, i.e. reserved headers and dictionary references
+    /// (whose ids may be unknown to the decoder).
+    #[inline]
+    pub(crate) fn is_collectable(byte: u8) -> bool {
+        Self::is_reserved(byte) || byte == super::StringHeader::DICTIONARY_REF
+    }
 }
 #[cfg(test)]
 mod tests {
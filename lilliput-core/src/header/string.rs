@@ -129,6 +129,10 @@ impl StringHeader {
     pub(crate) const COMPACT_MAX_LEN: u8 = Self::COMPACT_LEN_BITS;
     #[allow(dead_code)]
     pub(crate) const EXTENDED_MAX_LEN_WIDTH: u8 = 1 + Self::EXTENDED_LEN_WIDTH_BITS;
+
+    /// Header byte of references to dictionary strings, taken from the
+    /// reserved headers of the sequence type's value space.
+    pub(crate) const DICTIONARY_REF: u8 = 0b00101011;
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 //! I/O related stuff.
 
-use std::ops::Deref;
+use alloc::vec::Vec;
+use core::ops::Deref;
 
 use crate::error::{Error, Result};
 
@@ -39,6 +40,17 @@ pub trait Read<'r> {
     /// Returns the next byte without advancing the position.
     fn peek_one(&mut self) -> Result<u8>;
 
+    /// Returns the next `len` bytes without advancing the position.
+    ///
+    /// Readers not supporting look-ahead beyond a single byte return an error.
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        let _ = len;
+        Err(Error::uncategorized(
+            "reader does not support peeking multiple bytes",
+            None,
+        ))
+    }
+
     /// Skips the next byte, advancing the position.
     fn skip_one(&mut self) -> Result<()> {
         match self.read_one() {
@@ -94,55 +106,81 @@ pub trait Read<'r> {
 
     /// Reads the next `len` bytes into `buf`, advancing the position.
     fn read_into(&mut self, buf: &mut [u8]) -> Result<()>;
+
+    /// Reads the next `len` bytes into an owned buffer, advancing the position.
+    ///
+    /// Returns `None` (without advancing the position) if the reader has no
+    /// way of handing out owned buffers that is cheaper than copying
+    /// the result of `read()`, which callers are expected to fall back to.
+    fn read_owned(&mut self, len: usize) -> Result<Option<Vec<u8>>> {
+        let _ = len;
+        Ok(None)
+    }
+
+    /// Returns the number of bytes left to read, if known.
+    ///
+    /// Used for bounding pre-allocations based on decoded lengths.
+    fn remaining_len_hint(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns the bytes available for reading without further I/O,
+    /// without advancing the position.
+    ///
+    /// Used for decoding runs of small values in bulk,
+    /// with an empty slice making callers fall back to reading byte by byte.
+    fn buffered(&self) -> &[u8] {
+        &[]
+    }
 }
 
 // MARK: - StdIoReader
 
 /// A wrapper around instances of `std::io::Read`.
+///
+/// Only ever reads the bytes getting decoded (or peeked at) from `reader`,
+/// which makes it safe to decode consecutive values from a shared stream,
+/// at the cost of issuing a read call per header. Use `BufferedReader`
+/// for reading from files or sockets in larger chunks instead.
+#[cfg(feature = "std")]
 pub struct StdIoReader<R> {
-    reader: R,
-    peeked: Option<u8>,
+    reader: BufferedReader<R>,
 }
 
+#[cfg(feature = "std")]
 impl<R> StdIoReader<R> {
     /// Creates an instance from a `reader`.
     pub fn new(reader: R) -> Self {
         Self {
-            reader,
-            peeked: None,
+            reader: BufferedReader::with_capacity(1, reader),
         }
     }
 
     /// Returns the internal `reader`, consuming `self`.
     pub fn into_reader(self) -> R {
-        self.reader
+        self.reader.into_inner()
     }
 }
 
+#[cfg(feature = "std")]
 impl<'r, R> Read<'r> for StdIoReader<R>
 where
     R: std::io::Read,
 {
     fn peek_one(&mut self) -> Result<u8> {
-        if let Some(byte) = self.peeked {
-            return Ok(byte);
-        }
+        self.reader.peek_one()
+    }
 
-        let byte = self.read_one()?;
-        self.peeked = Some(byte);
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        self.reader.peek(len)
+    }
 
-        Ok(byte)
+    fn skip(&mut self, len: usize) -> Result<()> {
+        Read::skip(&mut self.reader, len)
     }
 
     fn read_one(&mut self) -> Result<u8> {
-        if let Some(byte) = self.peeked.take() {
-            return Ok(byte);
-        }
-
-        let mut bytes: [u8; 1] = [0b0];
-        self.read_into(&mut bytes)?;
-
-        Ok(bytes[0])
+        self.reader.read_one()
     }
 
     fn read<'s>(
@@ -150,60 +188,462 @@ where
         len: usize,
         scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'r, 's, [u8]>> {
-        // Copied from the default buffer length of `std::io::BufReader`:
-        const MAX_CHUNK_LENGTH: usize = 8192;
+        self.reader.read(len, scratch)
+    }
 
-        let mut total_read = 0;
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader.read_into(buf)
+    }
 
-        if len == 0 {
-            return Ok(Reference::Copied(&[]));
-        }
+    fn read_owned(&mut self, len: usize) -> Result<Option<Vec<u8>>> {
+        self.reader.read_owned(len)
+    }
+
+    fn buffered(&self) -> &[u8] {
+        Read::buffered(&self.reader)
+    }
+}
+
+// MARK: - BufferedReader
 
-        if let Some(byte) = self.peeked.take() {
-            scratch.resize(1, byte);
-            total_read += 1;
+/// A buffering wrapper around instances of `std::io::Read`.
+///
+/// Reads from `reader` in chunks of up to the buffer's capacity, serving
+/// headers (and other small reads) from the buffer, while reading longer
+/// byte sequences directly. Like with `std::io::BufReader`, the buffer may
+/// hold bytes beyond the decoded values, which get lost with `into_inner()`.
+///
+/// ```
+/// use lilliput_core::{decoder::Decoder, io::BufferedReader};
+///
+/// // A map of `"name"` to `"lilliput"`:
+/// let encoded: &[u8] = &[0x19, 0x64, b'n', b'a', b'm', b'e', 0x68, b'l', b'i', b'l', b'l', b'i', b'p', b'u', b't'];
+///
+/// let mut decoder = Decoder::from_reader(BufferedReader::new(encoded));
+/// let value = decoder.decode_value().unwrap();
+///
+/// assert_eq!(value.get("name").and_then(|name| name.as_str()), Some("lilliput"));
+/// ```
+#[cfg(feature = "std")]
+pub struct BufferedReader<R> {
+    reader: R,
+    buf: Vec<u8>,
+    start: usize,
+    end: usize,
+    capacity: usize,
+}
+
+#[cfg(feature = "std")]
+impl<R> BufferedReader<R> {
+    /// The default capacity of the buffer, matching that of `std::io::BufReader`.
+    pub const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+    /// Creates an instance from a `reader`, with the default capacity.
+    pub fn new(reader: R) -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY, reader)
+    }
+
+    /// Creates an instance from a `reader`, with a buffer of `capacity` bytes.
+    ///
+    /// The buffer gets allocated lazily, on its first use.
+    pub fn with_capacity(capacity: usize, reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            start: 0,
+            end: 0,
+            capacity: capacity.max(1),
         }
+    }
+
+    /// Returns the capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns a reference to the internal `reader`.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns the internal `reader`, consuming `self`,
+    /// discarding any bytes buffered but not read yet.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    // MARK: - Private
+
+    fn available(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Consumes up to `len` buffered bytes, returning their range within the buffer.
+    fn consume(&mut self, len: usize) -> core::ops::Range<usize> {
+        let len = len.min(self.available());
+        let range = self.start..(self.start + len);
+        self.start += len;
+        range
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R> BufferedReader<R>
+where
+    R: std::io::Read,
+{
+    // MARK: - Private
 
-        while total_read < len {
-            let remaining = len - total_read;
-            let to_read = remaining.min(MAX_CHUNK_LENGTH);
+    /// Reads from `reader` until at least `len` bytes are buffered,
+    /// reading no further than the larger of `len` and the buffer's capacity.
+    fn fill_to(&mut self, len: usize) -> Result<()> {
+        if self.available() >= len {
+            return Ok(());
+        }
 
-            let old_len = scratch.len();
-            scratch.resize(old_len + to_read, 0);
+        // Moves the buffered bytes to the front, making room for more:
+        if self.start > 0 {
+            self.buf.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
 
-            let read = self
-                .reader
-                .read(&mut scratch[old_len..])
-                .map_err(Error::io)?;
+        let limit = self.capacity.max(len);
+        if self.buf.len() < limit {
+            self.buf.resize(limit, 0b0);
+        }
 
-            if read < to_read {
-                return Err(Error::end_of_file());
+        while self.end < len {
+            match self.reader.read(&mut self.buf[self.end..limit]) {
+                Ok(0) => return Err(Error::end_of_file()),
+                Ok(read) => self.end += read,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(Error::io(err)),
             }
+        }
+
+        Ok(())
+    }
+
+    /// Appends the next `len` bytes to `vec`, reading them directly from `reader`.
+    fn read_unbuffered_into_vec(&mut self, len: usize, vec: &mut Vec<u8>) -> Result<()> {
+        use std::io::Read as _;
+
+        let expected = vec.len() + len;
+
+        // Reads directly into the buffer, growing it as data arrives,
+        // rather than trusting `len` for pre-allocating it up front:
+        (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(vec)
+            .map_err(Error::io)?;
+
+        if vec.len() < expected {
+            return Err(Error::end_of_file());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'r, R> Read<'r> for BufferedReader<R>
+where
+    R: std::io::Read,
+{
+    fn peek_one(&mut self) -> Result<u8> {
+        self.fill_to(1)?;
+
+        Ok(self.buf[self.start])
+    }
+
+    /// Peeking beyond the buffer's capacity grows the buffer.
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        self.fill_to(len)?;
+
+        Ok(&self.buf[self.start..(self.start + len)])
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        let remaining = len - self.consume(len).len();
+
+        if remaining == 0 {
+            return Ok(());
+        }
+
+        if remaining < self.capacity {
+            self.fill_to(remaining)?;
+            self.consume(remaining);
+            return Ok(());
+        }
+
+        let skipped = std::io::copy(
+            &mut std::io::Read::take(&mut self.reader, remaining as u64),
+            &mut std::io::sink(),
+        )
+        .map_err(Error::io)?;
+
+        if skipped < remaining as u64 {
+            return Err(Error::end_of_file());
+        }
+
+        Ok(())
+    }
+
+    fn read_one(&mut self) -> Result<u8> {
+        let byte = self.peek_one()?;
+        self.start += 1;
+
+        Ok(byte)
+    }
 
-            total_read += read;
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        if len < self.capacity {
+            self.fill_to(len)?;
+        }
+
+        if self.available() >= len {
+            let range = self.consume(len);
+            return Ok(Reference::Copied(&self.buf[range]));
         }
 
+        scratch.clear();
+
+        let range = self.consume(len);
+        scratch.extend_from_slice(&self.buf[range]);
+
+        self.read_unbuffered_into_vec(len - scratch.len(), scratch)?;
+
         Ok(Reference::Copied(scratch))
     }
 
     fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
-        if buf.is_empty() {
+        let range = self.consume(buf.len());
+        let (head, tail) = buf.split_at_mut(range.len());
+        head.copy_from_slice(&self.buf[range]);
+
+        if tail.is_empty() {
             return Ok(());
         }
 
-        let offset = if let Some(byte) = self.peeked.take() {
-            buf[0] = byte;
-            1
-        } else {
-            0
-        };
+        if tail.len() >= self.capacity {
+            return std::io::Read::read_exact(&mut self.reader, tail).map_err(Error::io);
+        }
+
+        self.fill_to(tail.len())?;
+        let range = self.consume(tail.len());
+        tail.copy_from_slice(&self.buf[range]);
+
+        Ok(())
+    }
 
+    fn read_owned(&mut self, len: usize) -> Result<Option<Vec<u8>>> {
+        let range = self.consume(len);
+        let mut buf = self.buf[range].to_vec();
+
+        self.read_unbuffered_into_vec(len - buf.len(), &mut buf)?;
+
+        Ok(Some(buf))
+    }
+
+    fn buffered(&self) -> &[u8] {
+        &self.buf[self.start..self.end]
+    }
+}
+
+// MARK: - EmbeddedIoReader
+
+/// A wrapper around instances of `embedded_io::Read`.
+///
+/// Reads the bytes getting decoded directly into their destination,
+/// only holding on to those getting peeked at, and never reads beyond
+/// the decoded values, which makes it safe to decode consecutive values
+/// from a shared transport, such as a serial port.
+#[cfg(feature = "embedded-io")]
+pub struct EmbeddedIoReader<R> {
+    reader: R,
+    peeked: Vec<u8>,
+    start: usize,
+}
+
+#[cfg(feature = "embedded-io")]
+impl<R> EmbeddedIoReader<R> {
+    /// Creates an instance from a `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            peeked: Vec::new(),
+            start: 0,
+        }
+    }
+
+    /// Returns the internal `reader`, consuming `self`,
+    /// discarding any bytes peeked at but not read yet.
+    pub fn into_reader(self) -> R {
         self.reader
-            .read_exact(&mut buf[offset..])
-            .map_err(Error::io)
+    }
+
+    // MARK: - Private
+
+    fn available(&self) -> usize {
+        self.peeked.len() - self.start
+    }
+
+    /// Consumes up to `len` peeked bytes, returning their range within `peeked`.
+    fn consume(&mut self, len: usize) -> core::ops::Range<usize> {
+        let len = len.min(self.available());
+        let range = self.start..(self.start + len);
+        self.start += len;
+        range
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<R> EmbeddedIoReader<R>
+where
+    R: embedded_io::Read,
+{
+    // MARK: - Private
+
+    /// Reads from `reader` until exactly `len` bytes are held for peeking.
+    fn fill_to(&mut self, len: usize) -> Result<()> {
+        if self.available() >= len {
+            return Ok(());
+        }
+
+        self.peeked.drain(..self.start);
+        self.start = 0;
+
+        let filled = self.peeked.len();
+        self.peeked.resize(len, 0b0);
+
+        let result = read_exact(&mut self.reader, &mut self.peeked[filled..]);
+
+        if result.is_err() {
+            self.peeked.truncate(filled);
+        }
+
+        result
+    }
+
+    /// Appends the next `len` bytes to `vec`, reading them directly from `reader`.
+    fn read_unbuffered_into_vec(&mut self, len: usize, vec: &mut Vec<u8>) -> Result<()> {
+        const CHUNK_LEN: usize = 1024;
+
+        // Grows the buffer as data arrives, rather than
+        // trusting `len` for pre-allocating it up front:
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk_len = remaining.min(CHUNK_LEN);
+            let filled = vec.len();
+            vec.resize(filled + chunk_len, 0b0);
+            read_exact(&mut self.reader, &mut vec[filled..])?;
+            remaining -= chunk_len;
+        }
+
+        Ok(())
     }
 }
 
+#[cfg(feature = "embedded-io")]
+impl<'r, R> Read<'r> for EmbeddedIoReader<R>
+where
+    R: embedded_io::Read,
+{
+    fn peek_one(&mut self) -> Result<u8> {
+        self.fill_to(1)?;
+
+        Ok(self.peeked[self.start])
+    }
+
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        self.fill_to(len)?;
+
+        Ok(&self.peeked[self.start..(self.start + len)])
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        let mut remaining = len - self.consume(len).len();
+
+        let mut bytes = [0b0; 64];
+        while remaining > 0 {
+            let chunk_len = remaining.min(bytes.len());
+            read_exact(&mut self.reader, &mut bytes[..chunk_len])?;
+            remaining -= chunk_len;
+        }
+
+        Ok(())
+    }
+
+    fn read_one(&mut self) -> Result<u8> {
+        let byte = self.peek_one()?;
+        self.start += 1;
+
+        Ok(byte)
+    }
+
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        if self.available() >= len {
+            let range = self.consume(len);
+            return Ok(Reference::Copied(&self.peeked[range]));
+        }
+
+        scratch.clear();
+
+        let range = self.consume(len);
+        scratch.extend_from_slice(&self.peeked[range]);
+
+        self.read_unbuffered_into_vec(len - scratch.len(), scratch)?;
+
+        Ok(Reference::Copied(scratch))
+    }
+
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        let range = self.consume(buf.len());
+        let (head, tail) = buf.split_at_mut(range.len());
+        head.copy_from_slice(&self.peeked[range]);
+
+        read_exact(&mut self.reader, tail)
+    }
+
+    fn read_owned(&mut self, len: usize) -> Result<Option<Vec<u8>>> {
+        let range = self.consume(len);
+        let mut buf = self.peeked[range].to_vec();
+
+        self.read_unbuffered_into_vec(len - buf.len(), &mut buf)?;
+
+        Ok(Some(buf))
+    }
+
+    fn buffered(&self) -> &[u8] {
+        &self.peeked[self.start..]
+    }
+}
+
+/// Reads exactly `buf.len()` bytes from `reader` into `buf`.
+#[cfg(feature = "embedded-io")]
+fn read_exact<R>(reader: &mut R, buf: &mut [u8]) -> Result<()>
+where
+    R: embedded_io::Read,
+{
+    if buf.is_empty() {
+        return Ok(());
+    }
+
+    reader.read_exact(buf).map_err(|err| match err {
+        embedded_io::ReadExactError::UnexpectedEof => Error::end_of_file(),
+        embedded_io::ReadExactError::Other(err) => Error::embedded_io(err),
+    })
+}
+
 // MARK: - SliceReader
 
 /// A wrapper around instances of `&[u8]`.
@@ -233,12 +673,30 @@ impl<'r> Read<'r> for SliceReader<'r> {
         Ok(self.slice[self.pos])
     }
 
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        if len > self.slice.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        Ok(&self.slice[self.pos..(self.pos + len)])
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        if len > self.slice.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        self.pos += len;
+
+        Ok(())
+    }
+
     fn read<'s>(
         &'s mut self,
         len: usize,
         _scratch: &'s mut Vec<u8>,
     ) -> Result<Reference<'r, 's, [u8]>> {
-        if self.pos + len > self.slice.len() {
+        if len > self.slice.len() - self.pos {
             return Err(Error::end_of_file());
         }
 
@@ -251,7 +709,7 @@ impl<'r> Read<'r> for SliceReader<'r> {
     fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
         let len = buf.len();
 
-        if self.pos + len > self.slice.len() {
+        if len > self.slice.len() - self.pos {
             return Err(Error::end_of_file());
         }
 
@@ -262,6 +720,86 @@ impl<'r> Read<'r> for SliceReader<'r> {
 
         Ok(())
     }
+
+    fn read_owned(&mut self, len: usize) -> Result<Option<Vec<u8>>> {
+        if len > self.slice.len() - self.pos {
+            return Err(Error::end_of_file());
+        }
+
+        let range = self.pos..(self.pos + len);
+        self.pos += len;
+
+        Ok(Some(self.slice[range].to_vec()))
+    }
+
+    fn remaining_len_hint(&self) -> Option<usize> {
+        Some(self.slice.len().saturating_sub(self.pos))
+    }
+
+    fn buffered(&self) -> &[u8] {
+        self.slice.get(self.pos..).unwrap_or_default()
+    }
+}
+
+// MARK: - PeekingReader
+
+/// A reader over the bytes peeked from another reader, leaving its position as is.
+pub(crate) struct PeekingReader<'a, R> {
+    reader: &'a mut R,
+    pos: usize,
+}
+
+impl<'a, R> PeekingReader<'a, R> {
+    pub(crate) fn new(reader: &'a mut R) -> Self {
+        Self { reader, pos: 0 }
+    }
+}
+
+impl<'r, 'p, R> Read<'r> for PeekingReader<'_, R>
+where
+    R: Read<'p>,
+{
+    fn peek_one(&mut self) -> Result<u8> {
+        Ok(self.reader.peek(self.pos + 1)?[self.pos])
+    }
+
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self.pos + len;
+
+        Ok(&self.reader.peek(end)?[self.pos..end])
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        let end = self.pos + len;
+        self.reader.peek(end)?;
+        self.pos = end;
+
+        Ok(())
+    }
+
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        _scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        let start = self.pos;
+        let end = start + len;
+
+        let bytes = &self.reader.peek(end)?[start..];
+        self.pos = end;
+
+        Ok(Reference::Copied(bytes))
+    }
+
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        let start = self.pos;
+        let end = start + buf.len();
+
+        buf.copy_from_slice(&self.reader.peek(end)?[start..]);
+        self.pos = end;
+
+        Ok(())
+    }
 }
 
 // MARK: - Write
@@ -281,11 +819,13 @@ pub trait Write {
 // MARK: - MutSliceWriter
 
 /// A wrapper around instances of `&mut [u8]`.
+#[deprecated(note = "use `SliceWriter` instead")]
 pub struct MutSliceWriter<'w> {
     slice: &'w mut [u8],
     pos: usize,
 }
 
+#[allow(deprecated)]
 impl<'w> MutSliceWriter<'w> {
     /// Creates a writer from a mutable `slice`.
     pub fn new(slice: &'w mut Vec<u8>) -> Self {
@@ -293,6 +833,7 @@ impl<'w> MutSliceWriter<'w> {
     }
 }
 
+#[allow(deprecated)]
 impl Write for MutSliceWriter<'_> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         let len = buf.len();
@@ -314,6 +855,168 @@ impl Write for MutSliceWriter<'_> {
     }
 }
 
+// MARK: - SliceWriter
+
+/// A writer into a fixed-size `&mut [u8]`.
+///
+/// Writes that don't fit into the slice's remaining space
+/// fail with `ErrorCode::BufferFull`, without writing any of their bytes.
+///
+/// ```
+/// use lilliput_core::{encoder::Encoder, io::SliceWriter};
+///
+/// let mut buf = [0; 16];
+/// let mut encoder = Encoder::from_writer(SliceWriter::new(&mut buf));
+/// encoder.encode_str("lilliput").unwrap();
+///
+/// let writer = encoder.into_writer();
+/// assert_eq!(writer.as_slice(), b"\x68lilliput");
+/// ```
+pub struct SliceWriter<'w> {
+    slice: &'w mut [u8],
+    pos: usize,
+}
+
+impl<'w> SliceWriter<'w> {
+    /// Creates a writer into `slice`, starting at its beginning.
+    pub fn new(slice: &'w mut [u8]) -> Self {
+        Self { slice, pos: 0 }
+    }
+
+    /// Returns the number of bytes written.
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns `true` if no bytes have been written.
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
+    /// Returns the number of bytes left to write.
+    pub fn remaining(&self) -> usize {
+        self.slice.len() - self.pos
+    }
+
+    /// Returns the bytes written.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.slice[..self.pos]
+    }
+
+    /// Returns the bytes written, consuming `self`.
+    pub fn into_slice(self) -> &'w mut [u8] {
+        &mut self.slice[..self.pos]
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.pos = write_fixed(self.slice, self.pos, buf)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// MARK: - ArrayWriter
+
+/// A writer into an owned, fixed-size `[u8; N]`,
+/// for encoding into stack-allocated buffers.
+///
+/// Writes that don't fit into the array's remaining space
+/// fail with `ErrorCode::BufferFull`, without writing any of their bytes.
+///
+/// ```
+/// use lilliput_core::{encoder::Encoder, io::ArrayWriter};
+///
+/// let mut encoder = Encoder::from_writer(ArrayWriter::<4>::new());
+/// encoder.encode_u16(1000).unwrap();
+/// assert_eq!(encoder.into_writer().as_slice(), &[0x81, 0x03, 0xE8]);
+///
+/// let mut encoder = Encoder::from_writer(ArrayWriter::<4>::new());
+/// let err = encoder.encode_str("lilliput").unwrap_err();
+/// assert_eq!(err.code(), lilliput_core::error::ErrorCode::BufferFull);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArrayWriter<const N: usize> {
+    array: [u8; N],
+    pos: usize,
+}
+
+impl<const N: usize> ArrayWriter<N> {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Self {
+            array: [0b0; N],
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes written.
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns `true` if no bytes have been written.
+    pub fn is_empty(&self) -> bool {
+        self.pos == 0
+    }
+
+    /// Returns the number of bytes left to write.
+    pub fn remaining(&self) -> usize {
+        N - self.pos
+    }
+
+    /// Returns the bytes written.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.array[..self.pos]
+    }
+
+    /// Returns the array, along with the number of bytes written to it, consuming `self`.
+    pub fn into_parts(self) -> ([u8; N], usize) {
+        (self.array, self.pos)
+    }
+
+    /// Discards the bytes written, for reusing the writer.
+    pub fn clear(&mut self) {
+        self.pos = 0;
+    }
+}
+
+impl<const N: usize> Default for ArrayWriter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Write for ArrayWriter<N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.pos = write_fixed(&mut self.array, self.pos, buf)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes `buf` into `slice` at `pos`, returning the position after it,
+/// or an error (without writing anything) if it doesn't fit.
+fn write_fixed(slice: &mut [u8], pos: usize, buf: &[u8]) -> Result<usize> {
+    let end = pos + buf.len();
+
+    let Some(dst) = slice.get_mut(pos..end) else {
+        return Err(Error::buffer_full(pos));
+    };
+    dst.copy_from_slice(buf);
+
+    Ok(end)
+}
+
 // MARK: - VecWriter
 
 /// A wrapper around instances of `Vec<u8>`.
@@ -347,10 +1050,12 @@ impl Write for VecWriter<'_> {
 // MARK: - StdIoBufWriter
 
 /// A wrapper around instances of `std::io::Write`.
+#[cfg(feature = "std")]
 pub struct StdIoWriter<W> {
     writer: W,
 }
 
+#[cfg(feature = "std")]
 impl<W> StdIoWriter<W> {
     /// Creates an instance from a `reader`.
     pub fn new(writer: W) -> Self {
@@ -363,6 +1068,7 @@ impl<W> StdIoWriter<W> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<W> Write for StdIoWriter<W>
 where
     W: std::io::Write,
@@ -376,9 +1082,49 @@ where
     }
 }
 
+// MARK: - EmbeddedIoWriter
+
+/// A wrapper around instances of `embedded_io::Write`.
+///
+/// Writes each buffer in full, retrying partial writes,
+/// as they are common with transports such as serial ports.
+#[cfg(feature = "embedded-io")]
+pub struct EmbeddedIoWriter<W> {
+    writer: W,
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W> EmbeddedIoWriter<W> {
+    /// Creates an instance from a `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Returns the internal `writer`, consuming `self`.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W> Write for EmbeddedIoWriter<W>
+where
+    W: embedded_io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.writer.write_all(buf).map_err(Error::embedded_io)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(Error::embedded_io)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::error::ErrorCode;
+    use crate::error::{ErrorCode, ErrorKind};
 
     use super::*;
 
@@ -498,6 +1244,22 @@ mod test {
             );
         }
 
+        #[test]
+        fn read_owned() {
+            let slice: &[u8] = &[1, 2, 3, 4, 5];
+            let mut reader = StdIoReader::new(slice);
+
+            assert_eq!(reader.peek_one().unwrap(), 1);
+            assert_eq!(reader.read_owned(0).unwrap(), Some(vec![]));
+            assert_eq!(reader.read_owned(2).unwrap(), Some(vec![1, 2]));
+            assert_eq!(reader.read_owned(1).unwrap(), Some(vec![3]));
+
+            assert_eq!(
+                reader.read_owned(3).err().unwrap().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+        }
+
         #[test]
         fn read_into() {
             let slice: &[u8] = &[1, 2, 3, 4, 5];
@@ -523,6 +1285,253 @@ mod test {
         }
     }
 
+    mod buffered_reader {
+        use super::*;
+
+        /// A reader counting its read calls, reading at most `chunk_len` bytes per call.
+        struct Counting<'a> {
+            bytes: &'a [u8],
+            chunk_len: usize,
+            reads: usize,
+        }
+
+        impl std::io::Read for Counting<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.reads += 1;
+
+                let len = buf.len().min(self.chunk_len).min(self.bytes.len());
+                buf[..len].copy_from_slice(&self.bytes[..len]);
+                self.bytes = &self.bytes[len..];
+
+                Ok(len)
+            }
+        }
+
+        fn counting(bytes: &[u8], chunk_len: usize) -> Counting<'_> {
+            Counting {
+                bytes,
+                chunk_len,
+                reads: 0,
+            }
+        }
+
+        #[test]
+        fn peek() {
+            let bytes: Vec<u8> = (0..32).collect();
+            let mut reader = BufferedReader::with_capacity(4, counting(&bytes, 3));
+
+            assert_eq!(reader.peek(2).unwrap(), &[0, 1]);
+            assert_eq!(reader.peek_one().unwrap(), 0);
+            assert_eq!(reader.read_one().unwrap(), 0);
+
+            // Peeking across reads, and beyond the capacity:
+            assert_eq!(reader.peek(6).unwrap(), &[1, 2, 3, 4, 5, 6]);
+            assert_eq!(reader.read_one().unwrap(), 1);
+            assert_eq!(reader.buffered(), &[2, 3, 4, 5, 6]);
+
+            assert_eq!(
+                reader.peek(64).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+        }
+
+        #[test]
+        fn read() {
+            let bytes: Vec<u8> = (0..32).collect();
+            let mut reader = BufferedReader::with_capacity(4, counting(&bytes, 32));
+            let mut scratch = Vec::new();
+
+            // Short reads get served from the buffer:
+            match reader.read(2, &mut scratch).unwrap() {
+                Reference::Borrowed(_) => panic!("reader should always copy"),
+                Reference::Copied(bytes) => assert_eq!(bytes, &[0, 1]),
+            }
+            assert!(scratch.is_empty());
+
+            // Long reads drain the buffer, reading the rest directly:
+            match reader.read(6, &mut scratch).unwrap() {
+                Reference::Borrowed(_) => panic!("reader should always copy"),
+                Reference::Copied(bytes) => assert_eq!(bytes, &[2, 3, 4, 5, 6, 7]),
+            }
+
+            let buf = &mut [0; 5];
+            reader.read_into(buf).unwrap();
+            assert_eq!(buf, &[8, 9, 10, 11, 12]);
+
+            assert_eq!(reader.read_owned(3).unwrap(), Some(vec![13, 14, 15]));
+
+            reader.skip(10).unwrap();
+            assert_eq!(reader.read_one().unwrap(), 26);
+
+            assert_eq!(
+                reader.read(6, &mut scratch).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+        }
+
+        #[test]
+        fn read_calls() {
+            use crate::{decoder::Decoder, encoder::Encoder, value::Value};
+
+            let value: Value = crate::text::from_str(
+                r#"{"id": 7_u8, "tags": ["a", "b", "c"], "nested": {"deep": [null, true]}}"#,
+            )
+            .unwrap();
+
+            let mut encoded = Vec::new();
+            Encoder::from_writer(VecWriter::new(&mut encoded))
+                .encode_value(&value)
+                .unwrap();
+
+            let mut decoder = Decoder::from_reader(BufferedReader::new(counting(&encoded, 1024)));
+            assert_eq!(decoder.decode_value().unwrap(), value);
+            assert!(decoder.into_reader().into_inner().reads <= 2);
+
+            // Unbuffered readers never read beyond the decoded value:
+            encoded.push(0xFF);
+            let mut decoder = Decoder::from_reader(StdIoReader::new(counting(&encoded, 1024)));
+            assert_eq!(decoder.decode_value().unwrap(), value);
+            let reader = decoder.into_reader().into_reader();
+            assert_eq!(reader.bytes, &[0xFF]);
+            assert!(reader.reads > 2);
+        }
+    }
+
+    #[cfg(feature = "embedded-io")]
+    mod embedded_io {
+        use super::*;
+
+        /// A reader reading at most `chunk_len` bytes per call.
+        struct Chunked<'a> {
+            bytes: &'a [u8],
+            chunk_len: usize,
+        }
+
+        impl ::embedded_io::ErrorType for Chunked<'_> {
+            type Error = ::embedded_io::ErrorKind;
+        }
+
+        impl ::embedded_io::Read for Chunked<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> core::result::Result<usize, Self::Error> {
+                let len = buf.len().min(self.chunk_len).min(self.bytes.len());
+                buf[..len].copy_from_slice(&self.bytes[..len]);
+                self.bytes = &self.bytes[len..];
+
+                Ok(len)
+            }
+        }
+
+        #[test]
+        fn read() {
+            let bytes: Vec<u8> = (0..32).collect();
+            let mut reader = EmbeddedIoReader::new(Chunked {
+                bytes: &bytes,
+                chunk_len: 3,
+            });
+            let mut scratch = Vec::new();
+
+            assert_eq!(reader.peek(4).unwrap(), &[0, 1, 2, 3]);
+            assert_eq!(reader.read_one().unwrap(), 0);
+            assert_eq!(reader.buffered(), &[1, 2, 3]);
+
+            match reader.read(5, &mut scratch).unwrap() {
+                Reference::Borrowed(_) => panic!("reader should always copy"),
+                Reference::Copied(bytes) => assert_eq!(bytes, &[1, 2, 3, 4, 5]),
+            }
+
+            let buf = &mut [0; 5];
+            reader.read_into(buf).unwrap();
+            assert_eq!(buf, &[6, 7, 8, 9, 10]);
+
+            assert_eq!(reader.read_owned(3).unwrap(), Some(vec![11, 12, 13]));
+
+            reader.skip(10).unwrap();
+            assert_eq!(reader.read_one().unwrap(), 24);
+
+            assert_eq!(
+                reader.read(8, &mut scratch).unwrap_err().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+        }
+
+        #[test]
+        fn encode_decode() {
+            use crate::{decoder::Decoder, encoder::Encoder, value::Value};
+
+            let value: Value =
+                crate::text::from_str(r#"{"id": 7_u8, "tags": ["a", "b"], "blob": b"\x01\x02"}"#)
+                    .unwrap();
+
+            let mut buf = [0b0; 64];
+            let mut encoder = Encoder::from_writer(EmbeddedIoWriter::new(&mut buf[..]));
+            encoder.encode_value(&value).unwrap();
+            encoder.encode_bool(true).unwrap();
+            let unused = encoder.into_writer().into_writer().len();
+            let len = buf.len() - unused;
+
+            // Readers never read beyond the decoded value:
+            let mut decoder = Decoder::from_reader(EmbeddedIoReader::new(Chunked {
+                bytes: &buf[..len],
+                chunk_len: 2,
+            }));
+            assert_eq!(decoder.decode_value().unwrap(), value);
+            assert_eq!(decoder.into_reader().into_reader().bytes.len(), 1);
+
+            // Writer errors get reported by their kind:
+            let mut buf = [0b0; 4];
+            let mut encoder = Encoder::from_writer(EmbeddedIoWriter::new(&mut buf[..]));
+            let err = encoder.encode_value(&value).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::EmbeddedIo);
+        }
+    }
+
+    mod fixed_writers {
+        use super::*;
+
+        #[test]
+        fn slice_writer() {
+            let mut buf = [0b0; 4];
+            let mut writer = SliceWriter::new(&mut buf);
+            assert!(writer.is_empty());
+
+            assert_eq!(writer.write(&[1, 2, 3]).unwrap(), 3);
+            assert_eq!(writer.remaining(), 1);
+
+            // Writes that don't fit get rejected entirely:
+            let err = writer.write(&[4, 5]).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::BufferFull);
+            assert!(matches!(err.kind(), ErrorKind::BufferFull(3)));
+            assert_eq!(writer.as_slice(), &[1, 2, 3]);
+
+            writer.write(&[4]).unwrap();
+            assert_eq!(writer.into_slice(), &[1, 2, 3, 4]);
+        }
+
+        #[test]
+        fn array_writer() {
+            use crate::{decoder::Decoder, encoder::Encoder, value::Value};
+
+            let value: Value =
+                crate::text::from_str(r#"{"id": 7_u8, "tags": ["a", "b"]}"#).unwrap();
+
+            let mut encoder = Encoder::from_writer(ArrayWriter::<32>::new());
+            encoder.encode_value(&value).unwrap();
+            let (array, len) = encoder.into_writer().into_parts();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&array[..len]));
+            assert_eq!(decoder.decode_value().unwrap(), value);
+
+            let mut encoder = Encoder::from_writer(ArrayWriter::<8>::new());
+            let err = encoder.encode_value(&value).unwrap_err();
+            assert_eq!(err.code(), ErrorCode::BufferFull);
+
+            let mut writer = encoder.into_writer();
+            assert!(writer.len() <= 8);
+            writer.clear();
+            assert!(writer.is_empty());
+        }
+    }
+
     mod slice_reader {
         use super::*;
 
@@ -622,6 +1631,42 @@ mod test {
             );
         }
 
+        #[test]
+        fn read_owned() {
+            let slice: &[u8] = &[1, 2, 3, 4, 5];
+            let mut reader = SliceReader::new(slice);
+
+            assert_eq!(reader.read_owned(0).unwrap(), Some(vec![]));
+            assert_eq!(reader.read_owned(2).unwrap(), Some(vec![1, 2]));
+            assert_eq!(reader.read_owned(1).unwrap(), Some(vec![3]));
+            assert_eq!(reader.pos(), 3);
+
+            assert_eq!(
+                reader.read_owned(3).err().unwrap().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+        }
+
+        #[test]
+        fn skip_and_buffered() {
+            let slice: &[u8] = &[1, 2, 3, 4, 5];
+            let mut reader = SliceReader::new(slice);
+
+            assert_eq!(reader.buffered(), &[1, 2, 3, 4, 5]);
+
+            reader.skip(3).unwrap();
+            assert_eq!(reader.buffered(), &[4, 5]);
+            assert_eq!(reader.pos(), 3);
+
+            assert_eq!(
+                reader.skip(3).err().unwrap().code(),
+                ErrorCode::UnexpectedEndOfFile
+            );
+
+            reader.skip(2).unwrap();
+            assert_eq!(reader.buffered(), &[] as &[u8]);
+        }
+
         #[test]
         fn read_into() {
             let slice: &[u8] = &[1, 2, 3, 4, 5];
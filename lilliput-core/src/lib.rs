@@ -1,5 +1,10 @@
 //! Low-level implementation of encoding/decoding logic for lilliput format.
+//!
+//! With its default `std` feature disabled the crate is `no_std` (yet requires `alloc`),
+//! leaving out the readers and writers wrapping `std::io` types, as well as
+//! conversions from and to `std::time::SystemTime`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 
 extern crate alloc;
@@ -7,20 +12,60 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod codegen;
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub mod compression;
 pub mod config;
 pub mod decoder;
+pub mod dictionary;
+pub mod document;
 pub mod encoder;
 pub mod error;
+pub mod framing;
 pub mod header;
 pub mod io;
+#[cfg(feature = "lite")]
+pub mod lite;
 pub mod marker;
+pub mod parser;
+pub mod preamble;
+pub mod resumable;
+pub mod spec;
+pub mod text;
+pub mod timestamp;
+pub mod transcode;
+pub mod validation;
 pub mod value;
+pub mod visit;
 
 mod binary;
+mod crc32c;
+mod num;
 mod sealed;
+#[cfg(feature = "std")]
+mod utf8;
 
-#[doc(hidden)]
-pub(crate) mod num;
+pub use config::Profile;
+pub use validation::{decode_untrusted, validate};
+
+/// Numeric building blocks, for third-party encoders and decoders.
+///
+/// The traits in this module are sealed, i.e. they can be
+/// used, but not implemented outside of this crate.
+///
+/// ```
+/// use lilliput_core::numeric::{FromZigZag as _, ToZigZag as _};
+///
+/// assert_eq!((-2_i16).to_zig_zag(), 3_u16);
+/// assert_eq!(i16::from_zig_zag(3_u16), -2);
+/// ```
+pub mod numeric {
+    pub use crate::num::{
+        from_zig_zag, from_zig_zag_slice, from_zig_zag_slice_in_place, to_zig_zag,
+        to_zig_zag_slice, to_zig_zag_slice_in_place, FromZigZag, PrimZigZag, ToZigZag, TryFromInt,
+        TryIntoInt, WithBeBytes, WithPackedBeBytes,
+    };
+}
 
 /// Internal names, not for external use.
 ///
@@ -35,6 +80,7 @@ pub mod plumbing {
 /// The crates's prelude.
 pub mod prelude {
     pub use crate::{
-        config::*, decoder::*, encoder::*, error::Error, header::*, io::*, marker::*, value::*,
+        config::*, decoder::*, dictionary::Dictionary, encoder::*, error::Error, header::*, io::*,
+        marker::*, value::*,
     };
 }
@@ -0,0 +1,738 @@
+//! A minimal, allocation-free codec for scalars and flat structs.
+//!
+//! The types in this module only depend on `core`, never allocate and
+//! operate on caller-provided buffers, which makes them suitable for
+//! microcontrollers and other constrained peers that need to exchange
+//! messages with services using the full `Encoder`/`Decoder`.
+//!
+//! Values produced by `LiteEncoder` are decodable by `Decoder` and vice versa,
+//! as long as they stay within the lite codec's subset of the format:
+//!
+//! - integers of up to 64 bits,
+//! - strings and byte arrays (borrowed from the input when decoding),
+//! - native-width floats (`f32` and `f64`, packed floats are not supported),
+//! - bools, units and nulls,
+//! - sequence and map headers, followed by their items/entries.
+//!
+//! A flat struct is encoded as a map of its fields, keyed by their names:
+//!
+//! ```
+//! use lilliput_core::lite::{LiteDecoder, LiteEncoder};
+//!
+//! let mut buf = [0_u8; 32];
+//!
+//! let mut encoder = LiteEncoder::new(&mut buf);
+//! encoder.encode_map_len(2).unwrap();
+//! encoder.encode_str("id").unwrap();
+//! encoder.encode_u64(1234).unwrap();
+//! encoder.encode_str("temp").unwrap();
+//! encoder.encode_f32(21.5).unwrap();
+//! let encoded = encoder.into_bytes();
+//!
+//! let mut decoder = LiteDecoder::new(encoded);
+//! assert_eq!(decoder.decode_map_len().unwrap(), 2);
+//! assert_eq!(decoder.decode_str().unwrap(), "id");
+//! assert_eq!(decoder.decode_u64().unwrap(), 1234);
+//! assert_eq!(decoder.decode_str().unwrap(), "temp");
+//! assert_eq!(decoder.decode_f32().unwrap(), 21.5);
+//! assert!(decoder.is_empty());
+//! ```
+
+use core::fmt;
+
+use crate::{
+    header::{
+        BoolHeader, BytesHeader, FloatHeader, IntHeader, MapHeader, NullHeader, SeqHeader,
+        StringHeader, UnitHeader,
+    },
+    marker::Marker,
+};
+
+/// An error produced by `LiteEncoder` or `LiteDecoder`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum LiteError {
+    /// The buffer has no room left for the value to be encoded.
+    BufferFull,
+    /// The input ended in the middle of the value at `pos`.
+    UnexpectedEnd {
+        /// The position of the value's header.
+        pos: usize,
+    },
+    /// The value at `pos` is of a different type than requested.
+    InvalidType {
+        /// The position of the value's header.
+        pos: usize,
+    },
+    /// The value at `pos` does not fit into the requested type.
+    OutOfRange {
+        /// The position of the value's header.
+        pos: usize,
+    },
+    /// The string at `pos` is not valid UTF-8.
+    InvalidUtf8 {
+        /// The position of the value's header.
+        pos: usize,
+    },
+    /// The value at `pos` uses a representation outside of the lite codec's subset.
+    Unsupported {
+        /// The position of the value's header.
+        pos: usize,
+    },
+}
+
+impl fmt::Display for LiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferFull => write!(f, "buffer full"),
+            Self::UnexpectedEnd { pos } => write!(f, "unexpected end of input at {pos}"),
+            Self::InvalidType { pos } => write!(f, "invalid type at {pos}"),
+            Self::OutOfRange { pos } => write!(f, "number out of range at {pos}"),
+            Self::InvalidUtf8 { pos } => write!(f, "invalid UTF-8 at {pos}"),
+            Self::Unsupported { pos } => write!(f, "unsupported representation at {pos}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LiteError {}
+
+/// A `Result` alias for the lite codec.
+pub type LiteResult<T> = core::result::Result<T, LiteError>;
+
+// MARK: - Encoder
+
+/// An encoder writing into a fixed-size buffer.
+///
+/// Each value is written as a whole, or not at all: if a value does not fit
+/// into the remaining buffer `LiteError::BufferFull` is returned and the
+/// encoder's position is left unchanged.
+#[derive(Debug)]
+pub struct LiteEncoder<'b> {
+    buf: &'b mut [u8],
+    pos: usize,
+}
+
+impl<'b> LiteEncoder<'b> {
+    /// Creates an encoder, writing into `buf`.
+    pub fn new(buf: &'b mut [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the bytes written so far.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.pos]
+    }
+
+    /// Consumes the encoder, returning the bytes written.
+    pub fn into_bytes(self) -> &'b [u8] {
+        let Self { buf, pos } = self;
+        &buf[..pos]
+    }
+
+    /// Encodes an unsigned integer.
+    pub fn encode_u64(&mut self, value: u64) -> LiteResult<()> {
+        self.encode_int(value, 0b0)
+    }
+
+    /// Encodes a signed integer.
+    pub fn encode_i64(&mut self, value: i64) -> LiteResult<()> {
+        let zig_zag = ((value << 1) ^ (value >> 63)) as u64;
+        self.encode_int(zig_zag, IntHeader::SIGNEDNESS_BIT)
+    }
+
+    /// Encodes a 32-bit float.
+    pub fn encode_f32(&mut self, value: f32) -> LiteResult<()> {
+        self.encode_float(&value.to_be_bytes())
+    }
+
+    /// Encodes a 64-bit float.
+    pub fn encode_f64(&mut self, value: f64) -> LiteResult<()> {
+        self.encode_float(&value.to_be_bytes())
+    }
+
+    /// Encodes a string.
+    pub fn encode_str(&mut self, value: &str) -> LiteResult<()> {
+        let len = value.len();
+        let mut header = [0_u8; 9];
+
+        let header_len = if len <= StringHeader::COMPACT_MAX_LEN as usize {
+            header[0] = StringHeader::TYPE_BITS | StringHeader::COMPACT_VARIANT_BIT | len as u8;
+            1
+        } else {
+            let width = write_be_bytes(len as u64, &mut header[1..]);
+            header[0] = StringHeader::TYPE_BITS | (width as u8 - 1);
+            1 + width
+        };
+
+        self.write(&header[..header_len], value.as_bytes())
+    }
+
+    /// Encodes a byte array.
+    pub fn encode_bytes(&mut self, value: &[u8]) -> LiteResult<()> {
+        let mut header = [0_u8; 9];
+
+        let width = write_be_bytes(value.len() as u64, &mut header[1..]);
+        // Byte array lengths are 1, 2, 4 or 8 bytes wide:
+        let exponent = width.next_power_of_two().trailing_zeros() as usize;
+        let padded = 1 << exponent;
+        header.copy_within(1..1 + width, 1 + padded - width);
+        header[1..1 + padded - width].fill(0);
+        header[0] = BytesHeader::TYPE_BITS | exponent as u8;
+
+        self.write(&header[..1 + padded], value)
+    }
+
+    /// Encodes a bool.
+    pub fn encode_bool(&mut self, value: bool) -> LiteResult<()> {
+        self.write(&[BoolHeader::TYPE_BITS | value as u8], &[])
+    }
+
+    /// Encodes a unit value.
+    pub fn encode_unit(&mut self) -> LiteResult<()> {
+        self.write(&[UnitHeader::TYPE_BITS], &[])
+    }
+
+    /// Encodes a null value.
+    pub fn encode_null(&mut self) -> LiteResult<()> {
+        self.write(&[NullHeader::TYPE_BITS], &[])
+    }
+
+    /// Encodes the header of a sequence of `len` items.
+    ///
+    /// The sequence's items are to be encoded right after.
+    pub fn encode_seq_len(&mut self, len: usize) -> LiteResult<()> {
+        self.encode_container_len(
+            len,
+            SeqHeader::TYPE_BITS,
+            SeqHeader::COMPACT_VARIANT_BIT,
+            SeqHeader::COMPACT_MAX_LEN,
+        )
+    }
+
+    /// Encodes the header of a map of `len` entries.
+    ///
+    /// The map's keys and values are to be encoded right after, alternatingly.
+    pub fn encode_map_len(&mut self, len: usize) -> LiteResult<()> {
+        self.encode_container_len(
+            len,
+            MapHeader::TYPE_BITS,
+            MapHeader::COMPACT_VARIANT_BIT,
+            MapHeader::COMPACT_MAX_LEN,
+        )
+    }
+
+    // MARK: - Private
+
+    fn encode_int(&mut self, bits: u64, signedness: u8) -> LiteResult<()> {
+        let mut header = [0_u8; 9];
+
+        let header_len = if bits <= IntHeader::MAX_COMPACT_VALUE as u64 {
+            header[0] =
+                IntHeader::TYPE_BITS | IntHeader::COMPACT_VARIANT_BIT | signedness | bits as u8;
+            1
+        } else {
+            let width = write_be_bytes(bits, &mut header[1..]);
+            header[0] = IntHeader::TYPE_BITS | signedness | (width as u8 - 1);
+            1 + width
+        };
+
+        self.write(&header[..header_len], &[])
+    }
+
+    fn encode_float(&mut self, bytes: &[u8]) -> LiteResult<()> {
+        let byte = FloatHeader::TYPE_BITS | (bytes.len() as u8 - 1);
+
+        self.write(&[byte], bytes)
+    }
+
+    fn encode_container_len(
+        &mut self,
+        len: usize,
+        type_bits: u8,
+        compact_bit: u8,
+        compact_max_len: u8,
+    ) -> LiteResult<()> {
+        let mut header = [0_u8; 9];
+
+        let header_len = if len <= compact_max_len as usize {
+            header[0] = type_bits | compact_bit | len as u8;
+            1
+        } else {
+            let width = write_be_bytes(len as u64, &mut header[1..]);
+            header[0] = type_bits | (width as u8 - 1);
+            1 + width
+        };
+
+        self.write(&header[..header_len], &[])
+    }
+
+    fn write(&mut self, header: &[u8], body: &[u8]) -> LiteResult<()> {
+        let end = self.pos + header.len() + body.len();
+
+        if end > self.buf.len() {
+            return Err(LiteError::BufferFull);
+        }
+
+        let (header_buf, body_buf) = self.buf[self.pos..end].split_at_mut(header.len());
+        header_buf.copy_from_slice(header);
+        body_buf.copy_from_slice(body);
+        self.pos = end;
+
+        Ok(())
+    }
+}
+
+// MARK: - Decoder
+
+/// A decoder reading from a byte slice.
+///
+/// If decoding a value fails the decoder's position is left unchanged.
+#[derive(Clone, Debug)]
+pub struct LiteDecoder<'b> {
+    buf: &'b [u8],
+    pos: usize,
+}
+
+impl<'b> LiteDecoder<'b> {
+    /// Creates a decoder, reading from `buf`.
+    pub fn new(buf: &'b [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns `true` if all of the input has been read.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+
+    /// Returns the type of the next value, without consuming it.
+    pub fn peek_marker(&self) -> LiteResult<Marker> {
+        self.peek_byte().map(Marker::detect)
+    }
+
+    /// Decodes an integer as an unsigned 64-bit integer.
+    pub fn decode_u64(&mut self) -> LiteResult<u64> {
+        self.transaction(|decoder, pos| match decoder.pull_int()? {
+            (false, bits) => u64::try_from(bits).map_err(|_| LiteError::OutOfRange { pos }),
+            (true, _) => Err(LiteError::OutOfRange { pos }),
+        })
+    }
+
+    /// Decodes an integer as a signed 64-bit integer.
+    pub fn decode_i64(&mut self) -> LiteResult<i64> {
+        self.transaction(|decoder, pos| {
+            let value = match decoder.pull_int()? {
+                (false, bits) => i64::try_from(bits).ok(),
+                (true, zig_zag) => u64::try_from(zig_zag)
+                    .ok()
+                    .map(|zig_zag| ((zig_zag >> 1) as i64) ^ -((zig_zag & 0b1) as i64)),
+            };
+
+            value.ok_or(LiteError::OutOfRange { pos })
+        })
+    }
+
+    /// Decodes a 32-bit float.
+    pub fn decode_f32(&mut self) -> LiteResult<f32> {
+        self.transaction(|decoder, pos| {
+            let bytes = decoder.pull_float_bytes(4)?;
+            let bytes: [u8; 4] = bytes
+                .try_into()
+                .map_err(|_| LiteError::OutOfRange { pos })?;
+            Ok(f32::from_be_bytes(bytes))
+        })
+    }
+
+    /// Decodes a float as a 64-bit float.
+    pub fn decode_f64(&mut self) -> LiteResult<f64> {
+        self.transaction(|decoder, _| {
+            let bytes = decoder.pull_float_bytes(8)?;
+            Ok(match *bytes {
+                [a, b, c, d] => f32::from_be_bytes([a, b, c, d]).into(),
+                _ => f64::from_be_bytes(bytes.try_into().expect("width should be 8")),
+            })
+        })
+    }
+
+    /// Decodes a string, borrowed from the input.
+    pub fn decode_str(&mut self) -> LiteResult<&'b str> {
+        self.transaction(|decoder, pos| {
+            let byte = decoder.pull_header(Marker::String)?;
+
+            let len = if byte & StringHeader::COMPACT_VARIANT_BIT != 0b0 {
+                (byte & StringHeader::COMPACT_LEN_BITS) as usize
+            } else {
+                let width = 1 + (byte & StringHeader::EXTENDED_LEN_WIDTH_BITS);
+                decoder.pull_len(width)?
+            };
+
+            let bytes = decoder.pull_bytes(len)?;
+            core::str::from_utf8(bytes).map_err(|_| LiteError::InvalidUtf8 { pos })
+        })
+    }
+
+    /// Decodes a byte array, borrowed from the input.
+    pub fn decode_bytes(&mut self) -> LiteResult<&'b [u8]> {
+        self.transaction(|decoder, _| {
+            let byte = decoder.pull_header(Marker::Bytes)?;
+            let width = 1 << (byte & BytesHeader::LEN_WIDTH_EXPONENT_BITS);
+            let len = decoder.pull_len(width)?;
+            decoder.pull_bytes(len)
+        })
+    }
+
+    /// Decodes a bool.
+    pub fn decode_bool(&mut self) -> LiteResult<bool> {
+        self.transaction(|decoder, _| {
+            let byte = decoder.pull_header(Marker::Bool)?;
+            Ok(byte & BoolHeader::VALUE_BIT != 0b0)
+        })
+    }
+
+    /// Decodes a unit value.
+    pub fn decode_unit(&mut self) -> LiteResult<()> {
+        self.transaction(|decoder, _| decoder.pull_header(Marker::Unit).map(|_| ()))
+    }
+
+    /// Decodes a null value.
+    pub fn decode_null(&mut self) -> LiteResult<()> {
+        self.transaction(|decoder, _| decoder.pull_header(Marker::Null).map(|_| ()))
+    }
+
+    /// Decodes the header of a sequence, returning its number of items.
+    ///
+    /// Streamed sequences are not supported.
+    pub fn decode_seq_len(&mut self) -> LiteResult<usize> {
+        self.transaction(|decoder, pos| {
+            let byte = decoder.pull_header(Marker::Seq)?;
+
+            if byte & SeqHeader::COMPACT_VARIANT_BIT != 0b0 {
+                Ok((byte & SeqHeader::COMPACT_LEN_BITS) as usize)
+            } else if byte & SeqHeader::STREAMED_VARIANT_BIT != 0b0 {
+                Err(LiteError::Unsupported { pos })
+            } else {
+                decoder.pull_len(1 + (byte & SeqHeader::EXTENDED_LEN_WIDTH_BITS))
+            }
+        })
+    }
+
+    /// Decodes the header of a map, returning its number of entries.
+    pub fn decode_map_len(&mut self) -> LiteResult<usize> {
+        self.transaction(|decoder, _| {
+            let byte = decoder.pull_header(Marker::Map)?;
+
+            if byte & MapHeader::COMPACT_VARIANT_BIT != 0b0 {
+                Ok((byte & MapHeader::COMPACT_LEN_BITS) as usize)
+            } else {
+                decoder.pull_len(1 + (byte & MapHeader::EXTENDED_LEN_WIDTH_BITS))
+            }
+        })
+    }
+
+    /// Skips the next value, including any nested values.
+    ///
+    /// Unlike the `decode_*` methods this supports all of the format's
+    /// representations, which allows for skipping unknown fields.
+    pub fn skip_value(&mut self) -> LiteResult<()> {
+        self.transaction(|decoder, _| decoder.skip_value_unchecked())
+    }
+
+    // MARK: - Private
+
+    /// Runs `f`, restoring the decoder's position if it fails.
+    fn transaction<T, F>(&mut self, f: F) -> LiteResult<T>
+    where
+        F: FnOnce(&mut Self, usize) -> LiteResult<T>,
+    {
+        let pos = self.pos;
+        let result = f(self, pos);
+
+        if result.is_err() {
+            self.pos = pos;
+        }
+
+        result
+    }
+
+    fn skip_value_unchecked(&mut self) -> LiteResult<()> {
+        let pos = self.pos;
+        let byte = self.pull_byte()?;
+
+        match Marker::detect(byte) {
+            Marker::Int if byte & IntHeader::COMPACT_VARIANT_BIT != 0b0 => Ok(()),
+            Marker::Int => {
+                self.pull_bytes(1 + (byte & IntHeader::EXTENDED_WIDTH_BITS) as usize)?;
+                Ok(())
+            }
+            Marker::String => {
+                self.pos = pos;
+                self.decode_str().map(|_| ())
+            }
+            Marker::Seq if byte & SeqHeader::COMPACT_VARIANT_BIT != 0b0 => {
+                self.skip_values((byte & SeqHeader::COMPACT_LEN_BITS) as usize)
+            }
+            Marker::Seq if byte == SeqHeader::STREAM_START => {
+                while self.peek_byte()? != SeqHeader::STREAM_END {
+                    self.skip_value_unchecked()?;
+                }
+                self.pull_byte().map(|_| ())
+            }
+            Marker::Seq if byte == SeqHeader::STREAM_END => Err(LiteError::InvalidType { pos }),
+            Marker::Seq if byte & SeqHeader::STREAMED_VARIANT_BIT != 0b0 => {
+                // Length-prefixed representations (e.g. front-coded maps):
+                let len = self.decode_u64()?;
+                let len = usize::try_from(len).map_err(|_| LiteError::OutOfRange { pos })?;
+                self.pull_bytes(len).map(|_| ())
+            }
+            Marker::Seq => {
+                let len = self.pull_len(1 + (byte & SeqHeader::EXTENDED_LEN_WIDTH_BITS))?;
+                self.skip_values(len)
+            }
+            Marker::Map => {
+                self.pos = pos;
+                let len = self.decode_map_len()?;
+                self.skip_values(len.checked_mul(2).ok_or(LiteError::OutOfRange { pos })?)
+            }
+            Marker::Float => {
+                self.pull_bytes(1 + (byte & FloatHeader::VALUE_WIDTH_BITS) as usize)?;
+                Ok(())
+            }
+            Marker::Bytes => {
+                self.pos = pos;
+                self.decode_bytes().map(|_| ())
+            }
+            Marker::Bool | Marker::Unit | Marker::Null => Ok(()),
+        }
+    }
+
+    fn skip_values(&mut self, count: usize) -> LiteResult<()> {
+        for _ in 0..count {
+            self.skip_value_unchecked()?;
+        }
+
+        Ok(())
+    }
+
+    /// Pulls an integer, returning its signedness and its (possibly zig-zag encoded) bits.
+    fn pull_int(&mut self) -> LiteResult<(bool, u128)> {
+        let byte = self.pull_header(Marker::Int)?;
+        let is_signed = byte & IntHeader::SIGNEDNESS_BIT != 0b0;
+
+        let bits = if byte & IntHeader::COMPACT_VARIANT_BIT != 0b0 {
+            (byte & IntHeader::COMPACT_VALUE_BITS).into()
+        } else {
+            let width = 1 + (byte & IntHeader::EXTENDED_WIDTH_BITS) as usize;
+            read_be_bytes(self.pull_bytes(width)?)
+        };
+
+        Ok((is_signed, bits))
+    }
+
+    /// Pulls a native-width float's bytes, of at most `max_width` bytes.
+    fn pull_float_bytes(&mut self, max_width: usize) -> LiteResult<&'b [u8]> {
+        let pos = self.pos;
+        let byte = self.pull_header(Marker::Float)?;
+        let width = 1 + (byte & FloatHeader::VALUE_WIDTH_BITS) as usize;
+
+        match width {
+            4 | 8 if width <= max_width => self.pull_bytes(width),
+            4 | 8 => Err(LiteError::OutOfRange { pos }),
+            _ => Err(LiteError::Unsupported { pos }),
+        }
+    }
+
+    fn pull_len(&mut self, width: u8) -> LiteResult<usize> {
+        let pos = self.pos;
+        let len = read_be_bytes(self.pull_bytes(width.into())?);
+
+        usize::try_from(len).map_err(|_| LiteError::OutOfRange { pos })
+    }
+
+    /// Pulls a header byte, making sure it is of type `marker`.
+    fn pull_header(&mut self, marker: Marker) -> LiteResult<u8> {
+        let pos = self.pos;
+        let byte = self.pull_byte()?;
+
+        match byte {
+            MapHeader::FRONT_CODED
+            | StringHeader::DICTIONARY_REF
+            | SeqHeader::COLUMNAR
+            | SeqHeader::TABULAR
+            | SeqHeader::EXTENSION => Err(LiteError::Unsupported { pos }),
+            _ if Marker::detect(byte) == marker => Ok(byte),
+            _ => Err(LiteError::InvalidType { pos }),
+        }
+    }
+
+    fn peek_byte(&self) -> LiteResult<u8> {
+        self.buf
+            .get(self.pos)
+            .copied()
+            .ok_or(LiteError::UnexpectedEnd { pos: self.pos })
+    }
+
+    fn pull_byte(&mut self) -> LiteResult<u8> {
+        let byte = self.peek_byte()?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn pull_bytes(&mut self, len: usize) -> LiteResult<&'b [u8]> {
+        let buf: &'b [u8] = self.buf;
+        let bytes = buf
+            .get(self.pos..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or(LiteError::UnexpectedEnd { pos: self.pos })?;
+        self.pos += len;
+        Ok(bytes)
+    }
+}
+
+/// Writes the minimal big-endian bytes of `value` (at least one) to the start of `buf`,
+/// returning their number.
+fn write_be_bytes(value: u64, buf: &mut [u8]) -> usize {
+    let bytes = value.to_be_bytes();
+    let width = (8 - value.leading_zeros() as usize / 8).max(1);
+
+    buf[..width].copy_from_slice(&bytes[8 - width..]);
+
+    width
+}
+
+fn read_be_bytes(bytes: &[u8]) -> u128 {
+    bytes
+        .iter()
+        .fold(0, |value, &byte| (value << 8) | u128::from(byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+        value::{StringValue, Value},
+    };
+
+    use super::*;
+
+    fn encode_full(f: impl FnOnce(&mut Encoder<VecWriter<'_>>)) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        f(&mut Encoder::from_writer(VecWriter::new(&mut encoded)));
+        encoded
+    }
+
+    #[test]
+    fn struct_roundtrip() {
+        let mut buf = [0_u8; 64];
+        let mut encoder = LiteEncoder::new(&mut buf);
+        encoder.encode_map_len(4).unwrap();
+        encoder.encode_str("id").unwrap();
+        encoder.encode_i64(-1000).unwrap();
+        encoder.encode_str("ratio").unwrap();
+        encoder.encode_f64(0.1).unwrap();
+        encoder.encode_str("raw").unwrap();
+        encoder.encode_bytes(&[1, 2, 3]).unwrap();
+        encoder.encode_str("ok").unwrap();
+        encoder.encode_bool(true).unwrap();
+        assert_eq!(encoder.encode_bytes(&[0; 64]), Err(LiteError::BufferFull));
+        let encoded = encoder.into_bytes();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(encoded));
+        let value = decoder.decode_value().unwrap();
+        let map = value.as_map().unwrap();
+        let field = |key: &str| {
+            map.get(&Value::String(StringValue::from(key.to_owned())))
+                .unwrap()
+        };
+        assert_eq!(field("id").as_i64(), Some(-1000));
+        assert_eq!(field("ratio").as_f64(), Some(0.1));
+        assert_eq!(field("raw").as_bytes(), Some(&[1, 2, 3][..]));
+        assert_eq!(field("ok").as_bool(), Some(true));
+
+        let mut decoder = LiteDecoder::new(encoded);
+        assert_eq!(decoder.decode_map_len(), Ok(4));
+        assert_eq!(decoder.decode_str(), Ok("id"));
+        assert_eq!(decoder.decode_u64(), Err(LiteError::OutOfRange { pos: 4 }));
+        assert_eq!(decoder.decode_i64(), Ok(-1000));
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.decode_f32(), Err(LiteError::OutOfRange { pos: 13 }));
+        assert_eq!(decoder.decode_f64(), Ok(0.1));
+        assert_eq!(decoder.decode_str(), Ok("raw"));
+        decoder.skip_value().unwrap();
+        decoder.skip_value().unwrap();
+        assert_eq!(
+            decoder.decode_unit(),
+            Err(LiteError::InvalidType { pos: 34 })
+        );
+        assert_eq!(decoder.decode_bool(), Ok(true));
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn unsupported() {
+        // Packed floats:
+        let mut decoder = LiteDecoder::new(&[0x09, 0x00, 0x00]);
+        assert_eq!(decoder.decode_f64(), Err(LiteError::Unsupported { pos: 0 }));
+        decoder.skip_value().unwrap();
+
+        // Dictionary references:
+        let mut decoder = LiteDecoder::new(&[0x2B, 0xC1, 0xC0]);
+        assert_eq!(decoder.decode_str(), Err(LiteError::Unsupported { pos: 0 }));
+        decoder.skip_value().unwrap();
+        assert!(decoder.is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn ints_match_full_codec(unsigned in any::<u64>(), signed in any::<i64>()) {
+            let mut buf = [0_u8; 18];
+            let mut encoder = LiteEncoder::new(&mut buf);
+            encoder.encode_u64(unsigned).unwrap();
+            encoder.encode_i64(signed).unwrap();
+
+            let encoded = encode_full(|encoder| {
+                encoder.encode_u64(unsigned).unwrap();
+                encoder.encode_i64(signed).unwrap();
+            });
+            prop_assert_eq!(encoder.as_bytes(), &encoded[..]);
+
+            let mut decoder = LiteDecoder::new(&encoded);
+            prop_assert_eq!(decoder.decode_u64(), Ok(unsigned));
+            prop_assert_eq!(decoder.decode_i64(), Ok(signed));
+        }
+
+        #[test]
+        fn strings_and_bytes_roundtrip(string in ".{0,300}", bytes in prop::collection::vec(any::<u8>(), 0..300)) {
+            let mut buf = [0_u8; 2048];
+            let mut encoder = LiteEncoder::new(&mut buf);
+            encoder.encode_str(&string).unwrap();
+            encoder.encode_bytes(&bytes).unwrap();
+            let encoded = encoder.into_bytes();
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(encoded));
+            prop_assert_eq!(decoder.decode_string().unwrap(), &string[..]);
+            prop_assert_eq!(decoder.decode_bytes_buf().unwrap(), &bytes[..]);
+
+            let encoded = encode_full(|encoder| {
+                encoder.encode_str(&string).unwrap();
+                encoder.encode_bytes(&bytes).unwrap();
+            });
+            let mut decoder = LiteDecoder::new(&encoded);
+            prop_assert_eq!(decoder.decode_str(), Ok(&string[..]));
+            prop_assert_eq!(decoder.decode_bytes(), Ok(&bytes[..]));
+        }
+    }
+}
@@ -32,8 +32,8 @@ pub enum Marker {
     Null = 0b00000000,
 }
 
-impl std::fmt::Display for Marker {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Marker {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Int => write!(f, "integer"),
             Self::String => write!(f, "string"),
@@ -50,8 +50,8 @@ impl std::fmt::Display for Marker {
 
 #[cfg(feature = "serde")]
 impl serde::de::Expected for Marker {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        std::fmt::Display::fmt(self, f)
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
     }
 }
 
@@ -74,7 +74,7 @@ impl Marker {
         //     8 => Self::Reserved,
         // }
         // ```
-        unsafe { std::mem::transmute_copy(&Self::repr_for(byte)) }
+        unsafe { core::mem::transmute_copy(&Self::repr_for(byte)) }
     }
 
     /// Returns a given mask's bit-mask.
@@ -118,7 +118,7 @@ impl Marker {
 
 #[cfg(test)]
 mod tests {
-    use std::ops::RangeInclusive;
+    use core::ops::RangeInclusive;
 
     use test_log::test;
 
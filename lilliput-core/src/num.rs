@@ -9,4 +9,7 @@ mod zigzag;
 
 pub use self::be_bytes::{WithBeBytes, WithPackedBeBytes, WithValidatedPackedBeBytes};
 pub use self::int_cast::{TryFromInt, TryIntoInt};
-pub use self::zigzag::{FromZigZag, ToZigZag};
+pub use self::zigzag::{
+    from_zig_zag, from_zig_zag_slice, from_zig_zag_slice_in_place, to_zig_zag, to_zig_zag_slice,
+    to_zig_zag_slice_in_place, FromZigZag, PrimZigZag, ToZigZag,
+};
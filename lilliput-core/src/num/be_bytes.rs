@@ -1,20 +1,29 @@
-use crate::config::PackingMode;
+use crate::{config::PackingMode, sealed::Sealed};
 
-pub trait WithBeBytes {
+/// Access to a number's big-endian byte representation.
+pub trait WithBeBytes: Sealed {
+    /// Calls `f` with the number's full-width big-endian bytes.
     fn with_be_bytes<T, F>(&self, f: F) -> T
     where
         F: FnOnce(&[u8]) -> T;
 }
 
+/// Access to a number's packed big-endian byte representation,
+/// as written by the encoder for a given `PackingMode`.
 pub trait WithPackedBeBytes: WithBeBytes {
+    /// Calls `f` with the number's big-endian bytes, packed to
+    /// the narrowest native width the number fits into losslessly.
     fn with_native_packed_be_bytes<T, F>(&self, f: F) -> T
     where
         F: FnOnce(&[u8]) -> T;
 
+    /// Calls `f` with the number's big-endian bytes, packed to
+    /// the fewest bytes the number fits into losslessly.
     fn with_optimal_packed_be_bytes<T, F>(&self, f: F) -> T
     where
         F: FnOnce(&[u8]) -> T;
 
+    /// Calls `f` with the number's big-endian bytes, packed according to `packing_mode`.
     #[inline]
     fn with_packed_be_bytes<T, F>(&self, packing_mode: PackingMode, f: F) -> T
     where
@@ -28,17 +37,26 @@ pub trait WithPackedBeBytes: WithBeBytes {
     }
 }
 
+/// Access to a number's packed big-endian byte representation,
+/// with lossy packings being subject to validation.
 pub trait WithValidatedPackedBeBytes: WithBeBytes {
+    /// The validator deciding whether a lossy packing is acceptable.
     type Validator;
 
+    /// Like `WithPackedBeBytes::with_native_packed_be_bytes()`,
+    /// but only accepting packings approved by `validator`.
     fn with_validated_native_packed_be_bytes<T, F>(&self, validator: &Self::Validator, f: F) -> T
     where
         F: FnOnce(&[u8]) -> T;
 
+    /// Like `WithPackedBeBytes::with_optimal_packed_be_bytes()`,
+    /// but only accepting packings approved by `validator`.
     fn with_validated_optimal_packed_be_bytes<T, F>(&self, validator: &Self::Validator, f: F) -> T
     where
         F: FnOnce(&[u8]) -> T;
 
+    /// Calls `f` with the number's big-endian bytes, packed according
+    /// to `packing_mode`, only accepting packings approved by `validator`.
     #[inline]
     fn with_validated_packed_be_bytes<T, F>(
         &self,
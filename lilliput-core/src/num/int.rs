@@ -30,16 +30,18 @@ macro_rules! impl_try_from_int {
     };
 }
 
-impl_try_from_int!(i8, infallible: [i8], fallible: [i16, i32, i64, u8, u16, u32, u64]);
-impl_try_from_int!(i16, infallible: [i8, i16, u8], fallible: [i32, i64, u16, u32, u64]);
-impl_try_from_int!(i32, infallible: [i8, i16, i32, u8, u16], fallible: [i64, u32, u64]);
-impl_try_from_int!(i64, infallible: [i8, i16, i32, i64, u8, u16, u32], fallible: [u64]);
-impl_try_from_int!(isize, infallible: [i8, i16, u8], fallible: [i32, i64, u16, u32, u64]);
-impl_try_from_int!(u8, infallible: [u8], fallible: [u16, u32, u64, i8, i16, i32, i64]);
-impl_try_from_int!(u16, infallible: [u8, u16], fallible: [u32, u64, i8, i16, i32, i64]);
-impl_try_from_int!(u32, infallible: [u8, u16, u32], fallible: [u64, i8, i16, i32, i64]);
-impl_try_from_int!(u64, infallible: [u8, u16, u32, u64], fallible: [i8, i16, i32, i64]);
-impl_try_from_int!(usize, infallible: [u8, u16, usize], fallible: [u32, u64, i8, i16, i32, i64]);
+impl_try_from_int!(i8, infallible: [i8], fallible: [i16, i32, i64, i128, u8, u16, u32, u64, u128]);
+impl_try_from_int!(i16, infallible: [i8, i16, u8], fallible: [i32, i64, i128, u16, u32, u64, u128]);
+impl_try_from_int!(i32, infallible: [i8, i16, i32, u8, u16], fallible: [i64, i128, u32, u64, u128]);
+impl_try_from_int!(i64, infallible: [i8, i16, i32, i64, u8, u16, u32], fallible: [i128, u64, u128]);
+impl_try_from_int!(i128, infallible: [i8, i16, i32, i64, i128, u8, u16, u32, u64], fallible: [u128]);
+impl_try_from_int!(isize, infallible: [i8, i16, u8], fallible: [i32, i64, i128, u16, u32, u64, u128]);
+impl_try_from_int!(u8, infallible: [u8], fallible: [u16, u32, u64, u128, i8, i16, i32, i64, i128]);
+impl_try_from_int!(u16, infallible: [u8, u16], fallible: [u32, u64, u128, i8, i16, i32, i64, i128]);
+impl_try_from_int!(u32, infallible: [u8, u16, u32], fallible: [u64, u128, i8, i16, i32, i64, i128]);
+impl_try_from_int!(u64, infallible: [u8, u16, u32, u64], fallible: [u128, i8, i16, i32, i64, i128]);
+impl_try_from_int!(u128, infallible: [u8, u16, u32, u64, u128], fallible: [i8, i16, i32, i64, i128]);
+impl_try_from_int!(usize, infallible: [u8, u16, usize], fallible: [u32, u64, u128, i8, i16, i32, i64, i128]);
 
 macro_rules! impl_with_packed_be_bytes_for_unsigned_int {
     ($t:ty) => {
@@ -83,7 +85,17 @@ macro_rules! impl_with_packed_be_bytes_for_unsigned_int {
                         0
                     };
 
-                    (overflows_u32 << 2) + (overflows_u16 << 1) + overflows_u8 + 1
+                    let overflows_u64 = if u64::BITS < Self::BITS {
+                        (*self > u64::MAX as Self) as u8
+                    } else {
+                        0
+                    };
+
+                    (overflows_u64 << 3)
+                        + (overflows_u32 << 2)
+                        + (overflows_u16 << 1)
+                        + overflows_u8
+                        + 1
                 };
 
                 let bytes: &[u8] = &be_bytes[(be_bytes.len() - (width as usize))..];
@@ -114,6 +126,7 @@ impl_with_packed_be_bytes_for_unsigned_int!(u8);
 impl_with_packed_be_bytes_for_unsigned_int!(u16);
 impl_with_packed_be_bytes_for_unsigned_int!(u32);
 impl_with_packed_be_bytes_for_unsigned_int!(u64);
+impl_with_packed_be_bytes_for_unsigned_int!(u128);
 impl_with_packed_be_bytes_for_unsigned_int!(usize);
 
 macro_rules! impl_with_packed_be_bytes_for_signed_int {
@@ -158,4 +171,5 @@ impl_with_packed_be_bytes_for_signed_int!(i8);
 impl_with_packed_be_bytes_for_signed_int!(i16);
 impl_with_packed_be_bytes_for_signed_int!(i32);
 impl_with_packed_be_bytes_for_signed_int!(i64);
+impl_with_packed_be_bytes_for_signed_int!(i128);
 impl_with_packed_be_bytes_for_signed_int!(isize);
@@ -1,16 +1,24 @@
 use num_traits::PrimInt;
 
-pub trait TryFromInt<T>: PrimInt
+use crate::sealed::Sealed;
+
+/// Fallible conversion between primitive integer types,
+/// implemented uniformly for all pairs of them (including `usize`/`isize`).
+pub trait TryFromInt<T>: PrimInt + Sealed
 where
     T: PrimInt,
 {
+    /// Converts `int` to `Self`, failing if its value is out of range.
     fn try_from_int(int: T) -> Result<Self, core::num::TryFromIntError>;
 }
 
+/// Fallible conversion between primitive integer types,
+/// the reciprocal of `TryFromInt`.
 pub trait TryIntoInt<T>: PrimInt
 where
     T: PrimInt,
 {
+    /// Converts `self` to `T`, failing if its value is out of range.
     fn try_into_int(self) -> Result<T, core::num::TryFromIntError>;
 }
 
@@ -3,20 +3,147 @@ use crate::{
     value::{SignedIntValue, UnsignedIntValue},
 };
 
+/// Conversion of signed integers into their zig-zag encoding, as used on the wire.
+///
+/// Zig-zag encoding maps signed integers to unsigned ones of the same width,
+/// such that values of small magnitude end up with small encodings:
+/// `0 => 0`, `-1 => 1`, `1 => 2`, `-2 => 3`, …
 pub trait ToZigZag: Sized + Sealed {
+    /// The zig-zag encoding's unsigned type.
     type ZigZag: Sized;
 
+    /// Returns `self`'s zig-zag encoding.
     fn to_zig_zag(self) -> Self::ZigZag;
 }
 
+/// Conversion of zig-zag encoded integers back into signed integers,
+/// the inverse of `ToZigZag`.
 pub trait FromZigZag: Sized + Sealed {
+    /// The zig-zag encoding's unsigned type.
     type ZigZag: Sized;
 
+    /// Returns the signed integer for `zig_zag`.
     fn from_zig_zag(zig_zag: Self::ZigZag) -> Self;
 }
 
+/// A primitive signed integer type, which shares its memory layout with its
+/// zig-zag encoding's unsigned type, allowing for in-place conversions.
+pub trait PrimZigZag:
+    Copy + ToZigZag<ZigZag = Self::Unsigned> + FromZigZag<ZigZag = Self::Unsigned>
+{
+    /// The unsigned integer type of the same width.
+    type Unsigned: Copy;
+}
+
+/// Returns `value`'s zig-zag encoding.
+#[inline]
+pub fn to_zig_zag<T>(value: T) -> T::ZigZag
+where
+    T: ToZigZag,
+{
+    value.to_zig_zag()
+}
+
+/// Returns the signed integer for the zig-zag encoded `zig_zag`.
+#[inline]
+pub fn from_zig_zag<T>(zig_zag: T::ZigZag) -> T
+where
+    T: FromZigZag,
+{
+    T::from_zig_zag(zig_zag)
+}
+
+/// Writes the zig-zag encodings of `values` into `zig_zags`.
+///
+/// The conversion is branch-free, allowing the compiler to auto-vectorize it.
+///
+/// # Panics
+///
+/// Panics if `values` and `zig_zags` differ in length.
+pub fn to_zig_zag_slice<T>(values: &[T], zig_zags: &mut [T::ZigZag])
+where
+    T: Copy + ToZigZag,
+{
+    assert_eq!(values.len(), zig_zags.len(), "slice lengths must match");
+
+    for (zig_zag, value) in zig_zags.iter_mut().zip(values) {
+        *zig_zag = value.to_zig_zag();
+    }
+}
+
+/// Writes the signed integers for the zig-zag encoded `zig_zags` into `values`.
+///
+/// The conversion is branch-free, allowing the compiler to auto-vectorize it.
+///
+/// # Panics
+///
+/// Panics if `zig_zags` and `values` differ in length.
+pub fn from_zig_zag_slice<T>(zig_zags: &[T::ZigZag], values: &mut [T])
+where
+    T: FromZigZag,
+    T::ZigZag: Copy,
+{
+    assert_eq!(zig_zags.len(), values.len(), "slice lengths must match");
+
+    for (value, zig_zag) in values.iter_mut().zip(zig_zags) {
+        *value = T::from_zig_zag(*zig_zag);
+    }
+}
+
+/// Replaces `values` with their zig-zag encodings, in place,
+/// returning the slice re-interpreted as their unsigned type.
+///
+/// Useful for preparing delta-encoded sequences for packing, without allocating.
+pub fn to_zig_zag_slice_in_place<T>(values: &mut [T]) -> &mut [T::Unsigned]
+where
+    T: PrimZigZag,
+{
+    let len = values.len();
+    let ptr = values.as_mut_ptr();
+
+    for index in 0..len {
+        // Safety: `index` is within bounds, and `PrimZigZag` is only implemented
+        // for signed integer types whose unsigned counterpart shares their layout:
+        unsafe {
+            let value = ptr.add(index).read();
+            ptr.cast::<T::Unsigned>()
+                .add(index)
+                .write(value.to_zig_zag());
+        }
+    }
+
+    // Safety: see above, with all elements having been converted:
+    unsafe { core::slice::from_raw_parts_mut(ptr.cast::<T::Unsigned>(), len) }
+}
+
+/// Replaces the zig-zag encoded `zig_zags` with their signed integers, in place,
+/// returning the slice re-interpreted as the signed type.
+pub fn from_zig_zag_slice_in_place<T>(zig_zags: &mut [T::Unsigned]) -> &mut [T]
+where
+    T: PrimZigZag,
+{
+    let len = zig_zags.len();
+    let ptr = zig_zags.as_mut_ptr();
+
+    for index in 0..len {
+        // Safety: `index` is within bounds, and `PrimZigZag` is only implemented
+        // for signed integer types whose unsigned counterpart shares their layout:
+        unsafe {
+            let zig_zag = ptr.add(index).read();
+            ptr.cast::<T>().add(index).write(T::from_zig_zag(zig_zag));
+        }
+    }
+
+    // Safety: see above, with all elements having been converted:
+    unsafe { core::slice::from_raw_parts_mut(ptr.cast::<T>(), len) }
+}
+
 macro_rules! impl_zig_zag {
     (signed: $s:ty, unsigned: $u:ty) => {
+        impl PrimZigZag for $s {
+            type Unsigned = $u;
+        }
+
         impl ToZigZag for $s {
             type ZigZag = $u;
 
@@ -39,6 +166,7 @@ impl_zig_zag!(signed: i8, unsigned: u8);
 impl_zig_zag!(signed: i16, unsigned: u16);
 impl_zig_zag!(signed: i32, unsigned: u32);
 impl_zig_zag!(signed: i64, unsigned: u64);
+impl_zig_zag!(signed: i128, unsigned: u128);
 impl_zig_zag!(signed: isize, unsigned: usize);
 
 impl ToZigZag for SignedIntValue {
@@ -50,6 +178,7 @@ impl ToZigZag for SignedIntValue {
             Self::I16(signed) => UnsignedIntValue::U16(signed.to_zig_zag()),
             Self::I32(signed) => UnsignedIntValue::U32(signed.to_zig_zag()),
             Self::I64(signed) => UnsignedIntValue::U64(signed.to_zig_zag()),
+            Self::I128(signed) => UnsignedIntValue::U128(signed.to_zig_zag()),
         }
     }
 }
@@ -63,6 +192,7 @@ impl FromZigZag for SignedIntValue {
             UnsignedIntValue::U16(unsigned) => Self::I16(i16::from_zig_zag(unsigned)),
             UnsignedIntValue::U32(unsigned) => Self::I32(i32::from_zig_zag(unsigned)),
             UnsignedIntValue::U64(unsigned) => Self::I64(i64::from_zig_zag(unsigned)),
+            UnsignedIntValue::U128(unsigned) => Self::I128(i128::from_zig_zag(unsigned)),
         }
     }
 }
@@ -82,5 +212,50 @@ mod tests {
 
             prop_assert_eq!(&before, &after);
         }
+
+        #[test]
+        fn zig_zag_slice_roundtrip(before in proptest::collection::vec(any::<i64>(), 0..100)) {
+            let mut zig_zags = vec![0_u64; before.len()];
+            to_zig_zag_slice(&before, &mut zig_zags);
+
+            for (value, zig_zag) in before.iter().zip(&zig_zags) {
+                prop_assert_eq!(value.to_zig_zag(), *zig_zag);
+            }
+
+            let mut after = vec![0_i64; before.len()];
+            from_zig_zag_slice(&zig_zags, &mut after);
+
+            prop_assert_eq!(&before, &after);
+        }
+
+        #[test]
+        fn zig_zag_slice_in_place_roundtrip(before in proptest::collection::vec(any::<i16>(), 0..100)) {
+            let mut values = before.clone();
+
+            let zig_zags = to_zig_zag_slice_in_place(&mut values);
+            for (value, zig_zag) in before.iter().zip(zig_zags.iter()) {
+                prop_assert_eq!(value.to_zig_zag(), *zig_zag);
+            }
+
+            let after = from_zig_zag_slice_in_place::<i16>(zig_zags);
+            prop_assert_eq!(&before[..], &after[..]);
+        }
+    }
+
+    #[test]
+    fn zig_zag() {
+        assert_eq!(to_zig_zag(0_i32), 0_u32);
+        assert_eq!(to_zig_zag(-1_i32), 1_u32);
+        assert_eq!(to_zig_zag(1_i32), 2_u32);
+        assert_eq!(to_zig_zag(i32::MIN), u32::MAX);
+        assert_eq!(from_zig_zag::<i32>(u32::MAX - 1), i32::MAX);
+        assert_eq!(to_zig_zag(i128::MIN), u128::MAX);
+        assert_eq!(from_zig_zag::<i128>(u128::MAX - 1), i128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice lengths must match")]
+    fn zig_zag_slice_length_mismatch() {
+        to_zig_zag_slice(&[1_i8, 2], &mut [0_u8]);
     }
 }
@@ -0,0 +1,453 @@
+//! Incremental, event-based parsing of encoded values.
+//!
+//! A `Parser` gets fed bytes received in arbitrary chunks, turning them
+//! into a flat stream of `Event`s (e.g. the start of a map, followed by its
+//! keys and values, followed by its end), without ever decoding a container
+//! in full, which allows for processing documents much larger than memory.
+//!
+//! ```
+//! use lilliput_core::{
+//!     encoder::Encoder,
+//!     io::VecWriter,
+//!     parser::{Event, Parser},
+//!     value::Value,
+//! };
+//!
+//! let value: Value = lilliput_core::text::from_str(r#"{"id": 7_u8, "tags": ["a"]}"#).unwrap();
+//!
+//! let mut encoded = Vec::new();
+//! Encoder::from_writer(VecWriter::new(&mut encoded)).encode_value(&value).unwrap();
+//!
+//! let mut parser = Parser::default();
+//!
+//! // Events get parsed as soon as their bytes have been received:
+//! parser.extend_from_slice(&encoded[..2]);
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::MapStart(2)));
+//! assert_eq!(parser.next_event().unwrap(), None);
+//!
+//! parser.extend_from_slice(&encoded[2..]);
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::Str(b"id")));
+//! assert!(matches!(parser.next_event().unwrap(), Some(Event::Int(_))));
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::Str(b"tags")));
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::SeqStart(Some(1))));
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::Str(b"a")));
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::SeqEnd));
+//! assert_eq!(parser.next_event().unwrap(), Some(Event::MapEnd));
+//! assert_eq!(parser.next_event().unwrap(), None);
+//! ```
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{
+    config::DecoderConfig,
+    decoder::Decoder,
+    dictionary::Dictionary,
+    error::{Error, ErrorCode, Result},
+    io::SliceReader,
+    value::{FloatValue, IntValue, Value, ValueKind},
+};
+
+/// An event of a `Parser`.
+///
+/// Containers get reported by their start, followed by the events of
+/// their items (with map entries as alternating keys and values),
+/// followed by their end.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Event<'a> {
+    /// An integer value.
+    Int(IntValue),
+    /// A string value's bytes, without them having been validated as UTF-8.
+    Str(&'a [u8]),
+    /// A float value.
+    Float(FloatValue),
+    /// A byte sequence value.
+    Bytes(&'a [u8]),
+    /// A boolean value.
+    Bool(bool),
+    /// A unit value.
+    Unit,
+    /// A null value.
+    Null,
+    /// The start of a sequence value,
+    /// with a length of `None` for streamed sequences.
+    SeqStart(Option<usize>),
+    /// The end of a sequence value.
+    SeqEnd,
+    /// The start of a map value, with its number of entries.
+    MapStart(usize),
+    /// The end of a map value.
+    MapEnd,
+    /// A value whose encoding doesn't allow for it to be parsed incrementally
+    /// (i.e. extension and unknown values, columnar and tabular sequences and front-coded maps),
+    /// decoded in full, once it has been received completely.
+    Value(Value),
+}
+
+/// A container entered by a `Parser`, with the number of items left to parse.
+#[derive(Copy, Clone, Debug)]
+enum Container {
+    Seq(Option<usize>),
+    Map(usize),
+}
+
+/// An event, referring to its bytes by their range.
+enum Parsed {
+    Int(IntValue),
+    Str(Range<usize>),
+    CopiedStr,
+    Float(FloatValue),
+    Bytes(Range<usize>),
+    Bool(bool),
+    Unit,
+    Null,
+    SeqStart(Option<usize>),
+    SeqEnd,
+    MapStart(usize),
+    Value(Value),
+}
+
+/// An incremental parser of encoded values, from bytes received in arbitrary chunks.
+///
+/// Bytes get provided via `extend_from_slice()` (or `read_from()`), after which
+/// `next_event()` returns the events they complete, followed by `None` once more
+/// bytes are needed. Consecutive top-level values get parsed one after another.
+#[derive(Debug, Default)]
+pub struct Parser {
+    buffer: Vec<u8>,
+    start: usize,
+    pos: usize,
+    stack: Vec<Container>,
+    scratch: Vec<u8>,
+    config: DecoderConfig,
+    dictionary: Option<Dictionary>,
+}
+
+impl Parser {
+    /// Creates a parser, configured by `config`.
+    pub fn new(config: DecoderConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Attaches `dictionary`, for resolving references to dictionary strings, returning `self`.
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Appends received `bytes` to the parser's buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.compact();
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Reads a chunk of bytes from `reader` into the parser's buffer,
+    /// returning the number of bytes read, with `0` indicating its end.
+    #[cfg(feature = "std")]
+    pub fn read_from<R>(&mut self, reader: &mut R) -> Result<usize>
+    where
+        R: std::io::Read,
+    {
+        const CHUNK_LEN: usize = 8 * 1024;
+
+        self.compact();
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_LEN, 0b0);
+
+        let result = reader.read(&mut self.buffer[len..]);
+        let read = *result.as_ref().unwrap_or(&0);
+        self.buffer.truncate(len + read);
+
+        result.map_err(Error::io)
+    }
+
+    /// Returns the bytes received, but not parsed yet.
+    ///
+    /// Non-empty buffered bytes at the end of a stream indicate a truncated value.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Returns the parser's current read position,
+    /// i.e. the number of bytes parsed so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of containers entered, but not ended yet,
+    /// with `0` indicating a position between top-level values.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Parses the next event, if its bytes have been received completely,
+    /// returning `None` otherwise (without consuming any bytes).
+    ///
+    /// Strings and byte sequences get reported once received in full.
+    pub fn next_event(&mut self) -> Result<Option<Event<'_>>> {
+        let start = self.start;
+
+        match self.stack.last() {
+            Some(Container::Seq(Some(0))) => {
+                self.stack.pop();
+                return Ok(Some(Event::SeqEnd));
+            }
+            Some(Container::Map(0)) => {
+                self.stack.pop();
+                return Ok(Some(Event::MapEnd));
+            }
+            _ => {}
+        }
+
+        let (parsed, len) = match self.parse() {
+            Ok(parsed) => parsed,
+            Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => return Ok(None),
+            Err(err) => return Err(err.offset_pos(self.pos)),
+        };
+
+        self.start += len;
+        self.pos += len;
+
+        if !matches!(parsed, Parsed::SeqEnd) {
+            match self.stack.last_mut() {
+                Some(Container::Seq(Some(remaining)) | Container::Map(remaining)) => {
+                    *remaining -= 1;
+                }
+                Some(Container::Seq(None)) | None => {}
+            }
+        }
+
+        let offset = |range: Range<usize>| (start + range.start)..(start + range.end);
+
+        Ok(Some(match parsed {
+            Parsed::Int(value) => Event::Int(value),
+            Parsed::Str(range) => Event::Str(&self.buffer[offset(range)]),
+            Parsed::CopiedStr => Event::Str(&self.scratch),
+            Parsed::Float(value) => Event::Float(value),
+            Parsed::Bytes(range) => Event::Bytes(&self.buffer[offset(range)]),
+            Parsed::Bool(value) => Event::Bool(value),
+            Parsed::Unit => Event::Unit,
+            Parsed::Null => Event::Null,
+            Parsed::SeqStart(len) => {
+                self.stack.push(Container::Seq(len));
+                Event::SeqStart(len)
+            }
+            Parsed::SeqEnd => {
+                self.stack.pop();
+                Event::SeqEnd
+            }
+            Parsed::MapStart(len) => {
+                let items = len
+                    .checked_mul(2)
+                    .ok_or_else(|| Error::length_exceeds_platform(len as u64, Some(self.pos)))?;
+                self.stack.push(Container::Map(items));
+                Event::MapStart(len)
+            }
+            Parsed::Value(value) => Event::Value(value),
+        }))
+    }
+
+    // MARK: - Private
+
+    /// Parses the next event from the buffered bytes,
+    /// returning it along with the number of bytes it spans.
+    fn parse(&mut self) -> Result<(Parsed, usize)> {
+        let bytes = &self.buffer[self.start..];
+        let in_stream = matches!(self.stack.last(), Some(Container::Seq(None)));
+
+        let mut decoder = Decoder::new(SliceReader::new(bytes), self.config);
+        decoder.set_dictionary(self.dictionary.take());
+
+        let result = parse_with(&mut decoder, in_stream, &mut self.scratch);
+
+        self.dictionary = decoder.set_dictionary(None);
+
+        Ok((result?, decoder.pos()))
+    }
+
+    fn compact(&mut self) {
+        self.buffer.drain(..self.start);
+        self.start = 0;
+    }
+}
+
+/// Parses the next event using `decoder`, copying
+/// strings that aren't part of the input into `scratch`.
+fn parse_with(
+    decoder: &mut Decoder<SliceReader<'_>>,
+    in_stream: bool,
+    scratch: &mut Vec<u8>,
+) -> Result<Parsed> {
+    if in_stream && decoder.decode_seq_stream_end()? {
+        return Ok(Parsed::SeqEnd);
+    }
+
+    Ok(match decoder.peek_value_kind()? {
+        ValueKind::Int => Parsed::Int(decoder.decode_int_value()?),
+        ValueKind::String if decoder.peek_dictionary_ref()? => {
+            let mut buf = Vec::new();
+            let bytes = decoder.decode_str_bytes(&mut buf)?;
+            scratch.clear();
+            scratch.extend_from_slice(&bytes);
+            Parsed::CopiedStr
+        }
+        ValueKind::String => {
+            let header = decoder.decode_string_header()?;
+            let start = decoder.pos();
+            decoder.skip_string_value_of(header)?;
+            Parsed::Str(start..decoder.pos())
+        }
+        ValueKind::Seq if decoder.peek_columnar_seq()? || decoder.peek_tabular_seq()? => {
+            Parsed::Value(decoder.decode_value()?)
+        }
+        ValueKind::Seq => Parsed::SeqStart(decoder.decode_seq_start()?),
+        ValueKind::Map if decoder.peek_front_coded_map()? => Parsed::Value(decoder.decode_value()?),
+        ValueKind::Map => Parsed::MapStart(decoder.decode_map_header()?.len()),
+        ValueKind::Float => Parsed::Float(decoder.decode_float_value()?),
+        ValueKind::Bytes => {
+            let header = decoder.decode_bytes_header()?;
+            let start = decoder.pos();
+            decoder.skip_bytes_value_of(header)?;
+            Parsed::Bytes(start..decoder.pos())
+        }
+        ValueKind::Bool => Parsed::Bool(decoder.decode_bool()?),
+        ValueKind::Unit => {
+            decoder.decode_unit()?;
+            Parsed::Unit
+        }
+        ValueKind::Null => {
+            decoder.decode_null()?;
+            Parsed::Null
+        }
+        _ => Parsed::Value(decoder.decode_value()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::{EncoderConfig, MapKeyCoding, SeqCoding},
+        encoder::Encoder,
+        io::VecWriter,
+        value::{BoolValue, BytesValue, MapValue, NullValue, SeqValue, StringValue, UnitValue},
+    };
+
+    use super::*;
+
+    /// Rebuilds values from a parser's events.
+    fn rebuild(parser: &mut Parser, values: &mut Vec<Value>, stack: &mut Vec<Vec<Value>>) {
+        while let Some(event) = parser.next_event().unwrap() {
+            let value = match event {
+                Event::Int(value) => Value::Int(value),
+                Event::Str(bytes) => Value::String(StringValue::from(
+                    core::str::from_utf8(bytes).unwrap().to_owned(),
+                )),
+                Event::Float(value) => Value::Float(value),
+                Event::Bytes(bytes) => Value::Bytes(BytesValue::from(bytes.to_vec())),
+                Event::Bool(value) => Value::Bool(BoolValue(value)),
+                Event::Unit => Value::Unit(UnitValue),
+                Event::Null => Value::Null(NullValue),
+                Event::SeqStart(_) | Event::MapStart(_) => {
+                    stack.push(Vec::new());
+                    continue;
+                }
+                Event::SeqEnd => Value::Seq(SeqValue::from(stack.pop().unwrap())),
+                Event::MapEnd => {
+                    let items = stack.pop().unwrap();
+                    let mut entries = items.into_iter();
+                    let mut map = crate::value::Map::default();
+                    while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                        map.insert(key, value);
+                    }
+                    Value::Map(MapValue::from(map))
+                }
+                Event::Value(value) => value,
+            };
+
+            match stack.last_mut() {
+                Some(items) => items.push(value),
+                None => values.push(value),
+            }
+        }
+    }
+
+    #[test]
+    fn chunked() {
+        let value: Value = crate::text::from_str(
+            r#"{"id": 7_u8, "name": "lilliput", "blob": b"\x01\x02", "tags": ["a", [], {}], "ok": true, "none": null, "pi": 3.5_f32}"#,
+        )
+        .unwrap();
+
+        for config in [
+            EncoderConfig::default(),
+            EncoderConfig::default().with_map_keys(MapKeyCoding::FrontCoded),
+            EncoderConfig::default().with_seqs(SeqCoding::Columnar),
+            EncoderConfig::default().with_seqs(SeqCoding::Tabular),
+        ] {
+            let mut encoded = Vec::new();
+            let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config);
+            encoder.encode_value(&value).unwrap();
+            encoder.encode_seq_stream_start().unwrap();
+            encoder.encode_u8(1).unwrap();
+            encoder.encode_seq_stream_end().unwrap();
+
+            for chunk_len in [1, 3, encoded.len()] {
+                let mut parser = Parser::default();
+                let mut values = Vec::new();
+                let mut stack = Vec::new();
+
+                for chunk in encoded.chunks(chunk_len) {
+                    parser.extend_from_slice(chunk);
+                    rebuild(&mut parser, &mut values, &mut stack);
+                }
+
+                assert_eq!(values.len(), 2);
+                assert_eq!(values[0], value);
+                assert_eq!(parser.depth(), 0);
+                assert_eq!(parser.pos(), encoded.len());
+                assert!(parser.buffered().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn dictionary_refs() {
+        let dictionary: Dictionary = ["temperature", "humidity"].into_iter().collect();
+
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder =
+            Encoder::from_writer(VecWriter::new(&mut encoded)).with_dictionary(dictionary.clone());
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_str("humidity").unwrap();
+
+        let mut parser = Parser::default().with_dictionary(dictionary);
+        parser.extend_from_slice(&encoded);
+        assert_eq!(
+            parser.next_event().unwrap(),
+            Some(Event::Int(IntValue::from(1_u8)))
+        );
+        assert_eq!(parser.next_event().unwrap(), Some(Event::Str(b"humidity")));
+        assert_eq!(parser.next_event().unwrap(), None);
+
+        // Without a dictionary ids are unknown:
+        let mut parser = Parser::default();
+        parser.extend_from_slice(&encoded);
+        assert_eq!(
+            parser.next_event().unwrap(),
+            Some(Event::Int(IntValue::from(1_u8)))
+        );
+        let err = parser.next_event().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+        assert_eq!(err.pos(), Some(1));
+
+        // ... with the parser staying put:
+        assert_eq!(parser.pos(), 1);
+        assert_eq!(parser.buffered(), &encoded[1..]);
+    }
+}
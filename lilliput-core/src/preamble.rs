@@ -0,0 +1,53 @@
+//! Preambles, identifying encoded documents (e.g. files on disk) as lilliput.
+//!
+//! A preamble consists of `PREAMBLE_MAGIC`, followed by the version of
+//! the wire format used by the document that follows, as a single byte,
+//! and the `Profile` it got encoded with, as another.
+//! Future revisions of the format (and documents exceeding a decoder's
+//! profile) can thus be told apart from the current one, before decoding
+//! any of the document's values.
+//!
+//! ```
+//! use lilliput_core::{
+//!     decoder::Decoder,
+//!     encoder::Encoder,
+//!     io::{SliceReader, VecWriter},
+//!     preamble::FORMAT_VERSION,
+//!     Profile,
+//! };
+//!
+//! let mut encoded = Vec::new();
+//! let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+//! encoder.encode_preamble().unwrap();
+//! encoder.encode_str("lilliput").unwrap();
+//!
+//! let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+//! let preamble = decoder.expect_preamble().unwrap();
+//! assert_eq!(preamble.version, FORMAT_VERSION);
+//! assert_eq!(preamble.profile, Profile::None);
+//! assert_eq!(decoder.decode_string().unwrap(), "lilliput");
+//! ```
+//!
+//! Preambles are optional and only ever written (or expected) when asked to,
+//! either explicitly, or via `EncoderConfig::preamble`/`DecoderConfig::preamble`
+//! for higher-level APIs, such as lilliput-serde's `to_vec()`/`from_slice()`.
+
+use crate::config::Profile;
+
+/// The magic bytes preambles start with.
+pub const PREAMBLE_MAGIC: [u8; 4] = *b"LLPT";
+
+/// The version of the wire format, as written by preambles.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// The length (in bytes) of a preamble.
+pub const PREAMBLE_LEN: usize = PREAMBLE_MAGIC.len() + 2;
+
+/// A decoded preamble.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Preamble {
+    /// The version of the wire format used by the document.
+    pub version: u8,
+    /// The profile the document got encoded with.
+    pub profile: Profile,
+}
@@ -0,0 +1,296 @@
+//! Resumable decoding of values, from bytes received in arbitrary chunks.
+//!
+//! Unlike with `Decoder`s, for which running out of input is a terminal error,
+//! a `ResumableDecoder` reports the need for more bytes, after which decoding
+//! can be resumed, once they have been received (e.g. from a non-blocking socket).
+//!
+//! ```
+//! use lilliput_core::{
+//!     encoder::Encoder,
+//!     io::VecWriter,
+//!     resumable::{Progress, ResumableDecoder},
+//!     value::{StringValue, Value},
+//! };
+//!
+//! let value = Value::String(StringValue::from("lilliput".to_owned()));
+//!
+//! let mut encoded = Vec::new();
+//! Encoder::from_writer(VecWriter::new(&mut encoded)).encode_value(&value).unwrap();
+//!
+//! let mut decoder = ResumableDecoder::default();
+//!
+//! decoder.extend_from_slice(&encoded[..3]);
+//! assert_eq!(decoder.decode_value().unwrap(), Progress::NeedMore(6));
+//!
+//! decoder.extend_from_slice(&encoded[3..]);
+//! assert_eq!(decoder.decode_value().unwrap(), Progress::Done(value));
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    config::DecoderConfig,
+    decoder::Decoder,
+    dictionary::Dictionary,
+    error::{ErrorCode, Result},
+    io::{Read, Reference, SliceReader},
+    value::Value,
+};
+
+/// The progress of a resumable decoding.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Progress<T> {
+    /// Decoding has completed.
+    Done(T),
+    /// Decoding requires more bytes, at least as many as given.
+    NeedMore(usize),
+}
+
+impl<T> Progress<T> {
+    /// Returns the decoded value, if done.
+    pub fn done(self) -> Option<T> {
+        match self {
+            Self::Done(value) => Some(value),
+            Self::NeedMore(_) => None,
+        }
+    }
+}
+
+/// A decoder of values, from bytes received in arbitrary chunks.
+///
+/// Bytes get provided via `extend_from_slice()` (or `read_from()`), after which
+/// values get decoded from them, if received completely, or return the number
+/// of bytes they need at least, otherwise (without consuming any bytes).
+///
+/// A value's decoding gets restarted from its beginning once resumed,
+/// so for large values it's best to wait for the hinted number of bytes.
+#[derive(Debug, Default)]
+pub struct ResumableDecoder {
+    buffer: Vec<u8>,
+    start: usize,
+    pos: usize,
+    config: DecoderConfig,
+    dictionary: Option<Dictionary>,
+}
+
+impl ResumableDecoder {
+    /// Creates a decoder, configured by `config`.
+    pub fn new(config: DecoderConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Attaches `dictionary`, for resolving references to dictionary strings, returning `self`.
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// Appends received `bytes` to the decoder's buffer.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.compact();
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Reads a chunk of bytes from `reader` into the decoder's buffer,
+    /// returning the number of bytes read, with `0` indicating its end.
+    #[cfg(feature = "std")]
+    pub fn read_from<R>(&mut self, reader: &mut R) -> Result<usize>
+    where
+        R: std::io::Read,
+    {
+        const CHUNK_LEN: usize = 8 * 1024;
+
+        self.compact();
+
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_LEN, 0b0);
+
+        let result = reader.read(&mut self.buffer[len..]);
+        let read = *result.as_ref().unwrap_or(&0);
+        self.buffer.truncate(len + read);
+
+        result.map_err(crate::error::Error::io)
+    }
+
+    /// Returns the bytes received, but not decoded yet.
+    ///
+    /// Non-empty buffered bytes at the end of a stream indicate a truncated value.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Returns the decoder's current read position,
+    /// i.e. the number of bytes decoded so far.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Decodes the next value, if it has been received completely.
+    pub fn decode_value(&mut self) -> Result<Progress<Value>> {
+        let reader = ShortfallReader::new(&self.buffer[self.start..]);
+
+        let mut decoder = Decoder::new(reader, self.config);
+        decoder.set_dictionary(self.dictionary.take());
+
+        let result = decoder.decode_value();
+        let len = decoder.pos();
+
+        self.dictionary = decoder.set_dictionary(None);
+        let shortfall = decoder.into_reader().shortfall;
+
+        match result {
+            Ok(value) => {
+                self.start += len;
+                self.pos += len;
+
+                Ok(Progress::Done(value))
+            }
+            Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => {
+                Ok(Progress::NeedMore(shortfall.max(1)))
+            }
+            Err(err) => Err(err.offset_pos(self.pos)),
+        }
+    }
+
+    // MARK: - Private
+
+    fn compact(&mut self) {
+        self.buffer.drain(..self.start);
+        self.start = 0;
+    }
+}
+
+/// A reader over a slice, recording by how many bytes a read exceeded it.
+struct ShortfallReader<'r> {
+    reader: SliceReader<'r>,
+    shortfall: usize,
+}
+
+impl<'r> ShortfallReader<'r> {
+    fn new(slice: &'r [u8]) -> Self {
+        Self {
+            reader: SliceReader::new(slice),
+            shortfall: 0,
+        }
+    }
+
+    fn record(&mut self, len: usize) {
+        let remaining = self.reader.buffered().len();
+
+        if len > remaining {
+            self.shortfall = len - remaining;
+        }
+    }
+}
+
+impl<'r> Read<'r> for ShortfallReader<'r> {
+    fn peek_one(&mut self) -> Result<u8> {
+        self.record(1);
+        self.reader.peek_one()
+    }
+
+    fn peek(&mut self, len: usize) -> Result<&[u8]> {
+        self.record(len);
+        self.reader.peek(len)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.record(len);
+        self.reader.skip(len)
+    }
+
+    fn read<'s>(
+        &'s mut self,
+        len: usize,
+        scratch: &'s mut Vec<u8>,
+    ) -> Result<Reference<'r, 's, [u8]>> {
+        self.record(len);
+        self.reader.read(len, scratch)
+    }
+
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.record(buf.len());
+        self.reader.read_into(buf)
+    }
+
+    fn read_owned(&mut self, len: usize) -> Result<Option<Vec<u8>>> {
+        self.record(len);
+        self.reader.read_owned(len)
+    }
+
+    fn remaining_len_hint(&self) -> Option<usize> {
+        self.reader.remaining_len_hint()
+    }
+
+    fn buffered(&self) -> &[u8] {
+        self.reader.buffered()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::EncoderConfig,
+        encoder::Encoder,
+        io::VecWriter,
+        value::{BytesValue, SeqValue, StringValue},
+    };
+
+    use super::*;
+
+    #[test]
+    fn chunked() {
+        let value = Value::Seq(SeqValue::from(vec![
+            Value::String(StringValue::from("lilliput".repeat(100))),
+            Value::Bytes(BytesValue::from(vec![42; 300])),
+        ]));
+
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::new(VecWriter::new(&mut encoded), EncoderConfig::default());
+        encoder.encode_value(&value).unwrap();
+        encoder.encode_value(&value).unwrap();
+
+        let mut decoder = ResumableDecoder::default();
+        let mut decoded = Vec::new();
+        let mut fed = 0;
+
+        while fed < encoded.len() {
+            match decoder.decode_value().unwrap() {
+                Progress::Done(value) => decoded.push(value),
+                Progress::NeedMore(hint) => {
+                    // Feeding no more than the hinted number of bytes:
+                    let end = (fed + hint).min(encoded.len());
+                    decoder.extend_from_slice(&encoded[fed..end]);
+                    fed = end;
+                }
+            }
+        }
+
+        decoded.push(decoder.decode_value().unwrap().done().unwrap());
+
+        assert_eq!(decoded, [value.clone(), value]);
+        assert_eq!(decoder.pos(), encoded.len());
+        assert!(decoder.buffered().is_empty());
+        assert_eq!(decoder.decode_value().unwrap(), Progress::NeedMore(1));
+    }
+
+    #[test]
+    fn errors() {
+        let mut decoder = ResumableDecoder::default();
+        decoder.extend_from_slice(&[0xC1, 0x61]);
+
+        assert!(matches!(decoder.decode_value().unwrap(), Progress::Done(_)));
+
+        // Strings are expected to contain valid UTF-8:
+        decoder.extend_from_slice(&[0xFF]);
+        let err = decoder.decode_value().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Utf8);
+        assert_eq!(err.pos(), Some(3));
+        assert_eq!(decoder.buffered(), [0x61, 0xFF]);
+    }
+}
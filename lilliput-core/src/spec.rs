@@ -0,0 +1,341 @@
+//! A minimal, data-driven model of the lilliput wire format.
+//!
+//! Each value type's header layout is described by a [`HeaderSpec`],
+//! from which the [reference codec](self::reference) derives its encoding and decoding
+//! rules, without relying on any of the optimized code paths of
+//! `Encoder` and `Decoder`.
+//!
+//! The reference codec is deliberately simple (and slow). Its purpose
+//! is to serve as an executable specification that the optimized
+//! implementation gets tested against.
+
+use crate::{
+    header::{
+        BoolHeader, BytesHeader, FloatHeader, IntHeader, MapHeader, NullHeader, SeqHeader,
+        StringHeader, UnitHeader,
+    },
+    marker::Marker,
+};
+
+pub mod reference;
+
+/// The layout of a value type's header byte.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct HeaderSpec {
+    /// The value type's marker.
+    pub marker: Marker,
+    /// The bits identifying the value type.
+    ///
+    /// A header byte belongs to the type whose `type_bits`
+    /// match the byte's most significant set bit.
+    pub type_bits: u8,
+    /// The bits used by the type's header (including `type_bits`).
+    pub mask: u8,
+    /// The bit holding a single-bit flag (e.g. signedness, or truth).
+    pub flag_bit: Option<u8>,
+    /// The compact variant's layout, if supported by the type.
+    pub compact: Option<CompactSpec>,
+    /// The extended variant's layout, if supported by the type.
+    pub extended: Option<ExtendedSpec>,
+    /// The body following the header.
+    pub body: BodySpec,
+}
+
+/// The layout of a header's compact variant, which stores
+/// its content directly within the header byte.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct CompactSpec {
+    /// The bit marking a header as compact.
+    pub variant_bit: u8,
+    /// The bits holding the header's content.
+    pub content_bits: u8,
+}
+
+impl CompactSpec {
+    /// Returns the largest content representable by the compact variant.
+    pub const fn max_content(&self) -> u64 {
+        self.content_bits as u64
+    }
+}
+
+/// The layout of a header's extended variant, which stores
+/// its content in big-endian bytes following the header byte.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ExtendedSpec {
+    /// The bits holding the width of the header's extension.
+    pub width_bits: u8,
+    /// The rule for mapping between `width_bits` and widths.
+    pub width_rule: WidthRule,
+}
+
+/// The rule for mapping between a header's width bits and widths (in bytes).
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum WidthRule {
+    /// Width is `bits + 1`.
+    Linear,
+    /// Width is `1 << bits`.
+    Exponential,
+}
+
+impl WidthRule {
+    /// Returns the width (in bytes) for given `bits`.
+    pub const fn width_for_bits(self, bits: u8) -> usize {
+        match self {
+            Self::Linear => bits as usize + 1,
+            Self::Exponential => 1 << bits,
+        }
+    }
+
+    /// Returns the width bits for a given `width` (in bytes),
+    /// or `None` if the width is not representable.
+    pub const fn bits_for_width(self, width: usize) -> Option<u8> {
+        match self {
+            Self::Linear if matches!(width, 1..=16) => Some((width - 1) as u8),
+            Self::Exponential if width.is_power_of_two() && width <= 8 => {
+                Some(width.trailing_zeros() as u8)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest representable width (in bytes) that fits `content`.
+    pub const fn min_width_for(self, content: u128) -> usize {
+        let significant = (u128::BITS - content.leading_zeros()) as usize;
+        let width = if significant == 0 {
+            1
+        } else {
+            (significant + 7) / 8
+        };
+
+        match self {
+            Self::Linear => width,
+            Self::Exponential => width.next_power_of_two(),
+        }
+    }
+}
+
+/// The body following a value's header.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum BodySpec {
+    /// No body, the header's content (if any) is the value itself.
+    None,
+    /// The header's content is a length, followed by as many bytes.
+    Bytes,
+    /// The header's content is a length, followed by as many values.
+    Values,
+    /// The header's content is a length, followed by as many key-value pairs.
+    Entries,
+}
+
+/// The header layouts of all value types, ordered by descending `type_bits`.
+pub const HEADER_SPECS: [HeaderSpec; 9] = [
+    HeaderSpec {
+        marker: Marker::Int,
+        type_bits: IntHeader::TYPE_BITS,
+        mask: IntHeader::MASK,
+        flag_bit: Some(IntHeader::SIGNEDNESS_BIT),
+        compact: Some(CompactSpec {
+            variant_bit: IntHeader::COMPACT_VARIANT_BIT,
+            content_bits: IntHeader::COMPACT_VALUE_BITS,
+        }),
+        extended: Some(ExtendedSpec {
+            width_bits: IntHeader::EXTENDED_WIDTH_BITS,
+            width_rule: WidthRule::Linear,
+        }),
+        body: BodySpec::None,
+    },
+    HeaderSpec {
+        marker: Marker::String,
+        type_bits: StringHeader::TYPE_BITS,
+        mask: StringHeader::MASK,
+        flag_bit: None,
+        compact: Some(CompactSpec {
+            variant_bit: StringHeader::COMPACT_VARIANT_BIT,
+            content_bits: StringHeader::COMPACT_LEN_BITS,
+        }),
+        extended: Some(ExtendedSpec {
+            width_bits: StringHeader::EXTENDED_LEN_WIDTH_BITS,
+            width_rule: WidthRule::Linear,
+        }),
+        body: BodySpec::Bytes,
+    },
+    HeaderSpec {
+        marker: Marker::Seq,
+        type_bits: SeqHeader::TYPE_BITS,
+        mask: SeqHeader::MASK,
+        flag_bit: None,
+        compact: Some(CompactSpec {
+            variant_bit: SeqHeader::COMPACT_VARIANT_BIT,
+            content_bits: SeqHeader::COMPACT_LEN_BITS,
+        }),
+        extended: Some(ExtendedSpec {
+            width_bits: SeqHeader::EXTENDED_LEN_WIDTH_BITS,
+            width_rule: WidthRule::Linear,
+        }),
+        body: BodySpec::Values,
+    },
+    HeaderSpec {
+        marker: Marker::Map,
+        type_bits: MapHeader::TYPE_BITS,
+        mask: MapHeader::MASK,
+        flag_bit: None,
+        compact: Some(CompactSpec {
+            variant_bit: MapHeader::COMPACT_VARIANT_BIT,
+            content_bits: MapHeader::COMPACT_LEN_BITS,
+        }),
+        extended: Some(ExtendedSpec {
+            width_bits: MapHeader::EXTENDED_LEN_WIDTH_BITS,
+            width_rule: WidthRule::Linear,
+        }),
+        body: BodySpec::Entries,
+    },
+    HeaderSpec {
+        marker: Marker::Float,
+        type_bits: FloatHeader::TYPE_BITS,
+        mask: FloatHeader::MASK,
+        flag_bit: None,
+        compact: None,
+        extended: Some(ExtendedSpec {
+            width_bits: FloatHeader::VALUE_WIDTH_BITS,
+            width_rule: WidthRule::Linear,
+        }),
+        body: BodySpec::None,
+    },
+    HeaderSpec {
+        marker: Marker::Bytes,
+        type_bits: BytesHeader::TYPE_BITS,
+        mask: BytesHeader::MASK,
+        flag_bit: None,
+        compact: None,
+        extended: Some(ExtendedSpec {
+            width_bits: BytesHeader::LEN_WIDTH_EXPONENT_BITS,
+            width_rule: WidthRule::Exponential,
+        }),
+        body: BodySpec::Bytes,
+    },
+    HeaderSpec {
+        marker: Marker::Bool,
+        type_bits: BoolHeader::TYPE_BITS,
+        mask: BoolHeader::MASK,
+        flag_bit: Some(BoolHeader::VALUE_BIT),
+        compact: None,
+        extended: None,
+        body: BodySpec::None,
+    },
+    HeaderSpec {
+        marker: Marker::Unit,
+        type_bits: UnitHeader::TYPE_BITS,
+        mask: UnitHeader::MASK,
+        flag_bit: None,
+        compact: None,
+        extended: None,
+        body: BodySpec::None,
+    },
+    HeaderSpec {
+        marker: Marker::Null,
+        type_bits: NullHeader::TYPE_BITS,
+        mask: NullHeader::MASK,
+        flag_bit: None,
+        compact: None,
+        extended: None,
+        body: BodySpec::None,
+    },
+];
+
+impl HeaderSpec {
+    /// Returns the header layout for a given `marker`.
+    pub fn for_marker(marker: Marker) -> &'static Self {
+        HEADER_SPECS
+            .iter()
+            .find(|spec| spec.marker == marker)
+            .expect("every marker has a spec")
+    }
+
+    /// Returns the header layout matching a given header `byte`.
+    pub fn for_byte(byte: u8) -> &'static Self {
+        HEADER_SPECS
+            .iter()
+            .find(|spec| spec.matches(byte))
+            .expect("every byte matches a spec")
+    }
+
+    /// Returns `true` if `byte` is a header of this layout, otherwise `false`.
+    pub fn matches(&self, byte: u8) -> bool {
+        (byte & !self.mask) == 0b0 && (byte & self.type_bits) == self.type_bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use super::*;
+
+    #[test]
+    fn for_byte_matches_marker_detection() {
+        for byte in 0..=u8::MAX {
+            assert_eq!(HeaderSpec::for_byte(byte).marker, Marker::detect(byte));
+        }
+    }
+
+    #[test]
+    fn every_byte_matches_exactly_one_spec() {
+        for byte in 0..=u8::MAX {
+            let matches = HEADER_SPECS.iter().filter(|spec| spec.matches(byte));
+            assert_eq!(matches.count(), 1, "byte: {byte:#010b}");
+        }
+    }
+
+    #[test]
+    fn layouts_are_disjoint() {
+        for spec in HEADER_SPECS.iter() {
+            let mut used = spec.type_bits;
+
+            let mut claim = |bits: u8| {
+                assert_eq!(used & bits, 0b0, "{:?}", spec.marker);
+                assert_eq!(bits & !spec.mask, 0b0, "{:?}", spec.marker);
+                used |= bits;
+            };
+
+            if let Some(flag_bit) = spec.flag_bit {
+                claim(flag_bit);
+            }
+
+            if let Some(compact) = spec.compact {
+                claim(compact.variant_bit);
+            }
+
+            // The compact content bits and extended width bits may overlap,
+            // as they belong to mutually exclusive variants:
+            let mut content = 0b0;
+            if let Some(compact) = spec.compact {
+                content |= compact.content_bits;
+            }
+            if let Some(extended) = spec.extended {
+                content |= extended.width_bits;
+            }
+            claim(content);
+        }
+    }
+
+    #[test]
+    fn width_rules() {
+        assert_eq!(WidthRule::Linear.min_width_for(0), 1);
+        assert_eq!(WidthRule::Linear.min_width_for(0x1_00_00), 3);
+        assert_eq!(WidthRule::Linear.min_width_for(u64::MAX.into()), 8);
+        assert_eq!(WidthRule::Linear.min_width_for(u128::MAX), 16);
+        assert_eq!(WidthRule::Exponential.min_width_for(0x1_00_00), 4);
+        assert_eq!(WidthRule::Exponential.min_width_for(0x1_00_00_00_00), 8);
+
+        for width in 1..=16 {
+            let bits = WidthRule::Linear.bits_for_width(width).unwrap();
+            assert_eq!(WidthRule::Linear.width_for_bits(bits), width);
+        }
+
+        for width in [1, 2, 4, 8] {
+            let bits = WidthRule::Exponential.bits_for_width(width).unwrap();
+            assert_eq!(WidthRule::Exponential.width_for_bits(bits), width);
+        }
+        assert_eq!(WidthRule::Exponential.bits_for_width(3), None);
+    }
+}
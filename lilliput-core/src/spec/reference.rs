@@ -0,0 +1,352 @@
+//! A reference encoder/decoder, derived from the header layouts in [`HEADER_SPECS`](super::HEADER_SPECS).
+//!
+//! Encodings produced by [`encode`] are those of an `Encoder`
+//! using the default (i.e. optimal) packing modes.
+
+use alloc::vec::Vec;
+
+use crate::{error::Error, error::Result, marker::Marker};
+
+use super::{BodySpec, HeaderSpec};
+
+/// A value, as represented on the wire.
+///
+/// Unlike `Value` a node does not interpret the encoded data beyond
+/// what is necessary for walking its structure: string bodies are not
+/// validated as UTF-8 and floating-point values are kept as their packed
+/// big-endian bytes.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Node {
+    /// An integer, with signed values in their zig-zag encoding.
+    Int {
+        /// Whether the integer is signed.
+        is_signed: bool,
+        /// The integer's (zig-zag encoded, if signed) bits.
+        bits: u128,
+    },
+    /// A string's bytes.
+    String(Vec<u8>),
+    /// A sequence of values.
+    Seq(Vec<Node>),
+    /// A map's key-value pairs, in encoded order.
+    Map(Vec<(Node, Node)>),
+    /// A floating-point value's packed big-endian bytes.
+    Float(Vec<u8>),
+    /// A byte array.
+    Bytes(Vec<u8>),
+    /// A boolean.
+    Bool(bool),
+    /// A unit value.
+    Unit,
+    /// A null value.
+    Null,
+}
+
+impl Node {
+    /// Returns the node's type marker.
+    pub fn marker(&self) -> Marker {
+        match self {
+            Self::Int { .. } => Marker::Int,
+            Self::String(_) => Marker::String,
+            Self::Seq(_) => Marker::Seq,
+            Self::Map(_) => Marker::Map,
+            Self::Float(_) => Marker::Float,
+            Self::Bytes(_) => Marker::Bytes,
+            Self::Bool(_) => Marker::Bool,
+            Self::Unit => Marker::Unit,
+            Self::Null => Marker::Null,
+        }
+    }
+}
+
+// MARK: - Encoding
+
+/// Encodes `node`, appending its bytes to `out`.
+///
+/// # Panics
+///
+/// Panics if a floating-point node's width is not representable.
+pub fn encode(node: &Node, out: &mut Vec<u8>) {
+    let spec = HeaderSpec::for_marker(node.marker());
+
+    match node {
+        Node::Int { is_signed, bits } => {
+            encode_header(spec, *is_signed, *bits, out);
+        }
+        Node::String(bytes) | Node::Bytes(bytes) => {
+            encode_header(spec, false, bytes.len() as u128, out);
+            out.extend_from_slice(bytes);
+        }
+        Node::Seq(items) => {
+            encode_header(spec, false, items.len() as u128, out);
+            for item in items {
+                encode(item, out);
+            }
+        }
+        Node::Map(entries) => {
+            encode_header(spec, false, entries.len() as u128, out);
+            for (key, value) in entries {
+                encode(key, out);
+                encode(value, out);
+            }
+        }
+        Node::Float(be_bytes) => {
+            // Floats are never compact and their width is that of their payload:
+            let extended = spec.extended.expect("floats are extended");
+            let width_bits = extended
+                .width_rule
+                .bits_for_width(be_bytes.len())
+                .expect("valid float width");
+            out.push(spec.type_bits | (width_bits & extended.width_bits));
+            out.extend_from_slice(be_bytes);
+        }
+        Node::Bool(value) => {
+            encode_header(spec, *value, 0, out);
+        }
+        Node::Unit | Node::Null => {
+            encode_header(spec, false, 0, out);
+        }
+    }
+}
+
+fn encode_header(spec: &HeaderSpec, flag: bool, content: u128, out: &mut Vec<u8>) {
+    let mut byte = spec.type_bits;
+
+    if flag {
+        byte |= spec.flag_bit.expect("flag bit");
+    }
+
+    if let Some(compact) = spec.compact {
+        if content <= compact.max_content().into() {
+            out.push(byte | compact.variant_bit | (content as u8));
+            return;
+        }
+    }
+
+    let Some(extended) = spec.extended else {
+        out.push(byte);
+        return;
+    };
+
+    let width = extended.width_rule.min_width_for(content);
+    let width_bits = extended
+        .width_rule
+        .bits_for_width(width)
+        .expect("valid width");
+
+    out.push(byte | (width_bits & extended.width_bits));
+    out.extend_from_slice(&content.to_be_bytes()[(16 - width)..]);
+}
+
+// MARK: - Decoding
+
+/// Decodes a single node from the front of `bytes`,
+/// returning it along with the number of bytes consumed.
+pub fn decode(bytes: &[u8]) -> Result<(Node, usize)> {
+    let mut cursor = Cursor { bytes, pos: 0 };
+    let node = cursor.decode()?;
+    Ok((node, cursor.pos))
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn decode(&mut self) -> Result<Node> {
+        let byte = self.take(1)?[0];
+
+        let spec = HeaderSpec::for_byte(byte);
+
+        let flag = spec.flag_bit.is_some_and(|bit| (byte & bit) != 0b0);
+
+        let compact = spec
+            .compact
+            .filter(|compact| (byte & compact.variant_bit) != 0b0);
+
+        let content = match (compact, spec.extended) {
+            (Some(compact), _) => Some(Content::Inline(byte & compact.content_bits)),
+            (None, Some(extended)) => {
+                let width = extended
+                    .width_rule
+                    .width_for_bits(byte & extended.width_bits);
+                Some(Content::Extension(self.take(width)?))
+            }
+            (None, None) => None,
+        };
+
+        match (spec.marker, spec.body) {
+            (Marker::Float, _) => match content {
+                Some(Content::Extension(be_bytes)) => Ok(Node::Float(be_bytes.to_vec())),
+                _ => unreachable!("floats are extended"),
+            },
+            (Marker::Bool, _) => Ok(Node::Bool(flag)),
+            (Marker::Unit, _) => Ok(Node::Unit),
+            (Marker::Null, _) => Ok(Node::Null),
+            (Marker::Int, _) => Ok(Node::Int {
+                is_signed: flag,
+                bits: content.map_or(0, Content::to_u128),
+            }),
+            (_, BodySpec::Bytes) => {
+                let len = self.len_of(content)?;
+                let bytes = self.take(len)?.to_vec();
+                match spec.marker {
+                    Marker::String => Ok(Node::String(bytes)),
+                    _ => Ok(Node::Bytes(bytes)),
+                }
+            }
+            (_, BodySpec::Values) => {
+                let len = self.len_of(content)?;
+                let mut items = Vec::new();
+                for _ in 0..len {
+                    items.push(self.decode()?);
+                }
+                Ok(Node::Seq(items))
+            }
+            (_, BodySpec::Entries) => {
+                let len = self.len_of(content)?;
+                let mut entries = Vec::new();
+                for _ in 0..len {
+                    let key = self.decode()?;
+                    let value = self.decode()?;
+                    entries.push((key, value));
+                }
+                Ok(Node::Map(entries))
+            }
+            (_, BodySpec::None) => unreachable!("covered by markers above"),
+        }
+    }
+
+    fn len_of(&self, content: Option<Content<'_>>) -> Result<usize> {
+        // Lengths are at most 8 bytes wide, so `len` fits into a `u64`:
+        let len = content.map_or(0, Content::to_u128);
+        usize::try_from(len).map_err(|_| Error::length_exceeds_platform(len as u64, Some(self.pos)))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(Error::end_of_file)?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Content<'a> {
+    Inline(u8),
+    Extension(&'a [u8]),
+}
+
+impl<'a> Content<'a> {
+    fn to_u128(self) -> u128 {
+        match self {
+            Self::Inline(bits) => bits.into(),
+            Self::Extension(be_bytes) => be_bytes
+                .iter()
+                .fold(0, |acc, byte| (acc << 8) | u128::from(*byte)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    fn encode_value(value: &Value) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        let writer = VecWriter::new(&mut encoded);
+        let mut encoder = Encoder::from_writer(writer);
+        encoder.encode_value(value).unwrap();
+        encoded
+    }
+
+    fn decode_value(encoded: &[u8]) -> Value {
+        let reader = SliceReader::new(encoded);
+        let mut decoder = Decoder::from_reader(reader);
+        decoder.decode_value().unwrap()
+    }
+
+    fn encode_node(node: &Node) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        encode(node, &mut encoded);
+        encoded
+    }
+
+    // Floats are excluded, as their payloads are opaque to the spec,
+    // and maps use unique, ordered string keys, which `Value` preserves.
+    fn arbitrary_node() -> impl Strategy<Value = Node> {
+        let leaf = prop_oneof![
+            (any::<bool>(), any::<u128>())
+                .prop_map(|(is_signed, bits)| Node::Int { is_signed, bits }),
+            any::<String>().prop_map(|string| Node::String(string.into_bytes())),
+            proptest::collection::vec(any::<u8>(), 0..300).prop_map(Node::Bytes),
+            any::<bool>().prop_map(Node::Bool),
+            Just(Node::Unit),
+            Just(Node::Null),
+        ];
+
+        leaf.prop_recursive(3, 64, 10, |inner| {
+            prop_oneof![
+                proptest::collection::vec(inner.clone(), 0..10).prop_map(Node::Seq),
+                proptest::collection::btree_map(any::<String>(), inner, 0..10).prop_map(
+                    |map: BTreeMap<String, Node>| {
+                        Node::Map(
+                            map.into_iter()
+                                .map(|(key, value)| (Node::String(key.into_bytes()), value))
+                                .collect(),
+                        )
+                    }
+                ),
+            ]
+        })
+    }
+
+    #[test]
+    fn decode_truncated() {
+        let encoded = encode_node(&Node::String(b"lilliput".to_vec()));
+        for len in 0..encoded.len() {
+            assert!(decode(&encoded[..len]).is_err());
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn reference_reencodes_encoder_output(value in Value::arbitrary()) {
+            let encoded = encode_value(&value);
+
+            let (node, len) = decode(&encoded).unwrap();
+            prop_assert_eq!(len, encoded.len());
+
+            prop_assert_eq!(encode_node(&node), encoded);
+        }
+
+        #[test]
+        fn decoder_and_encoder_agree_with_reference(node in arbitrary_node()) {
+            let encoded = encode_node(&node);
+
+            let value = decode_value(&encoded);
+            prop_assert_eq!(encode_value(&value), encoded.clone());
+
+            let (decoded, len) = decode(&encoded).unwrap();
+            prop_assert_eq!(len, encoded.len());
+            prop_assert_eq!(decoded, node);
+        }
+    }
+}
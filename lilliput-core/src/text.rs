@@ -0,0 +1,725 @@
+//! A lossless textual representation of values ("lilliput text").
+//!
+//! Unlike `Value`'s `Debug` output the textual representation preserves the exact
+//! type and width of every value, making it suitable for writing test fixtures
+//! and config files that need to express exact wire documents.
+//!
+//! # Syntax
+//!
+//! | Value   | Syntax                                                           |
+//! | ------- | ---------------------------------------------------------------- |
+//! | Int     | `42_u8`, `-42_i16`, … (the width suffix is required)             |
+//! | Float   | `1.5_f32`, `-1e100_f64`, or `f32(0x7fc00000)` for raw bits       |
+//! | String  | `"lilliput"`, with escapes `\"`, `\\`, `\n`, `\r`, `\t`, `\u{…}` |
+//! | Bytes   | `b"lilliput\x00"`, with escapes `\"`, `\\`, `\xNN`               |
+//! | Seq     | `[1_u8, 2_u8]`                                                   |
+//! | Map     | `{"key": 1_u8, 2_u8: null}`                                      |
+//! | Bool    | `true`, `false`                                                  |
+//! | Unit    | `()`                                                             |
+//! | Null    | `null`                                                           |
+//! | Ext     | `ext(42, b"…")`, with the tag and the encoded payload            |
+//! | Unknown | `unknown(0x2f, b"…")`, with the header byte and the encoded body |
+//!
+//! Whitespace between tokens is insignificant and seqs and maps
+//! allow for a trailing comma. Seqs and maps may be nested
+//! at most `MAX_DEPTH` levels deep.
+//!
+//! Non-finite floats are rendered as their raw bits, which
+//! preserves infinities, as well as NaN payloads.
+
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    config::DecoderConfig,
+    decoder::Decoder,
+    error::{Error, Result},
+    io::SliceReader,
+    validation::DEFAULT_MAX_DEPTH,
+    value::{
+        BoolValue, BytesValue, ExtValue, FloatValue, IntValue, Map, MapValue, NullValue, SeqValue,
+        SignedIntValue, StringValue, UnitValue, UnknownValue, UnsignedIntValue, Value,
+    },
+};
+
+/// The maximum nesting depth of seqs and maps accepted by the parser.
+pub const MAX_DEPTH: usize = 128;
+
+/// Renders `value` as lilliput text.
+pub fn to_string(value: &Value) -> String {
+    let mut text = String::new();
+    write(value, &mut text).expect("writing to a string never fails");
+    text
+}
+
+/// Writes `value` as lilliput text to `writer`.
+pub fn write<W>(value: &Value, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    match value {
+        Value::Int(value) => write_int(value, writer),
+        Value::String(value) => write_str(value.as_str(), writer),
+        Value::Seq(value) => {
+            writer.write_char('[')?;
+            for (index, item) in value.as_slice().iter().enumerate() {
+                if index > 0 {
+                    writer.write_str(", ")?;
+                }
+                write(item, writer)?;
+            }
+            writer.write_char(']')
+        }
+        Value::Map(value) => {
+            writer.write_char('{')?;
+            for (index, (key, value)) in value.as_map_ref().iter().enumerate() {
+                if index > 0 {
+                    writer.write_str(", ")?;
+                }
+                write(key, writer)?;
+                writer.write_str(": ")?;
+                write(value, writer)?;
+            }
+            writer.write_char('}')
+        }
+        Value::Float(value) => write_float(value, writer),
+        Value::Bytes(value) => write_bytes(value.as_slice(), writer),
+        Value::Bool(value) => writer.write_str(if value.0 { "true" } else { "false" }),
+        Value::Unit(_) => writer.write_str("()"),
+        Value::Null(_) => writer.write_str("null"),
+        Value::Ext(value) => {
+            write!(writer, "ext({}, ", value.tag)?;
+            write_bytes(&value.payload, writer)?;
+            writer.write_char(')')
+        }
+        Value::Unknown(value) => {
+            write!(writer, "unknown({:#04x}, ", value.header)?;
+            write_bytes(&value.body, writer)?;
+            writer.write_char(')')
+        }
+    }
+}
+
+/// Parses a value from lilliput text.
+///
+/// The entire `text` has to be consumed, except for trailing whitespace.
+pub fn from_str(text: &str) -> Result<Value> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_value()?;
+
+    parser.skip_whitespace();
+    if parser.pos < text.len() {
+        return Err(parser.unexpected("end of text"));
+    }
+
+    Ok(value)
+}
+
+/// Parses a value from the front of `text`,
+/// returning it along with the number of bytes consumed.
+pub(crate) fn from_str_prefix(text: &str) -> Result<(Value, usize)> {
+    let mut parser = Parser {
+        text,
+        pos: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_value()?;
+
+    Ok((value, parser.pos))
+}
+
+/// Decodes a value from its encoded `bytes`, rendering it as lilliput text,
+/// e.g. for logging binary payloads.
+///
+/// As such payloads may be untrusted, decoding fails for values nested
+/// deeper than `validation::DEFAULT_MAX_DEPTH`.
+pub fn decode_to_string(bytes: &[u8]) -> Result<String> {
+    let config = DecoderConfig::default().with_max_depth(Some(DEFAULT_MAX_DEPTH));
+    let mut decoder = Decoder::new(SliceReader::new(bytes), config);
+    decoder.decode_value().map(|value| to_string(&value))
+}
+
+impl Value {
+    /// Renders `self` as lilliput text.
+    ///
+    /// ```
+    /// use lilliput_core::value::{IntValue, SeqValue, Value};
+    ///
+    /// let value = Value::Seq(SeqValue::from(vec![Value::Int(IntValue::from(42_u8))]));
+    /// assert_eq!(value.to_text(), "[42_u8]");
+    /// assert_eq!(Value::from_text("[42_u8]").unwrap(), value);
+    /// ```
+    pub fn to_text(&self) -> String {
+        to_string(self)
+    }
+
+    /// Parses a value from lilliput `text`.
+    ///
+    /// The entire `text` has to be consumed, except for trailing whitespace.
+    pub fn from_text(text: &str) -> Result<Self> {
+        from_str(text)
+    }
+}
+
+// MARK: - Writing
+
+pub(crate) fn write_int<W>(value: &IntValue, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    match *value {
+        IntValue::Signed(SignedIntValue::I8(value)) => write!(writer, "{value}_i8"),
+        IntValue::Signed(SignedIntValue::I16(value)) => write!(writer, "{value}_i16"),
+        IntValue::Signed(SignedIntValue::I32(value)) => write!(writer, "{value}_i32"),
+        IntValue::Signed(SignedIntValue::I64(value)) => write!(writer, "{value}_i64"),
+        IntValue::Signed(SignedIntValue::I128(value)) => write!(writer, "{value}_i128"),
+        IntValue::Unsigned(UnsignedIntValue::U8(value)) => write!(writer, "{value}_u8"),
+        IntValue::Unsigned(UnsignedIntValue::U16(value)) => write!(writer, "{value}_u16"),
+        IntValue::Unsigned(UnsignedIntValue::U32(value)) => write!(writer, "{value}_u32"),
+        IntValue::Unsigned(UnsignedIntValue::U64(value)) => write!(writer, "{value}_u64"),
+        IntValue::Unsigned(UnsignedIntValue::U128(value)) => write!(writer, "{value}_u128"),
+    }
+}
+
+fn write_float<W>(value: &FloatValue, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    // Rust's `Debug` output for floats is the shortest
+    // representation that parses back to the same value:
+    match *value {
+        FloatValue::F32(value) if value.is_finite() => write!(writer, "{value:?}_f32"),
+        FloatValue::F64(value) if value.is_finite() => write!(writer, "{value:?}_f64"),
+        FloatValue::F32(value) => write!(writer, "f32({:#010x})", value.to_bits()),
+        FloatValue::F64(value) => write!(writer, "f64({:#018x})", value.to_bits()),
+    }
+}
+
+pub(crate) fn write_str<W>(value: &str, writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    writer.write_char('"')?;
+    for char in value.chars() {
+        match char {
+            '"' => writer.write_str("\\\"")?,
+            '\\' => writer.write_str("\\\\")?,
+            '\n' => writer.write_str("\\n")?,
+            '\r' => writer.write_str("\\r")?,
+            '\t' => writer.write_str("\\t")?,
+            char if char.is_control() => write!(writer, "\\u{{{:x}}}", char as u32)?,
+            char => writer.write_char(char)?,
+        }
+    }
+    writer.write_char('"')
+}
+
+pub(crate) fn write_bytes<W>(value: &[u8], writer: &mut W) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    writer.write_str("b\"")?;
+    for &byte in value {
+        match byte {
+            b'"' => writer.write_str("\\\"")?,
+            b'\\' => writer.write_str("\\\\")?,
+            b' '..=b'~' => writer.write_char(byte as char)?,
+            byte => write!(writer, "\\x{byte:02x}")?,
+        }
+    }
+    writer.write_char('"')
+}
+
+// MARK: - Parsing
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    /// The number of seqs and maps enclosing the current position.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_value(&mut self) -> Result<Value> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('[') => self.nested(Self::parse_seq),
+            Some('{') => self.nested(Self::parse_map),
+            Some('"') => self
+                .parse_string()
+                .map(|string| Value::String(StringValue(string))),
+            Some('(') => {
+                self.expect_str("()")?;
+                Ok(Value::Unit(UnitValue))
+            }
+            Some('-' | '+' | '0'..='9') => self.parse_number(),
+            Some('b') if self.rest().starts_with("b\"") => self
+                .parse_bytes()
+                .map(|bytes| Value::Bytes(BytesValue(bytes))),
+            Some('a'..='z') => self.parse_keyword(),
+            _ => Err(self.unexpected("value")),
+        }
+    }
+
+    fn parse_seq(&mut self) -> Result<Value> {
+        self.expect_str("[")?;
+
+        let mut items = Vec::new();
+        while !self.consume_closing(']', items.is_empty())? {
+            items.push(self.parse_value()?);
+        }
+
+        Ok(Value::Seq(SeqValue::from(items)))
+    }
+
+    fn parse_map(&mut self) -> Result<Value> {
+        self.expect_str("{")?;
+
+        let mut map = Map::default();
+        let mut is_first = true;
+        while !self.consume_closing('}', is_first)? {
+            is_first = false;
+            let key = self.parse_value()?;
+            self.skip_whitespace();
+            self.expect_str(":")?;
+            let value = self.parse_value()?;
+            map.insert(key, value);
+        }
+
+        Ok(Value::Map(MapValue::from(map)))
+    }
+
+    /// Runs `parse` one level deeper, failing if that exceeds `MAX_DEPTH`.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.depth >= MAX_DEPTH {
+            return Err(Error::depth_limit_exceeded(Some(self.pos)));
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+
+        result
+    }
+
+    /// Consumes the separator before the next element, returning `true`
+    /// if instead the `closing` delimiter was found (and consumed).
+    fn consume_closing(&mut self, closing: char, is_first: bool) -> Result<bool> {
+        self.skip_whitespace();
+
+        if !is_first && self.peek() != Some(closing) {
+            self.expect_str(",")?;
+            self.skip_whitespace();
+        }
+
+        if self.peek() == Some(closing) {
+            self.pos += closing.len_utf8();
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn parse_keyword(&mut self) -> Result<Value> {
+        let start = self.pos;
+        let ident = self.take_while(|char| char.is_ascii_alphanumeric());
+
+        match ident {
+            "true" => Ok(Value::Bool(BoolValue(true))),
+            "false" => Ok(Value::Bool(BoolValue(false))),
+            "null" => Ok(Value::Null(NullValue)),
+            "ext" => self.parse_ext(),
+            "unknown" => self.parse_unknown(),
+            "f32" => {
+                let bits = self.parse_float_bits()?;
+                let bits = u32::try_from(bits).map_err(|_| self.out_of_range(start))?;
+                Ok(Value::Float(FloatValue::F32(f32::from_bits(bits))))
+            }
+            "f64" => {
+                let bits = self.parse_float_bits()?;
+                Ok(Value::Float(FloatValue::F64(f64::from_bits(bits))))
+            }
+            _ => {
+                self.pos = start;
+                Err(self.unexpected("value"))
+            }
+        }
+    }
+
+    fn parse_ext(&mut self) -> Result<Value> {
+        self.expect_str("(")?;
+        self.skip_whitespace();
+        let start = self.pos;
+        let digits = self.take_while(|char| char.is_ascii_digit());
+        let tag = digits.parse::<u64>().map_err(|_| {
+            self.pos = start;
+            self.unexpected("decimal tag")
+        })?;
+        self.skip_whitespace();
+        self.expect_str(",")?;
+        self.skip_whitespace();
+        let payload = self.parse_bytes()?;
+        self.skip_whitespace();
+        self.expect_str(")")?;
+        Ok(Value::Ext(ExtValue::new(tag, payload)))
+    }
+
+    fn parse_unknown(&mut self) -> Result<Value> {
+        self.expect_str("(0x")?;
+        let start = self.pos;
+        let digits = self.take_while(|char| char.is_ascii_hexdigit());
+        let header = u8::from_str_radix(digits, 16).map_err(|_| {
+            self.pos = start;
+            self.unexpected("hexadecimal header byte")
+        })?;
+        self.skip_whitespace();
+        self.expect_str(",")?;
+        self.skip_whitespace();
+        let body = self.parse_bytes()?;
+        self.skip_whitespace();
+        self.expect_str(")")?;
+        Ok(Value::Unknown(UnknownValue::new(header, body)))
+    }
+
+    fn parse_float_bits(&mut self) -> Result<u64> {
+        self.expect_str("(0x")?;
+        let start = self.pos;
+        let digits = self.take_while(|char| char.is_ascii_hexdigit());
+        let bits = u64::from_str_radix(digits, 16).map_err(|_| {
+            self.pos = start;
+            self.unexpected("hexadecimal float bits")
+        })?;
+        self.expect_str(")")?;
+        Ok(bits)
+    }
+
+    fn parse_number(&mut self) -> Result<Value> {
+        let start = self.pos;
+
+        let literal = self.take_while(|char| {
+            char.is_ascii_digit() || matches!(char, '-' | '+' | '.' | 'e' | 'E')
+        });
+
+        if self.peek() != Some('_') {
+            return Err(self.unexpected("width suffix (e.g. `_u8`, `_f32`)"));
+        }
+        self.pos += 1;
+
+        let suffix_start = self.pos;
+        let suffix = self.take_while(|char| char.is_ascii_alphanumeric());
+
+        macro_rules! parse {
+            ($t:ty) => {
+                literal
+                    .parse::<$t>()
+                    .map_err(|_| self.invalid_literal(literal, stringify!($t), start))
+            };
+        }
+
+        let value = match suffix {
+            "i8" => Value::Int(IntValue::from(parse!(i8)?)),
+            "i16" => Value::Int(IntValue::from(parse!(i16)?)),
+            "i32" => Value::Int(IntValue::from(parse!(i32)?)),
+            "i64" => Value::Int(IntValue::from(parse!(i64)?)),
+            "i128" => Value::Int(IntValue::from(parse!(i128)?)),
+            "u8" => Value::Int(IntValue::from(parse!(u8)?)),
+            "u16" => Value::Int(IntValue::from(parse!(u16)?)),
+            "u32" => Value::Int(IntValue::from(parse!(u32)?)),
+            "u64" => Value::Int(IntValue::from(parse!(u64)?)),
+            "u128" => Value::Int(IntValue::from(parse!(u128)?)),
+            "f32" => Value::Float(FloatValue::F32(parse!(f32)?)),
+            "f64" => Value::Float(FloatValue::F64(parse!(f64)?)),
+            _ => {
+                self.pos = suffix_start;
+                return Err(self.unexpected("width suffix (e.g. `_u8`, `_f32`)"));
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn parse_string(&mut self) -> Result<String> {
+        self.expect_str("\"")?;
+
+        let mut string = String::new();
+        loop {
+            let Some(char) = self.next() else {
+                return Err(Error::end_of_file());
+            };
+
+            match char {
+                '"' => return Ok(string),
+                '\\' => match self.next() {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('n') => string.push('\n'),
+                    Some('r') => string.push('\r'),
+                    Some('t') => string.push('\t'),
+                    Some('u') => string.push(self.parse_unicode_escape()?),
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.unexpected("escape sequence"));
+                    }
+                },
+                char => string.push(char),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self) -> Result<char> {
+        self.expect_str("{")?;
+        let start = self.pos;
+        let digits = self.take_while(|char| char.is_ascii_hexdigit());
+        let char = u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| {
+                self.pos = start;
+                self.unexpected("unicode scalar value")
+            })?;
+        self.expect_str("}")?;
+        Ok(char)
+    }
+
+    fn parse_bytes(&mut self) -> Result<Vec<u8>> {
+        self.expect_str("b\"")?;
+
+        let mut bytes = Vec::new();
+        loop {
+            let Some(char) = self.next() else {
+                return Err(Error::end_of_file());
+            };
+
+            match char {
+                '"' => return Ok(bytes),
+                '\\' => match self.next() {
+                    Some('"') => bytes.push(b'"'),
+                    Some('\\') => bytes.push(b'\\'),
+                    Some('x') => {
+                        let start = self.pos;
+                        let digits = self.rest().get(..2).unwrap_or_default();
+                        let byte = u8::from_str_radix(digits, 16).map_err(|_| {
+                            self.pos = start;
+                            self.unexpected("two hexadecimal digits")
+                        })?;
+                        self.pos += 2;
+                        bytes.push(byte);
+                    }
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.unexpected("escape sequence"));
+                    }
+                },
+                char if char.is_ascii() && !char.is_ascii_control() => bytes.push(char as u8),
+                _ => {
+                    self.pos -= char.len_utf8();
+                    return Err(self.unexpected("printable ASCII char or escape sequence"));
+                }
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn expect_str(&mut self, expected: &str) -> Result<()> {
+        if self.rest().starts_with(expected) {
+            self.pos += expected.len();
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{expected}`")))
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .char_indices()
+            .find(|(_, char)| !predicate(*char))
+            .map_or(rest.len(), |(index, _)| index);
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.pos += char.len_utf8();
+        Some(char)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn rest(&self) -> &'a str {
+        let text: &'a str = self.text;
+        &text[self.pos..]
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        let unexpected = match self.peek() {
+            Some(char) => format!("`{char}`"),
+            None => "end of text".to_owned(),
+        };
+        Error::invalid_value(unexpected, expected.to_owned(), Some(self.pos))
+    }
+
+    fn invalid_literal(&self, literal: &str, ty: &str, pos: usize) -> Error {
+        Error::invalid_value(format!("`{literal}`"), format!("{ty} literal"), Some(pos))
+    }
+
+    fn out_of_range(&self, pos: usize) -> Error {
+        Error::number_out_of_range(Some(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::error::ErrorCode;
+
+    use super::*;
+
+    fn roundtrip(text: &str) {
+        let value = from_str(text).unwrap();
+        assert_eq!(to_string(&value), text);
+    }
+
+    #[test]
+    fn decode_to_string() {
+        let value = Value::from_text(r#"{"key": [1_u8, b"\x00"]}"#).unwrap();
+
+        let mut encoded = Vec::new();
+        let mut encoder =
+            crate::encoder::Encoder::from_writer(crate::io::VecWriter::new(&mut encoded));
+        encoder.encode_value(&value).unwrap();
+
+        assert_eq!(super::decode_to_string(&encoded).unwrap(), value.to_text());
+
+        // Deeply nested single-item seqs, terminated by an int:
+        let mut encoded = vec![0b00110001; 1024 * 1024];
+        encoded.push(0xc1);
+
+        let err = super::decode_to_string(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+    }
+
+    #[test]
+    fn scalars() {
+        roundtrip("42_u8");
+        roundtrip("-42_i16");
+        roundtrip("18446744073709551615_u64");
+        roundtrip("340282366920938463463374607431768211455_u128");
+        roundtrip("-170141183460469231731687303715884105728_i128");
+        roundtrip("1.5_f32");
+        roundtrip("-1e100_f64");
+        roundtrip("f32(0x7fc00000)");
+        roundtrip("f64(0xfff0000000000000)");
+        roundtrip("true");
+        roundtrip("false");
+        roundtrip("()");
+        roundtrip("null");
+        roundtrip(r#"ext(42, b"\x01\x02")"#);
+        roundtrip(r#"unknown(0x2f, b"\x01\x02")"#);
+    }
+
+    #[test]
+    fn widths_are_preserved() {
+        let Value::Int(IntValue::Unsigned(value)) = from_str("1_u32").unwrap() else {
+            panic!("expected unsigned int");
+        };
+        assert_eq!(value, UnsignedIntValue::U32(1));
+
+        let Value::Float(value) = from_str("1.5_f64").unwrap() else {
+            panic!("expected float");
+        };
+        assert!(matches!(value, FloatValue::F64(_)));
+    }
+
+    #[test]
+    fn strings_and_bytes() {
+        roundtrip(r#""lilliput""#);
+        roundtrip(r#""\"quoted\"\n\t\u{0}ñ""#);
+        roundtrip(r#"b"lilliput\x00\xff\"""#);
+
+        assert_eq!(
+            from_str(r#"b"\x2A""#).unwrap(),
+            Value::Bytes(BytesValue(vec![42]))
+        );
+    }
+
+    #[test]
+    fn containers() {
+        roundtrip("[]");
+        roundtrip("{}");
+        roundtrip(r#"[1_u8, "two", [3.0_f32, null], {"four": ()}]"#);
+
+        let value = from_str(" [ 1_u8 ,\n 2_u8 , ] ").unwrap();
+        assert_eq!(to_string(&value), "[1_u8, 2_u8]");
+    }
+
+    #[test]
+    fn errors() {
+        for text in [
+            "42",
+            "256_u8",
+            "-1_u32",
+            "1.5_u8",
+            "1_f16",
+            "[1_u8 2_u8]",
+            "[,]",
+            "{1_u8}",
+            "nil",
+            "b\"ñ\"",
+            "\"\\q\"",
+            "f32(0x1ffffffff)",
+            "ext(0x1, b\"\")",
+            "unknown(0x100, b\"\")",
+            "1_u8 2_u8",
+        ] {
+            assert!(from_str(text).is_err(), "{text:?} should fail to parse");
+        }
+
+        let err = from_str("[1_u8, 2]").unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+        assert_eq!(err.pos(), Some(8));
+
+        let err = from_str("\"unterminated").unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn max_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(from_str(&nested(MAX_DEPTH)).is_ok());
+
+        let err = from_str(&nested(MAX_DEPTH + 1)).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+        assert_eq!(err.pos(), Some(MAX_DEPTH));
+
+        let err = from_str(&"[{".repeat(1_000_000)).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+    }
+
+    proptest! {
+        #[test]
+        fn text_roundtrip(value in Value::arbitrary()) {
+            let text = to_string(&value);
+            let parsed = from_str(&text).unwrap();
+
+            prop_assert_eq!(&parsed, &value);
+            prop_assert_eq!(to_string(&parsed), text);
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Points in time and durations, encoded as extension values.
+//!
+//! Timestamps and durations get encoded with the extension header, followed by
+//! the length of their body, the extension's tag, and their seconds and nanoseconds,
+//! which keeps them compact and interoperable across languages
+//! (see the [specification](https://github.com/lilliput/lilliput/blob/main/spec/Extension.md)).
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//!
+//! use lilliput_core::{
+//!     decoder::Decoder,
+//!     encoder::Encoder,
+//!     io::{SliceReader, VecWriter},
+//!     timestamp::Timestamp,
+//! };
+//!
+//! let now = Timestamp::try_from(SystemTime::now()).unwrap();
+//!
+//! let mut encoded = Vec::new();
+//! let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+//! encoder.encode_timestamp(now).unwrap();
+//! encoder.encode_duration(Duration::from_millis(1500)).unwrap();
+//!
+//! let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+//! assert_eq!(decoder.decode_timestamp().unwrap(), now);
+//! assert_eq!(decoder.decode_duration().unwrap(), Duration::from_millis(1500));
+//! ```
+
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "std")]
+use crate::error::{Error, Result};
+
+/// Extension tag of timestamps.
+pub(crate) const TIMESTAMP_TAG: u64 = 0;
+
+/// Extension tag of durations.
+pub(crate) const DURATION_TAG: u64 = 1;
+
+pub(crate) const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// A point in time, as seconds and nanoseconds relative to the Unix epoch.
+///
+/// Points in time before the epoch have negative seconds, with the
+/// nanoseconds always counting forward, i.e. `-0.5s` is `(-1, 500_000_000)`.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Timestamp {
+    secs: i64,
+    nanos: u32,
+}
+
+impl Timestamp {
+    /// The Unix epoch, i.e. `1970-01-01T00:00:00Z`.
+    pub const UNIX_EPOCH: Self = Self { secs: 0, nanos: 0 };
+
+    /// Creates a timestamp from the seconds and nanoseconds since the Unix epoch,
+    /// returning `None` if `nanos` exceeds a second.
+    pub fn new(secs: i64, nanos: u32) -> Option<Self> {
+        (nanos < NANOS_PER_SEC).then_some(Self { secs, nanos })
+    }
+
+    /// Returns the seconds since the Unix epoch.
+    pub fn secs(&self) -> i64 {
+        self.secs
+    }
+
+    /// Returns the nanoseconds within the second.
+    pub fn nanos(&self) -> u32 {
+        self.nanos
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<SystemTime> for Timestamp {
+    type Error = Error;
+
+    fn try_from(time: SystemTime) -> Result<Self> {
+        let out_of_range = || Error::number_out_of_range(None);
+
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => Ok(Self {
+                secs: since.as_secs().try_into().map_err(|_| out_of_range())?,
+                nanos: since.subsec_nanos(),
+            }),
+            Err(err) => {
+                let before = err.duration();
+                let secs: i64 = before.as_secs().try_into().map_err(|_| out_of_range())?;
+
+                Ok(match before.subsec_nanos() {
+                    0 => Self {
+                        secs: -secs,
+                        nanos: 0,
+                    },
+                    nanos => Self {
+                        secs: -secs - 1,
+                        nanos: NANOS_PER_SEC - nanos,
+                    },
+                })
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = Error;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self> {
+        let secs = Duration::from_secs(timestamp.secs.unsigned_abs());
+        let nanos = Duration::from_nanos(timestamp.nanos.into());
+
+        let time = if timestamp.secs >= 0 {
+            UNIX_EPOCH.checked_add(secs)
+        } else {
+            UNIX_EPOCH.checked_sub(secs)
+        };
+
+        time.and_then(|time| time.checked_add(nanos))
+            .ok_or_else(|| Error::number_out_of_range(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        decoder::Decoder,
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{SliceReader, VecWriter},
+        value::{ExtValue, Value},
+    };
+
+    use super::*;
+
+    #[test]
+    fn system_time() {
+        let cases = [
+            (UNIX_EPOCH, (0, 0)),
+            (UNIX_EPOCH + Duration::from_millis(1500), (1, 500_000_000)),
+            (UNIX_EPOCH - Duration::from_millis(500), (-1, 500_000_000)),
+            (UNIX_EPOCH - Duration::from_secs(2), (-2, 0)),
+        ];
+
+        for (time, (secs, nanos)) in cases {
+            let timestamp = Timestamp::try_from(time).unwrap();
+            assert_eq!((timestamp.secs(), timestamp.nanos()), (secs, nanos));
+            assert_eq!(SystemTime::try_from(timestamp).unwrap(), time);
+        }
+
+        assert_eq!(Timestamp::new(0, NANOS_PER_SEC), None);
+    }
+
+    #[test]
+    fn encode_decode() {
+        let timestamp = Timestamp::new(-1, 500_000_000).unwrap();
+        let duration = Duration::new(90, 1);
+
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_timestamp(timestamp).unwrap();
+        encoder.encode_duration(duration).unwrap();
+
+        // header, body length, tag, seconds, nanoseconds:
+        assert_eq!(
+            encoded[..9],
+            [0x2D, 0xC7, 0xC0, 0xE1, 0x83, 0x1D, 0xCD, 0x65, 0x00]
+        );
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_timestamp().unwrap(), timestamp);
+        assert_eq!(decoder.decode_duration().unwrap(), duration);
+
+        // Mismatching tags:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let err = decoder.decode_duration().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert_eq!(err.pos(), Some(0));
+
+        // Generic decoders see extension values:
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let value = decoder.decode_value().unwrap();
+        assert_eq!(
+            value,
+            Value::Ext(ExtValue::new(TIMESTAMP_TAG, encoded[3..9].to_vec()))
+        );
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+    }
+}
@@ -0,0 +1,201 @@
+//! Re-encoding values from one stream to another, without decoding them into `Value`s.
+//!
+//! ```
+//! use lilliput_core::{
+//!     config::{EncoderConfig, PackingMode},
+//!     decoder::Decoder,
+//!     encoder::Encoder,
+//!     io::{SliceReader, VecWriter},
+//!     transcode::copy_value,
+//! };
+//!
+//! let mut encoded = Vec::new();
+//! let config = EncoderConfig::default().with_packing(PackingMode::None);
+//! let mut encoder = Encoder::new(VecWriter::new(&mut encoded), config);
+//! encoder.encode_seq_stream_start().unwrap();
+//! encoder.encode_u64(1).unwrap();
+//! encoder.encode_str("two").unwrap();
+//! encoder.encode_seq_stream_end().unwrap();
+//!
+//! let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+//!
+//! let mut repacked = Vec::new();
+//! let mut encoder = Encoder::from_writer(VecWriter::new(&mut repacked));
+//! copy_value(&mut decoder, &mut encoder).unwrap();
+//!
+//! assert!(repacked.len() < encoded.len());
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    decoder::Decoder,
+    encoder::Encoder,
+    error::Result,
+    io::{Read, Write},
+    value::ValueKind,
+};
+
+/// Re-encodes the next to-be-decoded value of `decoder` using `encoder`,
+/// without constructing a `Value` for it.
+///
+/// Unlike `Decoder::decode_into_writer()`, which copies values verbatim,
+/// this applies the encoder's config (e.g. its packing) and dictionary.
+///
+/// Sequences and maps get copied item by item, preserving their order
+/// (as well as whether a sequence is streamed), and are written in their plain
+/// layout. Values which are encoded as a whole (i.e. extension and unknown values,
+/// columnar and tabular sequences and front-coded maps) get decoded into a `Value` first.
+pub fn copy_value<'de, R, W>(decoder: &mut Decoder<R>, encoder: &mut Encoder<W>) -> Result<()>
+where
+    R: Read<'de>,
+    W: Write,
+{
+    let mut scratch = Vec::new();
+
+    copy_value_with(decoder, encoder, &mut scratch)
+}
+
+// MARK: - Private
+
+fn copy_value_with<'de, R, W>(
+    decoder: &mut Decoder<R>,
+    encoder: &mut Encoder<W>,
+    scratch: &mut Vec<u8>,
+) -> Result<()>
+where
+    R: Read<'de>,
+    W: Write,
+{
+    match decoder.peek_value_kind()? {
+        ValueKind::Int => encoder.encode_int_value(&decoder.decode_int_value()?),
+        ValueKind::String => {
+            scratch.clear();
+            let value = decoder.decode_str(scratch)?;
+            encoder.encode_str(&value)
+        }
+        ValueKind::Seq if decoder.peek_columnar_seq()? || decoder.peek_tabular_seq()? => {
+            encoder.encode_value(&decoder.decode_value()?)
+        }
+        ValueKind::Seq => match decoder.decode_seq_start()? {
+            Some(len) => {
+                encoder.encode_seq_header(&encoder.header_for_seq_len(len))?;
+
+                for _ in 0..len {
+                    copy_value_with(decoder, encoder, scratch)?;
+                }
+
+                Ok(())
+            }
+            None => {
+                encoder.encode_seq_stream_start()?;
+
+                while !decoder.decode_seq_stream_end()? {
+                    copy_value_with(decoder, encoder, scratch)?;
+                }
+
+                encoder.encode_seq_stream_end()
+            }
+        },
+        ValueKind::Map if decoder.peek_front_coded_map()? => {
+            encoder.encode_value(&decoder.decode_value()?)
+        }
+        ValueKind::Map => {
+            let len = decoder.decode_map_header()?.len();
+            encoder.encode_map_header(&encoder.header_for_map_len(len))?;
+
+            for _ in 0..len {
+                copy_value_with(decoder, encoder, scratch)?; // key
+                copy_value_with(decoder, encoder, scratch)?; // value
+            }
+
+            Ok(())
+        }
+        ValueKind::Float => encoder.encode_float_value(&decoder.decode_float_value()?),
+        ValueKind::Bytes => {
+            scratch.clear();
+            let value = decoder.decode_bytes(scratch)?;
+            encoder.encode_bytes(&value)
+        }
+        ValueKind::Bool => encoder.encode_bool(decoder.decode_bool()?),
+        ValueKind::Unit => {
+            decoder.decode_unit()?;
+            encoder.encode_unit()
+        }
+        ValueKind::Null => {
+            decoder.decode_null()?;
+            encoder.encode_null()
+        }
+        ValueKind::Ext | ValueKind::Unknown => encoder.encode_value(&decoder.decode_value()?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        config::{DecoderConfig, EncoderConfig, PackingMode, UnknownHeaderPolicy},
+        dictionary::Dictionary,
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    fn transcode(encoded: &[u8], config: EncoderConfig) -> Vec<u8> {
+        let decoder_config =
+            DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Collect);
+        let mut decoder = Decoder::new(SliceReader::new(encoded), decoder_config);
+
+        let mut transcoded = Vec::new();
+        let mut encoder = Encoder::new(VecWriter::new(&mut transcoded), config);
+        copy_value(&mut decoder, &mut encoder).unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+
+        transcoded
+    }
+
+    #[test]
+    fn streamed_seq() {
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_seq_stream_start().unwrap();
+        encoder.encode_str("humidity").unwrap();
+        encoder.encode_u64(1).unwrap();
+        encoder.encode_seq_stream_end().unwrap();
+
+        // Re-encoding with the same config results in the same bytes:
+        assert_eq!(transcode(&encoded, EncoderConfig::default()), encoded);
+
+        // ... while a dictionary gets applied:
+        let dictionary: Dictionary = ["humidity"].into_iter().collect();
+        let mut transcoded = Vec::new();
+        let mut encoder =
+            Encoder::from_writer(VecWriter::new(&mut transcoded)).with_dictionary(dictionary);
+        copy_value(
+            &mut Decoder::from_reader(SliceReader::new(&encoded)),
+            &mut encoder,
+        )
+        .unwrap();
+        assert!(transcoded.len() < encoded.len());
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(value in Value::arbitrary(), config in EncoderConfig::arbitrary()) {
+            let mut encoded = Vec::new();
+            Encoder::new(VecWriter::new(&mut encoded), config.clone())
+                .encode_value(&value)
+                .unwrap();
+
+            let transcoded = transcode(&encoded, EncoderConfig::default().with_packing(PackingMode::None));
+            let transcoded = transcode(&transcoded, config);
+
+            let config = DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Collect);
+            let mut decoder = Decoder::new(SliceReader::new(&transcoded), config);
+            prop_assert_eq!(decoder.decode_value().unwrap(), value);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+//! Validation of UTF-8 provided in chunks.
+
+use crate::error::{Error, Result};
+
+/// Validates UTF-8 provided in chunks, which may split chars.
+pub(crate) struct Utf8Validator {
+    /// The leading bytes of a char split by the previous chunk.
+    pending: [u8; 4],
+    pending_len: usize,
+    pos: usize,
+}
+
+impl Utf8Validator {
+    /// Creates a validator, for chunks starting at `pos`.
+    pub(crate) fn new(pos: usize) -> Self {
+        Self {
+            pending: [0b0; 4],
+            pending_len: 0,
+            pos,
+        }
+    }
+
+    /// Validates the next `chunk`, with any char split by its end
+    /// getting validated once completed by subsequent chunks.
+    pub(crate) fn validate(&mut self, mut chunk: &[u8]) -> Result<()> {
+        if self.pending_len > 0 {
+            // Complete the split char, with as many bytes as it may need:
+            let len = (self.pending.len() - self.pending_len).min(chunk.len());
+            let mut buf = self.pending;
+            buf[self.pending_len..][..len].copy_from_slice(&chunk[..len]);
+            let buf = &buf[..(self.pending_len + len)];
+
+            let valid_len = match core::str::from_utf8(buf) {
+                Ok(_) => buf.len(),
+                Err(err) if err.valid_up_to() > 0 => err.valid_up_to(),
+                Err(err) if err.error_len().is_none() => {
+                    // Still incomplete:
+                    self.pending[..buf.len()].copy_from_slice(buf);
+                    self.pending_len = buf.len();
+                    return Ok(());
+                }
+                Err(err) => return Err(Error::utf8(err, Some(self.pos))),
+            };
+
+            let consumed = valid_len - self.pending_len;
+            chunk = &chunk[consumed..];
+            self.pos += valid_len;
+            self.pending_len = 0;
+        }
+
+        match core::str::from_utf8(chunk) {
+            Ok(_) => {
+                self.pos += chunk.len();
+                Ok(())
+            }
+            Err(err) if err.error_len().is_none() => {
+                let split = &chunk[err.valid_up_to()..];
+                self.pending[..split.len()].copy_from_slice(split);
+                self.pending_len = split.len();
+                self.pos += err.valid_up_to();
+                Ok(())
+            }
+            Err(err) => Err(Error::utf8(err, Some(self.pos + err.valid_up_to()))),
+        }
+    }
+
+    /// Finishes validation, failing if the last chunk split a char.
+    pub(crate) fn finish(self) -> Result<()> {
+        match core::str::from_utf8(&self.pending[..self.pending_len]) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::utf8(err, Some(self.pos))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use super::*;
+
+    fn validate_chunks(chunks: &[&[u8]]) -> Result<()> {
+        let mut validator = Utf8Validator::new(0);
+        for chunk in chunks {
+            validator.validate(chunk)?;
+        }
+        validator.finish()
+    }
+
+    #[test]
+    fn split_chars() {
+        let bytes = "a€ñ😀".as_bytes();
+
+        assert!(validate_chunks(&[&bytes[..2], &bytes[2..3], &bytes[3..7], &bytes[7..]]).is_ok());
+        assert!(validate_chunks(&[&bytes[..8], &bytes[8..9], &[], &bytes[9..]]).is_ok());
+
+        // Split char at the end:
+        let error = validate_chunks(&[&bytes[..2]]).unwrap_err();
+        assert_eq!(error.pos(), Some(1));
+    }
+
+    #[test]
+    fn invalid() {
+        let error = validate_chunks(&[b"ab", b"c\xff"]).unwrap_err();
+        assert_eq!(error.pos(), Some(3));
+
+        // Invalid continuation of a split char:
+        let error = validate_chunks(&[b"a\xe2", b"\x82", b"a"]).unwrap_err();
+        assert_eq!(error.pos(), Some(1));
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_splits(value in any::<String>(), splits in proptest::collection::vec(any::<prop::sample::Index>(), 0..4)) {
+            let bytes = value.as_bytes();
+
+            let mut splits: Vec<usize> = splits.iter().map(|split| split.index(bytes.len() + 1)).collect();
+            splits.sort();
+
+            let mut chunks = Vec::new();
+            let mut start = 0;
+            for split in splits {
+                chunks.push(&bytes[start..split]);
+                start = split;
+            }
+            chunks.push(&bytes[start..]);
+
+            prop_assert!(validate_chunks(&chunks).is_ok());
+        }
+    }
+}
@@ -0,0 +1,623 @@
+//! Structural validation of encoded values, without decoding them into `Value`s.
+//!
+//! Validation checks a value's headers, the UTF-8 of its strings,
+//! its nesting depth and its lengths against configured limits,
+//! making it a fast pre-flight for untrusted input:
+//!
+//! ```
+//! use lilliput_core::{
+//!     error::ErrorCode,
+//!     io::SliceReader,
+//!     validate,
+//!     validation::ValidationConfig,
+//! };
+//!
+//! // [1, -2, "three", [4]]
+//! let encoded = [0x34, 0xc1, 0xe3, 0x65, b't', b'h', b'r', b'e', b'e', 0x31, 0xc4];
+//!
+//! let summary = validate(SliceReader::new(&encoded), &ValidationConfig::default()).unwrap();
+//! assert_eq!(summary.ints, 3);
+//! assert_eq!(summary.max_depth, 2);
+//! assert_eq!(summary.size, encoded.len());
+//!
+//! let config = ValidationConfig::default().with_max_depth(1);
+//! let err = validate(SliceReader::new(&encoded), &config).unwrap_err();
+//! assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    config::{DecoderConfig, UnknownHeaderPolicy},
+    decoder::Decoder,
+    error::{Error, Result},
+    io::{Read, SliceReader},
+    value::{FloatValue, IntValue, Value, ValueKind},
+    visit::{ValueVisitor, Walk},
+};
+
+/// The default nesting depth of sequences and maps, upon reaching which validation fails.
+pub const DEFAULT_MAX_DEPTH: u8 = 128;
+
+/// Configuration used for validating values.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ValidationConfig {
+    /// The nesting depth of sequences and maps,
+    /// upon exceeding which validation fails.
+    pub max_depth: u8,
+    /// The maximum length of strings, byte arrays and extension values, if limited.
+    pub max_len: Option<usize>,
+    /// The maximum number of elements of sequences and maps, if limited.
+    pub max_elements: Option<usize>,
+    /// The maximum number of bytes of the value in total, if limited.
+    ///
+    /// Lengths get checked against the limit before skipping any of
+    /// their bytes, so oversized values get rejected upfront.
+    pub max_size: Option<usize>,
+    /// Low-level configuration for decoding values.
+    pub decoder: DecoderConfig,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_len: None,
+            max_elements: None,
+            max_size: None,
+            decoder: DecoderConfig::default(),
+        }
+    }
+}
+
+impl ValidationConfig {
+    /// Returns a config with strict limits, suitable for untrusted input:
+    /// a depth of 32, lengths of up to 1 MiB, up to 65536 elements
+    /// per sequence (or map), and up to 16 MiB in total.
+    pub fn strict() -> Self {
+        Self {
+            max_depth: 32,
+            max_len: Some(1 << 20),
+            max_elements: Some(1 << 16),
+            max_size: Some(16 << 20),
+            decoder: DecoderConfig::default(),
+        }
+    }
+
+    /// Sets max-depth to `max_depth`, returning `self`.
+    pub fn with_max_depth(mut self, max_depth: u8) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets max-len to `max_len`, returning `self`.
+    pub fn with_max_len(mut self, max_len: Option<usize>) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Sets max-elements to `max_elements`, returning `self`.
+    pub fn with_max_elements(mut self, max_elements: Option<usize>) -> Self {
+        self.max_elements = max_elements;
+        self
+    }
+
+    /// Sets max-size to `max_size`, returning `self`.
+    pub fn with_max_size(mut self, max_size: Option<usize>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets decoder to `decoder`, returning `self`.
+    pub fn with_decoder(mut self, decoder: DecoderConfig) -> Self {
+        self.decoder = decoder;
+        self
+    }
+}
+
+/// Statistics on a validated value.
+///
+/// Map keys get counted as values of their respective types,
+/// references to dictionary strings as strings.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Summary {
+    /// Number of integer values.
+    pub ints: usize,
+    /// Number of string values.
+    pub strings: usize,
+    /// Number of sequence values.
+    pub seqs: usize,
+    /// Number of map values.
+    pub maps: usize,
+    /// Number of floating-point values.
+    pub floats: usize,
+    /// Number of byte array values.
+    pub bytes: usize,
+    /// Number of boolean values.
+    pub bools: usize,
+    /// Number of unit values.
+    pub units: usize,
+    /// Number of null values.
+    pub nulls: usize,
+    /// Number of extension values.
+    pub exts: usize,
+    /// Number of values with reserved headers.
+    pub unknowns: usize,
+    /// The maximum nesting depth of sequences and maps.
+    pub max_depth: usize,
+    /// The number of bytes of the value.
+    pub size: usize,
+}
+
+impl Summary {
+    /// Returns the total number of values.
+    pub fn values(&self) -> usize {
+        self.ints
+            + self.strings
+            + self.seqs
+            + self.maps
+            + self.floats
+            + self.bytes
+            + self.bools
+            + self.units
+            + self.nulls
+            + self.exts
+            + self.unknowns
+    }
+}
+
+/// Validates the next encoded value of `reader`, without decoding
+/// it into a `Value`, returning statistics on it.
+///
+/// String bodies are only copied (into a reused buffer) if the reader
+/// doesn't support borrowing, while byte arrays and extension values get skipped.
+/// Any bytes following the value are left unread.
+///
+/// Exceeded limits get reported with specific error codes, i.e.
+/// `StringTooLong`, `BytesTooLong`, `SeqTooLong`, `MapTooLong`,
+/// `DepthLimitExceeded` and (for the total size) `LimitExceeded`,
+/// as do reserved headers (`ReservedMarker`), unless collected.
+pub fn validate<'de, R>(reader: R, config: &ValidationConfig) -> Result<Summary>
+where
+    R: Read<'de>,
+{
+    let mut decoder = Decoder::new(reader, config.decoder);
+    let mut validator = Validator::new(config);
+    let mut scratch = Vec::new();
+
+    validator.validate_value(&mut decoder, &mut scratch)?;
+    validator.summary.size = decoder.pos();
+
+    Ok(validator.summary)
+}
+
+/// Decodes the single encoded value contained in `bytes`,
+/// after validating it against `config` (e.g. `ValidationConfig::strict()`).
+///
+/// Nothing gets allocated for values failing validation, making this the
+/// entry point of choice for untrusted input (e.g. from fuzzers, or the network).
+/// Bytes following the value get rejected with a `TrailingBytes` error.
+///
+/// ```
+/// use lilliput_core::{decode_untrusted, error::ErrorCode, validation::ValidationConfig};
+///
+/// // "lilliput"
+/// let encoded = [0x68, b'l', b'i', b'l', b'l', b'i', b'p', b'u', b't'];
+///
+/// let config = ValidationConfig::strict();
+/// assert!(decode_untrusted(&encoded, &config).is_ok());
+///
+/// let config = ValidationConfig::strict().with_max_len(Some(4));
+/// let err = decode_untrusted(&encoded, &config).unwrap_err();
+/// assert_eq!(err.code(), ErrorCode::StringTooLong);
+///
+/// let err = decode_untrusted(&[0xC1, 0xC2], &ValidationConfig::strict()).unwrap_err();
+/// assert_eq!(err.code(), ErrorCode::TrailingBytes);
+/// assert_eq!(err.pos(), Some(1));
+/// ```
+pub fn decode_untrusted(bytes: &[u8], config: &ValidationConfig) -> Result<Value> {
+    let summary = validate(SliceReader::new(bytes), config)?;
+
+    if summary.size != bytes.len() {
+        return Err(Error::trailing_bytes(
+            bytes.len() - summary.size,
+            Some(summary.size),
+        ));
+    }
+
+    Decoder::new(SliceReader::new(bytes), config.decoder).decode_value()
+}
+
+// MARK: - Private
+
+/// Constructor of the error for a length exceeding its limit.
+type TooLong = fn(usize, usize, Option<usize>) -> Error;
+
+struct Validator<'c> {
+    config: &'c ValidationConfig,
+    summary: Summary,
+    depth: usize,
+    pos: usize,
+}
+
+impl<'c> Validator<'c> {
+    fn new(config: &'c ValidationConfig) -> Self {
+        Self {
+            config,
+            summary: Summary::default(),
+            depth: 0,
+            pos: 0,
+        }
+    }
+
+    fn validate_value<'de, R>(
+        &mut self,
+        decoder: &mut Decoder<R>,
+        scratch: &mut Vec<u8>,
+    ) -> Result<()>
+    where
+        R: Read<'de>,
+    {
+        let pos = decoder.pos();
+        self.pos = pos;
+
+        match decoder.peek_value_kind()? {
+            ValueKind::Int => self.visit_int(decoder.decode_int_value()?)?,
+            ValueKind::String if decoder.peek_dictionary_ref()? => {
+                decoder.skip_value()?;
+                self.summary.strings += 1;
+            }
+            ValueKind::String => {
+                let header = decoder.decode_string_header()?;
+                self.check_len(header.len(), decoder.pos(), Error::string_too_long)?;
+
+                scratch.clear();
+                decoder.decode_str_of(header, scratch)?;
+                self.summary.strings += 1;
+            }
+            ValueKind::Seq if decoder.peek_columnar_seq()? || decoder.peek_tabular_seq()? => {
+                decoder.walk_value(self)?
+            }
+            ValueKind::Seq => {
+                let len = decoder.decode_seq_start()?;
+                self.visit_seq_start(len)?;
+
+                match len {
+                    Some(len) => {
+                        for _ in 0..len {
+                            self.validate_value(decoder, scratch)?;
+                        }
+                    }
+                    None => {
+                        let mut count = 0;
+
+                        while !decoder.decode_seq_stream_end()? {
+                            count += 1;
+                            self.check_elements(count, decoder.pos(), Error::seq_too_long)?;
+                            self.validate_value(decoder, scratch)?;
+                        }
+                    }
+                }
+
+                self.visit_seq_end()?;
+            }
+            ValueKind::Map if decoder.peek_front_coded_map()? => decoder.walk_value(self)?,
+            ValueKind::Map => {
+                let len = decoder.decode_map_header()?.len();
+                self.visit_map_start(len)?;
+
+                for _ in 0..len {
+                    self.validate_value(decoder, scratch)?; // key
+                    self.validate_value(decoder, scratch)?; // value
+                }
+
+                self.visit_map_end()?;
+            }
+            ValueKind::Float => self.visit_float(decoder.decode_float_value()?)?,
+            ValueKind::Bytes => {
+                let header = decoder.decode_bytes_header()?;
+                self.check_len(header.len(), decoder.pos(), Error::bytes_too_long)?;
+
+                decoder.skip_bytes_value_of(header)?;
+                self.summary.bytes += 1;
+            }
+            ValueKind::Bool => self.visit_bool(decoder.decode_bool()?)?,
+            ValueKind::Unit => {
+                decoder.decode_unit()?;
+                self.visit_unit()?;
+            }
+            ValueKind::Null => {
+                decoder.decode_null()?;
+                self.visit_null()?;
+            }
+            ValueKind::Ext => {
+                let header = decoder.decode_ext_header()?;
+                self.check_len(header.len(), decoder.pos(), Error::bytes_too_long)?;
+
+                decoder.skip_ext_value_of(header)?;
+                self.summary.exts += 1;
+            }
+            ValueKind::Unknown
+                if decoder.config().unknown_headers == UnknownHeaderPolicy::Reject =>
+            {
+                return Err(Error::reserved_marker(decoder.peek_byte()?, Some(pos)));
+            }
+            ValueKind::Unknown => {
+                decoder.skip_value()?;
+                self.summary.unknowns += 1;
+            }
+        }
+
+        self.check_size(decoder.pos(), pos)
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        if self.depth >= usize::from(self.config.max_depth) {
+            return Err(Error::depth_limit_exceeded(Some(self.pos)));
+        }
+
+        self.depth += 1;
+        self.summary.max_depth = self.summary.max_depth.max(self.depth);
+
+        Ok(())
+    }
+
+    /// Checks a string's (or byte array's) length against the configured limits,
+    /// before skipping its body, starting at `pos`.
+    fn check_len(&self, len: usize, pos: usize, too_long: TooLong) -> Result<()> {
+        if let Some(max_len) = self.config.max_len {
+            if len > max_len {
+                return Err(too_long(len, max_len, Some(self.pos)));
+            }
+        }
+
+        self.check_size(pos.saturating_add(len), self.pos)
+    }
+
+    /// Checks a sequence's (or map's) element count against the configured limits.
+    fn check_elements(&self, count: usize, pos: usize, too_long: TooLong) -> Result<()> {
+        if let Some(max_elements) = self.config.max_elements {
+            if count > max_elements {
+                return Err(too_long(count, max_elements, Some(pos)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_size(&self, size: usize, pos: usize) -> Result<()> {
+        if let Some(max_size) = self.config.max_size {
+            if size > max_size {
+                return Err(Error::limit_exceeded(size, max_size, Some(pos)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Visitor of columnar and tabular sequences and front-coded maps,
+/// whose contents can't be validated piecemeal.
+impl ValueVisitor for Validator<'_> {
+    fn visit_int(&mut self, _value: IntValue) -> Result<()> {
+        self.summary.ints += 1;
+        Ok(())
+    }
+
+    fn visit_str(&mut self, value: &str) -> Result<()> {
+        self.check_len(value.len(), self.pos, Error::string_too_long)?;
+        self.summary.strings += 1;
+        Ok(())
+    }
+
+    fn visit_float(&mut self, _value: FloatValue) -> Result<()> {
+        self.summary.floats += 1;
+        Ok(())
+    }
+
+    fn visit_bytes(&mut self, value: &[u8]) -> Result<()> {
+        self.check_len(value.len(), self.pos, Error::bytes_too_long)?;
+        self.summary.bytes += 1;
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, _value: bool) -> Result<()> {
+        self.summary.bools += 1;
+        Ok(())
+    }
+
+    fn visit_unit(&mut self) -> Result<()> {
+        self.summary.units += 1;
+        Ok(())
+    }
+
+    fn visit_null(&mut self) -> Result<()> {
+        self.summary.nulls += 1;
+        Ok(())
+    }
+
+    fn visit_seq_start(&mut self, len: Option<usize>) -> Result<Walk> {
+        if let Some(len) = len {
+            self.check_elements(len, self.pos, Error::seq_too_long)?;
+        }
+
+        self.enter()?;
+        self.summary.seqs += 1;
+
+        Ok(Walk::Enter)
+    }
+
+    fn visit_seq_end(&mut self) -> Result<()> {
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn visit_map_start(&mut self, len: usize) -> Result<Walk> {
+        self.check_elements(len, self.pos, Error::map_too_long)?;
+        self.enter()?;
+        self.summary.maps += 1;
+
+        Ok(Walk::Enter)
+    }
+
+    fn visit_map_end(&mut self) -> Result<()> {
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::{EncoderConfig, MapKeyCoding, SeqCoding, UnknownHeaderPolicy},
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{SliceReader, VecWriter},
+        value::{BytesValue, Map, MapValue, SeqValue, StringValue, UnknownValue, Value},
+    };
+
+    use super::*;
+
+    fn encode(value: &Value, config: EncoderConfig) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        Encoder::new(VecWriter::new(&mut encoded), config)
+            .encode_value(value)
+            .unwrap();
+        encoded
+    }
+
+    fn record(id: i64, name: &str) -> Value {
+        let mut map = Map::default();
+        map.insert(
+            Value::String(StringValue::from("id".to_owned())),
+            Value::Int(id.into()),
+        );
+        map.insert(
+            Value::String(StringValue::from("name".to_owned())),
+            Value::String(StringValue::from(name.to_owned())),
+        );
+        Value::Map(MapValue::from(map))
+    }
+
+    #[test]
+    fn summary() {
+        let value = Value::Seq(SeqValue::from(vec![
+            record(1, "lilliput"),
+            record(2, "blefuscu"),
+            Value::Bytes(BytesValue::from(vec![42; 3])),
+        ]));
+
+        let configs = [
+            EncoderConfig::default(),
+            EncoderConfig::default().with_map_keys(MapKeyCoding::FrontCoded),
+            EncoderConfig::default().with_seqs(SeqCoding::Columnar),
+            EncoderConfig::default().with_seqs(SeqCoding::Tabular),
+        ];
+
+        for config in configs {
+            let encoded = encode(&value, config);
+            let summary =
+                validate(SliceReader::new(&encoded), &ValidationConfig::default()).unwrap();
+
+            assert_eq!(summary.ints, 2);
+            assert_eq!(summary.strings, 6);
+            assert_eq!(summary.bytes, 1);
+            assert_eq!(summary.values(), 12);
+            assert_eq!(summary.max_depth, 2);
+            assert_eq!(summary.size, encoded.len());
+        }
+    }
+
+    #[test]
+    fn limits() {
+        let value = Value::Seq(SeqValue::from(vec![record(1, "lilliput")]));
+        let encoded = encode(&value, EncoderConfig::default());
+
+        let validate_with = |config: ValidationConfig| {
+            validate(SliceReader::new(&encoded), &config).map(|summary| summary.size)
+        };
+
+        assert_eq!(
+            validate_with(ValidationConfig::default().with_max_depth(2)).unwrap(),
+            encoded.len()
+        );
+
+        let err = validate_with(ValidationConfig::default().with_max_depth(1)).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+        assert_eq!(err.pos(), Some(1));
+
+        let err = validate_with(ValidationConfig::default().with_max_len(Some(7))).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::StringTooLong);
+
+        let err =
+            validate_with(ValidationConfig::default().with_max_elements(Some(1))).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::MapTooLong);
+        assert_eq!(err.pos(), Some(1));
+
+        let err = validate_with(ValidationConfig::default().with_max_size(Some(encoded.len() - 1)))
+            .unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+
+    #[test]
+    fn invalid() {
+        let config = ValidationConfig::default();
+
+        // ["a", <invalid UTF-8>]
+        let err = validate(SliceReader::new(&[0x32, 0x61, b'a', 0x61, 0xFF]), &config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::Utf8);
+
+        // [1, <missing item>]
+        let err = validate(SliceReader::new(&[0x32, 0xC1]), &config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+
+    #[test]
+    fn unknown_headers() {
+        let value = Value::Unknown(UnknownValue::new(0b0010_1111, vec![1, 2, 3]));
+        let encoded = encode(&value, EncoderConfig::default());
+
+        let err = validate(SliceReader::new(&encoded), &ValidationConfig::default()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::ReservedMarker);
+        assert_eq!(err.pos(), Some(0));
+
+        let config = ValidationConfig::default().with_decoder(
+            DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Collect),
+        );
+        let summary = validate(SliceReader::new(&encoded), &config).unwrap();
+        assert_eq!(summary.unknowns, 1);
+        assert_eq!(summary.size, encoded.len());
+    }
+
+    #[test]
+    fn untrusted() {
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_seq_stream_start().unwrap();
+        for item in 0..3 {
+            encoder.encode_u64(item).unwrap();
+        }
+        encoder.encode_seq_stream_end().unwrap();
+
+        let value = decode_untrusted(&encoded, &ValidationConfig::strict()).unwrap();
+        assert_eq!(
+            value,
+            Value::Seq(SeqValue::from(vec![
+                Value::Int(0_u64.into()),
+                Value::Int(1_u64.into()),
+                Value::Int(2_u64.into()),
+            ]))
+        );
+
+        let config = ValidationConfig::strict().with_max_elements(Some(2));
+        let err = decode_untrusted(&encoded, &config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::SeqTooLong);
+
+        encoded.push(0b0000_0000);
+        let err = decode_untrusted(&encoded, &ValidationConfig::strict()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TrailingBytes);
+        assert_eq!(err.pos(), Some(encoded.len() - 1));
+    }
+}
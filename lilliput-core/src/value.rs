@@ -1,30 +1,64 @@
 //! Values.
 
+use alloc::vec::Vec;
+
 #[cfg(any(test, feature = "testing"))]
-use proptest::{prelude::*, sample::SizeRange};
+use proptest::sample::SizeRange;
 
+mod access;
 mod bool;
 mod bytes;
+#[cfg(feature = "serde")]
+mod de;
+mod ext;
 mod float;
+mod index;
 mod int;
+#[cfg(feature = "json")]
+mod json;
+mod kind;
 mod map;
 mod null;
+mod path;
+mod prune;
+mod sanitize;
 mod seq;
 mod string;
+mod traverse;
 mod unit;
+mod unknown;
+mod value_ref;
 
 pub use self::{
     bool::BoolValue,
     bytes::BytesValue,
+    ext::ExtValue,
     float::FloatValue,
     int::{IntValue, SignedIntValue, UnsignedIntValue},
-    map::{Map, MapValue},
+    kind::ValueKind,
+    map::{Map, MapEntry, MapValue},
     null::NullValue,
+    path::{Path, PathSegment},
+    prune::PrunePolicy,
+    sanitize::{ExcessAction, InvalidKeyAction, SanitizePolicy},
     seq::{Seq, SeqValue},
     string::StringValue,
+    traverse::VisitOrder,
     unit::UnitValue,
+    unknown::UnknownValue,
+    value_ref::ValueRef,
 };
 
+#[cfg(feature = "bigint")]
+pub(crate) use self::ext::BIG_INT_TAG;
+#[cfg(feature = "decimal")]
+pub(crate) use self::ext::DECIMAL_TAG;
+#[cfg(feature = "uuid")]
+pub(crate) use self::ext::UUID_TAG;
+
+#[cfg(feature = "json")]
+pub use self::json::{JsonBytesAction, JsonFloatAction, JsonKeyAction, JsonPolicy};
+
 /// Represents a value.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Value {
@@ -40,8 +74,8 @@ pub enum Value {
     /// Represents a map of key-value pairs.
     ///
     /// By default the map is backed by a `BTreeMap`. Enable the `preserve_order`
-    /// feature of serde_lilliput to use `OrderMap` instead, which preserves
-    /// entries in the order they are inserted into the map.
+    /// feature (of either lilliput-core or lilliput-serde) to use `OrderMap` instead,
+    /// which preserves entries in the order they are inserted into the map.
     Map(MapValue),
 
     /// Represents a floating-point number.
@@ -58,6 +92,14 @@ pub enum Value {
 
     /// Represents a null value.
     Null(NullValue),
+
+    /// Represents an extension value, i.e. a value of a type identified by its tag.
+    Ext(ExtValue),
+
+    /// Represents a value of an unknown type.
+    ///
+    /// Only ever decoded if enabled via `DecoderConfig::unknown_headers`.
+    Unknown(UnknownValue),
 }
 
 impl Default for Value {
@@ -84,6 +126,16 @@ impl From<SeqValue> for Value {
     }
 }
 
+/// Converts the items of `value` into values, as a sequence.
+impl<T> From<Vec<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Vec<T>) -> Self {
+        Self::Seq(value.into_iter().collect())
+    }
+}
+
 impl From<MapValue> for Value {
     fn from(value: MapValue) -> Self {
         Self::Map(value)
@@ -120,8 +172,41 @@ impl From<NullValue> for Value {
     }
 }
 
-impl std::fmt::Debug for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl From<ExtValue> for Value {
+    fn from(value: ExtValue) -> Self {
+        Self::Ext(value)
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl From<num_bigint::BigInt> for Value {
+    fn from(value: num_bigint::BigInt) -> Self {
+        Self::Ext(value.into())
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<rust_decimal::Decimal> for Value {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::Ext(value.into())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Value {
+    fn from(value: uuid::Uuid) -> Self {
+        Self::Ext(value.into())
+    }
+}
+
+impl From<UnknownValue> for Value {
+    fn from(value: UnknownValue) -> Self {
+        Self::Unknown(value)
+    }
+}
+
+impl core::fmt::Debug for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::Int(value) => f.debug_tuple("Int").field(value).finish(),
@@ -133,18 +218,22 @@ impl std::fmt::Debug for Value {
                 Self::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
                 Self::Unit(value) => f.debug_tuple("Unit").field(value).finish(),
                 Self::Null(value) => f.debug_tuple("Null").field(value).finish(),
+                Self::Ext(value) => f.debug_tuple("Ext").field(value).finish(),
+                Self::Unknown(value) => f.debug_tuple("Unknown").field(value).finish(),
             }
         } else {
             match self {
-                Self::Int(value) => std::fmt::Debug::fmt(value, f),
-                Self::String(value) => std::fmt::Debug::fmt(value, f),
-                Self::Seq(value) => std::fmt::Debug::fmt(value, f),
-                Self::Map(value) => std::fmt::Debug::fmt(value, f),
-                Self::Float(value) => std::fmt::Debug::fmt(value, f),
-                Self::Bytes(value) => std::fmt::Debug::fmt(value, f),
-                Self::Bool(value) => std::fmt::Debug::fmt(value, f),
-                Self::Unit(value) => std::fmt::Debug::fmt(value, f),
-                Self::Null(value) => std::fmt::Debug::fmt(value, f),
+                Self::Int(value) => core::fmt::Debug::fmt(value, f),
+                Self::String(value) => core::fmt::Debug::fmt(value, f),
+                Self::Seq(value) => core::fmt::Debug::fmt(value, f),
+                Self::Map(value) => core::fmt::Debug::fmt(value, f),
+                Self::Float(value) => core::fmt::Debug::fmt(value, f),
+                Self::Bytes(value) => core::fmt::Debug::fmt(value, f),
+                Self::Bool(value) => core::fmt::Debug::fmt(value, f),
+                Self::Unit(value) => core::fmt::Debug::fmt(value, f),
+                Self::Null(value) => core::fmt::Debug::fmt(value, f),
+                Self::Ext(value) => core::fmt::Debug::fmt(value, f),
+                Self::Unknown(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
@@ -166,6 +255,8 @@ impl serde::Serialize for Value {
             Value::Bool(value) => value.serialize(serializer),
             Value::Unit(value) => value.serialize(serializer),
             Value::Null(value) => value.serialize(serializer),
+            Value::Ext(value) => value.serialize(serializer),
+            Value::Unknown(value) => value.serialize(serializer),
         }
     }
 }
@@ -176,6 +267,8 @@ impl<'de> serde::Deserialize<'de> for Value {
     where
         D: serde::Deserializer<'de>,
     {
+        use alloc::{borrow::ToOwned, string::String};
+
         struct ValueVisitor;
 
         impl<'de> serde::de::Visitor<'de> for ValueVisitor {
@@ -220,14 +313,11 @@ impl<'de> serde::Deserialize<'de> for Value {
                 Ok(Value::Int(IntValue::from(value)))
             }
 
-            fn visit_i128<E>(self, _value: i128) -> Result<Self::Value, E>
+            fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                Err(serde::de::Error::invalid_type(
-                    serde::de::Unexpected::Other("i128 value"),
-                    &self,
-                ))
+                Ok(Value::Int(IntValue::from(value)))
             }
 
             fn visit_u8<E>(self, value: u8) -> Result<Self::Value, E>
@@ -258,14 +348,11 @@ impl<'de> serde::Deserialize<'de> for Value {
                 Ok(Value::Int(IntValue::from(value)))
             }
 
-            fn visit_u128<E>(self, _value: u128) -> Result<Self::Value, E>
+            fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E>
             where
                 E: serde::de::Error,
             {
-                Err(serde::de::Error::invalid_type(
-                    serde::de::Unexpected::Other("u128 value"),
-                    &self,
-                ))
+                Ok(Value::Int(IntValue::from(value)))
             }
 
             fn visit_f32<E>(self, value: f32) -> Result<Self::Value, E>
@@ -387,6 +474,10 @@ impl<'de> serde::Deserialize<'de> for Value {
 
                         Ok(Value::Map(MapValue::from(values)))
                     }
+                    Some(map::MapKeyClass::Ext) => {
+                        let ext::ExtParts(value) = map.next_value()?;
+                        Ok(Value::Ext(value))
+                    }
                     None => Ok(Value::Map(MapValue::default())),
                 }
             }
@@ -407,12 +498,38 @@ impl<'de> serde::Deserialize<'de> for Value {
     }
 }
 
-#[doc(hidden)]
+/// Parameters for generating arbitrary `Value`s.
+///
+/// # Example
+///
+/// ```
+/// use lilliput_core::value::{Value, ValueArbitraryParameters};
+/// use proptest::prelude::*;
+///
+/// let params = ValueArbitraryParameters::default()
+///     .with_depth(2)
+///     .with_max_string_len(16)
+///     .with_scalar_leaves(true);
+///
+/// let strategy = Value::arbitrary_with(params);
+/// # let _ = strategy;
+/// ```
 #[cfg(any(test, feature = "testing"))]
+#[derive(Clone, Debug)]
 pub struct ValueArbitraryParameters {
+    /// Maximum nesting depth of sequences and maps.
     pub depth: u32,
+    /// Desired total number of nodes.
     pub desired_size: u32,
+    /// Expected number of items per sequence or map.
     pub expected_branch_size: u32,
+    /// Maximum length (in chars) of generated strings, if limited.
+    pub max_string_len: Option<usize>,
+    /// Maximum length of generated byte arrays, if limited.
+    pub max_bytes_len: Option<usize>,
+    /// Whether leaves are restricted to scalar values
+    /// (i.e. integers, floats, bools, units and nulls).
+    pub scalar_leaves: bool,
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -425,34 +542,104 @@ impl Default for ValueArbitraryParameters {
             desired_size: 128,
             // We put up to 5 items per collection
             expected_branch_size: 5,
+            max_string_len: None,
+            max_bytes_len: None,
+            scalar_leaves: false,
         }
     }
 }
 
+#[cfg(any(test, feature = "testing"))]
+impl ValueArbitraryParameters {
+    /// Sets the maximum nesting depth to `depth`, returning `self`.
+    pub fn with_depth(mut self, depth: u32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the desired total number of nodes to `desired_size`, returning `self`.
+    pub fn with_desired_size(mut self, desired_size: u32) -> Self {
+        self.desired_size = desired_size;
+        self
+    }
+
+    /// Sets the expected number of items per collection to `expected_branch_size`, returning `self`.
+    pub fn with_expected_branch_size(mut self, expected_branch_size: u32) -> Self {
+        self.expected_branch_size = expected_branch_size;
+        self
+    }
+
+    /// Limits generated strings to `max_string_len` chars, returning `self`.
+    pub fn with_max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = Some(max_string_len);
+        self
+    }
+
+    /// Limits generated byte arrays to `max_bytes_len` bytes, returning `self`.
+    pub fn with_max_bytes_len(mut self, max_bytes_len: usize) -> Self {
+        self.max_bytes_len = Some(max_bytes_len);
+        self
+    }
+
+    /// Restricts leaves to scalar values, if `scalar_leaves` is `true`, returning `self`.
+    pub fn with_scalar_leaves(mut self, scalar_leaves: bool) -> Self {
+        self.scalar_leaves = scalar_leaves;
+        self
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 impl proptest::arbitrary::Arbitrary for Value {
     type Parameters = ValueArbitraryParameters;
     type Strategy = proptest::prelude::BoxedStrategy<Value>;
 
     fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        use alloc::string::String;
+
         use proptest::prelude::*;
 
         let ValueArbitraryParameters {
             depth,
             desired_size,
             expected_branch_size,
+            max_string_len,
+            max_bytes_len,
+            scalar_leaves,
         } = args;
 
-        let leaf = prop_oneof![
+        let string = match max_string_len {
+            Some(max_len) => proptest::collection::vec(any::<char>(), 0..=max_len)
+                .prop_map(|chars| StringValue::from(String::from_iter(chars)))
+                .boxed(),
+            None => StringValue::arbitrary().boxed(),
+        };
+
+        let bytes = match max_bytes_len {
+            Some(max_len) => proptest::collection::vec(any::<u8>(), 0..=max_len)
+                .prop_map(BytesValue::from)
+                .boxed(),
+            None => BytesValue::arbitrary().boxed(),
+        };
+
+        let scalar = prop_oneof![
             IntValue::arbitrary().prop_map(Value::Int),
-            StringValue::arbitrary().prop_map(Value::String),
             FloatValue::arbitrary().prop_map(Value::Float),
-            BytesValue::arbitrary().prop_map(Value::Bytes),
             BoolValue::arbitrary().prop_map(Value::Bool),
             UnitValue::arbitrary().prop_map(Value::Unit),
             NullValue::arbitrary().prop_map(Value::Null),
         ];
 
+        let leaf = if scalar_leaves {
+            scalar.boxed()
+        } else {
+            prop_oneof![
+                5 => scalar,
+                1 => string.prop_map(Value::String),
+                1 => bytes.prop_map(Value::Bytes),
+            ]
+            .boxed()
+        };
+
         let len: SizeRange = (0..(expected_branch_size as usize)).into();
 
         leaf.prop_recursive(depth, desired_size, expected_branch_size, move |inner| {
@@ -469,6 +656,7 @@ impl proptest::arbitrary::Arbitrary for Value {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
     use test_log::test;
 
     use super::*;
@@ -534,4 +722,45 @@ mod tests {
             "Null(\n    null,\n)"
         );
     }
+    fn leaves(value: &Value) -> Vec<&Value> {
+        match value {
+            Value::Seq(seq) => seq.0.iter().flat_map(leaves).collect(),
+            Value::Map(map) => map
+                .0
+                .iter()
+                .flat_map(|(key, value)| leaves(key).into_iter().chain(leaves(value)))
+                .collect(),
+            leaf => vec![leaf],
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_with_max_lens(
+            value in Value::arbitrary_with(
+                ValueArbitraryParameters::default()
+                    .with_max_string_len(3)
+                    .with_max_bytes_len(5)
+            )
+        ) {
+            for leaf in leaves(&value) {
+                match leaf {
+                    Value::String(string) => prop_assert!(string.0.chars().count() <= 3),
+                    Value::Bytes(bytes) => prop_assert!(bytes.0.len() <= 5),
+                    _ => {}
+                }
+            }
+        }
+
+        #[test]
+        fn arbitrary_with_scalar_leaves(
+            value in Value::arbitrary_with(
+                ValueArbitraryParameters::default().with_scalar_leaves(true)
+            )
+        ) {
+            for leaf in leaves(&value) {
+                prop_assert!(!matches!(leaf, Value::String(_) | Value::Bytes(_)));
+            }
+        }
+    }
 }
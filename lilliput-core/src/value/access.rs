@@ -0,0 +1,266 @@
+use super::{IntValue, MapValue, SeqValue, Value};
+
+impl Value {
+    /// Returns `true` if `self` is an integer.
+    pub fn is_int(&self) -> bool {
+        matches!(self, Value::Int(_))
+    }
+
+    /// Returns `true` if `self` is a string.
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::String(_))
+    }
+
+    /// Returns `true` if `self` is a sequence.
+    pub fn is_seq(&self) -> bool {
+        matches!(self, Value::Seq(_))
+    }
+
+    /// Returns `true` if `self` is a map.
+    pub fn is_map(&self) -> bool {
+        matches!(self, Value::Map(_))
+    }
+
+    /// Returns `true` if `self` is a floating-point number.
+    pub fn is_float(&self) -> bool {
+        matches!(self, Value::Float(_))
+    }
+
+    /// Returns `true` if `self` is a byte array.
+    pub fn is_bytes(&self) -> bool {
+        matches!(self, Value::Bytes(_))
+    }
+
+    /// Returns `true` if `self` is a boolean.
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Value::Bool(_))
+    }
+
+    /// Returns `true` if `self` is a unit value.
+    pub fn is_unit(&self) -> bool {
+        matches!(self, Value::Unit(_))
+    }
+
+    /// Returns `true` if `self` is a null value.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null(_))
+    }
+
+    /// Returns `true` if `self` is a value of an unknown type.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Value::Unknown(_))
+    }
+
+    /// Returns `true` if `self` is an integer that fits into an `i64`.
+    pub fn is_i64(&self) -> bool {
+        self.as_i64().is_some()
+    }
+
+    /// Returns `true` if `self` is an integer that fits into a `u64`.
+    pub fn is_u64(&self) -> bool {
+        self.as_u64().is_some()
+    }
+
+    /// Returns the integer, if `self` is an integer.
+    pub fn as_int(&self) -> Option<IntValue> {
+        match self {
+            Value::Int(int) => Some(*int),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer as an `i64`, if `self` is an integer that fits into one,
+    /// regardless of its signedness and width.
+    ///
+    /// ```
+    /// use lilliput_core::value::{IntValue, Value};
+    ///
+    /// assert_eq!(Value::Int(IntValue::from(42_u128)).as_i64(), Some(42));
+    /// assert_eq!(Value::Int(IntValue::from(u64::MAX)).as_i64(), None);
+    /// ```
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128()?.try_into().ok()
+    }
+
+    /// Returns the integer as a `u64`, if `self` is an integer that fits into one,
+    /// regardless of its signedness and width.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_u128()?.try_into().ok()
+    }
+
+    /// Returns the integer as an `i128`, if `self` is an integer that fits into one.
+    pub fn as_i128(&self) -> Option<i128> {
+        self.as_int()?.try_into().ok()
+    }
+
+    /// Returns the integer as a `u128`, if `self` is an integer that fits into one.
+    pub fn as_u128(&self) -> Option<u128> {
+        self.as_int()?.try_into().ok()
+    }
+
+    /// Returns the number as an `f64`, if `self` is a floating-point number or an integer.
+    ///
+    /// Integers beyond `±2^53` get rounded to the nearest representable `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(float) => Some(float.as_f64()),
+            Value::Int(IntValue::Signed(_)) => self.as_i128().map(|int| int as f64),
+            Value::Int(IntValue::Unsigned(_)) => self.as_u128().map(|int| int as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if `self` is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if `self` is a byte array.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// Returns the UUID, if `self` is a UUID extension value,
+    /// or a byte array of exactly 16 bytes.
+    ///
+    /// ```
+    /// use lilliput_core::value::Value;
+    ///
+    /// let uuid = uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+    ///
+    /// assert_eq!(Value::from(uuid).as_uuid(), Some(uuid));
+    /// ```
+    #[cfg(feature = "uuid")]
+    pub fn as_uuid(&self) -> Option<uuid::Uuid> {
+        match self {
+            Value::Ext(ext) => ext.as_uuid(),
+            Value::Bytes(bytes) => uuid::Uuid::from_slice(bytes.as_slice()).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the big integer, if `self` is a big integer extension value, or an integer.
+    ///
+    /// ```
+    /// use lilliput_core::value::{IntValue, Value};
+    /// use num_bigint::BigInt;
+    ///
+    /// let big_int: BigInt = "-1267650600228229401496703205376".parse().unwrap();
+    ///
+    /// assert_eq!(Value::from(big_int.clone()).as_big_int(), Some(big_int));
+    /// assert_eq!(Value::Int(IntValue::from(42_u8)).as_big_int(), Some(BigInt::from(42)));
+    /// ```
+    #[cfg(feature = "bigint")]
+    pub fn as_big_int(&self) -> Option<num_bigint::BigInt> {
+        match self {
+            Value::Ext(ext) => ext.as_big_int(),
+            Value::Int(IntValue::Signed(_)) => self.as_i128().map(From::from),
+            Value::Int(IntValue::Unsigned(_)) => self.as_u128().map(From::from),
+            _ => None,
+        }
+    }
+
+    /// Returns the decimal, if `self` is a decimal extension value,
+    /// or an integer that fits into one.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<rust_decimal::Decimal> {
+        match self {
+            Value::Ext(ext) => ext.as_decimal(),
+            Value::Int(_) => {
+                rust_decimal::Decimal::try_from_i128_with_scale(self.as_i128()?, 0).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, if `self` is a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(bool) => Some(bool.0),
+            _ => None,
+        }
+    }
+
+    /// Returns the sequence, if `self` is a sequence.
+    pub fn as_seq(&self) -> Option<&SeqValue> {
+        match self {
+            Value::Seq(seq) => Some(seq),
+            _ => None,
+        }
+    }
+
+    /// Returns the map, if `self` is a map.
+    pub fn as_map(&self) -> Option<&MapValue> {
+        match self {
+            Value::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::{BoolValue, BytesValue, FloatValue, NullValue, StringValue};
+
+    use super::*;
+
+    #[test]
+    fn predicates() {
+        let value = Value::Null(NullValue);
+        assert!(value.is_null());
+        assert!(!value.is_int());
+
+        let value = Value::Int(IntValue::from(-1_i8));
+        assert!(value.is_int());
+        assert!(value.is_i64());
+        assert!(!value.is_u64());
+
+        assert!(Value::Seq(SeqValue::default()).is_seq());
+        assert!(!Value::Seq(SeqValue::default()).is_map());
+    }
+
+    #[test]
+    fn ints() {
+        let value = Value::Int(IntValue::from(-1_i8));
+        assert_eq!(value.as_i64(), Some(-1));
+        assert_eq!(value.as_u64(), None);
+        assert_eq!(value.as_f64(), Some(-1.0));
+
+        let value = Value::Int(IntValue::from(u128::MAX));
+        assert_eq!(value.as_u128(), Some(u128::MAX));
+        assert_eq!(value.as_i128(), None);
+        assert_eq!(value.as_u64(), None);
+        assert_eq!(value.as_f64(), Some(u128::MAX as f64));
+
+        let value = Value::Int(IntValue::from(i64::MIN));
+        assert_eq!(value.as_i64(), Some(i64::MIN));
+
+        assert_eq!(Value::Float(FloatValue::from(1.5_f32)).as_f64(), Some(1.5));
+        assert_eq!(Value::Float(FloatValue::from(1.5_f32)).as_i64(), None);
+    }
+
+    #[test]
+    fn references() {
+        let value = Value::String(StringValue::from("a".to_owned()));
+        assert_eq!(value.as_str(), Some("a"));
+        assert_eq!(value.as_bytes(), None);
+
+        let value = Value::Bytes(BytesValue::from(vec![1, 2]));
+        assert_eq!(value.as_bytes(), Some(&[1, 2][..]));
+
+        assert_eq!(Value::Bool(BoolValue::from(true)).as_bool(), Some(true));
+        assert_eq!(
+            Value::Seq(SeqValue::default()).as_seq(),
+            Some(&SeqValue::default())
+        );
+        assert_eq!(Value::Seq(SeqValue::default()).as_map(), None);
+    }
+}
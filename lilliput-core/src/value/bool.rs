@@ -20,14 +20,14 @@ impl From<BoolValue> for bool {
     }
 }
 
-impl std::fmt::Debug for BoolValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for BoolValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:?}", self.0)
     }
 }
 
-impl std::fmt::Display for BoolValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for BoolValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -82,16 +82,34 @@ mod tests {
         assert_eq!(format!("{:#?}", BoolValue::from(true)), "true");
     }
 
+    #[test]
+    fn encoded_within_header() {
+        for (value, byte) in [(false, 0b00000010), (true, 0b00000011)] {
+            let mut encoded: Vec<u8> = Vec::new();
+            let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+            encoder.encode_bool(value).unwrap();
+            assert_eq!(encoded, [byte]);
+
+            let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+            decoder.skip_value().unwrap();
+            assert_eq!(decoder.pos(), 1);
+        }
+    }
+
     proptest! {
         #[test]
         fn encode_decode_roundtrip(value in BoolValue::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
             let writer = VecWriter::new(&mut encoded);
-            let mut encoder = Encoder::new(writer, config);
+            let mut encoder = Encoder::new(writer, config.clone());
             encoder.encode_bool(value.0).unwrap();
             prop_assert_eq!(encoded.len(), 1);
 
-            prop_assert!(encoded.len() == 1);
+            let mut encoded_value: Vec<u8> = Vec::new();
+            let writer = VecWriter::new(&mut encoded_value);
+            let mut encoder = Encoder::new(writer, config);
+            encoder.encode_value(&Value::Bool(value)).unwrap();
+            prop_assert_eq!(&encoded_value, &encoded);
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
@@ -1,9 +1,11 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::*;
 #[cfg(any(test, feature = "testing"))]
 use proptest_derive::Arbitrary;
 
-use crate::binary::BytesSlice;
+use crate::{binary::BytesSlice, error::Error};
 
 /// Represents a byte sequence.
 #[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
@@ -30,6 +32,106 @@ impl BytesValue {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the bytes as a lowercase hex string, with two digits per byte.
+    pub fn to_hex(&self) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let mut hex = String::with_capacity(self.0.len() * 2);
+        for byte in &self.0 {
+            hex.push(DIGITS[(byte >> 4) as usize] as char);
+            hex.push(DIGITS[(byte & 0x0f) as usize] as char);
+        }
+        hex
+    }
+
+    /// Parses a byte sequence from a hex string, with two digits per byte.
+    ///
+    /// Both lowercase and uppercase digits are accepted.
+    pub fn from_hex(hex: &str) -> crate::error::Result<Self> {
+        fn digit(byte: u8) -> Option<u8> {
+            match byte {
+                b'0'..=b'9' => Some(byte - b'0'),
+                b'a'..=b'f' => Some(byte - b'a' + 10),
+                b'A'..=b'F' => Some(byte - b'A' + 10),
+                _ => None,
+            }
+        }
+
+        let invalid = || Error::invalid_value(hex.to_owned(), "hex string".to_owned(), None);
+
+        if hex.len() % 2 != 0 {
+            return Err(invalid());
+        }
+
+        hex.as_bytes()
+            .chunks_exact(2)
+            .map(|pair| Some((digit(pair[0])? << 4) | digit(pair[1])?))
+            .collect::<Option<Vec<u8>>>()
+            .map(Self)
+            .ok_or_else(invalid)
+    }
+
+    /// Returns the bytes as a standard, padded base64 string.
+    ///
+    /// Only available with the `base64` feature enabled.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        use base64::Engine as _;
+
+        base64::engine::general_purpose::STANDARD.encode(&self.0)
+    }
+
+    /// Parses a byte sequence from a standard, padded base64 string.
+    ///
+    /// Only available with the `base64` feature enabled.
+    #[cfg(feature = "base64")]
+    pub fn from_base64(base64: &str) -> crate::error::Result<Self> {
+        use base64::Engine as _;
+
+        base64::engine::general_purpose::STANDARD
+            .decode(base64)
+            .map(Self)
+            .map_err(|_| Error::invalid_value(base64.to_owned(), "base64 string".to_owned(), None))
+    }
+
+    /// Returns a displayable representation of the bytes that shows
+    /// at most `max_len` of them, followed by a count of the omitted ones.
+    ///
+    /// Useful for logging values that might contain large blobs.
+    pub fn display_truncated(&self, max_len: usize) -> impl core::fmt::Display + '_ {
+        TruncatedBytes {
+            bytes: &self.0,
+            max_len,
+        }
+    }
+}
+
+struct TruncatedBytes<'a> {
+    bytes: &'a [u8],
+    max_len: usize,
+}
+
+impl core::fmt::Display for TruncatedBytes<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.bytes.len() <= self.max_len {
+            return core::fmt::Display::fmt(&BytesSlice(self.bytes), f);
+        }
+
+        let (shown, omitted) = self.bytes.split_at(self.max_len);
+
+        write!(f, "[")?;
+        for (index, byte) in BytesSlice(shown).iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{byte:0>2x}")?;
+        }
+        if !shown.is_empty() {
+            write!(f, ", ")?;
+        }
+        write!(f, "… ({} more bytes)]", omitted.len())
+    }
 }
 
 impl From<Vec<u8>> for BytesValue {
@@ -50,15 +152,15 @@ impl From<BytesValue> for Vec<u8> {
     }
 }
 
-impl std::fmt::Debug for BytesValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Debug::fmt(&BytesSlice(&self.0), f)
+impl core::fmt::Debug for BytesValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(&BytesSlice(&self.0), f)
     }
 }
 
-impl std::fmt::Display for BytesValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&BytesSlice(&self.0), f)
+impl core::fmt::Display for BytesValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&BytesSlice(&self.0), f)
     }
 }
 
@@ -120,7 +222,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_truncated() {
+        let value = BytesValue::from(vec![1, 2, 3, 4, 5]);
+
+        assert_eq!(
+            format!("{}", value.display_truncated(5)),
+            "[01, 02, 03, 04, 05]"
+        );
+        assert_eq!(
+            format!("{}", value.display_truncated(2)),
+            "[01, 02, … (3 more bytes)]"
+        );
+        assert_eq!(
+            format!("{}", value.display_truncated(0)),
+            "[… (5 more bytes)]"
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let value = BytesValue::from(vec![0x00, 0x2a, 0xff]);
+
+        assert_eq!(value.to_hex(), "002aff");
+        assert_eq!(BytesValue::from_hex("002AFF").unwrap(), value);
+        assert_eq!(BytesValue::from_hex("").unwrap(), BytesValue::default());
+
+        assert!(BytesValue::from_hex("002af").is_err());
+        assert!(BytesValue::from_hex("00 2a").is_err());
+        assert!(BytesValue::from_hex("0ä").is_err());
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn base64() {
+        let value = BytesValue::from(b"lilliput".to_vec());
+
+        assert_eq!(value.to_base64(), "bGlsbGlwdXQ=");
+        assert_eq!(BytesValue::from_base64("bGlsbGlwdXQ=").unwrap(), value);
+
+        assert!(BytesValue::from_base64("bGlsbGlwdXQ").is_err());
+    }
+
     proptest! {
+        #[test]
+        fn hex_roundtrip(value in BytesValue::arbitrary()) {
+            prop_assert_eq!(BytesValue::from_hex(&value.to_hex()).unwrap(), value);
+        }
+
         #[test]
         fn encode_decode_roundtrip(value in BytesValue::arbitrary(), config in EncoderConfig::arbitrary()) {
             let mut encoded: Vec<u8> = Vec::new();
@@ -0,0 +1,413 @@
+use alloc::{borrow::ToOwned, format, string::ToString, vec};
+
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    IntoDeserializer,
+};
+
+use crate::{error::Error, marker::Marker};
+
+use super::{
+    BytesValue, ExtValue, FloatValue, IntValue, SignedIntValue, StringValue, UnsignedIntValue,
+    Value,
+};
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Int(IntValue::Signed(value)) => match value {
+                SignedIntValue::I8(value) => visitor.visit_i8(value),
+                SignedIntValue::I16(value) => visitor.visit_i16(value),
+                SignedIntValue::I32(value) => visitor.visit_i32(value),
+                SignedIntValue::I64(value) => visitor.visit_i64(value),
+                SignedIntValue::I128(value) => visitor.visit_i128(value),
+            },
+            Value::Int(IntValue::Unsigned(value)) => match value {
+                UnsignedIntValue::U8(value) => visitor.visit_u8(value),
+                UnsignedIntValue::U16(value) => visitor.visit_u16(value),
+                UnsignedIntValue::U32(value) => visitor.visit_u32(value),
+                UnsignedIntValue::U64(value) => visitor.visit_u64(value),
+                UnsignedIntValue::U128(value) => visitor.visit_u128(value),
+            },
+            Value::String(value) => visitor.visit_string(value.into_string()),
+            Value::Seq(value) => {
+                let mut deserializer = SeqDeserializer::new(value.into_vec().into_iter());
+                let value = visitor.visit_seq(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            }
+            Value::Map(value) => {
+                let mut deserializer = MapDeserializer::new(value.into_map().into_iter());
+                let value = visitor.visit_map(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            }
+            Value::Float(value) => match value {
+                FloatValue::F32(value) => visitor.visit_f32(value),
+                FloatValue::F64(value) => visitor.visit_f64(value),
+            },
+            Value::Bytes(value) => visitor.visit_byte_buf(value.into_vec()),
+            Value::Bool(value) => visitor.visit_bool(value.0),
+            Value::Unit(_) => visitor.visit_unit(),
+            Value::Null(_) => visitor.visit_none(),
+            Value::Ext(value) => visit_ext(value, visitor),
+            Value::Unknown(value) => visitor.visit_byte_buf(value.body),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Null(_) => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // Tolerates nulls, as emitted for units by JSON-compatible serializers:
+        match self {
+            Value::Null(_) => visitor.visit_unit(),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Ext(value) => visitor.visit_newtype_struct(ext_parts(value)),
+            value => visitor.visit_newtype_struct(value),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            variant @ (Value::Int(_) | Value::String(_)) => visitor.visit_enum(EnumDeserializer {
+                variant,
+                value: None,
+            }),
+            Value::Map(value) => {
+                let len = value.len();
+
+                let (variant, value) = match (len, value.into_map().into_iter().next()) {
+                    (1, Some(entry)) => entry,
+                    (len, _) => return Err(invalid_enum_map_len(len)),
+                };
+
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            other => Err(invalid_enum_type(&other)),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // Ignored values don't need to be walked:
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &'de Value {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::String(value) => visitor.visit_borrowed_str(value.as_str()),
+            Value::Seq(value) => {
+                let mut deserializer = SeqDeserializer::new(value.as_slice().iter());
+                let value = visitor.visit_seq(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            }
+            Value::Map(value) => {
+                let mut deserializer = MapDeserializer::new(value.as_map_ref().iter());
+                let value = visitor.visit_map(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            }
+            Value::Bytes(value) => visitor.visit_borrowed_bytes(value.as_slice()),
+            Value::Unknown(value) => visitor.visit_borrowed_bytes(&value.body),
+            // Extension values get deserialized as their parts, which need to be owned:
+            Value::Ext(value) => visit_ext(value.clone(), visitor),
+            // Scalars are cheap to copy, so borrowing them gains nothing:
+            Value::Int(_) | Value::Float(_) | Value::Bool(_) | Value::Unit(_) | Value::Null(_) => {
+                self.clone().deserialize_any(visitor)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Null(_) => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        // Tolerates nulls, as emitted for units by JSON-compatible serializers:
+        match self {
+            Value::Null(_) => visitor.visit_unit(),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            Value::Ext(value) => visitor.visit_newtype_struct(ext_parts(value.clone())),
+            value => visitor.visit_newtype_struct(value),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self {
+            variant @ (Value::Int(_) | Value::String(_)) => visitor.visit_enum(EnumDeserializer {
+                variant,
+                value: None,
+            }),
+            Value::Map(value) => {
+                let map = value.as_map_ref();
+
+                let (variant, value) = match (map.len(), map.iter().next()) {
+                    (1, Some(entry)) => entry,
+                    (len, _) => return Err(invalid_enum_map_len(len)),
+                };
+
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            other => Err(invalid_enum_type(other)),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct
+        identifier
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for &'de Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// Deserializes an enum from its variant's discriminant (index or name),
+/// and its payload, if any.
+struct EnumDeserializer<D> {
+    variant: D,
+    value: Option<D>,
+}
+
+impl<'de, D> de::EnumAccess<'de> for EnumDeserializer<D>
+where
+    D: de::Deserializer<'de, Error = Error>,
+{
+    type Error = Error;
+    type Variant = VariantDeserializer<D>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(self.variant)?;
+
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+struct VariantDeserializer<D> {
+    value: Option<D>,
+}
+
+impl<D> VariantDeserializer<D> {
+    fn into_payload(self, expected: &str) -> Result<D, Error> {
+        self.value.ok_or_else(|| {
+            Error::invalid_type("unit variant".to_owned(), expected.to_owned(), None)
+        })
+    }
+}
+
+impl<'de, D> de::VariantAccess<'de> for VariantDeserializer<D>
+where
+    D: de::Deserializer<'de, Error = Error>,
+{
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.into_payload("newtype variant")?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_payload("tuple variant")?.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_payload("struct variant")?
+            .deserialize_any(visitor)
+    }
+}
+
+/// Returns the parts (i.e. tag and payload) of an extension value, as a sequence.
+fn ext_parts(value: ExtValue) -> Value {
+    Value::from(vec![
+        Value::Int(IntValue::from(value.tag)),
+        Value::Bytes(BytesValue::from(value.payload)),
+    ])
+}
+
+/// Visits an extension value as a map with a single entry, keyed by
+/// `ExtValue::NEWTYPE_NAME`, which `Value`'s visitor recognizes as such.
+fn visit_ext<'de, V>(value: ExtValue, visitor: V) -> Result<V::Value, Error>
+where
+    V: de::Visitor<'de>,
+{
+    let key = Value::String(StringValue::from(ExtValue::NEWTYPE_NAME.to_owned()));
+    let entry = (key, ext_parts(value));
+
+    let mut deserializer = MapDeserializer::new(core::iter::once(entry));
+    let value = visitor.visit_map(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+fn invalid_enum_map_len(len: usize) -> Error {
+    Error::invalid_length(
+        format!("map of length {len}"),
+        "map of length 1".to_owned(),
+        None,
+    )
+}
+
+fn invalid_enum_type(value: &Value) -> Error {
+    Error::invalid_type(
+        marker_of(value).to_string(),
+        "int, string or map".to_owned(),
+        None,
+    )
+}
+
+fn marker_of(value: &Value) -> Marker {
+    match value {
+        Value::Int(_) => Marker::Int,
+        Value::String(_) => Marker::String,
+        Value::Seq(_) => Marker::Seq,
+        Value::Map(_) => Marker::Map,
+        Value::Float(_) => Marker::Float,
+        Value::Bytes(_) => Marker::Bytes,
+        Value::Bool(_) => Marker::Bool,
+        Value::Unit(_) => Marker::Unit,
+        Value::Null(_) => Marker::Null,
+        Value::Ext(_) => Marker::Seq,
+        Value::Unknown(value) => Marker::detect(value.header),
+    }
+}
@@ -0,0 +1,405 @@
+use alloc::vec::Vec;
+
+#[cfg(any(test, feature = "testing"))]
+use proptest::prelude::*;
+#[cfg(any(test, feature = "testing"))]
+use proptest_derive::Arbitrary;
+
+use crate::binary::BytesSlice;
+#[cfg(any(feature = "uuid", feature = "bigint", feature = "decimal"))]
+use crate::{
+    decoder::Decoder,
+    encoder::Encoder,
+    io::{SliceReader, VecWriter},
+};
+
+/// Extension tag of UUIDs.
+#[cfg(feature = "uuid")]
+pub(crate) const UUID_TAG: u64 = 2;
+
+/// Extension tag of big integers.
+#[cfg(feature = "bigint")]
+pub(crate) const BIG_INT_TAG: u64 = 3;
+
+/// Extension tag of decimals.
+#[cfg(feature = "decimal")]
+pub(crate) const DECIMAL_TAG: u64 = 4;
+
+/// Represents an extension value, i.e. a value of a type identified by its tag,
+/// such as a timestamp, or an application-specific type.
+///
+/// The value's payload is kept in its encoded form, leaving its
+/// interpretation to whoever knows about the extension's tag.
+#[cfg_attr(any(test, feature = "testing"), derive(Arbitrary))]
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ExtValue {
+    /// The value's tag.
+    pub tag: u64,
+    /// The value's payload, in its encoded form.
+    pub payload: Vec<u8>,
+}
+
+impl ExtValue {
+    /// The name of the newtype struct used for (de)serializing extension values via serde.
+    ///
+    /// The newtype struct wraps a tuple of the value's tag and payload bytes,
+    /// which lilliput's serializers recognize and encode as extension value,
+    /// while other formats encode them as plain tuple.
+    #[doc(hidden)]
+    pub const NEWTYPE_NAME: &'static str = "$lilliput::Ext";
+
+    /// Creates an extension value from its `tag` and encoded `payload`.
+    pub fn new(tag: u64, payload: Vec<u8>) -> Self {
+        Self { tag, payload }
+    }
+
+    /// Returns the UUID, if `self` is a UUID extension value.
+    #[cfg(feature = "uuid")]
+    pub fn as_uuid(&self) -> Option<uuid::Uuid> {
+        self.decode_as(|decoder| decoder.decode_uuid())
+    }
+
+    /// Returns the big integer, if `self` is a big integer extension value.
+    #[cfg(feature = "bigint")]
+    pub fn as_big_int(&self) -> Option<num_bigint::BigInt> {
+        self.decode_as(|decoder| decoder.decode_big_int())
+    }
+
+    /// Returns the decimal, if `self` is a decimal extension value.
+    #[cfg(feature = "decimal")]
+    pub fn as_decimal(&self) -> Option<rust_decimal::Decimal> {
+        self.decode_as(|decoder| decoder.decode_decimal())
+    }
+
+    // MARK: - Private
+
+    /// Decodes `self` from its encoded form, using `decode`.
+    #[cfg(any(feature = "uuid", feature = "bigint", feature = "decimal"))]
+    fn decode_as<T, F>(&self, decode: F) -> Option<T>
+    where
+        F: FnOnce(&mut Decoder<SliceReader<'_>>) -> crate::error::Result<T>,
+    {
+        let mut encoded = Vec::with_capacity(self.payload.len() + 20);
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_ext_value(self)
+            .ok()?;
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let value = decode(&mut decoder).ok()?;
+
+        (decoder.pos() == encoded.len()).then_some(value)
+    }
+
+    /// Encodes an extension value using `encode`, returning it as `ExtValue`.
+    #[cfg(any(feature = "uuid", feature = "bigint", feature = "decimal"))]
+    fn encode_with<F>(encode: F) -> Self
+    where
+        F: FnOnce(&mut Encoder<VecWriter<'_>>) -> crate::error::Result<()>,
+    {
+        let mut encoded = Vec::new();
+        encode(&mut Encoder::from_writer(VecWriter::new(&mut encoded)))
+            .expect("extension value should be encodable");
+
+        Decoder::from_reader(SliceReader::new(&encoded))
+            .decode_ext_value()
+            .expect("extension value should be decodable")
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for ExtValue {
+    fn from(value: uuid::Uuid) -> Self {
+        Self::encode_with(|encoder| encoder.encode_uuid(&value))
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl From<num_bigint::BigInt> for ExtValue {
+    fn from(value: num_bigint::BigInt) -> Self {
+        Self::encode_with(|encoder| encoder.encode_big_int(&value))
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl From<rust_decimal::Decimal> for ExtValue {
+    fn from(value: rust_decimal::Decimal) -> Self {
+        Self::encode_with(|encoder| encoder.encode_decimal(&value))
+    }
+}
+
+impl core::fmt::Debug for ExtValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ext({}, ", self.tag)?;
+        core::fmt::Debug::fmt(&BytesSlice(&self.payload), f)?;
+        write!(f, ")")
+    }
+}
+
+impl core::fmt::Display for ExtValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ext({}, ", self.tag)?;
+        core::fmt::Display::fmt(&BytesSlice(&self.payload), f)?;
+        write!(f, ")")
+    }
+}
+
+/// Serialized as newtype struct named `ExtValue::NEWTYPE_NAME`,
+/// wrapping a tuple of the value's tag and payload bytes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExtValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        struct Payload<'a>(&'a [u8]);
+
+        impl serde::Serialize for Payload<'_> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+
+        serializer.serialize_newtype_struct(Self::NEWTYPE_NAME, &(self.tag, Payload(&self.payload)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExtValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(Self::NEWTYPE_NAME, ExtVisitor)
+    }
+}
+
+/// The parts (i.e. tag and payload) of an extension value, deserialized as tuple.
+#[cfg(feature = "serde")]
+pub(crate) struct ExtParts(pub(crate) ExtValue);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ExtParts {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(2, ExtVisitor).map(Self)
+    }
+}
+
+/// Visitor of extension values, as newtype structs of their parts.
+///
+/// Also accepts maps with a single entry keyed by `ExtValue::NEWTYPE_NAME`,
+/// as extension values get visited as such by `deserialize_any()`.
+#[cfg(feature = "serde")]
+struct ExtVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for ExtVisitor {
+    type Value = ExtValue;
+
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("extension value")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer).map(|ExtParts(value)| value)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        match map.next_key::<alloc::borrow::Cow<'_, str>>()? {
+            Some(key) if key == ExtValue::NEWTYPE_NAME => {
+                map.next_value().map(|ExtParts(value)| value)
+            }
+            _ => Err(serde::de::Error::invalid_type(
+                serde::de::Unexpected::Map,
+                &self,
+            )),
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        struct Payload(Vec<u8>);
+
+        impl<'de> serde::Deserialize<'de> for Payload {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct PayloadVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for PayloadVisitor {
+                    type Value = Payload;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                        formatter.write_str("extension payload")
+                    }
+
+                    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+                        Ok(Payload(value.to_vec()))
+                    }
+
+                    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+                        Ok(Payload(value))
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: serde::de::SeqAccess<'de>,
+                    {
+                        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                        while let Some(byte) = seq.next_element()? {
+                            bytes.push(byte);
+                        }
+                        Ok(Payload(bytes))
+                    }
+                }
+
+                deserializer.deserialize_byte_buf(PayloadVisitor)
+            }
+        }
+
+        let tag = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let Payload(payload) = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+
+        Ok(ExtValue { tag, payload })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::{
+        config::{DecoderConfig, UnknownHeaderPolicy},
+        decoder::Decoder,
+        encoder::Encoder,
+        io::{SliceReader, VecWriter},
+        value::Value,
+    };
+
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let value = ExtValue::new(300, vec![1, 2, 3]);
+
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_ext_value(&value).unwrap();
+        encoder.encode_bool(true).unwrap();
+
+        // header, body length, tag, payload:
+        assert_eq!(encoded[..8], [0x2D, 0xC6, 0x81, 0x01, 0x2C, 1, 2, 3]);
+
+        // Extension values are not subject to the unknown header policy:
+        let config = DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Reject);
+        let mut decoder = Decoder::new(SliceReader::new(&encoded), config);
+        assert_eq!(decoder.decode_value().unwrap(), Value::Ext(value));
+        assert!(decoder.decode_bool().unwrap());
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        decoder.skip_value().unwrap();
+        assert!(decoder.decode_bool().unwrap());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid() {
+        let uuid = uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_uuid(&uuid).unwrap();
+
+        // header, body length, tag, 16 bytes:
+        assert_eq!(encoded[..5], [0x2D, 0xD3, 0xC2, 0x04, 0x10]);
+        assert_eq!(encoded.len(), 21);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_uuid().unwrap(), uuid);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let value = decoder.decode_value().unwrap();
+        assert_eq!(value, Value::from(uuid));
+        assert_eq!(value.as_uuid(), Some(uuid));
+
+        assert_eq!(
+            ExtValue::new(UUID_TAG, vec![0x04, 0x02, 0x01, 0x02]).as_uuid(),
+            None
+        );
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_int() {
+        use num_bigint::BigInt;
+
+        let big_int: BigInt = "-1267650600228229401496703205376".parse().unwrap();
+
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_big_int(&big_int).unwrap();
+        encoder.encode_u8(42).unwrap();
+
+        // header, body length, tag, bytes (i.e. -2^100):
+        assert_eq!(encoded[..5], [0x2D, 0xD0, 0xC3, 0x04, 0x0D]);
+        assert_eq!(encoded[5..18], [0xF0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_big_int().unwrap(), big_int);
+        assert_eq!(decoder.decode_big_int().unwrap(), BigInt::from(42));
+
+        let value = Value::from(big_int.clone());
+        assert_eq!(value.as_big_int(), Some(big_int));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal() {
+        use rust_decimal::Decimal;
+
+        let decimal = Decimal::new(-12_345, 2);
+
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_decimal(&decimal).unwrap();
+        encoder.encode_i8(-7).unwrap();
+
+        // header, body length, tag, scale, bytes (i.e. -12345):
+        assert_eq!(
+            encoded[..8],
+            [0x2D, 0xC6, 0xC4, 0xC2, 0x04, 0x02, 0xCF, 0xC7]
+        );
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_decimal().unwrap(), decimal);
+        assert_eq!(decoder.decode_decimal().unwrap(), Decimal::new(-7, 0));
+
+        let value = Value::from(decimal);
+        assert_eq!(value.as_decimal(), Some(decimal));
+    }
+
+    #[test]
+    fn fmt() {
+        let value = ExtValue::new(42, vec![0xC1]);
+
+        assert_eq!(format!("{value:?}"), "ext(42, [11000001])");
+    }
+}
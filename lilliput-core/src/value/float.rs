@@ -1,4 +1,4 @@
-use std::hash::{Hash, Hasher};
+use core::hash::{Hash, Hasher};
 
 #[cfg(any(test, feature = "testing"))]
 use proptest::prelude::*;
@@ -74,13 +74,13 @@ impl PartialEq for FloatValue {
 }
 
 impl Ord for FloatValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.canonical_total().cmp(&other.canonical_total())
     }
 }
 
 impl PartialOrd for FloatValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -91,8 +91,8 @@ impl Hash for FloatValue {
     }
 }
 
-impl std::fmt::Debug for FloatValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for FloatValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::F32(value) => write!(f, "{value:#?}_f32"),
@@ -100,18 +100,18 @@ impl std::fmt::Debug for FloatValue {
             }
         } else {
             match self {
-                Self::F32(value) => std::fmt::Debug::fmt(value, f),
-                Self::F64(value) => std::fmt::Debug::fmt(value, f),
+                Self::F32(value) => core::fmt::Debug::fmt(value, f),
+                Self::F64(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
 }
 
-impl std::fmt::Display for FloatValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for FloatValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::F32(value) => std::fmt::Display::fmt(value, f),
-            Self::F64(value) => std::fmt::Display::fmt(value, f),
+            Self::F32(value) => core::fmt::Display::fmt(value, f),
+            Self::F64(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
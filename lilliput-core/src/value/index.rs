@@ -0,0 +1,330 @@
+use alloc::{borrow::ToOwned, string::String};
+use core::ops::Index;
+
+use super::{BytesValue, MapValue, NullValue, Path, PathSegment, SeqValue, StringValue, Value};
+
+/// Returned by `Index` impls for missing values, like `serde_json` does.
+static NULL: Value = Value::Null(NullValue);
+
+impl Value {
+    /// Returns the value of the map entry with the string key `key`,
+    /// or `None` if `self` is not a map, or has no such entry.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(map) => map.0.get(&string_key(key)),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable value of the map entry with the string key `key`,
+    /// or `None` if `self` is not a map, or has no such entry.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        match self {
+            Value::Map(map) => map.0.get_mut(&string_key(key)),
+            _ => None,
+        }
+    }
+
+    /// Returns the sequence item at `index`,
+    /// or `None` if `self` is not a sequence, or `index` is out of bounds.
+    pub fn get_index(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Seq(seq) => seq.0.get(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable sequence item at `index`,
+    /// or `None` if `self` is not a sequence, or `index` is out of bounds.
+    pub fn get_index_mut(&mut self, index: usize) -> Option<&mut Value> {
+        match self {
+            Value::Seq(seq) => seq.0.get_mut(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable sequence, if `self` is a sequence.
+    pub fn as_seq_mut(&mut self) -> Option<&mut SeqValue> {
+        match self {
+            Value::Seq(seq) => Some(seq),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable map, if `self` is a map.
+    pub fn as_map_mut(&mut self) -> Option<&mut MapValue> {
+        match self {
+            Value::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable string, if `self` is a string.
+    pub fn as_string_mut(&mut self) -> Option<&mut StringValue> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable byte array, if `self` is a byte array.
+    pub fn as_bytes_mut(&mut self) -> Option<&mut BytesValue> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Takes the value out of `self`, leaving `Value::Null` in its place.
+    ///
+    /// ```
+    /// use lilliput_core::value::{NullValue, StringValue, Value};
+    ///
+    /// let mut value = Value::String(StringValue::from("taken".to_owned()));
+    ///
+    /// assert_eq!(value.take(), Value::String(StringValue::from("taken".to_owned())));
+    /// assert_eq!(value, Value::Null(NullValue));
+    /// ```
+    pub fn take(&mut self) -> Value {
+        core::mem::replace(self, Value::Null(NullValue))
+    }
+
+    /// Returns the value at `path`, if any.
+    ///
+    /// The empty path refers to `self`.
+    pub fn get_path(&self, path: &Path) -> Option<&Value> {
+        path.segments()
+            .iter()
+            .try_fold(self, |value, segment| match (value, segment) {
+                (Value::Seq(seq), PathSegment::Index(index)) => seq.0.get(*index),
+                (Value::Map(map), PathSegment::Key(key)) => map.0.get(key),
+                _ => None,
+            })
+    }
+
+    /// Returns the mutable value at `path`, if any.
+    ///
+    /// The empty path refers to `self`.
+    pub fn get_path_mut(&mut self, path: &Path) -> Option<&mut Value> {
+        path.segments()
+            .iter()
+            .try_fold(self, |value, segment| match (value, segment) {
+                (Value::Seq(seq), PathSegment::Index(index)) => seq.0.get_mut(*index),
+                (Value::Map(map), PathSegment::Key(key)) => map.0.get_mut(key),
+                _ => None,
+            })
+    }
+
+    /// Looks up a value by a JSON pointer (as per RFC 6901), if any.
+    ///
+    /// Each reference token refers to a sequence item by its index,
+    /// or to a map entry by its string key, with `~1` and `~0` being
+    /// unescaped to `/` and `~`, respectively. The empty pointer refers to `self`.
+    ///
+    /// ```
+    /// use lilliput_core::value::Value;
+    ///
+    /// let value = Value::from_text(r#"{"users": [{"id": 42_u8}]}"#).unwrap();
+    ///
+    /// assert_eq!(value.pointer("/users/0/id"), Some(&Value::from_text("42_u8").unwrap()));
+    /// assert_eq!(value["users"][0]["id"], Value::from_text("42_u8").unwrap());
+    /// assert_eq!(value.pointer("/users/1"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        let mut tokens = pointer_tokens(pointer)?;
+
+        tokens.try_fold(self, |value, token| match value {
+            Value::Seq(seq) => seq.0.get(parse_index(&token)?),
+            Value::Map(map) => map.0.get(&Value::String(StringValue(token))),
+            _ => None,
+        })
+    }
+
+    /// Looks up a mutable value by a JSON pointer (as per RFC 6901), if any.
+    ///
+    /// See `Value::pointer()` for details.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        let mut tokens = pointer_tokens(pointer)?;
+
+        tokens.try_fold(self, |value, token| match value {
+            Value::Seq(seq) => seq.0.get_mut(parse_index(&token)?),
+            Value::Map(map) => map.0.get_mut(&Value::String(StringValue(token))),
+            _ => None,
+        })
+    }
+}
+
+/// Indexes into a map by string key, returning `Value::Null`
+/// if `self` is not a map, or has no such entry.
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Value {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// Indexes into a sequence, returning `Value::Null`
+/// if `self` is not a sequence, or `index` is out of bounds.
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        self.get_index(index).unwrap_or(&NULL)
+    }
+}
+
+// MARK: - Private
+
+fn string_key(key: &str) -> Value {
+    Value::String(StringValue(key.to_owned()))
+}
+
+/// Splits `pointer` into its unescaped reference tokens,
+/// returning `None` if it is neither empty, nor starts with `/`.
+fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = String> + '_> {
+    let tokens = match pointer.strip_prefix('/') {
+        Some(tokens) => Some(tokens.split('/')),
+        None if pointer.is_empty() => None,
+        None => return None,
+    };
+
+    Some(
+        tokens
+            .into_iter()
+            .flatten()
+            .map(|token| token.replace("~1", "/").replace("~0", "~")),
+    )
+}
+
+/// Parses a sequence index, rejecting leading zeros and signs, as RFC 6901 does.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+
+    token.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_log::test;
+
+    use crate::value::{IntValue, Map, MapValue, SeqValue};
+
+    use super::*;
+
+    fn int(value: u8) -> Value {
+        Value::Int(IntValue::from(value))
+    }
+
+    fn string(value: &str) -> Value {
+        Value::String(StringValue::from(value.to_owned()))
+    }
+
+    fn subject() -> Value {
+        let mut user = Map::default();
+        user.insert(string("id"), int(42));
+        user.insert(string("a/b~c"), int(1));
+        user.insert(int(7), int(2));
+
+        let mut map = Map::default();
+        map.insert(
+            string("users"),
+            Value::Seq(SeqValue::from(vec![Value::Map(MapValue::from(user))])),
+        );
+        Value::Map(MapValue::from(map))
+    }
+
+    #[test]
+    fn get() {
+        let value = subject();
+
+        assert_eq!(
+            value
+                .get("users")
+                .and_then(|users| users.get_index(0))
+                .and_then(|user| user.get("id")),
+            Some(&int(42))
+        );
+        assert_eq!(value.get("nobody"), None);
+        assert_eq!(value.get_index(0), None);
+        assert_eq!(int(1).get("id"), None);
+
+        let path = Path::new().with_key("users").with_index(0).with_key(int(7));
+        assert_eq!(value.get_path(&path), Some(&int(2)));
+        assert_eq!(value.get_path(&Path::new()), Some(&value));
+        assert_eq!(value.get_path(&Path::new().with_index(0)), None);
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut value = subject();
+
+        *value
+            .get_mut("users")
+            .unwrap()
+            .get_index_mut(0)
+            .unwrap()
+            .get_mut("id")
+            .unwrap() = int(43);
+        assert_eq!(value["users"][0]["id"], int(43));
+
+        *value.pointer_mut("/users/0/id").unwrap() = int(44);
+        let path = Path::new().with_key("users").with_index(0).with_key("id");
+        assert_eq!(value.get_path_mut(&path), Some(&mut int(44)));
+    }
+
+    #[test]
+    fn mutate() {
+        let mut value = subject();
+
+        let users = value.get_mut("users").unwrap().as_seq_mut().unwrap();
+        users.push(int(1));
+        assert_eq!(users.remove(0)["id"], int(42));
+        assert_eq!(users.pop(), Some(int(1)));
+        assert!(users.is_empty());
+
+        let map = value.as_map_mut().unwrap();
+        assert_eq!(map.insert(string("count"), int(1)), None);
+        assert_eq!(map.insert(string("count"), int(2)), Some(int(1)));
+        assert!(map.contains_key(&string("count")));
+        assert_eq!(
+            map.get_mut(&string("users")).unwrap().take(),
+            Value::Seq(SeqValue::default())
+        );
+        assert_eq!(map.remove(&string("users")), Some(Value::Null(NullValue)));
+        assert_eq!(map.remove(&string("users")), None);
+
+        assert_eq!(value.as_map_mut().unwrap().len(), 1);
+        assert_eq!(value.as_seq_mut(), None);
+        assert_eq!(int(1).as_string_mut(), None);
+    }
+
+    #[test]
+    fn index() {
+        let value = subject();
+
+        assert_eq!(value["users"][0]["id"], int(42));
+        assert_eq!(value["users"][1]["id"], Value::Null(NullValue));
+        assert_eq!(value["nobody"][0], Value::Null(NullValue));
+    }
+
+    #[test]
+    fn pointer() {
+        let value = subject();
+
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(value.pointer("/users/0/id"), Some(&int(42)));
+        assert_eq!(value.pointer("/users/0/a~1b~0c"), Some(&int(1)));
+
+        // Non-string keys can't be referred to:
+        assert_eq!(value.pointer("/users/0/7"), None);
+        // Indices must be canonical:
+        assert_eq!(value.pointer("/users/00/id"), None);
+        assert_eq!(value.pointer("/users/+0/id"), None);
+        // Pointers must be empty or start with a slash:
+        assert_eq!(value.pointer("users"), None);
+        assert_eq!(value.pointer("/users/"), None);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{
+use core::{
     hash::{Hash, Hasher},
     num::TryFromIntError,
 };
@@ -53,11 +53,13 @@ impl_int_value_from!(i8 => Signed);
 impl_int_value_from!(i16 => Signed);
 impl_int_value_from!(i32 => Signed);
 impl_int_value_from!(i64 => Signed);
+impl_int_value_from!(i128 => Signed);
 
 impl_int_value_from!(u8 => Unsigned);
 impl_int_value_from!(u16 => Unsigned);
 impl_int_value_from!(u32 => Unsigned);
 impl_int_value_from!(u64 => Unsigned);
+impl_int_value_from!(u128 => Unsigned);
 
 macro_rules! impl_int_value_from_size {
     ($t:ty) => {
@@ -78,6 +80,28 @@ macro_rules! impl_int_value_from_size {
 impl_int_value_from_size!(isize);
 impl_int_value_from_size!(usize);
 
+impl TryFrom<IntValue> for i128 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: IntValue) -> Result<Self, Self::Error> {
+        match value {
+            IntValue::Signed(signed) => Ok(signed.canonicalized()),
+            IntValue::Unsigned(unsigned) => i128::try_from(unsigned.canonicalized()),
+        }
+    }
+}
+
+impl TryFrom<IntValue> for u128 {
+    type Error = TryFromIntError;
+
+    fn try_from(value: IntValue) -> Result<Self, Self::Error> {
+        match value {
+            IntValue::Signed(signed) => u128::try_from(signed.canonicalized()),
+            IntValue::Unsigned(unsigned) => Ok(unsigned.canonicalized()),
+        }
+    }
+}
+
 impl PartialEq for IntValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -89,7 +113,7 @@ impl PartialEq for IntValue {
                 if lhs.is_negative() {
                     false
                 } else {
-                    (lhs as u64) == rhs
+                    (lhs as u128) == rhs
                 }
             }
             (Self::Unsigned(lhs), Self::Signed(rhs)) => {
@@ -99,7 +123,7 @@ impl PartialEq for IntValue {
                 if rhs.is_negative() {
                     false
                 } else {
-                    lhs == (rhs as u64)
+                    lhs == (rhs as u128)
                 }
             }
             (Self::Unsigned(lhs), Self::Unsigned(rhs)) => lhs == rhs,
@@ -108,7 +132,7 @@ impl PartialEq for IntValue {
 }
 
 impl PartialOrd for IntValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -116,7 +140,7 @@ impl PartialOrd for IntValue {
 impl Eq for IntValue {}
 
 impl Ord for IntValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         match (self, other) {
             (Self::Unsigned(lhs), Self::Unsigned(rhs)) => lhs.cmp(rhs),
             (Self::Signed(lhs), Self::Signed(rhs)) => lhs.cmp(rhs),
@@ -124,18 +148,18 @@ impl Ord for IntValue {
                 let lhs = lhs.canonicalized();
                 let rhs = rhs.canonicalized();
                 if rhs.is_negative() {
-                    std::cmp::Ordering::Greater
+                    core::cmp::Ordering::Greater
                 } else {
-                    lhs.cmp(&(rhs as u64))
+                    lhs.cmp(&(rhs as u128))
                 }
             }
             (Self::Signed(lhs), Self::Unsigned(rhs)) => {
                 let lhs = lhs.canonicalized();
                 let rhs = rhs.canonicalized();
                 if lhs.is_negative() {
-                    std::cmp::Ordering::Less
+                    core::cmp::Ordering::Less
                 } else {
-                    (lhs as u64).cmp(&rhs)
+                    (lhs as u128).cmp(&rhs)
                 }
             }
         }
@@ -154,27 +178,27 @@ impl Hash for IntValue {
                 if value.is_negative() {
                     value.to_ne_bytes().hash(state)
                 } else {
-                    (value as u64).to_ne_bytes().hash(state)
+                    (value as u128).to_ne_bytes().hash(state)
                 }
             }
         }
     }
 }
 
-impl std::fmt::Debug for IntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for IntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::Signed(value) => std::fmt::Debug::fmt(&value, f),
-            Self::Unsigned(value) => std::fmt::Debug::fmt(&value, f),
+            Self::Signed(value) => core::fmt::Debug::fmt(&value, f),
+            Self::Unsigned(value) => core::fmt::Debug::fmt(&value, f),
         }
     }
 }
 
-impl std::fmt::Display for IntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for IntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::Signed(value) => std::fmt::Display::fmt(value, f),
-            Self::Unsigned(value) => std::fmt::Display::fmt(value, f),
+            Self::Signed(value) => core::fmt::Display::fmt(value, f),
+            Self::Unsigned(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -246,6 +270,16 @@ impl<'de> serde::Deserialize<'de> for IntValue {
             fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
                 Ok(value.into())
             }
+
+            #[inline]
+            fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
+
+            #[inline]
+            fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
         }
 
         deserializer.deserialize_any(ValueVisitor)
@@ -295,6 +329,7 @@ mod tests {
                 IntValue::from(signed as i16),
                 IntValue::from(signed as i32),
                 IntValue::from(signed as i64),
+                IntValue::from(signed as i128),
             ];
 
             let unsigned_values = [
@@ -302,6 +337,7 @@ mod tests {
                 IntValue::from(unsigned as u16),
                 IntValue::from(unsigned as u32),
                 IntValue::from(unsigned as u64),
+                IntValue::from(unsigned as u128),
             ];
 
             // signed vs signed
@@ -341,6 +377,7 @@ mod tests {
                 IntValue::from(signed as i16),
                 IntValue::from(signed as i32),
                 IntValue::from(signed as i64),
+                IntValue::from(signed as i128),
             ];
 
             let unsigned_values = [
@@ -348,6 +385,7 @@ mod tests {
                 IntValue::from(unsigned as u16),
                 IntValue::from(unsigned as u32),
                 IntValue::from(unsigned as u64),
+                IntValue::from(unsigned as u128),
             ];
 
             // signed vs signed
@@ -383,13 +421,14 @@ mod tests {
 
         #[test]
         fn hash(signed in i8::MIN..=i8::MAX, unsigned in u8::MIN..=u8::MAX) {
-            use std::hash::BuildHasher as _;
+            use core::hash::BuildHasher as _;
 
             let signed_values = [
                 IntValue::from(signed),
                 IntValue::from(signed as i16),
                 IntValue::from(signed as i32),
                 IntValue::from(signed as i64),
+                IntValue::from(signed as i128),
             ];
 
             let unsigned_values = [
@@ -397,6 +436,7 @@ mod tests {
                 IntValue::from(unsigned as u16),
                 IntValue::from(unsigned as u32),
                 IntValue::from(unsigned as u64),
+                IntValue::from(unsigned as u128),
             ];
 
             // signed vs signed
@@ -427,11 +467,13 @@ mod tests {
         assert_eq!(format!("{}", IntValue::from(42_u16)), "42");
         assert_eq!(format!("{}", IntValue::from(42_u32)), "42");
         assert_eq!(format!("{}", IntValue::from(42_u64)), "42");
+        assert_eq!(format!("{}", IntValue::from(42_u128)), "42");
 
         assert_eq!(format!("{}", IntValue::from(42_i8)), "42");
         assert_eq!(format!("{}", IntValue::from(42_i16)), "42");
         assert_eq!(format!("{}", IntValue::from(42_i32)), "42");
         assert_eq!(format!("{}", IntValue::from(42_i64)), "42");
+        assert_eq!(format!("{}", IntValue::from(42_i128)), "42");
     }
 
     #[test]
@@ -440,21 +482,25 @@ mod tests {
         assert_eq!(format!("{:?}", IntValue::from(42_u16)), "42");
         assert_eq!(format!("{:?}", IntValue::from(42_u32)), "42");
         assert_eq!(format!("{:?}", IntValue::from(42_u64)), "42");
+        assert_eq!(format!("{:?}", IntValue::from(42_u128)), "42");
 
         assert_eq!(format!("{:?}", IntValue::from(42_i8)), "42");
         assert_eq!(format!("{:?}", IntValue::from(42_i16)), "42");
         assert_eq!(format!("{:?}", IntValue::from(42_i32)), "42");
         assert_eq!(format!("{:?}", IntValue::from(42_i64)), "42");
+        assert_eq!(format!("{:?}", IntValue::from(42_i128)), "42");
 
         assert_eq!(format!("{:#?}", IntValue::from(42_u8)), "42_u8");
         assert_eq!(format!("{:#?}", IntValue::from(42_u16)), "42_u16");
         assert_eq!(format!("{:#?}", IntValue::from(42_u32)), "42_u32");
         assert_eq!(format!("{:#?}", IntValue::from(42_u64)), "42_u64");
+        assert_eq!(format!("{:#?}", IntValue::from(42_u128)), "42_u128");
 
         assert_eq!(format!("{:#?}", IntValue::from(42_i8)), "42_i8");
         assert_eq!(format!("{:#?}", IntValue::from(42_i16)), "42_i16");
         assert_eq!(format!("{:#?}", IntValue::from(42_i32)), "42_i32");
         assert_eq!(format!("{:#?}", IntValue::from(42_i64)), "42_i64");
+        assert_eq!(format!("{:#?}", IntValue::from(42_i128)), "42_i128");
     }
 
     proptest! {
@@ -465,7 +511,7 @@ mod tests {
             let mut encoder = Encoder::new(writer, config);
             encoder.encode_int_value(&value).unwrap();
 
-            prop_assert!(encoded.len() <= 1 + 8);
+            prop_assert!(encoded.len() <= 1 + 16);
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
@@ -480,5 +526,29 @@ mod tests {
             };
             prop_assert_eq!(&decoded, &value);
         }
+
+        #[test]
+        fn i128_roundtrip(value in IntValue::arbitrary()) {
+            if let Ok(wide) = i128::try_from(value) {
+                prop_assert_eq!(IntValue::from(wide), value);
+            } else {
+                prop_assert!(u128::try_from(value).unwrap() > i128::MAX as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn conversions_128_bit() {
+        assert_eq!(IntValue::from(-1_i128), IntValue::from(-1_i64));
+        assert_eq!(
+            IntValue::from(u128::from(u64::MAX)),
+            IntValue::from(u64::MAX)
+        );
+
+        assert_eq!(i128::try_from(IntValue::from(i128::MIN)), Ok(i128::MIN));
+        assert_eq!(u128::try_from(IntValue::from(u128::MAX)), Ok(u128::MAX));
+
+        assert_eq!(i128::try_from(IntValue::from(u128::MAX)).ok(), None);
+        assert_eq!(u128::try_from(IntValue::from(-1_i8)).ok(), None);
     }
 }
@@ -1,4 +1,4 @@
-use std::{
+use core::{
     hash::{Hash, Hasher},
     num::TryFromIntError,
 };
@@ -24,6 +24,8 @@ pub enum SignedIntValue {
     I32(i32),
     /// 64-bit value.
     I64(i64),
+    /// 128-bit value.
+    I128(i128),
 }
 
 impl Default for SignedIntValue {
@@ -46,11 +48,12 @@ impl_signed_int_value_from!(i8 => I8);
 impl_signed_int_value_from!(i16 => I16);
 impl_signed_int_value_from!(i32 => I32);
 impl_signed_int_value_from!(i64 => I64);
+impl_signed_int_value_from!(i128 => I128);
 
 macro_rules! impl_try_from_signed_int_value {
     ($t:ty) => {
         impl TryFrom<SignedIntValue> for $t {
-            type Error = std::num::TryFromIntError;
+            type Error = core::num::TryFromIntError;
 
             fn try_from(value: SignedIntValue) -> Result<Self, Self::Error> {
                 match value {
@@ -58,6 +61,7 @@ macro_rules! impl_try_from_signed_int_value {
                     SignedIntValue::I16(value) => value.try_into_int(),
                     SignedIntValue::I32(value) => value.try_into_int(),
                     SignedIntValue::I64(value) => value.try_into_int(),
+                    SignedIntValue::I128(value) => value.try_into_int(),
                 }
             }
         }
@@ -68,28 +72,31 @@ impl_try_from_signed_int_value!(i8);
 impl_try_from_signed_int_value!(i16);
 impl_try_from_signed_int_value!(i32);
 impl_try_from_signed_int_value!(i64);
+impl_try_from_signed_int_value!(i128);
 impl_try_from_signed_int_value!(isize);
 
 impl PartialEq for SignedIntValue {
     fn eq(&self, other: &Self) -> bool {
         let lhs = match *self {
-            Self::I8(value) => value as i64,
-            Self::I16(value) => value as i64,
-            Self::I32(value) => value as i64,
-            Self::I64(value) => value,
+            Self::I8(value) => value as i128,
+            Self::I16(value) => value as i128,
+            Self::I32(value) => value as i128,
+            Self::I64(value) => value as i128,
+            Self::I128(value) => value,
         };
         let rhs = match *other {
-            Self::I8(value) => value as i64,
-            Self::I16(value) => value as i64,
-            Self::I32(value) => value as i64,
-            Self::I64(value) => value,
+            Self::I8(value) => value as i128,
+            Self::I16(value) => value as i128,
+            Self::I32(value) => value as i128,
+            Self::I64(value) => value as i128,
+            Self::I128(value) => value,
         };
         lhs == rhs
     }
 }
 
 impl PartialOrd for SignedIntValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -97,7 +104,7 @@ impl PartialOrd for SignedIntValue {
 impl Eq for SignedIntValue {}
 
 impl Ord for SignedIntValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.canonicalized().cmp(&other.canonicalized())
     }
 }
@@ -108,33 +115,36 @@ impl Hash for SignedIntValue {
     }
 }
 
-impl std::fmt::Debug for SignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for SignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::I8(value) => write!(f, "{value:#?}_i8"),
                 Self::I16(value) => write!(f, "{value:#?}_i16"),
                 Self::I32(value) => write!(f, "{value:#?}_i32"),
                 Self::I64(value) => write!(f, "{value:#?}_i64"),
+                Self::I128(value) => write!(f, "{value:#?}_i128"),
             }
         } else {
             match self {
-                Self::I8(value) => std::fmt::Debug::fmt(value, f),
-                Self::I16(value) => std::fmt::Debug::fmt(value, f),
-                Self::I32(value) => std::fmt::Debug::fmt(value, f),
-                Self::I64(value) => std::fmt::Debug::fmt(value, f),
+                Self::I8(value) => core::fmt::Debug::fmt(value, f),
+                Self::I16(value) => core::fmt::Debug::fmt(value, f),
+                Self::I32(value) => core::fmt::Debug::fmt(value, f),
+                Self::I64(value) => core::fmt::Debug::fmt(value, f),
+                Self::I128(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
 }
 
-impl std::fmt::Display for SignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::I8(value) => std::fmt::Display::fmt(value, f),
-            Self::I16(value) => std::fmt::Display::fmt(value, f),
-            Self::I32(value) => std::fmt::Display::fmt(value, f),
-            Self::I64(value) => std::fmt::Display::fmt(value, f),
+            Self::I8(value) => core::fmt::Display::fmt(value, f),
+            Self::I16(value) => core::fmt::Display::fmt(value, f),
+            Self::I32(value) => core::fmt::Display::fmt(value, f),
+            Self::I64(value) => core::fmt::Display::fmt(value, f),
+            Self::I128(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -150,6 +160,7 @@ impl serde::Serialize for SignedIntValue {
             Self::I16(value) => value.serialize(serializer),
             Self::I32(value) => value.serialize(serializer),
             Self::I64(value) => value.serialize(serializer),
+            Self::I128(value) => value.serialize(serializer),
         }
     }
 }
@@ -188,6 +199,11 @@ impl<'de> serde::Deserialize<'de> for SignedIntValue {
             fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
                 Ok(value.into())
             }
+
+            #[inline]
+            fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
         }
 
         deserializer.deserialize_any(ValueVisitor)
@@ -202,15 +218,17 @@ impl SignedIntValue {
             Self::I16(signed) => u16::try_from_int(signed).map(UnsignedIntValue::U16),
             Self::I32(signed) => u32::try_from_int(signed).map(UnsignedIntValue::U32),
             Self::I64(signed) => u64::try_from_int(signed).map(UnsignedIntValue::U64),
+            Self::I128(signed) => u128::try_from_int(signed).map(UnsignedIntValue::U128),
         }
     }
 
-    pub(crate) fn canonicalized(&self) -> i64 {
+    pub(crate) fn canonicalized(&self) -> i128 {
         match *self {
-            Self::I8(value) => value as i64,
-            Self::I16(value) => value as i64,
-            Self::I32(value) => value as i64,
-            Self::I64(value) => value,
+            Self::I8(value) => value as i128,
+            Self::I16(value) => value as i128,
+            Self::I32(value) => value as i128,
+            Self::I64(value) => value as i128,
+            Self::I128(value) => value,
         }
     }
 }
@@ -241,6 +259,7 @@ mod tests {
                 SignedIntValue::I16(lhs as i16),
                 SignedIntValue::I32(lhs as i32),
                 SignedIntValue::I64(lhs as i64),
+                SignedIntValue::I128(lhs as i128),
             ];
 
             let rhs_values = [
@@ -248,6 +267,7 @@ mod tests {
                 SignedIntValue::I16(rhs as i16),
                 SignedIntValue::I32(rhs as i32),
                 SignedIntValue::I64(rhs as i64),
+                SignedIntValue::I128(rhs as i128),
             ];
 
             for lhs_value in &lhs_values {
@@ -266,6 +286,7 @@ mod tests {
                 SignedIntValue::I16(lhs as i16),
                 SignedIntValue::I32(lhs as i32),
                 SignedIntValue::I64(lhs as i64),
+                SignedIntValue::I128(lhs as i128),
             ];
 
             let rhs_values = [
@@ -273,6 +294,7 @@ mod tests {
                 SignedIntValue::I16(rhs as i16),
                 SignedIntValue::I32(rhs as i32),
                 SignedIntValue::I64(rhs as i64),
+                SignedIntValue::I128(rhs as i128),
             ];
 
             for lhs_value in &lhs_values {
@@ -286,13 +308,14 @@ mod tests {
 
         #[test]
         fn hash(lhs in i8::MIN..=i8::MAX) {
-            use std::hash::BuildHasher as _;
+            use core::hash::BuildHasher as _;
 
             let values = [
                 SignedIntValue::I8(lhs),
                 SignedIntValue::I16(lhs as i16),
                 SignedIntValue::I32(lhs as i32),
                 SignedIntValue::I64(lhs as i64),
+                SignedIntValue::I128(lhs as i128),
             ];
 
             for lhs_value in &values {
@@ -312,6 +335,7 @@ mod tests {
         assert_eq!(format!("{}", SignedIntValue::from(42_i16)), "42");
         assert_eq!(format!("{}", SignedIntValue::from(42_i32)), "42");
         assert_eq!(format!("{}", SignedIntValue::from(42_i64)), "42");
+        assert_eq!(format!("{}", SignedIntValue::from(42_i128)), "42");
     }
 
     #[test]
@@ -320,11 +344,13 @@ mod tests {
         assert_eq!(format!("{:?}", SignedIntValue::from(42_i16)), "42");
         assert_eq!(format!("{:?}", SignedIntValue::from(42_i32)), "42");
         assert_eq!(format!("{:?}", SignedIntValue::from(42_i64)), "42");
+        assert_eq!(format!("{:?}", SignedIntValue::from(42_i128)), "42");
 
         assert_eq!(format!("{:#?}", SignedIntValue::from(42_i8)), "42_i8");
         assert_eq!(format!("{:#?}", SignedIntValue::from(42_i16)), "42_i16");
         assert_eq!(format!("{:#?}", SignedIntValue::from(42_i32)), "42_i32");
         assert_eq!(format!("{:#?}", SignedIntValue::from(42_i64)), "42_i64");
+        assert_eq!(format!("{:#?}", SignedIntValue::from(42_i128)), "42_i128");
     }
 
     proptest! {
@@ -335,7 +361,7 @@ mod tests {
             let mut encoder = Encoder::new(writer, config);
             encoder.encode_signed_int_value(&value).unwrap();
 
-            prop_assert!(encoded.len() <= 1 + 8);
+            prop_assert!(encoded.len() <= 1 + 16);
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
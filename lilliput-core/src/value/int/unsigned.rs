@@ -1,4 +1,4 @@
-use std::{
+use core::{
     hash::{Hash, Hasher},
     num::TryFromIntError,
 };
@@ -24,6 +24,8 @@ pub enum UnsignedIntValue {
     U32(u32),
     /// 64-bit value.
     U64(u64),
+    /// 128-bit value.
+    U128(u128),
 }
 
 impl Default for UnsignedIntValue {
@@ -46,11 +48,12 @@ impl_unsigned_int_value_from!(u8 => U8);
 impl_unsigned_int_value_from!(u16 => U16);
 impl_unsigned_int_value_from!(u32 => U32);
 impl_unsigned_int_value_from!(u64 => U64);
+impl_unsigned_int_value_from!(u128 => U128);
 
 macro_rules! impl_try_from_unsigned_int_value {
     ($t:ty) => {
         impl TryFrom<UnsignedIntValue> for $t {
-            type Error = std::num::TryFromIntError;
+            type Error = core::num::TryFromIntError;
 
             fn try_from(value: UnsignedIntValue) -> Result<Self, Self::Error> {
                 match value {
@@ -58,6 +61,7 @@ macro_rules! impl_try_from_unsigned_int_value {
                     UnsignedIntValue::U16(value) => value.try_into_int(),
                     UnsignedIntValue::U32(value) => value.try_into_int(),
                     UnsignedIntValue::U64(value) => value.try_into_int(),
+                    UnsignedIntValue::U128(value) => value.try_into_int(),
                 }
             }
         }
@@ -68,28 +72,31 @@ impl_try_from_unsigned_int_value!(u8);
 impl_try_from_unsigned_int_value!(u16);
 impl_try_from_unsigned_int_value!(u32);
 impl_try_from_unsigned_int_value!(u64);
+impl_try_from_unsigned_int_value!(u128);
 impl_try_from_unsigned_int_value!(usize);
 
 impl PartialEq for UnsignedIntValue {
     fn eq(&self, other: &Self) -> bool {
         let lhs = match *self {
-            Self::U8(value) => value as u64,
-            Self::U16(value) => value as u64,
-            Self::U32(value) => value as u64,
-            Self::U64(value) => value,
+            Self::U8(value) => value as u128,
+            Self::U16(value) => value as u128,
+            Self::U32(value) => value as u128,
+            Self::U64(value) => value as u128,
+            Self::U128(value) => value,
         };
         let rhs = match *other {
-            Self::U8(value) => value as u64,
-            Self::U16(value) => value as u64,
-            Self::U32(value) => value as u64,
-            Self::U64(value) => value,
+            Self::U8(value) => value as u128,
+            Self::U16(value) => value as u128,
+            Self::U32(value) => value as u128,
+            Self::U64(value) => value as u128,
+            Self::U128(value) => value,
         };
         lhs == rhs
     }
 }
 
 impl PartialOrd for UnsignedIntValue {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -97,7 +104,7 @@ impl PartialOrd for UnsignedIntValue {
 impl Eq for UnsignedIntValue {}
 
 impl Ord for UnsignedIntValue {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.canonicalized().cmp(&other.canonicalized())
     }
 }
@@ -108,33 +115,36 @@ impl Hash for UnsignedIntValue {
     }
 }
 
-impl std::fmt::Debug for UnsignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for UnsignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if f.alternate() {
             match self {
                 Self::U8(value) => write!(f, "{value:#?}_u8"),
                 Self::U16(value) => write!(f, "{value:#?}_u16"),
                 Self::U32(value) => write!(f, "{value:#?}_u32"),
                 Self::U64(value) => write!(f, "{value:#?}_u64"),
+                Self::U128(value) => write!(f, "{value:#?}_u128"),
             }
         } else {
             match self {
-                Self::U8(value) => std::fmt::Debug::fmt(value, f),
-                Self::U16(value) => std::fmt::Debug::fmt(value, f),
-                Self::U32(value) => std::fmt::Debug::fmt(value, f),
-                Self::U64(value) => std::fmt::Debug::fmt(value, f),
+                Self::U8(value) => core::fmt::Debug::fmt(value, f),
+                Self::U16(value) => core::fmt::Debug::fmt(value, f),
+                Self::U32(value) => core::fmt::Debug::fmt(value, f),
+                Self::U64(value) => core::fmt::Debug::fmt(value, f),
+                Self::U128(value) => core::fmt::Debug::fmt(value, f),
             }
         }
     }
 }
 
-impl std::fmt::Display for UnsignedIntValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for UnsignedIntValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::U8(value) => std::fmt::Display::fmt(value, f),
-            Self::U16(value) => std::fmt::Display::fmt(value, f),
-            Self::U32(value) => std::fmt::Display::fmt(value, f),
-            Self::U64(value) => std::fmt::Display::fmt(value, f),
+            Self::U8(value) => core::fmt::Display::fmt(value, f),
+            Self::U16(value) => core::fmt::Display::fmt(value, f),
+            Self::U32(value) => core::fmt::Display::fmt(value, f),
+            Self::U64(value) => core::fmt::Display::fmt(value, f),
+            Self::U128(value) => core::fmt::Display::fmt(value, f),
         }
     }
 }
@@ -150,6 +160,7 @@ impl serde::Serialize for UnsignedIntValue {
             Self::U16(value) => value.serialize(serializer),
             Self::U32(value) => value.serialize(serializer),
             Self::U64(value) => value.serialize(serializer),
+            Self::U128(value) => value.serialize(serializer),
         }
     }
 }
@@ -188,6 +199,11 @@ impl<'de> serde::Deserialize<'de> for UnsignedIntValue {
             fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
                 Ok(value.into())
             }
+
+            #[inline]
+            fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E> {
+                Ok(value.into())
+            }
         }
 
         deserializer.deserialize_any(ValueVisitor)
@@ -219,16 +235,24 @@ impl UnsignedIntValue {
                     i64::try_from_int(unsigned).map(SignedIntValue::I64)
                 }
             }
-            Self::U64(unsigned) => i64::try_from_int(unsigned).map(SignedIntValue::I64),
+            Self::U64(unsigned) => {
+                if unsigned <= i64::MAX as u64 {
+                    i64::try_from_int(unsigned).map(SignedIntValue::I64)
+                } else {
+                    i128::try_from_int(unsigned).map(SignedIntValue::I128)
+                }
+            }
+            Self::U128(unsigned) => i128::try_from_int(unsigned).map(SignedIntValue::I128),
         }
     }
 
-    pub(crate) fn canonicalized(&self) -> u64 {
+    pub(crate) fn canonicalized(&self) -> u128 {
         match *self {
-            Self::U8(value) => value as u64,
-            Self::U16(value) => value as u64,
-            Self::U32(value) => value as u64,
-            Self::U64(value) => value,
+            Self::U8(value) => value as u128,
+            Self::U16(value) => value as u128,
+            Self::U32(value) => value as u128,
+            Self::U64(value) => value as u128,
+            Self::U128(value) => value,
         }
     }
 }
@@ -259,6 +283,7 @@ mod tests {
                 UnsignedIntValue::U16(lhs as u16),
                 UnsignedIntValue::U32(lhs as u32),
                 UnsignedIntValue::U64(lhs as u64),
+                UnsignedIntValue::U128(lhs as u128),
             ];
 
             let rhs_values = [
@@ -266,6 +291,7 @@ mod tests {
                 UnsignedIntValue::U16(rhs as u16),
                 UnsignedIntValue::U32(rhs as u32),
                 UnsignedIntValue::U64(rhs as u64),
+                UnsignedIntValue::U128(rhs as u128),
             ];
 
             for lhs_value in &lhs_values {
@@ -284,6 +310,7 @@ mod tests {
                 UnsignedIntValue::U16(lhs as u16),
                 UnsignedIntValue::U32(lhs as u32),
                 UnsignedIntValue::U64(lhs as u64),
+                UnsignedIntValue::U128(lhs as u128),
             ];
 
             let rhs_values = [
@@ -291,6 +318,7 @@ mod tests {
                 UnsignedIntValue::U16(rhs as u16),
                 UnsignedIntValue::U32(rhs as u32),
                 UnsignedIntValue::U64(rhs as u64),
+                UnsignedIntValue::U128(rhs as u128),
             ];
 
             for lhs_value in &lhs_values {
@@ -304,13 +332,14 @@ mod tests {
 
         #[test]
         fn hash(lhs in u8::MIN..=u8::MAX) {
-            use std::hash::BuildHasher as _;
+            use core::hash::BuildHasher as _;
 
             let values = [
                 UnsignedIntValue::U8(lhs),
                 UnsignedIntValue::U16(lhs as u16),
                 UnsignedIntValue::U32(lhs as u32),
                 UnsignedIntValue::U64(lhs as u64),
+                UnsignedIntValue::U128(lhs as u128),
             ];
 
             for lhs_value in &values {
@@ -330,6 +359,7 @@ mod tests {
         assert_eq!(format!("{}", UnsignedIntValue::from(42_u16)), "42");
         assert_eq!(format!("{}", UnsignedIntValue::from(42_u32)), "42");
         assert_eq!(format!("{}", UnsignedIntValue::from(42_u64)), "42");
+        assert_eq!(format!("{}", UnsignedIntValue::from(42_u128)), "42");
     }
 
     #[test]
@@ -338,11 +368,13 @@ mod tests {
         assert_eq!(format!("{:?}", UnsignedIntValue::from(42_u16)), "42");
         assert_eq!(format!("{:?}", UnsignedIntValue::from(42_u32)), "42");
         assert_eq!(format!("{:?}", UnsignedIntValue::from(42_u64)), "42");
+        assert_eq!(format!("{:?}", UnsignedIntValue::from(42_u128)), "42");
 
         assert_eq!(format!("{:#?}", UnsignedIntValue::from(42_u8)), "42_u8");
         assert_eq!(format!("{:#?}", UnsignedIntValue::from(42_u16)), "42_u16");
         assert_eq!(format!("{:#?}", UnsignedIntValue::from(42_u32)), "42_u32");
         assert_eq!(format!("{:#?}", UnsignedIntValue::from(42_u64)), "42_u64");
+        assert_eq!(format!("{:#?}", UnsignedIntValue::from(42_u128)), "42_u128");
     }
 
     proptest! {
@@ -353,7 +385,7 @@ mod tests {
             let mut encoder = Encoder::new(writer, config);
             encoder.encode_unsigned_int_value(&value).unwrap();
 
-            prop_assert!(encoded.len() <= 1 + 8);
+            prop_assert!(encoded.len() <= 1 + 16);
 
             let reader = SliceReader::new(&encoded);
             let mut decoder = Decoder::from_reader(reader);
@@ -0,0 +1,338 @@
+use serde_json::{Map as JsonMap, Number as JsonNumber, Value as JsonValue};
+
+use crate::error::{Error, Result};
+
+use super::{BytesValue, FloatValue, IntValue, Map, MapValue, NullValue, SeqValue, Value};
+
+/// The action taken for byte arrays, which JSON has no representation for.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum JsonBytesAction {
+    /// Converts the bytes to a standard, padded base64 string.
+    #[default]
+    Base64,
+    /// Converts the bytes to an array of numbers.
+    Array,
+    /// Returns an error.
+    Error,
+}
+
+/// The action taken for map keys that are not strings,
+/// which JSON objects have no representation for.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum JsonKeyAction {
+    /// Converts the key to JSON and uses its JSON text as key
+    /// (e.g. `42` for an integer key, or `[1,2]` for a sequence key).
+    #[default]
+    Stringify,
+    /// Returns an error.
+    Error,
+}
+
+/// The action taken for non-finite floats (i.e. NaN and infinities),
+/// which JSON has no representation for.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum JsonFloatAction {
+    /// Converts the float to `null`.
+    #[default]
+    Null,
+    /// Converts the float to one of the strings `"NaN"`, `"inf"` or `"-inf"`.
+    String,
+    /// Returns an error.
+    Error,
+}
+
+/// Policy used for converting values to JSON, via `Value::to_json()`.
+///
+/// Only available with the `json` feature enabled.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct JsonPolicy {
+    /// Action taken for byte arrays.
+    pub on_bytes: JsonBytesAction,
+    /// Action taken for map keys that are not strings.
+    pub on_non_string_key: JsonKeyAction,
+    /// Action taken for non-finite floats.
+    pub on_non_finite_float: JsonFloatAction,
+}
+
+impl JsonPolicy {
+    /// Sets the action for byte arrays to `on_bytes`, returning `self`.
+    pub fn with_bytes_action(mut self, on_bytes: JsonBytesAction) -> Self {
+        self.on_bytes = on_bytes;
+        self
+    }
+
+    /// Sets the action for non-string map keys to `on_non_string_key`, returning `self`.
+    pub fn with_key_action(mut self, on_non_string_key: JsonKeyAction) -> Self {
+        self.on_non_string_key = on_non_string_key;
+        self
+    }
+
+    /// Sets the action for non-finite floats to `on_non_finite_float`, returning `self`.
+    pub fn with_float_action(mut self, on_non_finite_float: JsonFloatAction) -> Self {
+        self.on_non_finite_float = on_non_finite_float;
+        self
+    }
+}
+
+impl Value {
+    /// Converts `self` to a JSON value, handling the values
+    /// that JSON has no representation for according to `policy`.
+    ///
+    /// Units convert to `null`, just like nulls do, while integers
+    /// outside of the range of `i64`/`u64` are rejected with an error,
+    /// as are values of unknown types.
+    ///
+    /// Only available with the `json` feature enabled.
+    ///
+    /// ```
+    /// use lilliput_core::value::{BytesValue, JsonBytesAction, JsonPolicy, Value};
+    ///
+    /// let value = Value::Bytes(BytesValue::from(vec![1, 2, 3]));
+    ///
+    /// let json = value.to_json(&JsonPolicy::default()).unwrap();
+    /// assert_eq!(json, serde_json::json!("AQID"));
+    ///
+    /// let policy = JsonPolicy::default().with_bytes_action(JsonBytesAction::Array);
+    /// let json = value.to_json(&policy).unwrap();
+    /// assert_eq!(json, serde_json::json!([1, 2, 3]));
+    /// ```
+    pub fn to_json(&self, policy: &JsonPolicy) -> Result<JsonValue> {
+        policy.convert(self)
+    }
+}
+
+/// Converts a JSON value into a `Value`, which is lossless.
+///
+/// Non-negative integers become `u64`s, negative integers `i64`s
+/// and all other numbers `f64`s.
+impl From<JsonValue> for Value {
+    fn from(json: JsonValue) -> Self {
+        match json {
+            JsonValue::Null => Value::Null(NullValue),
+            JsonValue::Bool(value) => Value::Bool(value.into()),
+            JsonValue::Number(number) => {
+                if let Some(value) = number.as_u64() {
+                    Value::Int(IntValue::from(value))
+                } else if let Some(value) = number.as_i64() {
+                    Value::Int(IntValue::from(value))
+                } else {
+                    let value = number.as_f64().unwrap_or(f64::NAN);
+                    Value::Float(FloatValue::F64(value))
+                }
+            }
+            JsonValue::String(value) => Value::String(value.into()),
+            JsonValue::Array(items) => {
+                let seq: Vec<Value> = items.into_iter().map(Value::from).collect();
+                Value::Seq(SeqValue::from(seq))
+            }
+            JsonValue::Object(entries) => {
+                let map: Map = entries
+                    .into_iter()
+                    .map(|(key, value)| (Value::String(key.into()), Value::from(value)))
+                    .collect();
+                Value::Map(MapValue::from(map))
+            }
+        }
+    }
+}
+
+/// Converts a `Value` into a JSON value, as per `JsonPolicy::default()`.
+impl TryFrom<Value> for JsonValue {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        value.to_json(&JsonPolicy::default())
+    }
+}
+
+// MARK: - Private
+
+impl JsonPolicy {
+    fn convert(&self, value: &Value) -> Result<JsonValue> {
+        match value {
+            Value::Int(value) => convert_int(*value),
+            Value::String(value) => Ok(JsonValue::String(value.as_str().to_owned())),
+            Value::Seq(value) => value
+                .as_slice()
+                .iter()
+                .map(|item| self.convert(item))
+                .collect::<Result<_>>()
+                .map(JsonValue::Array),
+            Value::Map(value) => {
+                let mut entries = JsonMap::new();
+                for (key, value) in value.as_map_ref() {
+                    entries.insert(self.convert_key(key)?, self.convert(value)?);
+                }
+                Ok(JsonValue::Object(entries))
+            }
+            Value::Float(value) => self.convert_float(*value),
+            Value::Bytes(value) => self.convert_bytes(value),
+            Value::Bool(value) => Ok(JsonValue::Bool(value.0)),
+            Value::Unit(_) | Value::Null(_) => Ok(JsonValue::Null),
+            Value::Ext(_) => Err(unrepresentable("extension value")),
+            Value::Unknown(_) => Err(unrepresentable("unknown value")),
+        }
+    }
+
+    fn convert_key(&self, key: &Value) -> Result<String> {
+        if let Value::String(key) = key {
+            return Ok(key.as_str().to_owned());
+        }
+
+        match self.on_non_string_key {
+            JsonKeyAction::Stringify => match self.convert(key)? {
+                JsonValue::String(key) => Ok(key),
+                key => Ok(key.to_string()),
+            },
+            JsonKeyAction::Error => Err(unrepresentable("non-string map key")),
+        }
+    }
+
+    fn convert_float(&self, value: FloatValue) -> Result<JsonValue> {
+        let value = match value {
+            FloatValue::F32(value) => f64::from(value),
+            FloatValue::F64(value) => value,
+        };
+
+        if let Some(number) = JsonNumber::from_f64(value) {
+            return Ok(JsonValue::Number(number));
+        }
+
+        match self.on_non_finite_float {
+            JsonFloatAction::Null => Ok(JsonValue::Null),
+            JsonFloatAction::String => {
+                let string = if value.is_nan() {
+                    "NaN"
+                } else if value.is_sign_negative() {
+                    "-inf"
+                } else {
+                    "inf"
+                };
+                Ok(JsonValue::String(string.to_owned()))
+            }
+            JsonFloatAction::Error => Err(unrepresentable("non-finite float")),
+        }
+    }
+
+    fn convert_bytes(&self, value: &BytesValue) -> Result<JsonValue> {
+        match self.on_bytes {
+            JsonBytesAction::Base64 => Ok(JsonValue::String(value.to_base64())),
+            JsonBytesAction::Array => Ok(JsonValue::Array(
+                value.as_slice().iter().map(|&byte| byte.into()).collect(),
+            )),
+            JsonBytesAction::Error => Err(unrepresentable("byte array")),
+        }
+    }
+}
+
+fn convert_int(value: IntValue) -> Result<JsonValue> {
+    if let Ok(value) = u128::try_from(value) {
+        u64::try_from(value)
+            .map(JsonValue::from)
+            .map_err(|_| Error::number_out_of_range(None))
+    } else {
+        let value = i128::try_from(value).map_err(|_| Error::number_out_of_range(None))?;
+        i64::try_from(value)
+            .map(JsonValue::from)
+            .map_err(|_| Error::number_out_of_range(None))
+    }
+}
+
+fn unrepresentable(unexpected: &str) -> Error {
+    Error::invalid_type(
+        unexpected.to_owned(),
+        "JSON-representable value".to_owned(),
+        None,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use test_log::test;
+
+    use crate::{error::ErrorCode, value::StringValue};
+
+    use super::*;
+
+    #[test]
+    fn from_json() {
+        let value = Value::from(json!({"a": [1, -2, 1.5, true, null, "b"]}));
+
+        let mut map = Map::default();
+        map.insert(
+            Value::String(StringValue::from("a".to_owned())),
+            Value::Seq(SeqValue::from(vec![
+                Value::Int(IntValue::from(1_u64)),
+                Value::Int(IntValue::from(-2_i64)),
+                Value::Float(FloatValue::F64(1.5)),
+                Value::Bool(true.into()),
+                Value::Null(NullValue),
+                Value::String(StringValue::from("b".to_owned())),
+            ])),
+        );
+        assert_eq!(value, Value::Map(MapValue::from(map)));
+
+        let json = json!({"a": [1, -2, 1.5, true, null, "b"]});
+        assert_eq!(
+            JsonValue::try_from(Value::from(json.clone())).unwrap(),
+            json
+        );
+    }
+
+    #[test]
+    fn ints() {
+        let int = |value: IntValue| Value::Int(value).to_json(&JsonPolicy::default());
+
+        assert_eq!(int(IntValue::from(u64::MAX)).unwrap(), json!(u64::MAX));
+        assert_eq!(int(IntValue::from(i64::MIN)).unwrap(), json!(i64::MIN));
+        assert_eq!(int(IntValue::from(42_u128)).unwrap(), json!(42));
+
+        let error_code = int(IntValue::from(u128::MAX)).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::NumberOutOfRange);
+        let error_code = int(IntValue::from(i128::MIN)).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::NumberOutOfRange);
+    }
+
+    #[test]
+    fn non_finite_floats() {
+        let nan = Value::Float(FloatValue::F64(f64::NAN));
+        let inf = Value::Float(FloatValue::F32(f32::NEG_INFINITY));
+
+        let policy = JsonPolicy::default();
+        assert_eq!(nan.to_json(&policy).unwrap(), JsonValue::Null);
+
+        let policy = policy.with_float_action(JsonFloatAction::String);
+        assert_eq!(nan.to_json(&policy).unwrap(), json!("NaN"));
+        assert_eq!(inf.to_json(&policy).unwrap(), json!("-inf"));
+
+        let policy = policy.with_float_action(JsonFloatAction::Error);
+        assert!(nan.to_json(&policy).is_err());
+    }
+
+    #[test]
+    fn non_string_keys() {
+        let mut map = Map::default();
+        map.insert(Value::Int(IntValue::from(1_u8)), Value::Null(NullValue));
+        map.insert(
+            Value::Seq(SeqValue::from(vec![Value::Bool(true.into())])),
+            Value::Null(NullValue),
+        );
+        let value = Value::Map(MapValue::from(map));
+
+        let json = value.to_json(&JsonPolicy::default()).unwrap();
+        assert_eq!(json, json!({"1": null, "[true]": null}));
+
+        let policy = JsonPolicy::default().with_key_action(JsonKeyAction::Error);
+        assert!(value.to_json(&policy).is_err());
+    }
+
+    #[test]
+    fn bytes() {
+        let value = Value::Bytes(BytesValue::from(vec![0, 255]));
+
+        let policy = JsonPolicy::default().with_bytes_action(JsonBytesAction::Error);
+        let error_code = value.to_json(&policy).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::InvalidType);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::marker::Marker;
+
+use super::Value;
+
+/// The kind of a value, as told by its header.
+///
+/// Unlike `Marker`, which mirrors the wire format's header bits,
+/// value kinds tell apart extension values and values of unknown types,
+/// and are not subject to changes of the wire format.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[non_exhaustive]
+pub enum ValueKind {
+    /// Integer values.
+    Int,
+    /// String values.
+    String,
+    /// Sequence values.
+    Seq,
+    /// Map values.
+    Map,
+    /// Floating-point values.
+    Float,
+    /// Byte array values.
+    Bytes,
+    /// Bool values.
+    Bool,
+    /// Unit values.
+    Unit,
+    /// Null values.
+    Null,
+    /// Extension values.
+    Ext,
+    /// Values of an unknown type.
+    Unknown,
+}
+
+impl core::fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Int => write!(f, "integer"),
+            Self::String => write!(f, "string"),
+            Self::Seq => write!(f, "sequence"),
+            Self::Map => write!(f, "map"),
+            Self::Float => write!(f, "float"),
+            Self::Bytes => write!(f, "byte sequence"),
+            Self::Bool => write!(f, "bool"),
+            Self::Unit => write!(f, "unit"),
+            Self::Null => write!(f, "null"),
+            Self::Ext => write!(f, "extension"),
+            Self::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl From<Marker> for ValueKind {
+    fn from(marker: Marker) -> Self {
+        match marker {
+            Marker::Int => Self::Int,
+            Marker::String => Self::String,
+            Marker::Seq => Self::Seq,
+            Marker::Map => Self::Map,
+            Marker::Float => Self::Float,
+            Marker::Bytes => Self::Bytes,
+            Marker::Bool => Self::Bool,
+            Marker::Unit => Self::Unit,
+            Marker::Null => Self::Null,
+        }
+    }
+}
+
+impl Value {
+    /// Returns the kind of `self`.
+    pub fn kind(&self) -> ValueKind {
+        match self {
+            Value::Int(_) => ValueKind::Int,
+            Value::String(_) => ValueKind::String,
+            Value::Seq(_) => ValueKind::Seq,
+            Value::Map(_) => ValueKind::Map,
+            Value::Float(_) => ValueKind::Float,
+            Value::Bytes(_) => ValueKind::Bytes,
+            Value::Bool(_) => ValueKind::Bool,
+            Value::Unit(_) => ValueKind::Unit,
+            Value::Null(_) => ValueKind::Null,
+            Value::Ext(_) => ValueKind::Ext,
+            Value::Unknown(_) => ValueKind::Unknown,
+        }
+    }
+}
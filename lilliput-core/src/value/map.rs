@@ -11,7 +11,15 @@ pub type Map = ordermap::OrderMap<Value, Value>;
 
 /// An unordered map.
 #[cfg(not(feature = "preserve_order"))]
-pub type Map = std::collections::BTreeMap<Value, Value>;
+pub type Map = alloc::collections::BTreeMap<Value, Value>;
+
+/// A view into a single entry of an ordered map, which may either be vacant or occupied.
+#[cfg(feature = "preserve_order")]
+pub type MapEntry<'a> = ordermap::map::Entry<'a, Value, Value>;
+
+/// A view into a single entry of an unordered map, which may either be vacant or occupied.
+#[cfg(not(feature = "preserve_order"))]
+pub type MapEntry<'a> = alloc::collections::btree_map::Entry<'a, Value, Value>;
 
 #[cfg(any(test, feature = "testing"))]
 pub(crate) fn arbitrary_map() -> impl Strategy<Value = Map> {
@@ -40,11 +48,65 @@ impl MapValue {
         &self.0
     }
 
+    /// Returns a mutable reference to the internal map.
+    pub fn as_map_mut(&mut self) -> &mut Map {
+        &mut self.0
+    }
+
     /// Returns the internal map, consuming `self`.
     pub fn into_map(self) -> Map {
         self.0
     }
 
+    /// Returns the value for `key`, if any.
+    pub fn get(&self, key: &Value) -> Option<&Value> {
+        self.0.get(key)
+    }
+
+    /// Returns the mutable value for `key`, if any.
+    pub fn get_mut(&mut self, key: &Value) -> Option<&mut Value> {
+        self.0.get_mut(key)
+    }
+
+    /// Returns `true`, if the internal map contains `key`, otherwise `false`.
+    pub fn contains_key(&self, key: &Value) -> bool {
+        self.0.contains_key(key)
+    }
+
+    /// Inserts `value` for `key`, returning the value previously stored for `key`, if any.
+    pub fn insert(&mut self, key: Value, value: Value) -> Option<Value> {
+        self.0.insert(key, value)
+    }
+
+    /// Removes the entry for `key`, returning its value, if any.
+    ///
+    /// With the `preserve_order` feature enabled the order of the remaining entries is preserved.
+    pub fn remove(&mut self, key: &Value) -> Option<Value> {
+        self.0.remove(key)
+    }
+
+    /// Returns the entry for `key`, for in-place manipulation.
+    ///
+    /// ```
+    /// use lilliput_core::value::{MapValue, SeqValue, StringValue, Value};
+    ///
+    /// let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+    ///
+    /// let mut map = MapValue::default();
+    ///
+    /// for tag in ["a", "b"] {
+    ///     let tags = map
+    ///         .entry(string("tags"))
+    ///         .or_insert_with(|| Value::Seq(SeqValue::default()));
+    ///     tags.as_seq_mut().unwrap().push(string(tag));
+    /// }
+    ///
+    /// assert_eq!(map.get(&string("tags")).unwrap()[1], string("b"));
+    /// ```
+    pub fn entry(&mut self, key: Value) -> MapEntry<'_> {
+        self.0.entry(key)
+    }
+
     /// Returns the length of the internal map.
     pub fn len(&self) -> usize {
         self.0.len()
@@ -62,6 +124,33 @@ impl From<Map> for MapValue {
     }
 }
 
+impl<K, V> FromIterator<(K, V)> for MapValue
+where
+    K: Into<Value>,
+    V: Into<Value>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V> Extend<(K, V)> for MapValue
+where
+    K: Into<Value>,
+    V: Into<Value>,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.0.extend(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into())),
+        );
+    }
+}
+
 impl<'a> From<&'a MapValue> for &'a Map {
     fn from(value: &'a MapValue) -> Self {
         &value.0
@@ -74,8 +163,8 @@ impl From<MapValue> for Map {
     }
 }
 
-impl std::fmt::Debug for MapValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for MapValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_map().entries(self.0.iter()).finish()
     }
 }
@@ -106,6 +195,7 @@ pub(crate) struct MapKeyClassifier;
 #[cfg(feature = "serde")]
 pub(crate) enum MapKeyClass {
     Map(Value),
+    Ext,
 }
 
 #[cfg(feature = "serde")]
@@ -118,7 +208,13 @@ impl<'de> serde::de::DeserializeSeed<'de> for MapKeyClassifier {
     {
         use serde::Deserialize as _;
 
-        Ok(MapKeyClass::Map(Value::deserialize(deserializer)?))
+        // Extension values get visited as maps with a single, magic key:
+        match Value::deserialize(deserializer)? {
+            Value::String(key) if key.as_str() == super::ExtValue::NEWTYPE_NAME => {
+                Ok(MapKeyClass::Ext)
+            }
+            key => Ok(MapKeyClass::Map(key)),
+        }
     }
 }
 
@@ -132,7 +228,7 @@ mod tests {
         decoder::Decoder,
         encoder::Encoder,
         io::{SliceReader, VecWriter},
-        value::{NullValue, Value},
+        value::{NullValue, StringValue, Value},
     };
 
     use super::*;
@@ -150,6 +246,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_iter() {
+        let key = |value: &str| StringValue::from(value.to_owned());
+
+        let mut value: MapValue = [(key("a"), NullValue)].into_iter().collect();
+        value.extend([(key("b"), Value::Null(NullValue))]);
+
+        assert_eq!(value.len(), 2);
+        assert_eq!(
+            value.get(&Value::String(key("b"))),
+            Some(&Value::Null(NullValue))
+        );
+    }
+
     proptest! {
         #[test]
         fn encode_decode_roundtrip(value in MapValue::arbitrary(), config in EncoderConfig::arbitrary()) {
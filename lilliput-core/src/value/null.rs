@@ -14,14 +14,14 @@ impl From<()> for NullValue {
     }
 }
 
-impl std::fmt::Debug for NullValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for NullValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "null")
     }
 }
 
-impl std::fmt::Display for NullValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for NullValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "null")
     }
 }
@@ -47,7 +47,7 @@ impl<'de> serde::Deserialize<'de> for NullValue {
         impl serde::de::Visitor<'_> for NullValueVisitor {
             type Value = NullValue;
 
-            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 formatter.write_str("null value")
             }
 
@@ -0,0 +1,332 @@
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use crate::{
+    error::{Error, Result},
+    text,
+};
+
+use super::{StringValue, Value};
+
+/// A segment of a `Path`.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum PathSegment {
+    /// A map entry's key.
+    Key(Value),
+    /// A sequence item's index.
+    Index(usize),
+}
+
+impl From<Value> for PathSegment {
+    fn from(key: Value) -> Self {
+        Self::Key(key)
+    }
+}
+
+impl From<&str> for PathSegment {
+    fn from(key: &str) -> Self {
+        Self::Key(Value::String(StringValue::from(key.to_owned())))
+    }
+}
+
+impl From<String> for PathSegment {
+    fn from(key: String) -> Self {
+        Self::Key(Value::String(StringValue::from(key)))
+    }
+}
+
+impl From<usize> for PathSegment {
+    fn from(index: usize) -> Self {
+        Self::Index(index)
+    }
+}
+
+/// A path to a value nested within sequences and maps.
+///
+/// # Syntax
+///
+/// The empty path refers to the root value. Each segment is written as:
+///
+/// - `.name` for string keys consisting of ASCII alphanumerics
+///   and underscores only, not starting with a digit.
+/// - `["key"]` for all other string keys, using the string escapes
+///   of the [lilliput text](crate::text) format.
+/// - `[42_u8]`, `[null]`, … for non-string keys, using the lilliput text format.
+/// - `[42]` for sequence indices, which unlike integer keys carry no width suffix.
+///
+/// For example: `.users[0].name`, `["first name"]`, `.scores[42_u8]`.
+#[derive(Default, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Path(Vec<PathSegment>);
+
+impl Path {
+    /// Creates an empty path, referring to the root value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the path's segments.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+
+    /// Returns the number of segments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true`, if the path refers to the root value, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Appends `segment` to the path.
+    pub fn push(&mut self, segment: impl Into<PathSegment>) {
+        self.0.push(segment.into());
+    }
+
+    /// Removes the last segment, returning it, if any.
+    pub fn pop(&mut self) -> Option<PathSegment> {
+        self.0.pop()
+    }
+
+    /// Appends a map key segment, returning `self`.
+    pub fn with_key(mut self, key: impl Into<PathSegment>) -> Self {
+        self.push(key);
+        self
+    }
+
+    /// Appends a sequence index segment, returning `self`.
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.push(index);
+        self
+    }
+
+    /// Returns the path of the enclosing value, if any.
+    pub fn parent(&self) -> Option<Path> {
+        let (_, parent) = self.0.split_last()?;
+        Some(Self(parent.to_vec()))
+    }
+
+    /// Returns `true`, if `self` is a (non-strict) prefix of `other`, otherwise `false`.
+    pub fn is_prefix_of(&self, other: &Path) -> bool {
+        other.0.starts_with(&self.0)
+    }
+
+    /// Returns the path's segments, consuming `self`.
+    pub fn into_segments(self) -> Vec<PathSegment> {
+        self.0
+    }
+}
+
+impl From<Vec<PathSegment>> for Path {
+    fn from(segments: Vec<PathSegment>) -> Self {
+        Self(segments)
+    }
+}
+
+impl FromIterator<PathSegment> for Path {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = PathSegment>,
+    {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<PathSegment> for Path {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = PathSegment>,
+    {
+        self.0.extend(iter);
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Index(index) => write!(f, "[{index}]"),
+            Self::Key(Value::String(key)) if is_identifier(key.as_str()) => {
+                write!(f, ".{}", key.as_str())
+            }
+            Self::Key(key) => {
+                f.write_str("[")?;
+                text::write(key, f)?;
+                f.write_str("]")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.0 {
+            fmt::Display::fmt(segment, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut pos = 0;
+
+        while pos < path.len() {
+            let rest = &path[pos..];
+
+            if let Some(rest) = rest.strip_prefix('.') {
+                let len = rest
+                    .find(|char: char| !(char.is_ascii_alphanumeric() || char == '_'))
+                    .unwrap_or(rest.len());
+                let name = &rest[..len];
+
+                if !is_identifier(name) {
+                    return Err(invalid_segment(rest, "identifier", pos + 1));
+                }
+
+                segments.push(PathSegment::from(name));
+                pos += 1 + len;
+            } else if let Some(rest) = rest.strip_prefix('[') {
+                let digits =
+                    rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+                if digits > 0 && rest[digits..].starts_with(']') {
+                    let index = rest[..digits]
+                        .parse()
+                        .map_err(|_| Error::number_out_of_range(Some(pos + 1)))?;
+                    segments.push(PathSegment::Index(index));
+                    pos += 1 + digits + 1;
+                    continue;
+                }
+
+                let (key, len) =
+                    text::from_str_prefix(rest).map_err(|err| err.offset_pos(pos + 1))?;
+
+                let after_key = rest[len..].trim_start();
+                if !after_key.starts_with(']') {
+                    let pos = path.len() - after_key.len();
+                    return Err(invalid_segment(after_key, "`]`", pos));
+                }
+
+                segments.push(PathSegment::Key(key));
+                pos = path.len() - after_key.len() + 1;
+            } else {
+                return Err(invalid_segment(rest, "`.` or `[`", pos));
+            }
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+fn invalid_segment(rest: &str, expected: &str, pos: usize) -> Error {
+    let unexpected = match rest.chars().next() {
+        Some(char) => format!("`{char}`"),
+        None => "end of path".to_owned(),
+    };
+    Error::invalid_value(unexpected, expected.to_owned(), Some(pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        error::ErrorCode,
+        value::{IntValue, NullValue},
+    };
+
+    use super::*;
+
+    fn roundtrip(path: &Path, expected: &str) {
+        assert_eq!(path.to_string(), expected);
+        assert_eq!(&expected.parse::<Path>().unwrap(), path);
+    }
+
+    #[test]
+    fn format_and_parse() {
+        roundtrip(&Path::new(), "");
+        roundtrip(
+            &Path::new().with_key("users").with_index(0).with_key("name"),
+            ".users[0].name",
+        );
+        roundtrip(&Path::new().with_key("first name"), r#"["first name"]"#);
+        roundtrip(&Path::new().with_key("0"), r#"["0"]"#);
+        roundtrip(&Path::new().with_key(""), r#"[""]"#);
+        roundtrip(&Path::new().with_key("a\"]b"), r#"["a\"]b"]"#);
+        roundtrip(
+            &Path::new()
+                .with_key(Value::Int(IntValue::from(42_u8)))
+                .with_key(Value::Null(NullValue)),
+            "[42_u8][null]",
+        );
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let path: Path = "[ \"name\" ]".parse().unwrap();
+        assert_eq!(path, Path::new().with_key("name"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        for path in ["users", ".", ".0", "[", "[0", "[\"name\"", "[42]]", "[-1]"] {
+            assert!(
+                path.parse::<Path>().is_err(),
+                "{path:?} should fail to parse"
+            );
+        }
+
+        let err = ".users[0]x".parse::<Path>().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+        assert_eq!(err.pos(), Some(9));
+
+        // Positions of invalid keys are relative to the entire path:
+        let err = ".users[42_u1]".parse::<Path>().unwrap_err();
+        assert_eq!(err.pos(), Some(10));
+    }
+
+    #[test]
+    fn navigation() {
+        let mut path = Path::new().with_key("users").with_index(0);
+
+        assert!(Path::new().is_prefix_of(&path));
+        assert!(path.parent().unwrap().is_prefix_of(&path));
+        assert!(!path.is_prefix_of(&path.parent().unwrap()));
+
+        assert_eq!(path.pop(), Some(PathSegment::Index(0)));
+        assert_eq!(path.segments(), &[PathSegment::from("users")]);
+        assert_eq!(Path::new().parent(), None);
+    }
+
+    fn arbitrary_segment() -> impl Strategy<Value = PathSegment> {
+        prop_oneof![
+            any::<usize>().prop_map(PathSegment::Index),
+            any::<String>().prop_map(PathSegment::from),
+            "[a-z_][a-z0-9_]*".prop_map(PathSegment::from),
+            Value::arbitrary().prop_map(PathSegment::Key),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn display_parse_roundtrip(segments in proptest::collection::vec(arbitrary_segment(), 0..5)) {
+            let path = Path::from(segments);
+            let parsed: Path = path.to_string().parse().unwrap();
+            prop_assert_eq!(parsed, path);
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+use crate::{
+    config::EncoderConfig,
+    decoder::Decoder,
+    encoder::Encoder,
+    error::Result,
+    io::{Read, SliceReader, VecWriter, Write},
+    marker::Marker,
+};
+
+use super::Value;
+
+/// Policy used for pruning values before storage,
+/// via `Value::prune()` or `PrunePolicy::prune_encoded()`.
+///
+/// Pruning never removes the top-level value itself, nor any map keys.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PrunePolicy {
+    /// Whether to remove map entries with null values.
+    pub strip_nulls: bool,
+    /// Whether to remove empty sequences and maps from their enclosing
+    /// sequence or map, including ones that got emptied by pruning.
+    pub drop_empty: bool,
+    /// Whether to remove strings from sequences that already
+    /// contain an equal string at a lower index.
+    pub dedup_strings: bool,
+}
+
+impl PrunePolicy {
+    /// Enables all of the policy's transforms.
+    pub fn all() -> Self {
+        Self {
+            strip_nulls: true,
+            drop_empty: true,
+            dedup_strings: true,
+        }
+    }
+
+    /// Sets whether to remove map entries with null values to `strip_nulls`, returning `self`.
+    pub fn with_strip_nulls(mut self, strip_nulls: bool) -> Self {
+        self.strip_nulls = strip_nulls;
+        self
+    }
+
+    /// Sets whether to remove empty sequences and maps to `drop_empty`, returning `self`.
+    pub fn with_drop_empty(mut self, drop_empty: bool) -> Self {
+        self.drop_empty = drop_empty;
+        self
+    }
+
+    /// Sets whether to remove repeated strings from sequences to `dedup_strings`, returning `self`.
+    pub fn with_dedup_strings(mut self, dedup_strings: bool) -> Self {
+        self.dedup_strings = dedup_strings;
+        self
+    }
+
+    /// Prunes the next value of `decoder`, encoding the result into `encoder`,
+    /// without decoding it into a `Value`.
+    ///
+    /// Kept scalars (and map keys) get copied verbatim, while containers
+    /// get buffered, as their headers need to encode their pruned lengths.
+    ///
+    /// ```
+    /// use lilliput_core::{
+    ///     decoder::Decoder,
+    ///     encoder::Encoder,
+    ///     io::{SliceReader, VecWriter},
+    ///     value::PrunePolicy,
+    /// };
+    ///
+    /// // {"a": null, "b": 1}
+    /// let encoded = [0x1a, 0x61, b'a', 0x00, 0x61, b'b', 0xc1];
+    ///
+    /// let mut pruned = Vec::new();
+    /// let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+    /// let mut encoder = Encoder::from_writer(VecWriter::new(&mut pruned));
+    /// PrunePolicy::all().prune_encoded(&mut decoder, &mut encoder).unwrap();
+    ///
+    /// // {"b": 1}
+    /// assert_eq!(pruned, [0x19, 0x61, b'b', 0xc1]);
+    /// ```
+    pub fn prune_encoded<'de, R, W>(
+        &self,
+        decoder: &mut Decoder<R>,
+        encoder: &mut Encoder<W>,
+    ) -> Result<()>
+    where
+        R: Read<'de>,
+        W: Write,
+    {
+        let mut buffer = Vec::new();
+        self.prune_encoded_into(decoder, encoder.config(), &mut buffer)?;
+
+        encoder.encode_raw_bytes(&buffer)
+    }
+}
+
+impl Value {
+    /// Applies the transforms of `policy` to `self`, recursively.
+    ///
+    /// ```
+    /// use lilliput_core::value::{Map, MapValue, NullValue, PrunePolicy, StringValue, Value};
+    ///
+    /// let key = Value::String(StringValue::from("key".to_owned()));
+    /// let mut value = Value::Map(MapValue::from(Map::from_iter([(key, Value::Null(NullValue))])));
+    ///
+    /// value.prune(&PrunePolicy::default().with_strip_nulls(true));
+    /// assert_eq!(value, Value::Map(MapValue::default()));
+    /// ```
+    pub fn prune(&mut self, policy: &PrunePolicy) {
+        policy.prune_value(self);
+    }
+
+    /// Removes map entries with null values from `self`, recursively.
+    pub fn strip_nulls(&mut self) {
+        self.prune(&PrunePolicy::default().with_strip_nulls(true));
+    }
+}
+
+// MARK: - Private
+
+/// What a pruned value's parent needs to know about it.
+enum Pruned {
+    Null,
+    Empty,
+    String(String),
+    Other,
+}
+
+impl PrunePolicy {
+    fn prune_value(&self, value: &mut Value) {
+        match value {
+            Value::Seq(seq) => {
+                let mut strings = BTreeSet::new();
+
+                seq.0.retain_mut(|item| {
+                    self.prune_value(item);
+
+                    match item {
+                        Value::String(string) if self.dedup_strings => {
+                            strings.insert(string.0.clone())
+                        }
+                        _ => !self.drop_empty || !is_empty_container(item),
+                    }
+                });
+            }
+            Value::Map(map) => {
+                map.0.retain(|_, value| {
+                    self.prune_value(value);
+
+                    let is_stripped = self.strip_nulls && matches!(value, Value::Null(_));
+                    let is_dropped = self.drop_empty && is_empty_container(value);
+                    !is_stripped && !is_dropped
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn prune_encoded_into<'de, R>(
+        &self,
+        decoder: &mut Decoder<R>,
+        config: &EncoderConfig,
+        buffer: &mut Vec<u8>,
+    ) -> Result<Pruned>
+    where
+        R: Read<'de>,
+    {
+        match decoder.peek_marker()? {
+            // Columnar sequences can't be pruned item by item, as their items are interleaved:
+            Marker::Seq if decoder.peek_columnar_seq()? => {
+                self.prune_decoded_into(decoder, config, buffer)
+            }
+            // Neither can tabular sequences, as their items share their keys:
+            Marker::Seq if decoder.peek_tabular_seq()? => {
+                self.prune_decoded_into(decoder, config, buffer)
+            }
+            Marker::Seq => self.prune_encoded_seq_into(decoder, config, buffer),
+            // Front-coded maps can't be pruned entry by entry, as their keys depend on each other:
+            Marker::Map if decoder.peek_front_coded_map()? => {
+                self.prune_decoded_into(decoder, config, buffer)
+            }
+            Marker::Map => self.prune_encoded_map_into(decoder, config, buffer),
+            Marker::Null => {
+                decoder.decode_into_writer(&mut VecWriter::new(buffer))?;
+                Ok(Pruned::Null)
+            }
+            Marker::String if self.dedup_strings => {
+                let start = buffer.len();
+                decoder.decode_into_writer(&mut VecWriter::new(buffer))?;
+
+                let mut string_decoder = Decoder::from_reader(SliceReader::new(&buffer[start..]));
+                string_decoder.decode_string().map(Pruned::String)
+            }
+            _ => {
+                decoder.decode_into_writer(&mut VecWriter::new(buffer))?;
+                Ok(Pruned::Other)
+            }
+        }
+    }
+
+    /// Prunes the next value as a decoded `Value`, re-encoding it into `buffer`.
+    fn prune_decoded_into<'de, R>(
+        &self,
+        decoder: &mut Decoder<R>,
+        config: &EncoderConfig,
+        buffer: &mut Vec<u8>,
+    ) -> Result<Pruned>
+    where
+        R: Read<'de>,
+    {
+        let mut value = decoder.decode_value()?;
+        self.prune_value(&mut value);
+        Encoder::new(VecWriter::new(buffer), config.clone()).encode_value(&value)?;
+
+        Ok(if is_empty_container(&value) {
+            Pruned::Empty
+        } else {
+            Pruned::Other
+        })
+    }
+
+    fn prune_encoded_seq_into<'de, R>(
+        &self,
+        decoder: &mut Decoder<R>,
+        config: &EncoderConfig,
+        buffer: &mut Vec<u8>,
+    ) -> Result<Pruned>
+    where
+        R: Read<'de>,
+    {
+        let len = decoder.decode_seq_start()?;
+
+        let mut items = Vec::new();
+        let mut items_len = 0;
+        let mut strings = BTreeSet::new();
+
+        let mut index = 0;
+        loop {
+            let is_end = match len {
+                Some(len) => index == len,
+                None => decoder.decode_seq_stream_end()?,
+            };
+            if is_end {
+                break;
+            }
+            index += 1;
+
+            let start = items.len();
+            let is_kept = match self.prune_encoded_into(decoder, config, &mut items)? {
+                Pruned::String(string) => strings.insert(string),
+                Pruned::Empty => !self.drop_empty,
+                Pruned::Null | Pruned::Other => true,
+            };
+
+            if is_kept {
+                items_len += 1;
+            } else {
+                items.truncate(start);
+            }
+        }
+
+        let mut encoder = Encoder::new(VecWriter::new(buffer), config.clone());
+        encoder.encode_seq_header(&encoder.header_for_seq_len(items_len))?;
+        encoder.encode_raw_bytes(&items)?;
+
+        Ok(if items_len == 0 {
+            Pruned::Empty
+        } else {
+            Pruned::Other
+        })
+    }
+
+    fn prune_encoded_map_into<'de, R>(
+        &self,
+        decoder: &mut Decoder<R>,
+        config: &EncoderConfig,
+        buffer: &mut Vec<u8>,
+    ) -> Result<Pruned>
+    where
+        R: Read<'de>,
+    {
+        let len = decoder.decode_map_header()?.len();
+
+        let mut entries = Vec::new();
+        let mut entries_len = 0;
+
+        for _ in 0..len {
+            let start = entries.len();
+
+            decoder.decode_into_writer(&mut VecWriter::new(&mut entries))?; // key
+            let is_kept = match self.prune_encoded_into(decoder, config, &mut entries)? {
+                Pruned::Null => !self.strip_nulls,
+                Pruned::Empty => !self.drop_empty,
+                Pruned::String(_) | Pruned::Other => true,
+            };
+
+            if is_kept {
+                entries_len += 1;
+            } else {
+                entries.truncate(start);
+            }
+        }
+
+        let mut encoder = Encoder::new(VecWriter::new(buffer), config.clone());
+        encoder.encode_map_header(&encoder.header_for_map_len(entries_len))?;
+        encoder.encode_raw_bytes(&entries)?;
+
+        Ok(if entries_len == 0 {
+            Pruned::Empty
+        } else {
+            Pruned::Other
+        })
+    }
+}
+
+fn is_empty_container(value: &Value) -> bool {
+    match value {
+        Value::Seq(seq) => seq.is_empty(),
+        Value::Map(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::value::{IntValue, Map, MapValue, NullValue, SeqValue, StringValue};
+
+    use super::*;
+
+    fn string(value: &str) -> Value {
+        Value::String(StringValue::from(value.to_owned()))
+    }
+
+    fn int(value: u8) -> Value {
+        Value::Int(IntValue::from(value))
+    }
+
+    fn seq(items: Vec<Value>) -> Value {
+        Value::Seq(SeqValue::from(items))
+    }
+
+    fn map(entries: Vec<(Value, Value)>) -> Value {
+        Value::Map(MapValue::from(Map::from_iter(entries)))
+    }
+
+    fn subject() -> Value {
+        map(vec![
+            (string("null"), Value::Null(NullValue)),
+            (
+                string("empty"),
+                map(vec![(string("null"), Value::Null(NullValue))]),
+            ),
+            (
+                string("tags"),
+                seq(vec![string("a"), seq(vec![]), string("a"), int(1)]),
+            ),
+            (Value::Null(NullValue), seq(vec![Value::Null(NullValue)])),
+        ])
+    }
+
+    fn prune_encoded(value: &Value, policy: &PrunePolicy) -> Value {
+        let mut encoded = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder.encode_value(value).unwrap();
+
+        let mut pruned = Vec::new();
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut pruned));
+        policy.prune_encoded(&mut decoder, &mut encoder).unwrap();
+        assert_eq!(decoder.pos(), encoded.len());
+
+        Decoder::from_reader(SliceReader::new(&pruned))
+            .decode_value()
+            .unwrap()
+    }
+
+    #[test]
+    fn strip_nulls() {
+        let mut value = subject();
+        value.strip_nulls();
+
+        let expected = map(vec![
+            (string("empty"), map(vec![])),
+            (
+                string("tags"),
+                seq(vec![string("a"), seq(vec![]), string("a"), int(1)]),
+            ),
+            (Value::Null(NullValue), seq(vec![Value::Null(NullValue)])),
+        ]);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn prune_all() {
+        let mut value = subject();
+        value.prune(&PrunePolicy::all());
+
+        let expected = map(vec![
+            (string("tags"), seq(vec![string("a"), int(1)])),
+            (Value::Null(NullValue), seq(vec![Value::Null(NullValue)])),
+        ]);
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn top_level_is_kept() {
+        let mut value = seq(vec![seq(vec![])]);
+        value.prune(&PrunePolicy::all());
+
+        assert_eq!(value, seq(vec![]));
+    }
+
+    #[test]
+    fn streamed_seq() {
+        // ["a", [], "a"], streamed:
+        let encoded = [0x28, 0x61, b'a', 0x30, 0x61, b'a', 0x29];
+
+        let mut pruned = Vec::new();
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut pruned));
+        PrunePolicy::all()
+            .prune_encoded(&mut decoder, &mut encoder)
+            .unwrap();
+
+        assert_eq!(pruned, [0x31, 0x61, b'a']);
+    }
+
+    fn policies() -> impl Strategy<Value = PrunePolicy> {
+        (any::<bool>(), any::<bool>(), any::<bool>()).prop_map(
+            |(strip_nulls, drop_empty, dedup_strings)| PrunePolicy {
+                strip_nulls,
+                drop_empty,
+                dedup_strings,
+            },
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prune_encoded_matches_prune(value in Value::arbitrary(), policy in policies()) {
+            let mut pruned = value.clone();
+            pruned.prune(&policy);
+
+            prop_assert_eq!(prune_encoded(&value, &policy), pruned);
+        }
+
+        #[test]
+        fn prune_is_idempotent(value in Value::arbitrary(), policy in policies()) {
+            let mut pruned = value;
+            pruned.prune(&policy);
+
+            let mut pruned_twice = pruned.clone();
+            pruned_twice.prune(&policy);
+
+            prop_assert_eq!(pruned_twice, pruned);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use alloc::string::String;
+
+use super::{Map, NullValue, Value};
+
+/// The action taken for values exceeding a limit of a `SanitizePolicy`.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ExcessAction {
+    /// Truncates the value to fit the limit.
+    ///
+    /// Strings get cut to the maximum length, while
+    /// containers nested too deeply get replaced by null.
+    #[default]
+    Truncate,
+    /// Drops the value from its enclosing sequence or map.
+    ///
+    /// A dropped top-level value gets replaced by null.
+    Drop,
+}
+
+/// The action taken for map keys containing disallowed chars.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum InvalidKeyAction {
+    /// Drops the key's entry from its map.
+    #[default]
+    Drop,
+    /// Replaces each disallowed char of the key with the given char.
+    ///
+    /// Should a key thereby collide with one of another entry,
+    /// only the entry first in the map's order is retained.
+    Replace(char),
+}
+
+/// Policy used for sanitizing values, via `Value::sanitize()`.
+#[derive(Default, Clone, Debug)]
+pub struct SanitizePolicy {
+    /// Maximum nesting depth of sequences and maps, if limited.
+    ///
+    /// A top-level sequence or map has a depth of 1.
+    pub max_depth: Option<usize>,
+    /// Maximum length (in bytes) of strings, including map keys, if limited.
+    ///
+    /// Strings get truncated at the nearest char boundary.
+    /// Should a truncated key collide with one of another entry,
+    /// only the entry first in the map's order is retained.
+    pub max_string_len: Option<usize>,
+    /// Predicate for chars allowed in string map keys, if restricted.
+    pub key_charset: Option<fn(char) -> bool>,
+    /// Action taken for values exceeding `max_depth` or `max_string_len`.
+    pub on_excess: ExcessAction,
+    /// Action taken for map keys containing chars not allowed by `key_charset`.
+    pub on_invalid_key: InvalidKeyAction,
+}
+
+impl SanitizePolicy {
+    /// Limits nesting depth to `max_depth`, returning `self`.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Limits string lengths to `max_string_len` bytes, returning `self`.
+    pub fn with_max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = Some(max_string_len);
+        self
+    }
+
+    /// Restricts string map keys to chars matching `key_charset`, returning `self`.
+    pub fn with_key_charset(mut self, key_charset: fn(char) -> bool) -> Self {
+        self.key_charset = Some(key_charset);
+        self
+    }
+
+    /// Sets the action for values exceeding limits to `on_excess`, returning `self`.
+    pub fn with_excess_action(mut self, on_excess: ExcessAction) -> Self {
+        self.on_excess = on_excess;
+        self
+    }
+
+    /// Sets the action for invalid map keys to `on_invalid_key`, returning `self`.
+    pub fn with_invalid_key_action(mut self, on_invalid_key: InvalidKeyAction) -> Self {
+        self.on_invalid_key = on_invalid_key;
+        self
+    }
+}
+
+impl Value {
+    /// Enforces the limits of `policy` on `self`, recursively.
+    pub fn sanitize(&mut self, policy: &SanitizePolicy) {
+        if !policy.sanitize_value(self, 0) {
+            *self = Value::Null(NullValue);
+        }
+    }
+}
+
+// MARK: - Private
+
+impl SanitizePolicy {
+    /// Sanitizes `value`, enclosed by `depth` containers,
+    /// returning `false` if it is to be dropped.
+    fn sanitize_value(&self, value: &mut Value, depth: usize) -> bool {
+        match value {
+            Value::String(string) => self.sanitize_string(&mut string.0),
+            Value::Seq(_) | Value::Map(_) if self.exceeds_depth(depth + 1) => {
+                match self.on_excess {
+                    ExcessAction::Truncate => {
+                        *value = Value::Null(NullValue);
+                        true
+                    }
+                    ExcessAction::Drop => false,
+                }
+            }
+            Value::Seq(seq) => {
+                seq.0
+                    .retain_mut(|item| self.sanitize_value(item, depth + 1));
+
+                true
+            }
+            Value::Map(map) => {
+                let entries = core::mem::take(&mut map.0);
+                map.0 = self.sanitize_entries(entries, depth + 1);
+
+                true
+            }
+            _ => true,
+        }
+    }
+
+    fn sanitize_entries(&self, entries: Map, depth: usize) -> Map {
+        let mut sanitized = Map::default();
+
+        for (mut key, mut value) in entries {
+            if !self.sanitize_key(&mut key, depth) || !self.sanitize_value(&mut value, depth) {
+                continue;
+            }
+
+            // Keys may collide once sanitized, with the first one winning:
+            if sanitized.contains_key(&key) {
+                continue;
+            }
+
+            sanitized.insert(key, value);
+        }
+
+        sanitized
+    }
+
+    fn sanitize_key(&self, key: &mut Value, depth: usize) -> bool {
+        if let (Value::String(string), Some(is_allowed)) = (&mut *key, self.key_charset) {
+            if !string.0.chars().all(is_allowed) {
+                match self.on_invalid_key {
+                    InvalidKeyAction::Drop => return false,
+                    InvalidKeyAction::Replace(replacement) => {
+                        string.0 = string
+                            .0
+                            .chars()
+                            .map(|char| if is_allowed(char) { char } else { replacement })
+                            .collect();
+                    }
+                }
+            }
+        }
+
+        self.sanitize_value(key, depth)
+    }
+
+    fn sanitize_string(&self, string: &mut String) -> bool {
+        let Some(max_len) = self.max_string_len else {
+            return true;
+        };
+
+        if string.len() <= max_len {
+            return true;
+        }
+
+        match self.on_excess {
+            ExcessAction::Truncate => {
+                let mut len = max_len;
+                while !string.is_char_boundary(len) {
+                    len -= 1;
+                }
+                string.truncate(len);
+                true
+            }
+            ExcessAction::Drop => false,
+        }
+    }
+
+    fn exceeds_depth(&self, depth: usize) -> bool {
+        self.max_depth.is_some_and(|max_depth| depth > max_depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::value::{IntValue, MapValue, SeqValue, StringValue};
+
+    use super::*;
+
+    fn string(value: &str) -> Value {
+        Value::String(StringValue::from(value.to_owned()))
+    }
+
+    fn seq(items: Vec<Value>) -> Value {
+        Value::Seq(SeqValue::from(items))
+    }
+
+    fn map(entries: Vec<(Value, Value)>) -> Value {
+        Value::Map(MapValue::from(Map::from_iter(entries)))
+    }
+
+    fn int(value: i64) -> Value {
+        Value::Int(IntValue::from(value))
+    }
+
+    fn depth_of(value: &Value) -> usize {
+        match value {
+            Value::Seq(seq) => 1 + seq.0.iter().map(depth_of).max().unwrap_or(0),
+            Value::Map(map) => {
+                1 + (map.0.iter())
+                    .map(|(key, value)| depth_of(key).max(depth_of(value)))
+                    .max()
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn max_depth_truncate() {
+        let policy = SanitizePolicy::default().with_max_depth(2);
+
+        let mut value = seq(vec![int(1), seq(vec![int(2), seq(vec![int(3)])])]);
+        value.sanitize(&policy);
+
+        assert_eq!(
+            value,
+            seq(vec![int(1), seq(vec![int(2), Value::Null(NullValue)])])
+        );
+    }
+
+    #[test]
+    fn max_depth_drop() {
+        let policy = SanitizePolicy::default()
+            .with_max_depth(1)
+            .with_excess_action(ExcessAction::Drop);
+
+        let mut value = map(vec![(string("a"), int(1)), (string("b"), seq(vec![]))]);
+        value.sanitize(&policy);
+        assert_eq!(value, map(vec![(string("a"), int(1))]));
+
+        let mut value = seq(vec![]);
+        value.sanitize(&policy.clone().with_max_depth(0));
+        assert_eq!(value, Value::Null(NullValue));
+    }
+
+    #[test]
+    fn max_string_len() {
+        let policy = SanitizePolicy::default().with_max_string_len(4);
+
+        let mut value = seq(vec![string("lilliput"), string("añb")]);
+        value.sanitize(&policy);
+        assert_eq!(value, seq(vec![string("lill"), string("añb")]));
+
+        // Truncation must not split the two-byte 'ñ':
+        let mut value = string("abñ");
+        value.sanitize(&policy.clone().with_max_string_len(3));
+        assert_eq!(value, string("ab"));
+
+        let mut value = seq(vec![string("lilliput"), string("ok")]);
+        value.sanitize(&policy.with_excess_action(ExcessAction::Drop));
+        assert_eq!(value, seq(vec![string("ok")]));
+    }
+
+    #[test]
+    fn key_charset() {
+        let policy = SanitizePolicy::default()
+            .with_key_charset(|char| char.is_ascii_alphanumeric() || char == '_');
+
+        let value = map(vec![
+            (string("valid_key"), int(1)),
+            (string("in valid"), int(2)),
+            (int(3), int(3)),
+        ]);
+
+        let mut dropped = value.clone();
+        dropped.sanitize(&policy);
+        assert_eq!(
+            dropped,
+            map(vec![(string("valid_key"), int(1)), (int(3), int(3))])
+        );
+
+        let mut replaced = value;
+        replaced.sanitize(&policy.with_invalid_key_action(InvalidKeyAction::Replace('_')));
+        assert_eq!(
+            replaced,
+            map(vec![
+                (string("valid_key"), int(1)),
+                (string("in_valid"), int(2)),
+                (int(3), int(3)),
+            ])
+        );
+    }
+
+    #[test]
+    fn colliding_keys() {
+        let policy = SanitizePolicy::default()
+            .with_key_charset(|char| char.is_ascii_alphanumeric() || char == '_')
+            .with_invalid_key_action(InvalidKeyAction::Replace('_'))
+            .with_max_string_len(4);
+
+        let mut value = map(vec![
+            (string("a b"), int(1)),
+            (string("a_b"), int(2)),
+            (string("abcd"), int(3)),
+            (string("abcde"), int(4)),
+        ]);
+        value.sanitize(&policy);
+
+        assert_eq!(
+            value,
+            map(vec![(string("a_b"), int(1)), (string("abcd"), int(3))])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn sanitized_values_are_bounded(value in Value::arbitrary(), max_depth in 0_usize..4, max_string_len in 0_usize..8) {
+            let policy = SanitizePolicy::default()
+                .with_max_depth(max_depth)
+                .with_max_string_len(max_string_len);
+
+            let mut truncated = value.clone();
+            truncated.sanitize(&policy);
+            prop_assert!(depth_of(&truncated) <= max_depth);
+
+            let mut dropped = value;
+            dropped.sanitize(&policy.with_excess_action(ExcessAction::Drop));
+            prop_assert!(depth_of(&dropped) <= max_depth);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 #[cfg(any(test, feature = "testing"))]
 use proptest::{prelude::*, sample::SizeRange};
 #[cfg(any(test, feature = "testing"))]
@@ -34,11 +36,60 @@ impl SeqValue {
         &self.0
     }
 
+    /// Returns a mutable slice of the entire internal vec.
+    pub fn as_mut_slice(&mut self) -> &mut [Value] {
+        &mut self.0
+    }
+
+    /// Returns a mutable reference to the internal vec.
+    pub fn as_vec_mut(&mut self) -> &mut Seq {
+        &mut self.0
+    }
+
     /// Returns the internal vec, consuming `self`.
     pub fn into_vec(self) -> Seq {
         self.0
     }
 
+    /// Returns the item at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.0.get(index)
+    }
+
+    /// Returns the mutable item at `index`, if any.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Value> {
+        self.0.get_mut(index)
+    }
+
+    /// Appends `value` to the back of the internal vec.
+    pub fn push(&mut self, value: Value) {
+        self.0.push(value)
+    }
+
+    /// Removes the last item, returning it, if any.
+    pub fn pop(&mut self) -> Option<Value> {
+        self.0.pop()
+    }
+
+    /// Inserts `value` at `index`, shifting all items after it to the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: Value) {
+        self.0.insert(index, value)
+    }
+
+    /// Removes the item at `index`, returning it,
+    /// and shifting all items after it to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> Value {
+        self.0.remove(index)
+    }
+
     /// Returns the length of the internal vec.
     pub fn len(&self) -> usize {
         self.0.len()
@@ -56,6 +107,24 @@ impl From<Seq> for SeqValue {
     }
 }
 
+impl<T> FromIterator<T> for SeqValue
+where
+    T: Into<Value>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> Extend<T> for SeqValue
+where
+    T: Into<Value>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(Into::into));
+    }
+}
+
 impl<'a> From<&'a SeqValue> for &'a [Value] {
     fn from(value: &'a SeqValue) -> Self {
         &value.0
@@ -68,8 +137,8 @@ impl From<SeqValue> for Seq {
     }
 }
 
-impl std::fmt::Debug for SeqValue {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for SeqValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.0.iter()).finish()
     }
 }
@@ -104,7 +173,7 @@ mod tests {
         decoder::Decoder,
         encoder::Encoder,
         io::{SliceReader, VecWriter},
-        value::{NullValue, Value},
+        value::{BoolValue, NullValue, Value},
     };
 
     use super::*;
@@ -122,6 +191,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_iter() {
+        let mut value: SeqValue = [NullValue, NullValue].into_iter().collect();
+        value.extend([BoolValue(true)]);
+
+        assert_eq!(
+            value.as_slice(),
+            [
+                Value::Null(NullValue),
+                Value::Null(NullValue),
+                Value::Bool(BoolValue(true)),
+            ]
+        );
+        assert_eq!(
+            Value::from(vec![NullValue]),
+            Value::Seq(SeqValue::from(vec![Value::Null(NullValue)]))
+        );
+    }
+
     proptest! {
         #[test]
         fn encode_decode_roundtrip(value in SeqValue::arbitrary(), config in EncoderConfig::arbitrary()) {
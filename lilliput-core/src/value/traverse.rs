@@ -0,0 +1,290 @@
+use super::{Path, PathSegment, Value};
+
+/// The order in which `Value::visit_mut()` visits nested values.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum VisitOrder {
+    /// Visits each value before the values nested within it.
+    ///
+    /// Mutations of a container affect which of its contents get visited.
+    #[default]
+    PreOrder,
+    /// Visits each value after the values nested within it.
+    PostOrder,
+}
+
+impl Value {
+    /// Visits `self` and all values nested within it, in `order`,
+    /// passing each value's path to `visit`, which may mutate the value.
+    ///
+    /// Map keys are immutable, so only the values of map entries get visited.
+    ///
+    /// ```
+    /// use lilliput_core::value::{FloatValue, SeqValue, Value, VisitOrder};
+    ///
+    /// let mut value = Value::Seq(SeqValue::from(vec![Value::Float(FloatValue::from(1.25))]));
+    ///
+    /// value.visit_mut(VisitOrder::PreOrder, |_path, node| {
+    ///     if let Value::Float(float) = node {
+    ///         *float = FloatValue::from(float.as_f64().round());
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(value, Value::Seq(SeqValue::from(vec![Value::Float(FloatValue::from(1.0))])));
+    /// ```
+    pub fn visit_mut<F>(&mut self, order: VisitOrder, mut visit: F)
+    where
+        F: FnMut(&Path, &mut Value),
+    {
+        visit_mut(self, order, &mut Path::new(), &mut visit);
+    }
+
+    /// Removes all values nested within `self` for which `keep` returns `false`,
+    /// along with their contents, which don't get passed to `keep`.
+    ///
+    /// The top-level value itself is never removed.
+    ///
+    /// ```
+    /// use lilliput_core::value::{NullValue, SeqValue, Value};
+    ///
+    /// let mut value = Value::Seq(SeqValue::from(vec![Value::Null(NullValue)]));
+    /// value.retain_paths(|_path, node| !matches!(node, Value::Null(_)));
+    ///
+    /// assert_eq!(value, Value::Seq(SeqValue::default()));
+    /// ```
+    pub fn retain_paths<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&Path, &Value) -> bool,
+    {
+        retain_paths(self, &mut Path::new(), &mut keep);
+    }
+
+    /// Removes the value at `path`, returning it, if any.
+    ///
+    /// Returns `None` for the empty path, as the top-level value can't be removed.
+    pub fn remove_path(&mut self, path: &Path) -> Option<Value> {
+        let (last, parent) = path.segments().split_last()?;
+
+        let mut value = self;
+        for segment in parent {
+            value = match (value, segment) {
+                (Value::Seq(seq), PathSegment::Index(index)) => seq.0.get_mut(*index)?,
+                (Value::Map(map), PathSegment::Key(key)) => map.0.get_mut(key)?,
+                _ => return None,
+            };
+        }
+
+        match (value, last) {
+            (Value::Seq(seq), PathSegment::Index(index)) if *index < seq.0.len() => {
+                Some(seq.0.remove(*index))
+            }
+            (Value::Map(map), PathSegment::Key(key)) => map.0.remove(key),
+            _ => None,
+        }
+    }
+}
+
+// MARK: - Private
+
+fn visit_mut<F>(value: &mut Value, order: VisitOrder, path: &mut Path, visit: &mut F)
+where
+    F: FnMut(&Path, &mut Value),
+{
+    if order == VisitOrder::PreOrder {
+        visit(path, value);
+    }
+
+    match value {
+        Value::Seq(seq) => {
+            for (index, item) in seq.0.iter_mut().enumerate() {
+                path.push(index);
+                visit_mut(item, order, path, visit);
+                path.pop();
+            }
+        }
+        Value::Map(map) => {
+            for (key, value) in map.0.iter_mut() {
+                path.push(key.clone());
+                visit_mut(value, order, path, visit);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+
+    if order == VisitOrder::PostOrder {
+        visit(path, value);
+    }
+}
+
+fn retain_paths<F>(value: &mut Value, path: &mut Path, keep: &mut F)
+where
+    F: FnMut(&Path, &Value) -> bool,
+{
+    match value {
+        Value::Seq(seq) => {
+            // Indices refer to the items' original positions:
+            let mut index = 0;
+            seq.0.retain_mut(|item| {
+                path.push(index);
+                index += 1;
+
+                let is_kept = keep(path, item);
+                if is_kept {
+                    retain_paths(item, path, keep);
+                }
+
+                path.pop();
+                is_kept
+            });
+        }
+        Value::Map(map) => {
+            map.0.retain(|key, value| {
+                path.push(key.clone());
+
+                let is_kept = keep(path, value);
+                if is_kept {
+                    retain_paths(value, path, keep);
+                }
+
+                path.pop();
+                is_kept
+            });
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::value::{FloatValue, IntValue, Map, MapValue, NullValue, SeqValue, StringValue};
+
+    use super::*;
+
+    fn string(value: &str) -> Value {
+        Value::String(StringValue::from(value.to_owned()))
+    }
+
+    fn int(value: u8) -> Value {
+        Value::Int(IntValue::from(value))
+    }
+
+    fn seq(items: Vec<Value>) -> Value {
+        Value::Seq(SeqValue::from(items))
+    }
+
+    fn map(entries: Vec<(Value, Value)>) -> Value {
+        Value::Map(MapValue::from(Map::from_iter(entries)))
+    }
+
+    fn subject() -> Value {
+        map(vec![
+            (string("a"), seq(vec![int(1), Value::Null(NullValue)])),
+            (string("b"), Value::Null(NullValue)),
+        ])
+    }
+
+    fn visited_paths(order: VisitOrder) -> Vec<String> {
+        let mut paths = Vec::new();
+        subject().visit_mut(order, |path, _| paths.push(path.to_string()));
+        paths
+    }
+
+    #[test]
+    fn visit_order() {
+        assert_eq!(
+            visited_paths(VisitOrder::PreOrder),
+            ["", ".a", ".a[0]", ".a[1]", ".b"]
+        );
+        assert_eq!(
+            visited_paths(VisitOrder::PostOrder),
+            [".a[0]", ".a[1]", ".a", ".b", ""]
+        );
+    }
+
+    #[test]
+    fn pre_order_visits_mutated_contents() {
+        let mut value = seq(vec![int(1)]);
+        let mut visited = 0;
+
+        value.visit_mut(VisitOrder::PreOrder, |path, node| {
+            visited += 1;
+            if path.is_empty() {
+                *node = seq(vec![int(1), int(2)]);
+            }
+        });
+
+        assert_eq!(visited, 3);
+    }
+
+    #[test]
+    fn strip_nulls() {
+        let mut value = subject();
+        value.retain_paths(|_, node| !matches!(node, Value::Null(_)));
+
+        assert_eq!(value, map(vec![(string("a"), seq(vec![int(1)]))]));
+    }
+
+    #[test]
+    fn retain_paths_by_path() {
+        let mut value = seq(vec![int(0), int(1), int(2), seq(vec![int(3), int(4)])]);
+        value.retain_paths(|path, _| path.to_string() != "[1]" && path.to_string() != "[3][0]");
+
+        assert_eq!(value, seq(vec![int(0), int(2), seq(vec![int(4)])]));
+    }
+
+    #[test]
+    fn remove_path() {
+        let mut value = subject();
+
+        let path: Path = ".a[1]".parse().unwrap();
+        assert_eq!(value.remove_path(&path), Some(Value::Null(NullValue)));
+        assert_eq!(value.remove_path(&path), None);
+
+        assert_eq!(
+            value.remove_path(&".b".parse().unwrap()),
+            Some(Value::Null(NullValue))
+        );
+        assert_eq!(value.remove_path(&".a.b".parse().unwrap()), None);
+        assert_eq!(value.remove_path(&Path::new()), None);
+
+        assert_eq!(value, map(vec![(string("a"), seq(vec![int(1)]))]));
+    }
+
+    #[test]
+    fn round_floats() {
+        let mut value = seq(vec![Value::Float(FloatValue::from(1.75_f64))]);
+
+        value.visit_mut(VisitOrder::PostOrder, |_, node| {
+            if let Value::Float(float) = node {
+                *float = FloatValue::from(float.as_f64().round());
+            }
+        });
+
+        assert_eq!(value, seq(vec![Value::Float(FloatValue::from(2.0_f64))]));
+    }
+
+    proptest! {
+        #[test]
+        fn visit_orders_agree(value in Value::arbitrary()) {
+            let mut pre_order = Vec::new();
+            value.clone().visit_mut(VisitOrder::PreOrder, |path, _| pre_order.push(path.clone()));
+
+            let mut post_order = Vec::new();
+            value.clone().visit_mut(VisitOrder::PostOrder, |path, _| post_order.push(path.clone()));
+
+            pre_order.sort();
+            post_order.sort();
+            prop_assert_eq!(pre_order, post_order);
+        }
+
+        #[test]
+        fn retain_all(value in Value::arbitrary()) {
+            let mut retained = value.clone();
+            retained.retain_paths(|_, _| true);
+            prop_assert_eq!(retained, value);
+        }
+    }
+}
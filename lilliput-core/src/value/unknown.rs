@@ -0,0 +1,51 @@
+use alloc::vec::Vec;
+
+use crate::binary::BytesSlice;
+
+/// Represents a value of a type unknown to this version of the format,
+/// as identified by one of the reserved header bytes.
+///
+/// Unknown values only get decoded if enabled via
+/// [`DecoderConfig::unknown_headers`](crate::config::DecoderConfig::unknown_headers),
+/// and are kept opaque, so that encoding them again reproduces their original type.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct UnknownValue {
+    /// The value's (reserved) header byte.
+    pub header: u8,
+    /// The value's body, in its encoded form.
+    pub body: Vec<u8>,
+}
+
+impl UnknownValue {
+    /// Creates an unknown value from its `header` byte and encoded `body`.
+    pub fn new(header: u8, body: Vec<u8>) -> Self {
+        Self { header, body }
+    }
+}
+
+impl core::fmt::Debug for UnknownValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown({:#04x}, ", self.header)?;
+        core::fmt::Debug::fmt(&BytesSlice(&self.body), f)?;
+        write!(f, ")")
+    }
+}
+
+impl core::fmt::Display for UnknownValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown({:#04x}, ", self.header)?;
+        core::fmt::Display::fmt(&BytesSlice(&self.body), f)?;
+        write!(f, ")")
+    }
+}
+
+/// Serialized as its body's bytes, as serde has no notion of unknown types.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UnknownValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.body)
+    }
+}
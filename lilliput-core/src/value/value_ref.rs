@@ -0,0 +1,154 @@
+use alloc::{borrow::Cow, vec::Vec};
+
+use super::{
+    BoolValue, BytesValue, ExtValue, FloatValue, IntValue, MapValue, NullValue, SeqValue,
+    StringValue, UnitValue, UnknownValue, Value,
+};
+
+/// Represents a value, borrowing its strings and byte arrays from the input, where possible.
+///
+/// Decoded via `Decoder::decode_value_ref()`, which avoids copying strings and
+/// byte arrays when decoding from a slice. Unlike `Value` maps are kept as their
+/// entries, in the order they were decoded in.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ValueRef<'a> {
+    /// Represents a integer number.
+    Int(IntValue),
+
+    /// Represents a string.
+    String(Cow<'a, str>),
+
+    /// Represents a sequence of values.
+    Seq(Vec<ValueRef<'a>>),
+
+    /// Represents a map of key-value pairs, as its entries.
+    Map(Vec<(ValueRef<'a>, ValueRef<'a>)>),
+
+    /// Represents a floating-point number.
+    Float(FloatValue),
+
+    /// Represents a byte array.
+    Bytes(Cow<'a, [u8]>),
+
+    /// Represents a boolean.
+    Bool(BoolValue),
+
+    /// Represents a unit value.
+    Unit(UnitValue),
+
+    /// Represents a null value.
+    Null(NullValue),
+
+    /// Represents an extension value.
+    Ext(ExtValue),
+
+    /// Represents a value of an unknown type.
+    Unknown(UnknownValue),
+}
+
+impl<'a> ValueRef<'a> {
+    /// Returns the string, if `self` is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if `self` is a byte array.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the items, if `self` is a sequence.
+    pub fn as_seq(&self) -> Option<&[ValueRef<'a>]> {
+        match self {
+            Self::Seq(seq) => Some(seq),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries, if `self` is a map.
+    pub fn as_map(&self) -> Option<&[(ValueRef<'a>, ValueRef<'a>)]> {
+        match self {
+            Self::Map(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    /// Returns the value for the string `key`, if `self` is a map containing it.
+    ///
+    /// Maps are searched linearly, returning the first matching entry's value.
+    pub fn get(&self, key: &str) -> Option<&ValueRef<'a>> {
+        self.as_map()?
+            .iter()
+            .find(|(entry_key, _)| entry_key.as_str() == Some(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns `true` if `self` (and all of its nested values) borrow from the input,
+    /// rather than holding copies of their strings and byte arrays.
+    pub fn is_borrowed(&self) -> bool {
+        match self {
+            Self::String(string) => matches!(string, Cow::Borrowed(_)),
+            Self::Bytes(bytes) => matches!(bytes, Cow::Borrowed(_)),
+            Self::Seq(seq) => seq.iter().all(Self::is_borrowed),
+            Self::Map(map) => map
+                .iter()
+                .all(|(key, value)| key.is_borrowed() && value.is_borrowed()),
+            _ => true,
+        }
+    }
+
+    /// Returns an owned copy of `self`, as a `Value`.
+    ///
+    /// Map entries with duplicate keys are collapsed, with the last one winning.
+    pub fn to_value(&self) -> Value {
+        self.clone().into()
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(value: ValueRef<'_>) -> Self {
+        match value {
+            ValueRef::Int(value) => Value::Int(value),
+            ValueRef::String(value) => Value::String(StringValue::from(value.into_owned())),
+            ValueRef::Seq(value) => Value::Seq(SeqValue::from_iter(value)),
+            ValueRef::Map(value) => Value::Map(MapValue::from_iter(value)),
+            ValueRef::Float(value) => Value::Float(value),
+            ValueRef::Bytes(value) => Value::Bytes(BytesValue::from(value.into_owned())),
+            ValueRef::Bool(value) => Value::Bool(value),
+            ValueRef::Unit(value) => Value::Unit(value),
+            ValueRef::Null(value) => Value::Null(value),
+            ValueRef::Ext(value) => Value::Ext(value),
+            ValueRef::Unknown(value) => Value::Unknown(value),
+        }
+    }
+}
+
+impl From<Value> for ValueRef<'_> {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Int(value) => Self::Int(value),
+            Value::String(value) => Self::String(Cow::Owned(value.into())),
+            Value::Seq(value) => Self::Seq(value.0.into_iter().map(From::from).collect()),
+            Value::Map(value) => Self::Map(
+                value
+                    .0
+                    .into_iter()
+                    .map(|(key, value)| (key.into(), value.into()))
+                    .collect(),
+            ),
+            Value::Float(value) => Self::Float(value),
+            Value::Bytes(value) => Self::Bytes(Cow::Owned(value.into())),
+            Value::Bool(value) => Self::Bool(value),
+            Value::Unit(value) => Self::Unit(value),
+            Value::Null(value) => Self::Null(value),
+            Value::Ext(value) => Self::Ext(value),
+            Value::Unknown(value) => Self::Unknown(value),
+        }
+    }
+}
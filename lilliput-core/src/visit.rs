@@ -0,0 +1,324 @@
+//! Visiting encoded values, without decoding them into `Value`s.
+//!
+//! ```
+//! use lilliput_core::{
+//!     error::Result,
+//!     value::IntValue,
+//!     visit::{walk, ValueVisitor},
+//! };
+//!
+//! #[derive(Default)]
+//! struct SumInts(i128);
+//!
+//! impl ValueVisitor for SumInts {
+//!     fn visit_int(&mut self, value: IntValue) -> Result<()> {
+//!         self.0 += i128::try_from(value).expect("should fit into i128");
+//!         Ok(())
+//!     }
+//! }
+//!
+//! // [1, -2, "three", [4]]
+//! let encoded = [0x34, 0xc1, 0xe3, 0x65, b't', b'h', b'r', b'e', b'e', 0x31, 0xc4];
+//!
+//! let mut visitor = SumInts::default();
+//! walk(&encoded, &mut visitor).unwrap();
+//! assert_eq!(visitor.0, 3);
+//! ```
+
+use crate::{
+    decoder::Decoder,
+    error::{Error, Result},
+    io::SliceReader,
+    value::{FloatValue, IntValue},
+};
+
+/// Whether to walk into a container, or to skip its contents.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Walk {
+    /// Visit the container's contents.
+    #[default]
+    Enter,
+    /// Skip the container's contents, without visiting them.
+    Skip,
+}
+
+/// A visitor of encoded values, receiving borrowed scalars and container events.
+///
+/// All methods default to doing nothing (and entering all containers),
+/// so implementors only need to implement the ones they are interested in.
+///
+/// Map entries get visited as alternating keys and values.
+/// Returning an error from any method aborts the walk.
+pub trait ValueVisitor {
+    /// Visits an integer value.
+    fn visit_int(&mut self, value: IntValue) -> Result<()> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Visits a string value.
+    fn visit_str(&mut self, value: &str) -> Result<()> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Visits a float value.
+    fn visit_float(&mut self, value: FloatValue) -> Result<()> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Visits a byte sequence value.
+    fn visit_bytes(&mut self, value: &[u8]) -> Result<()> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Visits a boolean value.
+    fn visit_bool(&mut self, value: bool) -> Result<()> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Visits a unit value.
+    fn visit_unit(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Visits a null value.
+    fn visit_null(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Visits the start of a sequence value,
+    /// with a length of `None` for streamed sequences.
+    fn visit_seq_start(&mut self, len: Option<usize>) -> Result<Walk> {
+        let _ = len;
+        Ok(Walk::Enter)
+    }
+
+    /// Visits the end of a sequence value, whether entered or skipped.
+    fn visit_seq_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Visits the start of a map value.
+    fn visit_map_start(&mut self, len: usize) -> Result<Walk> {
+        let _ = len;
+        Ok(Walk::Enter)
+    }
+
+    /// Visits the end of a map value, whether entered or skipped.
+    fn visit_map_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Walks the single encoded value contained in `bytes`, calling `visitor` for its contents.
+///
+/// Returns an error if `bytes` contain anything past the value.
+pub fn walk<V>(bytes: &[u8], visitor: &mut V) -> Result<()>
+where
+    V: ?Sized + ValueVisitor,
+{
+    let mut decoder = Decoder::from_reader(SliceReader::new(bytes));
+    decoder.walk_value(visitor)?;
+
+    let pos = decoder.into_reader().pos();
+
+    if pos != bytes.len() {
+        return Err(Error::trailing_bytes(bytes.len() - pos, Some(pos)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use test_log::test;
+
+    use crate::{
+        encoder::Encoder,
+        error::ErrorCode,
+        io::VecWriter,
+        value::{
+            BoolValue, BytesValue, Map, MapValue, NullValue, SeqValue, StringValue, UnitValue,
+            Value,
+        },
+    };
+
+    use super::*;
+
+    /// Rebuilds the visited values, verifying the order of events.
+    #[derive(Default)]
+    struct Rebuild {
+        stack: Vec<Vec<Value>>,
+        kinds: Vec<bool>,
+        done: Option<Value>,
+    }
+
+    impl Rebuild {
+        fn push(&mut self, value: Value) -> Result<()> {
+            match self.stack.last_mut() {
+                Some(items) => items.push(value),
+                None => self.done = Some(value),
+            }
+            Ok(())
+        }
+
+        fn start(&mut self, is_map: bool) -> Result<Walk> {
+            self.stack.push(Vec::new());
+            self.kinds.push(is_map);
+            Ok(Walk::Enter)
+        }
+    }
+
+    impl ValueVisitor for Rebuild {
+        fn visit_int(&mut self, value: IntValue) -> Result<()> {
+            self.push(Value::Int(value))
+        }
+
+        fn visit_str(&mut self, value: &str) -> Result<()> {
+            self.push(Value::String(StringValue::from(value.to_owned())))
+        }
+
+        fn visit_float(&mut self, value: FloatValue) -> Result<()> {
+            self.push(Value::Float(value))
+        }
+
+        fn visit_bytes(&mut self, value: &[u8]) -> Result<()> {
+            self.push(Value::Bytes(BytesValue::from(value.to_vec())))
+        }
+
+        fn visit_bool(&mut self, value: bool) -> Result<()> {
+            self.push(Value::Bool(BoolValue::from(value)))
+        }
+
+        fn visit_unit(&mut self) -> Result<()> {
+            self.push(Value::Unit(UnitValue))
+        }
+
+        fn visit_null(&mut self) -> Result<()> {
+            self.push(Value::Null(NullValue))
+        }
+
+        fn visit_seq_start(&mut self, _len: Option<usize>) -> Result<Walk> {
+            self.start(false)
+        }
+
+        fn visit_seq_end(&mut self) -> Result<()> {
+            assert_eq!(self.kinds.pop(), Some(false));
+            let items = self.stack.pop().unwrap();
+            self.push(Value::Seq(SeqValue::from(items)))
+        }
+
+        fn visit_map_start(&mut self, _len: usize) -> Result<Walk> {
+            self.start(true)
+        }
+
+        fn visit_map_end(&mut self) -> Result<()> {
+            assert_eq!(self.kinds.pop(), Some(true));
+            let items = self.stack.pop().unwrap();
+            assert_eq!(items.len() % 2, 0);
+
+            let mut map = Map::default();
+            let mut items = items.into_iter();
+            while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                map.insert(key, value);
+            }
+
+            self.push(Value::Map(MapValue::from(map)))
+        }
+    }
+
+    /// Counts strings, skipping the contents of all maps.
+    #[derive(Default)]
+    struct CountStrings {
+        strings: usize,
+        maps: usize,
+    }
+
+    impl ValueVisitor for CountStrings {
+        fn visit_str(&mut self, _value: &str) -> Result<()> {
+            self.strings += 1;
+            Ok(())
+        }
+
+        fn visit_map_start(&mut self, _len: usize) -> Result<Walk> {
+            Ok(Walk::Skip)
+        }
+
+        fn visit_map_end(&mut self) -> Result<()> {
+            self.maps += 1;
+            Ok(())
+        }
+    }
+
+    fn encode(value: &Value) -> Vec<u8> {
+        let mut encoded: Vec<u8> = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_value(value)
+            .unwrap();
+        encoded
+    }
+
+    fn string(value: &str) -> Value {
+        Value::String(StringValue::from(value.to_owned()))
+    }
+
+    #[test]
+    fn skip() {
+        let map = Map::from_iter([(string("key"), string("value"))]);
+        let value = Value::Seq(SeqValue::from(vec![
+            string("a"),
+            Value::Map(MapValue::from(map)),
+            Value::Seq(SeqValue::from(vec![string("b")])),
+        ]));
+
+        let mut visitor = CountStrings::default();
+        walk(&encode(&value), &mut visitor).unwrap();
+
+        assert_eq!(visitor.strings, 2);
+        assert_eq!(visitor.maps, 1);
+    }
+
+    #[test]
+    fn trailing_bytes() {
+        let mut encoded = encode(&string("lilliput"));
+        encoded.push(0b0000_0000);
+
+        let error_code = walk(&encoded, &mut Rebuild::default()).unwrap_err().code();
+        assert_eq!(error_code, ErrorCode::TrailingBytes);
+    }
+
+    proptest! {
+        #[test]
+        fn walk_matches_decode(value in Value::arbitrary()) {
+            let encoded = encode(&value);
+
+            let mut visitor = Rebuild::default();
+            walk(&encoded, &mut visitor).unwrap();
+
+            prop_assert!(visitor.stack.is_empty());
+            prop_assert_eq!(visitor.done, Some(value));
+        }
+
+        #[test]
+        fn skip_everything(value in Value::arbitrary()) {
+            struct SkipAll;
+
+            impl ValueVisitor for SkipAll {
+                fn visit_seq_start(&mut self, _len: Option<usize>) -> Result<Walk> {
+                    Ok(Walk::Skip)
+                }
+
+                fn visit_map_start(&mut self, _len: usize) -> Result<Walk> {
+                    Ok(Walk::Skip)
+                }
+            }
+
+            walk(&encode(&value), &mut SkipAll).unwrap();
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::num::FpCategory;
+use core::num::FpCategory;
 
 use crate::bits::FpToBits;
 use crate::floats::{F16, F24, F32, F40, F48, F56, F64, F8};
@@ -18,8 +18,8 @@
 #[repr(transparent)]
 pub struct F8(pub(crate) u8);
 
-impl std::fmt::Debug for F8 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F8 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:08b}", self.0)
     }
 }
@@ -44,8 +44,8 @@ impl std::fmt::Debug for F8 {
 #[repr(transparent)]
 pub struct F16(pub(crate) u16);
 
-impl std::fmt::Debug for F16 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:016b}", self.0)
     }
 }
@@ -71,8 +71,8 @@ impl std::fmt::Debug for F16 {
 #[repr(transparent)]
 pub struct F24(pub(crate) u32);
 
-impl std::fmt::Debug for F24 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F24 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:024b}", self.0)
     }
 }
@@ -97,8 +97,8 @@ impl std::fmt::Debug for F24 {
 #[repr(transparent)]
 pub struct F32(pub(crate) u32);
 
-impl std::fmt::Debug for F32 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F32 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:032b}", self.0)
     }
 }
@@ -124,8 +124,8 @@ impl std::fmt::Debug for F32 {
 #[repr(transparent)]
 pub struct F40(pub(crate) u64);
 
-impl std::fmt::Debug for F40 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F40 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:040b}", self.0)
     }
 }
@@ -151,8 +151,8 @@ impl std::fmt::Debug for F40 {
 #[repr(transparent)]
 pub struct F48(pub(crate) u64);
 
-impl std::fmt::Debug for F48 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F48 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:048b}", self.0)
     }
 }
@@ -178,8 +178,8 @@ impl std::fmt::Debug for F48 {
 #[repr(transparent)]
 pub struct F56(pub(crate) u64);
 
-impl std::fmt::Debug for F56 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F56 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:056b}", self.0)
     }
 }
@@ -204,8 +204,8 @@ impl std::fmt::Debug for F56 {
 #[repr(transparent)]
 pub struct F64(pub(crate) u64);
 
-impl std::fmt::Debug for F64 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for F64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:064b}", self.0)
     }
 }
@@ -1,3 +1,8 @@
+#![no_std]
+
+#[cfg(test)]
+extern crate std;
+
 mod be_bytes;
 mod bits;
 mod classify;
@@ -1,4 +1,4 @@
-use std::mem::transmute_copy;
+use core::mem::transmute_copy;
 
 use crate::floats::{F32, F64};
 
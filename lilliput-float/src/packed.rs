@@ -14,7 +14,7 @@ pub enum PackedFloat {
 }
 
 impl PartialOrd for PackedFloat {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         match (self, other) {
             (Self::F8(lhs), Self::F8(rhs)) => lhs.partial_cmp(rhs),
             (Self::F16(lhs), Self::F16(rhs)) => lhs.partial_cmp(rhs),
@@ -212,7 +212,7 @@ impl_float_truncate!(F64 => [F8, F16, F24, F32, F40, F48, F56]);
 
 #[cfg(test)]
 mod tests {
-    use std::num::FpCategory;
+    use core::num::FpCategory;
 
     use proptest::prelude::*;
 
@@ -1,4 +1,4 @@
-use std::num::FpCategory;
+use core::num::FpCategory;
 
 #[derive(Clone, Debug)]
 pub enum PackedFloatValidator<T> {
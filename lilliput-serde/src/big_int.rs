@@ -0,0 +1,115 @@
+//! Serialization of `num_bigint::BigInt` as big integer extension values.
+//!
+//! This module is meant for use with serde's `with` attribute:
+//!
+//! ```
+//! use lilliput_serde::{de::from_slice, ser::to_vec};
+//! use num_bigint::BigInt;
+//!
+//! #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+//! struct Supply {
+//!     #[serde(with = "lilliput_serde::big_int")]
+//!     total: BigInt,
+//! }
+//!
+//! let supply = Supply {
+//!     total: "1267650600228229401496703205376".parse().unwrap(),
+//! };
+//!
+//! let encoded = to_vec(&supply).unwrap();
+//! assert_eq!(from_slice::<Supply>(&encoded).unwrap(), supply);
+//! ```
+//!
+//! Lilliput encodes big integers as extension values wrapping their two's
+//! complement bytes, while other formats get them as decimal strings.
+//!
+//! Plain integers and decimal strings still get accepted when deserializing.
+
+use core::fmt;
+
+use num_bigint::BigInt;
+use serde::{de, ser, Deserializer, Serialize, Serializer};
+
+use lilliput_core::{encoder::Encoder, io::Write};
+
+use crate::{config::SerializerConfig, error::Error, value::ValueSerializer};
+
+/// Name of the newtype struct wrapping the decimal strings of big integers.
+pub(crate) const BIG_INT_NAME: &str = "$lilliput::BigInt";
+
+/// Serializes `big_int` as big integer.
+pub fn serialize<S>(big_int: &BigInt, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_newtype_struct(BIG_INT_NAME, &big_int.to_string())
+}
+
+/// Deserializes a big integer as `BigInt`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<BigInt, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(BIG_INT_NAME, BigIntVisitor)
+}
+
+/// Encodes the decimal `string` of the big integer newtype struct.
+pub(crate) fn encode_big_int<W, T>(encoder: &mut Encoder<W>, string: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let string = string.serialize(&ValueSerializer::new(SerializerConfig::default()))?;
+
+    let big_int: BigInt = string
+        .as_str()
+        .and_then(|string| string.parse().ok())
+        .ok_or_else(|| <Error as ser::Error>::custom("expected decimal integer string"))?;
+
+    encoder.encode_big_int(&big_int)
+}
+
+// MARK: - Private
+
+/// Visitor of big integers, as decimal strings or integers.
+struct BigIntVisitor;
+
+impl<'de> de::Visitor<'de> for BigIntVisitor {
+    type Value = BigInt;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("big integer")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
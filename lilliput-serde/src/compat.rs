@@ -0,0 +1,107 @@
+//! Compatibility shims, for easing migration from other serde binary formats.
+//!
+//! The shims mirror the names of common idioms of `rmp-serde` and `ciborium`,
+//! mapped to their closest lilliput equivalents:
+//!
+//! | Idiom                                        | lilliput                                              |
+//! | -------------------------------------------- | ----------------------------------------------------- |
+//! | `rmp_serde::to_vec_named(value)`             | `to_vec_named(value)`                                 |
+//! | `rmp_serde::to_vec(value)`                   | `to_vec_with_config(value, StructMode::Tuple.into())` |
+//! | `rmp_serde::from_read(reader)`               | `from_read(reader)`                                   |
+//! | `rmp_serde::Serializer::with_struct_map()`   | `Serializer::with_struct_map()`                       |
+//! | `rmp_serde::Serializer::with_struct_tuple()` | `Serializer::with_struct_tuple()`                     |
+//! | `ciborium::into_writer(value, writer)`       | `into_writer(value, writer)`                          |
+//! | `ciborium::from_reader(reader)`              | `de::from_reader(reader)`                             |
+//!
+//! Only the APIs get mirrored, not the encodings, so data
+//! encoded by either of those crates needs to be re-encoded.
+
+use serde::Serialize;
+
+use crate::{
+    config::{SerializerConfig, StructRepr},
+    error::Result,
+    ser::{to_vec_with_config, Serializer},
+};
+
+/// The encoding of structs, named after `rmp-serde`'s
+/// `with_struct_map()`/`with_struct_tuple()` toggles.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum StructMode {
+    /// Encode structs as maps of fields, keyed by field name.
+    #[default]
+    Map,
+    /// Encode structs as tuples (i.e. sequences) of fields.
+    Tuple,
+}
+
+impl From<StructMode> for StructRepr {
+    fn from(mode: StructMode) -> Self {
+        match mode {
+            StructMode::Map => Self::Map,
+            StructMode::Tuple => Self::Seq,
+        }
+    }
+}
+
+impl From<StructMode> for SerializerConfig {
+    fn from(mode: StructMode) -> Self {
+        Self::default().with_struct_repr(mode.into())
+    }
+}
+
+impl SerializerConfig {
+    /// Sets struct-repr to `StructRepr::Map`, returning `self`.
+    pub fn with_struct_map(self) -> Self {
+        self.with_struct_repr(StructMode::Map.into())
+    }
+
+    /// Sets struct-repr to `StructRepr::Seq`, returning `self`.
+    pub fn with_struct_tuple(self) -> Self {
+        self.with_struct_repr(StructMode::Tuple.into())
+    }
+}
+
+impl<W> Serializer<W> {
+    /// Serializes structs as maps of fields, keyed by field name, returning `self`.
+    pub fn with_struct_map(mut self) -> Self {
+        self.config = self.config.with_struct_map();
+        self
+    }
+
+    /// Serializes structs as sequences of fields, returning `self`.
+    pub fn with_struct_tuple(mut self) -> Self {
+        self.config = self.config.with_struct_tuple();
+        self
+    }
+}
+
+/// Serializes `value` into a `Vec<u8>`, with structs as maps of fields.
+pub fn to_vec_named<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    to_vec_with_config(value, StructMode::Map.into())
+}
+
+/// Deserializes an instance of `T` from `reader`.
+#[cfg(feature = "std")]
+pub fn from_read<R, T>(reader: R) -> Result<T>
+where
+    R: std::io::Read,
+    T: serde::de::DeserializeOwned,
+{
+    crate::de::from_reader(reader)
+}
+
+/// Serializes `value` into `writer`.
+///
+/// Equivalent to `ser::to_writer()`, but with its arguments swapped.
+#[cfg(feature = "std")]
+pub fn into_writer<T, W>(value: &T, writer: W) -> Result<()>
+where
+    T: ?Sized + Serialize,
+    W: std::io::Write,
+{
+    crate::ser::to_writer(writer, value)
+}
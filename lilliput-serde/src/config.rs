@@ -1,15 +1,29 @@
-//! Configurations used for serializing values.
+//! Configurations used for serializing/deserializing values.
 
-use lilliput_core::config::EncoderConfig;
+use lilliput_core::config::{DecoderConfig, EncoderConfig, FormatVersion, PackingMode};
 
 /// The representation to serialize structs to.
 #[derive(Default, Clone, Eq, PartialEq, Debug)]
 pub enum StructRepr {
     /// Serialize as sequence of fields.
-    #[default]
+    ///
+    /// Fields get decoded positionally, so schemas may only evolve by
+    /// appending fields, which older data would then lack.
+    /// Deserializing accepts either representation, without the need
+    /// for configuring it, or for a custom `Deserialize` impl.
     Seq,
-    /// Serialize as map of fields.
+    /// Serialize as map of fields, keyed by field name.
+    #[default]
     Map,
+    /// Serialize as map of fields, keyed by field index.
+    ///
+    /// Retains the tolerance of `Map` towards reordered or omitted
+    /// fields, while getting close to the compactness of `Seq`.
+    ///
+    /// Indices get mapped back to names via the struct's declared fields,
+    /// so schemas may evolve by appending fields (with defaults, for older
+    /// data), but not by reordering or removing any but the last ones.
+    IndexMap,
 }
 
 /// The representation to serialize enums to.
@@ -22,6 +36,35 @@ pub enum EnumVariantRepr {
     Name,
 }
 
+/// The representation to serialize sequences of unknown length to.
+#[derive(Default, Clone, Eq, PartialEq, Debug)]
+pub enum UnsizedSeqRepr {
+    /// Buffer the elements until their count is known,
+    /// then serialize as a regular sequence.
+    #[default]
+    Buffered,
+    /// Serialize elements directly, as a streamed sequence,
+    /// terminated by an end marker.
+    ///
+    /// Avoids buffering, at the cost of one extra byte, and of producing
+    /// output not decodable by versions prior to streamed sequences.
+    Streamed,
+}
+
+/// The representation to serialize units (and unit structs) to.
+#[derive(Default, Clone, Eq, PartialEq, Debug)]
+pub enum UnitRepr {
+    /// Serialize as unit.
+    #[default]
+    Unit,
+    /// Serialize as null, for consumers without a concept of unit,
+    /// such as data models derived from JSON.
+    ///
+    /// Deserializing units accepts either representation, but
+    /// `Some(())` becomes indistinguishable from `None`.
+    Null,
+}
+
 /// Configuration used for serializing values.
 #[derive(Default, Clone, Debug)]
 pub struct SerializerConfig {
@@ -29,6 +72,10 @@ pub struct SerializerConfig {
     pub struct_repr: StructRepr,
     /// The representation to serialize enums to.
     pub enum_variant_repr: EnumVariantRepr,
+    /// The representation to serialize sequences of unknown length to.
+    pub unsized_seq_repr: UnsizedSeqRepr,
+    /// The representation to serialize units to.
+    pub unit_repr: UnitRepr,
     /// Low-level configuration for encoding values.
     pub encoder: EncoderConfig,
 }
@@ -46,9 +93,205 @@ impl SerializerConfig {
         self
     }
 
+    /// Sets unsized-seq-repr to `unsized_seq_repr`, returning `self`.
+    pub fn with_unsized_seq_repr(mut self, unsized_seq_repr: UnsizedSeqRepr) -> Self {
+        self.unsized_seq_repr = unsized_seq_repr;
+        self
+    }
+
+    /// Sets unit-repr to `unit_repr`, returning `self`.
+    pub fn with_unit_repr(mut self, unit_repr: UnitRepr) -> Self {
+        self.unit_repr = unit_repr;
+        self
+    }
+
     /// Sets encoder to `encoder`, returning `self`.
     pub fn with_encoder(mut self, encoder: EncoderConfig) -> Self {
         self.encoder = encoder;
         self
     }
+
+    /// Returns a configuration producing the smallest output.
+    ///
+    /// Packs every length, integer and float down to its smallest
+    /// lossless representation, and serializes struct fields and enum
+    /// variants by index, rather than by name, which requires both ends
+    /// to agree on the order of fields and variants (see `StructRepr::IndexMap`).
+    ///
+    /// Packing takes extra work per value, so serializing may be slower.
+    pub fn smallest() -> Self {
+        Self::default()
+            .with_struct_repr(StructRepr::IndexMap)
+            .with_enum_variant_repr(EnumVariantRepr::Index)
+            .with_unsized_seq_repr(UnsizedSeqRepr::Buffered)
+            .with_encoder(EncoderConfig::default().with_packing(PackingMode::Optimal))
+    }
+
+    /// Returns a configuration for serializing as fast as possible.
+    ///
+    /// Avoids packing (encoding values in their native width instead),
+    /// as well as buffering of sequences of unknown length (streaming them
+    /// instead), and serializes struct fields and enum variants by index,
+    /// rather than by name, which requires both ends to agree on the order
+    /// of fields and variants (see `StructRepr::IndexMap`).
+    ///
+    /// The output is larger than the default's, and only decodable by
+    /// versions supporting streamed sequences.
+    pub fn fastest() -> Self {
+        Self::default()
+            .with_struct_repr(StructRepr::IndexMap)
+            .with_enum_variant_repr(EnumVariantRepr::Index)
+            .with_unsized_seq_repr(UnsizedSeqRepr::Streamed)
+            .with_encoder(EncoderConfig::default().with_packing(PackingMode::None))
+    }
+
+    /// Returns a configuration producing canonical output, where equal
+    /// values get serialized to identical bytes, across releases.
+    ///
+    /// Pins the header selection rules to `FormatVersion::V1` and packs
+    /// values optimally (and losslessly), with sequences of unknown length
+    /// getting buffered, so they match the encoding of sized ones.
+    ///
+    /// Maps get serialized in their iteration order, so only ones with a
+    /// deterministic order (e.g. `BTreeMap`, rather than `HashMap`) are canonical.
+    pub fn canonical() -> Self {
+        let encoder = EncoderConfig::default()
+            .with_format(FormatVersion::V1)
+            .with_packing(PackingMode::Optimal);
+
+        Self::default()
+            .with_unsized_seq_repr(UnsizedSeqRepr::Buffered)
+            .with_encoder(encoder)
+    }
+
+    /// Returns a configuration for the broadest compatibility,
+    /// with both older decoders and evolving schemas.
+    ///
+    /// Serializes structs as maps keyed by field name and enum variants by
+    /// name, which tolerates reordered, added (and, with defaults, removed)
+    /// fields and variants, buffers sequences of unknown length, since older
+    /// decoders don't support streamed ones, and serializes units as nulls,
+    /// for consumers without a concept of unit.
+    pub fn compatible() -> Self {
+        Self::default()
+            .with_struct_repr(StructRepr::Map)
+            .with_enum_variant_repr(EnumVariantRepr::Name)
+            .with_unsized_seq_repr(UnsizedSeqRepr::Buffered)
+            .with_unit_repr(UnitRepr::Null)
+            .with_encoder(EncoderConfig::default().with_format(FormatVersion::V1))
+    }
+}
+
+/// The policy for handling unknown struct fields while deserializing.
+///
+/// Unless collected, the values of unknown fields get skipped without being
+/// decoded (i.e. without allocating), however large, as do any other values
+/// ignored via `deserialize_ignored_any()`. This does not hold while interning
+/// strings, which requires skipped strings to be decoded all the same.
+///
+/// Errors for unknown fields, whether raised by this policy or by
+/// `#[serde(deny_unknown_fields)]`, are `ErrorKind::UnknownField`s carrying
+/// the field's name, positioned at its key.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum UnknownFieldsPolicy {
+    /// Silently ignore unknown fields.
+    #[default]
+    Ignore,
+    /// Fail with an error upon encountering an unknown field,
+    /// equivalent to `#[serde(deny_unknown_fields)]`.
+    Error,
+    /// Collect unknown fields, retrievable after deserialization
+    /// via `Deserializer::take_unknown_fields()`.
+    Collect,
+}
+
+/// The default nesting depth limit of deserialized values.
+pub const DEFAULT_MAX_DEPTH: u8 = 128;
+
+/// Configuration used for deserializing values.
+///
+/// Enums get deserialized from either of `EnumVariantRepr`'s
+/// representations, so there is no need for configuring them.
+#[derive(Clone, Debug)]
+pub struct DeserializerConfig {
+    /// The policy for handling unknown struct fields.
+    pub unknown_fields: UnknownFieldsPolicy,
+    /// The nesting depth of sequences, maps and enums,
+    /// upon reaching which deserializing fails.
+    pub max_depth: u8,
+    /// The maximum length of strings and byte arrays, if limited.
+    pub max_len: Option<usize>,
+    /// The maximum number of elements of sequences and maps, if limited.
+    pub max_elements: Option<usize>,
+    /// The maximum number of bytes to decode in total, if limited.
+    ///
+    /// Lengths get checked against the limit before reading any of
+    /// their bytes, so oversized values get rejected upfront.
+    pub max_size: Option<usize>,
+    /// Whether `from_slice()` and `from_reader()` reject any bytes
+    /// following the deserialized value with a `TrailingBytes` error.
+    pub ensure_eof: bool,
+    /// Low-level configuration for decoding values.
+    pub decoder: DecoderConfig,
+}
+
+impl Default for DeserializerConfig {
+    fn default() -> Self {
+        Self {
+            unknown_fields: UnknownFieldsPolicy::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_len: None,
+            max_elements: None,
+            max_size: None,
+            ensure_eof: false,
+            decoder: DecoderConfig::default(),
+        }
+    }
+}
+
+impl DeserializerConfig {
+    /// Sets unknown-fields policy to `unknown_fields`, returning `self`.
+    pub fn with_unknown_fields(mut self, unknown_fields: UnknownFieldsPolicy) -> Self {
+        self.unknown_fields = unknown_fields;
+        self
+    }
+
+    /// Sets max-depth to `max_depth`, returning `self`.
+    pub fn with_max_depth(mut self, max_depth: u8) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets max-len to `max_len`, returning `self`.
+    pub fn with_max_len(mut self, max_len: Option<usize>) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    /// Sets max-elements to `max_elements`, returning `self`.
+    pub fn with_max_elements(mut self, max_elements: Option<usize>) -> Self {
+        self.max_elements = max_elements;
+        self
+    }
+
+    /// Sets max-size to `max_size`, returning `self`.
+    pub fn with_max_size(mut self, max_size: Option<usize>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets ensure-eof to `ensure_eof`, returning `self`.
+    pub fn with_ensure_eof(mut self, ensure_eof: bool) -> Self {
+        self.ensure_eof = ensure_eof;
+        self
+    }
+
+    /// Sets decoder to `decoder`, returning `self`.
+    ///
+    /// Enabling `DecoderConfig::force_owned` makes deserializing
+    /// borrowed strings and bytes (e.g. `&str`) fail.
+    pub fn with_decoder(mut self, decoder: DecoderConfig) -> Self {
+        self.decoder = decoder;
+        self
+    }
 }
@@ -1,40 +1,134 @@
 //! Deserializers for deserializing lilliput-encoded values.
 
+use alloc::collections::BTreeSet;
+use core::marker::PhantomData;
+
 use serde::{
-    de::{self, Error as _, IntoDeserializer as _},
+    de::{
+        self,
+        value::{StrDeserializer, U64Deserializer},
+        Error as _, IntoDeserializer as _,
+    },
     Deserialize, Deserializer as _,
 };
 
 use lilliput_core::{
-    decoder::Decoder,
-    io::{Read, Reference, SliceReader, StdIoReader},
+    config::DuplicateKeyPolicy,
+    decoder::{ColumnarSeq, Decoder, DecoderStats, FrontCodedKeys},
+    dictionary::Dictionary,
+    error::ErrorCode,
+    io::{Read, Reference, SliceReader},
     marker::Marker,
-    value::{FloatValue, IntValue, SignedIntValue, UnsignedIntValue},
+    value::{
+        ExtValue, FloatValue, IntValue, Map, SignedIntValue, UnsignedIntValue, Value, ValueKind,
+    },
 };
 
-use crate::error::{Error, Result};
+use crate::{
+    config::{DeserializerConfig, UnknownFieldsPolicy},
+    error::{Error, Result},
+};
 
 /// A deserializer for deserializing lilliput values.
 pub struct Deserializer<R> {
     decoder: Decoder<R>,
+    config: DeserializerConfig,
     scratch: Vec<u8>,
+    unknown_fields: Map,
     remaining_depth: u8,
     #[cfg(feature = "unbounded_depth")]
     disable_depth_limit: bool,
+    path: Option<PathTracker>,
 }
 
 impl<R> Deserializer<R> {
     /// Creates a deserializer from a `reader`.
     pub fn from_reader(reader: R) -> Self {
+        Self::new(reader, DeserializerConfig::default())
+    }
+
+    /// Creates a deserializer from a `reader`, configured by `config`.
+    pub fn new(reader: R, config: DeserializerConfig) -> Self {
         Deserializer {
-            decoder: Decoder::from_reader(reader),
+            decoder: Decoder::new(reader, config.decoder),
+            remaining_depth: config.max_depth,
+            config,
             scratch: Vec::new(),
-            remaining_depth: 128,
+            unknown_fields: Map::default(),
             #[cfg(feature = "unbounded_depth")]
             disable_depth_limit: false,
+            path: None,
         }
     }
 
+    /// Enables the collection of allocation statistics, returning `self`.
+    pub fn with_stats(mut self) -> Self {
+        self.decoder = self.decoder.with_stats();
+        self
+    }
+
+    /// Returns the statistics collected so far, if enabled.
+    pub fn stats(&self) -> Option<&DecoderStats> {
+        self.decoder.stats()
+    }
+
+    /// Attaches `dictionary` to the deserializer's decoder, returning `self`.
+    ///
+    /// See [`Decoder::with_dictionary()`] for details.
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.decoder = self.decoder.with_dictionary(dictionary);
+        self
+    }
+
+    /// Returns the dictionary attached to the deserializer's decoder, if any.
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.decoder.dictionary()
+    }
+
+    /// Enables tracking of the path to the value being deserialized, returning `self`.
+    ///
+    /// Errors then report the path to the value they occurred at
+    /// (e.g. `.users[3].address.zip`), via `Error::path()`:
+    ///
+    /// ```
+    /// use lilliput_serde::{de::Deserializer, ser::to_vec};
+    /// use lilliput_core::io::SliceReader;
+    /// use serde::Deserialize as _;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct User {
+    ///     name: &'static str,
+    ///     zip: &'static str,
+    /// }
+    ///
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct ParsedUser {
+    ///     name: String,
+    ///     zip: u32,
+    /// }
+    ///
+    /// let users = vec![User { name: "Bob", zip: "12345" }];
+    /// let encoded = to_vec(&users).unwrap();
+    ///
+    /// let mut deserializer = Deserializer::from_reader(SliceReader::new(&encoded)).with_path_tracking();
+    /// let err = Vec::<ParsedUser>::deserialize(&mut deserializer).unwrap_err();
+    /// assert_eq!(err.path(), Some(".[0].zip"));
+    /// ```
+    ///
+    /// Tracking comes at the cost of recording the key of each map entry.
+    pub fn with_path_tracking(mut self) -> Self {
+        self.path = Some(PathTracker::default());
+        self
+    }
+
+    /// Returns the unknown struct fields collected so far, resetting them.
+    ///
+    /// The fields are grouped by the name of their containing struct,
+    /// as a map of maps, and only get collected for `UnknownFieldsPolicy::Collect`.
+    pub fn take_unknown_fields(&mut self) -> Map {
+        std::mem::take(&mut self.unknown_fields)
+    }
+
     /// Parse arbitrarily deep Lilliput structures without any consideration for
     /// overflowing the stack.
     ///
@@ -55,13 +149,89 @@ impl<R> Deserializer<R> {
     }
 }
 
+impl<'de, R> Deserializer<R>
+where
+    R: Read<'de>,
+{
+    /// Checks that the input has been deserialized completely,
+    /// returning a `TrailingBytes` error, if not.
+    ///
+    /// To be called after deserializing the last value, unless enabled via
+    /// `DeserializerConfig::ensure_eof` for `from_slice()` and `from_reader()`.
+    pub fn end(&mut self) -> Result<()> {
+        self.decoder.end()
+    }
+
+    /// Turns the deserializer into an iterator over successive values of type `T`,
+    /// e.g. the records of a stream, until reaching the end of the input.
+    ///
+    /// ```
+    /// use lilliput_core::io::SliceReader;
+    /// use lilliput_serde::{de::Deserializer, ser::to_vec};
+    ///
+    /// let mut encoded = Vec::new();
+    /// for record in ["first", "second"] {
+    ///     encoded.extend(to_vec(&record).unwrap());
+    /// }
+    ///
+    /// let records: Vec<String> = Deserializer::from_reader(SliceReader::new(&encoded))
+    ///     .into_iter()
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(records, ["first", "second"]);
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn into_iter<T>(self) -> StreamDeserializer<'de, R, T>
+    where
+        T: Deserialize<'de>,
+    {
+        StreamDeserializer {
+            de: self,
+            failed: false,
+            marker: PhantomData,
+        }
+    }
+}
+
 /// Deserializes an instance of `T` from `bytes`.
+///
+/// Any bytes following the value get ignored, see `from_slice_exact()`.
 pub fn from_slice<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: 'de + Deserialize<'de>,
+{
+    from_slice_with_config(bytes, DeserializerConfig::default())
+}
+
+/// Deserializes an instance of `T` from `bytes`, failing with
+/// a `TrailingBytes` error if any bytes follow the value.
+///
+/// ```
+/// use lilliput_core::error::ErrorCode;
+/// use lilliput_serde::de::{from_slice, from_slice_exact};
+///
+/// let encoded = [0xC1, 0xC2];
+/// assert_eq!(from_slice::<u8>(&encoded).unwrap(), 1);
+///
+/// let err = from_slice_exact::<u8>(&encoded).unwrap_err();
+/// assert_eq!(err.code(), ErrorCode::TrailingBytes);
+/// assert_eq!(err.pos(), Some(1));
+/// ```
+pub fn from_slice_exact<'de, T>(bytes: &'de [u8]) -> Result<T>
+where
+    T: 'de + Deserialize<'de>,
+{
+    from_slice_with_config(bytes, DeserializerConfig::default().with_ensure_eof(true))
+}
+
+/// Deserializes an instance of `T` from `bytes`, configured by `config`.
+pub fn from_slice_with_config<'de, T>(bytes: &'de [u8], config: DeserializerConfig) -> Result<T>
 where
     T: 'de + Deserialize<'de>,
 {
     let reader = SliceReader::new(bytes);
-    T::deserialize(&mut Deserializer::from_reader(reader))
+    deserialize_from(Deserializer::new(reader, config))
 }
 
 /// Deserializes an instance of `T` from `reader`.
@@ -71,8 +241,119 @@ where
     R: std::io::Read,
     T: de::DeserializeOwned,
 {
-    let reader = StdIoReader::new(reader);
-    T::deserialize(&mut Deserializer::from_reader(reader))
+    from_reader_with_config(reader, DeserializerConfig::default())
+}
+
+/// Deserializes an instance of `T` from `reader`, configured by `config`.
+#[cfg(feature = "std")]
+pub fn from_reader_with_config<R, T>(reader: R, config: DeserializerConfig) -> Result<T>
+where
+    R: std::io::Read,
+    T: de::DeserializeOwned,
+{
+    let reader = lilliput_core::io::StdIoReader::new(reader);
+    deserialize_from(Deserializer::new(reader, config))
+}
+
+/// Deserializes an instance of `T` from `bytes`, compressed
+/// with the codec advertised by their header (see `ser::to_vec_compressed()`).
+///
+/// *This function is only available if lilliput_serde is built with
+/// the `"zstd"` and/or `"lz4"` features, enabling the respective codecs.*
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub fn from_slice_compressed<T>(bytes: &[u8]) -> Result<T>
+where
+    T: de::DeserializeOwned,
+{
+    from_reader(lilliput_core::compression::CompressedReader::new(bytes)?)
+}
+
+/// Deserializes each of the length-prefixed frames in `bytes` (as produced
+/// by `lilliput_core::framing::FrameEncoder`) as a `T`, in parallel.
+///
+/// The results preserve the order of the frames, with each frame failing
+/// (or succeeding) independently of the others. Only a truncated last frame
+/// fails as a whole, before deserializing any of the frames.
+///
+/// *This function is only available if lilliput_serde is built with the
+/// `"parallel"` feature.*
+#[cfg(feature = "parallel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "parallel")))]
+pub fn decode_frames_parallel<T>(bytes: &[u8]) -> Result<Vec<Result<T>>>
+where
+    T: de::DeserializeOwned + Send,
+{
+    use lilliput_core::framing::FRAME_PREFIX_LEN;
+    use rayon::prelude::*;
+
+    let mut frames: Vec<(usize, &[u8])> = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let body_pos = pos + FRAME_PREFIX_LEN;
+        let prefix = bytes.get(pos..body_pos).ok_or_else(Error::end_of_file)?;
+        let prefix: [u8; FRAME_PREFIX_LEN] = prefix.try_into().expect("prefix should have length");
+        let len = u32::from_be_bytes(prefix) as usize;
+
+        let body = bytes
+            .get(body_pos..(body_pos + len))
+            .ok_or_else(Error::end_of_file)?;
+        frames.push((body_pos, body));
+
+        pos = body_pos + len;
+    }
+
+    Ok(frames
+        .into_par_iter()
+        .map(|(body_pos, body)| from_slice(body).map_err(|err| err.offset_pos(body_pos)))
+        .collect())
+}
+
+/// An iterator over successive values of type `T`, deserialized from a single input.
+///
+/// Created via `Deserializer::into_iter()`. Errors get reported with their
+/// position in the input, after which the iterator yields no further values.
+pub struct StreamDeserializer<'de, R, T> {
+    de: Deserializer<R>,
+    failed: bool,
+    marker: PhantomData<(&'de (), T)>,
+}
+
+impl<'de, R, T> StreamDeserializer<'de, R, T>
+where
+    R: Read<'de>,
+    T: Deserialize<'de>,
+{
+    /// Returns the number of bytes deserialized so far,
+    /// i.e. the position at which the next value starts.
+    pub fn byte_offset(&self) -> usize {
+        self.de.decoder.pos()
+    }
+}
+
+impl<'de, R, T> Iterator for StreamDeserializer<'de, R, T>
+where
+    R: Read<'de>,
+    T: Deserialize<'de>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        // Peeking the next value's kind only fails for I/O errors, or at the end:
+        let result = match self.de.decoder.peek_value_kind() {
+            Ok(_) => T::deserialize(&mut self.de),
+            Err(err) if err.code() == ErrorCode::UnexpectedEndOfFile => return None,
+            Err(err) => Err(err),
+        };
+
+        self.failed = result.is_err();
+
+        Some(result)
+    }
 }
 
 #[cfg(not(feature = "unbounded_depth"))]
@@ -96,7 +377,7 @@ macro_rules! check_depth {
         if_checking_depth_limit! {
             this: $this;
 
-            $this.remaining_depth -= 1;
+            $this.remaining_depth = $this.remaining_depth.saturating_sub(1);
             if $this.remaining_depth == 0 {
                 return Err(Error::depth_limit_exceeded(Some($this.decoder.pos())));
             }
@@ -126,6 +407,9 @@ where
         match self.decoder.peek_marker()? {
             Marker::Int => self.deserialize_int(visitor),
             Marker::String => self.deserialize_str(visitor),
+            Marker::Seq if self.decoder.peek_extension()? => {
+                Value::Ext(self.decoder.decode_ext_value()?).deserialize_any(visitor)
+            }
             Marker::Seq => self.deserialize_seq(visitor),
             Marker::Map => self.deserialize_map(visitor),
             Marker::Float => self.deserialize_float(visitor),
@@ -149,7 +433,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i8(self.decoder.decode_i8()?)
+        let value = self.decoder.decode_i8()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_i8(value)
     }
 
     #[inline]
@@ -157,7 +443,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i16(self.decoder.decode_i16()?)
+        let value = self.decoder.decode_i16()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_i16(value)
     }
 
     #[inline]
@@ -165,7 +453,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i32(self.decoder.decode_i32()?)
+        let value = self.decoder.decode_i32()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_i32(value)
     }
 
     #[inline]
@@ -173,7 +463,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i64(self.decoder.decode_i64()?)
+        let value = self.decoder.decode_i64()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_i64(value)
     }
 
     #[inline]
@@ -181,7 +473,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_i128(self.decoder.decode_i64()? as i128)
+        let value = self.decoder.decode_i128()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_i128(value)
     }
 
     #[inline]
@@ -189,7 +483,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u8(self.decoder.decode_u8()?)
+        let value = self.decoder.decode_u8()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_u8(value)
     }
 
     #[inline]
@@ -197,7 +493,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u16(self.decoder.decode_u16()?)
+        let value = self.decoder.decode_u16()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_u16(value)
     }
 
     #[inline]
@@ -205,7 +503,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u32(self.decoder.decode_u32()?)
+        let value = self.decoder.decode_u32()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_u32(value)
     }
 
     #[inline]
@@ -213,15 +513,19 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u64(self.decoder.decode_u64()?)
+        let value = self.decoder.decode_u64()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_u64(value)
     }
 
     #[inline]
-    fn deserialize_u128<V>(self, visitor: V) -> std::result::Result<V::Value, Self::Error>
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_u128(self.decoder.decode_u64()? as u128)
+        let value = self.decoder.decode_u128()?;
+        capture_key(&mut self.path, &value);
+        visitor.visit_u128(value)
     }
 
     #[inline]
@@ -253,9 +557,24 @@ where
     where
         V: de::Visitor<'de>,
     {
-        match self.decoder.decode_str(&mut self.scratch)? {
-            Reference::Borrowed(str) => visitor.visit_borrowed_str(str),
-            Reference::Copied(str) => visitor.visit_str(str),
+        if self.decoder.peek_dictionary_ref()? {
+            let str = self.decoder.decode_str(&mut self.scratch)?;
+            capture_key(&mut self.path, &*str);
+            return visitor.visit_str(&str);
+        }
+
+        let header = self.decoder.decode_string_header()?;
+        self.check_len(header.len())?;
+
+        match self.decoder.decode_str_of(header, &mut self.scratch)? {
+            Reference::Borrowed(str) => {
+                capture_key(&mut self.path, str);
+                visitor.visit_borrowed_str(str)
+            }
+            Reference::Copied(str) => {
+                capture_key(&mut self.path, str);
+                visitor.visit_str(str)
+            }
         }
     }
 
@@ -264,7 +583,17 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_string(self.decoder.decode_string()?)
+        let string = if self.decoder.peek_dictionary_ref()? {
+            self.decoder.decode_string()?
+        } else {
+            let header = self.decoder.decode_string_header()?;
+            self.check_len(header.len())?;
+
+            self.decoder.decode_string_of(header)?
+        };
+
+        capture_key(&mut self.path, &string);
+        visitor.visit_string(string)
     }
 
     #[inline]
@@ -274,13 +603,18 @@ where
     {
         if self.decoder.peek_marker()? == Marker::Seq {
             let header = self.decoder.decode_seq_header()?;
+            self.check_len(header.len())?;
+
             let mut bytes: Vec<u8> = Vec::new();
             for _ in 0..header.len() {
                 bytes.push(self.decoder.decode_u8()?);
             }
             visitor.visit_bytes(&bytes)
         } else {
-            match self.decoder.decode_bytes(&mut self.scratch)? {
+            let header = self.decoder.decode_bytes_header()?;
+            self.check_len(header.len())?;
+
+            match self.decoder.decode_bytes_of(header, &mut self.scratch)? {
                 Reference::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
                 Reference::Copied(bytes) => visitor.visit_bytes(bytes),
             }
@@ -292,7 +626,10 @@ where
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_byte_buf(self.decoder.decode_bytes_buf()?)
+        let header = self.decoder.decode_bytes_header()?;
+        self.check_len(header.len())?;
+
+        visitor.visit_byte_buf(self.decoder.decode_bytes_buf_of(header)?)
     }
 
     #[inline]
@@ -314,7 +651,11 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.decoder.decode_unit()?;
+        // Tolerates nulls, as serialized for `UnitRepr::Null`:
+        match self.decoder.peek_marker()? == Marker::Null {
+            true => self.decoder.decode_null()?,
+            false => self.decoder.decode_unit()?,
+        }
         visitor.visit_unit()
     }
 
@@ -327,10 +668,35 @@ where
     }
 
     #[inline]
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
+        #[cfg(feature = "std")]
+        if crate::timestamp::is_extension(name) && self.decoder.peek_extension()? {
+            return crate::timestamp::decode_extension(&mut self.decoder, name, visitor);
+        }
+
+        #[cfg(feature = "uuid")]
+        if name == crate::uuid::UUID_NAME && self.decoder.peek_extension()? {
+            return visitor.visit_bytes(self.decoder.decode_uuid()?.as_bytes());
+        }
+
+        #[cfg(feature = "bigint")]
+        if name == crate::big_int::BIG_INT_NAME && self.decoder.peek_extension()? {
+            return visitor.visit_str(&self.decoder.decode_big_int()?.to_string());
+        }
+
+        #[cfg(feature = "decimal")]
+        if name == crate::decimal::DECIMAL_NAME && self.decoder.peek_extension()? {
+            return visitor.visit_str(&self.decoder.decode_decimal()?.to_string());
+        }
+
+        if name == ExtValue::NEWTYPE_NAME && self.decoder.peek_extension()? {
+            return Value::Ext(self.decoder.decode_ext_value()?)
+                .deserialize_newtype_struct(name, visitor);
+        }
+
         visitor.visit_newtype_struct(self)
     }
 
@@ -339,11 +705,22 @@ where
     where
         V: de::Visitor<'de>,
     {
-        let header = self.decoder.decode_seq_header()?;
+        if self.decoder.peek_columnar_seq()? {
+            return self.deserialize_columnar_seq(visitor);
+        }
+
+        if self.decoder.peek_tabular_seq()? {
+            return self.deserialize_tabular_seq(visitor);
+        }
+
+        let len = self.decoder.decode_seq_start()?;
+        if let Some(len) = len {
+            self.check_elements(len)?;
+        }
 
         check_depth! {
             this: self;
-            let value = visitor.visit_seq(SeqAccess::new(self, header.len()))?;
+            let value = visitor.visit_seq(SeqAccess::new(self, len))?;
         }
 
         Ok(value)
@@ -375,7 +752,12 @@ where
     where
         V: de::Visitor<'de>,
     {
+        if self.decoder.peek_front_coded_map()? {
+            return self.deserialize_front_coded_map(visitor);
+        }
+
         let header = self.decoder.decode_map_header()?;
+        self.check_elements(header.len())?;
 
         check_depth! {
             this: self;
@@ -388,14 +770,41 @@ where
     #[inline]
     fn deserialize_struct<V>(
         self,
-        _name: &'static str,
-        _fields: &'static [&'static str],
+        name: &'static str,
+        fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_map(visitor)
+        if name == crate::spanned::SPANNED_NAME && fields == crate::spanned::SPANNED_FIELDS {
+            return visitor.visit_map(SpannedAccess::new(self));
+        }
+
+        // Structs serialized as `StructRepr::Seq` are decoded positionally:
+        if self.decoder.peek_marker()? == Marker::Seq {
+            return self.deserialize_seq(visitor);
+        }
+
+        if self.config.unknown_fields == UnknownFieldsPolicy::Ignore {
+            return self.deserialize_map(visitor);
+        }
+
+        let (len, front_coded) = if self.decoder.peek_front_coded_map()? {
+            let keys = self.decoder.decode_front_coded_map_start()?;
+            (keys.len(), Some(keys))
+        } else {
+            (self.decoder.decode_map_header()?.len(), None)
+        };
+        self.check_elements(len)?;
+
+        check_depth! {
+            this: self;
+            let access = StructAccess::new(self, name, fields, len, front_coded);
+            let value = visitor.visit_map(access)?;
+        }
+
+        Ok(value)
     }
 
     #[inline]
@@ -410,12 +819,29 @@ where
     {
         match self.decoder.peek_marker()? {
             Marker::Int => {
-                let index = self.decoder.decode_u32()? as usize;
-                visitor.visit_enum(variants[index].into_deserializer())
+                let pos = self.decoder.pos();
+                let index = self.decoder.decode_u32()?;
+
+                let variant = variants.get(index as usize).ok_or_else(|| {
+                    Error::invalid_value(
+                        format!("variant index {index}"),
+                        format!("variant index 0 <= i < {}", variants.len()),
+                        Some(pos),
+                    )
+                })?;
+
+                visitor.visit_enum(variant.into_deserializer())
+            }
+            Marker::String if self.decoder.peek_dictionary_ref()? => {
+                let str_ref = self.decoder.decode_str(&mut self.scratch)?;
+                visitor.visit_enum(str_ref.into_deserializer())
             }
             Marker::String => {
+                let header = self.decoder.decode_string_header()?;
+                self.check_len(header.len())?;
+
                 let mut scratch = vec![];
-                let str_ref = self.decoder.decode_str(&mut scratch)?;
+                let str_ref = self.decoder.decode_str_of(header, &mut scratch)?;
                 visitor.visit_enum(str_ref.into_deserializer())
             }
             Marker::Map => {
@@ -449,7 +875,15 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_str(visitor)
+        match self.decoder.peek_marker()? {
+            // Fields serialized as `StructRepr::IndexMap` are keyed by their index:
+            Marker::Int => {
+                let index = self.decoder.decode_u64()?;
+                capture_key(&mut self.path, &index);
+                visitor.visit_u64(index)
+            }
+            _ => self.deserialize_str(visitor),
+        }
     }
 
     #[inline]
@@ -457,7 +891,9 @@ where
     where
         V: de::Visitor<'de>,
     {
-        self.deserialize_any(visitor)
+        // Skipping avoids materializing values that get thrown away:
+        self.skip_checked_value()?;
+        visitor.visit_unit()
     }
 }
 
@@ -470,106 +906,1016 @@ where
         self.decoder.pos()
     }
 
-    #[inline]
-    fn deserialize_float<V>(&mut self, visitor: V) -> Result<V::Value>
+    /// Deserializes a map with front-coded keys, which needs to be
+    /// decoded as a whole, as its keys depend on each other.
+    ///
+    /// Unknown fields of structs encoded as such maps are always ignored.
+    fn deserialize_front_coded_map<V>(&mut self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        match self.decoder.decode_float_value()? {
-            FloatValue::F32(value) => visitor.visit_f32(value),
-            FloatValue::F64(value) => visitor.visit_f64(value),
+        let keys = self.decoder.decode_front_coded_map_start()?;
+        self.check_elements(keys.len())?;
+
+        check_depth! {
+            this: self;
+            let value = visitor.visit_map(MapAccess::front_coded(self, keys))?;
         }
+
+        Ok(value)
     }
 
-    #[inline]
-    fn deserialize_int<V>(&mut self, visitor: V) -> Result<V::Value>
+    /// Deserializes a columnar sequence of maps.
+    ///
+    /// As the maps' values are stored column by column, the columns get decoded
+    /// up front, with each row then getting deserialized from its keys and
+    /// its values of the columns, like any other map (or struct).
+    fn deserialize_columnar_seq<V>(&mut self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        match self.decoder.decode_int_value()? {
-            IntValue::Signed(value) => match value {
-                SignedIntValue::I8(value) => visitor.visit_i8(value),
-                SignedIntValue::I16(value) => visitor.visit_i16(value),
-                SignedIntValue::I32(value) => visitor.visit_i32(value),
-                SignedIntValue::I64(value) => visitor.visit_i64(value),
-            },
-            IntValue::Unsigned(value) => match value {
-                UnsignedIntValue::U8(value) => visitor.visit_u8(value),
-                UnsignedIntValue::U16(value) => visitor.visit_u16(value),
-                UnsignedIntValue::U32(value) => visitor.visit_u32(value),
-                UnsignedIntValue::U64(value) => visitor.visit_u64(value),
-            },
+        let pos = self.pos();
+
+        let columnar = self.decoder.decode_columnar_seq_start()?;
+        self.check_elements(columnar.len())?;
+        self.check_elements(columnar.keys_len())?;
+
+        check_depth! {
+            this: self;
+            let (keys, columns) = self.decode_columnar_columns(&columnar)?;
         }
-    }
-}
 
-struct SeqAccess<'a, R> {
-    de: &'a mut Deserializer<R>,
-    remaining: usize,
-}
+        self.decoder.decode_columnar_seq_end(columnar)?;
 
-impl<'a, R: 'a> SeqAccess<'a, R> {
-    #[inline]
-    fn new(de: &'a mut Deserializer<R>, count: usize) -> Self {
-        SeqAccess {
-            de,
-            remaining: count,
+        check_depth! {
+            this: self;
+            let access = ColumnarSeqAccess::new(self, &keys, columns, pos);
+            let value = visitor.visit_seq(access)?;
         }
-    }
-}
 
-impl<'de, 'a, R> de::SeqAccess<'de> for SeqAccess<'a, R>
-where
-    R: Read<'de> + 'a,
-{
-    type Error = Error;
+        Ok(value)
+    }
 
-    #[inline]
-    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    /// Deserializes a tabular sequence of maps.
+    ///
+    /// As the maps' values are stored row by row, only the keys get decoded
+    /// up front, with each row's values then getting deserialized from the input,
+    /// like those of any other map (or struct).
+    fn deserialize_tabular_seq<V>(&mut self, visitor: V) -> Result<V::Value>
     where
-        T: de::DeserializeSeed<'de>,
+        V: de::Visitor<'de>,
     {
-        if self.remaining == 0 {
-            return Ok(None);
+        let tabular = self.decoder.decode_tabular_seq_start()?;
+        self.check_elements(tabular.len())?;
+        self.check_elements(tabular.keys_len())?;
+
+        check_depth! {
+            this: self;
+
+            let mut keys = Vec::new();
+            for _ in 0..tabular.keys_len() {
+                keys.push(Value::deserialize(&mut *self)?);
+            }
+
+            tabular.check_keys(&keys)?;
+
+            let access = TabularSeqAccess::new(self, &keys, tabular.len());
+            let value = visitor.visit_seq(access)?;
         }
 
-        self.remaining -= 1;
+        self.decoder.decode_tabular_seq_end(tabular)?;
 
-        Ok(Some(seed.deserialize(&mut *self.de)?))
+        Ok(value)
     }
-}
 
-struct MapAccess<'a, R: 'a> {
-    de: &'a mut Deserializer<R>,
-    remaining: usize,
-}
+    /// Decodes the keys and columns of a columnar sequence of maps.
+    fn decode_columnar_columns(
+        &mut self,
+        columnar: &ColumnarSeq,
+    ) -> Result<(Vec<Value>, Vec<alloc::vec::IntoIter<Value>>)> {
+        let mut keys = Vec::new();
+        for _ in 0..columnar.keys_len() {
+            keys.push(Value::deserialize(&mut *self)?);
+        }
 
-impl<'a, R: 'a> MapAccess<'a, R> {
-    #[inline]
-    fn new(de: &'a mut Deserializer<R>, count: usize) -> Self {
-        MapAccess {
-            de,
-            remaining: count,
+        columnar.check_keys(&keys)?;
+
+        let mut columns = Vec::with_capacity(keys.len());
+        for _ in &keys {
+            self.decoder.decode_columnar_seq_column(columnar)?;
+
+            let mut column = Vec::new();
+            for _ in 0..columnar.len() {
+                column.push(Value::deserialize(&mut *self)?);
+            }
+
+            columns.push(column.into_iter());
         }
-    }
-}
 
-impl<'de, 'a, R> de::MapAccess<'de> for MapAccess<'a, R>
-where
-    R: Read<'de> + 'a,
-{
-    type Error = Error;
+        Ok((keys, columns))
+    }
 
+    /// Deserializes `seed` at the path `segment` (if tracked),
+    /// attaching the path to any error occurring within.
     #[inline]
-    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    fn deserialize_at<T, S>(&mut self, segment: S, seed: T) -> Result<T::Value>
     where
-        K: de::DeserializeSeed<'de>,
+        T: de::DeserializeSeed<'de>,
+        S: FnOnce(&mut PathTracker) -> PathSegment,
     {
-        if self.remaining == 0 {
-            return Ok(None);
-        }
-
-        seed.deserialize(&mut *self.de).map(Some)
+        self.at_path(segment, |de| seed.deserialize(de))
+    }
+
+    /// Runs `f` at the path `segment` (if tracked),
+    /// attaching the path to any error occurring within.
+    #[inline]
+    fn at_path<T, S, F>(&mut self, segment: S, f: F) -> Result<T>
+    where
+        S: FnOnce(&mut PathTracker) -> PathSegment,
+        F: FnOnce(&mut Self) -> Result<T>,
+    {
+        let Some(path) = &mut self.path else {
+            return f(self);
+        };
+
+        let segment = segment(path);
+        path.segments.push(segment);
+
+        let result = f(&mut *self);
+
+        let path = self.path.as_mut().expect("path should be tracked");
+        let result = result.map_err(|err| path.attach_to(err));
+        path.segments.pop();
+
+        result
+    }
+
+    /// Decodes the next value as a `Value`, subject to the configured limits
+    /// (unlike `Decoder::decode_value()`), e.g. for collecting unknown fields.
+    fn decode_checked_value(&mut self) -> Result<Value> {
+        // Values with reserved headers are opaque, so can't be deserialized:
+        if self.decoder.peek_value_kind()? == ValueKind::Unknown {
+            return self.decoder.decode_value();
+        }
+
+        Value::deserialize(self)
+    }
+
+    /// Skips the next value, subject to the configured limits
+    /// (unlike `Decoder::skip_value()`), e.g. for ignoring unknown fields.
+    fn skip_checked_value(&mut self) -> Result<()> {
+        match self.decoder.peek_value_kind()? {
+            // Nested values get walked, to check their depth and element counts:
+            ValueKind::Seq | ValueKind::Map => {
+                de::Deserializer::deserialize_any(self, de::IgnoredAny).map(drop)
+            }
+            ValueKind::String if !self.decoder.peek_dictionary_ref()? => {
+                let header = self.decoder.decode_string_header()?;
+                self.check_len(header.len())?;
+                self.decoder.skip_value_of(header.into())
+            }
+            ValueKind::Bytes => {
+                let header = self.decoder.decode_bytes_header()?;
+                self.check_len(header.len())?;
+                self.decoder.skip_value_of(header.into())
+            }
+            ValueKind::Ext => {
+                let header = self.decoder.decode_ext_header()?;
+                self.check_len(header.len())?;
+                self.decoder.skip_value_of(header.into())
+            }
+            // Any other values are either of bounded size, or opaque:
+            _ => self.decoder.skip_value(),
+        }
+    }
+
+    /// Checks a string's (or byte array's) length against the configured limits,
+    /// before any of its bytes get read.
+    #[inline]
+    fn check_len(&self, len: usize) -> Result<()> {
+        self.check_limit(len, self.config.max_len)
+    }
+
+    /// Checks a sequence's (or map's) element count against the configured limits,
+    /// before any of its elements get decoded.
+    #[inline]
+    fn check_elements(&self, count: usize) -> Result<()> {
+        self.check_limit(count, self.config.max_elements)
+    }
+
+    fn check_limit(&self, len: usize, max_len: Option<usize>) -> Result<()> {
+        let pos = self.pos();
+
+        if let Some(max_len) = max_len {
+            if len > max_len {
+                return Err(Error::limit_exceeded(len, max_len, Some(pos)));
+            }
+        }
+
+        if let Some(max_size) = self.config.max_size {
+            // Every byte (or element) takes up at least one byte,
+            // so a length provides a lower bound for the remaining size:
+            let size = pos.saturating_add(len);
+            if size > max_size {
+                return Err(Error::limit_exceeded(size, max_size, Some(pos)));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize_float<V>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.decoder.decode_float_value()? {
+            FloatValue::F32(value) => visitor.visit_f32(value),
+            FloatValue::F64(value) => visitor.visit_f64(value),
+        }
+    }
+
+    #[inline]
+    fn deserialize_int<V>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let value = self.decoder.decode_int_value()?;
+        capture_key(&mut self.path, &value);
+
+        match value {
+            IntValue::Signed(value) => match value {
+                SignedIntValue::I8(value) => visitor.visit_i8(value),
+                SignedIntValue::I16(value) => visitor.visit_i16(value),
+                SignedIntValue::I32(value) => visitor.visit_i32(value),
+                SignedIntValue::I64(value) => visitor.visit_i64(value),
+                SignedIntValue::I128(value) => visitor.visit_i128(value),
+            },
+            IntValue::Unsigned(value) => match value {
+                UnsignedIntValue::U8(value) => visitor.visit_u8(value),
+                UnsignedIntValue::U16(value) => visitor.visit_u16(value),
+                UnsignedIntValue::U32(value) => visitor.visit_u32(value),
+                UnsignedIntValue::U64(value) => visitor.visit_u64(value),
+                UnsignedIntValue::U128(value) => visitor.visit_u128(value),
+            },
+        }
+    }
+}
+
+struct SeqAccess<'a, R> {
+    de: &'a mut Deserializer<R>,
+    /// The number of remaining elements, or `None` for streamed sequences.
+    remaining: Option<usize>,
+    /// The number of elements of a streamed sequence decoded so far.
+    streamed: usize,
+    /// The index of the next element.
+    index: usize,
+}
+
+impl<'a, R: 'a> SeqAccess<'a, R> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R>, count: Option<usize>) -> Self {
+        SeqAccess {
+            de,
+            remaining: count,
+            streamed: 0,
+            index: 0,
+        }
+    }
+}
+
+impl<'de, 'a, R> de::SeqAccess<'de> for SeqAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    #[inline]
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match &mut self.remaining {
+            Some(0) => return Ok(None),
+            Some(remaining) => *remaining -= 1,
+            None => {
+                if self.de.decoder.decode_seq_stream_end()? {
+                    return Ok(None);
+                }
+
+                // Streamed sequences don't announce their count upfront:
+                self.streamed += 1;
+                if let Some(max_elements) = self.de.config.max_elements {
+                    if self.streamed > max_elements {
+                        let pos = self.de.pos();
+                        return Err(Error::limit_exceeded(
+                            self.streamed,
+                            max_elements,
+                            Some(pos),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        let value = self
+            .de
+            .deserialize_at(|_| PathSegment::Index(index), seed)?;
+
+        Ok(Some(value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        self.remaining
+    }
+}
+
+struct MapAccess<'a, R: 'a> {
+    de: &'a mut Deserializer<R>,
+    remaining: usize,
+    /// The keys seen so far, if checking for duplicate keys.
+    seen_keys: Option<BTreeSet<Value>>,
+    /// The map's keys, if front-coded.
+    front_coded: Option<FrontCodedKeys>,
+}
+
+impl<'a, R: 'a> MapAccess<'a, R> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R>, count: usize) -> Self {
+        let seen_keys = match de.config.decoder.duplicate_keys {
+            DuplicateKeyPolicy::LastWins => None,
+            DuplicateKeyPolicy::FirstWins | DuplicateKeyPolicy::Reject => Some(BTreeSet::new()),
+        };
+
+        MapAccess {
+            de,
+            remaining: count,
+            seen_keys,
+            front_coded: None,
+        }
+    }
+
+    #[inline]
+    fn front_coded(de: &'a mut Deserializer<R>, keys: FrontCodedKeys) -> Self {
+        let mut access = Self::new(de, keys.len());
+        access.front_coded = Some(keys);
+        access
+    }
+}
+
+impl<'de, 'a, R> MapAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    /// Deserializes the next key not seen before, handling duplicate keys
+    /// according to `DecoderConfig::duplicate_keys`.
+    ///
+    /// Keys get decoded as `Value`s for comparison, so can't be borrowed from the input,
+    /// subject to the same limits as any other value.
+    fn next_distinct_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let seen_keys = self.seen_keys.as_mut().expect("keys should be tracked");
+
+        while self.remaining > 0 {
+            let pos = self.de.decoder.pos();
+            let key = match &mut self.front_coded {
+                Some(keys) => {
+                    let key = self.de.decoder.decode_front_coded_map_key(keys)?;
+                    self.de.check_len(key.len())?;
+                    Value::String(key.to_owned().into())
+                }
+                None => Value::deserialize(&mut *self.de)?,
+            };
+
+            if !seen_keys.contains(&key) {
+                if let Some(path) = &mut self.de.path {
+                    path.key = path_key(&key);
+                }
+
+                seen_keys.insert(key.clone());
+
+                return seed
+                    .deserialize(key)
+                    .map(Some)
+                    .map_err(|err| err.or_pos(pos));
+            }
+
+            if self.de.config.decoder.duplicate_keys == DuplicateKeyPolicy::Reject {
+                return Err(Error::duplicate_key(&key, Some(pos)));
+            }
+
+            // The first of the duplicate keys' values wins:
+            self.remaining -= 1;
+            self.de.skip_checked_value()?;
+        }
+
+        self.de.end_front_coded(&mut self.front_coded)?;
+
+        Ok(None)
+    }
+}
+
+impl<'de, R> Deserializer<R>
+where
+    R: Read<'de>,
+{
+    /// Checks the end of a map with front-coded `keys` (if any),
+    /// once all of its entries have been deserialized.
+    #[inline]
+    fn end_front_coded(&mut self, keys: &mut Option<FrontCodedKeys>) -> Result<()> {
+        match keys.take() {
+            Some(keys) => self.decoder.decode_front_coded_map_end(keys),
+            None => Ok(()),
+        }
+    }
+
+    /// Decodes the next key of a map with front-coded `keys`,
+    /// recording it in the path (if tracked).
+    fn decode_front_coded_key<'k>(&mut self, keys: &'k mut FrontCodedKeys) -> Result<&'k str> {
+        let key = self.decoder.decode_front_coded_map_key(keys)?;
+        self.check_len(key.len())?;
+
+        if let Some(path) = &mut self.path {
+            path.key = Some(key.to_owned());
+        }
+
+        Ok(key)
+    }
+}
+
+impl<'de, 'a, R> de::MapAccess<'de> for MapAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    #[inline]
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        if self.seen_keys.is_some() {
+            return self.next_distinct_key_seed(seed);
+        }
+
+        if self.remaining == 0 {
+            self.de.end_front_coded(&mut self.front_coded)?;
+            return Ok(None);
+        }
+
+        // Errors raised by the key's visitor (e.g. for unknown fields) lack a position:
+        let pos = self.de.pos();
+
+        if let Some(keys) = &mut self.front_coded {
+            let key = self.de.decode_front_coded_key(keys)?;
+            return seed
+                .deserialize(StrDeserializer::<Error>::new(key))
+                .map(Some)
+                .map_err(|err| err.or_pos(pos));
+        }
+
+        let Some(path) = &mut self.de.path else {
+            return seed
+                .deserialize(&mut *self.de)
+                .map(Some)
+                .map_err(|err| err.or_pos(pos));
+        };
+
+        // Keys get recorded by the deserializer, as they get decoded:
+        path.capturing = true;
+        path.key = None;
+
+        let key = seed.deserialize(&mut *self.de);
+
+        if let Some(path) = &mut self.de.path {
+            path.capturing = false;
+        }
+
+        key.map(Some).map_err(|err| err.or_pos(pos))
+    }
+
+    #[inline]
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.remaining -= 1;
+
+        self.de
+            .deserialize_at(|path| PathSegment::Key(path.key.take()), seed)
+    }
+}
+
+/// Access to a value and its span, as requested by `Spanned`.
+struct SpannedAccess<'a, R: 'a> {
+    de: &'a mut Deserializer<R>,
+    start: usize,
+    end: usize,
+    field: usize,
+}
+
+impl<'a, R: 'a> SpannedAccess<'a, R> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R>) -> Self {
+        let start = de.decoder.pos();
+
+        SpannedAccess {
+            de,
+            start,
+            end: start,
+            field: 0,
+        }
+    }
+}
+
+impl<'de, 'a, R> de::MapAccess<'de> for SpannedAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        // The value comes first, for its end to be known once requested:
+        let Some(field) = crate::spanned::SPANNED_FIELDS.get(self.field) else {
+            return Ok(None);
+        };
+
+        seed.deserialize(StrDeserializer::<Error>::new(field))
+            .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.field += 1;
+
+        match self.field {
+            1 => {
+                let value = seed.deserialize(&mut *self.de)?;
+                self.end = self.de.decoder.pos();
+                Ok(value)
+            }
+            2 => seed.deserialize(U64Deserializer::<Error>::new(self.start as u64)),
+            _ => seed.deserialize(U64Deserializer::<Error>::new(self.end as u64)),
+        }
+    }
+}
+
+struct StructAccess<'a, R: 'a> {
+    de: &'a mut Deserializer<R>,
+    name: &'static str,
+    fields: &'static [&'static str],
+    remaining: usize,
+    // The index of the field expected next, as fields tend to be encoded in order:
+    next_field: usize,
+    /// The struct's keys, if front-coded.
+    front_coded: Option<FrontCodedKeys>,
+}
+
+impl<'a, R: 'a> StructAccess<'a, R> {
+    #[inline]
+    fn new(
+        de: &'a mut Deserializer<R>,
+        name: &'static str,
+        fields: &'static [&'static str],
+        count: usize,
+        front_coded: Option<FrontCodedKeys>,
+    ) -> Self {
+        StructAccess {
+            de,
+            name,
+            fields,
+            remaining: count,
+            next_field: 0,
+            front_coded,
+        }
+    }
+
+    /// Returns the index of the field named `key`, if any,
+    /// checking the field expected next first.
+    fn field_named(fields: &'static [&'static str], next_field: usize, key: &str) -> Option<usize> {
+        if fields.get(next_field) == Some(&key) {
+            return Some(next_field);
+        }
+
+        fields.iter().position(|field| *field == key)
+    }
+
+    /// Deserializes the name of the field at `index` using `seed`.
+    fn visit_field<'de, K>(&mut self, index: usize, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        let field = self.fields[index];
+        self.next_field = index + 1;
+
+        if let Some(path) = &mut self.de.path {
+            path.key = Some(field.to_owned());
+        }
+
+        let deserializer = StrDeserializer::<Error>::new(field);
+        seed.deserialize(deserializer).map(Some)
+    }
+
+    /// Handles the entry of the unknown field `key` (decoded at `pos`),
+    /// according to `DeserializerConfig::unknown_fields`.
+    fn unknown_field<'de>(&mut self, key: Value, pos: usize) -> Result<()>
+    where
+        R: Read<'de>,
+    {
+        self.remaining -= 1;
+
+        match self.de.config.unknown_fields {
+            UnknownFieldsPolicy::Ignore => self.de.skip_checked_value(),
+            UnknownFieldsPolicy::Error => Err(unknown_field_error(&key, self.fields, pos)),
+            UnknownFieldsPolicy::Collect => {
+                let value = self.de.decode_checked_value()?;
+                self.de.collect_unknown_field(self.name, key, value);
+                Ok(())
+            }
+        }
+    }
+
+    fn field_index(fields: &'static [&'static str], key: &IntValue) -> Option<u64> {
+        let index = match key.to_unsigned().ok()? {
+            UnsignedIntValue::U8(index) => index.into(),
+            UnsignedIntValue::U16(index) => index.into(),
+            UnsignedIntValue::U32(index) => index.into(),
+            UnsignedIntValue::U64(index) => index,
+            UnsignedIntValue::U128(index) => u64::try_from(index).ok()?,
+        };
+
+        (index < fields.len() as u64).then_some(index)
+    }
+}
+
+impl<'de, 'a, R> de::MapAccess<'de> for StructAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        while self.remaining > 0 {
+            let pos = self.de.pos();
+
+            if let Some(keys) = &mut self.front_coded {
+                let name = self.de.decode_front_coded_key(keys)?;
+
+                if let Some(index) = Self::field_named(self.fields, self.next_field, name) {
+                    return self.visit_field(index, seed);
+                }
+
+                let key = Value::String(name.to_owned().into());
+                self.unknown_field(key, pos)?;
+                continue;
+            }
+
+            // Known field names get matched without allocating,
+            // with only unknown ones decoded into a `Value`:
+            let key = match self.de.decoder.peek_value_kind()? {
+                ValueKind::String if !self.de.decoder.peek_dictionary_ref()? => {
+                    let de = &mut *self.de;
+
+                    let header = de.decoder.decode_string_header()?;
+                    de.check_len(header.len())?;
+                    let name = de.decoder.decode_str_of(header, &mut de.scratch)?;
+
+                    if let Some(index) = Self::field_named(self.fields, self.next_field, &name) {
+                        return self.visit_field(index, seed);
+                    }
+
+                    Value::String((*name).to_owned().into())
+                }
+                ValueKind::Int => {
+                    let key = self.de.decoder.decode_int_value()?;
+
+                    if let Some(index) = Self::field_index(self.fields, &key) {
+                        if let Some(path) = &mut self.de.path {
+                            path.key = Some(self.fields[index as usize].to_owned());
+                        }
+                        let deserializer = U64Deserializer::<Error>::new(index);
+                        return seed.deserialize(deserializer).map(Some);
+                    }
+
+                    Value::Int(key)
+                }
+                // References to dictionary strings may get collected as unknown values:
+                _ => {
+                    let key = self.de.decoder.decode_value()?;
+
+                    if let Value::String(name) = &key {
+                        if let Some(index) =
+                            Self::field_named(self.fields, self.next_field, &name.0)
+                        {
+                            return self.visit_field(index, seed);
+                        }
+                    }
+
+                    key
+                }
+            };
+
+            self.unknown_field(key, pos)?;
+        }
+
+        self.de.end_front_coded(&mut self.front_coded)?;
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.remaining -= 1;
+
+        self.de
+            .deserialize_at(|path| PathSegment::Key(path.key.take()), seed)
+    }
+}
+
+impl<R> Deserializer<R> {
+    /// Collects the `value` of the unknown field `key` of the struct `name`.
+    fn collect_unknown_field(&mut self, name: &'static str, key: Value, value: Value) {
+        let Value::Map(fields) = self
+            .unknown_fields
+            .entry(Value::String(name.to_owned().into()))
+            .or_insert_with(|| Value::Map(Default::default()))
+        else {
+            unreachable!("unknown fields are always collected into maps");
+        };
+        fields.0.insert(key, value);
+    }
+}
+
+/// Returns the error for the unknown field `key` (decoded at `pos`) of a struct with `fields`.
+fn unknown_field_error(key: &Value, fields: &'static [&'static str], pos: usize) -> Error {
+    let field = match key {
+        Value::String(name) => name.0.clone(),
+        other => format!("{other:?}"),
+    };
+    Error::unknown_field(&field, fields, Some(pos))
+}
+
+/// Access to the rows of a columnar sequence, from its decoded keys and columns.
+struct ColumnarSeqAccess<'a, R> {
+    de: &'a mut Deserializer<R>,
+    keys: &'a [Value],
+    columns: Vec<alloc::vec::IntoIter<Value>>,
+    remaining: usize,
+    /// The index of the next row.
+    index: usize,
+    /// The position of the sequence, as its rows' values lack one.
+    pos: usize,
+}
+
+impl<'a, R: 'a> ColumnarSeqAccess<'a, R> {
+    #[inline]
+    fn new(
+        de: &'a mut Deserializer<R>,
+        keys: &'a [Value],
+        columns: Vec<alloc::vec::IntoIter<Value>>,
+        pos: usize,
+    ) -> Self {
+        let remaining = columns.first().map_or(0, |column| column.len());
+
+        ColumnarSeqAccess {
+            de,
+            keys,
+            columns,
+            remaining,
+            index: 0,
+            pos,
+        }
+    }
+}
+
+impl<'de, 'a, R> de::SeqAccess<'de> for ColumnarSeqAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        let index = self.index;
+        self.index += 1;
+
+        let values: Vec<Value> = self
+            .columns
+            .iter_mut()
+            .map(|column| column.next().expect("columns should hold a value per row"))
+            .collect();
+
+        let (keys, pos) = (self.keys, self.pos);
+        let value = self.de.at_path(
+            |_| PathSegment::Index(index),
+            |de| seed.deserialize(RowDeserializer::new(de, keys, values, pos)),
+        )?;
+
+        Ok(Some(value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// A deserializer for a row of a columnar sequence, as a map of its keys to its values.
+struct RowDeserializer<'a, R> {
+    de: &'a mut Deserializer<R>,
+    keys: &'a [Value],
+    values: Vec<Value>,
+    pos: usize,
+}
+
+impl<'a, R: 'a> RowDeserializer<'a, R> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R>, keys: &'a [Value], values: Vec<Value>, pos: usize) -> Self {
+        RowDeserializer {
+            de,
+            keys,
+            values,
+            pos,
+        }
+    }
+}
+
+impl<'de, 'a, R> de::Deserializer<'de> for RowDeserializer<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(RowAccess::new(self, None))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.de.config.unknown_fields == UnknownFieldsPolicy::Ignore {
+            return self.deserialize_any(visitor);
+        }
+
+        visitor.visit_map(RowAccess::new(self, Some((name, fields))))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map enum
+        identifier ignored_any
+    }
+}
+
+/// Access to the entries of a row of a columnar sequence.
+///
+/// Rows deserialized as structs get their unknown fields handled
+/// according to `DeserializerConfig::unknown_fields`.
+struct RowAccess<'a, R> {
+    de: &'a mut Deserializer<R>,
+    keys: core::slice::Iter<'a, Value>,
+    values: alloc::vec::IntoIter<Value>,
+    /// The value of the entry whose key got deserialized last.
+    value: Option<Value>,
+    /// The name and fields of the struct being deserialized, if any.
+    fields: Option<(&'static str, &'static [&'static str])>,
+    // The index of the field expected next, as fields tend to be encoded in order:
+    next_field: usize,
+    pos: usize,
+}
+
+impl<'a, R: 'a> RowAccess<'a, R> {
+    #[inline]
+    fn new(
+        row: RowDeserializer<'a, R>,
+        fields: Option<(&'static str, &'static [&'static str])>,
+    ) -> Self {
+        RowAccess {
+            de: row.de,
+            keys: row.keys.iter(),
+            values: row.values.into_iter(),
+            value: None,
+            fields,
+            next_field: 0,
+            pos: row.pos,
+        }
+    }
+
+    /// Returns the index of the struct field for `key`, if any,
+    /// checking the field expected next first.
+    fn field_for(fields: &'static [&'static str], next_field: usize, key: &Value) -> Option<usize> {
+        match key {
+            Value::String(name) => StructAccess::<R>::field_named(fields, next_field, &name.0),
+            Value::Int(key) => {
+                StructAccess::<R>::field_index(fields, key).map(|index| index as usize)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'de, 'a, R> de::MapAccess<'de> for RowAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        for key in self.keys.by_ref() {
+            let value = self
+                .values
+                .next()
+                .expect("rows should hold a value per key");
+
+            let Some((name, fields)) = self.fields else {
+                self.value = Some(value);
+
+                if let Some(path) = &mut self.de.path {
+                    path.key = path_key(key);
+                }
+
+                // Keys get borrowed from the sequence's, rather than cloned for each row:
+                let result = match key {
+                    Value::String(key) => seed.deserialize(StrDeserializer::<Error>::new(&key.0)),
+                    key => seed.deserialize(key.clone()),
+                };
+                return result.map(Some).map_err(|err| err.or_pos(self.pos));
+            };
+
+            if let Some(index) = Self::field_for(fields, self.next_field, key) {
+                self.value = Some(value);
+                self.next_field = index + 1;
+
+                if let Some(path) = &mut self.de.path {
+                    path.key = Some(fields[index].to_owned());
+                }
+
+                let deserializer = StrDeserializer::<Error>::new(fields[index]);
+                return seed.deserialize(deserializer).map(Some);
+            }
+
+            match self.de.config.unknown_fields {
+                UnknownFieldsPolicy::Ignore => {}
+                UnknownFieldsPolicy::Error => {
+                    return Err(unknown_field_error(key, fields, self.pos));
+                }
+                UnknownFieldsPolicy::Collect => {
+                    self.de.collect_unknown_field(name, key.clone(), value);
+                }
+            }
+        }
+
+        Ok(None)
     }
 
     #[inline]
@@ -577,9 +1923,265 @@ where
     where
         V: de::DeserializeSeed<'de>,
     {
+        let value = self.value.take().expect("value should follow its key");
+        let pos = self.pos;
+
+        self.de.at_path(
+            |path| PathSegment::Key(path.key.take()),
+            |_| seed.deserialize(value).map_err(|err| err.or_pos(pos)),
+        )
+    }
+}
+
+/// Access to the rows of a tabular sequence, deserialized from the input one by one.
+struct TabularSeqAccess<'a, R> {
+    de: &'a mut Deserializer<R>,
+    keys: &'a [Value],
+    remaining: usize,
+    /// The index of the next row.
+    index: usize,
+}
+
+impl<'a, R: 'a> TabularSeqAccess<'a, R> {
+    #[inline]
+    fn new(de: &'a mut Deserializer<R>, keys: &'a [Value], count: usize) -> Self {
+        TabularSeqAccess {
+            de,
+            keys,
+            remaining: count,
+            index: 0,
+        }
+    }
+}
+
+impl<'de, 'a, R> de::SeqAccess<'de> for TabularSeqAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
         self.remaining -= 1;
 
-        seed.deserialize(&mut *self.de)
+        let index = self.index;
+        self.index += 1;
+
+        let keys = self.keys;
+        let value = self.de.at_path(
+            |_| PathSegment::Index(index),
+            |de| seed.deserialize(TabularRowDeserializer { de, keys }),
+        )?;
+
+        Ok(Some(value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// A deserializer for a row of a tabular sequence, as a map of its keys to its values.
+struct TabularRowDeserializer<'a, R> {
+    de: &'a mut Deserializer<R>,
+    keys: &'a [Value],
+}
+
+impl<'de, 'a, R> TabularRowDeserializer<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    /// Deserializes the row as a map (or as the struct of `fields`, if any) using `visitor`.
+    fn deserialize_row<V>(
+        self,
+        fields: Option<(&'static str, &'static [&'static str])>,
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let de = self.de;
+
+        check_depth! {
+            this: de;
+
+            let mut access = TabularRowAccess::new(de, self.keys, fields);
+            let value = visitor.visit_map(&mut access)?;
+            access.end()?;
+        }
+
+        Ok(value)
+    }
+}
+
+impl<'de, 'a, R> de::Deserializer<'de> for TabularRowDeserializer<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_row(None, visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.de.config.unknown_fields == UnknownFieldsPolicy::Ignore {
+            return self.deserialize_row(None, visitor);
+        }
+
+        self.deserialize_row(Some((name, fields)), visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map enum
+        identifier ignored_any
+    }
+}
+
+/// Access to the entries of a row of a tabular sequence.
+///
+/// Rows deserialized as structs get their unknown fields handled
+/// according to `DeserializerConfig::unknown_fields`.
+struct TabularRowAccess<'a, R> {
+    de: &'a mut Deserializer<R>,
+    keys: core::slice::Iter<'a, Value>,
+    /// Whether the value of the entry whose key got deserialized last is pending.
+    pending_value: bool,
+    /// The name and fields of the struct being deserialized, if any.
+    fields: Option<(&'static str, &'static [&'static str])>,
+    // The index of the field expected next, as fields tend to be encoded in order:
+    next_field: usize,
+}
+
+impl<'de, 'a, R> TabularRowAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    #[inline]
+    fn new(
+        de: &'a mut Deserializer<R>,
+        keys: &'a [Value],
+        fields: Option<(&'static str, &'static [&'static str])>,
+    ) -> Self {
+        TabularRowAccess {
+            de,
+            keys: keys.iter(),
+            pending_value: false,
+            fields,
+            next_field: 0,
+        }
+    }
+
+    /// Skips the values of any entries left unvisited, as rows have no end of their own.
+    fn end(&mut self) -> Result<()> {
+        if self.pending_value {
+            self.de.skip_checked_value()?;
+        }
+
+        for _ in self.keys.by_ref() {
+            self.de.skip_checked_value()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, 'a, R> de::MapAccess<'de> for TabularRowAccess<'a, R>
+where
+    R: Read<'de> + 'a,
+{
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        for key in self.keys.by_ref() {
+            let pos = self.de.pos();
+
+            let Some((name, fields)) = self.fields else {
+                self.pending_value = true;
+
+                if let Some(path) = &mut self.de.path {
+                    path.key = path_key(key);
+                }
+
+                // Keys get borrowed from the sequence's, rather than cloned for each row:
+                let result = match key {
+                    Value::String(key) => seed.deserialize(StrDeserializer::<Error>::new(&key.0)),
+                    key => seed.deserialize(key.clone()),
+                };
+                return result.map(Some).map_err(|err| err.or_pos(pos));
+            };
+
+            if let Some(index) = RowAccess::<R>::field_for(fields, self.next_field, key) {
+                self.pending_value = true;
+                self.next_field = index + 1;
+
+                if let Some(path) = &mut self.de.path {
+                    path.key = Some(fields[index].to_owned());
+                }
+
+                let deserializer = StrDeserializer::<Error>::new(fields[index]);
+                return seed.deserialize(deserializer).map(Some);
+            }
+
+            match self.de.config.unknown_fields {
+                UnknownFieldsPolicy::Ignore => self.de.skip_checked_value()?,
+                UnknownFieldsPolicy::Error => {
+                    return Err(unknown_field_error(key, fields, pos));
+                }
+                UnknownFieldsPolicy::Collect => {
+                    let value = self.de.decode_checked_value()?;
+                    self.de.collect_unknown_field(name, key.clone(), value);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.pending_value = false;
+
+        self.de
+            .deserialize_at(|path| PathSegment::Key(path.key.take()), seed)
     }
 }
 
@@ -624,6 +2226,10 @@ where
                 let index = u32::deserialize(&mut *self.de)?;
                 seed.deserialize(index.into_deserializer())?
             }
+            Marker::String if self.de.decoder.peek_dictionary_ref()? => {
+                let string = String::deserialize(&mut *self.de)?;
+                seed.deserialize(string.into_deserializer())?
+            }
             Marker::String => {
                 let str = <&str>::deserialize(&mut *self.de)?;
                 seed.deserialize(str.into_deserializer())?
@@ -673,6 +2279,97 @@ where
     where
         V: de::Visitor<'de>,
     {
+        if self.de.decoder.peek_marker()? == Marker::Seq {
+            return self.de.deserialize_seq(visitor);
+        }
+
         self.de.deserialize_map(visitor)
     }
 }
+
+/// Tracking of the path to the value being deserialized.
+#[derive(Default, Debug)]
+struct PathTracker {
+    segments: Vec<PathSegment>,
+    /// Whether a map key is being deserialized, to be recorded as `key`.
+    capturing: bool,
+    /// The key of the map entry being deserialized, if recorded.
+    key: Option<String>,
+}
+
+impl PathTracker {
+    /// Attaches the current path to `err`, unless it already has one
+    /// (from a more deeply nested value).
+    fn attach_to(&self, err: Error) -> Error {
+        if err.path().is_some() {
+            return err;
+        }
+
+        // Paths start at the root, as `.`, e.g. `.[3].name`:
+        let mut path = match self.segments.first() {
+            Some(PathSegment::Index(_)) => ".".to_owned(),
+            _ => String::new(),
+        };
+        for segment in &self.segments {
+            match segment {
+                PathSegment::Index(index) => path.push_str(&format!("[{index}]")),
+                PathSegment::Key(Some(key)) => path.push_str(&format!(".{key}")),
+                PathSegment::Key(None) => path.push_str(".?"),
+            }
+        }
+
+        err.with_path(path)
+    }
+}
+
+/// A segment of the path to the value being deserialized.
+#[derive(Debug)]
+enum PathSegment {
+    /// The index of a sequence's element.
+    Index(usize),
+    /// The key of a map's entry, if it's a string or an integer.
+    Key(Option<String>),
+}
+
+/// Returns `key` as recorded in paths, if it's a string or an integer.
+fn path_key(key: &Value) -> Option<String> {
+    match key {
+        Value::String(key) => Some(key.to_string()),
+        Value::Int(key) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
+/// Records `key` as the key of the map entry being deserialized, if capturing.
+#[inline]
+fn capture_key<K>(path: &mut Option<PathTracker>, key: &K)
+where
+    K: ?Sized + core::fmt::Display,
+{
+    if let Some(path) = path {
+        if path.capturing {
+            path.key = Some(key.to_string());
+            path.capturing = false;
+        }
+    }
+}
+
+/// Deserializes a `T` using `deserializer`, expecting a preamble
+/// and checking for trailing bytes, if configured.
+fn deserialize_from<'de, R, T>(mut deserializer: Deserializer<R>) -> Result<T>
+where
+    R: Read<'de>,
+    T: Deserialize<'de>,
+{
+    if deserializer.config.decoder.preamble {
+        deserializer.decoder.expect_preamble()?;
+    }
+
+    let value = T::deserialize(&mut deserializer)?;
+
+    if deserializer.config.ensure_eof {
+        deserializer.end()?;
+    }
+
+    Ok(value)
+}
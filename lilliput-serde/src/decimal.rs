@@ -0,0 +1,128 @@
+//! Serialization of `rust_decimal::Decimal` as decimal extension values.
+//!
+//! This module is meant for use with serde's `with` attribute:
+//!
+//! ```
+//! use lilliput_serde::{de::from_slice, ser::to_vec};
+//! use rust_decimal::Decimal;
+//!
+//! #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+//! struct Payment {
+//!     #[serde(with = "lilliput_serde::decimal")]
+//!     amount: Decimal,
+//! }
+//!
+//! let payment = Payment {
+//!     amount: Decimal::new(1999, 2),
+//! };
+//!
+//! let encoded = to_vec(&payment).unwrap();
+//! assert_eq!(from_slice::<Payment>(&encoded).unwrap(), payment);
+//! ```
+//!
+//! Lilliput encodes decimals as extension values wrapping their scale and
+//! mantissa, while other formats get them as decimal strings.
+//!
+//! Plain integers and decimal strings still get accepted when deserializing.
+
+use core::fmt;
+
+use rust_decimal::Decimal;
+use serde::{de, ser, Deserializer, Serialize, Serializer};
+
+use lilliput_core::{encoder::Encoder, io::Write};
+
+use crate::{config::SerializerConfig, error::Error, value::ValueSerializer};
+
+/// Name of the newtype struct wrapping the strings of decimals.
+pub(crate) const DECIMAL_NAME: &str = "$lilliput::Decimal";
+
+/// Serializes `decimal` as decimal.
+pub fn serialize<S>(decimal: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_newtype_struct(DECIMAL_NAME, &decimal.to_string())
+}
+
+/// Deserializes a decimal as `Decimal`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(DECIMAL_NAME, DecimalVisitor)
+}
+
+/// Encodes the `string` of the decimal newtype struct.
+pub(crate) fn encode_decimal<W, T>(encoder: &mut Encoder<W>, string: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let string = string.serialize(&ValueSerializer::new(SerializerConfig::default()))?;
+
+    let decimal: Decimal = string
+        .as_str()
+        .and_then(|string| string.parse().ok())
+        .ok_or_else(|| <Error as ser::Error>::custom("expected decimal string"))?;
+
+    encoder.encode_decimal(&decimal)
+}
+
+// MARK: - Private
+
+/// Visitor of decimals, as strings or integers.
+struct DecimalVisitor;
+
+impl<'de> de::Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("decimal")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        decimal_from_int(value.into())
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        decimal_from_int(value.into())
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        decimal_from_int(value)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+fn decimal_from_int<E>(value: i128) -> Result<Decimal, E>
+where
+    E: de::Error,
+{
+    Decimal::try_from_i128_with_scale(value, 0)
+        .map_err(|_| E::custom(format!("decimal out of range: {value}")))
+}
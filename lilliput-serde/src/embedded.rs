@@ -0,0 +1,138 @@
+//! Embedding of lilliput documents within other documents.
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    config::SerializerConfig,
+    de::from_slice,
+    error::Error,
+    ser::{to_vec, to_vec_with_config},
+};
+
+/// A lilliput document, embedded within another document as opaque bytes.
+///
+/// Embedded documents are encoded as length-prefixed bytes values, so
+/// deserializing an envelope only copies its payload's bytes, rather than
+/// decoding them, which allows for routing messages by their envelope alone:
+///
+/// ```
+/// use lilliput_serde::{de::from_slice, embedded::Embedded, ser::to_vec};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Envelope {
+///     route: String,
+///     payload: Embedded,
+/// }
+///
+/// #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+/// struct Ping {
+///     seq: u32,
+/// }
+///
+/// let envelope = Envelope {
+///     route: "ping".to_owned(),
+///     payload: Embedded::encode(&Ping { seq: 42 }).unwrap(),
+/// };
+/// let encoded = to_vec(&envelope).unwrap();
+///
+/// let envelope: Envelope = from_slice(&encoded).unwrap();
+/// assert_eq!(envelope.route, "ping");
+/// assert_eq!(envelope.payload.decode::<Ping>().unwrap(), Ping { seq: 42 });
+/// ```
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Embedded(Vec<u8>);
+
+impl Embedded {
+    /// Encodes `value` into an embedded document.
+    pub fn encode<T>(value: &T) -> Result<Self, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        to_vec(value).map(Self)
+    }
+
+    /// Encodes `value` into an embedded document, configured by `config`.
+    pub fn encode_with_config<T>(value: &T, config: SerializerConfig) -> Result<Self, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        to_vec_with_config(value, config).map(Self)
+    }
+
+    /// Creates an embedded document from its already encoded `bytes`.
+    ///
+    /// The bytes are not validated until they get decoded.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Decodes the embedded document as a `T`.
+    pub fn decode<'de, T>(&'de self) -> Result<T, Error>
+    where
+        T: 'de + Deserialize<'de>,
+    {
+        from_slice(&self.0)
+    }
+
+    /// Returns the embedded document's encoded bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the embedded document's encoded bytes, consuming `self`.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Returns the length of the embedded document's encoding.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true`, if the embedded document's encoding is empty, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Serialize for Embedded {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Embedded {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct EmbeddedVisitor;
+
+        impl<'de> de::Visitor<'de> for EmbeddedVisitor {
+            type Value = Embedded;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("embedded document")
+            }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Ok(Embedded(value.to_vec()))
+            }
+
+            fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(Embedded(value))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(EmbeddedVisitor)
+    }
+}
+
+impl From<Embedded> for Vec<u8> {
+    fn from(value: Embedded) -> Self {
+        value.0
+    }
+}
@@ -7,20 +7,41 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
-/// Values.
-pub mod value {
-    pub use lilliput_core::value::*;
-}
+#[macro_use]
+mod macros;
 
+#[cfg(feature = "bigint")]
+pub mod big_int;
+pub mod compat;
 pub mod config;
 pub mod de;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod embedded;
 pub mod error;
+pub mod roundtrip;
+#[cfg(feature = "rpc")]
+pub mod rpc;
 pub mod ser;
+pub mod size;
+pub mod spanned;
+#[cfg(feature = "std")]
+pub mod timestamp;
+pub mod unknown_fields;
+#[cfg(feature = "uuid")]
+pub mod uuid;
+pub mod value;
 
 /// The crates's prelude.
 pub mod prelude {
-    pub use crate::{config::*, de::*, error::Error, ser::*, value::*};
+    pub use crate::{
+        config::*, de::*, embedded::*, error::Error, roundtrip::*, ser::*, size::*, spanned::*,
+        unknown_fields::*, value::*,
+    };
 }
 
+#[doc(hidden)]
+pub use crate::macros::__private;
+
 #[cfg(test)]
 mod tests;
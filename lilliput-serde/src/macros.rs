@@ -0,0 +1,185 @@
+/// Constructs a `Value` from literal syntax.
+///
+/// | Value  | Syntax                                                       |
+/// | ------ | ------------------------------------------------------------ |
+/// | Seq    | `[1_u8, "two", [3.0]]`                                       |
+/// | Map    | `{"key": 1_u8, 2_u8: "two", null: (), [1, 2]: true}`         |
+/// | Bytes  | `b"lilliput"`, or any other expression of type `&[u8]`       |
+/// | Unit   | `()`                                                         |
+/// | Null   | `null`                                                       |
+///
+/// Map keys can be of any type, just like values.
+/// Any other expression gets converted via `to_value()`, which makes
+/// integer literals without suffix (e.g. `42`) become `i32`s,
+/// so use suffixes (e.g. `42_u8`) for controlling their width.
+///
+/// ```
+/// use lilliput_serde::{lilliput, value::*};
+///
+/// let name = "lilliput";
+///
+/// let value = lilliput!({
+///     "name": name,
+///     "tags": [b"binary", null],
+///     42_u8: (),
+/// });
+///
+/// let Value::Map(map) = &value else {
+///     unreachable!();
+/// };
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(
+///     map.as_map_ref()[&lilliput!(42_u8)],
+///     Value::Unit(UnitValue),
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics if an expression fails to convert into a `Value`,
+/// e.g. due to its `Serialize` implementation returning an error.
+#[macro_export]
+macro_rules! lilliput {
+    (null) => {
+        $crate::value::Value::Null($crate::value::NullValue)
+    };
+
+    (()) => {
+        $crate::value::Value::Unit($crate::value::UnitValue)
+    };
+
+    ([]) => {
+        $crate::value::Value::Seq($crate::value::SeqValue::default())
+    };
+
+    ([ $($tt:tt)+ ]) => {
+        $crate::value::Value::Seq($crate::value::SeqValue::from(
+            $crate::__lilliput_seq!([] () $($tt)+)
+        ))
+    };
+
+    ({}) => {
+        $crate::value::Value::Map($crate::value::MapValue::default())
+    };
+
+    ({ $($tt:tt)+ }) => {{
+        let mut map = $crate::value::Map::default();
+        $crate::__lilliput_map!(map () $($tt)+);
+        $crate::value::Value::Map($crate::value::MapValue::from(map))
+    }};
+
+    ($other:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{ViaBytes as _, ViaSerialize as _};
+        (&$crate::__private::Wrap(&$other)).to_lilliput_value()
+    }};
+}
+
+/// Munches the items of a `lilliput!` seq, one token at a time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lilliput_seq {
+    // Done, with a trailing comma:
+    ([ $($items:expr,)* ] ()) => {
+        vec![$($items,)*]
+    };
+
+    // Done, without a trailing comma:
+    ([ $($items:expr,)* ] ($($item:tt)+)) => {
+        vec![$($items,)* $crate::lilliput!($($item)+)]
+    };
+
+    // End of an item:
+    ([ $($items:expr,)* ] ($($item:tt)+) , $($rest:tt)*) => {
+        $crate::__lilliput_seq!([ $($items,)* $crate::lilliput!($($item)+), ] () $($rest)*)
+    };
+
+    // Part of an item:
+    ([ $($items:expr,)* ] ($($item:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::__lilliput_seq!([ $($items,)* ] ($($item)* $next) $($rest)*)
+    };
+}
+
+/// Munches the entries of a `lilliput!` map, one token at a time,
+/// inserting them into `$map`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __lilliput_map {
+    // Done:
+    ($map:ident ()) => {};
+
+    // End of a key:
+    ($map:ident ($($key:tt)+) : $($rest:tt)*) => {
+        $crate::__lilliput_map!(@value $map ($($key)+) () $($rest)*)
+    };
+
+    // Missing value:
+    ($map:ident ($($key:tt)+)) => {
+        compile_error!("expected `:` and a value after map key")
+    };
+
+    // Part of a key:
+    ($map:ident ($($key:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::__lilliput_map!($map ($($key)* $next) $($rest)*)
+    };
+
+    // End of a value:
+    (@value $map:ident ($($key:tt)+) ($($value:tt)+) , $($rest:tt)*) => {
+        $map.insert($crate::lilliput!($($key)+), $crate::lilliput!($($value)+));
+        $crate::__lilliput_map!($map () $($rest)*)
+    };
+
+    // End of the last value:
+    (@value $map:ident ($($key:tt)+) ($($value:tt)+)) => {
+        $map.insert($crate::lilliput!($($key)+), $crate::lilliput!($($value)+));
+    };
+
+    // Part of a value:
+    (@value $map:ident ($($key:tt)+) ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::__lilliput_map!(@value $map ($($key)+) ($($value)* $next) $($rest)*)
+    };
+}
+
+/// Helpers of `lilliput!`, not for external use.
+pub mod __private {
+    use serde::Serialize;
+
+    use crate::value::{to_value, BytesValue, Value};
+
+    /// Wraps an expression, for dispatching its conversion
+    /// via auto-ref, based on its type.
+    pub struct Wrap<'a, T: ?Sized>(pub &'a T);
+
+    /// Converts byte slices (e.g. byte string literals) into bytes.
+    pub trait ViaBytes {
+        /// Converts the wrapped bytes into a value.
+        fn to_lilliput_value(&self) -> Value;
+    }
+
+    impl<const N: usize> ViaBytes for Wrap<'_, &[u8; N]> {
+        fn to_lilliput_value(&self) -> Value {
+            Value::Bytes(BytesValue::from(self.0.to_vec()))
+        }
+    }
+
+    impl ViaBytes for Wrap<'_, &[u8]> {
+        fn to_lilliput_value(&self) -> Value {
+            Value::Bytes(BytesValue::from(self.0.to_vec()))
+        }
+    }
+
+    /// Converts everything else, by serializing it.
+    pub trait ViaSerialize {
+        /// Converts the wrapped expression into a value.
+        fn to_lilliput_value(&self) -> Value;
+    }
+
+    impl<T> ViaSerialize for &Wrap<'_, T>
+    where
+        T: ?Sized + Serialize,
+    {
+        fn to_lilliput_value(&self) -> Value {
+            to_value(self.0).expect("expression should be convertible into a value")
+        }
+    }
+}
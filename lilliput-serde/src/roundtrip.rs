@@ -0,0 +1,192 @@
+//! Checking that values survive being serialized and deserialized again.
+
+use core::fmt;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    config::SerializerConfig,
+    de::from_slice,
+    error::Error,
+    ser::to_vec_with_config,
+    value::{to_value_with_config, Path, Value},
+};
+
+/// A failure to round-trip a value, as reported by `verify_roundtrip()`.
+#[derive(Debug)]
+pub enum Mismatch {
+    /// Serializing the original value failed.
+    Serialize(Error),
+    /// Deserializing the serialized value failed.
+    Deserialize(Error),
+    /// The deserialized value is not equal to the original value.
+    Value {
+        /// Path of the first differing value, relative to the root.
+        ///
+        /// Empty if the values differ only as per their `PartialEq` impl
+        /// (e.g. for `NaN`s), but not in their serialized form.
+        path: Path,
+        /// The original value at `path`, or `None` if there is none.
+        expected: Option<Value>,
+        /// The deserialized value at `path`, or `None` if there is none.
+        actual: Option<Value>,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialize(err) => write!(f, "serialization failed: {err}"),
+            Self::Deserialize(err) => write!(f, "deserialization failed: {err}"),
+            Self::Value {
+                path,
+                expected,
+                actual,
+            } => {
+                write!(f, "round-tripped value differs at `{path}`: expected ")?;
+                write_optional(expected, f)?;
+                write!(f, ", found ")?;
+                write_optional(actual, f)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Mismatch {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialize(err) | Self::Deserialize(err) => Some(err),
+            Self::Value { .. } => None,
+        }
+    }
+}
+
+/// Verifies that `value` is equal to itself after being serialized
+/// with `config`, and deserialized again.
+///
+/// Meant for use in tests, for proving that a type survives a given
+/// configuration, which may be lossy (e.g. when packing floats with
+/// a tolerance). On mismatch the first differing (nested) value is
+/// reported, by comparing both values in their `Value` form.
+///
+/// ```
+/// use lilliput_serde::{
+///     config::SerializerConfig,
+///     roundtrip::{verify_roundtrip, Mismatch},
+///     ser::{EncoderConfig, PackingMode},
+/// };
+/// use lilliput_core::config::{FloatEncoderConfig, PackedFloatValidation};
+///
+/// let value = vec![1.5, 0.1];
+///
+/// assert!(verify_roundtrip(&value, &SerializerConfig::default()).is_ok());
+///
+/// let encoder = EncoderConfig {
+///     floats: FloatEncoderConfig::default()
+///         .with_packing(PackingMode::Optimal)
+///         .with_validation(PackedFloatValidation::default().with_absolute(0.001)),
+///     ..EncoderConfig::default()
+/// };
+/// let config = SerializerConfig::default().with_encoder(encoder);
+///
+/// let Err(Mismatch::Value { path, .. }) = verify_roundtrip(&value, &config) else {
+///     panic!("expected mismatch");
+/// };
+/// assert_eq!(path.to_string(), "[1]");
+/// ```
+// Mismatches are rare, so boxing their values isn't worth it (they're larger with `preserve_order`):
+#[allow(clippy::result_large_err)]
+pub fn verify_roundtrip<T>(value: &T, config: &SerializerConfig) -> Result<(), Mismatch>
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    let encoded = to_vec_with_config(value, config.clone()).map_err(Mismatch::Serialize)?;
+    let decoded: T = from_slice(&encoded).map_err(Mismatch::Deserialize)?;
+
+    if decoded == *value {
+        return Ok(());
+    }
+
+    let expected = to_value_with_config(value, config.clone()).map_err(Mismatch::Serialize)?;
+    let actual = to_value_with_config(&decoded, config.clone()).map_err(Mismatch::Serialize)?;
+
+    let mut path = Path::new();
+    let (expected, actual) = match first_difference(&expected, &actual, &mut path) {
+        Some((expected, actual)) => (expected.cloned(), actual.cloned()),
+        None => (Some(expected), Some(actual)),
+    };
+
+    Err(Mismatch::Value {
+        path,
+        expected,
+        actual,
+    })
+}
+
+// MARK: - Private
+
+type Difference<'a> = (Option<&'a Value>, Option<&'a Value>);
+
+/// Returns the first pair of differing values within `expected` and `actual`,
+/// leaving `path` pointing at them, or `None` if both are equal.
+fn first_difference<'a>(
+    expected: &'a Value,
+    actual: &'a Value,
+    path: &mut Path,
+) -> Option<Difference<'a>> {
+    if expected == actual {
+        return None;
+    }
+
+    match (expected, actual) {
+        (Value::Seq(expected), Value::Seq(actual)) => {
+            let (expected, actual) = (expected.as_slice(), actual.as_slice());
+            for index in 0..expected.len().max(actual.len()) {
+                path.push(index);
+                match (expected.get(index), actual.get(index)) {
+                    (Some(expected), Some(actual)) => {
+                        if let Some(difference) = first_difference(expected, actual, path) {
+                            return Some(difference);
+                        }
+                    }
+                    difference => return Some(difference),
+                }
+                path.pop();
+            }
+        }
+        (Value::Map(expected), Value::Map(actual)) => {
+            let (expected, actual) = (expected.as_map_ref(), actual.as_map_ref());
+            for (key, expected_value) in expected {
+                path.push(key.clone());
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        if let Some(difference) =
+                            first_difference(expected_value, actual_value, path)
+                        {
+                            return Some(difference);
+                        }
+                    }
+                    None => return Some((Some(expected_value), None)),
+                }
+                path.pop();
+            }
+            if let Some((key, actual_value)) =
+                actual.iter().find(|(key, _)| !expected.contains_key(*key))
+            {
+                path.push(key.clone());
+                return Some((None, Some(actual_value)));
+            }
+        }
+        _ => {}
+    }
+
+    Some((Some(expected), Some(actual)))
+}
+
+fn write_optional(value: &Option<Value>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match value {
+        Some(value) => write!(f, "{value:?}"),
+        None => f.write_str("nothing"),
+    }
+}
@@ -0,0 +1,176 @@
+//! Request/response envelopes, for remote procedure calls over framed streams.
+//!
+//! Envelopes carry their params and results as `Embedded` documents,
+//! so they can be routed by their `id` and `method` alone, before
+//! deserializing the payload into the type expected by the handler.
+//!
+//! Only available with the `rpc` feature enabled.
+//!
+//! ```
+//! use lilliput_core::{
+//!     framing::{FrameDecoder, FrameEncoder},
+//!     io::VecWriter,
+//! };
+//! use lilliput_serde::rpc::{decode_frame, encode_frame, Request, Response};
+//!
+//! // Client:
+//! let mut outgoing = Vec::new();
+//! let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut outgoing));
+//! let request = Request::new(1, "add", &(2_u32, 3_u32)).unwrap();
+//! encode_frame(&mut encoder, &request).unwrap();
+//!
+//! // Server:
+//! let mut decoder = FrameDecoder::default();
+//! decoder.extend_from_slice(&outgoing);
+//! let request: Request = decode_frame(&mut decoder).unwrap().unwrap();
+//! assert_eq!(request.method, "add");
+//! let (a, b): (u32, u32) = request.params().unwrap();
+//! let response = request.respond(&(a + b)).unwrap();
+//!
+//! // Client, again:
+//! let result: u32 = response.into_result().unwrap();
+//! assert_eq!(result, 5);
+//! ```
+
+use lilliput_core::{
+    framing::{FrameDecoder, FrameEncoder},
+    io::Write,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{de::from_slice, embedded::Embedded, error::Error, ser::to_vec};
+
+/// The identifier of a request, for matching it with its response.
+pub type RequestId = u64;
+
+/// A request for calling `method`, with its params.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Request {
+    /// The request's identifier, echoed by its response.
+    pub id: RequestId,
+    /// The name of the method to call.
+    pub method: String,
+    /// The method's params, as an embedded document.
+    pub params: Embedded,
+}
+
+impl Request {
+    /// Creates a request for calling `method` with `params`.
+    pub fn new<P>(id: RequestId, method: impl Into<String>, params: &P) -> Result<Self, Error>
+    where
+        P: ?Sized + Serialize,
+    {
+        Ok(Self {
+            id,
+            method: method.into(),
+            params: Embedded::encode(params)?,
+        })
+    }
+
+    /// Decodes the request's params as a `P`.
+    pub fn params<'de, P>(&'de self) -> Result<P, Error>
+    where
+        P: 'de + Deserialize<'de>,
+    {
+        self.params.decode()
+    }
+
+    /// Creates a successful response to the request, with `result`.
+    pub fn respond<T>(&self, result: &T) -> Result<Response, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Response::ok(self.id, result)
+    }
+
+    /// Creates a failed response to the request, with `error`.
+    pub fn respond_err(&self, error: RpcError) -> Response {
+        Response::err(self.id, error)
+    }
+}
+
+/// A response to the request of the same `id`.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Response {
+    /// The identifier of the request responded to.
+    pub id: RequestId,
+    /// The method's result, as an embedded document, or its error.
+    pub result: Result<Embedded, RpcError>,
+}
+
+impl Response {
+    /// Creates a successful response to request `id`, with `result`.
+    pub fn ok<T>(id: RequestId, result: &T) -> Result<Self, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(Self {
+            id,
+            result: Ok(Embedded::encode(result)?),
+        })
+    }
+
+    /// Creates a failed response to request `id`, with `error`.
+    pub fn err(id: RequestId, error: RpcError) -> Self {
+        Self {
+            id,
+            result: Err(error),
+        }
+    }
+
+    /// Decodes the response's result as a `T`,
+    /// or returns its error as an `Error`.
+    pub fn into_result<T>(self) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        match self.result {
+            Ok(result) => result.decode(),
+            Err(error) => Err(Error::uncategorized(error, None)),
+        }
+    }
+}
+
+/// An error returned by a method, in place of its result.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RpcError {
+    /// An application-defined error code.
+    pub code: i64,
+    /// A human-readable description of the error.
+    pub message: String,
+}
+
+impl RpcError {
+    /// Creates an error with `code` and `message`.
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl core::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "rpc error {}: {}", self.code, self.message)
+    }
+}
+
+/// Encodes `value` (e.g. a `Request` or `Response`) as a frame.
+pub fn encode_frame<W, T>(encoder: &mut FrameEncoder<W>, value: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let bytes = to_vec(value)?;
+    encoder.encode_with(|encoder| encoder.encode_raw_bytes(&bytes))
+}
+
+/// Decodes the next frame as a `T` (e.g. a `Request` or `Response`),
+/// if it has been received completely.
+pub fn decode_frame<T>(decoder: &mut FrameDecoder) -> Result<Option<T>, Error>
+where
+    T: DeserializeOwned,
+{
+    decoder.decode_frame()?.map(from_slice).transpose()
+}
@@ -1,17 +1,28 @@
 //! Serializers for serializing lilliput-encoded values.
 
-use serde::{ser, Serialize};
+use serde::{ser, Deserialize as _, Serialize};
 
 pub use lilliput_core::config::{EncoderConfig, PackingMode};
 
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub use lilliput_core::compression::Codec;
+
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+use lilliput_core::compression::CompressedWriter;
+
 use lilliput_core::{
-    encoder::Encoder,
+    config::SeqCoding,
+    dictionary::Dictionary,
+    encoder::{Encoder, EncoderStats},
     io::{StdIoWriter, Write},
+    marker::Marker,
+    value::{ExtValue, Value},
 };
 
 use crate::{
-    config::{EnumVariantRepr, SerializerConfig},
+    config::{EnumVariantRepr, SerializerConfig, StructRepr, UnitRepr, UnsizedSeqRepr},
     error::{Error, Result},
+    value::ValueSerializer,
 };
 
 /// An serializer for serializing lilliput values.
@@ -31,6 +42,30 @@ impl<W> Serializer<W> {
         let encoder = Encoder::new(writer, config.encoder.clone());
         Self { encoder, config }
     }
+
+    /// Enables the collection of encoding statistics, returning `self`.
+    pub fn with_stats(mut self) -> Self {
+        self.encoder = self.encoder.with_stats();
+        self
+    }
+
+    /// Returns the statistics collected so far, if enabled.
+    pub fn stats(&self) -> Option<&EncoderStats> {
+        self.encoder.stats()
+    }
+
+    /// Attaches `dictionary` to the serializer's encoder, returning `self`.
+    ///
+    /// See [`Encoder::with_dictionary()`] for details.
+    pub fn with_dictionary(mut self, dictionary: Dictionary) -> Self {
+        self.encoder = self.encoder.with_dictionary(dictionary);
+        self
+    }
+
+    /// Returns the dictionary attached to the serializer's encoder, if any.
+    pub fn dictionary(&self) -> Option<&Dictionary> {
+        self.encoder.dictionary()
+    }
 }
 
 /// Serializes `value` into a `Vec<u8>`.
@@ -42,6 +77,28 @@ where
 }
 
 /// Serializes `value` into a `Vec<u8>`, configured by `config`.
+///
+/// ```
+/// use lilliput_serde::{
+///     config::{SerializerConfig, StructRepr},
+///     ser::{to_vec, to_vec_with_config, PackingMode},
+/// };
+///
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+/// }
+///
+/// let config = SerializerConfig::default().with_struct_repr(StructRepr::Seq);
+/// let encoded = to_vec_with_config(&Point { x: 1, y: 2 }, config).unwrap();
+/// assert!(encoded.len() < to_vec(&Point { x: 1, y: 2 }).unwrap().len());
+///
+/// let config = SerializerConfig::default();
+/// let config = config.clone().with_encoder(config.encoder.with_packing(PackingMode::None));
+/// let encoded = to_vec_with_config(&1_u64, config).unwrap();
+/// assert_eq!(encoded.len(), 1 + 8);
+/// ```
 pub fn to_vec_with_config<T>(value: &T, config: SerializerConfig) -> Result<Vec<u8>>
 where
     T: ?Sized + Serialize,
@@ -50,11 +107,28 @@ where
     let writer = StdIoWriter::new(&mut vec);
     let mut serializer = Serializer::new(writer, config);
 
-    value.serialize(&mut serializer)?;
+    serialize_document(&mut serializer, value)?;
 
     Ok(vec)
 }
 
+/// Serializes `value` into a `Vec<u8>`, configured by `config`,
+/// returning it together with the statistics collected during encoding.
+pub fn to_vec_with_stats<T>(value: &T, config: SerializerConfig) -> Result<(Vec<u8>, EncoderStats)>
+where
+    T: ?Sized + Serialize,
+{
+    let mut vec: Vec<u8> = Vec::new();
+    let writer = StdIoWriter::new(&mut vec);
+    let mut serializer = Serializer::new(writer, config).with_stats();
+
+    serialize_document(&mut serializer, value)?;
+
+    let stats = serializer.encoder.take_stats().unwrap_or_default();
+
+    Ok((vec, stats))
+}
+
 /// Serializes `value` into `writer`.
 #[cfg(feature = "std")]
 pub fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
@@ -74,23 +148,55 @@ where
 {
     let mut serializer = Serializer::new(StdIoWriter::new(writer), config);
 
-    value.serialize(&mut serializer)
+    serialize_document(&mut serializer, value)
+}
+
+/// Serializes `value` into a `Vec<u8>`, compressed with `codec`
+/// (see `lilliput_core::compression`).
+///
+/// ```
+/// use lilliput_serde::{
+///     de::from_slice_compressed,
+///     ser::{to_vec, to_vec_compressed, Codec},
+/// };
+///
+/// let value = vec!["lilliput"; 100];
+///
+/// let compressed = to_vec_compressed(&value, Codec::Lz4).unwrap();
+/// assert!(compressed.len() < to_vec(&value).unwrap().len());
+///
+/// let decompressed: Vec<String> = from_slice_compressed(&compressed).unwrap();
+/// assert_eq!(decompressed, value);
+/// ```
+///
+/// *This function is only available if lilliput_serde is built with
+/// the `"zstd"` and/or `"lz4"` features, enabling the respective codecs.*
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub fn to_vec_compressed<T>(value: &T, codec: Codec) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = CompressedWriter::new(Vec::new(), codec)?;
+
+    to_writer(&mut writer, value)?;
+
+    writer.finish()
 }
 
-impl<W> ser::Serializer for &mut Serializer<W>
+impl<'a, W> ser::Serializer for &'a mut Serializer<W>
 where
     W: Write,
 {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = Self;
+    type SerializeSeq = Compound<'a, W>;
     type SerializeTuple = Self;
     type SerializeTupleStruct = Self;
     type SerializeTupleVariant = Self;
-    type SerializeMap = Self;
-    type SerializeStruct = Self;
-    type SerializeStructVariant = Self;
+    type SerializeMap = Compound<'a, W>;
+    type SerializeStruct = StructSerializer<'a, W>;
+    type SerializeStructVariant = StructSerializer<'a, W>;
 
     fn serialize_bool(self, value: bool) -> Result<()> {
         self.encoder.encode_bool(value)
@@ -112,6 +218,10 @@ where
         self.encoder.encode_i64(value)
     }
 
+    fn serialize_i128(self, value: i128) -> Result<()> {
+        self.encoder.encode_i128(value)
+    }
+
     fn serialize_u8(self, value: u8) -> Result<()> {
         self.encoder.encode_u64(value.into())
     }
@@ -128,6 +238,10 @@ where
         self.encoder.encode_u64(value)
     }
 
+    fn serialize_u128(self, value: u128) -> Result<()> {
+        self.encoder.encode_u128(value)
+    }
+
     fn serialize_f32(self, value: f32) -> Result<()> {
         self.encoder.encode_f32(value)
     }
@@ -160,7 +274,10 @@ where
     }
 
     fn serialize_unit(self) -> Result<()> {
-        self.encoder.encode_unit()
+        match self.config.unit_repr {
+            UnitRepr::Unit => self.encoder.encode_unit(),
+            UnitRepr::Null => self.encoder.encode_null(),
+        }
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
@@ -179,10 +296,37 @@ where
         }
     }
 
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
+        if name == ExtValue::NEWTYPE_NAME {
+            let value = value.serialize(&ValueSerializer::new(SerializerConfig::default()))?;
+            return self
+                .encoder
+                .encode_ext_value(&ExtValue::deserialize(value)?);
+        }
+
+        #[cfg(feature = "std")]
+        if crate::timestamp::is_extension(name) {
+            return crate::timestamp::encode_extension(&mut self.encoder, name, value);
+        }
+
+        #[cfg(feature = "uuid")]
+        if name == crate::uuid::UUID_NAME {
+            return crate::uuid::encode_uuid(&mut self.encoder, value);
+        }
+
+        #[cfg(feature = "bigint")]
+        if name == crate::big_int::BIG_INT_NAME {
+            return crate::big_int::encode_big_int(&mut self.encoder, value);
+        }
+
+        #[cfg(feature = "decimal")]
+        if name == crate::decimal::DECIMAL_NAME {
+            return crate::decimal::encode_decimal(&mut self.encoder, value);
+        }
+
         value.serialize(self)
     }
 
@@ -210,26 +354,29 @@ where
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
-        let Some(len) = len else {
-            return Err(Error::unknown_length());
-        };
+        // Structs represented as sequences don't make for columns (or rows):
+        if self.config.encoder.seqs != SeqCoding::Plain
+            && self.config.struct_repr != StructRepr::Seq
+        {
+            return Ok(Compound::homogeneous(self, len));
+        }
+
+        Compound::seq(self, len)
+    }
 
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
         let header = self.encoder.header_for_seq_len(len);
         self.encoder.encode_seq_header(&header)?;
 
         Ok(self)
     }
 
-    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
-        self.serialize_seq(Some(len))
-    }
-
     fn serialize_tuple_struct(
         self,
         _name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        self.serialize_seq(Some(len))
+        self.serialize_tuple(len)
     }
 
     fn serialize_tuple_variant(
@@ -255,17 +402,19 @@ where
 
     fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
         let Some(len) = len else {
-            return Err(Error::unknown_length());
+            return Ok(Compound::buffered(self, Marker::Map));
         };
 
         let header = self.encoder.header_for_map_len(len);
         self.encoder.encode_map_header(&header)?;
 
-        Ok(self)
+        Ok(Compound::Direct(self))
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
-        self.serialize_map(Some(len))
+        self.serialize_struct_header(len)?;
+
+        Ok(StructSerializer::new(self))
     }
 
     fn serialize_struct_variant(
@@ -283,14 +432,213 @@ where
             EnumVariantRepr::Name => self.serialize_str(variant)?,
         }
 
-        let inner_map_header = self.encoder.header_for_map_len(len);
-        self.encoder.encode_map_header(&inner_map_header)?;
+        self.serialize_struct_header(len)?;
 
-        Ok(self)
+        Ok(StructSerializer::new(self))
     }
 }
 
-impl<W> ser::SerializeSeq for &mut Serializer<W>
+impl<W> Serializer<W>
+where
+    W: Write,
+{
+    fn serialize_struct_header(&mut self, len: usize) -> Result<()> {
+        match self.config.struct_repr {
+            StructRepr::Seq => {
+                let header = self.encoder.header_for_seq_len(len);
+                self.encoder.encode_seq_header(&header)
+            }
+            StructRepr::Map | StructRepr::IndexMap => {
+                let header = self.encoder.header_for_map_len(len);
+                self.encoder.encode_map_header(&header)
+            }
+        }
+    }
+
+    fn serialize_struct_field<T>(&mut self, index: u32, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        match self.config.struct_repr {
+            StructRepr::Seq => {}
+            StructRepr::Map => self.encoder.encode_str(key)?,
+            StructRepr::IndexMap => self.encoder.encode_u32(index)?,
+        }
+
+        value.serialize(self)
+    }
+}
+
+/// A serializer for serializing the elements of sequences and maps.
+///
+/// Elements of sequences and maps with a length unknown upfront
+/// get buffered, until their length is known, unless configured
+/// to be streamed (see `UnsizedSeqRepr`).
+///
+/// Elements of sequences of structs get buffered as `Value`s if configured
+/// to be encoded column by column (see `SeqCoding`), as that requires all of them.
+pub enum Compound<'a, W> {
+    /// Elements get serialized directly.
+    #[doc(hidden)]
+    Direct(&'a mut Serializer<W>),
+    /// Elements get serialized directly, followed by an end marker.
+    #[doc(hidden)]
+    Streamed(&'a mut Serializer<W>),
+    /// Elements get buffered, until their count is known.
+    #[doc(hidden)]
+    Buffered {
+        /// The serializer to write to, once the count is known.
+        ser: &'a mut Serializer<W>,
+        /// The serializer to buffer elements in.
+        buffer: Box<Serializer<StdIoWriter<Vec<u8>>>>,
+        /// The type of buffered value.
+        marker: Marker,
+        /// The number of buffered elements.
+        len: usize,
+    },
+    /// Elements get buffered as values, until all of them are known,
+    /// unless the first one turns out not to be a struct.
+    #[doc(hidden)]
+    Homogeneous {
+        /// The serializer to write to, once all elements are known.
+        ///
+        /// Only ever taken for switching to one of the other variants.
+        ser: Option<&'a mut Serializer<W>>,
+        /// The number of elements, if known.
+        len: Option<usize>,
+        /// The serializer to convert elements into values with.
+        values: ValueSerializer,
+        /// The buffered elements.
+        items: Vec<Value>,
+    },
+}
+
+impl<'a, W> Compound<'a, W> {
+    fn buffered(ser: &'a mut Serializer<W>, marker: Marker) -> Self {
+        let buffer = Serializer {
+            encoder: ser.encoder.fork(StdIoWriter::new(Vec::new())),
+            config: ser.config.clone(),
+        };
+
+        Self::Buffered {
+            ser,
+            buffer: Box::new(buffer),
+            marker,
+            len: 0,
+        }
+    }
+
+    fn homogeneous(ser: &'a mut Serializer<W>, len: Option<usize>) -> Self {
+        let values = ValueSerializer::new(ser.config.clone());
+
+        Self::Homogeneous {
+            ser: Some(ser),
+            len,
+            values,
+            items: Vec::new(),
+        }
+    }
+
+    /// Starts a sequence of `len` elements (if known), whose elements get
+    /// serialized directly, streamed or buffered, depending on `ser`'s config.
+    fn seq(ser: &'a mut Serializer<W>, len: Option<usize>) -> Result<Self>
+    where
+        W: Write,
+    {
+        let Some(len) = len else {
+            return match ser.config.unsized_seq_repr {
+                UnsizedSeqRepr::Buffered => Ok(Self::buffered(ser, Marker::Seq)),
+                UnsizedSeqRepr::Streamed => {
+                    ser.encoder.encode_seq_stream_start()?;
+                    Ok(Self::Streamed(ser))
+                }
+            };
+        };
+
+        let header = ser.encoder.header_for_seq_len(len);
+        ser.encoder.encode_seq_header(&header)?;
+
+        Ok(Self::Direct(ser))
+    }
+}
+
+impl<W> Compound<'_, W>
+where
+    W: Write,
+{
+    #[inline]
+    fn serialize_buffered<T>(&mut self, value: &T, is_element: bool) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        match self {
+            Self::Direct(ser) | Self::Streamed(ser) => value.serialize(&mut **ser),
+            Self::Buffered { buffer, len, .. } => {
+                value.serialize(&mut **buffer)?;
+
+                if is_element {
+                    *len += 1;
+                }
+
+                Ok(())
+            }
+            Self::Homogeneous {
+                ser,
+                len,
+                values,
+                items,
+            } => {
+                // Only sequences of structs get buffered, to be encoded by column (or by row):
+                if items.is_empty() && !StructProbe::is_struct(value) {
+                    let ser = ser.take().expect("serializer should not have been taken");
+                    *self = Self::seq(ser, *len)?;
+                    return self.serialize_buffered(value, is_element);
+                }
+
+                if items.is_empty() {
+                    items.reserve(len.unwrap_or(0));
+                }
+
+                items.push(value.serialize(&*values)?);
+                Ok(())
+            }
+        }
+    }
+
+    fn end_buffered(self) -> Result<()> {
+        let (ser, buffer, marker, len) = match self {
+            Self::Direct(_) => return Ok(()),
+            Self::Streamed(ser) => return ser.encoder.encode_seq_stream_end(),
+            Self::Homogeneous { ser, items, .. } => {
+                let ser = ser.expect("serializer should not have been taken");
+                return ser.encoder.encode_seq(&items);
+            }
+            Self::Buffered {
+                ser,
+                buffer,
+                marker,
+                len,
+            } => (ser, buffer, marker, len),
+        };
+
+        let bytes = ser.encoder.join(buffer.encoder).into_writer();
+
+        match marker {
+            Marker::Seq => {
+                let header = ser.encoder.header_for_seq_len(len);
+                ser.encoder.encode_seq_header(&header)?;
+            }
+            _ => {
+                let header = ser.encoder.header_for_map_len(len);
+                ser.encoder.encode_map_header(&header)?;
+            }
+        }
+
+        ser.encoder.encode_forked_bytes(&bytes)
+    }
+}
+
+impl<W> ser::SerializeSeq for Compound<'_, W>
 where
     W: Write,
 {
@@ -302,12 +650,53 @@ where
     where
         T: ?Sized + Serialize,
     {
-        value.serialize(&mut **self)
+        self.serialize_buffered(value, true)
     }
 
     #[inline]
     fn end(self) -> Result<()> {
-        Ok(())
+        self.end_buffered()
+    }
+}
+
+impl<W> ser::SerializeMap for Compound<'_, W>
+where
+    W: Write,
+{
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_buffered(key, false)
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.serialize_buffered(value, true)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        self.end_buffered()
+    }
+}
+
+/// A serializer for serializing struct fields, as configured by `StructRepr`.
+pub struct StructSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+    index: u32,
+}
+
+impl<'a, W> StructSerializer<'a, W> {
+    fn new(ser: &'a mut Serializer<W>) -> Self {
+        Self { ser, index: 0 }
     }
 }
 
@@ -374,7 +763,7 @@ where
     }
 }
 
-impl<W> ser::SerializeMap for &mut Serializer<W>
+impl<W> ser::SerializeStruct for StructSerializer<'_, W>
 where
     W: Write,
 {
@@ -382,19 +771,22 @@ where
     type Error = Error;
 
     #[inline]
-    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
-        key.serialize(&mut **self)
+        self.ser.serialize_struct_field(self.index, key, value)?;
+        self.index += 1;
+
+        Ok(())
     }
 
     #[inline]
-    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
-    where
-        T: ?Sized + Serialize,
-    {
-        value.serialize(&mut **self)
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        // Skipped fields retain their index, for other fields to keep theirs:
+        self.index += 1;
+
+        Ok(())
     }
 
     #[inline]
@@ -403,7 +795,7 @@ where
     }
 }
 
-impl<W> ser::SerializeStruct for &mut Serializer<W>
+impl<W> ser::SerializeStructVariant for StructSerializer<'_, W>
 where
     W: Write,
 {
@@ -415,8 +807,18 @@ where
     where
         T: ?Sized + Serialize,
     {
-        key.serialize(&mut **self)?;
-        value.serialize(&mut **self)
+        self.ser.serialize_struct_field(self.index, key, value)?;
+        self.index += 1;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        // Skipped fields retain their index, for other fields to keep theirs:
+        self.index += 1;
+
+        Ok(())
     }
 
     #[inline]
@@ -425,24 +827,224 @@ where
     }
 }
 
-impl<W> ser::SerializeStructVariant for &mut Serializer<W>
+/// Serializes `value` as a whole document, preceded by a preamble, if enabled.
+fn serialize_document<W, T>(serializer: &mut Serializer<W>, value: &T) -> Result<()>
 where
     W: Write,
+    T: ?Sized + Serialize,
 {
-    type Ok = ();
+    if serializer.config.encoder.preamble {
+        serializer.encoder.encode_preamble()?;
+    }
+
+    value.serialize(serializer)
+}
+
+/// A serializer merely checking whether a value is a struct,
+/// without serializing any of its contents.
+struct StructProbe;
+
+impl ser::Serializer for StructProbe {
+    type Ok = bool;
     type Error = Error;
 
-    #[inline]
-    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    type SerializeSeq = ser::Impossible<bool, Error>;
+    type SerializeTuple = ser::Impossible<bool, Error>;
+    type SerializeTupleStruct = ser::Impossible<bool, Error>;
+    type SerializeTupleVariant = ser::Impossible<bool, Error>;
+    type SerializeMap = ser::Impossible<bool, Error>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = ser::Impossible<bool, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_char(self, _v: char) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_none(self) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<bool>
     where
         T: ?Sized + Serialize,
     {
-        key.serialize(&mut **self)?;
-        value.serialize(&mut **self)
+        Ok(false)
     }
 
-    #[inline]
-    fn end(self) -> Result<()> {
+    fn serialize_unit(self) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<bool>
+    where
+        T: ?Sized + Serialize,
+    {
+        // Newtype structs are transparent (other than for extension values,
+        // none of which wrap structs):
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<bool>
+    where
+        T: ?Sized + Serialize,
+    {
+        Ok(false)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Self::not_a_struct())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(Self::not_a_struct())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(Self::not_a_struct())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(Self::not_a_struct())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(Self::not_a_struct())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(Self::not_a_struct())
+    }
+}
+
+impl StructProbe {
+    /// Returns `true` if `value` serializes as a struct.
+    fn is_struct<T>(value: &T) -> bool
+    where
+        T: ?Sized + Serialize,
+    {
+        // Errors only ever mean that the value is not a struct:
+        value.serialize(StructProbe).unwrap_or(false)
+    }
+
+    /// The error ending the probing of compound values other than structs,
+    /// which (unlike scalars) can't be answered with `Ok(false)`.
+    #[cold]
+    fn not_a_struct() -> Error {
+        <Error as ser::Error>::custom("not a struct")
+    }
+}
+
+impl ser::SerializeStruct for StructProbe {
+    type Ok = bool;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
         Ok(())
     }
+
+    fn end(self) -> Result<bool> {
+        Ok(true)
+    }
 }
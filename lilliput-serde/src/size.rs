@@ -0,0 +1,567 @@
+//! Upper bounds for the size of lilliput-encoded values.
+
+use serde::{ser, Serialize};
+
+use lilliput_core::preamble::PREAMBLE_LEN;
+
+use crate::{
+    config::SerializerConfig,
+    error::{Error, Result},
+};
+
+/// Maximum size of an integer value: header byte, plus up to 8 bytes of value.
+const MAX_INT_SIZE: usize = 1 + 8;
+/// Maximum size of a 128-bit integer value: header byte, plus up to 16 bytes of value.
+const MAX_INT128_SIZE: usize = 1 + 16;
+/// Maximum size of a 32-bit floating-point value: header byte, plus up to 4 bytes of value.
+const MAX_F32_SIZE: usize = 1 + 4;
+/// Maximum size of a 64-bit floating-point value: header byte, plus up to 8 bytes of value.
+const MAX_F64_SIZE: usize = 1 + 8;
+/// Maximum size of a length-prefixed header: header byte, plus up to 8 bytes of length.
+const MAX_LEN_HEADER_SIZE: usize = 1 + 8;
+/// Size of single-byte values (bools, units and nulls).
+const BYTE_SIZE: usize = 1;
+
+/// Returns an upper bound for the encoded size of `value`.
+///
+/// The returned size assumes the worst-case width for every header, length and number,
+/// but does not account for a document's preamble (see `max_encoded_size_of_with_config`).
+pub fn max_encoded_size_of<T>(value: &T) -> Result<usize>
+where
+    T: ?Sized + Serialize,
+{
+    let mut probe = SizeProbe::default();
+
+    value.serialize(&mut probe)?;
+
+    Ok(probe.size)
+}
+
+/// Returns an upper bound for the encoded size of `value`,
+/// as a document serialized with `config`.
+///
+/// Unlike `max_encoded_size_of`, this includes the document's preamble,
+/// if enabled by `config.encoder.preamble`.
+pub fn max_encoded_size_of_with_config<T>(value: &T, config: SerializerConfig) -> Result<usize>
+where
+    T: ?Sized + Serialize,
+{
+    let size = max_encoded_size_of(value)?;
+
+    if config.encoder.preamble {
+        Ok(size.saturating_add(PREAMBLE_LEN))
+    } else {
+        Ok(size)
+    }
+}
+
+/// Returns an upper bound for the encoded size of any value of type `T`,
+/// or `None` if the type does not have a fixed shape.
+///
+/// The type's shape is probed by serializing `T::default()`. Types containing
+/// strings, byte arrays, sequences, maps, options or enums are considered
+/// to not have a fixed shape, as the size of their values is not bounded
+/// by their type. Fixed-size arrays and tuples on the other hand are.
+pub fn max_encoded_size<T>() -> Result<Option<usize>>
+where
+    T: Serialize + Default,
+{
+    let mut probe = SizeProbe::default();
+
+    T::default().serialize(&mut probe)?;
+
+    Ok((!probe.is_unbounded).then_some(probe.size))
+}
+
+#[derive(Default, Debug)]
+struct SizeProbe {
+    size: usize,
+    is_unbounded: bool,
+}
+
+impl SizeProbe {
+    #[inline]
+    fn add(&mut self, size: usize) {
+        self.size = self.size.saturating_add(size);
+    }
+
+    #[inline]
+    fn add_unbounded(&mut self, size: usize) {
+        self.is_unbounded = true;
+        self.add(size);
+    }
+
+    #[inline]
+    fn add_variant(&mut self, variant: &'static str) {
+        // Variants get encoded either by index or by name,
+        // so we account for whichever is larger:
+        self.add(MAX_INT_SIZE.max(MAX_LEN_HEADER_SIZE + variant.len()));
+    }
+
+    #[inline]
+    fn add_field(&mut self, key: &'static str) {
+        // Fields get encoded with their name as key, at most:
+        self.add(MAX_LEN_HEADER_SIZE + key.len());
+    }
+}
+
+impl ser::Serializer for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, _value: bool) -> Result<()> {
+        self.add(BYTE_SIZE);
+        Ok(())
+    }
+
+    fn serialize_i8(self, _value: i8) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_i16(self, _value: i16) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_i32(self, _value: i32) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_i64(self, _value: i64) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_i128(self, _value: i128) -> Result<()> {
+        self.add(MAX_INT128_SIZE);
+        Ok(())
+    }
+
+    fn serialize_u8(self, _value: u8) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_u16(self, _value: u16) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_u32(self, _value: u32) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_u64(self, _value: u64) -> Result<()> {
+        self.add(MAX_INT_SIZE);
+        Ok(())
+    }
+
+    fn serialize_u128(self, _value: u128) -> Result<()> {
+        self.add(MAX_INT128_SIZE);
+        Ok(())
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<()> {
+        self.add(MAX_F32_SIZE);
+        Ok(())
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<()> {
+        self.add(MAX_F64_SIZE);
+        Ok(())
+    }
+
+    fn serialize_char(self, _value: char) -> Result<()> {
+        // Chars get encoded as strings of up to 4 bytes:
+        self.add(MAX_LEN_HEADER_SIZE + 4);
+        Ok(())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<()> {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE + value.len());
+        Ok(())
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE + value.len());
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        self.add_unbounded(BYTE_SIZE);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.is_unbounded = true;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        self.add(BYTE_SIZE);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        self.is_unbounded = true;
+        self.add_variant(variant);
+        Ok(())
+    }
+
+    #[cfg_attr(
+        not(any(
+            feature = "std",
+            feature = "uuid",
+            feature = "bigint",
+            feature = "decimal"
+        )),
+        allow(unused_variables)
+    )]
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        // Extension values (e.g. timestamps) start with their tag,
+        // followed by their parts, with the body's length taking
+        // the place of the parts' sequence header:
+        #[cfg(feature = "std")]
+        if crate::timestamp::is_extension(name) {
+            self.add(MAX_INT_SIZE);
+        }
+
+        #[cfg(feature = "uuid")]
+        if name == crate::uuid::UUID_NAME {
+            self.add(MAX_INT_SIZE);
+        }
+
+        #[cfg(feature = "bigint")]
+        if name == crate::big_int::BIG_INT_NAME {
+            self.add(MAX_INT_SIZE);
+        }
+
+        #[cfg(feature = "decimal")]
+        if name == crate::decimal::DECIMAL_NAME {
+            self.add(MAX_INT_SIZE);
+        }
+
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE);
+        self.add_variant(variant);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        self.add(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.add(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE);
+        self.add_variant(variant);
+        self.add(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.add(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        self.add_unbounded(MAX_LEN_HEADER_SIZE);
+        self.add_variant(variant);
+        self.add(MAX_LEN_HEADER_SIZE);
+        Ok(self)
+    }
+}
+
+impl ser::SerializeSeq for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        key.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.add_field(key);
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut SizeProbe {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.add_field(key);
+        value.serialize(&mut **self)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use serde::Serialize;
+
+    use crate::{
+        config::SerializerConfig,
+        ser::{to_vec, to_vec_with_config},
+    };
+    use lilliput_core::config::{EncoderConfig, PackingMode};
+
+    use super::*;
+
+    #[derive(Default, Serialize)]
+    struct Fixed {
+        id: u64,
+        position: (f32, f32, f32),
+        flags: [bool; 4],
+        level: i8,
+    }
+
+    #[derive(Default, Serialize)]
+    struct Variable {
+        id: u64,
+        name: String,
+    }
+
+    #[test]
+    fn fixed_shape() {
+        let max_size = max_encoded_size::<Fixed>().unwrap().unwrap();
+
+        let value = Fixed {
+            id: u64::MAX,
+            position: (1.5, -2.5, f32::MAX),
+            flags: [true; 4],
+            level: i8::MIN,
+        };
+
+        for packing in [PackingMode::None, PackingMode::Native, PackingMode::Optimal] {
+            let config = SerializerConfig::default()
+                .with_encoder(EncoderConfig::default().with_packing(packing));
+            let encoded = to_vec_with_config(&value, config).unwrap();
+            assert!(encoded.len() <= max_size);
+        }
+    }
+
+    #[test]
+    fn preamble() {
+        let value = Fixed {
+            id: u64::MAX,
+            position: (1.5, -2.5, f32::MAX),
+            flags: [true; 4],
+            level: i8::MIN,
+        };
+
+        for preamble in [false, true] {
+            let config = SerializerConfig::default().with_encoder(
+                EncoderConfig::default()
+                    .with_packing(PackingMode::None)
+                    .with_preamble(preamble),
+            );
+            let max_size = max_encoded_size_of_with_config(&value, config.clone()).unwrap();
+            let encoded = to_vec_with_config(&value, config).unwrap();
+            assert!(encoded.len() <= max_size);
+
+            let expected =
+                max_encoded_size_of(&value).unwrap() + if preamble { PREAMBLE_LEN } else { 0 };
+            assert_eq!(max_size, expected);
+        }
+    }
+
+    #[test]
+    fn variable_shape() {
+        assert_eq!(max_encoded_size::<Variable>().unwrap(), None);
+        assert_eq!(max_encoded_size::<Vec<u8>>().unwrap(), None);
+        assert_eq!(max_encoded_size::<Option<u8>>().unwrap(), None);
+    }
+
+    proptest! {
+        #[test]
+        fn encoded_size_within_bound(id in u64::arbitrary(), name in String::arbitrary()) {
+            let value = Variable { id, name };
+            let max_size = max_encoded_size_of(&value).unwrap();
+            let encoded = to_vec(&value).unwrap();
+            prop_assert!(encoded.len() <= max_size);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! Capturing of the byte spans of deserialized values.
+
+use core::{fmt, marker::PhantomData, ops::Range};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Name of the struct used for requesting a value's span from the deserializer.
+pub(crate) const SPANNED_NAME: &str = "$lilliput::Spanned";
+
+/// Fields of the struct used for requesting a value's span from the deserializer.
+pub(crate) const SPANNED_FIELDS: &[&str] = &[
+    "$lilliput::Spanned::value",
+    "$lilliput::Spanned::start",
+    "$lilliput::Spanned::end",
+];
+
+/// A value, along with the range of bytes it occupied in the input.
+///
+/// Spans are relative to the start of the input, making them useful for
+/// error reporting, patching encoded documents in place, or diffing them:
+///
+/// ```
+/// use lilliput_serde::{de::from_slice, ser::to_vec, spanned::Spanned};
+///
+/// #[derive(serde::Serialize)]
+/// struct Sample {
+///     id: u8,
+///     name: &'static str,
+/// }
+///
+/// #[derive(serde::Deserialize)]
+/// struct SpannedSample {
+///     id: Spanned<u8>,
+///     name: Spanned<String>,
+/// }
+///
+/// let encoded = to_vec(&Sample { id: 7, name: "lilliput" }).unwrap();
+/// let sample: SpannedSample = from_slice(&encoded).unwrap();
+///
+/// assert_eq!(&encoded[sample.name.span()][1..], b"lilliput");
+/// assert_eq!(sample.id.into_inner(), 7);
+/// ```
+///
+/// Spans are only reported by lilliput's `Deserializer`, with other
+/// deserializers (e.g. ones of `Value`s) failing to deserialize them.
+/// Spanned values get serialized as their inner value.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Spanned<T> {
+    span: Range<usize>,
+    value: T,
+}
+
+impl<T> Spanned<T> {
+    /// Creates a spanned value from its `span` and `value`.
+    pub fn new(span: Range<usize>, value: T) -> Self {
+        Self { span, value }
+    }
+
+    /// Returns the range of bytes the value occupied in the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns a reference to the value.
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Returns the value, consuming `self`.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Serialize for Spanned<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Spanned<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(SPANNED_NAME, SPANNED_FIELDS, SpannedVisitor(PhantomData))
+    }
+}
+
+// MARK: - Private
+
+/// Visitor of spanned values, as maps of their value, start and end.
+struct SpannedVisitor<T>(PhantomData<T>);
+
+impl<'de, T> de::Visitor<'de> for SpannedVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Spanned<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("spanned value")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut value = None;
+        let mut start = None;
+        let mut end = None;
+
+        while let Some(field) = map.next_key::<SpannedField>()? {
+            match field {
+                SpannedField::Value => value = Some(map.next_value()?),
+                SpannedField::Start => start = Some(map.next_value()?),
+                SpannedField::End => end = Some(map.next_value()?),
+            }
+        }
+
+        let value = value.ok_or_else(|| de::Error::missing_field(SPANNED_FIELDS[0]))?;
+        let start = start.ok_or_else(|| de::Error::missing_field(SPANNED_FIELDS[1]))?;
+        let end = end.ok_or_else(|| de::Error::missing_field(SPANNED_FIELDS[2]))?;
+
+        Ok(Spanned::new(start..end, value))
+    }
+}
+
+/// The fields of a spanned value.
+enum SpannedField {
+    Value,
+    Start,
+    End,
+}
+
+impl<'de> Deserialize<'de> for SpannedField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl de::Visitor<'_> for FieldVisitor {
+            type Value = SpannedField;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("spanned value field")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match SPANNED_FIELDS.iter().position(|field| *field == value) {
+                    Some(0) => Ok(SpannedField::Value),
+                    Some(1) => Ok(SpannedField::Start),
+                    Some(2) => Ok(SpannedField::End),
+                    _ => Err(E::unknown_field(value, SPANNED_FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
@@ -203,50 +203,2691 @@ mod bytes_repr {
 }
 
 mod zero_copy {
+    use lilliput_core::config::DecoderConfig;
+
+    use crate::{config::DeserializerConfig, de::from_slice_with_config};
+
+    use super::*;
+
+    #[test]
+    fn borrowed() {
+        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+        struct Subject<'a> {
+            id: u32,
+            name: &'a str,
+            #[serde(with = "serde_bytes")]
+            data: &'a [u8],
+        }
+
+        let value = Subject {
+            id: 42,
+            name: "Bob",
+            data: &[1, 2, 3, 4],
+        };
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Subject = from_slice(&encoded).unwrap();
+
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn owned() {
+        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+        struct Subject {
+            id: u32,
+            name: String,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        let value = Subject {
+            id: 42,
+            name: "Bob".to_owned(),
+            data: vec![1, 2, 3, 4],
+        };
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Subject = from_slice(&encoded).unwrap();
+
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn force_owned() {
+        let config = DeserializerConfig::default()
+            .with_decoder(DecoderConfig::default().with_force_owned(true));
+
+        let encoded = to_vec("Bob").unwrap();
+
+        let decoded: String = from_slice_with_config(&encoded, config.clone()).unwrap();
+        assert_eq!(decoded, "Bob");
+
+        let err = from_slice_with_config::<&str>(&encoded, config).unwrap_err();
+        assert!(err.to_string().contains("borrowed string"));
+    }
+}
+
+mod struct_repr {
+    use lilliput_core::io::SliceReader;
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig, StructRepr, UnknownFieldsPolicy},
+        de::Deserializer,
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Subject {
+        id: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        nickname: Option<String>,
+        name: String,
+    }
+
+    fn index_map_config() -> SerializerConfig {
+        SerializerConfig::default().with_struct_repr(StructRepr::IndexMap)
+    }
+
+    #[test]
+    fn index_map() {
+        let value = Subject {
+            id: 42,
+            nickname: None,
+            name: "Bob".to_owned(),
+        };
+
+        let encoded = to_vec_with_config(&value, index_map_config()).unwrap();
+        let decoded: Subject = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+
+        // Map of 2 entries: 0 => 42, 2 => "Bob"
+        assert_eq!(
+            encoded,
+            vec![0b00011010, 0b11000000, 0b10000000, 42, 0b11000010, 0b01100011, b'B', b'o', b'b']
+        );
+
+        let encoded_by_name = to_vec(&value).unwrap();
+        assert!(encoded.len() < encoded_by_name.len());
+    }
+
+    #[test]
+    fn index_map_reordered() {
+        let mut map = Map::default();
+        map.insert(
+            Value::Int(2_u8.into()),
+            Value::String("Bob".to_owned().into()),
+        );
+        map.insert(Value::Int(0_u8.into()), Value::Int(42_u32.into()));
+
+        let encoded = to_vec(&MapValue(map)).unwrap();
+        let decoded: Subject = from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            decoded,
+            Subject {
+                id: 42,
+                nickname: None,
+                name: "Bob".to_owned(),
+            }
+        );
+    }
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct EvolvedSubject {
+        id: u32,
+        nickname: Option<String>,
+        name: String,
+        #[serde(default)]
+        age: u8,
+    }
+
+    #[test]
+    fn index_map_appended_field() {
+        let value = EvolvedSubject {
+            id: 42,
+            nickname: None,
+            name: "Bob".to_owned(),
+            age: 7,
+        };
+        let encoded = to_vec_with_config(&value, index_map_config()).unwrap();
+
+        // Older readers skip the appended field's unknown index:
+        let decoded: Subject = from_slice(&encoded).unwrap();
+        assert_eq!(
+            decoded,
+            Subject {
+                id: 42,
+                nickname: None,
+                name: "Bob".to_owned(),
+            }
+        );
+
+        let config =
+            DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Collect);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        Subject::deserialize(&mut deserializer).unwrap();
+        let unknown_fields = deserializer.take_unknown_fields();
+        let Some(Value::Map(fields)) =
+            unknown_fields.get(&Value::String("Subject".to_owned().into()))
+        else {
+            panic!("expected unknown fields of `Subject`");
+        };
+        assert_eq!(
+            fields.0.get(&Value::Int(3_u8.into())),
+            Some(&Value::Int(7_u8.into()))
+        );
+
+        let config = DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Error);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        assert!(Subject::deserialize(&mut deserializer).is_err());
+    }
+
+    #[test]
+    fn index_map_missing_field() {
+        let value = Subject {
+            id: 42,
+            nickname: Some("Bobby".to_owned()),
+            name: "Bob".to_owned(),
+        };
+        let encoded = to_vec_with_config(&value, index_map_config()).unwrap();
+
+        // Newer readers fall back to the default of the appended field:
+        let decoded: EvolvedSubject = from_slice(&encoded).unwrap();
+        assert_eq!(
+            decoded,
+            EvolvedSubject {
+                id: 42,
+                nickname: Some("Bobby".to_owned()),
+                name: "Bob".to_owned(),
+                age: 0,
+            }
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn index_map_roundtrip(value in Struct::<bool>::arbitrary()) {
+            let encoded = to_vec_with_config(&value, index_map_config()).unwrap();
+            let decoded: Struct<bool> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+
+        #[test]
+        fn index_map_struct_variant_roundtrip(value in Enum::<bool>::arbitrary_struct_variant()) {
+            let encoded = to_vec_with_config(&value, index_map_config()).unwrap();
+            let decoded: Enum<bool> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+
+        #[test]
+        fn seq_roundtrip(value in Struct::<bool>::arbitrary()) {
+            let encoded = to_vec_with_config(&value, seq_config()).unwrap();
+            let decoded: Struct<bool> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+
+        #[test]
+        fn seq_struct_variant_roundtrip(value in Enum::<bool>::arbitrary_struct_variant()) {
+            let encoded = to_vec_with_config(&value, seq_config()).unwrap();
+            let decoded: Enum<bool> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &value);
+        }
+    }
+
+    fn seq_config() -> SerializerConfig {
+        SerializerConfig::default().with_struct_repr(StructRepr::Seq)
+    }
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Point {
+        x: u32,
+        y: u32,
+    }
+
+    #[test]
+    fn seq() {
+        let value = Point { x: 1, y: 2 };
+
+        let encoded = to_vec_with_config(&value, seq_config()).unwrap();
+        // Seq of 2 elements: 1, 2
+        assert_eq!(encoded, vec![0b00110010, 0b11000001, 0b11000010]);
+
+        // Both representations decode through the same `Deserialize` impl:
+        for encoded in [encoded, to_vec(&value).unwrap()] {
+            for policy in [UnknownFieldsPolicy::Ignore, UnknownFieldsPolicy::Error] {
+                let config = DeserializerConfig::default().with_unknown_fields(policy);
+                let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+                let decoded = Point::deserialize(&mut deserializer).unwrap();
+                assert_eq!(decoded, value);
+            }
+        }
+    }
+
+    #[test]
+    fn seq_missing_field() {
+        let encoded = to_vec(&(1_u32,)).unwrap();
+        let error = from_slice::<Point>(&encoded).unwrap_err();
+        assert!(error.to_string().contains("found length 1"), "{error}");
+    }
+}
+
+mod unknown_fields {
+    use lilliput_core::{
+        error::{ErrorCode, ErrorKind},
+        io::SliceReader,
+    };
+
+    use crate::{
+        config::{DeserializerConfig, UnknownFieldsPolicy},
+        de::Deserializer,
+    };
+
+    use super::*;
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Subject {
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct ExtendedSubject {
+        id: u32,
+        extra: bool,
+        name: String,
+    }
+
+    fn encoded() -> Vec<u8> {
+        to_vec(&ExtendedSubject {
+            id: 42,
+            extra: true,
+            name: "Bob".to_owned(),
+        })
+        .unwrap()
+    }
+
+    fn deserialize(encoded: &[u8], policy: UnknownFieldsPolicy) -> (Result<Subject, Error>, Map) {
+        let config = DeserializerConfig::default().with_unknown_fields(policy);
+        let mut deserializer = Deserializer::new(SliceReader::new(encoded), config);
+        let result = Subject::deserialize(&mut deserializer);
+        (result, deserializer.take_unknown_fields())
+    }
+
+    fn expected() -> Subject {
+        Subject {
+            id: 42,
+            name: "Bob".to_owned(),
+        }
+    }
+
+    #[test]
+    fn ignore() {
+        let (result, unknown_fields) = deserialize(&encoded(), UnknownFieldsPolicy::Ignore);
+
+        assert_eq!(result.unwrap(), expected());
+        assert!(unknown_fields.is_empty());
+    }
+
+    /// Returns the position of the `extra` field's key within `encoded`.
+    fn extra_key_pos(encoded: &[u8]) -> usize {
+        // The key's bytes are preceded by its header:
+        encoded
+            .windows(5)
+            .position(|bytes| bytes == b"extra")
+            .unwrap()
+            - 1
+    }
+
+    fn assert_unknown_extra_field(error: &Error, encoded: &[u8]) {
+        assert_eq!(error.code(), ErrorCode::UnknownField);
+        assert_eq!(error.pos(), Some(extra_key_pos(encoded)));
+
+        let ErrorKind::UnknownField(field) = error.kind() else {
+            panic!("expected unknown field error, got {error:?}");
+        };
+        assert_eq!(field.unexpected, "extra");
+        assert_eq!(field.expected, ["id", "name"]);
+    }
+
+    #[test]
+    fn error() {
+        let encoded = encoded();
+        let (result, _) = deserialize(&encoded, UnknownFieldsPolicy::Error);
+
+        let error = result.unwrap_err();
+        assert!(error.to_string().contains("unknown field `extra`"));
+        assert_unknown_extra_field(&error, &encoded);
+    }
+
+    #[test]
+    fn deny_unknown_fields() {
+        #[derive(Eq, PartialEq, Debug, serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct StrictSubject {
+            id: u32,
+            name: String,
+        }
+
+        let encoded = encoded();
+        let error = from_slice::<StrictSubject>(&encoded).unwrap_err();
+        assert_unknown_extra_field(&error, &encoded);
+    }
+
+    #[test]
+    fn skipped_without_allocating() {
+        #[derive(serde::Serialize)]
+        struct LargeSubject {
+            id: u32,
+            extra: Vec<String>,
+            name: String,
+        }
+
+        let encoded = to_vec(&LargeSubject {
+            id: 42,
+            extra: vec!["lilliput".repeat(16); 1024],
+            name: "Bob".to_owned(),
+        })
+        .unwrap();
+
+        for policy in [UnknownFieldsPolicy::Ignore, UnknownFieldsPolicy::Error] {
+            let config = DeserializerConfig::default().with_unknown_fields(policy);
+            let mut deserializer =
+                Deserializer::new(SliceReader::new(&encoded), config).with_stats();
+            let result = Subject::deserialize(&mut deserializer);
+
+            // Only the known fields (i.e. the name) get allocated:
+            let stats = deserializer.stats().unwrap();
+            assert!(stats.allocated_bytes < 64, "{stats:?}");
+
+            if policy == UnknownFieldsPolicy::Ignore {
+                assert_eq!(result.unwrap(), expected());
+            }
+        }
+    }
+
+    #[test]
+    fn collect() {
+        let (result, unknown_fields) = deserialize(&encoded(), UnknownFieldsPolicy::Collect);
+
+        assert_eq!(result.unwrap(), expected());
+
+        let mut fields = Map::default();
+        fields.insert(
+            Value::String("extra".to_owned().into()),
+            Value::Bool(true.into()),
+        );
+        let mut expected_unknown_fields = Map::default();
+        expected_unknown_fields.insert(
+            Value::String("Subject".to_owned().into()),
+            Value::Map(fields.into()),
+        );
+        assert_eq!(unknown_fields, expected_unknown_fields);
+    }
+
+    #[test]
+    fn collect_limits() {
+        let extra = (0..32).fold(Value::Null(NullValue), |value, _| {
+            Value::Seq(SeqValue::from(vec![value]))
+        });
+        let encoded = to_vec(&BTreeMap::from([
+            ("id", Value::Int(42_u32.into())),
+            ("extra", extra),
+            ("name", Value::String("Bob".to_owned().into())),
+        ]))
+        .unwrap();
+
+        let config = DeserializerConfig::default()
+            .with_unknown_fields(UnknownFieldsPolicy::Collect)
+            .with_max_depth(16);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let err = Subject::deserialize(&mut deserializer).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+
+        let encoded = to_vec(&BTreeMap::from([
+            ("id", Value::Int(42_u32.into())),
+            ("extra", Value::String("lilliput".repeat(4).into())),
+            ("name", Value::String("Bob".to_owned().into())),
+        ]))
+        .unwrap();
+
+        let config = DeserializerConfig::default()
+            .with_unknown_fields(UnknownFieldsPolicy::Collect)
+            .with_max_len(Some(16));
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let err = Subject::deserialize(&mut deserializer).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        // Ignored fields are subject to the limits all the same:
+        let config = DeserializerConfig::default()
+            .with_unknown_fields(UnknownFieldsPolicy::Ignore)
+            .with_max_len(Some(16));
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let err = Subject::deserialize(&mut deserializer).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+
+    #[test]
+    fn unknown_headers() {
+        use lilliput_core::{
+            config::{DecoderConfig, UnknownHeaderPolicy},
+            encoder::Encoder,
+            io::VecWriter,
+            value::UnknownValue,
+        };
+
+        let unknown = Value::Unknown(UnknownValue::new(0b0010_1111, vec![1, 2, 3]));
+
+        let mut map = Map::default();
+        map.insert(
+            Value::String("id".to_owned().into()),
+            Value::Int(42_u32.into()),
+        );
+        map.insert(Value::String("extra".to_owned().into()), unknown.clone());
+        map.insert(
+            Value::String("name".to_owned().into()),
+            Value::String("Bob".to_owned().into()),
+        );
+
+        let mut encoded = Vec::new();
+        Encoder::from_writer(VecWriter::new(&mut encoded))
+            .encode_value(&Value::Map(map.into()))
+            .unwrap();
+
+        let (result, _) = deserialize(&encoded, UnknownFieldsPolicy::Ignore);
+        assert!(result.is_err());
+
+        let decoder_config =
+            DecoderConfig::default().with_unknown_headers(UnknownHeaderPolicy::Collect);
+        let config = DeserializerConfig::default()
+            .with_unknown_fields(UnknownFieldsPolicy::Collect)
+            .with_decoder(decoder_config);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let result = Subject::deserialize(&mut deserializer);
+
+        assert_eq!(result.unwrap(), expected());
+
+        let mut fields = Map::default();
+        fields.insert(Value::String("extra".to_owned().into()), unknown);
+        let mut expected_unknown_fields = Map::default();
+        expected_unknown_fields.insert(
+            Value::String("Subject".to_owned().into()),
+            Value::Map(fields.into()),
+        );
+        assert_eq!(deserializer.take_unknown_fields(), expected_unknown_fields);
+    }
+}
+
+mod flatten {
+    use lilliput_core::{
+        dictionary::Dictionary,
+        io::{SliceReader, VecWriter},
+        marker::Marker,
+    };
+
+    use crate::{
+        config::SerializerConfig,
+        de::Deserializer,
+        ser::{to_vec_with_stats, Serializer},
+        unknown_fields::UnknownFields,
+    };
+
+    use super::*;
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Subject {
+        id: u32,
+        #[serde(flatten)]
+        unknown: UnknownFields,
+    }
+
+    #[test]
+    fn unknown_fields_roundtrip() {
+        let mut map = Map::default();
+        map.insert(
+            Value::String("id".to_owned().into()),
+            Value::Int(42_u32.into()),
+        );
+        map.insert(
+            Value::String("name".to_owned().into()),
+            Value::String("Bob".to_owned().into()),
+        );
+        map.insert(
+            Value::String("tags".to_owned().into()),
+            Value::Seq(vec![Value::Bool(true.into())].into()),
+        );
+        let encoded = to_vec(&MapValue(map)).unwrap();
+
+        let decoded: Subject = from_slice(&encoded).unwrap();
+        assert_eq!(decoded.id, 42);
+        assert_eq!(decoded.unknown.len(), 2);
+
+        let reencoded = to_vec(&decoded).unwrap();
+        assert_eq!(reencoded, encoded);
+    }
+
+    #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+    struct Flattened {
+        id: u32,
+        #[serde(flatten)]
+        inner: Struct<String>,
+    }
+
+    fn flattened() -> Flattened {
+        Flattened {
+            id: 42,
+            inner: Struct {
+                a: "Bob".to_owned(),
+                b: "Alice".to_owned(),
+            },
+        }
+    }
+
+    #[test]
+    fn buffered_stats() {
+        let (encoded, stats) =
+            to_vec_with_stats(&flattened(), SerializerConfig::default()).unwrap();
+
+        assert_eq!(stats.total_bytes(), encoded.len());
+        assert_eq!(stats.for_marker(Marker::Map).values, 1);
+        assert_eq!(stats.for_marker(Marker::Int).values, 1);
+        assert_eq!(stats.for_marker(Marker::String).values, 5);
+    }
+
+    #[test]
+    fn buffered_dictionary() {
+        let value = flattened();
+        let dictionary: Dictionary = ["id", "a", "b", "Alice"].into_iter().collect();
+
+        let plain = to_vec(&value).unwrap();
+
+        let mut encoded = Vec::new();
+        let mut serializer =
+            Serializer::new(VecWriter::new(&mut encoded), SerializerConfig::default())
+                .with_dictionary(dictionary.clone());
+        value.serialize(&mut serializer).unwrap();
+        assert!(serializer.dictionary().is_some());
+        assert!(encoded.len() < plain.len());
+
+        let mut deserializer =
+            Deserializer::from_reader(SliceReader::new(&encoded)).with_dictionary(dictionary);
+        let decoded = Flattened::deserialize(&mut deserializer).unwrap();
+        assert_eq!(decoded, value);
+    }
+}
+
+mod streamed_seq {
+    use crate::{
+        config::{SerializerConfig, UnsizedSeqRepr},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    struct Unsized<'a, T>(&'a [T]);
+
+    impl<T: Serialize> Serialize for Unsized<'_, T> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.0.iter().filter(|_| true))
+        }
+    }
+
+    fn config() -> SerializerConfig {
+        SerializerConfig::default().with_unsized_seq_repr(UnsizedSeqRepr::Streamed)
+    }
+
+    #[test]
+    fn nested() {
+        let inner: Vec<Vec<u8>> = vec![vec![4], vec![1, 2, 3]];
+        let rows: Vec<Unsized<'_, u8>> = inner.iter().map(|row| Unsized(row)).collect();
+        let encoded = to_vec_with_config(&Unsized(&rows), config()).unwrap();
+
+        // Outer and inner streams each take two extra bytes for start and end:
+        assert_eq!(encoded.len(), 2 + (2 + 1) + (2 + 3));
+
+        let decoded: Vec<Vec<u8>> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, inner);
+
+        let decoded: Value = from_slice(&encoded).unwrap();
+        let expected: Value = from_slice(&to_vec(&inner).unwrap()).unwrap();
+        assert_eq!(decoded, expected);
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(value in Vec::<String>::arbitrary()) {
+            let encoded = to_vec_with_config(&Unsized(&value), config()).unwrap();
+
+            let decoded: Vec<String> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(decoded, value);
+        }
+
+        #[test]
+        fn buffered(value in Vec::<u32>::arbitrary()) {
+            let encoded = to_vec(&Unsized(&value)).unwrap();
+            prop_assert_eq!(&encoded, &to_vec(&value).unwrap());
+        }
+    }
+}
+
+mod unit_repr {
+    use crate::{
+        config::{SerializerConfig, UnitRepr},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    fn config() -> SerializerConfig {
+        SerializerConfig::default().with_unit_repr(UnitRepr::Null)
+    }
+
+    #[test]
+    fn null() {
+        let encoded = to_vec_with_config(&((), UnitStruct), config()).unwrap();
+        assert_eq!(encoded, [0x32, 0x00, 0x00]);
+
+        let decoded: ((), UnitStruct) = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, ((), UnitStruct));
+
+        let value = to_value_with_config(&UnitStruct, config()).unwrap();
+        assert_eq!(value, Value::Null(NullValue));
+        assert_eq!(from_value::<UnitStruct>(value).unwrap(), UnitStruct);
+    }
+
+    #[test]
+    fn unit_still_decodes() {
+        let encoded = to_vec(&UnitStruct).unwrap();
+        assert_eq!(encoded, [0x01]);
+
+        let decoded: UnitStruct = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, UnitStruct);
+    }
+
+    #[test]
+    fn some_unit_becomes_none() {
+        let encoded = to_vec_with_config(&Some(()), config()).unwrap();
+
+        let decoded: Option<()> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, None);
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(value in Vec::<Struct<UnitStruct>>::arbitrary()) {
+            let encoded = to_vec_with_config(&value, config()).unwrap();
+
+            let decoded: Vec<Struct<UnitStruct>> = from_slice(&encoded).unwrap();
+            prop_assert_eq!(decoded, value);
+        }
+    }
+}
+
+mod enum_variants {
+    use lilliput_core::error::ErrorCode;
+
+    use super::*;
+
+    #[test]
+    fn out_of_range_index() {
+        let encoded = to_vec(&7_u32).unwrap();
+        let err = from_slice::<Enum<u8>>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+        assert_eq!(err.pos(), Some(0));
+
+        let encoded = to_vec(&u32::MAX).unwrap();
+        let err = from_slice::<Enum<u8>>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidValue);
+
+        assert!(from_slice::<Enum<u8>>(&[0x07]).is_err());
+    }
+}
+
+mod ignored_any {
+    use serde::de::IgnoredAny;
+
+    use super::*;
+
+    #[test]
+    fn skips_invalid_utf8() {
+        // ("\xff", 7), with the string being skipped without decoding it:
+        let encoded = [0x32, 0x61, 0xff, 0xc7];
+
+        let (_, decoded): (IgnoredAny, u8) = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, 7);
+    }
+
+    #[derive(Eq, PartialEq, Debug, Deserialize)]
+    struct Small {
+        b: u8,
+    }
+
+    #[test]
+    fn checks_depth_and_limits() {
+        use lilliput_core::error::ErrorCode;
+
+        use crate::{config::DeserializerConfig, de::from_slice_with_config};
+
+        // (<100k nested sequences>, 7), which would overflow the stack if skipped unchecked:
+        let mut encoded = vec![0b0011_0001; 100_000];
+        encoded.extend([0x30, 0xc7]);
+        encoded.insert(0, 0x32);
+
+        let err = from_slice::<(IgnoredAny, u8)>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+
+        #[derive(Serialize)]
+        struct Large {
+            a: &'static str,
+            b: u8,
+        }
+
+        let encoded = to_vec(&Large {
+            a: "lilliput",
+            b: 7,
+        })
+        .unwrap();
+        assert_eq!(from_slice::<Small>(&encoded).unwrap(), Small { b: 7 });
+
+        let config = DeserializerConfig::default().with_max_len(Some(4));
+        let err = from_slice_with_config::<Small>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+
+    proptest! {
+        #[test]
+        fn small_struct_from_large_document(a in Value::arbitrary(), b in any::<u8>()) {
+            let document = BTreeMap::from([("a", a), ("b", Value::from(IntValue::from(b)))]);
+
+            let encoded = to_vec(&document).unwrap();
+            let decoded: Small = from_slice(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &Small { b });
+
+            let value: Value = from_slice(&encoded).unwrap();
+            prop_assert_eq!(from_value::<Small>(value.clone()).unwrap(), Small { b });
+            prop_assert_eq!(Small::deserialize(&value).unwrap(), Small { b });
+        }
+
+        #[test]
+        fn skips_exactly_one_value(value in Value::arbitrary(), trailing in any::<u32>()) {
+            let encoded = to_vec(&(&value, trailing)).unwrap();
+
+            let (_, decoded): (IgnoredAny, u32) = from_slice(&encoded).unwrap();
+            prop_assert_eq!(decoded, trailing);
+        }
+    }
+}
+
+mod value_conversion {
+    use crate::{
+        config::{EnumVariantRepr, SerializerConfig, StructRepr},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    fn configs() -> impl Strategy<Value = SerializerConfig> {
+        let struct_repr = prop_oneof![
+            Just(StructRepr::Seq),
+            Just(StructRepr::Map),
+            Just(StructRepr::IndexMap),
+        ];
+        let enum_variant_repr =
+            prop_oneof![Just(EnumVariantRepr::Index), Just(EnumVariantRepr::Name)];
+
+        (struct_repr, enum_variant_repr).prop_map(|(struct_repr, enum_variant_repr)| {
+            SerializerConfig::default()
+                .with_struct_repr(struct_repr)
+                .with_enum_variant_repr(enum_variant_repr)
+        })
+    }
+
+    #[test]
+    fn struct_to_value() {
+        let value = to_value(&Struct { a: 1_u8, b: 2_u8 }).unwrap();
+
+        let expected = Map::from_iter([
+            (
+                Value::String("a".to_owned().into()),
+                Value::Int(1_u8.into()),
+            ),
+            (
+                Value::String("b".to_owned().into()),
+                Value::Int(2_u8.into()),
+            ),
+        ]);
+        assert_eq!(value, Value::Map(MapValue::from(expected)));
+    }
+
+    #[test]
+    fn borrowed_sections() {
+        #[derive(Eq, PartialEq, Debug, Deserialize)]
+        struct Server<'a> {
+            host: &'a str,
+            port: u16,
+        }
+
+        let server = Map::from_iter([
+            (
+                Value::String("host".to_owned().into()),
+                Value::String("localhost".to_owned().into()),
+            ),
+            (
+                Value::String("port".to_owned().into()),
+                Value::Int(8080_u16.into()),
+            ),
+        ]);
+        let config = Value::Map(MapValue::from(Map::from_iter([
+            (
+                Value::String("server".to_owned().into()),
+                Value::Map(MapValue::from(server)),
+            ),
+            (
+                Value::String("tags".to_owned().into()),
+                Value::Bytes(b"tags".to_vec().into()),
+            ),
+        ])));
+
+        let Value::Map(sections) = &config else {
+            unreachable!();
+        };
+        let section = &sections.as_map_ref()[&Value::String("server".to_owned().into())];
+
+        let server = Server::deserialize(section).unwrap();
+        assert_eq!(
+            server,
+            Server {
+                host: "localhost",
+                port: 8080
+            }
+        );
+
+        let tags = &sections.as_map_ref()[&Value::String("tags".to_owned().into())];
+        let tags: &[u8] = serde_bytes::deserialize(tags).unwrap();
+        assert_eq!(tags, b"tags");
+    }
+
+    #[test]
+    fn errors() {
+        let err = from_value::<u8>(Value::Int(300_u16.into())).unwrap_err();
+        assert!(err.to_string().contains("300"), "{err}");
+
+        let err = from_value::<String>(Value::Bool(true.into())).unwrap_err();
+        assert_eq!(err.code(), lilliput_core::error::ErrorCode::InvalidType);
+
+        let map = Map::from_iter([
+            (Value::Int(1_u8.into()), Value::Bool(true.into())),
+            (Value::Int(2_u8.into()), Value::Bool(true.into())),
+        ]);
+        let err = from_value::<Enum<bool>>(Value::Map(MapValue::from(map))).unwrap_err();
+        assert_eq!(err.code(), lilliput_core::error::ErrorCode::InvalidLength);
+    }
+
+    proptest! {
+        #[test]
+        fn matches_decoded(value in Enum::<Struct<Option<i16>>>::arbitrary(), config in configs()) {
+            let encoded = to_vec_with_config(&value, config.clone()).unwrap();
+            let decoded: Value = from_slice(&encoded).unwrap();
+
+            prop_assert_eq!(to_value_with_config(&value, config).unwrap(), decoded);
+        }
+
+        #[test]
+        fn roundtrip(value in Enum::<Struct<Option<i16>>>::arbitrary(), config in configs()) {
+            let converted = to_value_with_config(&value, config).unwrap();
+            prop_assert_eq!(from_value::<Enum<Struct<Option<i16>>>>(converted).unwrap(), value);
+        }
+
+        #[test]
+        fn borrowed_roundtrip(value in Enum::<Struct<Option<i16>>>::arbitrary(), config in configs()) {
+            let converted = to_value_with_config(&value, config).unwrap();
+            prop_assert_eq!(Enum::<Struct<Option<i16>>>::deserialize(&converted).unwrap(), value);
+        }
+
+        #[test]
+        fn value_roundtrip(value in Value::arbitrary()) {
+            prop_assert_eq!(&to_value(&value).unwrap(), &value);
+            prop_assert_eq!(&Value::deserialize(&value).unwrap(), &value);
+            prop_assert_eq!(from_value::<Value>(value.clone()).unwrap(), value);
+        }
+    }
+}
+
+mod lilliput_macro {
+    use super::*;
+
+    #[test]
+    fn scalars() {
+        assert_eq!(lilliput!(null), Value::Null(NullValue));
+        assert_eq!(lilliput!(()), Value::Unit(UnitValue));
+        assert_eq!(lilliput!(true), Value::Bool(true.into()));
+        assert_eq!(lilliput!(-42_i8), Value::Int(IntValue::from(-42_i8)));
+        assert_eq!(lilliput!(1.5_f32), Value::Float(FloatValue::from(1.5_f32)));
+        assert_eq!(
+            lilliput!("lilliput"),
+            Value::String("lilliput".to_owned().into())
+        );
+        assert_eq!(
+            lilliput!(b"\x00\xff"),
+            Value::Bytes(vec![0x00, 0xff].into())
+        );
+
+        let bytes: &[u8] = &[1, 2, 3];
+        assert_eq!(lilliput!(bytes), Value::Bytes(vec![1, 2, 3].into()));
+    }
+
+    #[test]
+    fn widths_are_preserved() {
+        let Value::Int(IntValue::Unsigned(value)) = lilliput!(1_u16) else {
+            panic!("expected unsigned int");
+        };
+        assert_eq!(value, UnsignedIntValue::U16(1));
+    }
+
+    #[test]
+    fn seqs() {
+        assert_eq!(lilliput!([]), Value::Seq(SeqValue::default()));
+
+        let expected = Value::Seq(SeqValue::from(vec![
+            Value::Int(IntValue::from(-1_i32)),
+            Value::Seq(SeqValue::from(vec![Value::Null(NullValue)])),
+            Value::Unit(UnitValue),
+        ]));
+        assert_eq!(lilliput!([-1, [null], ()]), expected);
+        assert_eq!(lilliput!([-1, [null], (),]), expected);
+    }
+
+    #[test]
+    fn maps() {
+        assert_eq!(lilliput!({}), Value::Map(MapValue::default()));
+
+        let key = "computed";
+        let value = lilliput!({
+            "name": "lilliput",
+            key: 1 + 2,
+            42_u8: { null: () },
+            [1_u8, 2_u8]: b"bytes",
+        });
+
+        let expected = Map::from_iter([
+            (
+                lilliput!("name"),
+                Value::String("lilliput".to_owned().into()),
+            ),
+            (lilliput!("computed"), Value::Int(3_i32.into())),
+            (
+                Value::Int(42_u8.into()),
+                Value::Map(MapValue::from(Map::from_iter([(
+                    Value::Null(NullValue),
+                    Value::Unit(UnitValue),
+                )]))),
+            ),
+            (
+                Value::Seq(SeqValue::from(vec![
+                    Value::Int(1_u8.into()),
+                    Value::Int(2_u8.into()),
+                ])),
+                Value::Bytes(b"bytes".to_vec().into()),
+            ),
+        ]);
+        assert_eq!(value, Value::Map(MapValue::from(expected)));
+    }
+
+    #[test]
+    fn serializable_expressions() {
+        let value = lilliput!({ "struct": Struct { a: 1_u8, b: 2_u8 } });
+        let expected = lilliput!({ "struct": { "a": 1_u8, "b": 2_u8 } });
+        assert_eq!(value, expected);
+    }
+}
+
+mod stats {
+    use lilliput_core::marker::Marker;
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig, UnknownFieldsPolicy},
+        ser::to_vec_with_stats,
+    };
+
+    use super::*;
+
+    #[test]
+    fn to_vec() {
+        let value = Struct {
+            a: "Bob".to_owned(),
+            b: "Alice".to_owned(),
+        };
+
+        let (encoded, stats) = to_vec_with_stats(&value, SerializerConfig::default()).unwrap();
+
+        assert_eq!(stats.total_bytes(), encoded.len());
+        assert_eq!(stats.for_marker(Marker::Map).values, 1);
+        assert_eq!(stats.for_marker(Marker::String).values, 4);
+        assert_eq!(stats.for_marker(Marker::String).compact, 4);
+    }
+
+    #[test]
+    fn deserializer() {
+        let value = Struct {
+            a: "Bob".to_owned(),
+            b: "Alice".to_owned(),
+        };
+        let encoded = super::to_vec(&value).unwrap();
+
+        let reader = lilliput_core::io::SliceReader::new(&encoded);
+        let mut deserializer = crate::de::Deserializer::from_reader(reader).with_stats();
+        let decoded = Struct::<String>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(decoded, value);
+
+        // Only the owned strings get allocated, not the borrowed field names:
+        let stats = deserializer.stats().unwrap();
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.allocated_bytes, "Bob".len() + "Alice".len());
+    }
+
+    #[test]
+    fn deserializer_field_names() {
+        let value = Struct { a: 1_u8, b: 2_u8 };
+        let encoded = super::to_vec(&value).unwrap();
+
+        for unknown_fields in [UnknownFieldsPolicy::Error, UnknownFieldsPolicy::Collect] {
+            let config = DeserializerConfig::default().with_unknown_fields(unknown_fields);
+            let reader = lilliput_core::io::StdIoReader::new(&encoded[..]);
+            let mut deserializer = crate::de::Deserializer::new(reader, config).with_stats();
+            let decoded = Struct::<u8>::deserialize(&mut deserializer).unwrap();
+            assert_eq!(decoded, value);
+
+            // Known field names get matched without being allocated:
+            assert_eq!(deserializer.stats().unwrap().allocations, 0);
+        }
+    }
+}
+
+mod int128 {
+    use lilliput_core::error::ErrorCode;
+
+    use super::*;
+
+    #[test]
+    fn in_range() {
+        for value in [0_i128, -1, i64::MIN.into(), u64::MAX.into()] {
+            assert_eq!(roundtrip(&value).unwrap(), value);
+        }
+
+        for value in [0_u128, u64::MAX.into()] {
+            assert_eq!(roundtrip(&value).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn encoded_as_64_bit() {
+        assert_eq!(to_vec(&-42_i128).unwrap(), to_vec(&-42_i64).unwrap());
+        assert_eq!(
+            to_vec(&u128::from(u64::MAX)).unwrap(),
+            to_vec(&u64::MAX).unwrap()
+        );
+    }
+
+    #[test]
+    fn full_range() {
+        for value in [i128::MIN, i128::from(i64::MIN) - 1, i128::MAX] {
+            assert_eq!(roundtrip(&value).unwrap(), value);
+        }
+
+        for value in [u128::from(u64::MAX) + 1, u128::MAX] {
+            assert_eq!(roundtrip(&value).unwrap(), value);
+        }
+
+        assert_eq!(to_vec(&u128::MAX).unwrap().len(), 1 + 16);
+    }
+
+    #[test]
+    fn into_value() {
+        let encoded = to_vec(&i128::MIN).unwrap();
+        let value: Value = from_slice(&encoded).unwrap();
+        assert_eq!(value, Value::Int(IntValue::from(i128::MIN)));
+
+        let value = to_value(&u128::MAX).unwrap();
+        assert_eq!(value, Value::Int(IntValue::from(u128::MAX)));
+        assert_eq!(from_value::<u128>(value).unwrap(), u128::MAX);
+    }
+
+    #[test]
+    fn narrowing_out_of_range() {
+        let encoded = to_vec(&(i128::from(i64::MAX) + 1)).unwrap();
+        let err = from_slice::<i64>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NumberOutOfRange);
+
+        let encoded = to_vec(&u128::MAX).unwrap();
+        let err = from_slice::<i128>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NumberOutOfRange);
+    }
+
+    #[test]
+    fn negative_into_u128() {
+        let encoded = to_vec(&-1_i64).unwrap();
+        let err = from_slice::<u128>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::NumberOutOfRange);
+    }
+}
+
+mod embedded {
+    use lilliput_core::error::ErrorCode;
+
+    use crate::embedded::Embedded;
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+    struct Envelope {
+        route: String,
+        payload: Embedded,
+    }
+
+    #[test]
+    fn envelope_roundtrip() {
+        let payload = Struct { a: 1_u32, b: 2 };
+        let envelope = Envelope {
+            route: "struct".to_owned(),
+            payload: Embedded::encode(&payload).unwrap(),
+        };
+
+        let decoded = roundtrip(&envelope).unwrap();
+        assert_eq!(decoded, envelope);
+        assert_eq!(decoded.payload.decode::<Struct<u32>>().unwrap(), payload);
+    }
+
+    #[test]
+    fn encoded_as_bytes() {
+        let embedded = Embedded::encode("lilliput").unwrap();
+
+        let encoded = to_vec(&embedded).unwrap();
+        let bytes = serde_bytes::Bytes::new(embedded.as_bytes());
+        assert_eq!(encoded, to_vec(&bytes).unwrap());
+
+        let value = to_value(&embedded).unwrap();
+        assert_eq!(
+            value,
+            Value::Bytes(BytesValue::from(embedded.as_bytes().to_vec()))
+        );
+        assert_eq!(from_value::<Embedded>(value).unwrap(), embedded);
+    }
+
+    #[test]
+    fn invalid_payload() {
+        let embedded = Embedded::from_bytes(vec![0x65, b'l']);
+
+        // Only decoding the payload itself fails:
+        let decoded = roundtrip(&embedded).unwrap();
+        let err = decoded.decode::<String>().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+}
+
+mod limits {
+    use lilliput_core::error::ErrorCode;
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig, UnsizedSeqRepr},
+        de::{from_reader_with_config, from_slice_with_config},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    #[test]
+    fn max_len() {
+        let config = DeserializerConfig::default().with_max_len(Some(4));
+
+        let encoded = to_vec("tiny").unwrap();
+        assert_eq!(
+            from_slice_with_config::<String>(&encoded, config.clone()).unwrap(),
+            "tiny"
+        );
+
+        let encoded = to_vec("lilliput").unwrap();
+        let err = from_slice_with_config::<String>(&encoded, config.clone()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        let encoded = to_vec(&serde_bytes::Bytes::new(b"lilliput")).unwrap();
+        let err = from_slice_with_config::<serde_bytes::ByteBuf>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+
+    #[test]
+    fn max_elements() {
+        let config = DeserializerConfig::default().with_max_elements(Some(2));
+
+        let encoded = to_vec(&vec![1_u8, 2]).unwrap();
+        assert_eq!(
+            from_slice_with_config::<Vec<u8>>(&encoded, config.clone()).unwrap(),
+            [1, 2]
+        );
+
+        let encoded = to_vec(&vec![1_u8, 2, 3]).unwrap();
+        let err = from_slice_with_config::<Vec<u8>>(&encoded, config.clone()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        let map: BTreeMap<u8, u8> = (0..3).map(|i| (i, i)).collect();
+        let encoded = to_vec(&map).unwrap();
+        let err = from_slice_with_config::<BTreeMap<u8, u8>>(&encoded, config.clone()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        // Streamed sequences get counted while decoding their elements:
+        let serializer_config =
+            SerializerConfig::default().with_unsized_seq_repr(UnsizedSeqRepr::Streamed);
+        let encoded = to_vec_with_config(&vec![1_u8, 2, 3], serializer_config).unwrap();
+        let err = from_slice_with_config::<Value>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+
+    #[test]
+    fn max_size() {
+        let config = DeserializerConfig::default().with_max_size(Some(16));
+
+        let encoded = to_vec(&vec!["lilliput"; 1]).unwrap();
+        let decoded: Vec<String> = from_slice_with_config(&encoded, config.clone()).unwrap();
+        assert_eq!(decoded, ["lilliput"]);
+
+        let encoded = to_vec(&vec!["lilliput"; 2]).unwrap();
+        let err = from_slice_with_config::<Vec<String>>(&encoded, config.clone()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        // A header claiming a huge length gets rejected before reading its bytes:
+        let encoded = [0b0100_0011, 0xff, 0xff, 0xff, 0x7f];
+        let err = from_slice_with_config::<String>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+
+    #[test]
+    fn unlimited_by_default() {
+        let value = vec!["lilliput".repeat(100); 100];
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Vec<String> =
+            from_slice_with_config(&encoded, DeserializerConfig::default()).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn max_depth() {
+        let config = DeserializerConfig::default().with_max_depth(3);
+
+        let encoded = to_vec(&vec![vec![1_u8]]).unwrap();
+        let decoded: Vec<Vec<u8>> = from_slice_with_config(&encoded, config.clone()).unwrap();
+        assert_eq!(decoded, [[1]]);
+
+        let encoded = to_vec(&vec![vec![vec![1_u8]]]).unwrap();
+        let err = from_slice_with_config::<Value>(&encoded, config.clone()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+
+        let err = from_reader_with_config::<_, Value>(encoded.as_slice(), config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+    }
+}
+
+mod compat {
+    use crate::{
+        compat::{from_read, into_writer, to_vec_named, StructMode},
+        config::{SerializerConfig, StructRepr},
+        ser::{to_vec_with_config, Serializer},
+    };
+
+    use super::*;
+
+    #[test]
+    fn struct_mode() {
+        assert_eq!(StructRepr::from(StructMode::Map), StructRepr::Map);
+        assert_eq!(StructRepr::from(StructMode::Tuple), StructRepr::Seq);
+
+        let config = SerializerConfig::default().with_struct_tuple();
+        assert_eq!(config.struct_repr, StructRepr::Seq);
+        let config = config.with_struct_map();
+        assert_eq!(config.struct_repr, StructRepr::Map);
+    }
+
+    #[test]
+    fn named_and_tuple() {
+        let value = Struct { a: 1_u8, b: 2 };
+
+        let named = to_vec_named(&value).unwrap();
+        let config = SerializerConfig::default().with_struct_repr(StructRepr::Map);
+        assert_eq!(named, to_vec_with_config(&value, config).unwrap());
+
+        let mut tuple = Vec::new();
+        let writer = lilliput_core::io::VecWriter::new(&mut tuple);
+        value
+            .serialize(&mut Serializer::from_writer(writer).with_struct_tuple())
+            .unwrap();
+        assert_eq!(
+            tuple,
+            to_vec_with_config(&value, StructMode::Tuple.into()).unwrap()
+        );
+        assert!(tuple.len() < named.len());
+    }
+
+    #[test]
+    fn reader_and_writer() {
+        let value = Struct { a: 1_u8, b: 2 };
+
+        let mut encoded = Vec::new();
+        into_writer(&value, &mut encoded).unwrap();
+        assert_eq!(encoded, to_vec(&value).unwrap());
+
+        let decoded: Struct<u8> = from_read(encoded.as_slice()).unwrap();
+        assert_eq!(decoded, value);
+    }
+}
+
+mod presets {
+    use crate::{config::SerializerConfig, ser::to_vec_with_config};
+
+    use super::*;
+
+    /// A sequence, serialized with an unknown length.
+    struct Unsized(Vec<u32>);
+
+    impl Serialize for Unsized {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_seq(self.0.iter().filter(|_| true))
+        }
+    }
+
+    fn presets() -> [SerializerConfig; 4] {
+        [
+            SerializerConfig::smallest(),
+            SerializerConfig::fastest(),
+            SerializerConfig::canonical(),
+            SerializerConfig::compatible(),
+        ]
+    }
+
+    #[test]
+    fn sizes() {
+        let value = vec![Struct { a: 1_u64, b: 1_000 }; 3];
+
+        let smallest = to_vec_with_config(&value, SerializerConfig::smallest()).unwrap();
+        let fastest = to_vec_with_config(&value, SerializerConfig::fastest()).unwrap();
+        let compatible = to_vec_with_config(&value, SerializerConfig::compatible()).unwrap();
+
+        assert!(smallest.len() < fastest.len());
+        assert!(smallest.len() < compatible.len());
+    }
+
+    #[test]
+    fn canonical() {
+        let config = SerializerConfig::canonical();
+
+        let sized = to_vec_with_config(&vec![1_u32, 2, 3], config.clone()).unwrap();
+        let unsized_ = to_vec_with_config(&Unsized(vec![1, 2, 3]), config).unwrap();
+        assert_eq!(sized, unsized_);
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(value in Enum::<Struct<u32>>::arbitrary(), seq in any::<Vec<u32>>()) {
+            for config in presets() {
+                let encoded = to_vec_with_config(&value, config.clone()).unwrap();
+                let decoded: Enum<Struct<u32>> = from_slice(&encoded).unwrap();
+                prop_assert_eq!(&decoded, &value);
+
+                let encoded = to_vec_with_config(&Unsized(seq.clone()), config).unwrap();
+                let decoded: Vec<u32> = from_slice(&encoded).unwrap();
+                prop_assert_eq!(&decoded, &seq);
+            }
+        }
+    }
+}
+
+mod front_coded_maps {
+    use lilliput_core::{
+        config::{EncoderConfig, MapKeyCoding},
+        encoder::Encoder,
+        error::ErrorCode,
+        io::{SliceReader, VecWriter},
+    };
+
+    use crate::{
+        config::{DeserializerConfig, UnknownFieldsPolicy},
+        de::{from_slice_with_config, Deserializer},
+    };
+
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let value = to_value(&Struct { a: 1_u8, b: 2 }).unwrap();
+
+        let config = EncoderConfig::default().with_map_keys(MapKeyCoding::FrontCoded);
+        let mut encoded = Vec::new();
+        Encoder::new(VecWriter::new(&mut encoded), config)
+            .encode_value(&value)
+            .unwrap();
+
+        let decoded: Struct<u8> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, Struct { a: 1, b: 2 });
+
+        let decoded: BTreeMap<String, u8> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, BTreeMap::from([("a".into(), 1), ("b".into(), 2)]));
+
+        let decoded: Value = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    fn encoded(value: &impl serde::Serialize) -> Vec<u8> {
+        let value = to_value(value).unwrap();
+
+        let config = EncoderConfig::default().with_map_keys(MapKeyCoding::FrontCoded);
+        let mut encoded = Vec::new();
+        Encoder::new(VecWriter::new(&mut encoded), config)
+            .encode_value(&value)
+            .unwrap();
+        encoded
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let encoded = encoded(&BTreeMap::from([("a", 1_u8), ("b", 2), ("c", 3)]));
+
+        let config = DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Error);
+        let err = from_slice_with_config::<Struct<u8>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnknownField);
+
+        let config =
+            DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Collect);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let decoded = Struct::<u8>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(decoded, Struct { a: 1, b: 2 });
+
+        let mut expected = Map::default();
+        expected.insert(
+            Value::String("Struct".to_owned().into()),
+            to_value(&BTreeMap::from([("c", 3_u8)])).unwrap(),
+        );
+        assert_eq!(deserializer.take_unknown_fields(), expected);
+    }
+
+    #[test]
+    fn max_len() {
+        let long_key = "lilliput".repeat(4);
+        let encoded = encoded(&BTreeMap::from([(long_key.as_str(), 1_u8)]));
+
+        let config = DeserializerConfig::default().with_max_len(Some(16));
+        let err =
+            from_slice_with_config::<BTreeMap<String, u8>>(&encoded, config.clone()).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        let config = config.with_unknown_fields(UnknownFieldsPolicy::Error);
+        let err = from_slice_with_config::<Struct<u8>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+    }
+}
+
+mod columnar_seqs {
+    use lilliput_core::{
+        config::{EncoderConfig, SeqCoding},
+        error::ErrorCode,
+        io::SliceReader,
+    };
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig, UnknownFieldsPolicy, UnsizedSeqRepr},
+        de::{from_slice_with_config, Deserializer},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    fn config() -> SerializerConfig {
+        SerializerConfig::default()
+            .with_encoder(EncoderConfig::default().with_seqs(SeqCoding::Columnar))
+    }
+
+    #[test]
+    fn roundtrip() {
+        let value: Vec<Struct<u32>> = (0..16).map(|a| Struct { a, b: a * 2 }).collect();
+
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+        assert!(encoded.len() < to_vec(&value).unwrap().len());
+
+        let decoded: Vec<Struct<u32>> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+
+        let decoded: Value = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, to_value(&value).unwrap());
+    }
+
+    #[test]
+    fn non_struct_items() {
+        /// A sequence of unknown length.
+        struct Unsized(Vec<u32>);
+
+        impl Serialize for Unsized {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.collect_seq(self.0.iter().filter(|_| true))
+            }
+        }
+
+        // Sequences of anything but structs don't get buffered:
+        let value: Vec<Vec<u32>> = vec![vec![1, 2], vec![3]];
+        assert_eq!(
+            to_vec_with_config(&value, config()).unwrap(),
+            to_vec(&value).unwrap()
+        );
+
+        let value: Vec<BTreeMap<String, u32>> = vec![BTreeMap::from([("a".to_owned(), 1)]); 2];
+        assert_eq!(
+            to_vec_with_config(&value, config()).unwrap(),
+            to_vec(&value).unwrap()
+        );
+
+        let value = Unsized(vec![1, 2, 3]);
+        let config = config().with_unsized_seq_repr(UnsizedSeqRepr::Streamed);
+        let encoded = to_vec_with_config(&value, config.clone()).unwrap();
+        assert_eq!(
+            encoded,
+            to_vec_with_config(&value, config.with_encoder(EncoderConfig::default())).unwrap()
+        );
+        assert_eq!(from_slice::<Vec<u32>>(&encoded).unwrap(), [1, 2, 3]);
+    }
+
+    #[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+    struct Narrow {
+        a: u32,
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let value: Vec<Struct<u32>> = (0..4).map(|a| Struct { a, b: a * 2 }).collect();
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        let config = DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Error);
+        let err = from_slice_with_config::<Vec<Narrow>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnknownField);
+        assert_eq!(err.pos(), Some(0));
+
+        let config =
+            DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Collect);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let decoded = Vec::<Narrow>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(decoded, (0..4).map(|a| Narrow { a }).collect::<Vec<_>>());
+        assert_eq!(deserializer.take_unknown_fields().len(), 1);
+    }
+
+    #[test]
+    fn limits() {
+        let value: Vec<Struct<String>> = (0..4)
+            .map(|i| Struct {
+                a: format!("{i}"),
+                b: "lilliput".repeat(i),
+            })
+            .collect();
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        let config = DeserializerConfig::default().with_max_len(Some(8));
+        let err = from_slice_with_config::<Vec<Struct<String>>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        let config = DeserializerConfig::default().with_max_size(Some(encoded.len()));
+        let decoded = from_slice_with_config::<Vec<Struct<String>>>(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn path_tracking() {
+        let value: Vec<Struct<u32>> = (0..4).map(|a| Struct { a, b: a * 100 }).collect();
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        // Errors within rows get reported at the position of their sequence:
+        let mut deserializer =
+            Deserializer::from_reader(SliceReader::new(&encoded)).with_path_tracking();
+        let err = Vec::<Struct<u8>>::deserialize(&mut deserializer).unwrap_err();
+        assert_eq!(err.path(), Some(".[3].b"));
+        assert_eq!(err.pos(), Some(0));
+    }
+}
+
+mod tabular_seqs {
+    use lilliput_core::{
+        config::{EncoderConfig, SeqCoding},
+        error::ErrorCode,
+        io::SliceReader,
+    };
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig, UnknownFieldsPolicy},
+        de::{from_slice_with_config, Deserializer},
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    fn config() -> SerializerConfig {
+        SerializerConfig::default()
+            .with_encoder(EncoderConfig::default().with_seqs(SeqCoding::Tabular))
+    }
+
+    #[test]
+    fn roundtrip() {
+        let value: Vec<Struct<u32>> = (0..16).map(|a| Struct { a, b: a * 2 }).collect();
+
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+        assert!(encoded.len() < to_vec(&value).unwrap().len());
+
+        let decoded: Vec<Struct<u32>> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+
+        let decoded: Value = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, to_value(&value).unwrap());
+
+        let decoded: Vec<BTreeMap<String, u32>> = from_slice(&encoded).unwrap();
+        assert_eq!(
+            decoded[1],
+            BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 2)])
+        );
+    }
+
+    #[test]
+    fn zero_copy() {
+        // Rows get deserialized straight from the input, so may borrow from it:
+        let value: Vec<Struct<&str>> = vec![Struct { a: "a", b: "b" }, Struct { a: "c", b: "d" }];
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        let decoded: Vec<Struct<&str>> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[derive(Eq, PartialEq, Debug, Serialize, Deserialize)]
+    struct Narrow {
+        a: u32,
+    }
+
+    #[test]
+    fn unknown_fields() {
+        let value: Vec<Struct<u32>> = (0..4).map(|a| Struct { a, b: a * 2 }).collect();
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        let decoded: Vec<Narrow> = from_slice(&encoded).unwrap();
+        assert_eq!(decoded, (0..4).map(|a| Narrow { a }).collect::<Vec<_>>());
+
+        let config = DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Error);
+        let err = from_slice_with_config::<Vec<Narrow>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnknownField);
+
+        let config =
+            DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Collect);
+        let mut deserializer = Deserializer::new(SliceReader::new(&encoded), config);
+        let decoded = Vec::<Narrow>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(decoded, (0..4).map(|a| Narrow { a }).collect::<Vec<_>>());
+        assert_eq!(deserializer.take_unknown_fields().len(), 1);
+    }
+
+    #[test]
+    fn limits() {
+        let value: Vec<Struct<String>> = (0..4)
+            .map(|i| Struct {
+                a: format!("{i}"),
+                b: "lilliput".repeat(i),
+            })
+            .collect();
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        let config = DeserializerConfig::default().with_max_len(Some(8));
+        let err = from_slice_with_config::<Vec<Struct<String>>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::LimitExceeded);
+
+        let config = DeserializerConfig::default().with_max_depth(2);
+        let err = from_slice_with_config::<Vec<Struct<String>>>(&encoded, config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::DepthLimitExceeded);
+
+        let config = DeserializerConfig::default().with_max_size(Some(encoded.len()));
+        let decoded = from_slice_with_config::<Vec<Struct<String>>>(&encoded, config).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn path_tracking() {
+        let value: Vec<Struct<u32>> = (0..4).map(|a| Struct { a, b: a * 100 }).collect();
+        let encoded = to_vec_with_config(&value, config()).unwrap();
+
+        let mut deserializer =
+            Deserializer::from_reader(SliceReader::new(&encoded)).with_path_tracking();
+        let err = Vec::<Struct<u8>>::deserialize(&mut deserializer).unwrap_err();
+        assert_eq!(err.path(), Some(".[3].b"));
+    }
+}
+
+mod dictionary {
+    use lilliput_core::{
+        dictionary::Dictionary,
+        io::{SliceReader, VecWriter},
+    };
+
+    use crate::{
+        config::{EnumVariantRepr, SerializerConfig},
+        de::Deserializer,
+        ser::Serializer,
+    };
+
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let value = vec![
+            Enum::UnitVariant,
+            Enum::StructVariant {
+                a: "a".to_owned(),
+                b: "UnitVariant".to_owned(),
+            },
+        ];
+        let dictionary: Dictionary = ["UnitVariant", "StructVariant", "a", "b"]
+            .into_iter()
+            .collect();
+
+        let config = SerializerConfig::default().with_enum_variant_repr(EnumVariantRepr::Name);
+        let plain = crate::ser::to_vec_with_config(&value, config.clone()).unwrap();
+
+        let mut encoded = Vec::new();
+        let mut serializer = Serializer::new(VecWriter::new(&mut encoded), config)
+            .with_dictionary(dictionary.clone());
+        value.serialize(&mut serializer).unwrap();
+        assert!(encoded.len() < plain.len());
+
+        let mut deserializer =
+            Deserializer::from_reader(SliceReader::new(&encoded)).with_dictionary(dictionary);
+        let decoded = Vec::<Enum<String>>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(decoded, value);
+
+        assert!(from_slice::<Vec<Enum<String>>>(&encoded).is_err());
+    }
+}
+
+#[cfg(feature = "preserve_order")]
+mod preserve_order {
+    use lilliput_core::value::{Map, MapValue, StringValue};
+
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let string = |value: &str| Value::String(StringValue::from(value.to_owned()));
+
+        let value = to_value(&Struct { a: 1_u8, b: 2 }).unwrap();
+        let keys: Vec<_> = value.as_map().unwrap().as_map_ref().keys().collect();
+        assert_eq!(keys, [&string("a"), &string("b")]);
+
+        let map: Map = ["z", "a", "m"]
+            .into_iter()
+            .map(|key| (string(key), Value::from(IntValue::from(0_u8))))
+            .collect();
+        let value = Value::Map(MapValue::from(map));
+
+        let encoded = to_vec(&value).unwrap();
+        let decoded: Value = from_slice(&encoded).unwrap();
+        let keys: Vec<_> = decoded.as_map().unwrap().as_map_ref().keys().collect();
+        assert_eq!(keys, [&string("z"), &string("a"), &string("m")]);
+        assert_eq!(to_vec(&decoded).unwrap(), encoded);
+    }
+}
+
+mod timestamps {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use lilliput_core::{decoder::Decoder, io::SliceReader};
+
+    use crate::{size::max_encoded_size_of, timestamp};
+
     use super::*;
 
+    #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+    struct Job {
+        #[serde(with = "timestamp::system_time")]
+        started_at: SystemTime,
+        #[serde(with = "timestamp::duration")]
+        timeout: Duration,
+    }
+
     #[test]
-    fn borrowed() {
-        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
-        struct Subject<'a> {
-            id: u32,
-            name: &'a str,
-            #[serde(with = "serde_bytes")]
-            data: &'a [u8],
+    fn roundtrip() {
+        for started_at in [
+            UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789),
+            UNIX_EPOCH - Duration::from_millis(1500),
+        ] {
+            let job = Job {
+                started_at,
+                timeout: Duration::new(30, 5),
+            };
+
+            let encoded = to_vec(&job).unwrap();
+            assert_eq!(from_slice::<Job>(&encoded).unwrap(), job);
+            assert!(encoded.len() <= max_encoded_size_of(&job).unwrap());
+
+            // Values keep the parts as sequences:
+            let value = to_value(&job).unwrap();
+            assert_eq!(from_value::<Job>(value).unwrap(), job);
         }
+    }
 
-        let value = Subject {
-            id: 42,
-            name: "Bob",
-            data: &[1, 2, 3, 4],
+    #[test]
+    fn encoded_as_extension() {
+        #[derive(Serialize)]
+        struct Parts {
+            started_at: (i64, u32),
+            timeout: (u64, u32),
+        }
+
+        let job = Job {
+            started_at: UNIX_EPOCH + Duration::new(1, 2),
+            timeout: Duration::new(3, 4),
         };
+        let encoded = to_vec(&job).unwrap();
+
+        let mut decoder = Decoder::from_reader(SliceReader::new(&encoded));
+        assert_eq!(decoder.decode_map_len().unwrap(), 2);
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.decode_timestamp().unwrap().secs(), 1);
+        decoder.skip_value().unwrap();
+        assert_eq!(decoder.decode_duration().unwrap(), Duration::new(3, 4));
+
+        // Parts encoded as plain tuples still get accepted:
+        let parts = Parts {
+            started_at: (1, 2),
+            timeout: (3, 4),
+        };
+        let encoded = to_vec(&parts).unwrap();
+        assert_eq!(from_slice::<Job>(&encoded).unwrap(), job);
 
-        let encoded = to_vec(&value).unwrap();
-        let decoded: Subject = from_slice(&encoded).unwrap();
+        let parts = Parts {
+            started_at: (1, 1_000_000_000),
+            timeout: (3, 4),
+        };
+        let encoded = to_vec(&parts).unwrap();
+        assert!(from_slice::<Job>(&encoded).is_err());
+    }
 
-        assert_eq!(decoded, value);
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono() {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Event(#[serde(with = "timestamp::chrono")] ::chrono::DateTime<::chrono::Utc>);
+
+        let event = Event(::chrono::DateTime::from_timestamp(-2, 500).unwrap());
+        let encoded = to_vec(&event).unwrap();
+        assert_eq!(encoded[0], 0x2D);
+        assert_eq!(from_slice::<Event>(&encoded).unwrap(), event);
     }
 
+    #[cfg(feature = "time")]
     #[test]
-    fn owned() {
-        #[derive(Eq, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
-        struct Subject {
-            id: u32,
-            name: String,
-            #[serde(with = "serde_bytes")]
-            data: Vec<u8>,
+    fn time() {
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Event(#[serde(with = "timestamp::time")] ::time::OffsetDateTime);
+
+        let event =
+            Event(::time::OffsetDateTime::from_unix_timestamp_nanos(-1_500_000_000).unwrap());
+        let encoded = to_vec(&event).unwrap();
+        assert_eq!(encoded[0], 0x2D);
+        assert_eq!(from_slice::<Event>(&encoded).unwrap(), event);
+    }
+}
+
+mod ext_values {
+    use lilliput_core::value::ExtValue;
+
+    use super::*;
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Record {
+        id: u8,
+        ext: ExtValue,
+    }
+
+    #[test]
+    fn roundtrip() {
+        let record = Record {
+            id: 7,
+            ext: ExtValue::new(1000, vec![0xAB, 0xCD]),
+        };
+
+        let encoded = to_vec(&record.ext).unwrap();
+        assert_eq!(encoded[0], 0x2D);
+        assert_eq!(from_slice::<ExtValue>(&encoded).unwrap(), record.ext);
+        assert_eq!(
+            from_slice::<Value>(&encoded).unwrap(),
+            Value::Ext(record.ext.clone())
+        );
+
+        let encoded = to_vec(&record).unwrap();
+        assert_eq!(from_slice::<Record>(&encoded).unwrap(), record);
+
+        let value = to_value(&record).unwrap();
+        assert_eq!(value, from_slice::<Value>(&encoded).unwrap());
+        assert_eq!(from_value::<Record>(value.clone()).unwrap(), record);
+        assert_eq!(
+            from_slice::<Value>(&to_vec(&value).unwrap()).unwrap(),
+            value
+        );
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuids {
+    use crate::size::max_encoded_size_of;
+
+    use super::*;
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct User {
+        #[serde(with = "crate::uuid")]
+        id: ::uuid::Uuid,
+    }
+
+    #[test]
+    fn roundtrip() {
+        let user = User {
+            id: ::uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef),
+        };
+
+        let encoded = to_vec(&user).unwrap();
+        assert_eq!(from_slice::<User>(&encoded).unwrap(), user);
+        assert!(encoded.len() <= max_encoded_size_of(&user).unwrap());
+
+        let value = from_slice::<Value>(&encoded).unwrap();
+        assert_eq!(value.get("id").and_then(Value::as_uuid), Some(user.id));
+
+        let value = to_value(&user).unwrap();
+        assert_eq!(value.get("id").and_then(Value::as_uuid), Some(user.id));
+        assert_eq!(from_value::<User>(value).unwrap(), user);
+    }
+
+    #[test]
+    fn encoded_as_extension() {
+        #[derive(Serialize)]
+        struct Legacy {
+            id: String,
         }
 
-        let value = Subject {
-            id: 42,
+        let id = ::uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef);
+
+        // map header, key, extension header, body length, tag:
+        let encoded = to_vec(&User { id }).unwrap();
+        assert_eq!(encoded[4..7], [0x2D, 0xD3, 0xC2]);
+        assert!(encoded.len() < to_vec(&id.to_string()).unwrap().len());
+
+        // UUIDs encoded as strings still get accepted:
+        let legacy = Legacy { id: id.to_string() };
+        let encoded = to_vec(&legacy).unwrap();
+        assert_eq!(from_slice::<User>(&encoded).unwrap(), User { id });
+    }
+}
+
+#[cfg(any(feature = "bigint", feature = "decimal"))]
+mod big_numbers {
+    use crate::size::max_encoded_size_of;
+
+    use super::*;
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn big_int() {
+        use ::num_bigint::BigInt;
+
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Supply(#[serde(with = "crate::big_int")] BigInt);
+
+        for big_int in ["-1267650600228229401496703205376", "0", "255"] {
+            let supply = Supply(big_int.parse().unwrap());
+
+            let encoded = to_vec(&supply).unwrap();
+            assert_eq!(encoded[0], 0x2D);
+            assert_eq!(from_slice::<Supply>(&encoded).unwrap(), supply);
+            assert!(encoded.len() <= max_encoded_size_of(&supply).unwrap());
+
+            let value = from_slice::<Value>(&encoded).unwrap();
+            assert_eq!(value.as_big_int().as_ref(), Some(&supply.0));
+
+            let value = to_value(&supply).unwrap();
+            assert_eq!(from_value::<Supply>(value).unwrap(), supply);
+        }
+
+        // Plain integers and strings still get accepted:
+        let encoded = to_vec(&u64::MAX).unwrap();
+        assert_eq!(
+            from_slice::<Supply>(&encoded).unwrap(),
+            Supply(u64::MAX.into())
+        );
+        let encoded = to_vec("-42").unwrap();
+        assert_eq!(
+            from_slice::<Supply>(&encoded).unwrap(),
+            Supply((-42).into())
+        );
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn decimal() {
+        use ::rust_decimal::Decimal;
+
+        #[derive(PartialEq, Debug, Serialize, Deserialize)]
+        struct Amount(#[serde(with = "crate::decimal")] Decimal);
+
+        for (mantissa, scale) in [(-12_345, 2), (0, 0), (1, 28)] {
+            let amount = Amount(Decimal::new(mantissa, scale));
+
+            let encoded = to_vec(&amount).unwrap();
+            assert_eq!(encoded[0], 0x2D);
+            assert_eq!(from_slice::<Amount>(&encoded).unwrap(), amount);
+            assert!(encoded.len() <= max_encoded_size_of(&amount).unwrap());
+
+            let value = from_slice::<Value>(&encoded).unwrap();
+            assert_eq!(value.as_decimal(), Some(amount.0));
+
+            let value = to_value(&amount).unwrap();
+            assert_eq!(from_value::<Amount>(value).unwrap(), amount);
+        }
+
+        // Plain integers and strings still get accepted:
+        let encoded = to_vec(&7_u8).unwrap();
+        assert_eq!(
+            from_slice::<Amount>(&encoded).unwrap(),
+            Amount(Decimal::new(7, 0))
+        );
+        let encoded = to_vec("0.5").unwrap();
+        assert_eq!(
+            from_slice::<Amount>(&encoded).unwrap(),
+            Amount(Decimal::new(5, 1))
+        );
+    }
+}
+
+mod spanned {
+    use crate::spanned::Spanned;
+
+    use super::*;
+
+    #[test]
+    fn spans() {
+        let sample = Struct {
+            a: vec!["x".to_owned()],
+            b: vec!["yz".to_owned(), "w".to_owned()],
+        };
+        let encoded = to_vec(&sample).unwrap();
+
+        let spanned: Struct<Spanned<Vec<String>>> = from_slice(&encoded).unwrap();
+        assert_eq!(spanned.a.get_ref(), &sample.a);
+        assert_eq!(spanned.b.get_ref(), &sample.b);
+
+        // Each span covers exactly the value's encoding:
+        for (spanned, value) in [(&spanned.a, &sample.a), (&spanned.b, &sample.b)] {
+            assert_eq!(
+                from_slice::<Vec<String>>(&encoded[spanned.span()]).unwrap(),
+                *value
+            );
+        }
+        assert_eq!(spanned.b.span().end, encoded.len());
+
+        // Spanned values serialize as their inner value:
+        assert_eq!(to_vec(&spanned).unwrap(), encoded);
+
+        // Values other than the top-level one have their offsets included:
+        let encoded = to_vec(&(1_u8, "lilliput")).unwrap();
+        let (_, name): (u8, Spanned<String>) = from_slice(&encoded).unwrap();
+        assert_eq!(name.span(), 2..encoded.len());
+    }
+}
+
+mod path_tracking {
+    use lilliput_core::io::SliceReader;
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig, StructRepr, UnknownFieldsPolicy},
+        de::Deserializer,
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Zip {
+        Code(u32),
+        Text(String),
+    }
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Address<Z> {
+        street: String,
+        zip: Z,
+    }
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct User<Z> {
+        name: String,
+        addresses: BTreeMap<u32, Address<Z>>,
+    }
+
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Users<Z> {
+        users: Vec<User<Z>>,
+    }
+
+    fn users(zip: Zip) -> Users<Zip> {
+        let user = |zip| User {
             name: "Bob".to_owned(),
-            data: vec![1, 2, 3, 4],
+            addresses: [(
+                7,
+                Address {
+                    street: "Main St".to_owned(),
+                    zip,
+                },
+            )]
+            .into_iter()
+            .collect(),
         };
 
-        let encoded = to_vec(&value).unwrap();
-        let decoded: Subject = from_slice(&encoded).unwrap();
+        Users {
+            users: vec![user(Zip::Code(12345)), user(zip)],
+        }
+    }
+
+    fn deserialize(encoded: &[u8], config: DeserializerConfig) -> Result<Users<u32>, Error> {
+        let mut deserializer =
+            Deserializer::new(SliceReader::new(encoded), config).with_path_tracking();
+        Users::deserialize(&mut deserializer)
+    }
+
+    #[test]
+    fn paths() {
+        let encoded = to_vec(&users(Zip::Text("n/a".to_owned()))).unwrap();
+
+        let err = deserialize(&encoded, DeserializerConfig::default()).unwrap_err();
+        assert_eq!(err.path(), Some(".users[1].addresses.7.zip"));
+        assert!(err.pos().is_some());
+        assert!(err
+            .to_string()
+            .contains(", at .users[1].addresses.7.zip (position: "));
+
+        // Without tracking, errors have no path:
+        let err = from_slice::<Users<u32>>(&encoded).unwrap_err();
+        assert_eq!(err.path(), None);
+
+        // Fields keyed by their index get reported by their name:
+        let config = SerializerConfig::default().with_struct_repr(StructRepr::IndexMap);
+        let encoded = to_vec_with_config(&users(Zip::Text("n/a".to_owned())), config).unwrap();
+        let config =
+            DeserializerConfig::default().with_unknown_fields(UnknownFieldsPolicy::Collect);
+        let err = deserialize(&encoded, config).unwrap_err();
+        assert_eq!(err.path(), Some(".users[1].addresses.7.zip"));
+
+        // Tracking doesn't get in the way of successful deserialization:
+        let encoded = to_vec(&users(Zip::Code(54321))).unwrap();
+        let users = deserialize(&encoded, DeserializerConfig::default()).unwrap();
+        assert_eq!(users.users[1].addresses[&7].zip, 54321);
+    }
+}
+
+mod trailing_bytes {
+    use lilliput_core::{error::ErrorCode, io::SliceReader};
+
+    use crate::{
+        config::DeserializerConfig,
+        de::{from_reader_with_config, from_slice_exact, Deserializer},
+    };
 
+    use super::*;
+
+    #[test]
+    fn trailing_bytes() {
+        let mut encoded = to_vec(&Struct { a: 1_u8, b: 2_u8 }).unwrap();
+        let len = encoded.len();
+        encoded.extend_from_slice(&[0xC1, 0xC2]);
+
+        // Trailing bytes are ignored by default:
+        assert!(from_slice::<Struct<u8>>(&encoded).is_ok());
+
+        let err = from_slice_exact::<Struct<u8>>(&encoded).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TrailingBytes);
+        assert_eq!(err.pos(), Some(len));
+        assert!(from_slice_exact::<Struct<u8>>(&encoded[..len]).is_ok());
+
+        let config = DeserializerConfig::default().with_ensure_eof(true);
+        let err = from_reader_with_config::<_, Struct<u8>>(&encoded[..], config).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::TrailingBytes);
+        assert_eq!(err.pos(), Some(len));
+
+        // Deserializing multiple values before checking for the end:
+        let mut deserializer = Deserializer::from_reader(SliceReader::new(&encoded));
+        Struct::<u8>::deserialize(&mut deserializer).unwrap();
+        assert_eq!(u8::deserialize(&mut deserializer).unwrap(), 1);
+        assert_eq!(deserializer.end().unwrap_err().pos(), Some(len + 1));
+        assert_eq!(u8::deserialize(&mut deserializer).unwrap(), 2);
+        deserializer.end().unwrap();
+    }
+}
+
+mod stream {
+    use lilliput_core::{
+        error::ErrorCode,
+        io::{SliceReader, StdIoReader},
+    };
+
+    use crate::de::Deserializer;
+
+    use super::*;
+
+    #[test]
+    fn stream() {
+        let records = [Struct { a: 1_u8, b: 2 }, Struct { a: 3, b: 4 }];
+
+        let mut encoded = Vec::new();
+        for record in &records {
+            encoded.extend(to_vec(record).unwrap());
+        }
+        let len = encoded.len();
+
+        let mut stream =
+            Deserializer::from_reader(SliceReader::new(&encoded)).into_iter::<Struct<u8>>();
+        assert_eq!(stream.next().unwrap().unwrap(), records[0]);
+        assert_eq!(stream.next().unwrap().unwrap(), records[1]);
+        assert_eq!(stream.byte_offset(), len);
+        assert!(stream.next().is_none());
+
+        let stream = Deserializer::from_reader(StdIoReader::new(&encoded[..])).into_iter();
+        let decoded: Vec<Struct<u8>> = stream.collect::<Result<_, _>>().unwrap();
+        assert_eq!(decoded, records);
+
+        // An empty input is an empty stream:
+        let mut stream = Deserializer::from_reader(SliceReader::new(&[])).into_iter::<u8>();
+        assert!(stream.next().is_none());
+
+        // Errors report their position, ending the stream:
+        encoded.extend(to_vec(&"lilliput").unwrap());
+        encoded.extend(to_vec(&records[0]).unwrap());
+
+        let mut stream =
+            Deserializer::from_reader(SliceReader::new(&encoded)).into_iter::<Struct<u8>>();
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_ok());
+        let err = stream.next().unwrap().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::InvalidType);
+        assert!(err.pos().unwrap() >= len);
+        assert!(stream.next().is_none());
+
+        // ... as does a truncated last value:
+        let mut stream = Deserializer::from_reader(SliceReader::new(&encoded[..len - 1]))
+            .into_iter::<Struct<u8>>();
+        assert!(stream.next().unwrap().is_ok());
+        let err = stream.next().unwrap().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+}
+
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+mod compression {
+    use lilliput_core::compression::Codec;
+
+    use crate::{de::from_slice_compressed, ser::to_vec_compressed};
+
+    use super::*;
+
+    fn roundtrip(codec: Codec) {
+        let value: Vec<Struct<u32>> = (0..64).map(|a| Struct { a, b: a % 2 }).collect();
+
+        let compressed = to_vec_compressed(&value, codec).unwrap();
+        assert!(compressed.len() < to_vec(&value).unwrap().len());
+
+        let decompressed: Vec<Struct<u32>> = from_slice_compressed(&compressed).unwrap();
+        assert_eq!(decompressed, value);
+
+        // Uncompressed bytes lack the header:
+        assert!(from_slice_compressed::<Vec<Struct<u32>>>(&to_vec(&value).unwrap()).is_err());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn zstd() {
+        roundtrip(Codec::Zstd);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn lz4() {
+        roundtrip(Codec::Lz4);
+    }
+}
+
+mod preamble {
+    use lilliput_core::{
+        config::{DecoderConfig, EncoderConfig},
+        preamble::PREAMBLE_MAGIC,
+    };
+
+    use crate::{
+        config::{DeserializerConfig, SerializerConfig},
+        de::from_slice_with_config,
+        ser::to_vec_with_config,
+    };
+
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let value = Struct { a: 1_u8, b: 2_u8 };
+
+        let config =
+            SerializerConfig::default().with_encoder(EncoderConfig::default().with_preamble(true));
+        let encoded = to_vec_with_config(&value, config).unwrap();
+        assert_eq!(encoded[..4], PREAMBLE_MAGIC);
+        assert_eq!(encoded[6..], to_vec(&value).unwrap());
+
+        let config = DeserializerConfig::default()
+            .with_decoder(DecoderConfig::default().with_preamble(true));
+        let decoded: Struct<u8> = from_slice_with_config(&encoded, config.clone()).unwrap();
         assert_eq!(decoded, value);
+
+        // Documents without a preamble get rejected:
+        let encoded = to_vec(&value).unwrap();
+        assert!(from_slice_with_config::<Struct<u8>>(&encoded, config).is_err());
+    }
+}
+
+mod duplicate_keys {
+    use lilliput_core::{
+        config::{DecoderConfig, DuplicateKeyPolicy},
+        encoder::Encoder,
+        error::ErrorCode,
+        io::VecWriter,
+    };
+
+    use crate::{config::DeserializerConfig, de::from_slice_with_config};
+
+    use super::*;
+
+    fn from_slice_with_policy(
+        bytes: &[u8],
+        policy: DuplicateKeyPolicy,
+    ) -> Result<BTreeMap<String, u8>, Error> {
+        let decoder = DecoderConfig::default().with_duplicate_keys(policy);
+        let config = DeserializerConfig::default().with_decoder(decoder);
+        from_slice_with_config(bytes, config)
+    }
+
+    #[test]
+    fn policies() {
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(3))
+            .unwrap();
+        for (key, value) in [("a", 1), ("a", 2), ("b", 3)] {
+            encoder.encode_str(key).unwrap();
+            encoder.encode_u8(value).unwrap();
+        }
+
+        let map = from_slice_with_policy(&encoded, DuplicateKeyPolicy::LastWins).unwrap();
+        assert_eq!(
+            map,
+            BTreeMap::from([("a".to_owned(), 2), ("b".to_owned(), 3)])
+        );
+
+        let map = from_slice_with_policy(&encoded, DuplicateKeyPolicy::FirstWins).unwrap();
+        assert_eq!(
+            map,
+            BTreeMap::from([("a".to_owned(), 1), ("b".to_owned(), 3)])
+        );
+
+        let error = from_slice_with_policy(&encoded, DuplicateKeyPolicy::Reject).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::InvalidValue);
+        assert_eq!(error.pos(), Some(4));
+    }
+
+    #[test]
+    fn limits() {
+        let key = "lilliput".repeat(125);
+        let encoded = to_vec(&BTreeMap::from([(key.as_str(), 1_u8)])).unwrap();
+
+        for policy in [DuplicateKeyPolicy::FirstWins, DuplicateKeyPolicy::Reject] {
+            let decoder = DecoderConfig::default().with_duplicate_keys(policy);
+            let config = DeserializerConfig::default()
+                .with_decoder(decoder)
+                .with_max_len(Some(16));
+            let error =
+                from_slice_with_config::<BTreeMap<String, u8>>(&encoded, config).unwrap_err();
+            assert_eq!(error.code(), ErrorCode::LimitExceeded);
+        }
+
+        // Keys' nesting counts towards the depth limit:
+        let key = (0..32).fold(Value::Null(NullValue), |key, _| {
+            Value::Seq(SeqValue::from(vec![key]))
+        });
+        let value = Value::Map(MapValue::from(Map::from_iter([(
+            key,
+            Value::Null(NullValue),
+        )])));
+        let encoded = to_vec(&value).unwrap();
+
+        let decoder = DecoderConfig::default().with_duplicate_keys(DuplicateKeyPolicy::Reject);
+        let config = DeserializerConfig::default()
+            .with_decoder(decoder)
+            .with_max_depth(16);
+        let error = from_slice_with_config::<Value>(&encoded, config).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DepthLimitExceeded);
+    }
+
+    #[test]
+    fn skipped_limits() {
+        // {"a": 1, "a": <100k nested sequences>}, with the latter getting skipped:
+        let mut encoded: Vec<u8> = Vec::new();
+        let mut encoder = Encoder::from_writer(VecWriter::new(&mut encoded));
+        encoder
+            .encode_map_header(&encoder.header_for_map_len(2))
+            .unwrap();
+        encoder.encode_str("a").unwrap();
+        encoder.encode_u8(1).unwrap();
+        encoder.encode_str("a").unwrap();
+        encoded.extend(vec![0b0011_0001; 100_000]);
+        encoded.extend([0x30]);
+
+        let error = from_slice_with_policy(&encoded, DuplicateKeyPolicy::FirstWins).unwrap_err();
+        assert_eq!(error.code(), ErrorCode::DepthLimitExceeded);
+    }
+}
+
+mod verify_roundtrip {
+    use lilliput_core::config::{EncoderConfig, FloatEncoderConfig, PackedFloatValidation};
+
+    use crate::{
+        config::SerializerConfig,
+        roundtrip::{verify_roundtrip, Mismatch},
+    };
+
+    use super::*;
+
+    /// A struct, whose `b` field gets lost in a round-trip.
+    #[derive(PartialEq, Debug, Serialize, Deserialize)]
+    struct Lossy {
+        a: u8,
+        #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+        b: Option<u8>,
+    }
+
+    #[test]
+    fn lossless() {
+        let config = SerializerConfig::default();
+
+        assert!(verify_roundtrip(&Struct { a: 1_u8, b: 2 }, &config).is_ok());
+        assert!(verify_roundtrip(&Lossy { a: 1, b: None }, &config).is_ok());
+    }
+
+    #[test]
+    fn lossy_floats() {
+        let encoder = EncoderConfig {
+            floats: FloatEncoderConfig::default()
+                .with_validation(PackedFloatValidation::default().with_absolute(0.001)),
+            ..EncoderConfig::default()
+        };
+        let config = SerializerConfig::default().with_encoder(encoder);
+
+        let value = vec![Struct { a: 0.5, b: 0.1 }];
+        let mismatch = verify_roundtrip(&value, &config).unwrap_err();
+
+        let Mismatch::Value {
+            path,
+            expected,
+            actual,
+        } = &mismatch
+        else {
+            panic!("unexpected mismatch: {mismatch}");
+        };
+        assert_eq!(path.to_string(), "[0].b");
+        assert_eq!(expected, &Some(Value::Float(FloatValue::F64(0.1))));
+        assert_ne!(actual, expected);
+        assert!(mismatch
+            .to_string()
+            .starts_with("round-tripped value differs at `[0].b`"));
+    }
+
+    #[test]
+    fn missing_entry() {
+        let config = SerializerConfig::default();
+
+        let mismatch = verify_roundtrip(&Lossy { a: 1, b: Some(2) }, &config).unwrap_err();
+
+        let Mismatch::Value {
+            path,
+            expected,
+            actual,
+        } = mismatch
+        else {
+            panic!("unexpected mismatch: {mismatch}");
+        };
+        assert_eq!(path.to_string(), ".b");
+        assert_eq!(expected, Some(Value::Int(IntValue::from(2_u8))));
+        assert_eq!(actual, None);
+    }
+}
+
+#[cfg(feature = "parallel")]
+mod parallel {
+    use lilliput_core::{error::ErrorCode, framing::FrameEncoder, io::VecWriter};
+
+    use crate::de::decode_frames_parallel;
+
+    use super::*;
+
+    fn encode_frames(frames: &[Vec<u8>]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut encoded));
+        for frame in frames {
+            encoder
+                .encode_with(|encoder| encoder.encode_raw_bytes(frame))
+                .unwrap();
+        }
+        encoded
+    }
+
+    #[test]
+    fn preserves_order() {
+        let values: Vec<String> = (0..100).map(|i| i.to_string()).collect();
+        let frames: Vec<Vec<u8>> = values.iter().map(|value| to_vec(value).unwrap()).collect();
+
+        let decoded = decode_frames_parallel::<String>(&encode_frames(&frames)).unwrap();
+        let decoded: Vec<String> = decoded.into_iter().map(Result::unwrap).collect();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn isolates_errors() {
+        let frames = vec![to_vec("a").unwrap(), vec![0x65], to_vec("c").unwrap()];
+
+        let decoded = decode_frames_parallel::<String>(&encode_frames(&frames)).unwrap();
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[0].as_ref().unwrap(), "a");
+        let err = decoded[1].as_ref().unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+        assert_eq!(decoded[2].as_ref().unwrap(), "c");
+    }
+
+    #[test]
+    fn truncated() {
+        let encoded = encode_frames(&[to_vec("a").unwrap()]);
+
+        let err = decode_frames_parallel::<String>(&encoded[..encoded.len() - 1]).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::UnexpectedEndOfFile);
+    }
+}
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use lilliput_core::{
+        framing::{FrameDecoder, FrameEncoder},
+        io::VecWriter,
+    };
+
+    use crate::rpc::{decode_frame, encode_frame, Request, Response, RpcError};
+
+    use super::*;
+
+    #[test]
+    fn request_response() {
+        let params = Struct { a: 1_u32, b: 2 };
+        let request = Request::new(7, "sum", &params).unwrap();
+
+        let decoded = roundtrip(&request).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.params::<Struct<u32>>().unwrap(), params);
+
+        let response = roundtrip(&decoded.respond(&3_u32).unwrap()).unwrap();
+        assert_eq!(response.id, 7);
+        assert_eq!(response.into_result::<u32>().unwrap(), 3);
+    }
+
+    #[test]
+    fn error_response() {
+        let request = Request::new(7, "missing", &()).unwrap();
+        let response = request.respond_err(RpcError::new(-1, "unknown method"));
+
+        let decoded = roundtrip(&response).unwrap();
+        assert_eq!(decoded, response);
+
+        let err = decoded.into_result::<()>().unwrap_err();
+        assert!(err.to_string().contains("unknown method"));
+    }
+
+    #[test]
+    fn framed() {
+        let requests: Vec<Request> = (0..3)
+            .map(|id| Request::new(id, "ping", &id).unwrap())
+            .collect();
+
+        let mut encoded = Vec::new();
+        let mut encoder = FrameEncoder::from_writer(VecWriter::new(&mut encoded));
+        for request in &requests {
+            encode_frame(&mut encoder, request).unwrap();
+        }
+
+        let mut decoder = FrameDecoder::default();
+        decoder.extend_from_slice(&encoded[..encoded.len() - 1]);
+        for request in &requests[..2] {
+            let decoded: Request = decode_frame(&mut decoder).unwrap().unwrap();
+            assert_eq!(&decoded, request);
+        }
+        assert_eq!(decode_frame::<Request>(&mut decoder).unwrap(), None);
+
+        decoder.extend_from_slice(&encoded[encoded.len() - 1..]);
+        let decoded: Request = decode_frame(&mut decoder).unwrap().unwrap();
+        assert_eq!(decoded, requests[2]);
+
+        let response: Option<Response> = decode_frame(&mut decoder).unwrap();
+        assert_eq!(response, None);
     }
 }
 
@@ -389,6 +3030,12 @@ proptest! {
         prop_assert_eq!(&decoded, &value);
     }
 
+    #[test]
+    fn generic_tuple_struct_roundtrip(value in TupleStruct::<bool>::arbitrary()) {
+        let decoded = roundtrip(&value)?;
+        prop_assert_eq!(&decoded, &value);
+    }
+
     #[test]
     fn tuple_variant_roundtrip(value in Enum::<bool>::arbitrary_tuple_variant()) {
         let decoded = roundtrip(&value)?;
@@ -0,0 +1,320 @@
+//! Serialization of points in time and durations as timestamp extension values.
+//!
+//! The modules of this module are meant for use with serde's `with` attribute:
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//!
+//! use lilliput_serde::{de::from_slice, ser::to_vec};
+//!
+//! #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+//! struct Job {
+//!     #[serde(with = "lilliput_serde::timestamp::system_time")]
+//!     started_at: SystemTime,
+//!     #[serde(with = "lilliput_serde::timestamp::duration")]
+//!     timeout: Duration,
+//! }
+//!
+//! let job = Job {
+//!     started_at: SystemTime::now(),
+//!     timeout: Duration::from_secs(30),
+//! };
+//!
+//! let encoded = to_vec(&job).unwrap();
+//! assert_eq!(from_slice::<Job>(&encoded).unwrap(), job);
+//! ```
+//!
+//! Lilliput encodes them as timestamp (or duration) extension values, while
+//! other formats get them as tuples of their seconds and nanoseconds.
+
+use core::{fmt, marker::PhantomData};
+use std::time::Duration;
+
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use lilliput_core::{
+    decoder::Decoder,
+    encoder::Encoder,
+    io::{Read, Write},
+    timestamp::Timestamp,
+};
+
+use crate::{config::SerializerConfig, error::Error, value::ValueSerializer};
+
+/// Name of the newtype struct wrapping the parts of timestamps.
+pub(crate) const TIMESTAMP_NAME: &str = "$lilliput::Timestamp";
+
+/// Name of the newtype struct wrapping the parts of durations.
+pub(crate) const DURATION_NAME: &str = "$lilliput::Duration";
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Serialization of `std::time::SystemTime` as timestamp.
+pub mod system_time {
+    use std::time::SystemTime;
+
+    use serde::{de, ser, Deserializer, Serializer};
+
+    use lilliput_core::timestamp::Timestamp;
+
+    /// Serializes `time` as timestamp.
+    pub fn serialize<S>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let timestamp = Timestamp::try_from(*time).map_err(ser::Error::custom)?;
+
+        super::serialize_timestamp(timestamp, serializer)
+    }
+
+    /// Deserializes a timestamp as `SystemTime`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SystemTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let timestamp = super::deserialize_timestamp(deserializer)?;
+
+        SystemTime::try_from(timestamp).map_err(de::Error::custom)
+    }
+}
+
+/// Serialization of `std::time::Duration` as duration.
+pub mod duration {
+    use std::time::Duration;
+
+    use serde::{Deserializer, Serializer};
+
+    use super::{Parts, DURATION_NAME};
+
+    /// Serializes `duration` as duration.
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let parts = (duration.as_secs(), duration.subsec_nanos());
+
+        serializer.serialize_newtype_struct(DURATION_NAME, &parts)
+    }
+
+    /// Deserializes a duration as `Duration`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (secs, nanos) = deserializer.deserialize_newtype_struct(DURATION_NAME, Parts::new())?;
+
+        Ok(Duration::new(secs, super::check_nanos::<D::Error>(nanos)?))
+    }
+}
+
+/// Serialization of `chrono::DateTime<Utc>` as timestamp.
+#[cfg(feature = "chrono")]
+pub mod chrono {
+    use chrono::{DateTime, Utc};
+    use serde::{de, ser, Deserializer, Serializer};
+
+    use lilliput_core::timestamp::Timestamp;
+
+    /// Serializes `time` as timestamp.
+    ///
+    /// Leap seconds can't be represented by timestamps and produce an error.
+    pub fn serialize<S>(time: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let timestamp = Timestamp::new(time.timestamp(), time.timestamp_subsec_nanos())
+            .ok_or_else(|| ser::Error::custom("leap second out of range"))?;
+
+        super::serialize_timestamp(timestamp, serializer)
+    }
+
+    /// Deserializes a timestamp as `DateTime<Utc>`.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let timestamp = super::deserialize_timestamp(deserializer)?;
+
+        DateTime::from_timestamp(timestamp.secs(), timestamp.nanos())
+            .ok_or_else(|| de::Error::custom("timestamp out of range"))
+    }
+}
+
+/// Serialization of `time::OffsetDateTime` as timestamp.
+#[cfg(feature = "time")]
+pub mod time {
+    use serde::{de, ser, Deserializer, Serializer};
+    use time::OffsetDateTime;
+
+    use lilliput_core::timestamp::Timestamp;
+
+    /// Serializes `time` as timestamp, dropping its offset.
+    pub fn serialize<S>(time: &OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let timestamp = Timestamp::new(time.unix_timestamp(), time.nanosecond())
+            .ok_or_else(|| ser::Error::custom("nanoseconds out of range"))?;
+
+        super::serialize_timestamp(timestamp, serializer)
+    }
+
+    /// Deserializes a timestamp as `OffsetDateTime`, in UTC.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<OffsetDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let timestamp = super::deserialize_timestamp(deserializer)?;
+        let nanos = i128::from(timestamp.secs()) * i128::from(super::NANOS_PER_SEC)
+            + i128::from(timestamp.nanos());
+
+        OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(de::Error::custom)
+    }
+}
+
+/// Returns `true` if `name` is the name of one of the extension's newtype structs.
+pub(crate) fn is_extension(name: &str) -> bool {
+    name == TIMESTAMP_NAME || name == DURATION_NAME
+}
+
+/// Encodes the `parts` (i.e. seconds and nanoseconds) of
+/// the extension's newtype struct named `name`.
+pub(crate) fn encode_extension<W, T>(
+    encoder: &mut Encoder<W>,
+    name: &str,
+    parts: &T,
+) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let parts = parts.serialize(&ValueSerializer::new(SerializerConfig::default()))?;
+
+    let (secs, nanos) = match parts.as_seq().map(|seq| seq.as_slice()) {
+        Some([secs, nanos]) => (secs, nanos.as_u64().and_then(|nanos| nanos.try_into().ok())),
+        _ => return Err(ser::Error::custom("expected seconds and nanoseconds")),
+    };
+
+    let out_of_range = || <Error as ser::Error>::custom(format!("{name} out of range"));
+
+    if name == TIMESTAMP_NAME {
+        let timestamp = secs
+            .as_i64()
+            .zip(nanos)
+            .and_then(|(secs, nanos)| Timestamp::new(secs, nanos))
+            .ok_or_else(out_of_range)?;
+
+        encoder.encode_timestamp(timestamp)
+    } else {
+        let duration = secs
+            .as_u64()
+            .zip(nanos.filter(|nanos| *nanos < NANOS_PER_SEC))
+            .map(|(secs, nanos)| Duration::new(secs, nanos))
+            .ok_or_else(out_of_range)?;
+
+        encoder.encode_duration(duration)
+    }
+}
+
+/// Decodes the extension value of the extension's newtype struct named `name`,
+/// passing its seconds and nanoseconds to `visitor` as a sequence.
+pub(crate) fn decode_extension<'de, R, V>(
+    decoder: &mut Decoder<R>,
+    name: &str,
+    visitor: V,
+) -> Result<V::Value, Error>
+where
+    R: Read<'de>,
+    V: de::Visitor<'de>,
+{
+    if name == TIMESTAMP_NAME {
+        let timestamp = decoder.decode_timestamp()?;
+        let parts = [timestamp.secs(), timestamp.nanos().into()];
+
+        visitor.visit_seq(de::value::SeqDeserializer::<_, Error>::new(
+            parts.into_iter(),
+        ))
+    } else {
+        let duration = decoder.decode_duration()?;
+        let parts = [duration.as_secs(), duration.subsec_nanos().into()];
+
+        visitor.visit_seq(de::value::SeqDeserializer::<_, Error>::new(
+            parts.into_iter(),
+        ))
+    }
+}
+
+// MARK: - Private
+
+fn serialize_timestamp<S>(timestamp: Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let parts = (timestamp.secs(), timestamp.nanos());
+
+    serializer.serialize_newtype_struct(TIMESTAMP_NAME, &parts)
+}
+
+fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let (secs, nanos) = deserializer.deserialize_newtype_struct(TIMESTAMP_NAME, Parts::new())?;
+
+    Ok(Timestamp::new(secs, check_nanos::<D::Error>(nanos)?).expect("nanos should be valid"))
+}
+
+fn check_nanos<E>(nanos: u32) -> Result<u32, E>
+where
+    E: de::Error,
+{
+    if nanos >= NANOS_PER_SEC {
+        return Err(E::invalid_value(
+            de::Unexpected::Unsigned(nanos.into()),
+            &"nanoseconds within a second",
+        ));
+    }
+
+    Ok(nanos)
+}
+
+/// Visitor of the seconds and nanoseconds of timestamps and durations.
+struct Parts<S>(PhantomData<S>);
+
+impl<S> Parts<S> {
+    fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<'de, S> de::Visitor<'de> for Parts<S>
+where
+    S: Deserialize<'de>,
+{
+    type Value = (S, u32);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("seconds and nanoseconds")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let secs = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let nanos = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        Ok((secs, nanos))
+    }
+}
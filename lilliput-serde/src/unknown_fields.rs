@@ -0,0 +1,88 @@
+//! Capturing of unknown struct fields.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::value::{Map, MapValue};
+
+/// Captures any struct fields not matched by the struct's other fields.
+///
+/// Embed it in a struct with `#[serde(flatten)]` to preserve
+/// the unknown fields of third-party documents across round-trips:
+///
+/// ```
+/// use lilliput_serde::{de::from_slice, ser::to_vec, unknown_fields::UnknownFields};
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Known {
+///     id: u32,
+///     #[serde(flatten)]
+///     unknown: UnknownFields,
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct ThirdParty {
+///     id: u32,
+///     name: &'static str,
+/// }
+///
+/// let encoded = to_vec(&ThirdParty { id: 42, name: "Bob" }).unwrap();
+/// let known: Known = from_slice(&encoded).unwrap();
+/// assert_eq!(known.unknown.len(), 1);
+///
+/// let reencoded = to_vec(&known).unwrap();
+/// assert_eq!(reencoded, encoded);
+/// ```
+#[derive(Default, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct UnknownFields(pub MapValue);
+
+impl UnknownFields {
+    /// Returns a reference to the captured fields.
+    pub fn as_map_ref(&self) -> &Map {
+        &self.0 .0
+    }
+
+    /// Returns the captured fields, consuming `self`.
+    pub fn into_map(self) -> Map {
+        self.0 .0
+    }
+
+    /// Returns the number of captured fields.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true`, if no fields were captured, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Serialize for UnknownFields {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for UnknownFields {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        MapValue::deserialize(deserializer).map(Self)
+    }
+}
+
+impl From<Map> for UnknownFields {
+    fn from(value: Map) -> Self {
+        Self(MapValue(value))
+    }
+}
+
+impl From<UnknownFields> for Map {
+    fn from(value: UnknownFields) -> Self {
+        value.0 .0
+    }
+}
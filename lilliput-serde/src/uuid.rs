@@ -0,0 +1,136 @@
+//! Serialization of `uuid::Uuid` as UUID extension values.
+//!
+//! This module is meant for use with serde's `with` attribute:
+//!
+//! ```
+//! use lilliput_serde::{de::from_slice, ser::to_vec};
+//!
+//! #[derive(PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+//! struct User {
+//!     #[serde(with = "lilliput_serde::uuid")]
+//!     id: uuid::Uuid,
+//! }
+//!
+//! let user = User {
+//!     id: uuid::Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef),
+//! };
+//!
+//! let encoded = to_vec(&user).unwrap();
+//! assert_eq!(from_slice::<User>(&encoded).unwrap(), user);
+//! ```
+//!
+//! Lilliput encodes UUIDs as extension values wrapping their 16 bytes
+//! (rather than as 36-character strings), while other formats get them as bytes.
+//!
+//! UUIDs previously encoded as strings still get accepted when deserializing.
+
+use core::fmt;
+
+use serde::{de, ser, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use lilliput_core::{encoder::Encoder, io::Write};
+
+use crate::{config::SerializerConfig, error::Error, value::ValueSerializer};
+
+/// Name of the newtype struct wrapping the bytes of UUIDs.
+pub(crate) const UUID_NAME: &str = "$lilliput::Uuid";
+
+/// Serializes `uuid` as UUID.
+pub fn serialize<S>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    struct Bytes<'a>(&'a [u8]);
+
+    impl Serialize for Bytes<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    serializer.serialize_newtype_struct(UUID_NAME, &Bytes(uuid.as_bytes()))
+}
+
+/// Deserializes a UUID as `Uuid`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Uuid, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(UUID_NAME, UuidVisitor)
+}
+
+/// Encodes the `bytes` of the UUID newtype struct.
+pub(crate) fn encode_uuid<W, T>(encoder: &mut Encoder<W>, bytes: &T) -> Result<(), Error>
+where
+    W: Write,
+    T: ?Sized + Serialize,
+{
+    let bytes = bytes.serialize(&ValueSerializer::new(SerializerConfig::default()))?;
+
+    let uuid = bytes
+        .as_uuid()
+        .ok_or_else(|| <Error as ser::Error>::custom("expected 16 bytes"))?;
+
+    encoder.encode_uuid(&uuid)
+}
+
+// MARK: - Private
+
+/// Visitor of UUIDs, as bytes or (hyphenated) strings.
+struct UuidVisitor;
+
+impl<'de> de::Visitor<'de> for UuidVisitor {
+    type Value = Uuid;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("UUID")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_bytes(self)
+        }
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Uuid::from_slice(value).map_err(|_| E::invalid_length(value.len(), &"16 bytes"))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Uuid::parse_str(value).map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut bytes = [0_u8; 16];
+
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(index, &"16 bytes"))?;
+        }
+
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(17, &"16 bytes"));
+        }
+
+        Ok(Uuid::from_bytes(bytes))
+    }
+}
@@ -0,0 +1,496 @@
+//! Values.
+//!
+//! ```
+//! use lilliput_serde::value::{from_value, to_value, Value};
+//!
+//! let value: Value = to_value(&(42_u8, "lilliput")).unwrap();
+//! let decoded: (u8, String) = from_value(value).unwrap();
+//!
+//! assert_eq!(decoded, (42, "lilliput".to_owned()));
+//! ```
+
+use serde::{de::DeserializeOwned, ser, Deserialize as _, Serialize};
+
+pub use lilliput_core::value::*;
+
+use crate::{
+    config::{EnumVariantRepr, SerializerConfig, StructRepr, UnitRepr},
+    error::{Error, Result},
+};
+
+/// Converts `value` into a `Value`.
+pub fn to_value<T>(value: &T) -> Result<Value>
+where
+    T: ?Sized + Serialize,
+{
+    to_value_with_config(value, SerializerConfig::default())
+}
+
+/// Converts `value` into a `Value`, configured by `config`.
+///
+/// Only the representations of `config` get applied,
+/// as no encoding takes place.
+pub fn to_value_with_config<T>(value: &T, config: SerializerConfig) -> Result<Value>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(&ValueSerializer::new(config))
+}
+
+/// Converts `value` into an instance of `T`.
+///
+/// Borrowed values can be deserialized without being cloned,
+/// via `T::deserialize(&value)`, which allows for `T` borrowing from them.
+pub fn from_value<T>(value: Value) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+/// A serializer for serializing into `Value`s.
+///
+/// Produces the same values as decoding the output of `Serializer`,
+/// when configured equally.
+#[derive(Default, Clone, Debug)]
+pub struct ValueSerializer {
+    config: SerializerConfig,
+}
+
+impl ValueSerializer {
+    /// Creates a serializer, configured by `config`.
+    pub fn new(config: SerializerConfig) -> Self {
+        Self { config }
+    }
+
+    fn variant(&self, variant_index: u32, variant: &'static str) -> Value {
+        match self.config.enum_variant_repr {
+            EnumVariantRepr::Index => Value::Int(IntValue::from(variant_index)),
+            EnumVariantRepr::Name => Value::String(StringValue::from(variant.to_owned())),
+        }
+    }
+}
+
+fn wrap_variant(variant: Option<Value>, value: Value) -> Value {
+    match variant {
+        Some(variant) => Value::Map(MapValue::from(Map::from_iter([(variant, value)]))),
+        None => value,
+    }
+}
+
+impl<'a> ser::Serializer for &'a ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = ValueSeqSerializer<'a>;
+    type SerializeTuple = ValueSeqSerializer<'a>;
+    type SerializeTupleStruct = ValueSeqSerializer<'a>;
+    type SerializeTupleVariant = ValueSeqSerializer<'a>;
+    type SerializeMap = ValueMapSerializer<'a>;
+    type SerializeStruct = ValueStructSerializer<'a>;
+    type SerializeStructVariant = ValueStructSerializer<'a>;
+
+    fn serialize_bool(self, value: bool) -> Result<Value> {
+        Ok(Value::Bool(BoolValue::from(value)))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Value> {
+        Ok(Value::Int(IntValue::from(value)))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Value> {
+        Ok(Value::Float(FloatValue::from(value)))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Value> {
+        Ok(Value::Float(FloatValue::from(value)))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Value> {
+        self.serialize_str(&value.to_string())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Value> {
+        Ok(Value::String(StringValue::from(value.to_owned())))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Value> {
+        Ok(Value::Bytes(BytesValue::from(value.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null(NullValue))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        match self.config.unit_repr {
+            UnitRepr::Unit => Ok(Value::Unit(UnitValue)),
+            UnitRepr::Null => Ok(Value::Null(NullValue)),
+        }
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        Ok(self.variant(variant_index, variant))
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = value.serialize(self)?;
+
+        if name == ExtValue::NEWTYPE_NAME {
+            return ExtValue::deserialize(value).map(Value::Ext);
+        }
+
+        Ok(value)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        let variant = self.variant(variant_index, variant);
+        Ok(wrap_variant(Some(variant), value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(ValueSeqSerializer::new(self, None, len.unwrap_or_default()))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        Ok(ValueSeqSerializer::new(self, None, len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_tuple(len)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        let variant = self.variant(variant_index, variant);
+        Ok(ValueSeqSerializer::new(self, Some(variant), len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(ValueMapSerializer {
+            ser: self,
+            map: Map::default(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        Ok(ValueStructSerializer::new(self, None, len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        let variant = self.variant(variant_index, variant);
+        Ok(ValueStructSerializer::new(self, Some(variant), len))
+    }
+}
+
+/// A serializer for serializing the elements of sequences and tuples into `Value`s.
+pub struct ValueSeqSerializer<'a> {
+    ser: &'a ValueSerializer,
+    variant: Option<Value>,
+    seq: Seq,
+}
+
+impl<'a> ValueSeqSerializer<'a> {
+    fn new(ser: &'a ValueSerializer, variant: Option<Value>, len: usize) -> Self {
+        Self {
+            ser,
+            variant,
+            seq: Seq::with_capacity(len),
+        }
+    }
+
+    fn push<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.seq.push(value.serialize(self.ser)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value> {
+        let value = Value::Seq(SeqValue::from(self.seq));
+        Ok(wrap_variant(self.variant, value))
+    }
+}
+
+impl ser::SerializeSeq for ValueSeqSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for ValueSeqSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for ValueSeqSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for ValueSeqSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+/// A serializer for serializing the entries of maps into `Value`s.
+pub struct ValueMapSerializer<'a> {
+    ser: &'a ValueSerializer,
+    map: Map,
+    key: Option<Value>,
+}
+
+impl ser::SerializeMap for ValueMapSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.key = Some(key.serialize(self.ser)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value should be called after serialize_key");
+        self.map.insert(key, value.serialize(self.ser)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Map(MapValue::from(self.map)))
+    }
+}
+
+/// A serializer for serializing struct fields into `Value`s, as configured by `StructRepr`.
+pub struct ValueStructSerializer<'a> {
+    ser: &'a ValueSerializer,
+    variant: Option<Value>,
+    seq: Seq,
+    map: Map,
+    index: u32,
+}
+
+impl<'a> ValueStructSerializer<'a> {
+    fn new(ser: &'a ValueSerializer, variant: Option<Value>, len: usize) -> Self {
+        let seq = match ser.config.struct_repr {
+            StructRepr::Seq => Seq::with_capacity(len),
+            StructRepr::Map | StructRepr::IndexMap => Seq::new(),
+        };
+
+        Self {
+            ser,
+            variant,
+            seq,
+            map: Map::default(),
+            index: 0,
+        }
+    }
+
+    fn push<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = value.serialize(self.ser)?;
+
+        match self.ser.config.struct_repr {
+            StructRepr::Seq => self.seq.push(value),
+            StructRepr::Map => {
+                let key = Value::String(StringValue::from(key.to_owned()));
+                self.map.insert(key, value);
+            }
+            StructRepr::IndexMap => {
+                let key = Value::Int(IntValue::from(self.index));
+                self.map.insert(key, value);
+            }
+        }
+
+        self.index += 1;
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Value> {
+        let value = match self.ser.config.struct_repr {
+            StructRepr::Seq => Value::Seq(SeqValue::from(self.seq)),
+            StructRepr::Map | StructRepr::IndexMap => Value::Map(MapValue::from(self.map)),
+        };
+
+        Ok(wrap_variant(self.variant, value))
+    }
+}
+
+impl ser::SerializeStruct for ValueStructSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        // Skipped fields retain their index, for other fields to keep theirs:
+        self.index += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for ValueStructSerializer<'_> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(key, value)
+    }
+
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        // Skipped fields retain their index, for other fields to keep theirs:
+        self.index += 1;
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        self.finish()
+    }
+}